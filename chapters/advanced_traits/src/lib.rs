@@ -0,0 +1,489 @@
+// ============================================================================
+// Rust 高度なトレイトサンプル
+// 公式ドキュメント: https://doc.rust-lang.org/book/ch20-02-advanced-traits.html
+// ============================================================================
+//
+// traits_generics の章（関連型、デフォルト型パラメータ、スーパートレイト）の続き。
+//
+// - 関連定数               トレイトや型に結び付いた定数（T::SIDES のように使う）
+// - 完全修飾構文            同じ名前のメソッドを <Type as Trait>::method で呼び分ける
+// - ニュータイプ            孤児ルールを避けて、外部のトレイトを外部の型に実装する
+// - スーパートレイトとdyn   トレイトオブジェクトからスーパートレイトのメソッドを呼ぶ
+
+use lesson_api::{println, text, CheatSheet};
+use std::fmt;
+use std::ops::{Add, Deref};
+
+// ----------------------------------------------------------------------------
+// 関連定数
+// ----------------------------------------------------------------------------
+
+/// 正多角形。辺の数は型ごとに決まるので、値ではなく型に持たせる
+pub trait RegularPolygon {
+    /// 実装する型ごとに必ず決める定数
+    const SIDES: u32;
+    /// 既定値のある定数（実装で上書きできる）
+    const NAME: &'static str = "多角形";
+
+    fn side(&self) -> f64;
+
+    fn perimeter(&self) -> f64 {
+        self.side() * Self::SIDES as f64
+    }
+
+    /// 内角の和（度）。SIDES から計算できる
+    fn interior_angle_sum() -> u32 {
+        (Self::SIDES - 2) * 180
+    }
+}
+
+pub struct Triangle(pub f64);
+pub struct Square(pub f64);
+pub struct Hexagon(pub f64);
+
+impl RegularPolygon for Triangle {
+    const SIDES: u32 = 3;
+    const NAME: &'static str = "正三角形";
+
+    fn side(&self) -> f64 {
+        self.0
+    }
+}
+
+impl RegularPolygon for Square {
+    const SIDES: u32 = 4;
+    const NAME: &'static str = "正方形";
+
+    fn side(&self) -> f64 {
+        self.0
+    }
+}
+
+// NAME は書かないので既定値の "多角形" になる
+impl RegularPolygon for Hexagon {
+    const SIDES: u32 = 6;
+
+    fn side(&self) -> f64 {
+        self.0
+    }
+}
+
+// 関連定数は定数なので、const の初期化にも使える
+const SQUARE_CORNERS: u32 = Square::SIDES;
+
+/// 型パラメータから定数を読む（値が無くても T::SIDES で分かる）
+fn polygon_row<T: RegularPolygon>(shape: &T) -> String {
+    format!(
+        "{} 辺 {} / 内角の和 {}° / 周の長さ {}",
+        text::pad_right(T::NAME, 8),
+        T::SIDES,
+        T::interior_angle_sum(),
+        shape.perimeter()
+    )
+}
+
+/// 関連定数
+pub fn associated_consts() {
+    println!("\n=== 関連定数 ===");
+
+    println!("{}", polygon_row(&Triangle(2.0)));
+    println!("{}", polygon_row(&Square(1.5)));
+    println!("{}", polygon_row(&Hexagon(0.5)));
+    println!(
+        "const SQUARE_CORNERS: u32 = Square::SIDES; → {}",
+        SQUARE_CORNERS
+    );
+
+    // 標準ライブラリの型にも関連定数がある（トレイトではなく型の impl に書かれている）
+    println!(
+        "\nu8::MAX = {}, i32::BITS = {}, f64::EPSILON = {:e}",
+        u8::MAX,
+        i32::BITS,
+        f64::EPSILON
+    );
+    println!("char::MAX = {:?}", char::MAX);
+
+    // 関連定数があるトレイトは dyn にできない（dyn RegularPolygon は E0038）。
+    // 値ごとに違う数を持たせたいなら、メソッド fn sides(&self) -> u32 にする
+    println!("関連定数を持つトレイトは dyn にできない。型ごとに決まる値だけを定数にする");
+}
+
+// ----------------------------------------------------------------------------
+// 完全修飾構文
+// ----------------------------------------------------------------------------
+
+pub trait Pilot {
+    fn fly(&self) -> String;
+}
+
+pub trait Wizard {
+    fn fly(&self) -> String;
+}
+
+pub struct Human;
+
+impl Pilot for Human {
+    fn fly(&self) -> String {
+        String::from("This is your captain speaking.")
+    }
+}
+
+impl Wizard for Human {
+    fn fly(&self) -> String {
+        String::from("Up!")
+    }
+}
+
+impl Human {
+    pub fn fly(&self) -> String {
+        String::from("*waving arms furiously*")
+    }
+}
+
+pub trait Animal {
+    // self を取らない関連関数
+    fn baby_name() -> String;
+}
+
+pub struct Dog;
+
+impl Dog {
+    pub fn baby_name() -> String {
+        String::from("Spot")
+    }
+}
+
+impl Animal for Dog {
+    fn baby_name() -> String {
+        String::from("puppy")
+    }
+}
+
+/// 完全修飾構文
+pub fn fully_qualified_syntax() {
+    println!("\n=== 完全修飾構文 ===");
+
+    // 型に直接あるメソッドが優先される
+    let person = Human;
+    println!("person.fly()          → {}", person.fly());
+    // self を取るメソッドは、トレイト名を付ければ呼び分けられる（self から型が分かる）
+    println!("Pilot::fly(&person)   → {}", Pilot::fly(&person));
+    println!("Wizard::fly(&person)  → {}", Wizard::fly(&person));
+    println!("Human::fly(&person)   → {}", Human::fly(&person));
+
+    // self を取らない関連関数は、トレイト名だけではどの型の実装か分からない
+    println!("\nDog::baby_name()                → {}", Dog::baby_name());
+    // Animal::baby_name() は E0790（cannot call associated function on trait without specifying the type）
+    println!(
+        "<Dog as Animal>::baby_name()    → {}",
+        <Dog as Animal>::baby_name()
+    );
+
+    // 完全修飾構文は <Type as Trait>::function(receiver_if_method, next_arg, ...)
+    println!(
+        "<Human as Wizard>::fly(&person) → {}",
+        <Human as Wizard>::fly(&person)
+    );
+
+    // 標準ライブラリでも、同じ名前のメソッドがあると呼び分けが必要になる
+    let owned = String::from("abc");
+    println!(
+        "\n<str as ToString>::to_string(\"abc\") → {:?}",
+        <str as ToString>::to_string(&owned)
+    );
+    println!(
+        "<String as AsRef<[u8]>>::as_ref(..) → {:?}（AsRef<str> もあるので型を決める）",
+        <String as AsRef<[u8]>>::as_ref(&owned)
+    );
+}
+
+// ----------------------------------------------------------------------------
+// ニュータイプで孤児ルールを避ける
+// ----------------------------------------------------------------------------
+//
+// 孤児ルール: トレイトか型のどちらかが自分のクレートのものでないと impl できない。
+// impl fmt::Display for Vec<String> は、どちらも std のものなので E0117 になる。
+// 自分の型で包めば（ニュータイプ）、その型に対しては何でも実装できる。
+
+/// Vec<String> を包んで Display を実装する
+pub struct Wrapper(pub Vec<String>);
+
+impl fmt::Display for Wrapper {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[{}]", self.0.join(", "))
+    }
+}
+
+/// 包んだ型のメソッドを全部使いたいなら Deref を実装する
+impl Deref for Wrapper {
+    type Target = Vec<String>;
+
+    fn deref(&self) -> &Vec<String> {
+        &self.0
+    }
+}
+
+/// 単位をまちがえないためのニュータイプ（実行時のコストは f64 と同じ）
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Meters(pub f64);
+
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Millimeters(pub f64);
+
+impl Add for Meters {
+    type Output = Meters;
+
+    fn add(self, other: Meters) -> Meters {
+        Meters(self.0 + other.0)
+    }
+}
+
+/// 違う単位どうしの足し算（Add<Rhs> の Rhs を変える）
+impl Add<Millimeters> for Meters {
+    type Output = Meters;
+
+    fn add(self, other: Millimeters) -> Meters {
+        Meters(self.0 + other.0 / 1000.0)
+    }
+}
+
+/// ニュータイプと孤児ルール
+pub fn newtype_orphan_rule() {
+    println!("\n=== ニュータイプと孤児ルール ===");
+
+    let words = Wrapper(vec![String::from("hello"), String::from("world")]);
+    println!("Wrapper の Display: {}", words);
+    // Deref があるので Vec<String> のメソッドがそのまま使える
+    println!(
+        "words.len() = {}, words.first() = {:?}",
+        words.len(),
+        words.first()
+    );
+
+    let total = Meters(1.5) + Meters(0.25) + Millimeters(40.0);
+    println!(
+        "\nMeters(1.5) + Meters(0.25) + Millimeters(40.0) = {:?}",
+        total
+    );
+    // Meters(1.0) + 2.0 は E0308。素の f64 とは混ざらない
+    println!(
+        "size_of::<Meters>() = {} バイト（f64 と同じ）",
+        std::mem::size_of::<Meters>()
+    );
+
+    println!("\nニュータイプの注意:");
+    println!("  包んだ型のメソッドは引き継がれない（Deref で全部見せるか、必要なものだけ書く）");
+    println!("  Deref で見せると単位の取り違えを防ぐ目的が薄れるので、Meters には付けていない");
+}
+
+// ----------------------------------------------------------------------------
+// スーパートレイトとトレイトオブジェクト
+// ----------------------------------------------------------------------------
+
+/// 名前を持つもの
+pub trait Named {
+    fn name(&self) -> String;
+}
+
+/// Named と Display を前提にするトレイト（スーパートレイト）
+///
+/// Shape を実装する型は Named と Display も実装していなければならない。
+/// その代わり、dyn Shape からも name() や to_string() が呼べる。
+pub trait Shape: Named + fmt::Display {
+    fn area(&self) -> f64;
+
+    /// スーパートレイトのメソッドを使う既定の実装
+    fn summary(&self) -> String {
+        format!("{}「{}」面積 {:.2}", self.name(), self, self.area())
+    }
+}
+
+pub struct Circle {
+    pub radius: f64,
+}
+
+pub struct Rect {
+    pub width: f64,
+    pub height: f64,
+}
+
+impl Named for Circle {
+    fn name(&self) -> String {
+        String::from("円")
+    }
+}
+
+impl fmt::Display for Circle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "r = {}", self.radius)
+    }
+}
+
+impl Shape for Circle {
+    fn area(&self) -> f64 {
+        std::f64::consts::PI * self.radius * self.radius
+    }
+}
+
+impl Named for Rect {
+    fn name(&self) -> String {
+        String::from("長方形")
+    }
+}
+
+impl fmt::Display for Rect {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} x {}", self.width, self.height)
+    }
+}
+
+impl Shape for Rect {
+    fn area(&self) -> f64 {
+        self.width * self.height
+    }
+
+    // 既定の実装は上書きできる
+    fn summary(&self) -> String {
+        format!(
+            "{}「{}」面積 {:.2}（正方形: {}）",
+            self.name(),
+            self,
+            self.area(),
+            self.width == self.height
+        )
+    }
+}
+
+/// Named だけを受け取る関数（&dyn Shape から変換して渡す）
+fn greet(named: &dyn Named) -> String {
+    format!("こんにちは、{}", named.name())
+}
+
+/// スーパートレイトとトレイトオブジェクト
+pub fn supertrait_objects() {
+    println!("\n=== スーパートレイトとトレイトオブジェクト ===");
+
+    let shapes: Vec<Box<dyn Shape>> = vec![
+        Box::new(Circle { radius: 1.0 }),
+        Box::new(Rect {
+            width: 2.0,
+            height: 3.0,
+        }),
+        Box::new(Rect {
+            width: 2.0,
+            height: 2.0,
+        }),
+    ];
+
+    // dyn Shape の vtable にはスーパートレイトのメソッドも入っているので、そのまま呼べる
+    for shape in &shapes {
+        println!("name() = {}, to_string() = {}", shape.name(), shape);
+        println!("  {}", shape.summary());
+    }
+
+    // &dyn Shape から &dyn Named への変換（トレイトのアップキャスト、Rust 1.86 から）
+    let first: &dyn Shape = shapes[0].as_ref();
+    let named: &dyn Named = first;
+    println!("\n&dyn Shape → &dyn Named: {}", greet(named));
+    let display: &dyn fmt::Display = first;
+    println!("&dyn Shape → &dyn Display: {}", display);
+
+    let total: f64 = shapes.iter().map(|shape| shape.area()).sum();
+    println!("面積の合計: {:.2}", total);
+    println!(
+        "size_of::<&dyn Shape>() = {} バイト（データと vtable のポインタ）",
+        std::mem::size_of::<&dyn Shape>()
+    );
+    println!(
+        "アップキャストが使えない古い Rust では fn as_named(&self) -> &dyn Named を足していた"
+    );
+}
+
+/// チートシート用のデータ（`cheatsheet advanced_traits` で表示）
+pub const CHEATSHEET: CheatSheet = CheatSheet {
+    syntax: &[
+        (
+            "trait T { const N: u32; }",
+            "関連定数（実装ごとに値を決める）",
+        ),
+        ("const NAME: &str = \"..\";", "既定値のある関連定数"),
+        ("T::N / Self::N", "型パラメータや Self から関連定数を読む"),
+        (
+            "<Type as Trait>::f()",
+            "完全修飾構文（self を取らない関数の呼び分け）",
+        ),
+        (
+            "Trait::method(&value)",
+            "同じ名前のメソッドをトレイト名で呼び分ける",
+        ),
+        (
+            "struct Wrapper(Vec<String>);",
+            "ニュータイプ（孤児ルールを避ける）",
+        ),
+        ("trait Shape: Named + Display", "スーパートレイト"),
+        (
+            "let n: &dyn Named = shape;",
+            "トレイトのアップキャスト（&dyn Shape → &dyn Named）",
+        ),
+    ],
+    methods: &[
+        (
+            "impl Deref for Wrapper",
+            "包んだ型のメソッドを使えるようにする",
+        ),
+        (
+            "impl Add<Millimeters> for Meters",
+            "右辺の型を変えた演算子の実装",
+        ),
+        ("u8::MAX / i32::BITS / f64::EPSILON", "標準の型の関連定数"),
+    ],
+    gotchas: &[
+        "関連定数を持つトレイトは dyn にできない（E0038）",
+        "self を取らない関連関数は Trait::f() では呼べない（E0790）。<Type as Trait>::f() と書く",
+        "外部のトレイトを外部の型に実装できない（E0117）。ニュータイプで包む",
+        "ニュータイプは包んだ型のメソッドを引き継がない",
+    ],
+};
+
+/// すべてのデモを実行
+pub fn run_all() {
+    println!("╔════════════════════════════════════════════════════════════════╗");
+    println!("║          Rust 高度なトレイトサンプル                             ║");
+    println!("╚════════════════════════════════════════════════════════════════╝");
+
+    lesson_api::run_topic("advanced_traits");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn associated_consts_and_fully_qualified_calls() {
+        assert_eq!((Triangle::SIDES, Triangle::NAME), (3, "正三角形"));
+        assert_eq!(Hexagon::NAME, "多角形");
+        assert_eq!(Hexagon::interior_angle_sum(), 720);
+        assert_eq!(Square(1.5).perimeter(), 6.0);
+
+        let person = Human;
+        assert_eq!(<Human as Wizard>::fly(&person), "Up!");
+        assert_eq!(Pilot::fly(&person), "This is your captain speaking.");
+        assert_eq!(<Dog as Animal>::baby_name(), "puppy");
+        assert_eq!(Dog::baby_name(), "Spot");
+    }
+
+    #[test]
+    fn newtypes_and_supertrait_objects() {
+        let words = Wrapper(vec!["a".to_string(), "b".to_string()]);
+        assert_eq!((words.to_string().as_str(), words.len()), ("[a, b]", 2));
+        assert_eq!(Meters(1.0) + Millimeters(500.0), Meters(1.5));
+
+        let shape: Box<dyn Shape> = Box::new(Rect {
+            width: 2.0,
+            height: 2.0,
+        });
+        assert_eq!(shape.summary(), "長方形「2 x 2」面積 4.00（正方形: true）");
+        let named: &dyn Named = shape.as_ref();
+        assert_eq!(greet(named), "こんにちは、長方形");
+    }
+}
@@ -0,0 +1,359 @@
+// ============================================================================
+// Rust 高度な型サンプル
+// 公式ドキュメント: https://doc.rust-lang.org/book/ch20-03-advanced-types.html
+// ============================================================================
+//
+// - 型エイリアス       type Thunk = Box<dyn Fn() + Send>; 長い型に名前を付ける（別の型にはならない）
+// - ニュータイプ        struct UserId(u32); 中身を隠し、取り違えを型で防ぐ（別の型になる）
+// - never 型（!）      値を返さない式の型。continue、panic!、loop の型で、どの型にもなれる
+// - 動的サイズ型（DST） str、[T]、dyn Trait。参照や Box の後ろに置き、?Sized で受け取る
+
+use lesson_api::{println, CheatSheet};
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::fmt::{self, Debug};
+use std::mem::{size_of, size_of_val};
+use std::rc::Rc;
+
+// ----------------------------------------------------------------------------
+// 型エイリアス
+// ----------------------------------------------------------------------------
+
+/// i32 の別名（Kilometers と i32 はまったく同じ型として扱われる）
+pub type Kilometers = i32;
+
+/// 長いクロージャの型に名前を付ける（The Book の Thunk）
+pub type Thunk = Box<dyn Fn() -> String + Send + 'static>;
+
+/// ジェネリックなエイリアスも作れる
+pub type Transform<T> = Box<dyn Fn(T) -> T>;
+
+/// エラー型を固定した Result（std::io::Result<T> と同じ作り）
+pub type ParseResult<T> = Result<T, String>;
+
+fn takes_long_type(jobs: &[Thunk]) -> Vec<String> {
+    jobs.iter().map(|job| job()).collect()
+}
+
+fn returns_long_type(label: &'static str) -> Thunk {
+    Box::new(move || format!("{} を実行", label))
+}
+
+fn parse_pair(text: &str) -> ParseResult<(i32, i32)> {
+    let (a, b) = text
+        .split_once(',')
+        .ok_or_else(|| format!("カンマがありません: {}", text))?;
+    let parse = |s: &str| s.trim().parse::<i32>().map_err(|e| e.to_string());
+    Ok((parse(a)?, parse(b)?))
+}
+
+/// 型エイリアス
+pub fn type_aliases() {
+    println!("\n=== 型エイリアス ===");
+
+    // 別名なので i32 とそのまま足せる（取り違えは防げない）
+    let x: i32 = 5;
+    let y: Kilometers = 5;
+    println!("i32 の 5 + Kilometers の 5 = {}", x + y);
+
+    // Box<dyn Fn() -> String + Send + 'static> を何度も書かずに済む
+    let jobs: Vec<Thunk> = vec![
+        returns_long_type("ビルド"),
+        returns_long_type("テスト"),
+        Box::new(|| String::from("デプロイ（その場で書いたクロージャ）")),
+    ];
+    for line in takes_long_type(&jobs) {
+        println!("Thunk: {}", line);
+    }
+
+    let steps: Vec<Transform<i32>> = vec![Box::new(|n| n + 1), Box::new(|n| n * 10)];
+    let result = steps.iter().fold(4, |acc, step| step(acc));
+    println!("Transform<i32> を順に適用: 4 → +1 → ×10 = {}", result);
+
+    for text in ["3, 4", "3; 4"] {
+        println!("parse_pair({:?}) → {:?}", text, parse_pair(text));
+    }
+    println!("エイリアスは名前を付けるだけ。新しい型が欲しいならニュータイプにする");
+}
+
+// ----------------------------------------------------------------------------
+// ニュータイプで抽象化する
+// ----------------------------------------------------------------------------
+
+/// ユーザーの ID。u32 と混ざらないよう別の型にする
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct UserId(u32);
+
+/// 注文の ID。UserId と同じ u32 でも、型が違うので取り違えるとコンパイルエラー
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OrderId(u32);
+
+/// 名前の一覧。中が HashMap であることは外から見えない（後で Vec や DB に変えられる）
+#[derive(Debug, Default)]
+pub struct People {
+    names: HashMap<UserId, String>,
+    next_id: u32,
+}
+
+impl People {
+    pub fn new() -> People {
+        People::default()
+    }
+
+    /// 名前を登録して、新しい ID を返す（ID は People だけが作れる）
+    pub fn add(&mut self, name: &str) -> UserId {
+        self.next_id += 1;
+        let id = UserId(self.next_id);
+        self.names.insert(id, name.to_string());
+        id
+    }
+
+    pub fn name(&self, id: UserId) -> Option<&str> {
+        self.names.get(&id).map(String::as_str)
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+}
+
+impl fmt::Display for UserId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "user#{}", self.0)
+    }
+}
+
+fn place_order(user: UserId, order: OrderId) -> String {
+    format!("{} が {:?} を注文", user, order)
+}
+
+/// ニュータイプで抽象化する
+pub fn newtype_abstraction() {
+    println!("\n=== ニュータイプで抽象化する ===");
+
+    let mut people = People::new();
+    let alice = people.add("Alice");
+    let bob = people.add("Bob");
+    println!(
+        "{} = {:?}, {} = {:?}",
+        alice,
+        people.name(alice),
+        bob,
+        people.name(bob)
+    );
+    println!("登録数: {}", people.len());
+
+    let order = OrderId(500);
+    println!("{}", place_order(alice, order));
+    // place_order(order, alice) は E0308（引数の順番を取り違えても気づける）
+    // UserId(3) は People の外（ほかのクレート）からは作れない。フィールドが非公開だから
+    println!("place_order(order, alice) はコンパイルエラー。u32 のままだと気づけない");
+
+    println!(
+        "\nsize_of::<UserId>() = {} バイト（u32 と同じ。ニュータイプに実行時のコストは無い）",
+        size_of::<UserId>()
+    );
+    println!("型エイリアス: 同じ型の別名 / ニュータイプ: 中身が同じでも別の型");
+}
+
+// ----------------------------------------------------------------------------
+// never 型（!）
+// ----------------------------------------------------------------------------
+
+/// 戻らない関数の戻り値の型は !（呼んだ後のコードには進まない）
+pub fn fail(message: &str) -> ! {
+    panic!("{}", message)
+}
+
+/// 数として読めた行だけを足す。読めない行は continue で飛ばす
+pub fn sum_numbers(lines: &[&str]) -> i32 {
+    let mut total = 0;
+    for line in lines {
+        // match の腕の型はそろっている必要がある。continue の型は ! なので i32 の腕と並べられる
+        let n: i32 = match line.trim().parse() {
+            Ok(n) => n,
+            Err(_) => continue,
+        };
+        total += n;
+    }
+    total
+}
+
+/// 失敗しない変換（エラー型が Infallible）
+fn always_ok(n: u8) -> Result<u16, Infallible> {
+    Ok(u16::from(n) * 2)
+}
+
+/// never 型（!）
+pub fn never_type() {
+    println!("\n=== never 型（!） ===");
+
+    let lines = ["10", "x", "20", "", "12"];
+    println!("sum_numbers({:?}) = {}", lines, sum_numbers(&lines));
+
+    // panic! も ! なので、どの型の腕にも置ける
+    let level = 2;
+    let name: &str = match level {
+        1 => "初級",
+        2 => "中級",
+        3 => "上級",
+        _ => fail("知らない難易度"),
+    };
+    println!("match の腕に fail(..)（-> !）を置ける: {}", name);
+
+    // unwrap_or_else にも ! を返すクロージャを渡せる
+    let value: i32 = "42".parse().unwrap_or_else(|_| fail("数ではありません"));
+    println!("\"42\".parse().unwrap_or_else(|_| fail(..)) = {}", value);
+
+    // break の無い loop も ! になる（サーバーのメインループなど、終わらない関数の戻り値に使える）
+    //   fn serve() -> ! { loop { handle_request(); } }
+
+    // Infallible は値を作れない型。Err が来ないことが型で分かるので、let Ok(..) だけで取り出せる
+    let Ok(doubled) = always_ok(21);
+    println!(
+        "let Ok(doubled) = always_ok(21); → {}（Result<u16, Infallible>）",
+        doubled
+    );
+
+    println!(
+        "\n! を返すもの: panic!, unreachable!, todo!, continue, break, return, std::process::exit"
+    );
+}
+
+// ----------------------------------------------------------------------------
+// 動的サイズ型と ?Sized
+// ----------------------------------------------------------------------------
+
+/// ジェネリクスは暗黙に T: Sized。?Sized を付けると str や [T] や dyn Trait も受け取れる
+pub fn describe<T: ?Sized + Debug>(value: &T) -> String {
+    format!(
+        "{:?}（値 {} バイト / 参照 {} バイト）",
+        value,
+        size_of_val(value),
+        size_of::<&T>()
+    )
+}
+
+/// 動的サイズ型と ?Sized
+pub fn dynamically_sized_types() {
+    println!("\n=== 動的サイズ型と ?Sized ===");
+
+    // str、[T]、dyn Trait は大きさがコンパイル時に決まらないので、そのままでは変数に置けない。
+    // &str のように参照の後ろに置くと、参照がポインタと長さ（または vtable）を持つ
+    let greeting: &str = "こんにちは";
+    let slice: &[i32] = &[1, 2, 3, 4];
+    let array: &[i32; 4] = &[1, 2, 3, 4];
+    let debug: &dyn Debug = &3.5_f64;
+
+    println!("str       {}", describe(greeting));
+    println!("[i32]     {}", describe(slice));
+    println!("[i32; 4]  {}", describe(array));
+    println!("dyn Debug {}", describe(debug));
+
+    println!("\n参照の大きさ（ポインタ {} バイト）:", size_of::<usize>());
+    for (name, size) in [
+        ("&[i32; 4]", size_of::<&[i32; 4]>()),
+        ("&[i32]", size_of::<&[i32]>()),
+        ("&str", size_of::<&str>()),
+        ("&dyn Debug", size_of::<&dyn Debug>()),
+        ("Box<str>", size_of::<Box<str>>()),
+    ] {
+        println!("  {:<10} {} バイト", name, size);
+    }
+    println!(
+        "&[i32; 4] は長さが型に入っているのでポインタだけ。&[i32] は長さも持つ（ファットポインタ）"
+    );
+
+    // Box や Rc の後ろにも置ける
+    let boxed: Box<str> = "所有する str".into();
+    let shared: Rc<[u8]> = Rc::from(&b"rc"[..]);
+    println!("\nBox<str> = {:?}, Rc<[u8]> = {:?}", boxed, shared);
+
+    // fn describe<T: Debug>(value: &T) と書くと、describe("..") は
+    // E0277（the size for values of type `str` cannot be known）になる
+    println!("?Sized が無いと describe(\"..\") は E0277（str のサイズが分からない）");
+}
+
+/// チートシート用のデータ（`cheatsheet advanced_types` で表示）
+pub const CHEATSHEET: CheatSheet = CheatSheet {
+    syntax: &[
+        ("type Kilometers = i32;", "型エイリアス（同じ型の別名）"),
+        (
+            "type Thunk = Box<dyn Fn() + Send + 'static>;",
+            "長い型に名前を付ける",
+        ),
+        (
+            "type Result<T> = std::result::Result<T, MyError>;",
+            "エラー型を固定した Result",
+        ),
+        ("struct UserId(u32);", "ニュータイプ（別の型として扱う）"),
+        ("fn fail() -> ! { panic!() }", "戻らない関数（never 型）"),
+        ("Err(_) => continue,", "! はどの型の腕にも置ける"),
+        ("fn f<T: ?Sized>(t: &T)", "動的サイズ型も受け取る"),
+    ],
+    methods: &[
+        ("std::mem::size_of::<T>()", "型の大きさ"),
+        (
+            "std::mem::size_of_val(&value)",
+            "値の大きさ（str や [T] にも使える）",
+        ),
+        (
+            "let Ok(x) = r; // r: Result<T, Infallible>",
+            "失敗しない Result から取り出す",
+        ),
+        ("Box<str> / Rc<[T]>", "動的サイズ型を所有する"),
+    ],
+    gotchas: &[
+        "型エイリアスは取り違えを防がない。防ぎたいならニュータイプ",
+        "ジェネリクスの T には暗黙に Sized が付く",
+        "str や [T] はそのまま変数に置けない。&str、Box<str> のように参照やポインタの後ろに置く",
+        "&[T] と &str はポインタと長さの2つ分の大きさになる",
+    ],
+};
+
+/// すべてのデモを実行
+pub fn run_all() {
+    println!("╔════════════════════════════════════════════════════════════════╗");
+    println!("║          Rust 高度な型サンプル                                   ║");
+    println!("╚════════════════════════════════════════════════════════════════╝");
+
+    lesson_api::run_topic("advanced_types");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aliases_newtypes_and_never() {
+        assert_eq!(parse_pair("3, 4"), Ok((3, 4)));
+        assert!(parse_pair("3; 4").is_err());
+        assert_eq!(takes_long_type(&[returns_long_type("a")]), ["a を実行"]);
+
+        let mut people = People::new();
+        let id = people.add("Alice");
+        assert_eq!((people.name(id), people.len()), (Some("Alice"), 1));
+        assert_eq!(id.to_string(), "user#1");
+
+        assert_eq!(sum_numbers(&["1", "x", "2"]), 3);
+        let Ok(doubled) = always_ok(4);
+        assert_eq!(doubled, 8);
+    }
+
+    #[test]
+    #[should_panic(expected = "戻らない")]
+    fn fail_never_returns() {
+        fail("戻らない");
+    }
+
+    #[test]
+    fn unsized_values_through_references() {
+        assert!(describe("ab").starts_with("\"ab\"（値 2 バイト"));
+        assert!(describe(&[1u8, 2, 3][..]).starts_with("[1, 2, 3]（値 3 バイト"));
+        assert_eq!(size_of::<&str>(), 2 * size_of::<usize>());
+    }
+}
@@ -0,0 +1,553 @@
+// ============================================================================
+// Rustアルゴリズムサンプル - ジェネリックなソートとメモ化
+// 公式ドキュメント: https://doc.rust-lang.org/std/primitive.slice.html#method.sort
+// ============================================================================
+//
+// バブルソート、挿入ソート、マージソート、クイックソートを
+// `T: Ord` のスライスに対するジェネリック関数として実装する。
+// トレイト境界・ジェネリクス・スライスの組み合わせの応用例。
+//
+// 後半はメモ化。計算結果を HashMap に覚えておく Memo<K, V> と、
+// RefCell を使って &self のまま書き換えられる RefMemo<K, V> を作る。
+
+use lesson_api::random::Rng;
+use lesson_api::timing::stopwatch;
+use lesson_api::{println, CheatSheet};
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// バブルソート: 隣どうしを比べて入れ替える。O(n²)
+/// 1周して一度も交換がなければ整列済みなので打ち切る
+pub fn bubble_sort<T: Ord>(items: &mut [T]) {
+    let mut end = items.len();
+    while end > 1 {
+        let mut swapped = false;
+        for i in 1..end {
+            if items[i - 1] > items[i] {
+                items.swap(i - 1, i);
+                swapped = true;
+            }
+        }
+        if !swapped {
+            break;
+        }
+        end -= 1; // 最大値は末尾に確定している
+    }
+}
+
+/// 挿入ソート: 整列済みの前半に1つずつ差し込む。O(n²) だがほぼ整列済みなら速い
+pub fn insertion_sort<T: Ord>(items: &mut [T]) {
+    for i in 1..items.len() {
+        let mut j = i;
+        while j > 0 && items[j - 1] > items[j] {
+            items.swap(j - 1, j);
+            j -= 1;
+        }
+    }
+}
+
+/// マージソート: 半分に分けて整列し、併合する。O(n log n)、安定
+/// 併合には一時領域が必要なので、要素のコピーのために Clone も要求する
+pub fn merge_sort<T: Ord + Clone>(items: &mut [T]) {
+    if items.len() <= 1 {
+        return;
+    }
+    let mid = items.len() / 2;
+    merge_sort(&mut items[..mid]);
+    merge_sort(&mut items[mid..]);
+
+    let mut merged = Vec::with_capacity(items.len());
+    let (left, right) = items.split_at(mid);
+    let (mut i, mut j) = (0, 0);
+    while i < left.len() && j < right.len() {
+        // <= にすることで等しい要素の順序を保つ（安定ソート）
+        if left[i] <= right[j] {
+            merged.push(left[i].clone());
+            i += 1;
+        } else {
+            merged.push(right[j].clone());
+            j += 1;
+        }
+    }
+    merged.extend_from_slice(&left[i..]);
+    merged.extend_from_slice(&right[j..]);
+    items.clone_from_slice(&merged);
+}
+
+/// クイックソート: ピボットより小さいものと大きいものに分けて再帰する
+/// 平均 O(n log n)、最悪 O(n²)。追加の領域はほぼ不要
+pub fn quick_sort<T: Ord>(items: &mut [T]) {
+    if items.len() <= 1 {
+        return;
+    }
+    let pivot = partition(items);
+    let (left, right) = items.split_at_mut(pivot);
+    quick_sort(left);
+    quick_sort(&mut right[1..]); // right[0] はピボット（位置が確定済み）
+}
+
+// 中央の要素をピボットにして分割し（Lomuto 方式）、ピボットの最終位置を返す
+fn partition<T: Ord>(items: &mut [T]) -> usize {
+    let last = items.len() - 1;
+    items.swap(items.len() / 2, last);
+    let mut store = 0;
+    for i in 0..last {
+        if items[i] < items[last] {
+            items.swap(i, store);
+            store += 1;
+        }
+    }
+    items.swap(store, last);
+    store
+}
+
+/// i32 で具体化したソート関数
+type SortFn = fn(&mut [i32]);
+
+/// ソートの名前と関数
+const SORTS: &[(&str, SortFn)] = &[
+    ("バブルソート", bubble_sort::<i32>),
+    ("挿入ソート", insertion_sort::<i32>),
+    ("マージソート", merge_sort::<i32>),
+    ("クイックソート", quick_sort::<i32>),
+    ("slice::sort（標準）", <[i32]>::sort),
+    ("slice::sort_unstable（標準）", <[i32]>::sort_unstable),
+];
+
+/// ジェネリックなソート
+pub fn generic_sorting() {
+    println!("\n=== ジェネリックなソート ===");
+
+    let numbers = [38, 27, 43, 3, 9, 82, 10];
+    println!("元の配列: {:?}", numbers);
+    for (name, sort) in SORTS.iter().take(4) {
+        let mut v = numbers;
+        sort(&mut v);
+        println!("  {:<14} {:?}", name, v);
+    }
+
+    // T: Ord を満たせば、文字列でも同じ関数が使える
+    let mut words = vec!["pear", "apple", "fig", "banana"];
+    quick_sort(&mut words);
+    println!("文字列もソートできる: {:?}", words);
+
+    // 自作の型は Ord を derive するとフィールドの順（辞書式）で比較される
+    #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+    struct Version {
+        major: u32,
+        minor: u32,
+        patch: u32,
+    }
+    let v = |major, minor, patch| Version {
+        major,
+        minor,
+        patch,
+    };
+    let mut versions = vec![v(1, 2, 0), v(0, 9, 3), v(1, 0, 10), v(1, 0, 2)];
+    merge_sort(&mut versions);
+    println!("構造体（derive(Ord)）:");
+    for version in &versions {
+        println!("  {}.{}.{}", version.major, version.minor, version.patch);
+    }
+
+    // f64 は NaN があるので Ord を実装していない
+    // quick_sort(&mut [1.5, 0.5]); // エラー！ f64: Ord を満たさない
+    let mut floats = vec![1.5, -0.5, 3.25];
+    floats.sort_by(|a: &f64, b| a.total_cmp(b));
+    println!(
+        "f64 は Ord ではないので sort_by(total_cmp) を使う: {:?}",
+        floats
+    );
+}
+
+/// ソートの速度比較
+pub fn sorting_timing() {
+    println!("\n=== ソートの速度比較 ===");
+
+    // wasm32-unknown-unknown（ブラウザ版）には時計が無く、Instant::now() が panic する
+    if cfg!(target_arch = "wasm32") {
+        println!("ブラウザ版では時間を計測できません。cargo run で試してください");
+        return;
+    }
+
+    let mut rng = Rng::new(42);
+    let random: Vec<i32> = (0..3000).map(|_| rng.below(100_000) as i32).collect();
+    let nearly_sorted: Vec<i32> = {
+        let mut v: Vec<i32> = (0..3000).collect();
+        for i in (0..v.len()).step_by(100) {
+            v.swap(i, (i + 7) % 3000);
+        }
+        v
+    };
+
+    for (label, data) in [("ランダム", &random), ("ほぼ整列済み", &nearly_sorted)] {
+        println!("{}な {}要素:", label, data.len());
+        let mut expected = data.clone();
+        expected.sort();
+        for (name, sort) in SORTS {
+            let mut v = data.clone();
+            let ((), elapsed) = stopwatch(|| sort(&mut v));
+            assert_eq!(v, expected, "{} の結果が正しくありません", name);
+            println!("  {:<28} {:>13}", name, elapsed);
+        }
+    }
+    println!("※ cargo run --release で実行すると差がよりはっきりします");
+}
+
+/// 計算結果を覚えておくキャッシュ（メモ化）
+/// 同じ引数で2回目に呼ばれたら、計算せずに覚えている値を返す
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+}
+
+impl<K: Eq + Hash, V> Memo<K, V> {
+    pub fn new() -> Self {
+        Memo {
+            cache: HashMap::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    /// 覚えていればその値を、無ければ compute で計算して覚えた値を返す（entry API）
+    /// entry が cache を借用している間は compute から self を使えないので、再帰には使えない
+    pub fn get_or_insert_with(&mut self, key: K, compute: impl FnOnce(&K) -> V) -> &V {
+        self.cache.entry(key).or_insert_with_key(compute)
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.cache.get(key)
+    }
+
+    pub fn insert(&mut self, key: K, value: V) {
+        self.cache.insert(key, value);
+    }
+}
+
+impl<K: Eq + Hash, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// &self で使えるメモ化（RefCell による内部可変性）
+/// 計算の間は借用を返しておくので、compute の中から同じキャッシュを再帰的に使える
+pub struct RefMemo<K, V> {
+    cache: RefCell<HashMap<K, V>>,
+}
+
+impl<K: Eq + Hash + Clone, V: Clone> RefMemo<K, V> {
+    pub fn new() -> Self {
+        RefMemo {
+            cache: RefCell::new(HashMap::new()),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.borrow().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.borrow().is_empty()
+    }
+
+    /// 覚えていればその値を、無ければ compute で計算して覚えた値を返す
+    /// borrow() を持ったまま compute を呼ぶと、中の borrow_mut() が panic する（BorrowMutError）
+    pub fn get_or_compute(&self, key: K, compute: impl FnOnce(&K) -> V) -> V {
+        if let Some(value) = self.cache.borrow().get(&key) {
+            return value.clone();
+        }
+        // ここでは借用を持っていない
+        let value = compute(&key);
+        self.cache.borrow_mut().insert(key, value.clone());
+        value
+    }
+}
+
+impl<K: Eq + Hash + Clone, V: Clone> Default for RefMemo<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 素朴な再帰のフィボナッチ数。同じ値を何度も計算するので O(φⁿ)
+pub fn fib_naive(n: u64, calls: &Cell<u64>) -> u64 {
+    calls.set(calls.get() + 1);
+    if n < 2 {
+        n
+    } else {
+        fib_naive(n - 1, calls) + fib_naive(n - 2, calls)
+    }
+}
+
+/// Memo を &mut で渡すフィボナッチ数。O(n)
+/// entry API は再帰の間ずっと借用してしまうので、get と insert に分ける
+pub fn fib_memo(n: u64, memo: &mut Memo<u64, u64>, calls: &Cell<u64>) -> u64 {
+    calls.set(calls.get() + 1);
+    if n < 2 {
+        return n;
+    }
+    if let Some(&value) = memo.get(&n) {
+        return value;
+    }
+    let value = fib_memo(n - 1, memo, calls) + fib_memo(n - 2, memo, calls);
+    memo.insert(n, value);
+    value
+}
+
+/// RefMemo を &self で使うフィボナッチ数。O(n)
+pub fn fib_ref_memo(n: u64, memo: &RefMemo<u64, u64>, calls: &Cell<u64>) -> u64 {
+    calls.set(calls.get() + 1);
+    memo.get_or_compute(n, |&n| {
+        if n < 2 {
+            n
+        } else {
+            fib_ref_memo(n - 1, memo, calls) + fib_ref_memo(n - 2, memo, calls)
+        }
+    })
+}
+
+/// メモ化（Memo<K, V>）
+pub fn memoization() {
+    println!("\n=== メモ化（Memo<K, V>） ===");
+
+    let n = 30;
+    let calls = Cell::new(0);
+    let (naive, naive_time) = stopwatch(|| fib_naive(n, &calls));
+    let naive_calls = calls.replace(0);
+
+    let mut memo = Memo::new();
+    let (memoized, memo_time) = stopwatch(|| fib_memo(n, &mut memo, &calls));
+    let memo_calls = calls.replace(0);
+
+    let ref_memo = RefMemo::new();
+    let (ref_memoized, ref_time) = stopwatch(|| fib_ref_memo(n, &ref_memo, &calls));
+    let ref_calls = calls.replace(0);
+
+    assert!(naive == memoized && memoized == ref_memoized);
+    println!("fib({}) = {}", n, naive);
+    println!("    呼び出し         時間  実装");
+    for (name, count, time) in [
+        ("素朴な再帰", naive_calls, naive_time),
+        ("Memo（&mut で渡す）", memo_calls, memo_time),
+        ("RefMemo（RefCell、&self）", ref_calls, ref_time),
+    ] {
+        println!("  {:>10} {:>12}  {}", count, time, name);
+    }
+
+    // 一度覚えた値は、次の呼び出しでは1回で返る
+    fib_ref_memo(n, &ref_memo, &calls);
+    println!(
+        "もう一度 fib({}) を呼ぶと {} 回で返る（覚えている値: {}個）",
+        n,
+        calls.replace(0),
+        ref_memo.len()
+    );
+    println!(
+        "fib(90) = {}（素朴な再帰では終わらない大きさ）",
+        fib_ref_memo(90, &ref_memo, &calls)
+    );
+
+    // 再帰しない関数なら entry API の get_or_insert_with が使える
+    let mut lengths: Memo<&str, usize> = Memo::new();
+    let mut computed = 0;
+    for word in ["apple", "banana", "apple", "cherry", "banana", "apple"] {
+        lengths.get_or_insert_with(word, |word| {
+            computed += 1;
+            word.chars().count()
+        });
+    }
+    println!(
+        "6回問い合わせて、計算したのは {} 回（覚えている単語: {}個）",
+        computed,
+        lengths.len()
+    );
+}
+
+/// チートシート用のデータ（`cheatsheet algorithms` で表示）
+pub const CHEATSHEET: CheatSheet = CheatSheet {
+    syntax: &[
+        (
+            "fn sort<T: Ord>(items: &mut [T])",
+            "スライスを受け取るジェネリックなソート",
+        ),
+        ("items.swap(i, j)", "2つの要素を入れ替える"),
+        (
+            "let (l, r) = items.split_at_mut(mid);",
+            "可変スライスを2つに分割",
+        ),
+        (
+            "#[derive(PartialEq, Eq, PartialOrd, Ord)]",
+            "フィールド順の辞書式比較",
+        ),
+        (
+            "const SORTS: &[fn(&mut [i32])]",
+            "具体化したジェネリック関数を関数ポインタとして扱う",
+        ),
+    ],
+    methods: &[
+        (
+            "v.sort() / v.sort_unstable()",
+            "標準の安定ソート / 不安定ソート",
+        ),
+        (
+            "v.sort_by(|a, b| a.total_cmp(b))",
+            "比較関数を指定（f64 など）",
+        ),
+        ("v.sort_by_key(|x| x.len())", "キーを取り出して比較"),
+        ("v.is_sorted()", "整列済みか調べる"),
+        ("Instant::now() / start.elapsed()", "経過時間の計測"),
+        (
+            "map.entry(k).or_insert_with_key(f)",
+            "無ければ計算して覚える（メモ化）",
+        ),
+        (
+            "cell.borrow() / cell.borrow_mut()",
+            "&self のキャッシュを読む / 書き換える",
+        ),
+    ],
+    gotchas: &[
+        "f64 は NaN があるため Ord を実装していない",
+        "再帰で同じスライスを2回可変借用することはできない。split_at_mut で分ける",
+        "デバッグビルドでの計測はあてにならない。--release で比べる",
+        "entry で借用したまま再帰すると借用エラー。get と insert に分ける",
+        "RefCell の borrow() を持ったまま再帰すると borrow_mut() が panic する",
+    ],
+};
+
+/// すべてのデモを実行
+pub fn run_all() {
+    println!("╔════════════════════════════════════════════════════════════════╗");
+    println!("║          Rustアルゴリズムサンプル（ソートとメモ化）              ║");
+    println!("╚════════════════════════════════════════════════════════════════╝");
+
+    lesson_api::run_topic("algorithms");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check_all(input: &[i32]) {
+        let mut expected = input.to_vec();
+        expected.sort();
+        for (name, sort) in SORTS {
+            let mut v = input.to_vec();
+            sort(&mut v);
+            assert_eq!(v, expected, "{} failed for {:?}", name, input);
+        }
+    }
+
+    #[test]
+    fn sorts_edge_cases() {
+        check_all(&[]);
+        check_all(&[1]);
+        check_all(&[2, 1]);
+        check_all(&[1, 2, 3, 4, 5]);
+        check_all(&[5, 4, 3, 2, 1]);
+        check_all(&[3, 3, 3, 3]);
+        check_all(&[i32::MAX, i32::MIN, 0, -1, 1]);
+    }
+
+    #[test]
+    fn memoized_fibonacci_matches_naive() {
+        let calls = Cell::new(0);
+        let mut memo = Memo::new();
+        let ref_memo = RefMemo::new();
+        for n in [0, 1, 2, 10, 20] {
+            let expected = fib_naive(n, &calls);
+            assert_eq!(fib_memo(n, &mut memo, &calls), expected);
+            assert_eq!(fib_ref_memo(n, &ref_memo, &calls), expected);
+        }
+        assert_eq!(
+            fib_ref_memo(90, &ref_memo, &calls),
+            2_880_067_194_370_816_120
+        );
+
+        // 覚えている値は計算し直さない
+        calls.set(0);
+        fib_memo(20, &mut memo, &calls);
+        assert_eq!(calls.get(), 1);
+
+        let mut squares = Memo::new();
+        let mut computed = 0;
+        for n in [3, 4, 3, 3] {
+            squares.get_or_insert_with(n, |n| {
+                computed += 1;
+                n * n
+            });
+        }
+        assert_eq!(
+            (computed, squares.len(), squares.get(&4)),
+            (2, 2, Some(&16))
+        );
+    }
+
+    #[test]
+    fn sorts_match_std_on_random_input() {
+        let mut rng = Rng::new(7);
+        for len in [10, 31, 100, 257] {
+            let data: Vec<i32> = (0..len).map(|_| rng.below(50) as i32 - 25).collect();
+            check_all(&data);
+        }
+    }
+
+    #[test]
+    fn sorts_non_copy_types() {
+        let words = ["delta", "alpha", "charlie", "bravo", "alpha"];
+        let mut expected: Vec<String> = words.iter().map(|w| w.to_string()).collect();
+        expected.sort();
+
+        let mut v: Vec<String> = words.iter().map(|w| w.to_string()).collect();
+        bubble_sort(&mut v);
+        assert_eq!(v, expected);
+        let mut v: Vec<String> = words.iter().map(|w| w.to_string()).collect();
+        insertion_sort(&mut v);
+        assert_eq!(v, expected);
+        let mut v: Vec<String> = words.iter().map(|w| w.to_string()).collect();
+        merge_sort(&mut v);
+        assert_eq!(v, expected);
+        let mut v: Vec<String> = words.iter().map(|w| w.to_string()).collect();
+        quick_sort(&mut v);
+        assert_eq!(v, expected);
+    }
+
+    #[test]
+    fn merge_sort_is_stable() {
+        // (キー, 元の位置) をキーだけで比較する型
+        #[derive(Debug, Clone)]
+        struct Item(u8, usize);
+        impl PartialEq for Item {
+            fn eq(&self, other: &Self) -> bool {
+                self.0 == other.0
+            }
+        }
+        impl Eq for Item {}
+        impl PartialOrd for Item {
+            fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+        impl Ord for Item {
+            fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+                self.0.cmp(&other.0)
+            }
+        }
+
+        let mut items: Vec<Item> = [2, 1, 2, 1, 0, 2]
+            .iter()
+            .enumerate()
+            .map(|(i, &k)| Item(k, i))
+            .collect();
+        merge_sort(&mut items);
+        let order: Vec<usize> = items.iter().map(|item| item.1).collect();
+        assert_eq!(order, vec![4, 1, 3, 0, 2, 5]);
+    }
+}
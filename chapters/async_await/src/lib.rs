@@ -0,0 +1,728 @@
+// ============================================================================
+// Rust 非同期プログラミング（async/await）サンプル
+// 公式ドキュメント: https://doc.rust-lang.org/book/ch17-00-async-await.html
+// ============================================================================
+//
+// async fn や async ブロックは Future を作るだけで、だれかが poll するまで動かない。
+// 実際のプログラムでは tokio などのランタイムが poll するが、この章では外部クレートを
+// 使わずに、小さなランタイム（block_on、sleep、join、race、channel）を自作する。
+//
+// - async fn        呼ぶと Future が返るだけ。本体は poll されたときに動く
+// - impl Future     poll で Ready か Pending を返す。Pending なら Waker で起こしてもらう
+// - block_on        Future が終わるまで poll し続ける（実行器）
+// - sleep(ms)       仮想の時計で ms ミリ秒待つ
+// - join(a, b)      2つを同時に進め、両方の結果を待つ
+// - race(a, b)      先に終わった方の結果を返し、もう一方は drop（キャンセル）する
+// - timeout(ms, f)  race(f, sleep(ms)) で時間切れを作る
+//
+// 時計は本物ではなく、待つものがなくなると次のタイマーまで一気に進む仮想の時計。
+// 何ミリ秒待っても実行は一瞬で終わり、出力の時刻は毎回同じになる（ブラウザ版でも動く）。
+
+use lesson_api::{println, CheatSheet};
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::fmt;
+use std::future::{poll_fn, Future};
+use std::mem;
+use std::pin::{pin, Pin};
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+
+// ----------------------------------------------------------------------------
+// 自作のミニランタイム
+// ----------------------------------------------------------------------------
+
+thread_local! {
+    // 仮想の時計（ミリ秒）と、起こす時刻を待っている Waker
+    static NOW: Cell<u64> = const { Cell::new(0) };
+    static TIMERS: RefCell<Vec<(u64, Waker)>> = const { RefCell::new(Vec::new()) };
+}
+
+/// block_on を始めてからの仮想の時刻（ミリ秒）
+pub fn now() -> u64 {
+    NOW.with(Cell::get)
+}
+
+// wake されたら旗を立てるだけの Waker（block_on が旗を見て poll し直す）
+struct WakeFlag(AtomicBool);
+
+impl Wake for WakeFlag {
+    fn wake(self: Arc<Self>) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+}
+
+/// future が終わるまで、このスレッドで poll し続ける
+///
+/// 起こされていないのに poll しても無駄なので、Waker が呼ばれたときだけ poll する。
+/// だれも起こしてくれないときは、仮想の時計を次のタイマーまで進める。
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let flag = Arc::new(WakeFlag(AtomicBool::new(true)));
+    let waker = Waker::from(Arc::clone(&flag));
+    let mut cx = Context::from_waker(&waker);
+    NOW.with(|now| now.set(0));
+    TIMERS.with(|timers| timers.borrow_mut().clear());
+
+    loop {
+        if flag.0.swap(false, Ordering::SeqCst) {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                TIMERS.with(|timers| timers.borrow_mut().clear());
+                return output;
+            }
+            continue;
+        }
+        // キャンセルされた sleep のタイマーも残っているが、起こしても poll が1回増えるだけ
+        let next = TIMERS.with(|timers| timers.borrow().iter().map(|(at, _)| *at).min());
+        let Some(at) = next else {
+            panic!("deadlock: the future is pending and nothing can wake it");
+        };
+        NOW.with(|now| now.set(at));
+        let due: Vec<Waker> = TIMERS.with(|timers| {
+            let mut timers = timers.borrow_mut();
+            let (due, rest) = timers.drain(..).partition(|(when, _)| *when <= at);
+            *timers = rest;
+            due.into_iter().map(|(_, waker)| waker).collect()
+        });
+        due.into_iter().for_each(Waker::wake);
+    }
+}
+
+/// sleep が返す Future
+pub struct Sleep {
+    deadline: u64,
+}
+
+/// 仮想の時計で ms ミリ秒待つ（await した時点ではなく、作った時点から数える）
+pub fn sleep(ms: u64) -> Sleep {
+    Sleep {
+        deadline: now() + ms,
+    }
+}
+
+impl Future for Sleep {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if now() >= self.deadline {
+            return Poll::Ready(());
+        }
+        // Pending を返すときは、あとで起こしてもらえるよう Waker を預ける
+        let entry = (self.deadline, cx.waker().clone());
+        TIMERS.with(|timers| timers.borrow_mut().push(entry));
+        Poll::Pending
+    }
+}
+
+/// 2つの Future を同時に進めて、両方の結果を返す
+pub async fn join<A: Future, B: Future>(a: A, b: B) -> (A::Output, B::Output) {
+    let (mut a, mut b) = (pin!(a), pin!(b));
+    let (mut out_a, mut out_b) = (None, None);
+    poll_fn(|cx| {
+        if out_a.is_none() {
+            if let Poll::Ready(value) = a.as_mut().poll(cx) {
+                out_a = Some(value);
+            }
+        }
+        if out_b.is_none() {
+            if let Poll::Ready(value) = b.as_mut().poll(cx) {
+                out_b = Some(value);
+            }
+        }
+        match (out_a.take(), out_b.take()) {
+            (Some(a), Some(b)) => Poll::Ready((a, b)),
+            (a, b) => {
+                (out_a, out_b) = (a, b);
+                Poll::Pending
+            }
+        }
+    })
+    .await
+}
+
+/// race の結果（どちらが先に終わったか）
+#[derive(Debug, PartialEq, Eq)]
+pub enum Either<A, B> {
+    Left(A),
+    Right(B),
+}
+
+/// 先に終わった方の結果を返す。負けた方は poll されずに drop される
+///
+/// 両方が同時に終わっていたら a を優先する（tokio::select! の biased; と同じ）。
+pub async fn race<A: Future, B: Future>(a: A, b: B) -> Either<A::Output, B::Output> {
+    let (mut a, mut b) = (pin!(a), pin!(b));
+    poll_fn(|cx| {
+        if let Poll::Ready(value) = a.as_mut().poll(cx) {
+            return Poll::Ready(Either::Left(value));
+        }
+        if let Poll::Ready(value) = b.as_mut().poll(cx) {
+            return Poll::Ready(Either::Right(value));
+        }
+        Poll::Pending
+    })
+    .await
+}
+
+/// timeout で時間切れになった
+#[derive(Debug, PartialEq, Eq)]
+pub struct Elapsed(pub u64);
+
+impl fmt::Display for Elapsed {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ms で時間切れ", self.0)
+    }
+}
+
+/// ms ミリ秒以内に終わらなければ future をキャンセルして Err
+pub async fn timeout<F: Future>(ms: u64, future: F) -> Result<F::Output, Elapsed> {
+    match race(future, sleep(ms)).await {
+        Either::Left(value) => Ok(value),
+        Either::Right(()) => Err(Elapsed(ms)),
+    }
+}
+
+// ----------------------------------------------------------------------------
+// 非同期チャネル
+// ----------------------------------------------------------------------------
+//
+// std::sync::mpsc の send / recv はスレッドを止めてしまう。非同期のチャネルは
+// 待つ代わりに Pending を返し、相手が動いたときに Waker で起こしてもらう。
+// 1つのスレッドで動かすので、共有部分は Arc<Mutex<..>> ではなく Rc<RefCell<..>> で足りる。
+
+struct Shared<T> {
+    queue: VecDeque<T>,
+    capacity: usize,
+    senders: usize,
+    receiver_alive: bool,
+    recv_waker: Option<Waker>,
+    send_wakers: Vec<Waker>,
+}
+
+/// 非同期チャネルの送信側（clone で増やせる）
+pub struct Sender<T>(Rc<RefCell<Shared<T>>>);
+
+/// 非同期チャネルの受信側
+pub struct Receiver<T>(Rc<RefCell<Shared<T>>>);
+
+/// 容量 capacity の非同期チャネルを作る
+pub fn channel<T>(capacity: usize) -> (Sender<T>, Receiver<T>) {
+    assert!(capacity > 0, "capacity must be non-zero");
+    let shared = Rc::new(RefCell::new(Shared {
+        queue: VecDeque::with_capacity(capacity),
+        capacity,
+        senders: 1,
+        receiver_alive: true,
+        recv_waker: None,
+        send_wakers: Vec::new(),
+    }));
+    (Sender(Rc::clone(&shared)), Receiver(shared))
+}
+
+impl<T> Sender<T> {
+    /// 送る。満杯なら空きができるまで待つ。受信側がもういなければ値を返す
+    pub async fn send(&self, value: T) -> Result<(), T> {
+        let mut value = Some(value);
+        poll_fn(|cx| {
+            let mut shared = self.0.borrow_mut();
+            if !shared.receiver_alive {
+                return Poll::Ready(Err(value.take().unwrap()));
+            }
+            if shared.queue.len() < shared.capacity {
+                shared.queue.push_back(value.take().unwrap());
+                if let Some(waker) = shared.recv_waker.take() {
+                    waker.wake();
+                }
+                return Poll::Ready(Ok(()));
+            }
+            shared.send_wakers.push(cx.waker().clone());
+            Poll::Pending
+        })
+        .await
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        self.0.borrow_mut().senders += 1;
+        Sender(Rc::clone(&self.0))
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let mut shared = self.0.borrow_mut();
+        shared.senders -= 1;
+        // 最後の送信側が消えたら、待っている受信側に「閉じた」と知らせる
+        if shared.senders == 0 {
+            if let Some(waker) = shared.recv_waker.take() {
+                waker.wake();
+            }
+        }
+    }
+}
+
+impl<T> Receiver<T> {
+    /// 受け取る。空なら届くまで待つ。送信側がすべて drop されて空なら None
+    ///
+    /// 値は Ready を返すときにだけ取り出すので、race で負けて drop されても値は失われない。
+    pub async fn recv(&mut self) -> Option<T> {
+        poll_fn(|cx| {
+            let mut shared = self.0.borrow_mut();
+            if let Some(value) = shared.queue.pop_front() {
+                shared.send_wakers.drain(..).for_each(Waker::wake);
+                return Poll::Ready(Some(value));
+            }
+            if shared.senders == 0 {
+                return Poll::Ready(None);
+            }
+            shared.recv_waker = Some(cx.waker().clone());
+            Poll::Pending
+        })
+        .await
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        let mut shared = self.0.borrow_mut();
+        shared.receiver_alive = false;
+        shared.send_wakers.drain(..).for_each(Waker::wake);
+    }
+}
+
+// ----------------------------------------------------------------------------
+// 手書きの Future
+// ----------------------------------------------------------------------------
+
+/// n 回 Pending を返してから Ready（poll された回数）になる Future
+pub struct Countdown {
+    remaining: u32,
+    polls: u32,
+}
+
+pub fn countdown(n: u32) -> Countdown {
+    Countdown {
+        remaining: n,
+        polls: 0,
+    }
+}
+
+impl Future for Countdown {
+    type Output = u32;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<u32> {
+        self.polls += 1;
+        if self.remaining == 0 {
+            return Poll::Ready(self.polls);
+        }
+        self.remaining -= 1;
+        // すぐに poll し直してほしいので、自分で起こす
+        // 起こす約束をしないまま Pending を返すと、二度と poll されない
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
+
+/// Future の結果に関数を適用する Future（futures クレートの FutureExt::map にあたる）
+pub struct Map<F, G> {
+    // 中の Future は Box で固定しておくので、Map 自体は動かしてもよい
+    future: Pin<Box<F>>,
+    f: Option<G>,
+}
+
+// f は固定する必要がないので、G が Unpin でなくても Map は Unpin にできる
+impl<F, G> Unpin for Map<F, G> {}
+
+pub fn map<F, G, T>(future: F, f: G) -> Map<F, G>
+where
+    F: Future,
+    G: FnOnce(F::Output) -> T,
+{
+    Map {
+        future: Box::pin(future),
+        f: Some(f),
+    }
+}
+
+impl<F, G, T> Future for Map<F, G>
+where
+    F: Future,
+    G: FnOnce(F::Output) -> T,
+{
+    type Output = T;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        match self.future.as_mut().poll(cx) {
+            Poll::Ready(value) => {
+                let f = self.f.take().expect("Ready を返した後に poll された");
+                Poll::Ready(f(value))
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+// デモ
+// ----------------------------------------------------------------------------
+
+// 仮想の時刻をつけて表示する
+fn stamp(message: impl fmt::Display) {
+    println!("[{:>3} ms] {}", now(), message);
+}
+
+// 途中で drop された（キャンセルされた）ことを知らせる
+struct CancelNotice {
+    name: &'static str,
+    finished: bool,
+}
+
+impl Drop for CancelNotice {
+    fn drop(&mut self) {
+        if !self.finished {
+            stamp(format!(
+                "{} は終わる前に drop された（キャンセル）",
+                self.name
+            ));
+        }
+    }
+}
+
+/// latency ミリ秒後に応答するサーバーのふり
+pub async fn fetch(name: &'static str, latency: u64) -> String {
+    let mut notice = CancelNotice {
+        name,
+        finished: false,
+    };
+    sleep(latency).await;
+    notice.finished = true;
+    format!("{} の応答（{} ms）", name, latency)
+}
+
+async fn add_one(x: u32) -> u32 {
+    println!("  add_one({}) の本体が動いた", x);
+    x + 1
+}
+
+/// async fn は Future を返す
+pub fn async_fn_basics() {
+    println!("\n=== async fn は Future を返す ===");
+
+    // 呼んだだけでは本体は動かない。「これからする計算」を表す Future が返るだけ
+    let future = add_one(1);
+    println!("add_one(1) を呼んだ（まだ本体は動いていない）");
+    // Future の中身は、引数と await をまたいで持つ変数を入れた状態機械
+    println!("この Future のサイズ: {} バイト", mem::size_of_val(&future));
+    let value = block_on(future);
+    println!("block_on で poll して、初めて本体が動く: {}", value);
+
+    // .await は、その Future が終わるまで待ってから次に進む
+    let total = block_on(async {
+        let a = add_one(10).await;
+        let b = add_one(a).await;
+        a + b
+    });
+    println!("async ブロックの中で2回 await: {}", total);
+    // async fn は「async ブロックを返す普通の関数」と同じ
+    //   fn add_one(x: u32) -> impl Future<Output = u32> { async move { x + 1 } }
+    println!("Future を作っただけで .await も block_on もしなければ、本体は一度も動かない");
+}
+
+/// Future を手で実装する（poll と Waker）
+pub fn hand_written_future() {
+    println!("\n=== Future を手で実装する（poll と Waker） ===");
+
+    // Countdown は Pending を返すたびに自分で Waker を呼ぶ
+    let polls = block_on(countdown(3));
+    println!(
+        "countdown(3): {} 回 poll されて Ready（Pending 3 回 + Ready 1 回）",
+        polls
+    );
+
+    // sleep も手書きの Future。Pending のときに Waker をタイマーに預けて、時刻が来たら起こされる
+    let mut polls = 0;
+    let at = block_on(async {
+        let mut timer = pin!(sleep(30));
+        poll_fn(|cx| {
+            polls += 1;
+            timer.as_mut().poll(cx)
+        })
+        .await;
+        now()
+    });
+    println!(
+        "sleep(30): {} 回 poll されて {} ms で Ready（起こされたときだけ poll される）",
+        polls, at
+    );
+
+    // block_on は「起こされたら poll、だれも起こさないなら時計を進める」を繰り返すだけ
+    // Waker を預けずに Pending を返すと、だれも起こせないので deadlock で止まる
+    println!("Pending を返すなら、必ずだれかが Waker を呼ぶようにする");
+}
+
+/// Future をつなげる（順番に・同時に・map）
+pub fn chaining_futures() {
+    println!("\n=== Future をつなげる（順番に・同時に・map） ===");
+
+    // 順に await すると、前が終わってから次が始まる（時間は足し算）
+    println!("順に await:");
+    block_on(async {
+        let user = fetch("ユーザー", 20).await;
+        stamp(&user);
+        let orders = fetch("注文履歴", 30).await;
+        stamp(&orders);
+    });
+
+    // 依存しないものは join で同時に進める（時間は長い方だけ）
+    println!("join で同時に:");
+    block_on(async {
+        let (user, orders) = join(fetch("ユーザー", 20), fetch("注文履歴", 30)).await;
+        stamp(format!("{} / {}", user, orders));
+    });
+
+    // 自作の map で結果を加工する Future をつなげる（async ブロックで書いても同じ）
+    let doubled = block_on(map(
+        map(fetch("設定", 10), |reply| reply.chars().count()),
+        |chars| chars * 2,
+    ));
+    println!("map を2回つなげる: 応答の文字数の2倍 = {}", doubled);
+    println!("map も async ブロックも、中の Future を poll して結果を渡す Future にすぎない");
+}
+
+/// 非同期チャネル（容量と背圧）
+pub fn async_channels() {
+    println!("\n=== 非同期チャネル（容量と背圧） ===");
+
+    // 生産者は 10 ms ごとに送るが、消費者は1件に 30 ms かかる
+    // 容量 2 のチャネルが埋まると、send().await で生産者が待たされる
+    block_on(async {
+        let (tx, mut rx) = channel::<u32>(2);
+        let producer = async move {
+            for i in 1..=5 {
+                tx.send(i).await.unwrap();
+                stamp(format!("生産者: {} を送った", i));
+                sleep(10).await;
+            }
+            stamp("生産者: 終了（tx が drop される）");
+        };
+        let consumer = async {
+            while let Some(item) = rx.recv().await {
+                stamp(format!("    消費者: {} を受け取った", item));
+                sleep(30).await;
+            }
+            stamp("    消費者: recv() が None（チャネルが閉じた）");
+        };
+        join(producer, consumer).await;
+    });
+
+    // スレッドは1本だけ。待っている間は Pending を返して、ほかの Future に順番を譲る
+    println!("スレッドは1本。send().await / recv().await は待つ間に順番を譲るだけ");
+}
+
+/// select（race）で先に終わった方を使う
+pub fn select_race() {
+    println!("\n=== select（race）で先に終わった方を使う ===");
+
+    // 2つのミラーに同時に問い合わせ、早い方の応答を使う
+    let winner = block_on(async {
+        match race(fetch("ミラー A", 40), fetch("ミラー B", 25)).await {
+            Either::Left(reply) => reply,
+            Either::Right(reply) => reply,
+        }
+    });
+    println!("採用: {}", winner);
+
+    // 待ち合わせの間にほかの仕事をする: recv と tick（sleep）を race で待つ
+    // recv は Ready のときにしか値を取り出さないので、tick が勝っても値は失われない
+    println!("\nメッセージを待ちながら、20 ms ごとに tick:");
+    block_on(async {
+        let (tx, mut rx) = channel(4);
+        let sender = async move {
+            for (delay, text) in [(30, "hello"), (50, "world")] {
+                sleep(delay).await;
+                tx.send(text).await.unwrap();
+            }
+        };
+        let receiver = async {
+            loop {
+                match race(rx.recv(), sleep(20)).await {
+                    Either::Left(Some(text)) => stamp(format!("受信: {}", text)),
+                    Either::Left(None) => break,
+                    Either::Right(()) => stamp("tick"),
+                }
+            }
+            stamp("チャネルが閉じたので終了");
+        };
+        join(sender, receiver).await;
+    });
+}
+
+/// タイムアウトとリトライ
+pub fn timeouts() {
+    println!("\n=== タイムアウトとリトライ ===");
+
+    // timeout は race(future, sleep(ms)) と同じ。時間切れなら future は drop される
+    block_on(async {
+        match timeout(50, fetch("速いサーバー", 20)).await {
+            Ok(reply) => stamp(format!("Ok: {}", reply)),
+            Err(e) => stamp(format!("Err: {}", e)),
+        }
+    });
+    block_on(async {
+        match timeout(50, fetch("遅いサーバー", 80)).await {
+            Ok(reply) => stamp(format!("Ok: {}", reply)),
+            Err(e) => stamp(format!("Err: {}", e)),
+        }
+    });
+
+    // 待ち時間を倍にしながらリトライする（毎回、前の試行は捨てて最初からやり直し）
+    println!("\n60 ms かかるサーバーに、待ち時間を倍にしながら3回まで:");
+    let result = block_on(async {
+        let mut limit = 20;
+        for attempt in 1..=3 {
+            match timeout(limit, fetch("API", 60)).await {
+                Ok(reply) => return Ok(reply),
+                Err(e) => stamp(format!("{} 回目: {}", attempt, e)),
+            }
+            limit *= 2;
+        }
+        Err("3回とも時間切れ")
+    });
+    println!("結果: {:?}", result);
+    println!("時間切れにした Future はその場で drop されるので、後始末は Drop に書く");
+}
+
+/// チートシート用のデータ（`cheatsheet async_await` で表示）
+pub const CHEATSHEET: CheatSheet = CheatSheet {
+    syntax: &[
+        (
+            "async fn fetch() -> String { .. }",
+            "Future を返す関数（呼んだだけでは動かない）",
+        ),
+        (
+            "let reply = fetch().await;",
+            "終わるまで待つ（待つ間はほかの Future に譲る）",
+        ),
+        ("async move { .. }", "変数を move で取り込む async ブロック"),
+        (
+            "impl Future for T { fn poll(..) }",
+            "Ready か Pending を返す。Pending なら Waker を預ける",
+        ),
+        (
+            "tokio::select! { a = f => .., b = g => .. }",
+            "先に終わった分岐を実行する（この章の race）",
+        ),
+        (
+            "tokio::time::timeout(d, f).await",
+            "時間切れで Err（この章の timeout）",
+        ),
+    ],
+    methods: &[
+        ("block_on(future)", "終わるまで poll する（自作の実行器）"),
+        ("join(a, b).await", "両方を同時に進めて両方の結果を待つ"),
+        (
+            "race(a, b).await",
+            "先に終わった方を返し、もう一方を drop する",
+        ),
+        (
+            "tx.send(v).await / rx.recv().await",
+            "満杯・空なら待つ非同期チャネル",
+        ),
+        (
+            "cx.waker().clone()",
+            "あとで起こしてもらうための Waker を預かる",
+        ),
+        ("cx.waker().wake_by_ref()", "すぐに poll し直してもらう"),
+        (
+            "map(future, |v| ..)",
+            "結果を加工する Future（自作の combinator）",
+        ),
+        (
+            "std::future::poll_fn(|cx| ..)",
+            "クロージャから Future を作る",
+        ),
+    ],
+    gotchas: &[
+        "Future は poll されるまで何もしない（.await を忘れると実行されない）",
+        "Pending を返すなら、必ずだれかが Waker を呼ぶようにする（呼ばれないと止まったまま）",
+        "race / select で負けた Future は drop される。途中まで進めた処理は失われる",
+        "async の中で thread::sleep やブロックする recv を使うと、実行器ごと止まる",
+    ],
+};
+
+/// すべてのデモを実行
+pub fn run_all() {
+    println!("╔════════════════════════════════════════════════════════════════╗");
+    println!("║          Rust 非同期プログラミング（async/await）サンプル        ║");
+    println!("╚════════════════════════════════════════════════════════════════╝");
+
+    lesson_api::run_topic("async_await");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hand_written_futures_and_chaining() {
+        assert_eq!(block_on(countdown(0)), 1);
+        assert_eq!(block_on(countdown(4)), 5);
+
+        let doubled = block_on(map(async { 21 }, |n| n * 2));
+        assert_eq!(doubled, 42);
+
+        // 順に await すると時間は足し算、join なら長い方だけ
+        let sequential = block_on(async {
+            fetch("a", 20).await;
+            fetch("b", 30).await;
+            now()
+        });
+        let concurrent = block_on(async {
+            join(fetch("a", 20), fetch("b", 30)).await;
+            now()
+        });
+        assert_eq!((sequential, concurrent), (50, 30));
+    }
+
+    #[test]
+    fn race_and_timeout_follow_the_virtual_clock() {
+        let (winner, at) = block_on(async {
+            let winner = race(sleep(30), fetch("b", 10)).await;
+            (winner, now())
+        });
+        assert_eq!(winner, Either::Right("b の応答（10 ms）".to_string()));
+        assert_eq!(at, 10);
+
+        assert_eq!(block_on(timeout(5, fetch("slow", 50))), Err(Elapsed(5)));
+        assert!(block_on(timeout(50, fetch("fast", 5))).is_ok());
+    }
+
+    #[test]
+    fn channel_applies_backpressure_and_closes() {
+        let received = block_on(async {
+            let (tx, mut rx) = channel(1);
+            let producer = async move {
+                let mut sent_at = Vec::new();
+                for i in 0..3 {
+                    tx.send(i).await.unwrap();
+                    sent_at.push(now());
+                }
+                sent_at
+            };
+            let consumer = async {
+                let mut items = Vec::new();
+                while let Some(item) = rx.recv().await {
+                    items.push(item);
+                    sleep(10).await;
+                }
+                items
+            };
+            join(producer, consumer).await
+        });
+        // 容量 1 なので、3つ目は消費者が1つ目を取り出したあとにしか送れない
+        assert_eq!(received, (vec![0, 0, 10], vec![0, 1, 2]));
+    }
+}
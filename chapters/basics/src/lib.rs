@@ -0,0 +1,607 @@
+// ============================================================================
+// Rust基本構文サンプル - 変数、データ型、関数
+// 公式ドキュメント: https://doc.rust-lang.org/book/ch03-00-common-programming-concepts.html
+// ============================================================================
+
+use lesson_api::input::{Input, ScriptedInput, StdinInput};
+use lesson_api::{print, println, CheatSheet};
+use std::io;
+use std::num::{IntErrorKind, ParseIntError};
+
+/// 変数と可変性のデモ
+/// Rustでは変数はデフォルトで不変（immutable）
+/// mutキーワードを使うことで可変（mutable）にできる
+pub fn variables_demo() {
+    println!("\n=== 変数と可変性 ===");
+
+    // 不変変数（デフォルト）
+    let x = 5;
+    println!("不変変数 x = {}", x);
+    // x = 6; // コンパイルエラー！不変変数は再代入できない
+
+    // 可変変数
+    let mut y = 5;
+    println!("可変変数 y = {}", y);
+    y = 6;
+    println!("y を変更後: y = {}", y);
+
+    // シャドーイング - 同じ名前の変数を再定義できる
+    let z = 5;
+    let z = z + 1; // 新しい変数zが前のzをシャドーイング
+    let z = z * 2;
+    println!("シャドーイング後の z = {}", z); // 12
+
+    // シャドーイングでは型も変更可能
+    let spaces = "   "; // &str型
+    let spaces = spaces.len(); // usize型に変更
+    println!("spacesの長さ: {}", spaces);
+}
+
+/// 定数のデモ
+/// 定数は常に不変で、型注釈が必須
+pub fn constants_demo() {
+    println!("\n=== 定数 ===");
+
+    // 定数は大文字スネークケースで命名
+    const MAX_POINTS: u32 = 100_000;
+    const PI: f64 = 3.14159;
+
+    println!("MAX_POINTS = {}", MAX_POINTS);
+    println!("PI = {}", PI);
+}
+
+/// データ型のデモ
+/// Rustは静的型付け言語で、コンパイル時にすべての変数の型が決まる
+pub fn data_types_demo() {
+    println!("\n=== データ型 ===");
+
+    // --- スカラー型 ---
+    println!("\n-- スカラー型 --");
+
+    // 整数型
+    let _i8: i8 = -128; // 8ビット符号付き
+    let _u8: u8 = 255; // 8ビット符号なし
+    let _i32: i32 = -2_147_483_648; // 32ビット符号付き（デフォルト）
+    let _u64: u64 = 18_446_744_073_709_551_615; // 64ビット符号なし
+    let _isize: isize = 100; // アーキテクチャ依存（32 or 64ビット）
+
+    println!("整数リテラル:");
+    println!("  10進数: {}", 98_222);
+    println!("  16進数: {}", 0xff);
+    println!("  8進数: {}", 0o77);
+    println!("  2進数: {}", 0b1111_0000);
+    println!("  バイト(u8のみ): {}", b'A');
+
+    // 浮動小数点型
+    let float64: f64 = 3.14159; // 64ビット（デフォルト）
+    let float32: f32 = 2.5; // 32ビット
+    println!("f64: {}, f32: {}", float64, float32);
+
+    // 数値演算
+    println!("\n数値演算:");
+    println!("  加算: 5 + 10 = {}", 5 + 10);
+    println!("  減算: 95.5 - 4.3 = {}", 95.5 - 4.3);
+    println!("  乗算: 4 * 30 = {}", 4 * 30);
+    println!("  除算: 56.7 / 32.2 = {}", 56.7 / 32.2);
+    println!("  整数除算: 5 / 3 = {}", 5 / 3); // 結果は1
+    println!("  剰余: 43 % 5 = {}", 43 % 5);
+
+    // ブーリアン型
+    let t: bool = true;
+    let f: bool = false;
+    println!("\nブーリアン: true={}, false={}", t, f);
+
+    // 文字型
+    let c: char = 'z';
+    let emoji: char = '🦀'; // Unicodeスカラー値を格納
+    let kanji: char = '漢';
+    println!("文字型: c='{}', emoji='{}', kanji='{}'", c, emoji, kanji);
+
+    // --- 複合型 ---
+    println!("\n-- 複合型 --");
+
+    // タプル型 - 異なる型の値をグループ化
+    let tup: (i32, f64, u8) = (500, 6.4, 1);
+    let (x, y, z) = tup; // 分解（デストラクチャリング）
+    println!("タプル分解: x={}, y={}, z={}", x, y, z);
+    println!(
+        "タプルインデックスアクセス: tup.0={}, tup.1={}, tup.2={}",
+        tup.0, tup.1, tup.2
+    );
+
+    // ユニット型 - 空のタプル、値を返さない式の戻り値
+    let _unit: () = ();
+
+    // 配列型 - 同じ型の固定長コレクション
+    let arr: [i32; 5] = [1, 2, 3, 4, 5];
+    println!("配列: {:?}", arr);
+    println!("配列の最初の要素: arr[0] = {}", arr[0]);
+
+    // 同じ値で初期化
+    let zeros = [0; 5]; // [0, 0, 0, 0, 0]
+    println!("ゼロで初期化した配列: {:?}", zeros);
+}
+
+/// 関数のデモ
+/// Rustの関数はfnキーワードで定義
+pub fn functions_demo() {
+    println!("\n=== 関数 ===");
+
+    // 引数なしの関数呼び出し
+    simple_function();
+
+    // 引数ありの関数呼び出し
+    print_value(42);
+    print_labeled_measurement(5, 'h');
+
+    // 戻り値のある関数
+    let result = add(5, 3);
+    println!("add(5, 3) = {}", result);
+
+    // 式と文の違い
+    let y = {
+        let x = 3;
+        x + 1 // セミコロンなし = 式（値を返す）
+    };
+    println!("ブロック式の結果: y = {}", y);
+
+    // 早期リターン
+    let five = return_five();
+    println!("return_five() = {}", five);
+}
+
+// 単純な関数
+fn simple_function() {
+    println!("  simple_function が呼ばれました");
+}
+
+// 引数を持つ関数（パラメータの型注釈は必須）
+fn print_value(x: i32) {
+    println!("  print_value: x = {}", x);
+}
+
+// 複数の引数
+fn print_labeled_measurement(value: i32, unit_label: char) {
+    println!("  measurement: {}{}", value, unit_label);
+}
+
+// 戻り値のある関数（-> で戻り値の型を指定）
+fn add(a: i32, b: i32) -> i32 {
+    a + b // 最後の式が戻り値（セミコロンなし）
+}
+
+// returnキーワードで早期リターン
+fn return_five() -> i32 {
+    return 5;
+    // この下のコードは実行されない
+}
+
+/// 制御フローのデモ
+pub fn control_flow_demo() {
+    println!("\n=== 制御フロー ===");
+
+    // --- if式 ---
+    println!("\n-- if式 --");
+    let number = 6;
+
+    if number % 4 == 0 {
+        println!("{} は4で割り切れる", number);
+    } else if number % 3 == 0 {
+        println!("{} は3で割り切れる", number);
+    } else if number % 2 == 0 {
+        println!("{} は2で割り切れる", number);
+    } else {
+        println!("{} は4, 3, 2で割り切れない", number);
+    }
+
+    // ifは式なのでletで使える
+    let condition = true;
+    let value = if condition { 5 } else { 6 };
+    println!("条件式の結果: value = {}", value);
+
+    // --- ループ ---
+    println!("\n-- ループ --");
+
+    // loop - 無限ループ（breakで抜ける）
+    let mut counter = 0;
+    let result = loop {
+        counter += 1;
+        if counter == 10 {
+            break counter * 2; // breakで値を返せる
+        }
+    };
+    println!("loopの結果: {}", result);
+
+    // ループラベル - ネストしたループで外側のループを制御
+    let mut count = 0;
+    'counting_up: loop {
+        let mut remaining = 10;
+        loop {
+            if remaining == 9 {
+                break; // 内側のループを抜ける
+            }
+            if count == 2 {
+                break 'counting_up; // 外側のループを抜ける
+            }
+            remaining -= 1;
+        }
+        count += 1;
+    }
+    println!("ループラベルの例: count = {}", count);
+
+    // while - 条件付きループ
+    let mut number = 3;
+    while number != 0 {
+        println!("while: {}!", number);
+        number -= 1;
+    }
+    println!("while終了!");
+
+    // for - コレクションの反復
+    let a = [10, 20, 30, 40, 50];
+    for element in a {
+        println!("for: 値は {}", element);
+    }
+
+    // Rangeを使ったfor
+    println!("Rangeでカウントダウン:");
+    for number in (1..4).rev() {
+        println!("  {}!", number);
+    }
+}
+
+/// 再帰のデモ
+/// 関数が自分自身を呼ぶと、呼び出しごとにスタックフレームが積まれる
+pub fn recursion_demo() {
+    println!("\n=== 再帰とスタック ===");
+
+    // 再帰で書いた階乗: n! = n * (n-1)!
+    fn factorial(n: u64) -> u64 {
+        if n <= 1 {
+            1 // 基底ケースがないと永遠に呼び続ける
+        } else {
+            n * factorial(n - 1)
+        }
+    }
+    println!("factorial(10) = {}", factorial(10));
+
+    // 素朴な再帰のフィボナッチは同じ計算を何度も繰り返す
+    fn fib(n: u32, calls: &mut u64) -> u64 {
+        *calls += 1;
+        if n < 2 {
+            n as u64
+        } else {
+            fib(n - 1, calls) + fib(n - 2, calls)
+        }
+    }
+    let mut calls = 0;
+    println!(
+        "fib(25) = {}（関数呼び出し {}回）",
+        fib(25, &mut calls),
+        calls
+    );
+
+    // ループに書き換える: 状態を変数で持てばスタックは積まれない
+    fn factorial_loop(n: u64) -> u64 {
+        let mut acc = 1;
+        for i in 2..=n {
+            acc *= i;
+        }
+        acc
+    }
+
+    fn fib_loop(n: u32) -> u64 {
+        let (mut a, mut b) = (0, 1);
+        for _ in 0..n {
+            (a, b) = (b, a + b);
+        }
+        a
+    }
+    println!("factorial_loop(10) = {}", factorial_loop(10));
+    println!("fib_loop(25) = {}（ループ 25回）", fib_loop(25));
+    println!("fib_loop(90) = {}", fib_loop(90));
+
+    // 「末尾再帰」風: 累積値を引数で渡す
+    // Rustは末尾呼び出しの最適化を保証しないので、深くなるならループにする
+    fn sum_to(n: u64, acc: u64) -> u64 {
+        if n == 0 {
+            acc
+        } else {
+            sum_to(n - 1, acc + n)
+        }
+    }
+    println!("sum_to(1000, 0) = {}", sum_to(1000, 0));
+
+    // スタックの深さには上限がある
+    // 子プロセスで底なしの再帰を実行し、どう終わるかを観察する
+    println!("\n子プロセスで底なしの再帰を実行します...");
+    // wasm32（ブラウザ版）では子プロセスを起動できない
+    if cfg!(target_arch = "wasm32") {
+        println!("  （ブラウザ版では子プロセスを起動できないので省略します）");
+    } else {
+        match std::env::current_exe().and_then(|exe| {
+            std::process::Command::new(exe)
+                .arg(OVERFLOW_CHILD_FLAG)
+                .output()
+        }) {
+            Ok(output) => {
+                println!("  終了ステータス: {}", output.status);
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                if let Some(line) = stderr.lines().find(|line| !line.is_empty()) {
+                    println!("  エラー出力: {}", line);
+                }
+            }
+            Err(e) => println!("  子プロセスを起動できませんでした: {}", e),
+        }
+    }
+    println!("スタックオーバーフローは panic ではないので catch_unwind でも捕まえられず、");
+    println!("プロセスごと異常終了します。深い再帰はループに書き換えるか、");
+    println!("std::thread::Builder::stack_size で大きなスタックのスレッドを使いましょう。");
+}
+
+/// スタックオーバーフローを観察するための子プロセス用フラグ
+pub const OVERFLOW_CHILD_FLAG: &str = "--overflow-stack-child";
+
+/// 基底ケースのない再帰でスタックを使い切る（子プロセスで呼ばれる）
+pub fn overflow_stack() -> ! {
+    fn dive(depth: u64) -> u64 {
+        // black_box で最適化によるループ化や除去を防ぐ
+        let frame = std::hint::black_box([depth as u8; 256]);
+        if std::hint::black_box(false) {
+            return depth;
+        }
+        dive(depth + 1) + frame[0] as u64
+    }
+    let depth = dive(0);
+    unreachable!("再帰が {} 段で止まりました", depth)
+}
+
+/// 再帰的なデータ型とBox
+/// 自分自身を直接含む型はサイズが決まらないので、Boxで間接参照にする
+pub fn recursive_types_demo() {
+    println!("\n=== 再帰的なデータ型とBox ===");
+
+    // enum List { Cons(i32, List), Nil } // エラー！無限のサイズになる
+    #[derive(Debug)]
+    enum List {
+        Cons(i32, Box<List>),
+        Nil,
+    }
+    use List::{Cons, Nil};
+
+    let list = Cons(1, Box::new(Cons(2, Box::new(Cons(3, Box::new(Nil))))));
+    println!("リスト: {:?}", list);
+
+    fn sum(list: &List) -> i32 {
+        match list {
+            Cons(value, rest) => value + sum(rest),
+            Nil => 0,
+        }
+    }
+    println!("合計: {}", sum(&list));
+    println!(
+        "List のサイズ: {}バイト（Box はポインタ1つ分: {}バイト）",
+        std::mem::size_of::<List>(),
+        std::mem::size_of::<Box<List>>()
+    );
+
+    // 式の木も再帰的なデータ型の典型例
+    enum Expr {
+        Num(i64),
+        Add(Box<Expr>, Box<Expr>),
+        Mul(Box<Expr>, Box<Expr>),
+    }
+
+    fn eval(expr: &Expr) -> i64 {
+        match expr {
+            Expr::Num(n) => *n,
+            Expr::Add(a, b) => eval(a) + eval(b),
+            Expr::Mul(a, b) => eval(a) * eval(b),
+        }
+    }
+
+    // (2 + 3) * 4
+    let expr = Expr::Mul(
+        Box::new(Expr::Add(Box::new(Expr::Num(2)), Box::new(Expr::Num(3)))),
+        Box::new(Expr::Num(4)),
+    );
+    println!("(2 + 3) * 4 = {}", eval(&expr));
+}
+
+// ----------------------------------------------------------------------------
+// 仕上げ: 入力を受け取って計算する
+// ----------------------------------------------------------------------------
+
+/// parse に失敗した理由を、入力した人に分かる言葉にする
+fn explain_parse_error(text: &str, error: &ParseIntError) -> String {
+    match error.kind() {
+        IntErrorKind::Empty => "何も入力されていません".to_string(),
+        IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => {
+            format!("{} は大きすぎます（i64 の範囲を超えています）", text)
+        }
+        _ => format!("{:?} は整数ではありません", text),
+    }
+}
+
+/// 整数を読む。読めるまで聞き直し、q か入力の終わりなら None
+/// 読み直しは Input::read_number に任せ、読めなかった理由だけをここで作る
+fn read_number(input: &mut impl Input, prompt: &str) -> io::Result<Option<i64>> {
+    input.read_number(prompt, i64::MIN..=i64::MAX, |text| {
+        // i64 の範囲はすべて受け付けるので、ここに来るのは parse に失敗したときだけ
+        let reason = match text.parse::<i64>() {
+            Err(e) => explain_parse_error(text, &e),
+            Ok(_) => String::new(),
+        };
+        format!("  {}", reason)
+    })
+}
+
+/// 演算子を1つ読む。読めるまで聞き直し、q か入力の終わりなら None
+fn read_operator(input: &mut impl Input) -> io::Result<Option<char>> {
+    loop {
+        print!("演算子（+ - * / %）: ");
+        let Some(line) = input.read_line()? else {
+            return Ok(None);
+        };
+        match line.trim() {
+            "q" => return Ok(None),
+            op @ ("+" | "-" | "*" | "/" | "%") => return Ok(op.chars().next()),
+            other => println!("  {:?} は使えない演算子です", other),
+        }
+    }
+}
+
+/// 計算する。0 での割り算と、i64 に収まらない結果はエラー
+pub fn calculate(a: i64, op: char, b: i64) -> Result<i64, String> {
+    if matches!(op, '/' | '%') && b == 0 {
+        return Err("0 では割れません".to_string());
+    }
+    let result = match op {
+        '+' => a.checked_add(b),
+        '-' => a.checked_sub(b),
+        '*' => a.checked_mul(b),
+        '/' => a.checked_div(b),
+        '%' => a.checked_rem(b),
+        _ => return Err(format!("{} は使えない演算子です", op)),
+    };
+    result.ok_or_else(|| format!("{} {} {} は i64 に収まりません", a, op, b))
+}
+
+/// 「数 演算子 数」を読んで計算するのを、q か入力の終わりまで繰り返す
+/// 戻り値は計算できた回数
+pub fn calculator_session(input: &mut impl Input) -> io::Result<u32> {
+    let mut count = 0;
+    while let Some(a) = read_number(input, "1つ目の数（q で終了）: ")? {
+        let Some(op) = read_operator(input)? else {
+            break;
+        };
+        let Some(b) = read_number(input, "2つ目の数: ")? else {
+            break;
+        };
+        match calculate(a, op, b) {
+            Ok(result) => {
+                count += 1;
+                println!("  {} {} {} = {}", a, op, b, result);
+            }
+            Err(e) => println!("  エラー: {}", e),
+        }
+    }
+    Ok(count)
+}
+
+/// 仕上げ: 入力を受け取って計算する
+pub fn calculator_finale() {
+    println!("\n=== 仕上げ: 入力を受け取って計算する ===");
+
+    // 変数・型・関数・制御フロー・エラー処理をまとめて使う
+    // 入力は Input トレイト越しに読むので、端末からでも用意した入力からでも同じコードで動く
+    let result = if lesson_api::input::is_interactive() {
+        println!("数・演算子・数の順に入力してください");
+        calculator_session(&mut StdinInput)
+    } else {
+        // 「すべて実行」やパイプからの実行では質問できないので、用意した入力で実演する
+        println!("（ここでは入力できないので、用意した入力で実行します）");
+        let script = "12\n*\nabc\n3\n7\n/\n0\n99999999999999999999\n5\n^\n-\n100\nq\n";
+        calculator_session(&mut ScriptedInput::new(script).echo())
+    };
+    match result {
+        Ok(count) => println!("{} 回計算しました", count),
+        Err(e) => println!("入力を読めませんでした: {}", e),
+    }
+}
+
+/// チートシート用のデータ（`cheatsheet basics` で表示）
+pub const CHEATSHEET: CheatSheet = CheatSheet {
+    syntax: &[
+        ("let x = 5;", "不変変数"),
+        ("let mut y = 5;", "可変変数"),
+        ("const MAX: u32 = 100_000;", "定数（型注釈が必須）"),
+        ("let x = x + 1;", "シャドーイング（型も変えられる）"),
+        (
+            "fn add(a: i32, b: i32) -> i32 { a + b }",
+            "関数（最後の式が戻り値）",
+        ),
+        ("let v = if cond { 5 } else { 6 };", "ifは式"),
+        ("let r = loop { break 10; };", "breakで値を返すloop"),
+        ("'outer: loop { break 'outer; }", "ループラベル"),
+        ("for i in (1..4).rev() {}", "Rangeを使ったfor"),
+        ("let (x, y) = (1, 2.0);", "タプルの分解"),
+        ("let a = [0; 5];", "同じ値で初期化した配列"),
+    ],
+    methods: &[
+        ("i32::MAX / u8::MIN", "整数型の最大値・最小値"),
+        ("x.pow(2) / f.powi(2)", "累乗"),
+        (
+            "text.trim().parse::<i64>()",
+            "入力した文字列を整数にする（Result）",
+        ),
+        ("a.checked_add(b)", "オーバーフローしたら None"),
+        ("f.sqrt()", "平方根"),
+        ("arr.len()", "配列の長さ"),
+        ("tup.0", "タプルのインデックスアクセス"),
+    ],
+    gotchas: &[
+        "変数はデフォルトで不変。再代入するには mut が必要",
+        "関数の最後の式にセミコロンを付けると () を返してしまう",
+        "整数除算は切り捨て（5 / 3 == 1）",
+        "配列の範囲外アクセスは実行時に panic する",
+        "深すぎる再帰はスタックオーバーフローでプロセスごと終了する（panic ではない）",
+    ],
+};
+
+/// すべてのデモを実行
+pub fn run_all() {
+    println!("╔════════════════════════════════════════════════════════════════╗");
+    println!("║          Rust基本構文サンプル                                   ║");
+    println!("╚════════════════════════════════════════════════════════════════╝");
+
+    lesson_api::run_topic("basics");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lesson_api::output;
+
+    // 用意した入力で電卓を動かし、計算できた回数と出力を返す
+    fn run_scripted(script: &str) -> (u32, String) {
+        let mut count = 0;
+        let text = output::capture(|| {
+            count = calculator_session(&mut ScriptedInput::new(script)).unwrap();
+        });
+        (count, text)
+    }
+
+    #[test]
+    fn calculates_until_quit() {
+        let (count, text) = run_scripted("12\n*\n3\n7\n-\n10\nq\n5\n");
+        assert_eq!(count, 2);
+        assert!(text.contains("12 * 3 = 36"));
+        assert!(text.contains("7 - 10 = -3"));
+    }
+
+    #[test]
+    fn asks_again_after_invalid_input() {
+        let (count, text) = run_scripted("abc\n\n99999999999999999999\n8\n^\n%\n3\n");
+        assert_eq!(count, 1);
+        assert!(text.contains("\"abc\" は整数ではありません"));
+        assert!(text.contains("何も入力されていません"));
+        assert!(text.contains("大きすぎます"));
+        assert!(text.contains("\"^\" は使えない演算子です"));
+        assert!(text.contains("8 % 3 = 2"));
+    }
+
+    #[test]
+    fn reports_errors_and_stops_at_end_of_input() {
+        let (count, text) = run_scripted("1\n/\n0\n");
+        assert_eq!(count, 0);
+        assert!(text.contains("0 では割れません"));
+
+        assert_eq!(
+            calculate(i64::MAX, '+', 1),
+            Err(format!("{} + 1 は i64 に収まりません", i64::MAX))
+        );
+        assert!(calculate(i64::MIN, '/', -1).is_err());
+        assert_eq!(calculate(-7, '/', 2), Ok(-3));
+    }
+}
@@ -0,0 +1,96 @@
+// ============================================================================
+// ビルドスクリプト: ビルドの情報と表をコードとして生成する
+// ============================================================================
+//
+// cargo はこのクレートをコンパイルする前に build.rs を実行し、
+// 環境変数（CARGO_FEATURE_*、PROFILE、TARGET など）で情報を渡す。
+// ここでは OUT_DIR/build_info.rs を書き出し、src/lib.rs が include! で取り込む。
+//
+// 標準出力に "cargo::..." と書くと cargo への指示になる:
+//   cargo::rerun-if-changed=build.rs  このファイルが変わったときだけ再実行する
+//   cargo::rustc-cfg=gk_release       コードで #[cfg(gk_release)] が使えるようになる
+
+use std::env;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+// 生成する素数表の上限
+const PRIME_LIMIT: usize = 100;
+
+fn main() {
+    println!("cargo::rerun-if-changed=build.rs");
+    // 独自の cfg は宣言しておかないと unexpected_cfgs の警告になる
+    println!("cargo::rustc-check-cfg=cfg(gk_release)");
+
+    let profile = env::var("PROFILE").unwrap_or_default();
+    if profile == "release" {
+        println!("cargo::rustc-cfg=gk_release");
+    }
+
+    // 有効な feature は CARGO_FEATURE_<大文字、- は _> の環境変数でわかる
+    let mut features: Vec<String> = env::vars()
+        .filter_map(|(key, _)| key.strip_prefix("CARGO_FEATURE_").map(str::to_string))
+        .map(|name| name.to_lowercase().replace('_', "-"))
+        .collect();
+    features.sort();
+
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let rustc_version = Command::new(rustc)
+        .arg("--version")
+        .output()
+        .ok()
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|version| version.trim().to_string())
+        .unwrap_or_else(|| "不明".to_string());
+
+    let mut code = String::new();
+    code.push_str("// build.rs が生成したファイル（手で編集しない）\n\n");
+    let _ = writeln!(code, "/// このクレートで有効になっている feature");
+    let _ = writeln!(code, "pub const FEATURES: &[&str] = &{:?};", features);
+    let _ = writeln!(code, "pub const PROFILE: &str = {:?};", profile);
+    let _ = writeln!(
+        code,
+        "pub const TARGET: &str = {:?};",
+        env::var("TARGET").unwrap_or_default()
+    );
+    let _ = writeln!(
+        code,
+        "pub const OPT_LEVEL: &str = {:?};",
+        env::var("OPT_LEVEL").unwrap_or_default()
+    );
+    let _ = writeln!(code, "pub const RUSTC_VERSION: &str = {:?};", rustc_version);
+
+    // 実行時に計算しなくて済むように、表をビルド時に作っておく
+    let primes = primes_below(PRIME_LIMIT);
+    let _ = writeln!(
+        code,
+        "\n/// {} 未満の素数（ビルド時にエラトステネスのふるいで計算）",
+        PRIME_LIMIT
+    );
+    let _ = writeln!(
+        code,
+        "pub const PRIMES: [u32; {}] = {:?};",
+        primes.len(),
+        primes
+    );
+
+    let out_dir = env::var("OUT_DIR").expect("cargo が OUT_DIR を設定するはず");
+    fs::write(Path::new(&out_dir).join("build_info.rs"), code)
+        .expect("build_info.rs を書き出せません");
+}
+
+fn primes_below(limit: usize) -> Vec<u32> {
+    let mut is_prime = vec![true; limit];
+    let mut primes = Vec::new();
+    for n in 2..limit {
+        if is_prime[n] {
+            primes.push(n as u32);
+            for multiple in (n * n..limit).step_by(n) {
+                is_prime[multiple] = false;
+            }
+        }
+    }
+    primes
+}
@@ -0,0 +1,329 @@
+// ============================================================================
+// Rust Cargo の feature とビルドスクリプトのサンプル
+// 公式ドキュメント: https://doc.rust-lang.org/cargo/reference/features.html
+//                   https://doc.rust-lang.org/cargo/reference/build-scripts.html
+// ============================================================================
+//
+// このクレート自身の Cargo.toml と build.rs を教材にする。
+//
+//   [features]
+//   default = ["color"]          # 何も指定しないときに有効
+//   color = []
+//   random = ["dep:rand"]        # optional な依存を有効にする
+//
+// ランナーからは次のように切り替えられる（feature は lessons を経由して届く）:
+//   cargo run --features random
+
+use lesson_api::input::{self, Input, StdinInput};
+use lesson_api::{println, CheatSheet};
+use std::process::Command;
+use std::time::Instant;
+
+/// build.rs が OUT_DIR に生成したコード（FEATURES、PROFILE、PRIMES など）
+pub mod build_info {
+    include!(concat!(env!("OUT_DIR"), "/build_info.rs"));
+}
+
+// feature によって中身が変わる関数（使う側は feature を気にしなくてよい）
+#[cfg(feature = "color")]
+fn highlight(text: &str) -> String {
+    format!("\x1b[1;32m{}\x1b[0m", text)
+}
+
+#[cfg(not(feature = "color"))]
+fn highlight(text: &str) -> String {
+    format!("[{}]", text)
+}
+
+/// cfg! と #[cfg(feature)]
+pub fn cfg_feature() {
+    println!("\n=== cfg! と #[cfg(feature)] ===");
+
+    // #[cfg(...)] はコンパイルするかどうかを決める（無効な側はコンパイルされない）
+    // cfg!(...) は true / false の定数になる（どちらの分岐もコンパイルされる）
+    println!("cfg!(feature = \"color\")  = {}", cfg!(feature = "color"));
+    println!("cfg!(feature = \"random\") = {}", cfg!(feature = "random"));
+    println!("highlight(\"OK\") = {}", highlight("OK"));
+
+    // feature 以外にも、ターゲットやビルドの種類で分けられる
+    println!("cfg!(debug_assertions) = {}", cfg!(debug_assertions));
+    println!(
+        "cfg!(target_os = \"linux\") = {}",
+        cfg!(target_os = "linux")
+    );
+    println!(
+        "cfg!(unix) = {}, cfg!(windows) = {}",
+        cfg!(unix),
+        cfg!(windows)
+    );
+}
+
+/// optional な依存
+pub fn optional_dependency() {
+    println!("\n=== optional な依存（dep:） ===");
+
+    // Cargo.toml:
+    //   rand = { version = "0.8.5", optional = true }
+    //   random = ["dep:rand"]
+    // random が無効なときは rand はダウンロードもコンパイルもされない
+    #[cfg(feature = "random")]
+    {
+        use rand::Rng;
+        let roll: u32 = rand::thread_rng().gen_range(1..=6);
+        println!("rand クレートでサイコロ: {}", roll);
+    }
+    #[cfg(not(feature = "random"))]
+    {
+        let mut rng = lesson_api::random::Rng::new(7);
+        println!(
+            "rand は無効なので、lesson-api の乱数でサイコロ: {}",
+            rng.below(6) + 1
+        );
+        println!("`cargo run --features random` で rand を使う版になります");
+    }
+}
+
+/// build.rs と生成コード（include!）
+pub fn build_script() {
+    println!("\n=== build.rs と生成コード（include!） ===");
+
+    // build.rs は cargo が渡す環境変数を読み、Rust のコードを書き出す
+    // src/ では include!(concat!(env!("OUT_DIR"), "/build_info.rs")) で取り込む
+    println!(
+        "プロファイル: {}（最適化レベル {}）",
+        build_info::PROFILE,
+        build_info::OPT_LEVEL
+    );
+    println!("ターゲット: {}", build_info::TARGET);
+    println!("コンパイラ: {}", build_info::RUSTC_VERSION);
+
+    // 表をビルド時に計算しておけば、実行時には配列を読むだけ
+    println!(
+        "ビルド時に計算した素数（{}個）: {:?} …",
+        build_info::PRIMES.len(),
+        &build_info::PRIMES[..10]
+    );
+
+    // build.rs が cargo::rustc-cfg=gk_release を出したときだけ有効になる独自の cfg
+    if cfg!(gk_release) {
+        println!("gk_release: リリースビルドです");
+    } else {
+        println!("gk_release: 無効（cargo run --release で有効になります）");
+    }
+}
+
+/// このビルドの feature
+pub fn features_of_this_build() {
+    println!("\n=== このビルドの feature ===");
+
+    // build.rs が CARGO_FEATURE_* の環境変数から集めた一覧
+    if build_info::FEATURES.is_empty() {
+        println!("有効な feature はありません（--no-default-features でビルドされています）");
+    } else {
+        println!("有効な feature: {}", build_info::FEATURES.join(", "));
+    }
+    for feature in ["default", "color", "random"] {
+        let mark = if build_info::FEATURES.contains(&feature) {
+            "✓"
+        } else {
+            " "
+        };
+        println!("  [{}] {}", mark, feature);
+    }
+
+    // ワークスペースでは、同じクレートに要求された feature は合算される（feature unification）
+    // どこかが random を有効にすると、このクレートを使うすべての場所で random が有効になる
+    println!("feature は足し算: どれか1つの依存元が有効にすると全体で有効になります");
+    println!("ランナーの --features random は lessons/random → build_system/random と伝わります");
+}
+
+/// 計測用の重いループ（結果と、かかったミリ秒）
+/// イテレータとクロージャを重ねているので、最適化の有無で速さが大きく変わる
+pub fn hot_loop() -> (u64, f64) {
+    let start = Instant::now();
+    let result = (0..3_000_000u64)
+        .map(std::hint::black_box)
+        .filter(|n| n % 3 != 0)
+        .map(|n| n.wrapping_mul(n) % 1_000_003)
+        .fold(0u64, |acc, n| acc.wrapping_add(n));
+    (result, start.elapsed().as_secs_f64() * 1000.0)
+}
+
+/// リリースビルドの子プロセスで hot_loop を実行するためのフラグ
+pub const HOT_LOOP_CHILD_FLAG: &str = "--hot-loop-child";
+
+/// hot_loop の結果を「結果 ミリ秒」の1行で出力して終了する（子プロセスで呼ばれる）
+pub fn hot_loop_child() -> ! {
+    let (result, ms) = hot_loop();
+    std::println!("{} {}", result, ms);
+    std::process::exit(0)
+}
+
+/// デバッグビルドとリリースビルド
+pub fn debug_vs_release() {
+    println!("\n=== デバッグビルドとリリースビルド ===");
+
+    // wasm32-unknown-unknown（ブラウザ版）には時計が無く、Instant::now() が panic する
+    if cfg!(target_arch = "wasm32") {
+        println!("ブラウザ版では時間を計測できません。cargo run で試してください");
+        return;
+    }
+
+    // cargo run は dev プロファイル（opt-level = 0、debug_assertions あり）でビルドする
+    println!(
+        "このビルド: {}（opt-level = {}、debug_assertions = {}）",
+        build_info::PROFILE,
+        build_info::OPT_LEVEL,
+        cfg!(debug_assertions)
+    );
+    let (result, debug_ms) = hot_loop();
+    println!("重いループ: {:.1} ms（結果 {}）", debug_ms, result);
+
+    if !cfg!(debug_assertions) {
+        println!(
+            "リリースビルドで実行しています。cargo run（デバッグビルド）の結果と比べてみてください"
+        );
+        return;
+    }
+    println!("デバッグビルドは最適化されず、オーバーフローの検査なども入るので何倍も遅い");
+
+    // 「すべて実行」（出力をためて並列に実行する）やパイプからの入力のときは、質問せずにコマンドだけ案内する
+    if !input::is_interactive() {
+        println!("比べるには: cargo run --release");
+        return;
+    }
+    let answer = StdinInput.prompt(
+        "cargo run --release でリリースビルドを起動して比べますか？（初回はビルドに時間がかかります）[y/N]: ",
+    );
+    if !matches!(answer, Ok(Some(answer)) if answer.trim().eq_ignore_ascii_case("y")) {
+        println!("比べるには: cargo run --release");
+        return;
+    }
+
+    println!("リリースビルドで実行しています…");
+    lesson_api::output::flush();
+
+    // 子プロセスの stdout は「結果 ミリ秒」の1行。stderr（cargo の進み具合や警告）は失敗したときだけ見せる
+    // CARGO は cargo run の実行中に cargo 自身のパスが入る環境変数
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let output = Command::new(cargo)
+        .args([
+            "run",
+            "--release",
+            "--quiet",
+            "--manifest-path",
+            WORKSPACE_MANIFEST,
+            "--",
+        ])
+        .arg(HOT_LOOP_CHILD_FLAG)
+        .output();
+    let release_ms = match output {
+        Ok(output) if output.status.success() => String::from_utf8_lossy(&output.stdout)
+            .split_whitespace()
+            .nth(1)
+            .and_then(|ms| ms.parse::<f64>().ok()),
+        Ok(output) => {
+            println!("リリースビルドを実行できませんでした: {}", output.status);
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            if let Some(line) = stderr.lines().rev().find(|line| !line.is_empty()) {
+                println!("  {}", line);
+            }
+            return;
+        }
+        Err(e) => {
+            println!("cargo を起動できませんでした: {}", e);
+            return;
+        }
+    };
+    match release_ms {
+        Some(release_ms) => {
+            println!("  debug  : {:>8.1} ms", debug_ms);
+            println!("  release: {:>8.1} ms", release_ms);
+            println!(
+                "  リリースビルドは {:.0} 倍速い",
+                debug_ms / release_ms.max(0.001)
+            );
+            println!("速さを測るときは必ず --release で（デバッグビルドの計測はあてにならない）");
+        }
+        None => println!("子プロセスの出力を読み取れませんでした"),
+    }
+}
+
+// ワークスペースの Cargo.toml（このクレートは chapters/build_system にある）
+const WORKSPACE_MANIFEST: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../Cargo.toml");
+
+/// チートシート用のデータ（`cheatsheet build_system` で表示）
+pub const CHEATSHEET: CheatSheet = CheatSheet {
+    syntax: &[
+        ("[features] default = [\"color\"]", "既定で有効な feature"),
+        (
+            "random = [\"dep:rand\"]",
+            "optional な依存を有効にする feature",
+        ),
+        (
+            "#[cfg(feature = \"color\")]",
+            "feature が有効なときだけコンパイルする",
+        ),
+        (
+            "if cfg!(feature = \"color\") { .. }",
+            "feature を bool 定数として使う",
+        ),
+        (
+            "include!(concat!(env!(\"OUT_DIR\"), \"/x.rs\"))",
+            "build.rs が生成したコードを取り込む",
+        ),
+        (
+            "println!(\"cargo::rustc-cfg=name\")",
+            "build.rs から独自の cfg を設定する",
+        ),
+    ],
+    methods: &[
+        ("cargo run --features random", "feature を有効にして実行"),
+        ("cargo build --no-default-features", "既定の feature を外す"),
+        (
+            "env::var(\"CARGO_FEATURE_COLOR\")",
+            "build.rs で feature を調べる",
+        ),
+        ("env::var(\"OUT_DIR\")", "生成したファイルを置く場所"),
+        (
+            "cargo tree -e features",
+            "どの feature がどこから有効になったか表示",
+        ),
+        ("cargo run --release", "最適化したリリースビルドで実行"),
+    ],
+    gotchas: &[
+        "feature は足し算。互いに排他的な feature を作らない",
+        "#[cfg] で外したコードはコンパイルされないので、エラーにも気付きにくい",
+        "build.rs は rerun-if-changed を書かないとパッケージのどのファイルが変わっても再実行される",
+        "生成したファイルは src/ ではなく OUT_DIR に書く",
+        "デバッグビルドで速さを測らない。比べるなら --release で",
+    ],
+};
+
+/// すべてのデモを実行
+pub fn run_all() {
+    println!("╔════════════════════════════════════════════════════════════════╗");
+    println!("║          Rust Cargo の feature とビルドスクリプトのサンプル      ║");
+    println!("╚════════════════════════════════════════════════════════════════╝");
+
+    lesson_api::run_topic("build_system");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::build_info;
+
+    #[test]
+    fn generated_code_matches_features() {
+        assert_eq!(build_info::PRIMES[..5], [2, 3, 5, 7, 11]);
+        assert_eq!(*build_info::PRIMES.last().unwrap(), 97);
+        assert_eq!(
+            build_info::FEATURES.contains(&"color"),
+            cfg!(feature = "color")
+        );
+        assert_eq!(
+            build_info::FEATURES.contains(&"random"),
+            cfg!(feature = "random")
+        );
+    }
+}
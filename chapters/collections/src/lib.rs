@@ -0,0 +1,1649 @@
+// ============================================================================
+// Rustコレクションサンプル
+// 公式ドキュメント: https://doc.rust-lang.org/book/ch08-00-common-collections.html
+// ============================================================================
+//
+// 主なコレクション:
+// - Vec<T>: 可変長の配列
+// - String: UTF-8エンコードされた可変長文字列
+// - HashMap<K, V>: キーと値のマッピング
+
+use lesson_api::random::Rng;
+use lesson_api::text::{display_width, group_digits, pad_left, pad_right};
+use lesson_api::timing::stopwatch;
+use lesson_api::{print, println, CheatSheet};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::fmt::Debug;
+use std::hash::{Hash, Hasher};
+use std::mem;
+
+/// ベクター（Vec<T>）の基本
+pub fn vector_basics() {
+    println!("\n=== ベクターの基本 ===");
+
+    // ベクターの作成
+    let v1: Vec<i32> = Vec::new(); // 空のベクター（型注釈が必要）
+    let v2 = vec![1, 2, 3]; // vec!マクロで初期化
+
+    println!("空のベクター: {:?}", v1);
+    println!("vec!マクロ: {:?}", v2);
+
+    // 要素の追加（mutが必要）
+    let mut v = Vec::new();
+    v.push(5);
+    v.push(6);
+    v.push(7);
+    v.push(8);
+    println!("pushで追加: {:?}", v);
+
+    // 要素へのアクセス
+    let third: &i32 = &v[2]; // インデックスアクセス（パニックの可能性）
+    println!("3番目の要素（インデックス）: {}", third);
+
+    let third: Option<&i32> = v.get(2); // getメソッド（安全）
+    match third {
+        Some(value) => println!("3番目の要素（get）: {}", value),
+        None => println!("3番目の要素はありません"),
+    }
+
+    // 範囲外アクセス
+    // let does_not_exist = &v[100]; // これはパニック!
+    let does_not_exist = v.get(100); // これはNoneを返す
+    println!("範囲外アクセス（get）: {:?}", does_not_exist);
+
+    // 要素の変更
+    let mut v = vec![100, 32, 57];
+    for i in &mut v {
+        *i += 50; // デリファレンスして値を変更
+    }
+    println!("各要素に50を加算: {:?}", v);
+}
+
+/// ベクターの操作
+pub fn vector_operations() {
+    println!("\n=== ベクターの操作 ===");
+
+    let mut v = vec![1, 2, 3, 4, 5];
+
+    // 最後の要素を取り出す
+    let last = v.pop();
+    println!("pop: {:?}, ベクター: {:?}", last, v);
+
+    // 特定位置に挿入
+    v.insert(0, 100);
+    println!("insert(0, 100): {:?}", v);
+
+    // 特定位置から削除
+    let removed = v.remove(0);
+    println!("remove(0): {}, ベクター: {:?}", removed, v);
+
+    // 長さと容量
+    println!("長さ: {}, 容量: {}", v.len(), v.capacity());
+
+    // ベクターのクリア
+    v.clear();
+    println!("clear後: {:?}, 空?: {}", v, v.is_empty());
+
+    // スライスとして使用
+    let v = vec![1, 2, 3, 4, 5];
+    let slice = &v[1..4];
+    println!("スライス [1..4]: {:?}", slice);
+
+    // ソート
+    let mut v = vec![5, 3, 1, 4, 2];
+    v.sort();
+    println!("ソート後: {:?}", v);
+
+    // 逆順
+    v.reverse();
+    println!("逆順: {:?}", v);
+
+    // 重複除去（ソート済みの場合）
+    let mut v = vec![1, 1, 2, 2, 3, 3];
+    v.dedup();
+    println!("重複除去後: {:?}", v);
+}
+
+/// ベクターでの反復処理
+pub fn vector_iteration() {
+    println!("\n=== ベクターでの反復処理 ===");
+
+    let v = vec![100, 32, 57];
+
+    // 不変参照での反復
+    println!("不変参照での反復:");
+    for i in &v {
+        println!("  {}", i);
+    }
+    println!("反復後もvは使用可能: {:?}", v);
+
+    // 可変参照での反復
+    let mut v = vec![100, 32, 57];
+    println!("可変参照での反復（2倍）:");
+    for i in &mut v {
+        *i *= 2;
+    }
+    println!("  結果: {:?}", v);
+
+    // インデックス付きの反復
+    println!("インデックス付き:");
+    for (index, value) in v.iter().enumerate() {
+        println!("  v[{}] = {}", index, value);
+    }
+}
+
+/// 異なる型を格納するベクター
+pub fn vector_with_enums() {
+    println!("\n=== 列挙型で異なる型を格納 ===");
+
+    // 列挙型を使えば異なる「型」の値を格納できる
+    #[derive(Debug)]
+    enum SpreadsheetCell {
+        Int(i32),
+        Float(f64),
+        Text(String),
+    }
+
+    let row = vec![
+        SpreadsheetCell::Int(3),
+        SpreadsheetCell::Text(String::from("blue")),
+        SpreadsheetCell::Float(10.12),
+    ];
+
+    println!("スプレッドシートの行: {:?}", row);
+
+    for cell in &row {
+        match cell {
+            SpreadsheetCell::Int(i) => println!("  整数: {}", i),
+            SpreadsheetCell::Float(f) => println!("  浮動小数点: {}", f),
+            SpreadsheetCell::Text(s) => println!("  テキスト: {}", s),
+        }
+    }
+}
+
+/// 文字列（String）の基本
+pub fn string_basics() {
+    println!("\n=== 文字列の基本 ===");
+
+    // 文字列の作成
+    let mut s = String::new(); // 空のString
+    println!("空のString: '{}'", s);
+
+    let s1 = "初期内容".to_string(); // &strからString
+    let s2 = String::from("初期内容"); // fromで作成
+    println!("to_string: '{}', from: '{}'", s1, s2);
+
+    // 文字列の追加
+    s.push_str("hello"); // 文字列スライスを追加
+    s.push(' '); // 単一文字を追加
+    s.push_str("world");
+    println!("push後: '{}'", s);
+
+    // +演算子での結合
+    let s1 = String::from("Hello, ");
+    let s2 = String::from("world!");
+    // s1はムーブされる、s2は借用
+    let s3 = s1 + &s2;
+    // println!("{}", s1); // エラー！s1はムーブ済み
+    println!("s2: '{}', s3: '{}'", s2, s3);
+
+    // format!マクロ（所有権を奪わない）
+    let s1 = String::from("tic");
+    let s2 = String::from("tac");
+    let s3 = String::from("toe");
+    let s = format!("{}-{}-{}", s1, s2, s3);
+    println!("format!: '{}'", s);
+    println!("s1, s2, s3はまだ使える: '{}', '{}', '{}'", s1, s2, s3);
+}
+
+/// 文字列のインデックスアクセス
+pub fn string_indexing() {
+    println!("\n=== 文字列のインデックスアクセス ===");
+
+    // Rustの文字列はUTF-8エンコード
+    // 直接インデックスアクセスはできない
+
+    let hello = "Здравствуйте"; // ロシア語
+    println!("ロシア語: {}", hello);
+    println!("バイト長: {} bytes", hello.len());
+
+    // let s = &hello[0]; // エラー！直接インデックスは不可
+
+    // スライスは可能だが注意が必要
+    let s = &hello[0..4]; // 最初の2文字（各2バイト）
+    println!("最初の2文字: {}", s);
+    // let s = &hello[0..1]; // パニック！文字の途中でスライス
+
+    // 安全な方法: chars()やbytes()を使う
+    println!("文字単位での反復:");
+    for c in hello.chars() {
+        print!("{} ", c);
+    }
+    println!();
+
+    println!("バイト単位での反復:");
+    for b in hello.bytes() {
+        print!("{} ", b);
+    }
+    println!();
+
+    // 日本語の例
+    let japanese = "こんにちは";
+    println!("\n日本語: {}", japanese);
+    println!("バイト長: {} bytes", japanese.len());
+    println!("文字数: {} 文字", japanese.chars().count());
+}
+
+/// 文字列の操作
+pub fn string_operations() {
+    println!("\n=== 文字列の操作 ===");
+
+    let s = String::from("  hello world  ");
+
+    // トリム
+    println!("トリム: '{}'", s.trim());
+
+    // 置換
+    let s = String::from("hello");
+    println!("置換: '{}'", s.replace("l", "L"));
+
+    // 分割
+    let s = "one,two,three";
+    println!("分割:");
+    for part in s.split(',') {
+        println!("  '{}'", part);
+    }
+
+    // 含むかどうか
+    let s = "Hello, World!";
+    println!("'World'を含む: {}", s.contains("World"));
+    println!("'Hello'で始まる: {}", s.starts_with("Hello"));
+    println!("'!'で終わる: {}", s.ends_with("!"));
+
+    // 大文字・小文字変換
+    println!("小文字: '{}'", s.to_lowercase());
+    println!("大文字: '{}'", s.to_uppercase());
+
+    // 行単位での反復
+    let multiline = "line1\nline2\nline3";
+    println!("行単位:");
+    for line in multiline.lines() {
+        println!("  '{}'", line);
+    }
+}
+
+type BuildString = fn(&[String]) -> String;
+
+// 文字列を組み立てる5つの方法（どれも同じ結果になる）
+const STRING_BUILDERS: [(&str, BuildString); 5] = [
+    ("s = s + piece", build_with_plus),
+    ("s.push_str(piece)", build_with_push_str),
+    ("with_capacity + push_str", build_with_capacity),
+    ("s = format!(\"{}{}\", s, piece)", build_with_format),
+    ("pieces.join(\"\")", build_with_join),
+];
+
+// + は左辺の String をムーブして、そのバッファの後ろに足す（コピーは右辺だけ）
+fn build_with_plus(pieces: &[String]) -> String {
+    let mut s = String::new();
+    for piece in pieces {
+        s = s + piece;
+    }
+    s
+}
+
+// 足りなくなるとバッファを2倍にするので、確保は数十回で済む
+fn build_with_push_str(pieces: &[String]) -> String {
+    let mut s = String::new();
+    for piece in pieces {
+        s.push_str(piece);
+    }
+    s
+}
+
+// 最終的な長さが分かるなら、最初に1回だけ確保する
+fn build_with_capacity(pieces: &[String]) -> String {
+    let total = pieces.iter().map(|piece| piece.len()).sum();
+    let mut s = String::with_capacity(total);
+    for piece in pieces {
+        s.push_str(piece);
+    }
+    s
+}
+
+// 毎回新しい String を作り、それまでの中身を丸ごとコピーする（長さの2乗に比例）
+fn build_with_format(pieces: &[String]) -> String {
+    let mut s = String::new();
+    for piece in pieces {
+        s = format!("{}{}", s, piece);
+    }
+    s
+}
+
+// join は先に合計の長さを数えてから1回で確保する
+fn build_with_join(pieces: &[String]) -> String {
+    pieces.join("")
+}
+
+/// 文字列の組み立て方の速度比較
+pub fn string_building_timing() {
+    println!("\n=== 文字列の組み立て方の速度比較 ===");
+
+    let pieces: Vec<String> = (0..5000).map(|i| format!("item{},", i)).collect();
+    let expected = build_with_join(&pieces);
+    println!(
+        "{}個の断片から {} バイトの文字列を作る:",
+        pieces.len(),
+        expected.len()
+    );
+
+    println!("  方法{}時間 確保回数", " ".repeat(39));
+    for (name, build) in STRING_BUILDERS {
+        let ((s, stats), time) = stopwatch(|| lesson_api::alloc_count::measure(|| build(&pieces)));
+        assert_eq!(s, expected, "{} の結果が正しくありません", name);
+        println!("  {:<34} {:>12} {:>8}", name, time, stats.allocations);
+    }
+
+    println!("ループで足していくなら push_str（+ も同じ）。長さが分かれば with_capacity");
+    println!("すでに Vec<String> があるなら join。format! で自分自身を作り直すのは避ける");
+    println!("※ cargo run --release で実行すると差がよりはっきりします");
+}
+
+// ----------------------------------------------------------------------------
+// Unicode: 結合文字、正規化、書記素クラスタ
+// ----------------------------------------------------------------------------
+//
+// char は Unicode のコードポイント1つ。人が「1文字」と思う単位（書記素クラスタ）とは限らない。
+//   - 「が」は U+304C 1つでも、「か」+ 結合用の濁点 U+3099 の2つでも書ける（見た目は同じ、== は false）
+//   - 👨‍👩‍👧‍👦 は 👨 👩 👧 👦 を ZWJ（U+200D）でつないだ7つのコードポイント
+// 本格的に扱うなら unicode-segmentation / unicode-normalization クレートを使う。
+// ここでは仕組みが分かる程度の小さな版を書く。
+
+// 前の文字にくっつく文字（結合用の記号、濁点、異体字セレクタ、肌の色、ZWJ など）
+fn is_extend(c: char) -> bool {
+    matches!(c as u32,
+        0x0300..=0x036F | 0x1AB0..=0x1AFF | 0x1DC0..=0x1DFF | 0x20D0..=0x20FF | 0xFE20..=0xFE2F
+        | 0x3099..=0x309A | 0xFF9E..=0xFF9F // 結合用の濁点・半濁点、半角の濁点・半濁点
+        | 0xFE00..=0xFE0F | 0xE0100..=0xE01EF // 異体字セレクタ
+        | 0x1F3FB..=0x1F3FF // 肌の色
+        | 0xE0020..=0xE007F // タグ文字（地域の旗）
+        | 0x200D) // ZWJ
+}
+
+// 絵文字（ZWJ の後ろにつながるもの。簡略版）
+fn is_pictographic(c: char) -> bool {
+    matches!(c as u32, 0x2600..=0x27BF | 0x1F000..=0x1FAFF)
+}
+
+// 地域指示子（2つ組で国旗になる）
+fn is_regional_indicator(c: char) -> bool {
+    matches!(c as u32, 0x1F1E6..=0x1F1FF)
+}
+
+/// 書記素クラスタ（見た目の1文字）に分ける。UAX #29 の一部だけの簡略版
+///
+/// CR LF、結合文字、ZWJ でつないだ絵文字、地域指示子の2つ組を1つにまとめる。
+/// ハングルの字母の組み合わせなどは扱わない。
+pub fn graphemes(s: &str) -> Vec<&str> {
+    let mut clusters = Vec::new();
+    let mut start = 0;
+    let mut prev: Option<char> = None;
+    // 直前まで続いている地域指示子の数（奇数なら次の1つと組になる）
+    let mut regional_run = 0;
+
+    for (i, c) in s.char_indices() {
+        if let Some(p) = prev {
+            let join = (p == '\r' && c == '\n')
+                || is_extend(c)
+                || (p == '\u{200D}' && is_pictographic(c))
+                || (is_regional_indicator(c) && regional_run % 2 == 1);
+            if !join {
+                clusters.push(&s[start..i]);
+                start = i;
+            }
+        }
+        regional_run = if is_regional_indicator(c) {
+            regional_run + 1
+        } else {
+            0
+        };
+        prev = Some(c);
+    }
+    if start < s.len() {
+        clusters.push(&s[start..]);
+    }
+    clusters
+}
+
+// 基底の文字と結合文字の組を、合成済みの1文字にする
+fn compose_pair(base: char, mark: char) -> Option<char> {
+    let b = base as u32;
+    // カタカナはひらがなの 0x60 後ろに同じ並びで置かれている
+    let katakana = (0x30A1..=0x30FA).contains(&b);
+    let kana = if katakana { b - 0x60 } else { b };
+    // 濁点は +1、半濁点は +2 の位置にある（か→が、は→ば→ぱ）
+    let offset = match (kana, mark) {
+        (0x304B..=0x3061, '\u{3099}') if (kana - 0x304B) % 2 == 0 => 1, // か〜ち
+        (0x3064 | 0x3066 | 0x3068, '\u{3099}') => 1,                    // つ て と
+        (0x306F..=0x307B, '\u{3099}') if (kana - 0x306F) % 3 == 0 => 1, // は〜ほ
+        (0x306F..=0x307B, '\u{309A}') if (kana - 0x306F) % 3 == 0 => 2,
+        (0x3046, '\u{3099}') => 0x3094 - 0x3046, // う → ゔ
+        _ => {
+            // ラテン文字はよく使うものだけ
+            return match (base, mark) {
+                ('e', '\u{301}') => Some('é'),
+                ('e', '\u{300}') => Some('è'),
+                ('a', '\u{300}') => Some('à'),
+                ('n', '\u{303}') => Some('ñ'),
+                ('o', '\u{308}') => Some('ö'),
+                ('u', '\u{308}') => Some('ü'),
+                _ => None,
+            };
+        }
+    };
+    char::from_u32(b + offset)
+}
+
+/// 結合文字を合成済みの文字にまとめる（NFC のごく一部: かなの濁点・半濁点と一部のラテン文字）
+pub fn compose(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        match chars.peek().and_then(|&next| compose_pair(c, next)) {
+            Some(composed) => {
+                out.push(composed);
+                chars.next();
+            }
+            None => out.push(c),
+        }
+    }
+    out
+}
+
+// "U+304B U+3099" の形で表示する
+fn code_points(s: &str) -> String {
+    let points: Vec<String> = s.chars().map(|c| format!("U+{:04X}", c as u32)).collect();
+    points.join(" ")
+}
+
+/// 結合文字と正規化
+pub fn combining_characters() {
+    println!("\n=== 結合文字と正規化 ===");
+
+    // 見た目は同じでも、コードポイントの並びが違う
+    for (composed, decomposed) in [
+        ("が", "か\u{3099}"),
+        ("パン", "ハ\u{309A}ン"),
+        ("é", "e\u{301}"),
+    ] {
+        println!("{:?} と {:?}", composed, decomposed);
+        println!(
+            "  合成済み: {}（{} バイト、{} 文字）",
+            code_points(composed),
+            composed.len(),
+            composed.chars().count()
+        );
+        println!(
+            "  分解済み: {}（{} バイト、{} 文字）",
+            code_points(decomposed),
+            decomposed.len(),
+            decomposed.chars().count()
+        );
+        println!(
+            "  == で比べる: {}、compose してから比べる: {}",
+            composed == decomposed,
+            compose(decomposed) == composed
+        );
+    }
+
+    // 検索も、バイト列で比べるので見つからない
+    let typed = "がっこう";
+    let from_file = "か\u{3099}っこう"; // macOS のファイル名などは分解済み（NFD）で来ることがある
+    println!(
+        "\n{:?}.contains({:?}) = {}、compose 後 = {}",
+        from_file,
+        typed,
+        from_file.contains(typed),
+        compose(from_file).contains(typed)
+    );
+
+    println!("\n正規化の形式:");
+    println!("  NFC: できるだけ合成する（Web やふつうのテキストはこれ）");
+    println!("  NFD: すべて分解する");
+    println!("  NFKC / NFKD: 互換文字もまとめる（半角の \"ｶﾞ\" → \"ガ\"、\"①\" → \"1\"）");
+    println!("比べる・検索する・キーにする前に、どれか1つにそろえておく");
+}
+
+/// バイト・char・書記素クラスタ
+pub fn grapheme_clusters() {
+    println!("\n=== バイト・char・書記素クラスタ ===");
+
+    let samples = [
+        ("hello", "ASCII"),
+        ("こんにちは", "ひらがな"),
+        ("か\u{3099}き\u{3099}", "結合用の濁点"),
+        ("e\u{301}te\u{301}", "結合用のアクセント"),
+        ("👍🏽", "肌の色"),
+        ("👨\u{200D}👩\u{200D}👧\u{200D}👦", "ZWJ でつないだ家族"),
+        ("🇯🇵🇺🇸", "国旗2つ"),
+        ("1\u{FE0F}\u{20E3}", "キーキャップ"),
+    ];
+    println!(
+        "  {} {} {} {}",
+        pad_right("文字列", 14),
+        pad_left("len()", 6),
+        pad_left("chars", 6),
+        pad_left("書記素", 7)
+    );
+    for (s, note) in samples {
+        println!(
+            "  {} {:>6} {:>6} {:>7}  {}",
+            pad_right(s, 14),
+            s.len(),
+            s.chars().count(),
+            graphemes(s).len(),
+            note
+        );
+    }
+
+    // 分け方を並べる
+    println!("\n書記素クラスタに分けた結果:");
+    for s in ["か\u{3099}っこう", "🇯🇵🇺🇸", "OK👍🏽!"] {
+        let clusters: Vec<String> = graphemes(s).iter().map(|g| format!("[{}]", g)).collect();
+        println!("  {} → {}", s, clusters.concat());
+    }
+
+    // char 単位で逆順にしたり切り詰めたりすると、見た目の文字が壊れる
+    let text = "か\u{3099}👍🏽";
+    let by_chars: String = text.chars().rev().collect();
+    let by_graphemes: String = graphemes(text).into_iter().rev().collect();
+    println!("\n{:?} を逆順に:", text);
+    println!(
+        "  chars().rev()    → {}（{}）",
+        by_chars,
+        code_points(&by_chars)
+    );
+    println!("  書記素で逆順     → {}", by_graphemes);
+
+    let family = "👨\u{200D}👩\u{200D}👧\u{200D}👦";
+    let first_chars: String = family.chars().take(2).collect();
+    println!("{} の先頭2つ:", family);
+    println!(
+        "  chars().take(2)  → {}（👨 と ZWJ だけが残る）",
+        first_chars
+    );
+    println!("  書記素の先頭     → {}", graphemes(family)[0]);
+
+    println!("\nlen() はバイト数、chars().count() はコードポイント数。どちらも「見た目の文字数」ではない");
+    println!("文字数の上限や切り詰めは書記素クラスタで数える（端末の表示幅はさらに別: lesson_api::text）");
+}
+
+// ----------------------------------------------------------------------------
+// ロケールによる書式の違い（数値、金額、全角と半角、日付）
+// ----------------------------------------------------------------------------
+//
+// 表示幅や桁区切りの関数は lesson_api::text にあり、メニューやチートシートの表でも使っている。
+
+/// 通貨ごとの金額の書き方
+pub struct Currency {
+    pub symbol: &'static str,
+    /// 補助単位の桁数（円は 0、ドルやユーロは 2）
+    pub decimals: u32,
+    pub group: char,
+    pub decimal: char,
+    /// 記号を数字の後ろに置くか（"12,34 €" や "100円"）
+    pub symbol_after: bool,
+}
+
+pub const YEN: Currency = Currency {
+    symbol: "円",
+    decimals: 0,
+    group: ',',
+    decimal: '.',
+    symbol_after: true,
+};
+pub const US_DOLLAR: Currency = Currency {
+    symbol: "$",
+    decimals: 2,
+    group: ',',
+    decimal: '.',
+    symbol_after: false,
+};
+pub const EURO_DE: Currency = Currency {
+    symbol: " €",
+    decimals: 2,
+    group: '.',
+    decimal: ',',
+    symbol_after: true,
+};
+
+/// 補助単位（セントなど）の整数で持った金額を書式化する
+/// 金額を f64 で持つと 0.1 + 0.2 のような誤差が出るので、整数で数えるのが定石
+pub fn format_money(minor: i64, currency: &Currency) -> String {
+    let unit = 10i64.pow(currency.decimals);
+    let mut number = group_digits(minor / unit, 3, currency.group);
+    if minor < 0 && minor / unit == 0 {
+        number.insert(0, '-');
+    }
+    if currency.decimals > 0 {
+        let fraction = (minor % unit).unsigned_abs();
+        number.push(currency.decimal);
+        number.push_str(&format!(
+            "{:0width$}",
+            fraction,
+            width = currency.decimals as usize
+        ));
+    }
+    if currency.symbol_after {
+        format!("{}{}", number, currency.symbol)
+    } else if let Some(digits) = number.strip_prefix('-') {
+        format!("-{}{}", currency.symbol, digits)
+    } else {
+        format!("{}{}", currency.symbol, number)
+    }
+}
+
+/// 万・億・兆の単位で書く（123456789 → "1億2345万6789"）
+pub fn japanese_units(mut n: u64) -> String {
+    if n == 0 {
+        return "0".to_string();
+    }
+    let mut parts = Vec::new();
+    for unit in ["", "万", "億", "兆", "京"] {
+        let chunk = n % 10_000;
+        if chunk > 0 {
+            parts.push(format!("{}{}", chunk, unit));
+        }
+        n /= 10_000;
+        if n == 0 {
+            break;
+        }
+    }
+    parts.reverse();
+    parts.concat()
+}
+
+/// 曜日（0 = 日曜）。Sakamoto の方法（グレゴリオ暦）
+pub fn weekday(year: i32, month: u32, day: u32) -> usize {
+    const OFFSETS: [i32; 12] = [0, 3, 2, 5, 0, 3, 5, 1, 4, 6, 2, 4];
+    let y = if month < 3 { year - 1 } else { year };
+    (y + y / 4 - y / 100 + y / 400 + OFFSETS[month as usize - 1] + day as i32).rem_euclid(7)
+        as usize
+}
+
+/// ロケールによる書式の違い
+pub fn locale_formatting() {
+    println!("\n=== ロケールによる書式の違い ===");
+
+    println!("-- 数値の区切り --");
+    let n = 123_456_789;
+    println!("  {{}} のまま:           {}", n);
+    println!("  3桁区切り（en / ja）: {}", group_digits(n, 3, ','));
+    println!("  3桁区切り（de）:      {}", group_digits(n, 3, '.'));
+    println!("  万・億の単位:         {}", japanese_units(n as u64));
+    println!("  std の {{}} には桁区切りがないので、自分で挿入する（ロケール対応が要るなら ICU 系のクレート）");
+
+    println!("\n-- 金額（補助単位の整数で持つ） --");
+    for (name, minor, currency) in [
+        ("日本円", 1_234_567, &YEN),
+        ("米ドル", 1_234_567, &US_DOLLAR),
+        ("ユーロ（ドイツ）", 1_234_567, &EURO_DE),
+        ("米ドル（マイナス）", -5, &US_DOLLAR),
+    ] {
+        println!(
+            "  {} {}",
+            pad_right(name, 18),
+            format_money(minor, currency)
+        );
+    }
+    println!("  0.1 + 0.2 = {}（f64 の金額計算は誤差が出る）", 0.1 + 0.2);
+
+    println!("\n-- 全角と半角の幅 --");
+    let rows = [
+        ("所有権", 4),
+        ("Box<T>", 15),
+        ("ライフタイム", 10),
+        ("ｶﾀｶﾅ（半角）", 8),
+    ];
+    println!("  format! の {{:<14}}（文字数で数える）:");
+    for (name, chapter) in rows {
+        println!("    |{:<14}|{:>4}|", name, chapter);
+    }
+    println!("  text::pad_right（表示幅で数える）:");
+    for (name, chapter) in rows {
+        println!(
+            "    |{}|{}|",
+            pad_right(name, 14),
+            pad_left(&chapter.to_string(), 4)
+        );
+    }
+    for s in ["Rust", "所有権", "ＡＢＣ", "ｶﾀｶﾅ"] {
+        println!(
+            "  {:?}: {} 文字、{} バイト、表示幅 {}",
+            s,
+            s.chars().count(),
+            s.len(),
+            display_width(s)
+        );
+    }
+
+    println!("\n-- 日付 --");
+    let (y, m, d) = (2024, 3, 5);
+    let weekday_ja = ["日", "月", "火", "水", "木", "金", "土"][weekday(y, m, d)];
+    let weekday_en = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"][weekday(y, m, d)];
+    println!("  ISO 8601: {:04}-{:02}-{:02}", y, m, d);
+    println!("  米国:     {}/{}/{}（{}）", m, d, y, weekday_en);
+    println!("  英国:     {:02}/{:02}/{}", d, m, y);
+    println!("  ドイツ:   {:02}.{:02}.{}", d, m, y);
+    println!("  日本:     {}年{}月{}日（{}）", y, m, d, weekday_ja);
+    println!("  和暦:     令和{}年{}月{}日", y - 2018, m, d);
+    println!("  03/05/2024 は米国では3月5日、英国では5月3日。やり取りには ISO 8601 を使う");
+}
+
+/// HashMap（ハッシュマップ）の基本
+pub fn hashmap_basics() {
+    println!("\n=== HashMapの基本 ===");
+
+    // HashMapの作成
+    let mut scores: HashMap<String, i32> = HashMap::new();
+
+    // 要素の挿入
+    scores.insert(String::from("Blue"), 10);
+    scores.insert(String::from("Yellow"), 50);
+
+    println!("scores: {:?}", scores);
+
+    // ベクターからHashMapを作成
+    let teams = vec![String::from("Blue"), String::from("Yellow")];
+    let initial_scores = vec![10, 50];
+
+    let scores: HashMap<_, _> = teams.into_iter().zip(initial_scores.into_iter()).collect();
+
+    println!("collectで作成: {:?}", scores);
+
+    // 値へのアクセス
+    let team_name = String::from("Blue");
+    let score = scores.get(&team_name);
+    println!("Blueのスコア: {:?}", score);
+
+    // getは Option<&V> を返す
+    match scores.get("Blue") {
+        Some(score) => println!("Blueのスコア: {}", score),
+        None => println!("Blueのスコアなし"),
+    }
+
+    // copiedとunwrap_orでデフォルト値
+    let score = scores.get("Blue").copied().unwrap_or(0);
+    println!("Blueのスコア（デフォルト付き）: {}", score);
+
+    // キーが存在しない場合
+    let score = scores.get("Red").copied().unwrap_or(0);
+    println!("Redのスコア（デフォルト付き）: {}", score);
+}
+
+/// HashMapの反復処理
+pub fn hashmap_iteration() {
+    println!("\n=== HashMapの反復処理 ===");
+
+    let mut scores = HashMap::new();
+    scores.insert(String::from("Blue"), 10);
+    scores.insert(String::from("Yellow"), 50);
+    scores.insert(String::from("Red"), 30);
+
+    // キーと値のペアで反復
+    println!("全エントリー:");
+    for (key, value) in &scores {
+        println!("  {}: {}", key, value);
+    }
+
+    // キーのみ
+    println!("キーのみ: {:?}", scores.keys().collect::<Vec<_>>());
+
+    // 値のみ
+    println!("値のみ: {:?}", scores.values().collect::<Vec<_>>());
+}
+
+/// HashMapの更新
+pub fn hashmap_updating() {
+    println!("\n=== HashMapの更新 ===");
+
+    let mut scores = HashMap::new();
+
+    // 値の上書き
+    scores.insert(String::from("Blue"), 10);
+    scores.insert(String::from("Blue"), 25); // 上書き
+    println!("上書き後: {:?}", scores);
+
+    // キーが存在しない場合のみ挿入
+    scores.entry(String::from("Yellow")).or_insert(50);
+    scores.entry(String::from("Blue")).or_insert(50); // Blueは既存なので挿入されない
+    println!("entry().or_insert()後: {:?}", scores);
+
+    // 古い値に基づいて更新
+    let text = "hello world wonderful world";
+    let mut map = HashMap::new();
+
+    for word in text.split_whitespace() {
+        let count = map.entry(word).or_insert(0);
+        *count += 1;
+    }
+    println!("単語カウント: {:?}", map);
+}
+
+/// HashMapと所有権
+pub fn hashmap_ownership() {
+    println!("\n=== HashMapと所有権 ===");
+
+    // Copy トレイトを実装している型（i32など）はコピーされる
+    let field_name = String::from("Favorite color");
+    let field_value = String::from("Blue");
+
+    let mut map = HashMap::new();
+    map.insert(field_name, field_value);
+
+    // println!("{}", field_name); // エラー！field_nameはムーブ済み
+
+    // 参照を挿入すれば所有権は移動しない
+    let key = String::from("key");
+    let value = String::from("value");
+
+    let mut map: HashMap<&String, &String> = HashMap::new();
+    map.insert(&key, &value);
+
+    println!("参照を使用: key = '{}', value = '{}'", key, value);
+    println!("map: {:?}", map);
+}
+
+/// その他のコレクション
+pub fn other_collections() {
+    println!("\n=== その他のコレクション ===");
+
+    // VecDeque - 両端キュー
+    use std::collections::VecDeque;
+    let mut deque = VecDeque::new();
+    deque.push_back(1);
+    deque.push_back(2);
+    deque.push_front(0);
+    println!("VecDeque: {:?}", deque);
+    println!("  pop_front: {:?}", deque.pop_front());
+    println!("  pop_back: {:?}", deque.pop_back());
+
+    // HashSet - 重複なしの集合
+    use std::collections::HashSet;
+    let mut set: HashSet<i32> = HashSet::new();
+    set.insert(1);
+    set.insert(2);
+    set.insert(2); // 重複は無視される
+    println!("HashSet: {:?}", set);
+    println!("  2を含む: {}", set.contains(&2));
+
+    // 集合演算
+    let set_a: HashSet<i32> = [1, 2, 3].iter().cloned().collect();
+    let set_b: HashSet<i32> = [2, 3, 4].iter().cloned().collect();
+    println!("set_a: {:?}", set_a);
+    println!("set_b: {:?}", set_b);
+    println!("  和集合: {:?}", set_a.union(&set_b).collect::<Vec<_>>());
+    println!(
+        "  積集合: {:?}",
+        set_a.intersection(&set_b).collect::<Vec<_>>()
+    );
+    println!(
+        "  差集合(a-b): {:?}",
+        set_a.difference(&set_b).collect::<Vec<_>>()
+    );
+
+    // 順序付きの BTreeMap は「HashMap と BTreeMap の比較」で扱う
+}
+
+/// HashMap と BTreeMap の比較
+pub fn hashmap_vs_btreemap() {
+    println!("\n=== HashMap と BTreeMap の比較 ===");
+
+    // BTreeMap はキーの順に並ぶ（HashMap の順序は実行するたびに変わる）
+    let mut btree = BTreeMap::new();
+    btree.insert("c", 3);
+    btree.insert("a", 1);
+    btree.insert("b", 2);
+    println!("BTreeMap（キー順）: {:?}", btree);
+
+    // 同じキーを両方に入れて、挿入と検索の時間を比べる
+    println!("\n同じ乱数のキーで insert と get（すべてのキー）:");
+    println!(
+        "  {:>7}   HashMap挿入  BTreeMap挿入   HashMap検索  BTreeMap検索",
+        "要素数"
+    );
+    let mut rng = Rng::new(42);
+    let mut last = None;
+    for n in [100, 1_000, 10_000, 100_000] {
+        let keys: Vec<u64> = (0..n).map(|_| rng.next_u64()).collect();
+
+        let ((hash, hash_stats), hash_insert) = stopwatch(|| {
+            lesson_api::alloc_count::measure(|| {
+                let mut map = HashMap::new();
+                for &key in &keys {
+                    map.insert(key, key);
+                }
+                map
+            })
+        });
+        let ((tree, tree_stats), tree_insert) = stopwatch(|| {
+            lesson_api::alloc_count::measure(|| {
+                let mut map = BTreeMap::new();
+                for &key in &keys {
+                    map.insert(key, key);
+                }
+                map
+            })
+        });
+        let (hash_sum, hash_get) = stopwatch(|| {
+            keys.iter()
+                .map(|key| hash[key])
+                .fold(0u64, u64::wrapping_add)
+        });
+        let (tree_sum, tree_get) = stopwatch(|| {
+            keys.iter()
+                .map(|key| tree[key])
+                .fold(0u64, u64::wrapping_add)
+        });
+        assert_eq!(hash_sum, tree_sum);
+
+        println!(
+            "  {:>7} {:>13} {:>13} {:>13} {:>13}",
+            n, hash_insert, tree_insert, hash_get, tree_get
+        );
+        last = Some((n, hash_stats, tree_stats));
+    }
+
+    // メモリの使い方の違い: HashMap は1つの大きな表を倍々に作り直し、
+    // BTreeMap は最大11個のキーを持つノードを少しずつ確保する
+    if let Some((n, hash_stats, tree_stats)) = last {
+        if lesson_api::alloc_count::is_installed() {
+            println!("\n{}要素を入れたときの確保:", n);
+            println!(
+                "  HashMap : {:>5}回、合計 {:>6} KB（表を作り直すたびに大きく確保）",
+                hash_stats.allocations,
+                hash_stats.bytes / 1024
+            );
+            println!(
+                "  BTreeMap: {:>5}回、合計 {:>6} KB（ノードごとに小さく確保）",
+                tree_stats.allocations,
+                tree_stats.bytes / 1024
+            );
+        }
+    }
+
+    // 順序があると、範囲の検索や最小・最大がすぐに分かる
+    let scores: BTreeMap<u32, &str> = [
+        (72, "佐藤"),
+        (95, "鈴木"),
+        (64, "高橋"),
+        (88, "田中"),
+        (80, "伊藤"),
+    ]
+    .into_iter()
+    .collect();
+    println!(
+        "\n80点以上（range）: {:?}",
+        scores.range(80..).collect::<Vec<_>>()
+    );
+    println!(
+        "最低点と最高点: {:?} / {:?}",
+        scores.first_key_value(),
+        scores.last_key_value()
+    );
+
+    // HashMap で同じことをするには、毎回すべてを取り出して並べ替える
+    let hash_scores: HashMap<u32, &str> = scores.iter().map(|(&k, &v)| (k, v)).collect();
+    let mut sorted: Vec<_> = hash_scores.iter().filter(|(&k, _)| k >= 80).collect();
+    sorted.sort();
+    println!("HashMap では filter して sort: {:?}", sorted);
+
+    println!("\nHashMap: 検索と挿入が平均 O(1)。順序が要らないならこちら");
+    println!("         （デバッグビルドではハッシュの計算が遅く、BTreeMap に負けることもある）");
+    println!("BTreeMap: O(log n) だが、キー順の反復・range・最小／最大が使える");
+    println!("          出力を毎回同じ順にしたいとき（テストやファイル出力）にも向いている");
+    println!("※ cargo run --release で実行すると差がよりはっきりします");
+}
+
+// ----------------------------------------------------------------------------
+// 自作のハッシュマップ（オープンアドレス法）
+// ----------------------------------------------------------------------------
+
+/// 表の1マス
+enum Slot<K, V> {
+    /// 一度も使われていない（探索はここで止まる）
+    Empty,
+    /// 削除済みの印（墓石）。探索は止めずに先へ進む
+    Tombstone,
+    Full(K, V),
+}
+
+/// オープンアドレス法（線形探索）の最小限のハッシュマップ
+///
+/// キーのハッシュ値から表の位置を決め、埋まっていたら隣のマスへ進む。
+/// std の HashMap も、もっと工夫された同じ仕組み（SwissTable）で動いている。
+pub struct OpenMap<K, V> {
+    slots: Vec<Slot<K, V>>,
+    len: usize,
+    tombstones: usize,
+}
+
+impl<K: Hash + Eq, V> OpenMap<K, V> {
+    pub fn new() -> Self {
+        OpenMap {
+            slots: Vec::new(),
+            len: 0,
+            tombstones: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// 表のマスの数（2のべき乗）
+    pub fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    // DefaultHasher::new() は毎回同じ鍵の SipHash（std の HashMap は起動ごとに鍵を変える）
+    fn hash(key: &K) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// キーが最初に入るはずのマス
+    pub fn home_slot(&self, key: &K) -> Option<usize> {
+        let mask = self.slots.len().checked_sub(1)?;
+        Some(Self::hash(key) as usize & mask)
+    }
+
+    /// キーのあるマス（Ok）か、無ければ入れるべきマス（Err）
+    /// 途中に墓石があれば、そこを再利用する
+    fn find(&self, key: &K) -> Result<usize, usize> {
+        let mask = self.slots.len() - 1;
+        let mut i = Self::hash(key) as usize & mask;
+        let mut first_tombstone = None;
+        // 表は 3/4 までしか埋めないので、必ずどこかで Empty に出会う
+        loop {
+            match &self.slots[i] {
+                Slot::Empty => return Err(first_tombstone.unwrap_or(i)),
+                Slot::Tombstone => {
+                    first_tombstone.get_or_insert(i);
+                }
+                Slot::Full(k, _) if k == key => return Ok(i),
+                Slot::Full(..) => {}
+            }
+            i = (i + 1) & mask;
+        }
+    }
+
+    /// 値を入れる。同じキーがあれば古い値を返す
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        // 墓石も「埋まっている」と数える（多すぎると探索が長くなる）
+        if (self.len + self.tombstones + 1) * 4 > self.slots.len() * 3 {
+            self.resize();
+        }
+        match self.find(&key) {
+            Ok(i) => match &mut self.slots[i] {
+                Slot::Full(_, old) => Some(mem::replace(old, value)),
+                _ => unreachable!(),
+            },
+            Err(i) => {
+                if let Slot::Tombstone = self.slots[i] {
+                    self.tombstones -= 1;
+                }
+                self.slots[i] = Slot::Full(key, value);
+                self.len += 1;
+                None
+            }
+        }
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        if self.slots.is_empty() {
+            return None;
+        }
+        match self.find(key) {
+            Ok(i) => match &self.slots[i] {
+                Slot::Full(_, value) => Some(value),
+                _ => unreachable!(),
+            },
+            Err(_) => None,
+        }
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// 取り除いた値を返す。マスは Empty ではなく墓石にする
+    /// （Empty にすると、その先に押し出されたキーが見つからなくなる）
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        if self.slots.is_empty() {
+            return None;
+        }
+        let i = self.find(key).ok()?;
+        self.len -= 1;
+        self.tombstones += 1;
+        match mem::replace(&mut self.slots[i], Slot::Tombstone) {
+            Slot::Full(_, value) => Some(value),
+            _ => unreachable!(),
+        }
+    }
+
+    /// 表を作り直す。要素が多ければ2倍に、墓石が多いだけなら同じ大きさで墓石を掃除する
+    fn resize(&mut self) {
+        let capacity = if (self.len + 1) * 2 > self.slots.len() {
+            (self.slots.len() * 2).max(8)
+        } else {
+            self.slots.len()
+        };
+        let old = mem::replace(
+            &mut self.slots,
+            (0..capacity).map(|_| Slot::Empty).collect(),
+        );
+        self.len = 0;
+        self.tombstones = 0;
+        // ハッシュ値は変わらないが、マスの数が変わると入る位置が変わるので入れ直す
+        for slot in old {
+            if let Slot::Full(key, value) = slot {
+                if let Err(i) = self.find(&key) {
+                    self.slots[i] = Slot::Full(key, value);
+                    self.len += 1;
+                }
+            }
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.slots.iter().filter_map(|slot| match slot {
+            Slot::Full(key, value) => Some((key, value)),
+            _ => None,
+        })
+    }
+}
+
+impl<K: Hash + Eq + Debug, V: Debug> OpenMap<K, V> {
+    /// 表の中身を1行で表示する（_ は空、† は墓石）
+    pub fn layout(&self) -> String {
+        let cells: Vec<String> = self
+            .slots
+            .iter()
+            .map(|slot| match slot {
+                Slot::Empty => "_".to_string(),
+                Slot::Tombstone => "†".to_string(),
+                Slot::Full(key, value) => format!("{:?}={:?}", key, value),
+            })
+            .collect();
+        format!("[{}]", cells.join(", "))
+    }
+}
+
+impl<K: Hash + Eq, V> Default for OpenMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// ハッシュマップを自作する
+pub fn build_your_own_hashmap() {
+    println!("\n=== ハッシュマップを自作する（オープンアドレス法） ===");
+
+    // 衝突の例に使う候補（map より先に作り、map がここから借用できるようにする）
+    let candidates: Vec<String> = (1..=64).map(|i| format!("key{}", i)).collect();
+
+    let mut map = OpenMap::new();
+    for (fruit, price) in [("apple", 120), ("banana", 80), ("cherry", 300)] {
+        map.insert(fruit, price);
+    }
+    println!("マスの数: {}、要素数: {}", map.capacity(), map.len());
+
+    // 1. ハッシュ値の下位ビットで最初のマスを決める（マスの数は2のべき乗）
+    for fruit in ["apple", "banana", "cherry"] {
+        println!("  {:<8} → マス {}", fruit, map.home_slot(&fruit).unwrap());
+    }
+    println!("表: {}", map.layout());
+
+    // 2. 同じマスに当たったら（衝突）、空くまで隣へ進む（線形探索）
+    let home = map.home_slot(&"banana").unwrap();
+    if let Some(rival) = candidates
+        .iter()
+        .map(String::as_str)
+        .find(|key| map.home_slot(key) == Some(home))
+    {
+        map.insert(rival, 1);
+        println!(
+            "{:?} も banana と同じマス {} に当たるので先へ進む: {}",
+            rival,
+            home,
+            map.layout()
+        );
+
+        // 3. 削除したマスには墓石を置く。空にすると、その先にある rival の探索がそこで止まってしまう
+        map.remove(&"banana");
+        println!("banana を削除: {}", map.layout());
+        println!(
+            "墓石の先も探す: get({:?}) = {:?}, get(\"banana\") = {:?}",
+            rival,
+            map.get(&rival),
+            map.get(&"banana")
+        );
+    }
+
+    // 4. 3/4 まで埋まったら、2倍の表に入れ直す（墓石もここで消える）
+    let mut numbers = OpenMap::new();
+    let mut capacities = Vec::new();
+    for n in 0..100 {
+        numbers.insert(n, n * n);
+        if capacities.last() != Some(&numbers.capacity()) {
+            capacities.push(numbers.capacity());
+        }
+    }
+    println!("100個入れる間のマスの数: {:?}", capacities);
+
+    // std の HashMap と同じ結果になるか
+    let std_map: HashMap<i32, i32> = (0..100).map(|n| (n, n * n)).collect();
+    let same = std_map.iter().all(|(k, v)| numbers.get(k) == Some(v));
+    println!(
+        "std::collections::HashMap と中身が一致: {}",
+        same && numbers.len() == std_map.len()
+    );
+    println!("std の HashMap は SwissTable（16マスずつ SIMD で調べる）と、起動ごとに変わるハッシュの鍵で動いている");
+}
+
+// ----------------------------------------------------------------------------
+// リングバッファ（固定長のキュー）
+// ----------------------------------------------------------------------------
+
+/// 容量が決まったキュー。末尾まで使ったら先頭に戻って（wraparound）使い回す
+///
+/// 取り出したマスは None に戻すので、T に Default や Clone は要らない。
+/// 一杯のときに push すると、値を Err で返す（新しい確保はしない）
+pub struct RingBuffer<T> {
+    slots: Vec<Option<T>>,
+    /// 次に取り出す位置
+    head: usize,
+    len: usize,
+}
+
+impl<T> RingBuffer<T> {
+    /// capacity 個まで入るバッファを作る（確保はここでの1回だけ）
+    pub fn with_capacity(capacity: usize) -> Self {
+        assert!(capacity > 0, "容量は1以上にしてください");
+        RingBuffer {
+            slots: (0..capacity).map(|_| None).collect(),
+            head: 0,
+            len: 0,
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len == self.slots.len()
+    }
+
+    // 論理的な位置（先頭から i 番目）を、配列の位置に直す
+    fn physical(&self, i: usize) -> usize {
+        (self.head + i) % self.slots.len()
+    }
+
+    /// 末尾に追加する。一杯なら値をそのまま Err で返す
+    pub fn push(&mut self, value: T) -> Result<(), T> {
+        if self.is_full() {
+            return Err(value);
+        }
+        let tail = self.physical(self.len);
+        self.slots[tail] = Some(value);
+        self.len += 1;
+        Ok(())
+    }
+
+    /// 末尾に追加する。一杯なら一番古い値を捨てて、それを返す（ログの直近 N 件など）
+    pub fn push_overwrite(&mut self, value: T) -> Option<T> {
+        let oldest = if self.is_full() { self.pop() } else { None };
+        let _ = self.push(value);
+        oldest
+    }
+
+    /// 先頭（一番古い値）を取り出す
+    pub fn pop(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        let value = self.slots[self.head].take();
+        self.head = (self.head + 1) % self.slots.len();
+        self.len -= 1;
+        value
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        if self.is_empty() {
+            None
+        } else {
+            self.slots[self.head].as_ref()
+        }
+    }
+
+    /// 古い順に参照を返すイテレータ
+    pub fn iter(&self) -> RingIter<'_, T> {
+        RingIter {
+            buffer: self,
+            index: 0,
+        }
+    }
+}
+
+impl<T: Debug> RingBuffer<T> {
+    /// 配列の中身をそのまま表示する（先頭の位置は h）
+    pub fn layout(&self) -> String {
+        let cells: Vec<String> = self
+            .slots
+            .iter()
+            .enumerate()
+            .map(|(i, slot)| {
+                let mark = if i == self.head { "h:" } else { "" };
+                match slot {
+                    Some(value) => format!("{}{:?}", mark, value),
+                    None => format!("{}_", mark),
+                }
+            })
+            .collect();
+        format!("[{}]", cells.join(", "))
+    }
+}
+
+/// RingBuffer::iter が返すイテレータ
+pub struct RingIter<'a, T> {
+    buffer: &'a RingBuffer<T>,
+    index: usize,
+}
+
+impl<'a, T> Iterator for RingIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        if self.index >= self.buffer.len {
+            return None;
+        }
+        let slot = &self.buffer.slots[self.buffer.physical(self.index)];
+        self.index += 1;
+        slot.as_ref()
+    }
+
+    // 残りの数が分かるので、collect が最初に必要な分だけ確保できる
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.buffer.len - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+// for value in &buffer と書けるようにする
+impl<'a, T> IntoIterator for &'a RingBuffer<T> {
+    type Item = &'a T;
+    type IntoIter = RingIter<'a, T>;
+
+    fn into_iter(self) -> RingIter<'a, T> {
+        self.iter()
+    }
+}
+
+/// リングバッファ
+pub fn ring_buffer_demo() {
+    println!("\n=== リングバッファ（固定長のキュー） ===");
+
+    let mut buffer = RingBuffer::with_capacity(4);
+    for n in 1..=4 {
+        buffer.push(n).unwrap();
+    }
+    println!(
+        "1〜4 を push: {}（一杯: {}）",
+        buffer.layout(),
+        buffer.is_full()
+    );
+    println!(
+        "一杯のときの push(5) = {:?}（値が返ってくる）",
+        buffer.push(5)
+    );
+
+    // 先頭から取り出すと、空いたマスに次の値が入る（配列の端で先頭に戻る）
+    println!("pop() = {:?}, pop() = {:?}", buffer.pop(), buffer.pop());
+    buffer.push(5).unwrap();
+    buffer.push(6).unwrap();
+    println!("5, 6 を push: {}", buffer.layout());
+    let items: Vec<_> = buffer.iter().collect();
+    println!("iter() は古い順: {:?}（配列の順ではない）", items);
+
+    // 直近 N 件だけ覚えておく（古いものから捨てる）
+    let mut recent = RingBuffer::with_capacity(3);
+    for line in ["起動", "接続", "受信", "切断", "終了"] {
+        if let Some(dropped) = recent.push_overwrite(line) {
+            println!("  {} を追加（{} を捨てた）", line, dropped);
+        }
+    }
+    print!("直近3件:");
+    for line in &recent {
+        print!(" {}", line);
+    }
+    println!();
+
+    // VecDeque も中身はリングバッファ。ただし一杯になると2倍に伸びる
+    let mut deque = VecDeque::with_capacity(4);
+    for n in 1..=5 {
+        deque.push_back(n);
+    }
+    println!(
+        "VecDeque は一杯でも push できる（容量が {} に伸びた）: {:?}",
+        deque.capacity(),
+        deque
+    );
+    println!(
+        "RingBuffer: 容量が固定でメモリを使いすぎない（生産者と消費者の間のキュー、直近のログ）"
+    );
+    println!("VecDeque: 両端から出し入れでき、必要なら伸びる。ふだんはこちらで十分");
+}
+
+/// チートシート用のデータ（`cheatsheet collections` で表示）
+pub const CHEATSHEET: CheatSheet = CheatSheet {
+    syntax: &[
+        ("let v: Vec<i32> = Vec::new();", "空のベクター"),
+        ("let v = vec![1, 2, 3];", "vec!マクロ"),
+        ("for i in &mut v { *i += 1; }", "可変参照で反復"),
+        ("let s = format!(\"{}-{}\", a, b);", "所有権を奪わない結合"),
+        ("let s3 = s1 + &s2;", "+演算子（s1はムーブ）"),
+        ("let mut map = HashMap::new();", "ハッシュマップ"),
+    ],
+    methods: &[
+        ("v.push(x) / v.pop()", "末尾に追加・取り出し"),
+        ("v.get(i)", "範囲外ならNone"),
+        ("v.sort() / v.dedup()", "ソート・連続する重複の除去"),
+        ("s.chars() / s.bytes()", "文字単位・バイト単位で反復"),
+        ("s.split(',') / s.trim()", "分割・前後の空白除去"),
+        ("String::with_capacity(n)", "長さが分かっているなら先に確保"),
+        ("pieces.join(\", \")", "区切り文字を挟んで連結"),
+        (
+            "map.entry(k).or_insert(0)",
+            "無ければ挿入して可変参照を返す",
+        ),
+        (
+            "VecDeque::push_back / pop_front",
+            "両端キュー（中身はリングバッファ）",
+        ),
+        (
+            "map.get(&k).copied().unwrap_or(0)",
+            "値のコピーとデフォルト",
+        ),
+        ("btree.range(80..)", "キーの範囲で取り出す（BTreeMap）"),
+        ("btree.first_key_value()", "最小のキーと値（BTreeMap）"),
+        (
+            "text::pad_right(s, width)",
+            "表示幅で左寄せ（全角は幅 2、lesson_api）",
+        ),
+        (
+            "graphemes(s).len()",
+            "見た目の文字数（書記素クラスタ、unicode-segmentation）",
+        ),
+    ],
+    gotchas: &[
+        "Stringは整数インデックスでアクセスできない（UTF-8のため）",
+        "文字の途中でスライスすると panic する",
+        "ベクターの要素への参照を持ったままpushはできない",
+        "HashMapの反復順序は不定。順序が必要ならBTreeMap（range や最小・最大も使える）",
+        "ループの中で s = format!(\"{}{}\", s, x) とすると毎回全体をコピーする",
+        "HashMap のキーは Hash と Eq が一致していること（a == b なら hash(a) == hash(b)）",
+        "{:<10} は文字数で数えるので、全角文字を含む表は列がずれる",
+        "\"が\" と \"か\u{3099}\" は見た目が同じでも == は false（正規化してから比べる）",
+        "chars() は見た目の1文字ではない（絵文字や結合文字は複数の char）",
+        "金額を f64 で持たない（補助単位の整数で数える）",
+    ],
+};
+
+/// すべてのデモを実行
+pub fn run_all() {
+    println!("╔════════════════════════════════════════════════════════════════╗");
+    println!("║          Rustコレクションサンプル                               ║");
+    println!("╚════════════════════════════════════════════════════════════════╝");
+
+    lesson_api::run_topic("collections");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lesson_api::random::Rng;
+
+    #[test]
+    fn open_map_matches_std_hashmap() {
+        let mut ours = OpenMap::new();
+        let mut std_map = HashMap::new();
+        let mut rng = Rng::new(11);
+        // キーの範囲を狭くして、上書き・削除・墓石の再利用を何度も起こす
+        for step in 0..5000 {
+            let key = rng.below(64);
+            if rng.below(3) == 0 {
+                assert_eq!(ours.remove(&key), std_map.remove(&key), "remove {}", step);
+            } else {
+                assert_eq!(
+                    ours.insert(key, step),
+                    std_map.insert(key, step),
+                    "insert {}",
+                    step
+                );
+            }
+            assert_eq!(ours.len(), std_map.len());
+            assert_eq!(ours.get(&key), std_map.get(&key));
+        }
+        let mut pairs: Vec<_> = ours.iter().map(|(&k, &v)| (k, v)).collect();
+        pairs.sort();
+        let mut expected: Vec<_> = std_map.into_iter().collect();
+        expected.sort();
+        assert_eq!(pairs, expected);
+    }
+
+    #[test]
+    fn ring_buffer_wraps_around() {
+        let mut buffer = RingBuffer::with_capacity(3);
+        assert_eq!(buffer.pop(), None);
+        assert_eq!(buffer.peek(), None);
+        for n in 1..=3 {
+            buffer.push(n).unwrap();
+        }
+        assert_eq!(buffer.push(4), Err(4));
+        assert_eq!(buffer.pop(), Some(1));
+        buffer.push(4).unwrap();
+        // 配列では [4, 2, 3] だが、古い順に返す
+        assert_eq!(buffer.iter().copied().collect::<Vec<_>>(), vec![2, 3, 4]);
+        assert_eq!(buffer.iter().size_hint(), (3, Some(3)));
+        assert_eq!(buffer.peek(), Some(&2));
+
+        assert_eq!(buffer.push_overwrite(5), Some(2));
+        assert_eq!(
+            (&buffer).into_iter().copied().collect::<Vec<_>>(),
+            vec![3, 4, 5]
+        );
+
+        // VecDeque と同じ順に出てくる
+        let mut deque: VecDeque<i32> = buffer.iter().copied().collect();
+        while let Some(n) = buffer.pop() {
+            assert_eq!(Some(n), deque.pop_front());
+        }
+        assert!(buffer.is_empty() && deque.is_empty());
+    }
+
+    #[test]
+    fn open_map_reuses_tombstones_and_grows() {
+        let mut map = OpenMap::new();
+        assert_eq!(map.get(&"a"), None);
+        assert_eq!(map.remove(&"a"), None);
+
+        map.insert("a", 1);
+        assert_eq!(map.capacity(), 8);
+        assert_eq!(map.remove(&"a"), Some(1));
+        assert!(map.is_empty());
+        assert!(map.layout().contains('†'));
+        // 同じキーを入れ直すと墓石のマスを使う
+        map.insert("a", 2);
+        assert!(!map.layout().contains('†'));
+
+        // 入れては消すのを繰り返しても、墓石を掃除するので表は大きくならない
+        for n in 0..1000 {
+            let key = if n % 2 == 0 { "x" } else { "y" };
+            map.insert(key, n);
+            map.remove(&key);
+        }
+        assert_eq!(map.capacity(), 8);
+        assert_eq!(map.len(), 1);
+
+        for n in 0..100 {
+            map.insert(if n < 50 { "b" } else { "c" }, n);
+        }
+        assert_eq!(map.len(), 3);
+        assert_eq!(map.get(&"c"), Some(&99));
+        assert!(map.contains_key(&"b"));
+    }
+
+    #[test]
+    fn money_units_and_weekdays() {
+        assert_eq!(format_money(1_234_567, &YEN), "1,234,567円");
+        assert_eq!(format_money(1_234_567, &US_DOLLAR), "$12,345.67");
+        assert_eq!(format_money(-1_234_567, &EURO_DE), "-12.345,67 €");
+        assert_eq!(format_money(-5, &US_DOLLAR), "-$0.05");
+        assert_eq!(japanese_units(123_456_789), "1億2345万6789");
+        assert_eq!(japanese_units(100_000_000), "1億");
+        assert_eq!(japanese_units(0), "0");
+        // 2024-03-05 は火曜、2000-01-01 は土曜
+        assert_eq!(weekday(2024, 3, 5), 2);
+        assert_eq!(weekday(2000, 1, 1), 6);
+    }
+
+    #[test]
+    fn graphemes_and_composition() {
+        assert_eq!(graphemes("か\u{3099}っ"), ["か\u{3099}", "っ"]);
+        assert_eq!(graphemes("a\r\nb"), ["a", "\r\n", "b"]);
+        assert_eq!(graphemes("🇯🇵🇺🇸🇫").len(), 3);
+        assert_eq!(graphemes("👨\u{200D}👩\u{200D}👧👍🏽").len(), 2);
+        assert!(graphemes("").is_empty());
+
+        assert_eq!(compose("か\u{3099}ハ\u{309A}ウ\u{3099}"), "がパヴ");
+        assert_eq!(compose("caf\u{E9} cafe\u{301}"), "café café");
+        // 濁点の付かない文字はそのまま残す
+        assert_eq!(compose("あ\u{3099}"), "あ\u{3099}");
+    }
+}
@@ -0,0 +1,626 @@
+// ============================================================================
+// Rust 並行処理サンプル
+// 公式ドキュメント: https://doc.rust-lang.org/book/ch16-00-concurrency.html
+// ============================================================================
+//
+// スレッドの間でデータを受け渡すには、チャネルで送るか、Arc<Mutex<T>> で共有する。
+// Rust では所有権と Send / Sync トレイトのおかげで、データ競合はコンパイルエラーになる。
+//
+// - thread::spawn        スレッドを作る（JoinHandle を join して結果を受け取る）
+// - move クロージャ       スレッドで使う値の所有権をスレッドに移す
+// - mpsc::channel        チャネル（tx.clone() で送信側を増やせる）
+// - mpsc::sync_channel   容量に上限のあるチャネル（満杯なら送信側が待つ）
+// - Arc<Mutex<T>>        複数のスレッドで1つの値を共有する
+// - thread::scope        スコープの中のスレッドには、スタックの値を借用のまま渡せる
+// - AtomicBool           ロックなしで読み書きできるフラグ
+//
+// wasm32-unknown-unknown（ブラウザ版）ではスレッドも時計も使えないので、
+// デモは説明だけを表示する。
+
+use lesson_api::{println, CheatSheet};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, TrySendError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+// wasm32-unknown-unknown（ブラウザ版）では thread::spawn も Instant::now() も panic する
+fn threads_unavailable() -> bool {
+    let unavailable = cfg!(target_arch = "wasm32");
+    if unavailable {
+        println!("ブラウザ版ではスレッドを作れません。cargo run で試してください");
+    }
+    unavailable
+}
+
+// ----------------------------------------------------------------------------
+// スレッドの基本
+// ----------------------------------------------------------------------------
+
+/// thread::spawn と JoinHandle
+pub fn spawn_and_join() {
+    println!("\n=== thread::spawn と JoinHandle ===");
+    if threads_unavailable() {
+        return;
+    }
+
+    // spawn はすぐに戻り、クロージャは別のスレッドで動く。戻り値は JoinHandle<T>
+    let handle = thread::spawn(|| {
+        let mut steps = Vec::new();
+        for i in 1..=3 {
+            steps.push(format!("spawned {}", i));
+            thread::sleep(Duration::from_millis(1));
+        }
+        steps
+    });
+    for i in 1..=2 {
+        println!("main {}（spawn したスレッドと同時に動いている）", i);
+        thread::sleep(Duration::from_millis(1));
+    }
+
+    // join で終わるのを待ち、クロージャの戻り値を受け取る
+    // join しないまま main が終わると、残りのスレッドは途中で止められる
+    let steps = handle.join().unwrap();
+    println!("join の戻り値: {:?}", steps);
+
+    // スレッドの中の panic は join が Err として返す（呼び出し元は巻き込まれない）
+    let panicked = thread::spawn(|| -> i32 { panic!("スレッドの中の panic") }).join();
+    println!(
+        "panic したスレッドの join: is_err = {}（メッセージは標準エラーに出る）",
+        panicked.is_err()
+    );
+
+    // どのスレッドが先に動くかは OS の割り当て次第で、実行のたびに変わる
+    let handles: Vec<_> = (0..4)
+        .map(|id| thread::spawn(move || (id, thread::current().id())))
+        .collect();
+    for handle in handles {
+        let (id, thread_id) = handle.join().unwrap();
+        println!("  スレッド {} の ThreadId: {:?}", id, thread_id);
+    }
+}
+
+/// move クロージャでスレッドに値を渡す
+pub fn move_closures() {
+    println!("\n=== move クロージャでスレッドに値を渡す ===");
+    if threads_unavailable() {
+        return;
+    }
+
+    let v = vec![1, 2, 3];
+    // move が無いと v を借用することになり、error[E0373] になる
+    // （スレッドがいつまで動くか分からないので、v より長生きするかもしれない）
+    let handle = thread::spawn(move || {
+        let sum: i32 = v.iter().sum();
+        format!("v = {:?}, 合計 = {}", v, sum)
+    });
+    // v の所有権はスレッドに移ったので、ここではもう使えない（drop(v) は E0382）
+    println!("スレッドの結果: {}", handle.join().unwrap());
+
+    // 複数のスレッドで同じデータを読むなら、それぞれに clone を渡すか Arc で共有する
+    let words = Arc::new(vec!["alpha", "beta", "gamma"]);
+    let handles: Vec<_> = (0..words.len())
+        .map(|i| {
+            let words = Arc::clone(&words);
+            thread::spawn(move || words[i].to_uppercase())
+        })
+        .collect();
+    let upper: Vec<String> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+    println!("Arc で共有した Vec を3つのスレッドで読んだ: {:?}", upper);
+    // thread::scope なら、スコープの終わりで必ず join されるので借用のまま渡せる
+    println!("thread::scope を使うと、move せずに借用のまま渡せる");
+}
+
+/// 複数の送信側から1つのチャネルに送る
+pub fn channels_multiple_producers() {
+    println!("\n=== 複数の送信側から1つのチャネルに送る ===");
+    if threads_unavailable() {
+        return;
+    }
+
+    // mpsc = multiple producer, single consumer
+    let (tx, rx) = mpsc::channel();
+    for producer in 0..3 {
+        // 送信側は clone で増やせる。それぞれのスレッドに1つずつ move する
+        let tx = tx.clone();
+        thread::spawn(move || {
+            for i in 0..3 {
+                // send は値の所有権もチャネルに移す（送った後は使えない）
+                let message = format!("producer {} - {}", producer, i);
+                tx.send(message).unwrap();
+                thread::sleep(Duration::from_millis(1));
+            }
+        });
+    }
+    // 手元の送信側を drop しないと、下の for が終わらない
+    drop(tx);
+
+    // rx をイテレータとして使うと、すべての送信側が drop されるまで受け取り続ける
+    let mut received = Vec::new();
+    for message in rx {
+        println!("  受信: {}", message);
+        received.push(message);
+    }
+    println!(
+        "{} 件受信（同じ producer の中の順序は保たれるが、producer どうしの順序は毎回変わる）",
+        received.len()
+    );
+
+    // 受信側がいなくなったチャネルへの send は Err になる
+    let (tx, rx) = mpsc::channel::<i32>();
+    drop(rx);
+    println!(
+        "受信側を drop した後の send: is_err = {}",
+        tx.send(1).is_err()
+    );
+}
+
+/// Mutex のカウンタを threads 個のスレッドで increments 回ずつ増やす
+pub fn count_with_mutex(threads: usize, increments: usize) -> usize {
+    let counter = Arc::new(Mutex::new(0));
+    let handles: Vec<_> = (0..threads)
+        .map(|_| {
+            let counter = Arc::clone(&counter);
+            thread::spawn(move || {
+                for _ in 0..increments {
+                    // lock() が返すガード（MutexGuard）が drop されるとロックが外れる
+                    *counter.lock().unwrap() += 1;
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    let total = *counter.lock().unwrap();
+    total
+}
+
+/// Arc<Mutex<T>> で状態を共有する
+pub fn shared_state_mutex() {
+    println!("\n=== Arc<Mutex<T>> で状態を共有する ===");
+    if threads_unavailable() {
+        return;
+    }
+
+    // Mutex は1つのスレッドからしか中身を触らせない
+    let m = Mutex::new(5);
+    {
+        let mut num = m.lock().unwrap();
+        *num = 6;
+        // ここでもう一度 m.lock() を呼ぶと、同じスレッドでもデッドロックする
+    }
+    println!("Mutex::new(5) を lock して書き換えた: {:?}", m);
+
+    // Rc は Send ではないので、スレッドに渡すとコンパイルエラー（E0277）
+    // スレッド間で所有を共有するには、カウントをアトミックに増減する Arc を使う
+    let threads = 10;
+    let increments = 1_000;
+    let total = count_with_mutex(threads, increments);
+    println!(
+        "{} スレッドで {} 回ずつ +1: {}（ロックがあるので数え落としは無い）",
+        threads, increments, total
+    );
+
+    // ガードを持っている間は、ほかのスレッドは待たされる
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let handles: Vec<_> = (0..3)
+        .map(|id| {
+            let log = Arc::clone(&log);
+            thread::spawn(move || log.lock().unwrap().push(id))
+        })
+        .collect();
+    println!(
+        "Arc::strong_count = {}（spawn したスレッドがまだ持っているかもしれない）",
+        Arc::strong_count(&log)
+    );
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    println!(
+        "join した後: Arc::strong_count = {}、ログ = {:?}（順序は毎回変わる）",
+        Arc::strong_count(&log),
+        log.lock().unwrap()
+    );
+    println!("Arc<Mutex<T>> は RefCell<T> / Rc<T> のスレッド版。単純な数なら AtomicUsize でもよい");
+}
+
+/// data を chunks 個に分け、スコープ付きスレッドで並列に合計する（Arc も clone も要らない）
+pub fn parallel_sum_scoped(data: &[u64], chunks: usize) -> u64 {
+    let chunk_len = data.len().div_ceil(chunks.max(1)).max(1);
+    thread::scope(|s| {
+        let handles: Vec<_> = data
+            .chunks(chunk_len)
+            .map(|chunk| s.spawn(move || chunk.iter().sum::<u64>()))
+            .collect();
+        handles.into_iter().map(|h| h.join().unwrap()).sum()
+    })
+}
+
+/// スコープ付きスレッド（thread::scope）
+pub fn scoped_threads() {
+    println!("\n=== スコープ付きスレッド（thread::scope） ===");
+    if threads_unavailable() {
+        return;
+    }
+
+    // thread::spawn のクロージャは 'static が必要なので、ローカル変数は move するしかない。
+    // thread::scope の中の s.spawn は、スコープを抜ける前に必ず join されるので借用できる
+    let numbers = vec![1, 2, 3, 4, 5];
+    let label = String::from("numbers");
+    let (sum, max) = thread::scope(|s| {
+        let sum = s.spawn(|| format!("{} の合計 = {}", label, numbers.iter().sum::<i32>()));
+        let max = s.spawn(|| format!("{} の最大 = {:?}", label, numbers.iter().max()));
+        (sum.join().unwrap(), max.join().unwrap())
+    }); // join しなかったスレッドも、ここで必ず join される
+    println!("  スレッド1: {}", sum);
+    println!("  スレッド2: {}", max);
+    println!(
+        "スコープの後も numbers と label はそのまま使える: {:?} / {}",
+        numbers, label
+    );
+
+    // 可変の借用も、重ならない部分に分ければ Mutex なしで並列に書き換えられる
+    let mut scores = [10, 20, 30, 40, 50, 60];
+    thread::scope(|s| {
+        for (i, chunk) in scores.chunks_mut(2).enumerate() {
+            // move で動くのは &mut [i32] と i だけ（配列そのものは動かない）
+            s.spawn(move || {
+                for score in chunk.iter_mut() {
+                    *score += i as i32;
+                }
+            });
+        }
+    });
+    println!("chunks_mut を3つのスレッドで書き換えた: {:?}", scores);
+
+    // s.spawn も ScopedJoinHandle を返すので、戻り値を受け取れる
+    let data: Vec<u64> = (1..=1_000).collect();
+    println!(
+        "parallel_sum_scoped(1..=1000, 4) = {}",
+        parallel_sum_scoped(&data, 4)
+    );
+
+    // 比較: thread::spawn では Arc に入れて、スレッドごとに clone を move する
+    let shared = Arc::new(data);
+    let handles: Vec<_> = (0..4)
+        .map(|i| {
+            let shared = Arc::clone(&shared);
+            thread::spawn(move || shared[i * 250..(i + 1) * 250].iter().sum::<u64>())
+        })
+        .collect();
+    let total: u64 = handles.into_iter().map(|h| h.join().unwrap()).sum();
+    println!(
+        "Arc + move の同じ計算 = {}（Vec を Arc に移し、範囲も手で計算する）",
+        total
+    );
+
+    println!("\nよくある誤解:");
+    println!(
+        "  - scope は非同期ではない: すべてのスレッドが終わるまで scope の呼び出しから戻らない"
+    );
+    println!("  - ScopedJoinHandle はスコープの外に持ち出せない（借用より長く生きられない）");
+    println!("  - join しなかったスレッドが panic すると、scope の終わりで panic が伝わる");
+    println!("  - ループで spawn するときは move が要ることが多い（動くのは参照だけ）");
+}
+
+// ----------------------------------------------------------------------------
+// 多段パイプライン（生成 → 変換 → 集計）
+// ----------------------------------------------------------------------------
+//
+//   生成スレッド ──[sync_channel]──> 変換スレッド × N ──[sync_channel]──> 集計（呼び出し元）
+//
+// 集計が目標の件数に届いたら停止フラグを立てる。生成スレッドが止まって送信側を
+// drop すると、チャネルが閉じたことが下流へ順に伝わり、全員が自然に終了する。
+
+/// パイプラインの設定
+#[derive(Debug, Clone)]
+pub struct PipelineConfig {
+    /// 変換スレッドの数
+    pub workers: usize,
+    /// 各チャネルにためられる件数（0 なら受け取られるまで送信側が待つ）
+    pub buffer: usize,
+    /// 集計がこの件数を受け取ったら停止を知らせる
+    pub limit: usize,
+}
+
+/// 段ごとの処理件数と、その段が動いていた時間
+#[derive(Debug, Clone, Copy)]
+pub struct StageStats {
+    pub items: usize,
+    pub elapsed: Duration,
+}
+
+impl StageStats {
+    /// 1秒あたりの処理件数
+    pub fn throughput(&self) -> f64 {
+        self.items as f64 / self.elapsed.as_secs_f64().max(1e-9)
+    }
+}
+
+/// パイプラインを1回動かした結果
+#[derive(Debug, Clone)]
+pub struct PipelineStats {
+    pub generator: StageStats,
+    /// チャネルが満杯で、生成スレッドが待たされた回数
+    pub generator_blocked: usize,
+    pub workers: Vec<StageStats>,
+    pub aggregator: StageStats,
+    /// Collatz 数列が最も長かった数と、その手数
+    pub longest: (u64, u32),
+}
+
+/// n から 1 になるまでの Collatz 操作の回数（変換スレッドの「重い処理」）
+pub fn collatz_steps(mut n: u64) -> u32 {
+    let mut steps = 0;
+    while n > 1 {
+        n = if n.is_multiple_of(2) {
+            n / 2
+        } else {
+            3 * n + 1
+        };
+        steps += 1;
+    }
+    steps
+}
+
+/// 生成 → 変換 → 集計のパイプラインを動かす
+pub fn run_pipeline(config: &PipelineConfig) -> PipelineStats {
+    assert!(config.workers > 0, "workers must be non-zero");
+    let stop = Arc::new(AtomicBool::new(false));
+    let (number_tx, number_rx) = mpsc::sync_channel::<u64>(config.buffer);
+    let (result_tx, result_rx) = mpsc::sync_channel::<(u64, u32)>(config.buffer);
+
+    // 生成: 停止フラグが立つまで 1, 2, 3, ... を送り続ける
+    let generator = {
+        let stop = Arc::clone(&stop);
+        thread::spawn(move || {
+            let start = Instant::now();
+            let (mut produced, mut blocked) = (0, 0);
+            for n in 1u64.. {
+                if stop.load(Ordering::Relaxed) {
+                    break;
+                }
+                // まず待たずに送ってみて、満杯なら数えてから待つ
+                let sent = match number_tx.try_send(n) {
+                    Ok(()) => true,
+                    Err(TrySendError::Full(n)) => {
+                        blocked += 1;
+                        number_tx.send(n).is_ok()
+                    }
+                    Err(TrySendError::Disconnected(_)) => false,
+                };
+                if !sent {
+                    break;
+                }
+                produced += 1;
+            }
+            // ここで number_tx が drop され、変換スレッドの recv がいずれ Err になる
+            let stats = StageStats {
+                items: produced,
+                elapsed: start.elapsed(),
+            };
+            (stats, blocked)
+        })
+    };
+
+    // 変換: Receiver は1つしかないので Arc<Mutex<..>> で分け合う
+    let number_rx = Arc::new(Mutex::new(number_rx));
+    let workers: Vec<_> = (0..config.workers)
+        .map(|_| {
+            let number_rx = Arc::clone(&number_rx);
+            let result_tx = result_tx.clone();
+            thread::spawn(move || {
+                let start = Instant::now();
+                let mut items = 0;
+                loop {
+                    // ロックは文の終わりで外れる。while let Ok(n) = rx.lock().unwrap().recv() と
+                    // 書くと、ループの本体を実行している間もロックを持ったままになる
+                    let received = number_rx.lock().unwrap().recv();
+                    let Ok(n) = received else { break };
+                    if result_tx.send((n, collatz_steps(n))).is_err() {
+                        break;
+                    }
+                    items += 1;
+                }
+                StageStats {
+                    items,
+                    elapsed: start.elapsed(),
+                }
+            })
+        })
+        .collect();
+    // 手元の送信側を残すと、変換スレッドが全員終わってもチャネルが閉じない
+    drop(result_tx);
+
+    // 集計: チャネルが閉じる（すべての送信側が drop される）までループが続く
+    let start = Instant::now();
+    let mut aggregated = 0;
+    let mut longest = (0, 0);
+    for (n, steps) in result_rx {
+        aggregated += 1;
+        if steps > longest.1 {
+            longest = (n, steps);
+        }
+        if aggregated == config.limit {
+            stop.store(true, Ordering::Relaxed);
+        }
+    }
+    let aggregator = StageStats {
+        items: aggregated,
+        elapsed: start.elapsed(),
+    };
+
+    let (generator, generator_blocked) = generator.join().unwrap();
+    PipelineStats {
+        generator,
+        generator_blocked,
+        workers: workers.into_iter().map(|w| w.join().unwrap()).collect(),
+        aggregator,
+        longest,
+    }
+}
+
+/// 多段パイプライン（チャネルと停止の合図）
+pub fn pipeline_demo() {
+    println!("\n=== 多段パイプライン（チャネルと停止の合図） ===");
+    if threads_unavailable() {
+        return;
+    }
+
+    let config = PipelineConfig {
+        workers: 3,
+        buffer: 16,
+        limit: 200_000,
+    };
+    println!(
+        "生成 → 変換 × {} → 集計（チャネルの容量 {}、{} 件で停止）",
+        config.workers, config.buffer, config.limit
+    );
+    let stats = run_pipeline(&config);
+
+    // 段ごとの件数と、1秒あたりの件数（スレッドの割り当て次第で毎回変わる）
+    let row = |name: &str, stage: &StageStats| {
+        println!(
+            "  {:<12} {:>8} items {:>8.1} ms {:>12.0} items/s",
+            name,
+            stage.items,
+            stage.elapsed.as_secs_f64() * 1000.0,
+            stage.throughput()
+        );
+    };
+    row("generator", &stats.generator);
+    for (i, worker) in stats.workers.iter().enumerate() {
+        row(&format!("worker #{}", i), worker);
+    }
+    row("aggregator", &stats.aggregator);
+
+    println!(
+        "生成スレッドがチャネルの満杯で待った回数: {}",
+        stats.generator_blocked
+    );
+    println!("容量のあるチャネルは、速い上流が遅い下流を追い越しすぎないよう抑える（背圧）");
+
+    // 停止フラグを立てたあとも、途中のチャネルにある分は最後まで流れる
+    let transformed: usize = stats.workers.iter().map(|w| w.items).sum();
+    println!(
+        "停止までに流れた件数: 生成 {} = 変換 {} = 集計 {}（{} 件を超えた分はチャネルの中にあった分）",
+        stats.generator.items, transformed, stats.aggregator.items, config.limit
+    );
+    println!(
+        "Collatz の手数が最も多かった数: {}（{} 手）",
+        stats.longest.0, stats.longest.1
+    );
+    println!("停止の順序: フラグ → 生成が送信側を drop → 変換の recv が Err → 集計の for が終わる");
+}
+
+/// チートシート用のデータ（`cheatsheet concurrency` で表示）
+pub const CHEATSHEET: CheatSheet = CheatSheet {
+    syntax: &[
+        (
+            "thread::spawn(move || { .. })",
+            "スレッドを作る（使う値はクロージャに move する）",
+        ),
+        (
+            "let (tx, rx) = mpsc::channel();",
+            "容量に上限のないチャネル",
+        ),
+        (
+            "let counter = Arc::new(Mutex::new(0));",
+            "スレッド間で共有して書き換える値",
+        ),
+        (
+            "handle.join().unwrap()",
+            "スレッドの終了を待って戻り値を受け取る",
+        ),
+        (
+            "let (tx, rx) = mpsc::sync_channel(16);",
+            "容量 16 のチャネル（満杯なら send が待つ）",
+        ),
+        (
+            "for item in rx { .. }",
+            "すべての送信側が drop されるまで受け取る",
+        ),
+        (
+            "Arc::new(Mutex::new(rx))",
+            "1つの Receiver を複数のスレッドで分け合う",
+        ),
+        (
+            "flag.store(true, Ordering::Relaxed)",
+            "AtomicBool で停止を知らせる",
+        ),
+        (
+            "thread::scope(|s| { s.spawn(|| ..); })",
+            "スコープ付きスレッド（借用を渡せる、終わりで join）",
+        ),
+    ],
+    methods: &[
+        ("tx.send(v)", "送る（受信側がいなければ Err）"),
+        (
+            "tx.try_send(v)",
+            "待たずに送る（Full / Disconnected のエラー）",
+        ),
+        ("tx.clone()", "送信側を増やす（複数の生産者）"),
+        ("rx.recv()", "受け取る（送信側が全部なくなり、空なら Err）"),
+        (
+            "mutex.lock().unwrap()",
+            "ロックを取る（ガードが drop されると外れる）",
+        ),
+        ("Arc::clone(&arc)", "スレッドごとに所有者を増やす"),
+    ],
+    gotchas: &[
+        "手元に残した送信側を drop しないと、受信側のループが終わらない",
+        "while let Ok(x) = m.lock().unwrap().recv() はループの本体でもロックを持ったまま",
+        "容量のない mpsc::channel は、遅い下流がいるとメモリを使い続ける",
+        "スレッドの処理件数や順序は実行のたびに変わる",
+        "Rc は Send ではないのでスレッドに渡せない（Arc を使う）",
+        "同じスレッドで同じ Mutex を2回 lock するとデッドロックする",
+        "thread::scope はすべてのスレッドが終わるまで戻らない（非同期ではない）",
+    ],
+};
+
+/// すべてのデモを実行
+pub fn run_all() {
+    println!("╔════════════════════════════════════════════════════════════════╗");
+    println!("║          Rust 並行処理サンプル                                   ║");
+    println!("╚════════════════════════════════════════════════════════════════╝");
+
+    lesson_api::run_topic("concurrency");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mutex_counter_does_not_lose_increments() {
+        assert_eq!(count_with_mutex(8, 500), 4_000);
+        assert_eq!(count_with_mutex(1, 0), 0);
+    }
+
+    #[test]
+    fn scoped_sum_matches_sequential_sum() {
+        let data: Vec<u64> = (1..=1_001).collect();
+        for chunks in [0, 1, 3, 8, 2_000] {
+            assert_eq!(parallel_sum_scoped(&data, chunks), 501_501);
+        }
+        assert_eq!(parallel_sum_scoped(&[], 4), 0);
+    }
+
+    #[test]
+    fn pipeline_drains_every_item_after_stop() {
+        let config = PipelineConfig {
+            workers: 2,
+            buffer: 4,
+            limit: 1_000,
+        };
+        let stats = run_pipeline(&config);
+        let transformed: usize = stats.workers.iter().map(|w| w.items).sum();
+        assert!(stats.aggregator.items >= config.limit);
+        assert_eq!(stats.generator.items, transformed);
+        assert_eq!(transformed, stats.aggregator.items);
+        // 1..=1000 の中では 871 が最長（178 手）
+        assert_eq!(collatz_steps(27), 111);
+        assert!(stats.longest.1 >= 178);
+    }
+}
@@ -0,0 +1,1040 @@
+// ============================================================================
+// Rustデータ構造サンプル - 連結リスト、スタック/キュー、二分探索木
+// 公式ドキュメント: https://doc.rust-lang.org/book/ch15-00-smart-pointers.html
+// ============================================================================
+//
+// ノードが次のノードを指す「つながった」データ構造を、所有権のルールの中で
+// どう表現するかを見る。
+//   - Box<Node>           : 子を1つの親だけが所有する（連結リスト、木）
+//   - Rc<RefCell<Node>>   : ノードを複数から共有し、後から書き換える
+//   - Arena + Handle      : ノードを Vec にまとめ、インデックスで指し合う
+
+use lesson_api::{print, println, CheatSheet};
+use std::cell::RefCell;
+use std::fmt::{self, Debug};
+use std::marker::PhantomData;
+use std::ops::{Index, IndexMut};
+use std::rc::Rc;
+
+// ----------------------------------------------------------------------------
+// 単方向連結リスト（Box）
+// ----------------------------------------------------------------------------
+
+/// 単方向連結リスト
+pub struct LinkedList<T> {
+    head: Option<Box<ListNode<T>>>,
+    len: usize,
+}
+
+struct ListNode<T> {
+    value: T,
+    next: Option<Box<ListNode<T>>>,
+}
+
+impl<T> LinkedList<T> {
+    pub fn new() -> Self {
+        LinkedList { head: None, len: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.head.is_none()
+    }
+
+    /// 先頭に追加する。O(1)
+    pub fn push_front(&mut self, value: T) {
+        // take() で head を None にしてから所有権を新しいノードに移す
+        let next = self.head.take();
+        self.head = Some(Box::new(ListNode { value, next }));
+        self.len += 1;
+    }
+
+    /// 先頭を取り出す。O(1)
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.head.take().map(|node| {
+            self.head = node.next;
+            self.len -= 1;
+            node.value
+        })
+    }
+
+    pub fn peek_front(&self) -> Option<&T> {
+        self.head.as_ref().map(|node| &node.value)
+    }
+
+    /// つなぎ替えだけで逆順にする（要素はコピーもムーブもしない）
+    pub fn reverse(&mut self) {
+        let mut reversed = None;
+        let mut current = self.head.take();
+        while let Some(mut node) = current {
+            current = node.next.take();
+            node.next = reversed;
+            reversed = Some(node);
+        }
+        self.head = reversed;
+    }
+
+    pub fn iter(&self) -> ListIter<'_, T> {
+        ListIter {
+            next: self.head.as_deref(),
+        }
+    }
+
+    pub fn contains(&self, value: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.iter().any(|v| v == value)
+    }
+}
+
+impl<T> Default for LinkedList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// 既定の Drop は再帰的にノードを解放するので、長いリストだとスタックが溢れる
+// ループで1つずつ外していく
+impl<T> Drop for LinkedList<T> {
+    fn drop(&mut self) {
+        let mut current = self.head.take();
+        while let Some(mut node) = current {
+            current = node.next.take();
+        }
+    }
+}
+
+/// 連結リストの要素を先頭から借用するイテレータ
+pub struct ListIter<'a, T> {
+    next: Option<&'a ListNode<T>>,
+}
+
+impl<'a, T> Iterator for ListIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.next.map(|node| {
+            self.next = node.next.as_deref();
+            &node.value
+        })
+    }
+}
+
+// ----------------------------------------------------------------------------
+// スタックとキュー
+// ----------------------------------------------------------------------------
+
+/// Vec をそのまま使ったスタック（後入れ先出し）
+pub struct Stack<T> {
+    items: Vec<T>,
+}
+
+impl<T> Stack<T> {
+    pub fn new() -> Self {
+        Stack { items: Vec::new() }
+    }
+
+    pub fn push(&mut self, value: T) {
+        self.items.push(value);
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        self.items.pop()
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.items.last()
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+impl<T> Default for Stack<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 2つのスタックで作るキュー（先入れ先出し）
+/// 取り出し用が空になったときだけ、入れ替えて逆順にする（償却 O(1)）
+/// 実際のコードでは std::collections::VecDeque を使う
+pub struct Queue<T> {
+    inbox: Vec<T>,
+    outbox: Vec<T>,
+}
+
+impl<T> Queue<T> {
+    pub fn new() -> Self {
+        Queue {
+            inbox: Vec::new(),
+            outbox: Vec::new(),
+        }
+    }
+
+    pub fn enqueue(&mut self, value: T) {
+        self.inbox.push(value);
+    }
+
+    pub fn dequeue(&mut self) -> Option<T> {
+        if self.outbox.is_empty() {
+            while let Some(value) = self.inbox.pop() {
+                self.outbox.push(value);
+            }
+        }
+        self.outbox.pop()
+    }
+
+    pub fn len(&self) -> usize {
+        self.inbox.len() + self.outbox.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T> Default for Queue<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ----------------------------------------------------------------------------
+// 二分探索木（Box）
+// ----------------------------------------------------------------------------
+
+/// 二分探索木: 左の子は小さく、右の子は大きい
+pub struct Bst<T> {
+    root: Option<Box<TreeNode<T>>>,
+    len: usize,
+}
+
+struct TreeNode<T> {
+    value: T,
+    left: Option<Box<TreeNode<T>>>,
+    right: Option<Box<TreeNode<T>>>,
+}
+
+impl<T: Ord> Bst<T> {
+    pub fn new() -> Self {
+        Bst { root: None, len: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// 値を挿入する。すでにあれば false
+    pub fn insert(&mut self, value: T) -> bool {
+        // 「次に値を置くべき場所」への可変参照をたどっていく
+        let mut slot = &mut self.root;
+        while let Some(node) = slot {
+            if value == node.value {
+                return false;
+            }
+            slot = if value < node.value {
+                &mut node.left
+            } else {
+                &mut node.right
+            };
+        }
+        *slot = Some(Box::new(TreeNode {
+            value,
+            left: None,
+            right: None,
+        }));
+        self.len += 1;
+        true
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        let mut current = self.root.as_deref();
+        while let Some(node) = current {
+            if *value == node.value {
+                return true;
+            }
+            current = if *value < node.value {
+                node.left.as_deref()
+            } else {
+                node.right.as_deref()
+            };
+        }
+        false
+    }
+
+    /// 通りがけ順（小さい順）に値を集める
+    pub fn in_order(&self) -> Vec<&T> {
+        fn walk<'a, T>(node: Option<&'a TreeNode<T>>, out: &mut Vec<&'a T>) {
+            if let Some(node) = node {
+                walk(node.left.as_deref(), out);
+                out.push(&node.value);
+                walk(node.right.as_deref(), out);
+            }
+        }
+        let mut out = Vec::with_capacity(self.len);
+        walk(self.root.as_deref(), &mut out);
+        out
+    }
+
+    /// 木の高さ（空なら 0）
+    pub fn height(&self) -> usize {
+        fn height<T>(node: Option<&TreeNode<T>>) -> usize {
+            node.map_or(0, |node| {
+                1 + height(node.left.as_deref()).max(height(node.right.as_deref()))
+            })
+        }
+        height(self.root.as_deref())
+    }
+}
+
+impl<T: Ord> Default for Bst<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ----------------------------------------------------------------------------
+// 二分探索木（Rc<RefCell>）
+// ----------------------------------------------------------------------------
+
+/// 共有できるノード。外からノードを握ったまま値を書き換えられる
+pub type SharedNode<T> = Rc<RefCell<SharedTreeNode<T>>>;
+
+pub struct SharedTreeNode<T> {
+    pub value: T,
+    pub left: Option<SharedNode<T>>,
+    pub right: Option<SharedNode<T>>,
+}
+
+/// Rc<RefCell> で作った二分探索木
+/// insert が挿入したノードのハンドルを返すので、木の外からも参照できる
+pub struct RcBst<T> {
+    root: Option<SharedNode<T>>,
+}
+
+impl<T: Ord + Clone> RcBst<T> {
+    pub fn new() -> Self {
+        RcBst { root: None }
+    }
+
+    /// 値を挿入し、そのノードを返す（すでにあれば既存のノード）
+    pub fn insert(&mut self, value: T) -> SharedNode<T> {
+        let new_node = |value| {
+            Rc::new(RefCell::new(SharedTreeNode {
+                value,
+                left: None,
+                right: None,
+            }))
+        };
+
+        let mut current = match &self.root {
+            Some(root) => Rc::clone(root),
+            None => {
+                let node = new_node(value);
+                self.root = Some(Rc::clone(&node));
+                return node;
+            }
+        };
+        loop {
+            // 借用は1回のループの中で終わらせる（次のノードへは Rc を複製して移る）
+            let next = {
+                let mut node = current.borrow_mut();
+                if value == node.value {
+                    None
+                } else {
+                    let child = if value < node.value {
+                        &mut node.left
+                    } else {
+                        &mut node.right
+                    };
+                    match child {
+                        Some(child) => Some(Rc::clone(child)),
+                        None => {
+                            let leaf = new_node(value);
+                            *child = Some(Rc::clone(&leaf));
+                            return leaf;
+                        }
+                    }
+                }
+            };
+            match next {
+                Some(next) => current = next,
+                None => return current,
+            }
+        }
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        let mut current = self.root.clone();
+        while let Some(node) = current {
+            let node = node.borrow();
+            if *value == node.value {
+                return true;
+            }
+            current = if *value < node.value {
+                node.left.clone()
+            } else {
+                node.right.clone()
+            };
+        }
+        false
+    }
+
+    /// 通りがけ順に値のコピーを集める（RefCell の中身は借用を返せない）
+    pub fn in_order(&self) -> Vec<T> {
+        fn walk<T: Clone>(node: &Option<SharedNode<T>>, out: &mut Vec<T>) {
+            if let Some(node) = node {
+                let node = node.borrow();
+                walk(&node.left, out);
+                out.push(node.value.clone());
+                walk(&node.right, out);
+            }
+        }
+        let mut out = Vec::new();
+        walk(&self.root, &mut out);
+        out
+    }
+}
+
+impl<T: Ord + Clone> Default for RcBst<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ----------------------------------------------------------------------------
+// アリーナ（Vec + インデックス）
+// ----------------------------------------------------------------------------
+
+/// アリーナの中の値を指すハンドル
+/// 中身は Vec のインデックスだけなので Copy でき、借用もライフタイムも持たない。
+/// 型引数で「どのアリーナの値か」を区別する（Handle<Node> を Arena<String> には使えない）
+pub struct Handle<T> {
+    index: usize,
+    // T を持たないが、T の型だけは覚えておく
+    _marker: PhantomData<fn() -> T>,
+}
+
+// derive だと T: Clone などを要求してしまうので手で実装する
+impl<T> Clone for Handle<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Handle<T> {}
+
+impl<T> PartialEq for Handle<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index
+    }
+}
+
+impl<T> Eq for Handle<T> {}
+
+impl<T> Debug for Handle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Handle({})", self.index)
+    }
+}
+
+/// 値を Vec にまとめて持つアリーナ
+/// 値を1つずつ Box にせず、Vec が伸びるときにまとめて確保する。
+/// 個別には解放できず、アリーナを捨てるとすべて一度に解放される
+pub struct Arena<T> {
+    items: Vec<T>,
+}
+
+impl<T> Arena<T> {
+    pub fn new() -> Self {
+        Arena { items: Vec::new() }
+    }
+
+    /// 入れる数が分かっていれば、確保は1回で済む
+    pub fn with_capacity(capacity: usize) -> Self {
+        Arena {
+            items: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// 値をアリーナに移し、ハンドルを返す
+    pub fn alloc(&mut self, value: T) -> Handle<T> {
+        self.items.push(value);
+        Handle {
+            index: self.items.len() - 1,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+impl<T> Default for Arena<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// arena[handle] で読み書きできるようにする
+impl<T> Index<Handle<T>> for Arena<T> {
+    type Output = T;
+
+    fn index(&self, handle: Handle<T>) -> &T {
+        &self.items[handle.index]
+    }
+}
+
+impl<T> IndexMut<Handle<T>> for Arena<T> {
+    fn index_mut(&mut self, handle: Handle<T>) -> &mut T {
+        &mut self.items[handle.index]
+    }
+}
+
+/// アリーナのノード。子も親もハンドルで指すので、親への参照に Weak が要らない
+pub struct ArenaNode<T> {
+    pub value: T,
+    pub parent: Option<Handle<ArenaNode<T>>>,
+    pub left: Option<Handle<ArenaNode<T>>>,
+    pub right: Option<Handle<ArenaNode<T>>>,
+}
+
+/// アリーナに作った二分探索木
+pub struct ArenaBst<T> {
+    nodes: Arena<ArenaNode<T>>,
+    root: Option<Handle<ArenaNode<T>>>,
+}
+
+impl<T: Ord> ArenaBst<T> {
+    pub fn new() -> Self {
+        ArenaBst {
+            nodes: Arena::new(),
+            root: None,
+        }
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        ArenaBst {
+            nodes: Arena::with_capacity(capacity),
+            root: None,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// 値を挿入し、そのノードのハンドルを返す（すでにあれば既存のノード）
+    pub fn insert(&mut self, value: T) -> Handle<ArenaNode<T>> {
+        let mut parent = None;
+        let mut current = self.root;
+        // ハンドルは Copy なので、借用を持ち越さずに木をたどれる
+        while let Some(handle) = current {
+            let node = &self.nodes[handle];
+            if value == node.value {
+                return handle;
+            }
+            parent = Some(handle);
+            current = if value < node.value {
+                node.left
+            } else {
+                node.right
+            };
+        }
+        let is_left = parent.is_some_and(|p| value < self.nodes[p].value);
+        let leaf = self.nodes.alloc(ArenaNode {
+            value,
+            parent,
+            left: None,
+            right: None,
+        });
+        match parent {
+            None => self.root = Some(leaf),
+            Some(p) if is_left => self.nodes[p].left = Some(leaf),
+            Some(p) => self.nodes[p].right = Some(leaf),
+        }
+        leaf
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        let mut current = self.root;
+        while let Some(handle) = current {
+            let node = &self.nodes[handle];
+            if *value == node.value {
+                return true;
+            }
+            current = if *value < node.value {
+                node.left
+            } else {
+                node.right
+            };
+        }
+        false
+    }
+
+    /// 通りがけ順（小さい順）に値を集める
+    pub fn in_order(&self) -> Vec<&T> {
+        fn walk<'a, T>(
+            nodes: &'a Arena<ArenaNode<T>>,
+            handle: Option<Handle<ArenaNode<T>>>,
+            out: &mut Vec<&'a T>,
+        ) {
+            if let Some(handle) = handle {
+                let node = &nodes[handle];
+                walk(nodes, node.left, out);
+                out.push(&node.value);
+                walk(nodes, node.right, out);
+            }
+        }
+        let mut out = Vec::with_capacity(self.len());
+        walk(&self.nodes, self.root, &mut out);
+        out
+    }
+
+    /// ノードから根までの値（親のハンドルをたどる）
+    pub fn path_to_root(&self, handle: Handle<ArenaNode<T>>) -> Vec<&T> {
+        let mut path = Vec::new();
+        let mut current = Some(handle);
+        while let Some(handle) = current {
+            path.push(&self.nodes[handle].value);
+            current = self.nodes[handle].parent;
+        }
+        path
+    }
+
+    pub fn node(&self, handle: Handle<ArenaNode<T>>) -> &ArenaNode<T> {
+        &self.nodes[handle]
+    }
+}
+
+impl<T: Ord> Default for ArenaBst<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ----------------------------------------------------------------------------
+// デモ
+// ----------------------------------------------------------------------------
+
+fn show<T: Debug>(label: &str, items: impl IntoIterator<Item = T>) {
+    let items: Vec<T> = items.into_iter().collect();
+    println!("{}: {:?}", label, items);
+}
+
+/// 単方向連結リスト
+pub fn linked_list_demo() {
+    println!("\n=== 単方向連結リスト（Box） ===");
+
+    let mut list = LinkedList::new();
+    for n in [3, 2, 1] {
+        list.push_front(n);
+    }
+    show("push_front(3, 2, 1)", list.iter());
+    println!("長さ: {}, 先頭: {:?}", list.len(), list.peek_front());
+    println!("contains(&2) = {}", list.contains(&2));
+
+    list.reverse();
+    show("reverse()", list.iter());
+
+    println!("pop_front() = {:?}", list.pop_front());
+    show("残り", list.iter());
+    while list.pop_front().is_some() {}
+    println!("すべて取り出した後 is_empty() = {}", list.is_empty());
+
+    // 10万要素でも Drop をループで書いているのでスタックは溢れない
+    let mut long = LinkedList::new();
+    for n in 0..100_000 {
+        long.push_front(n);
+    }
+    println!(
+        "10万要素のリストを作成して破棄します（len = {}）",
+        long.len()
+    );
+    drop(long);
+    println!("破棄完了");
+}
+
+/// スタックとキュー
+pub fn stack_queue_demo() {
+    println!("\n=== スタックとキュー ===");
+
+    let mut stack = Stack::new();
+    for c in ['a', 'b', 'c'] {
+        stack.push(c);
+    }
+    println!(
+        "スタックに a, b, c を push → len = {}, peek = {:?}",
+        stack.len(),
+        stack.peek()
+    );
+    let mut popped = Vec::new();
+    while let Some(c) = stack.pop() {
+        popped.push(c);
+    }
+    println!("pop した順（後入れ先出し）: {:?}", popped);
+
+    // スタックの応用: 括弧の対応チェック
+    fn balanced(text: &str) -> bool {
+        let mut stack = Stack::new();
+        for c in text.chars() {
+            match c {
+                '(' | '[' | '{' => stack.push(c),
+                ')' | ']' | '}' => {
+                    let expected = match c {
+                        ')' => '(',
+                        ']' => '[',
+                        _ => '{',
+                    };
+                    if stack.pop() != Some(expected) {
+                        return false;
+                    }
+                }
+                _ => {}
+            }
+        }
+        stack.is_empty()
+    }
+    for text in ["vec![(1, 2)]", "{[}]", "((())"] {
+        println!("  {:<14} 括弧の対応: {}", text, balanced(text));
+    }
+
+    let mut queue = Queue::new();
+    for task in ["ビルド", "テスト", "デプロイ"] {
+        queue.enqueue(task);
+    }
+    println!("キューに {} 件追加", queue.len());
+    print!("dequeue した順（先入れ先出し）:");
+    while !queue.is_empty() {
+        print!(" {}", queue.dequeue().unwrap());
+    }
+    println!();
+}
+
+/// 二分探索木
+pub fn bst_demo() {
+    println!("\n=== 二分探索木（Box） ===");
+
+    let mut tree = Bst::new();
+    println!("空の木: is_empty() = {}", tree.is_empty());
+    for n in [50, 30, 70, 20, 40, 60, 80, 30] {
+        let inserted = tree.insert(n);
+        if !inserted {
+            println!("{} はすでにあるので挿入しない", n);
+        }
+    }
+    show("通りがけ順", tree.in_order());
+    println!("要素数: {}, 高さ: {}", tree.len(), tree.height());
+    for n in [40, 45] {
+        println!("contains(&{}) = {}", n, tree.contains(&n));
+    }
+
+    // 整列済みの順に挿入すると、木が片側に伸びて連結リストと同じになる
+    let mut skewed = Bst::new();
+    for n in 1..=7 {
+        skewed.insert(n);
+    }
+    println!(
+        "1..=7 を順に挿入した木の高さ: {}（偏った木は探索が O(n)）",
+        skewed.height()
+    );
+}
+
+/// Rc<RefCell> で作る二分探索木
+pub fn rc_bst_demo() {
+    println!("\n=== 二分探索木（Rc<RefCell>） ===");
+
+    let mut tree = RcBst::new();
+    let mut handles = Vec::new();
+    for word in ["m", "f", "t", "b", "h"] {
+        handles.push(tree.insert(word.to_string()));
+    }
+    println!("通りがけ順: {:?}", tree.in_order());
+
+    // 木の外に持っているハンドルから、ノードの中身を書き換えられる
+    let h = &handles[4];
+    println!(
+        "ハンドル \"{}\" の参照カウント: {}（木の中 + handles）",
+        h.borrow().value,
+        Rc::strong_count(h)
+    );
+    h.borrow_mut().value.push_str("_edited");
+    println!("ハンドル経由で書き換え後: {:?}", tree.in_order());
+    println!(
+        "contains(\"h_edited\") = {}",
+        tree.contains(&"h_edited".to_string())
+    );
+
+    println!("Box版との違い:");
+    println!("  - Box: 親だけが子を所有。借用チェックはコンパイル時");
+    println!("  - Rc<RefCell>: 複数から共有でき、借用チェックは実行時（違反すると panic）");
+}
+
+/// アリーナで作る二分探索木
+pub fn arena_demo() {
+    println!("\n=== アリーナ（Vec + インデックス） ===");
+
+    let mut tree = ArenaBst::new();
+    let mut handles = Vec::new();
+    for n in [50, 30, 70, 20, 40, 60, 80] {
+        handles.push(tree.insert(n));
+    }
+    show("通りがけ順", tree.in_order());
+
+    // ハンドルはただの番号なので Copy でき、木を借用したままにもならない
+    let leaf = handles[4];
+    println!("{:?} の値: {}", leaf, tree.node(leaf).value);
+    // 親もハンドルで持てるので、Rc / Weak なしで根までさかのぼれる
+    show("40 から根まで", tree.path_to_root(leaf));
+    tree.insert(45);
+    println!(
+        "挿入した後も古いハンドルは使える: {:?} → {}",
+        leaf,
+        tree.node(leaf).value
+    );
+
+    // 同じ 1000 個の値を入れたときの確保の回数を比べる
+    let mut rng = lesson_api::random::Rng::new(7);
+    let values: Vec<u32> = (0..1000).map(|_| rng.below(1_000_000) as u32).collect();
+    let (_, boxed) = lesson_api::alloc_count::measure(|| {
+        let mut tree = Bst::new();
+        for &v in &values {
+            tree.insert(v);
+        }
+        tree
+    });
+    let (_, counted) = lesson_api::alloc_count::measure(|| {
+        let mut tree = RcBst::new();
+        for &v in &values {
+            tree.insert(v);
+        }
+        tree
+    });
+    let (_, arena) = lesson_api::alloc_count::measure(|| {
+        let mut tree = ArenaBst::new();
+        for &v in &values {
+            tree.insert(v);
+        }
+        tree
+    });
+    let (_, presized) = lesson_api::alloc_count::measure(|| {
+        let mut tree = ArenaBst::with_capacity(values.len());
+        for &v in &values {
+            tree.insert(v);
+        }
+        tree
+    });
+    if lesson_api::alloc_count::is_installed() {
+        println!("{}個の値を挿入したときの確保の回数:", values.len());
+        for (name, stats) in [
+            ("Box（ノードごと）", boxed),
+            ("Rc<RefCell>（ノードごと）", counted),
+            ("Arena（Vec が伸びるとき）", arena),
+            ("Arena::with_capacity", presized),
+        ] {
+            println!("  {:>6}回  {}", stats.allocations, name);
+        }
+    }
+
+    println!("アリーナの利点: 確保が少ない、ノードがメモリ上に並ぶ、親子の循環も書ける");
+    println!("注意点: 個別に削除できない（消すなら空き番号を再利用する仕組みが要る）");
+}
+
+/// チートシート用のデータ（`cheatsheet data_structures` で表示）
+pub const CHEATSHEET: CheatSheet = CheatSheet {
+    syntax: &[
+        (
+            "struct Node<T> { value: T, next: Option<Box<Node<T>>> }",
+            "再帰的なノード",
+        ),
+        (
+            "let next = self.head.take();",
+            "Option から所有権を取り出して None を残す",
+        ),
+        (
+            "while let Some(node) = current { ... }",
+            "ノードを順にたどる",
+        ),
+        (
+            "let mut slot = &mut self.root;",
+            "挿入位置への可変参照をたどる",
+        ),
+        (
+            "Rc<RefCell<Node<T>>>",
+            "共有 + 実行時に借用チェックする可変性",
+        ),
+        (
+            "struct Handle<T> { index: usize, _marker: PhantomData<fn() -> T> }",
+            "型付きのインデックス",
+        ),
+        (
+            "impl<T> Index<Handle<T>> for Arena<T>",
+            "arena[handle] で読めるようにする",
+        ),
+    ],
+    methods: &[
+        ("opt.take()", "値を取り出し None にする"),
+        ("opt.as_deref()", "Option<Box<T>> → Option<&T>"),
+        (
+            "Rc::clone(&rc)",
+            "参照カウントを増やす（中身はコピーしない）",
+        ),
+        (
+            "cell.borrow() / cell.borrow_mut()",
+            "RefCell の中身を借用する",
+        ),
+        ("Rc::strong_count(&rc)", "強い参照の数"),
+    ],
+    gotchas: &[
+        "既定の Drop は再帰的なので、長い連結リストはループで解放する",
+        "RefCell の borrow_mut を二重に取ると実行時に panic する",
+        "アリーナのハンドルは別のアリーナにも使えてしまう（型引数で区別するのが精一杯）",
+        "実際のコードでは Vec / VecDeque / BTreeMap を使うのが普通",
+    ],
+};
+
+/// すべてのデモを実行
+pub fn run_all() {
+    println!("╔════════════════════════════════════════════════════════════════╗");
+    println!("║          Rustデータ構造サンプル                                 ║");
+    println!("╚════════════════════════════════════════════════════════════════╝");
+
+    lesson_api::run_topic("data_structures");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn linked_list_push_pop_reverse() {
+        let mut list = LinkedList::new();
+        assert!(list.is_empty());
+        assert_eq!(list.pop_front(), None);
+
+        for n in 1..=4 {
+            list.push_front(n);
+        }
+        assert_eq!(list.len(), 4);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![4, 3, 2, 1]);
+        assert!(list.contains(&3));
+        assert!(!list.contains(&9));
+
+        list.reverse();
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+        assert_eq!(list.peek_front(), Some(&1));
+        assert_eq!(list.pop_front(), Some(1));
+        assert_eq!(list.len(), 3);
+    }
+
+    #[test]
+    fn linked_list_drops_long_list() {
+        let mut list = LinkedList::new();
+        for n in 0..1_000_000 {
+            list.push_front(n);
+        }
+        drop(list);
+    }
+
+    #[test]
+    fn stack_is_lifo_and_queue_is_fifo() {
+        let mut stack = Stack::new();
+        let mut queue = Queue::new();
+        for n in 1..=3 {
+            stack.push(n);
+            queue.enqueue(n);
+        }
+        assert_eq!(stack.peek(), Some(&3));
+        assert_eq!(stack.len(), 3);
+        assert_eq!(
+            [stack.pop(), stack.pop(), stack.pop()],
+            [Some(3), Some(2), Some(1)]
+        );
+        assert!(stack.is_empty());
+
+        assert_eq!(queue.dequeue(), Some(1));
+        queue.enqueue(4); // 取り出しの途中で追加しても順序は保たれる
+        assert_eq!(queue.len(), 3);
+        assert_eq!(
+            [queue.dequeue(), queue.dequeue(), queue.dequeue()],
+            [Some(2), Some(3), Some(4)]
+        );
+        assert_eq!(queue.dequeue(), None);
+    }
+
+    #[test]
+    fn bst_insert_search_traverse() {
+        let mut tree = Bst::new();
+        for n in [5, 3, 8, 1, 4, 9] {
+            assert!(tree.insert(n));
+        }
+        assert!(!tree.insert(4));
+        assert_eq!(tree.len(), 6);
+        assert_eq!(tree.in_order(), vec![&1, &3, &4, &5, &8, &9]);
+        assert!(tree.contains(&8));
+        assert!(!tree.contains(&7));
+        assert_eq!(tree.height(), 3);
+    }
+
+    #[test]
+    fn rc_bst_insert_search_traverse() {
+        let mut tree = RcBst::new();
+        for n in [5, 3, 8, 1, 4] {
+            tree.insert(n);
+        }
+        let existing = tree.insert(3);
+        assert_eq!(existing.borrow().value, 3);
+        assert_eq!(tree.in_order(), vec![1, 3, 4, 5, 8]);
+        assert!(tree.contains(&4));
+        assert!(!tree.contains(&6));
+
+        // 返されたハンドルは木の中のノードと同じもの
+        let leaf = tree.insert(9);
+        assert_eq!(Rc::strong_count(&leaf), 2);
+        leaf.borrow_mut().value = 10;
+        assert!(tree.contains(&10));
+    }
+
+    #[test]
+    fn arena_bst_insert_search_traverse() {
+        let mut tree = ArenaBst::new();
+        for n in [5, 3, 8, 1, 4] {
+            tree.insert(n);
+        }
+        let existing = tree.insert(3);
+        assert_eq!(tree.node(existing).value, 3);
+        assert_eq!(tree.len(), 5);
+        assert_eq!(tree.in_order(), vec![&1, &3, &4, &5, &8]);
+        assert!(tree.contains(&4));
+        assert!(!tree.contains(&6));
+
+        let leaf = tree.insert(9);
+        assert_eq!(tree.path_to_root(leaf), vec![&9, &8, &5]);
+        let eight = tree.insert(8);
+        assert_eq!(tree.node(leaf).parent, Some(eight));
+        assert!(tree.node(leaf).left.is_none());
+    }
+}
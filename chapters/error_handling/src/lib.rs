@@ -7,6 +7,9 @@
 // - 回復不能なエラー: panic! マクロ（プログラムを停止）
 // - 回復可能なエラー: Result<T, E> 型
 
+use lesson_api::{println, CheatSheet};
+use std::error::Error;
+use std::fmt;
 use std::fs::{self, File};
 use std::io::{self, ErrorKind, Read};
 
@@ -295,6 +298,104 @@ pub fn result_combinators() {
     println!("unwrap_or_else: {}", value);
 }
 
+// ----------------------------------------------------------------------------
+// Result の拡張トレイト
+// ----------------------------------------------------------------------------
+
+/// 元のエラーに「何をしていたか」を添えたエラー（anyhow の Context と同じ考え方）
+#[derive(Debug)]
+pub struct ContextError<E> {
+    context: String,
+    source: E,
+}
+
+impl<E> fmt::Display for ContextError<E> {
+    // 表示は自分の説明だけ。元のエラーは source() でたどる
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.context)
+    }
+}
+
+impl<E: Error + 'static> Error for ContextError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+/// 標準の Result にメソッドを足すトレイト（拡張トレイト）
+///
+/// 自分で定義したトレイトなら、標準の型にも impl できる（孤児ルールに反しない）
+pub trait ResultExt<T, E> {
+    /// Err のときだけ f に中身を見せる（値はそのまま返す）
+    fn tap_err(self, f: impl FnOnce(&E)) -> Self;
+
+    /// Err を ContextError で包む。説明はエラーのときだけ作る
+    fn with_context<C: fmt::Display>(self, f: impl FnOnce() -> C) -> Result<T, ContextError<E>>;
+}
+
+impl<T, E> ResultExt<T, E> for Result<T, E> {
+    fn tap_err(self, f: impl FnOnce(&E)) -> Self {
+        if let Err(e) = &self {
+            f(e);
+        }
+        self
+    }
+
+    fn with_context<C: fmt::Display>(self, f: impl FnOnce() -> C) -> Result<T, ContextError<E>> {
+        self.map_err(|source| ContextError {
+            context: f().to_string(),
+            source,
+        })
+    }
+}
+
+/// エラーと、その原因を source() でたどって表示する
+fn print_error_chain(error: &dyn Error) {
+    println!("エラー: {}", error);
+    let mut source = error.source();
+    while let Some(cause) = source {
+        println!("  原因: {}", cause);
+        source = cause.source();
+    }
+}
+
+/// 拡張トレイトでエラーに説明を添える
+pub fn result_extension_trait() {
+    println!("\n=== 拡張トレイトでエラーに説明を添える ===");
+
+    fn parse_port(text: &str) -> Result<u16, ContextError<std::num::ParseIntError>> {
+        text.trim()
+            .parse::<u16>()
+            .tap_err(|e| println!("  [ログ] parse に失敗: {:?}", e))
+            .with_context(|| format!("ポート番号 {:?} を読めません", text))
+    }
+
+    // use したトレイトのメソッドは、標準の Result に元からあったように呼べる
+    println!(
+        "parse_port(\"8080\") = {:?}",
+        parse_port("8080").map_err(|e| e.to_string())
+    );
+    if let Err(e) = parse_port("80800") {
+        print_error_chain(&e);
+    }
+
+    // with_context を重ねると、外側ほど大きな作業の説明になる
+    fn load_config(path: &str) -> Result<String, ContextError<ContextError<io::Error>>> {
+        fs::read_to_string(path)
+            .with_context(|| format!("{} を開けません", path))
+            .with_context(|| "設定を読み込めませんでした")
+    }
+    if let Err(e) = load_config("no_such_config.toml") {
+        print_error_chain(&e);
+    }
+
+    // 標準にも inspect_err（= tap_err）がある。自作するのは、名前と動きを自分で決めたいとき
+    let inspected: Result<i32, &str> =
+        Err("壊れた入力").inspect_err(|e| println!("  [ログ] {}", e));
+    println!("inspect_err の後も値はそのまま: {:?}", inspected);
+    println!("外部クレートなら anyhow::Context（.context / .with_context）が同じ役割です");
+}
+
 /// エラー処理のベストプラクティス
 pub fn best_practices() {
     println!("\n=== ベストプラクティス ===");
@@ -360,20 +461,86 @@ pub fn validation_pattern() {
     }
 }
 
+/// チートシート用のデータ（`cheatsheet error_handling` で表示）
+pub const CHEATSHEET: CheatSheet = CheatSheet {
+    syntax: &[
+        ("panic!(\"msg\")", "回復不能なエラー"),
+        ("fn f() -> Result<T, E>", "回復可能なエラーを返す"),
+        ("let x = f()?;", "エラーなら早期リターン（伝播）"),
+        ("text.lines().next()?", "Optionでも?が使える"),
+        (
+            "match err.kind() { ErrorKind::NotFound => .. }",
+            "エラーの種類で分岐",
+        ),
+        ("enum MyError { A, B }", "カスタムエラー型"),
+    ],
+    methods: &[
+        ("res.unwrap() / res.expect(\"msg\")", "Errならpanic"),
+        (
+            "res.unwrap_or(v) / unwrap_or_else(|e| ..)",
+            "Errならデフォルト値",
+        ),
+        ("res.map(|x| ..) / map_err(|e| ..)", "中身を変換"),
+        ("res.and_then(f) / or_else(f)", "Resultを返す処理をチェーン"),
+        (
+            "res.with_context(|| \"説明\")",
+            "エラーに説明を添える（この章の ResultExt）",
+        ),
+        ("err.source()", "エラーの原因をたどる"),
+        ("fs::read_to_string(path)", "ファイル全体を文字列で読む"),
+    ],
+    gotchas: &[
+        "?はErrの型を戻り値の型に変換できる場合のみ使える（From）",
+        "main以外でunwrapを乱用しない（ライブラリはResultを返す）",
+        "RUST_BACKTRACE=1 でpanic時のバックトレースを表示できる",
+    ],
+};
+
 /// すべてのデモを実行
 pub fn run_all() {
     println!("╔════════════════════════════════════════════════════════════════╗");
     println!("║          Rustエラーハンドリングサンプル                         ║");
     println!("╚════════════════════════════════════════════════════════════════╝");
 
-    panic_demo();
-    result_basics();
-    matching_on_different_errors();
-    unwrap_and_expect();
-    error_propagation();
-    question_mark_with_option();
-    custom_error_types();
-    result_combinators();
-    best_practices();
-    validation_pattern();
+    lesson_api::run_topic("error_handling");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tap_err_sees_only_errors_and_keeps_the_value() {
+        let mut seen = Vec::new();
+        let ok: Result<i32, &str> = Ok(1);
+        assert_eq!(ok.tap_err(|e| seen.push(e.to_string())), Ok(1));
+        let err: Result<i32, &str> = Err("壊れた");
+        assert_eq!(err.tap_err(|e| seen.push(e.to_string())), Err("壊れた"));
+        assert_eq!(seen, ["壊れた"]);
+    }
+
+    #[test]
+    fn with_context_wraps_the_error_and_keeps_the_source() {
+        let mut calls = 0;
+        let ok = "7".parse::<u16>().with_context(|| {
+            calls += 1;
+            "呼ばれない"
+        });
+        assert_eq!(ok.map_err(|e| e.to_string()), Ok(7));
+        // 説明はエラーのときだけ作る
+        assert_eq!(calls, 0);
+
+        let err = "x"
+            .parse::<u16>()
+            .with_context(|| "ポート番号")
+            .with_context(|| "設定")
+            .unwrap_err();
+        assert_eq!(err.to_string(), "設定");
+        let inner = err.source().unwrap();
+        assert_eq!(inner.to_string(), "ポート番号");
+        assert_eq!(
+            inner.source().unwrap().to_string(),
+            "invalid digit found in string"
+        );
+    }
 }
@@ -0,0 +1,12 @@
+// ============================================================================
+// ビルドスクリプト: 同梱の C ファイルをコンパイルする
+// ============================================================================
+//
+// cargo は src/ をコンパイルする前にこのファイルを実行する。
+// cc クレートが環境の C コンパイラ（cc / gcc / clang / MSVC）で c/gkmath.c を
+// libgkmath.a にし、リンクの指示（cargo:rustc-link-lib=static=gkmath）を出力する。
+
+fn main() {
+    println!("cargo:rerun-if-changed=c/gkmath.c");
+    cc::Build::new().file("c/gkmath.c").compile("gkmath");
+}
@@ -0,0 +1,57 @@
+/*
+ * ffi の章で Rust から呼び出す C の関数（build.rs がコンパイルする）
+ *
+ * Rust 側の宣言は src/lib.rs の extern "C" ブロックにある。
+ * 型を変えたら両方を直すこと（コンパイラは食い違いを検出できない）。
+ */
+
+#include <stddef.h>
+#include <stdint.h>
+#include <stdio.h>
+
+/* Rust 側で #[no_mangle] pub extern "C" として定義している関数 */
+int32_t gk_rust_triple(int32_t x);
+
+int32_t gk_add(int32_t a, int32_t b) {
+    return a + b;
+}
+
+/* スライスはポインタと長さの2つで受け取る */
+double gk_average(const int32_t *values, size_t len) {
+    if (values == NULL || len == 0) {
+        return 0.0;
+    }
+    int64_t sum = 0;
+    for (size_t i = 0; i < len; i++) {
+        sum += values[i];
+    }
+    return (double)sum / (double)len;
+}
+
+/* NUL 終端の文字列（ASCII）の母音を数える */
+size_t gk_count_vowels(const char *s) {
+    size_t count = 0;
+    for (; *s != '\0'; s++) {
+        switch (*s) {
+        case 'a': case 'e': case 'i': case 'o': case 'u':
+        case 'A': case 'E': case 'I': case 'O': case 'U':
+            count++;
+        }
+    }
+    return count;
+}
+
+/* 呼び出し側が用意したバッファに書き込む。必要な長さ（NUL を除く）を返す */
+int gk_greet(const char *name, char *out, size_t out_len) {
+    return snprintf(out, out_len, "Hello, %s!", name);
+}
+
+/* Rust から渡された関数ポインタ（コールバック）を呼ぶ */
+int32_t gk_apply(int32_t (*f)(int32_t), int32_t x) {
+    return f(f(x));
+}
+
+/* C から Rust の関数を呼ぶ */
+int32_t gk_call_rust(int32_t x) {
+    return gk_rust_triple(x) + 1;
+}
@@ -0,0 +1,252 @@
+// ============================================================================
+// Rust FFI サンプル - C との相互運用
+// 公式ドキュメント: https://doc.rust-lang.org/book/ch20-01-unsafe-rust.html#using-extern-functions-to-call-external-code
+// ============================================================================
+//
+// FFI（Foreign Function Interface）で、C の関数を Rust から呼び、
+// Rust の関数を C から呼ぶ。
+//
+// - extern "C" { fn ...; }       C の関数の宣言（呼び出しは unsafe）
+// - #[no_mangle] extern "C" fn   C から呼べる Rust の関数
+// - CString / CStr               Rust の文字列と NUL 終端の文字列の変換
+//
+// C の関数は c/gkmath.c にあり、build.rs がコンパイルしてリンクする。
+
+use lesson_api::{println, CheatSheet};
+use std::ffi::{c_char, c_int, CStr, CString};
+
+// 標準 C ライブラリ（libc）の関数。Rust の std が libc をリンクしているので宣言だけで呼べる
+extern "C" {
+    fn abs(x: c_int) -> c_int;
+    fn strlen(s: *const c_char) -> usize;
+    fn atoi(s: *const c_char) -> c_int;
+}
+
+// c/gkmath.c の関数（build.rs が libgkmath.a をリンクする）
+extern "C" {
+    fn gk_add(a: i32, b: i32) -> i32;
+    fn gk_average(values: *const i32, len: usize) -> f64;
+    fn gk_count_vowels(s: *const c_char) -> usize;
+    fn gk_greet(name: *const c_char, out: *mut c_char, out_len: usize) -> c_int;
+    fn gk_apply(f: extern "C" fn(i32) -> i32, x: i32) -> i32;
+    fn gk_call_rust(x: i32) -> i32;
+}
+
+/// C から呼ばれる Rust の関数（gkmath.c の gk_call_rust が呼ぶ）
+/// #[no_mangle] でシンボル名をそのまま残し、extern "C" で C の呼び出し規約にする
+#[no_mangle]
+pub extern "C" fn gk_rust_triple(x: i32) -> i32 {
+    x * 3
+}
+
+// C に渡すコールバック
+extern "C" fn square(x: i32) -> i32 {
+    x * x
+}
+
+/// 安全なラッパー: unsafe な呼び出しを関数の中に閉じ込める
+pub fn average(values: &[i32]) -> f64 {
+    // SAFETY: ポインタと長さはスライスから取ったもので、C 側は読むだけ
+    unsafe { gk_average(values.as_ptr(), values.len()) }
+}
+
+/// 安全なラッパー: Rust の文字列を CString にしてから渡す
+/// 途中に NUL を含む文字列は C に渡せないので None
+pub fn count_vowels(s: &str) -> Option<usize> {
+    let c_string = CString::new(s).ok()?;
+    // SAFETY: c_string は NUL 終端で、呼び出しの間は生きている
+    Some(unsafe { gk_count_vowels(c_string.as_ptr()) })
+}
+
+/// 安全なラッパー: C が書き込んだバッファを CStr として読む
+pub fn greet(name: &str) -> Option<String> {
+    let name = CString::new(name).ok()?;
+    let mut buffer = [0 as c_char; 32];
+    // SAFETY: バッファの長さを渡しているので、C はそれを超えて書かない
+    let needed = unsafe { gk_greet(name.as_ptr(), buffer.as_mut_ptr(), buffer.len()) };
+    // SAFETY: snprintf は必ず NUL 終端にする
+    let text = unsafe { CStr::from_ptr(buffer.as_ptr()) };
+    let mut greeting = text.to_string_lossy().into_owned();
+    if needed as usize >= buffer.len() {
+        greeting.push('…');
+    }
+    Some(greeting)
+}
+
+/// libc の関数を呼ぶ
+pub fn calling_libc() {
+    println!("\n=== libc の関数を呼ぶ ===");
+
+    // extern ブロックの関数は、Rust のコンパイラが中身を確かめられないので unsafe
+    // SAFETY: abs は任意の int を受け取れる（INT_MIN を除く）
+    let n = unsafe { abs(-42) };
+    println!("abs(-42) = {}", n);
+
+    // C の文字列は NUL（'\0'）で終わるバイト列。c"..." リテラルで &CStr を作れる
+    let number = c"12345";
+    // SAFETY: number は NUL 終端で 'static
+    let (len, value) = unsafe { (strlen(number.as_ptr()), atoi(number.as_ptr())) };
+    println!("strlen(\"12345\") = {}, atoi(\"12345\") = {}", len, value);
+
+    // c_int、c_char などは C の型に合わせた別名（c_int は多くの環境で i32）
+    println!(
+        "c_int は {} バイト、c_char は {} バイト",
+        std::mem::size_of::<c_int>(),
+        std::mem::size_of::<c_char>()
+    );
+}
+
+/// 同梱の C ファイルを呼ぶ（build.rs）
+pub fn bundled_c_library() {
+    println!("\n=== 同梱の C ファイルを呼ぶ（build.rs） ===");
+
+    // SAFETY: 整数を2つ受け取って返すだけの関数
+    let sum = unsafe { gk_add(2, 40) };
+    println!("gk_add(2, 40) = {}", sum);
+
+    // スライスを渡すときはポインタと長さに分ける（C は長さを知らない）
+    let scores = [72, 85, 91, 64];
+    println!("gk_average({:?}) = {:.2}", scores, average(&scores));
+    println!("空のスライス: {}", average(&[]));
+
+    // unsafe を毎回書かなくて済むように、安全なラッパーを公開するのが定番
+    println!(
+        "count_vowels(\"Programming in Rust\") = {:?}",
+        count_vowels("Programming in Rust")
+    );
+    println!("greet(\"Ferris\") = {:?}", greet("Ferris"));
+    println!(
+        "greet（32バイトに入りきらない名前）= {:?}",
+        greet("Ferris the friendly crab of Rust")
+    );
+}
+
+/// CString と CStr
+pub fn cstring_and_cstr() {
+    println!("\n=== CString と CStr ===");
+
+    // Rust → C: CString（所有する、末尾に NUL を足す）
+    let owned = CString::new("hello").unwrap();
+    println!(
+        "CString: {:?}（バイト列: {:?}）",
+        owned,
+        owned.as_bytes_with_nul()
+    );
+
+    // 途中に NUL がある文字列は C の文字列にできない
+    match CString::new("hel\0lo") {
+        Ok(s) => println!("作れた: {:?}", s),
+        Err(e) => println!("途中の NUL はエラー: {}（位置 {}）", e, e.nul_position()),
+    }
+
+    // C → Rust: CStr（借用）。UTF-8 とは限らないので to_str は Result
+    let bytes = b"caf\xe9\0"; // Latin-1 の é
+    let c_str = CStr::from_bytes_with_nul(bytes).unwrap();
+    println!("to_str: {:?}", c_str.to_str().map_err(|e| e.to_string()));
+    println!("to_string_lossy: {:?}", c_str.to_string_lossy());
+
+    // ハマりどころ: 一時的な CString のポインタ
+    //   let p = CString::new("hi").unwrap().as_ptr();   // この行の終わりで CString が解放される
+    //   unsafe { strlen(p) }                             // 解放済みのメモリを読む（未定義動作）
+    // ポインタを使い終わるまで、CString を変数に入れて生かしておく
+    let kept = CString::new("hi").unwrap();
+    // SAFETY: kept はこのブロックの最後まで生きている
+    let len = unsafe { strlen(kept.as_ptr()) };
+    println!("CString を変数に入れてから as_ptr(): strlen = {}", len);
+
+    // C に所有権を渡すときは into_raw、戻すときは from_raw（C の free で解放してはいけない）
+    let raw = CString::new("owned by C").unwrap().into_raw();
+    // SAFETY: raw は直前の into_raw で得たもの
+    let back = unsafe { CString::from_raw(raw) };
+    println!("into_raw → from_raw: {:?}", back);
+}
+
+/// Rust の関数を C から呼ぶ（#[no_mangle]）
+pub fn exporting_rust() {
+    println!("\n=== Rust の関数を C から呼ぶ（#[no_mangle]） ===");
+
+    // gk_call_rust（C）の中から gk_rust_triple（Rust）が呼ばれる
+    // SAFETY: gk_rust_triple は上で定義している
+    let result = unsafe { gk_call_rust(5) };
+    println!("gk_call_rust(5) = gk_rust_triple(5) + 1 = {}", result);
+
+    // extern "C" fn は C の関数ポインタとして渡せる（クロージャは渡せない）
+    // SAFETY: square は extern "C" で、パニックしない
+    let applied = unsafe { gk_apply(square, 3) };
+    println!("gk_apply(square, 3) = square(square(3)) = {}", applied);
+
+    // C の側に Rust のパニックを伝えてはいけない（extern "C" 関数でのパニックは abort する）
+    println!(
+        "コールバックの中で panic すると、巻き戻しが C をまたげないのでプロセスが abort します"
+    );
+}
+
+/// チートシート用のデータ（`cheatsheet ffi` で表示）
+pub const CHEATSHEET: CheatSheet = CheatSheet {
+    syntax: &[
+        (
+            "extern \"C\" { fn abs(x: c_int) -> c_int; }",
+            "C の関数を宣言する",
+        ),
+        ("unsafe { abs(-1) }", "extern の関数の呼び出しは unsafe"),
+        (
+            "#[no_mangle] pub extern \"C\" fn f()",
+            "C から呼べる関数を公開する",
+        ),
+        (
+            "extern \"C\" fn(i32) -> i32",
+            "C の関数ポインタ（コールバック）の型",
+        ),
+        ("c\"text\"", "&CStr のリテラル（NUL 終端）"),
+        (
+            "cc::Build::new().file(\"x.c\").compile(\"x\")",
+            "build.rs で C をコンパイルする",
+        ),
+    ],
+    methods: &[
+        (
+            "CString::new(s)?",
+            "Rust の文字列 → C の文字列（途中の NUL はエラー）",
+        ),
+        (
+            "c_string.as_ptr()",
+            "*const c_char を得る（CString より長く使わない）",
+        ),
+        ("CStr::from_ptr(p)", "C の文字列を借用する（unsafe）"),
+        ("c_str.to_str() / to_string_lossy()", "UTF-8 として読む"),
+        ("into_raw() / from_raw(p)", "所有権を C に渡す / 取り戻す"),
+    ],
+    gotchas: &[
+        "CString::new(..).unwrap().as_ptr() はすぐに解放される（ダングリングポインタ）",
+        "extern ブロックの型が C と食い違ってもコンパイラは気付かない",
+        "スライスはポインタと長さの2つで渡す",
+        "Rust で確保したメモリを C の free で解放しない（逆も同じ）",
+        "パニックを C に伝えない。extern \"C\" 関数でのパニックは abort になる",
+    ],
+};
+
+/// すべてのデモを実行
+pub fn run_all() {
+    println!("╔════════════════════════════════════════════════════════════════╗");
+    println!("║          Rust FFI（C との相互運用）サンプル                      ║");
+    println!("╚════════════════════════════════════════════════════════════════╝");
+
+    lesson_api::run_topic("ffi");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn safe_wrappers_call_into_c() {
+        assert_eq!(average(&[1, 2, 3, 4]), 2.5);
+        assert_eq!(average(&[]), 0.0);
+        assert_eq!(count_vowels("Programming in Rust"), Some(5));
+        assert_eq!(count_vowels("a\0b"), None);
+        assert_eq!(greet("Ferris").as_deref(), Some("Hello, Ferris!"));
+        assert!(greet(&"x".repeat(40)).unwrap().ends_with('…'));
+        // SAFETY: どちらも上で宣言・定義した関数
+        assert_eq!(unsafe { gk_call_rust(5) }, 16);
+    }
+}
@@ -0,0 +1,362 @@
+// ============================================================================
+// Rust ファイル I/O サンプル - BufReader、BufWriter、std::fs
+// 公式ドキュメント: https://doc.rust-lang.org/std/fs/index.html
+//                   https://doc.rust-lang.org/std/io/index.html
+// ============================================================================
+//
+// error_handling の章では File::open の Result を扱った。この章はその続きで、
+// 実際にファイルを読み書きするときの道具をひととおり使う。
+//   - BufWriter / BufReader  小さな read / write をまとめて、システムコールの回数を減らす
+//   - OpenOptions            追記や「無ければ作る」などの開き方を指定する
+//   - fs::copy / rename / metadata / read_dir / remove_*  ファイルとディレクトリの操作
+//
+// ファイルはすべて一時ディレクトリ（env::temp_dir()）の下に作り、
+// TempDir を drop したときにディレクトリごと削除する（RAII）。
+//
+// wasm32-unknown-unknown（ブラウザ版）にはファイルシステムが無く、temp_dir() は panic するので、
+// 説明だけを表示する。行を数える line_stats は BufRead なら何でも受け取れるのでそのまま動く。
+
+use lesson_api::{println, CheatSheet};
+use std::env;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, BufWriter, Cursor, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+
+// wasm32-unknown-unknown（ブラウザ版）にはファイルシステムが無い
+fn fs_unavailable() -> bool {
+    let unavailable = cfg!(target_arch = "wasm32");
+    if unavailable {
+        println!("ブラウザ版ではファイルを扱えません。cargo run で試してください");
+    }
+    unavailable
+}
+
+// ----------------------------------------------------------------------------
+// 後片付けをする一時ディレクトリ
+// ----------------------------------------------------------------------------
+
+/// drop されたときに中身ごと削除される一時ディレクトリ
+pub struct TempDir {
+    path: PathBuf,
+}
+
+impl TempDir {
+    /// `<temp_dir>/gk_rust_practice_<name>_<プロセス ID>` を作る
+    pub fn new(name: &str) -> io::Result<TempDir> {
+        let path = env::temp_dir().join(format!("gk_rust_practice_{}_{}", name, process::id()));
+        fs::create_dir_all(&path)?;
+        Ok(TempDir { path })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// ディレクトリの中のファイルのパス
+    pub fn join(&self, file: &str) -> PathBuf {
+        self.path.join(file)
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        // drop の中ではエラーを返せないので、失敗しても無視する
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
+
+// ----------------------------------------------------------------------------
+// BufWriter で書き、BufReader で1行ずつ読む
+// ----------------------------------------------------------------------------
+
+/// 行をまとめてファイルに書く（既にあれば上書き）
+pub fn write_lines(path: &Path, lines: &[&str]) -> io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    for line in lines {
+        writeln!(writer, "{}", line)?;
+    }
+    // drop でも書き出されるが、そのときのエラーは捨てられる。flush して確かめる
+    writer.flush()
+}
+
+/// 行数・単語数・いちばん長い行
+#[derive(Debug, PartialEq)]
+pub struct LineStats {
+    pub lines: usize,
+    pub words: usize,
+    pub longest: String,
+}
+
+/// BufRead から1行ずつ読んで数える（ファイル全体をメモリに載せない）
+pub fn line_stats(reader: impl BufRead) -> io::Result<LineStats> {
+    let mut stats = LineStats {
+        lines: 0,
+        words: 0,
+        longest: String::new(),
+    };
+    for line in reader.lines() {
+        let line = line?;
+        stats.lines += 1;
+        stats.words += line.split_whitespace().count();
+        if line.chars().count() > stats.longest.chars().count() {
+            stats.longest = line;
+        }
+    }
+    Ok(stats)
+}
+
+/// BufWriter で書き、BufReader で1行ずつ読む
+pub fn buffered_read_write() {
+    println!("\n=== BufWriter で書き、BufReader で1行ずつ読む ===");
+
+    // line_stats は BufRead を受け取るので、メモリ上の Cursor でもファイルでも同じように使える
+    let stats = line_stats(Cursor::new("一行目\nsecond line here\n\nlast"));
+    println!("Cursor から: {:?}", stats);
+
+    if fs_unavailable() {
+        return;
+    }
+    let run = || -> io::Result<()> {
+        let dir = TempDir::new("buffered")?;
+        let path = dir.join("poem.txt");
+        write_lines(
+            &path,
+            &[
+                "I'm nobody! Who are you?",
+                "Are you nobody, too?",
+                "Then there's a pair of us - don't tell!",
+            ],
+        )?;
+        println!("書き込み: {}", path.display());
+
+        // lines() は行末の改行を取り除いた String を返す
+        let reader = BufReader::new(File::open(&path)?);
+        for (number, line) in reader.lines().enumerate() {
+            println!("  {:>2}: {}", number + 1, line?);
+        }
+
+        let stats = line_stats(BufReader::new(File::open(&path)?))?;
+        println!(
+            "{} 行、{} 語、いちばん長い行: {:?}",
+            stats.lines, stats.words, stats.longest
+        );
+        Ok(())
+    };
+    if let Err(e) = run() {
+        println!("ファイルの読み書きに失敗しました: {}", e);
+    }
+}
+
+// ----------------------------------------------------------------------------
+// 追記、コピー、名前の変更
+// ----------------------------------------------------------------------------
+
+/// ファイルの末尾に1行追記する（無ければ作る）
+pub fn append_line(path: &Path, line: &str) -> io::Result<()> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", line)
+}
+
+/// 追記、コピー、名前の変更
+pub fn append_copy_rename() {
+    println!("\n=== 追記、コピー、名前の変更 ===");
+    if fs_unavailable() {
+        return;
+    }
+
+    let run = || -> io::Result<()> {
+        let dir = TempDir::new("append")?;
+        let log = dir.join("app.log");
+
+        // File::create は中身を消してしまう。追記は OpenOptions の append(true)
+        for message in ["起動しました", "設定を読み込みました", "終了しました"]
+        {
+            append_line(&log, message)?;
+        }
+        println!("app.log:\n{}", fs::read_to_string(&log)?.trim_end());
+
+        // fs::copy はコピーしたバイト数を返す
+        let backup = dir.join("app.log.bak");
+        let bytes = fs::copy(&log, &backup)?;
+        println!("\nfs::copy: {} バイトをコピーしました", bytes);
+
+        // 同じファイルシステムの中なら rename は中身をコピーせずに移動する
+        let renamed = dir.join("old.log");
+        fs::rename(&backup, &renamed)?;
+        println!("fs::rename の後: app.log.bak の存在 = {}", backup.exists());
+
+        // fs::read はバイト列、fs::read_to_string は UTF-8 の文字列
+        let raw = fs::read(&renamed)?;
+        println!(
+            "old.log の先頭3バイト（「起」の UTF-8）: {:02x?}",
+            &raw[..3]
+        );
+        Ok(())
+    };
+    if let Err(e) = run() {
+        println!("ファイル操作に失敗しました: {}", e);
+    }
+}
+
+// ----------------------------------------------------------------------------
+// メタデータ、ディレクトリ、後片付け
+// ----------------------------------------------------------------------------
+
+/// メタデータとディレクトリの一覧、後片付け
+pub fn metadata_and_directories() {
+    println!("\n=== メタデータとディレクトリ、後片付け ===");
+    if fs_unavailable() {
+        return;
+    }
+
+    let run = || -> io::Result<PathBuf> {
+        let dir = TempDir::new("metadata")?;
+        fs::create_dir_all(dir.join("src/bin"))?;
+        fs::write(dir.join("Cargo.toml"), "[package]\nname = \"demo\"\n")?;
+        fs::write(dir.join("src/main.rs"), "fn main() {}\n")?;
+
+        let meta = fs::metadata(dir.join("Cargo.toml"))?;
+        println!(
+            "Cargo.toml: {} バイト、is_file = {}",
+            meta.len(),
+            meta.is_file()
+        );
+        println!("読み取り専用: {}", meta.permissions().readonly());
+        if let Ok(modified) = meta.modified() {
+            println!(
+                "更新からの経過時間: {:?}",
+                modified.elapsed().unwrap_or_default()
+            );
+        }
+
+        // read_dir の順番は決まっていないので、並べ替えてから表示する
+        let mut entries = fs::read_dir(dir.path())?
+            .map(|entry| {
+                let entry = entry?;
+                let kind = if entry.file_type()?.is_dir() {
+                    "dir "
+                } else {
+                    "file"
+                };
+                Ok(format!("{} {}", kind, entry.file_name().to_string_lossy()))
+            })
+            .collect::<io::Result<Vec<String>>>()?;
+        entries.sort();
+        println!("read_dir:");
+        for entry in &entries {
+            println!("  {}", entry);
+        }
+
+        // 空のディレクトリは remove_dir、中身ごとなら remove_dir_all
+        fs::remove_file(dir.join("src/main.rs"))?;
+        fs::remove_dir(dir.join("src/bin"))?;
+        println!(
+            "remove_file / remove_dir の後の src: {} 個",
+            fs::read_dir(dir.join("src"))?.count()
+        );
+
+        // 存在しないファイルのメタデータは NotFound
+        match fs::metadata(dir.join("missing.txt")) {
+            Ok(_) => println!("missing.txt が見つかりました"),
+            Err(e) => println!("missing.txt: {:?}", e.kind()),
+        }
+        Ok(dir.path().to_path_buf())
+        // ここで dir が drop され、ディレクトリごと削除される
+    };
+    match run() {
+        Ok(path) => println!("TempDir を drop した後の存在: {}", path.exists()),
+        Err(e) => println!("ファイル操作に失敗しました: {}", e),
+    }
+}
+
+pub const CHEATSHEET: CheatSheet = CheatSheet {
+    syntax: &[
+        (
+            "let mut w = BufWriter::new(File::create(path)?);",
+            "まとめて書く（最後に flush）",
+        ),
+        (
+            "for line in BufReader::new(File::open(path)?).lines() { ... }",
+            "1行ずつ読む",
+        ),
+        (
+            "OpenOptions::new().create(true).append(true).open(path)?",
+            "追記で開く",
+        ),
+        (
+            "fs::read_to_string(path)? / fs::write(path, data)?",
+            "小さなファイルを一度に読み書きする",
+        ),
+    ],
+    methods: &[
+        ("fs::copy(from, to)?", "コピーしてバイト数を返す"),
+        ("fs::rename(from, to)?", "名前の変更・移動"),
+        (
+            "fs::metadata(path)?.len()",
+            "サイズ（is_file / is_dir / modified も）",
+        ),
+        (
+            "fs::read_dir(dir)?",
+            "ディレクトリの中身（順番は決まっていない）",
+        ),
+        (
+            "fs::create_dir_all(path)?",
+            "途中のディレクトリもまとめて作る",
+        ),
+        (
+            "fs::remove_file / remove_dir / remove_dir_all",
+            "ファイル・空のディレクトリ・中身ごと削除",
+        ),
+    ],
+    gotchas: &[
+        "File::create は既存のファイルを空にする。追記は OpenOptions の append",
+        "BufWriter を drop したときの書き込みエラーは捨てられる。flush で確かめる",
+        "BufReader なしの File に小さな read を繰り返すと、そのたびにシステムコールになる",
+        "lines() は行末の改行を取り除き、UTF-8 でない行はエラーになる",
+    ],
+};
+
+/// すべてのデモを実行
+pub fn run_all() {
+    println!("╔════════════════════════════════════════════════════════════════╗");
+    println!("║          Rust ファイル I/O サンプル                              ║");
+    println!("╚════════════════════════════════════════════════════════════════╝");
+
+    lesson_api::run_topic("file_io");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_lines_and_words() {
+        let stats = line_stats(Cursor::new("a b c\n\nこんにちは 世界\n")).unwrap();
+        assert_eq!(
+            stats,
+            LineStats {
+                lines: 3,
+                words: 5,
+                longest: "こんにちは 世界".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn writes_appends_and_cleans_up() {
+        let path = {
+            let dir = TempDir::new("test").unwrap();
+            let file = dir.join("notes.txt");
+            write_lines(&file, &["one", "two"]).unwrap();
+            append_line(&file, "three").unwrap();
+            assert_eq!(fs::read_to_string(&file).unwrap(), "one\ntwo\nthree\n");
+
+            // File::create で書き直すと、追記した行も消える
+            write_lines(&file, &["fresh"]).unwrap();
+            assert_eq!(fs::read_to_string(&file).unwrap(), "fresh\n");
+            dir.path().to_path_buf()
+        };
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,343 @@
+// ============================================================================
+// Rustグラフサンプル - 隣接リスト、BFS/DFS、ダイクストラ法
+// 公式ドキュメント: https://doc.rust-lang.org/std/collections/index.html
+// ============================================================================
+//
+// HashMap（頂点 → 辺のリスト）、Vec、VecDeque、HashSet、BinaryHeap が
+// 1つのアルゴリズムの中でどう協力するかを見る。
+//
+//   東京 ─3─ 横浜 ─4─ 小田原
+//    │         │          │
+//    5         2          6
+//    │         │          │
+//   大宮 ─7─ 町田 ─9─ 甲府        名古屋（どこともつながっていない）
+
+use lesson_api::{println, CheatSheet};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+
+/// 重み付き無向グラフ（隣接リスト）
+pub struct Graph<'a> {
+    adjacency: HashMap<&'a str, Vec<(&'a str, u32)>>,
+}
+
+impl<'a> Graph<'a> {
+    pub fn new() -> Self {
+        Graph {
+            adjacency: HashMap::new(),
+        }
+    }
+
+    /// 頂点を追加する（辺を追加するときにも自動で追加される）
+    pub fn add_node(&mut self, node: &'a str) {
+        self.adjacency.entry(node).or_default();
+    }
+
+    /// 無向の辺を追加する（両方向に登録する）
+    pub fn add_edge(&mut self, a: &'a str, b: &'a str, weight: u32) {
+        self.adjacency.entry(a).or_default().push((b, weight));
+        self.adjacency.entry(b).or_default().push((a, weight));
+    }
+
+    pub fn neighbors(&self, node: &str) -> &[(&'a str, u32)] {
+        self.adjacency
+            .get(node)
+            .map_or(&[], |edges| edges.as_slice())
+    }
+
+    /// 頂点の一覧（表示用に名前順）
+    pub fn nodes(&self) -> Vec<&'a str> {
+        let mut nodes: Vec<&str> = self.adjacency.keys().copied().collect();
+        nodes.sort();
+        nodes
+    }
+
+    /// 幅優先探索: 近い順に訪問する（キュー）
+    pub fn bfs(&self, start: &'a str) -> Vec<&'a str> {
+        let mut visited = HashSet::from([start]);
+        let mut queue = VecDeque::from([start]);
+        let mut order = Vec::new();
+        while let Some(node) = queue.pop_front() {
+            order.push(node);
+            for &(next, _) in self.neighbors(node) {
+                // insert は新しく追加できたときだけ true を返す
+                if visited.insert(next) {
+                    queue.push_back(next);
+                }
+            }
+        }
+        order
+    }
+
+    /// 辺の本数が最少の経路（重みは無視）。BFS で親をたどって復元する
+    pub fn fewest_hops(&self, start: &'a str, goal: &str) -> Option<Vec<&'a str>> {
+        let mut parent: HashMap<&str, &str> = HashMap::new();
+        let mut visited = HashSet::from([start]);
+        let mut queue = VecDeque::from([start]);
+        while let Some(node) = queue.pop_front() {
+            if node == goal {
+                return Some(build_path(&parent, node));
+            }
+            for &(next, _) in self.neighbors(node) {
+                if visited.insert(next) {
+                    parent.insert(next, node);
+                    queue.push_back(next);
+                }
+            }
+        }
+        None
+    }
+
+    /// 深さ優先探索（再帰版）: 行けるところまで進んでから戻る
+    pub fn dfs(&self, start: &'a str) -> Vec<&'a str> {
+        fn visit<'a>(
+            graph: &Graph<'a>,
+            node: &'a str,
+            visited: &mut HashSet<&'a str>,
+            order: &mut Vec<&'a str>,
+        ) {
+            if !visited.insert(node) {
+                return;
+            }
+            order.push(node);
+            for &(next, _) in graph.neighbors(node) {
+                visit(graph, next, visited, order);
+            }
+        }
+        let mut visited = HashSet::new();
+        let mut order = Vec::new();
+        visit(self, start, &mut visited, &mut order);
+        order
+    }
+
+    /// 深さ優先探索（スタック版）: 再帰をスタック（Vec）に置き換える
+    pub fn dfs_iterative(&self, start: &'a str) -> Vec<&'a str> {
+        let mut visited = HashSet::new();
+        let mut stack = vec![start];
+        let mut order = Vec::new();
+        while let Some(node) = stack.pop() {
+            if !visited.insert(node) {
+                continue;
+            }
+            order.push(node);
+            // 再帰版と同じ順に訪問するため、逆順に積む
+            for &(next, _) in self.neighbors(node).iter().rev() {
+                if !visited.contains(next) {
+                    stack.push(next);
+                }
+            }
+        }
+        order
+    }
+
+    /// ダイクストラ法: 重みの合計が最小の経路とその距離
+    pub fn dijkstra(&self, start: &'a str, goal: &str) -> Option<(u32, Vec<&'a str>)> {
+        let mut dist: HashMap<&str, u32> = HashMap::from([(start, 0)]);
+        let mut parent: HashMap<&str, &str> = HashMap::new();
+        // BinaryHeap は最大ヒープなので Reverse で「距離の小さい順」にする
+        let mut heap = BinaryHeap::from([Reverse((0, start))]);
+
+        while let Some(Reverse((d, node))) = heap.pop() {
+            if node == goal {
+                return Some((d, build_path(&parent, node)));
+            }
+            // すでにもっと短い距離が見つかっている古いエントリは読み飛ばす
+            if d > dist[node] {
+                continue;
+            }
+            for &(next, weight) in self.neighbors(node) {
+                let candidate = d + weight;
+                if dist.get(next).is_none_or(|&current| candidate < current) {
+                    dist.insert(next, candidate);
+                    parent.insert(next, node);
+                    heap.push(Reverse((candidate, next)));
+                }
+            }
+        }
+        None
+    }
+}
+
+impl Default for Graph<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// 親をたどってゴールからスタートまで戻り、逆順にする
+fn build_path<'a>(parent: &HashMap<&str, &'a str>, goal: &'a str) -> Vec<&'a str> {
+    let mut path = vec![goal];
+    let mut current = goal;
+    while let Some(&prev) = parent.get(current) {
+        path.push(prev);
+        current = prev;
+    }
+    path.reverse();
+    path
+}
+
+/// 冒頭の図の路線図
+fn sample_graph() -> Graph<'static> {
+    let mut graph = Graph::new();
+    graph.add_edge("東京", "横浜", 3);
+    graph.add_edge("東京", "大宮", 5);
+    graph.add_edge("横浜", "小田原", 4);
+    graph.add_edge("横浜", "町田", 2);
+    graph.add_edge("大宮", "町田", 7);
+    graph.add_edge("町田", "甲府", 9);
+    graph.add_edge("小田原", "甲府", 6);
+    graph.add_node("名古屋");
+    graph
+}
+
+/// 隣接リストによるグラフ表現
+pub fn adjacency_list_demo() {
+    println!("\n=== 隣接リスト ===");
+
+    let graph = sample_graph();
+    println!("HashMap<&str, Vec<(&str, u32)>> で「頂点 → (隣の頂点, 重み)」を持つ:");
+    for node in graph.nodes() {
+        let edges: Vec<String> = graph
+            .neighbors(node)
+            .iter()
+            .map(|(next, weight)| format!("{}({})", next, weight))
+            .collect();
+        println!("  {} → [{}]", node, edges.join(", "));
+    }
+}
+
+/// 幅優先探索と深さ優先探索
+pub fn traversal_demo() {
+    println!("\n=== 幅優先探索（BFS）と深さ優先探索（DFS） ===");
+
+    let graph = sample_graph();
+    println!("BFS（VecDeque）      : {}", graph.bfs("東京").join(" → "));
+    println!("DFS（再帰）          : {}", graph.dfs("東京").join(" → "));
+    println!(
+        "DFS（Vec をスタックに）: {}",
+        graph.dfs_iterative("東京").join(" → ")
+    );
+
+    match graph.fewest_hops("東京", "甲府") {
+        Some(path) => println!(
+            "乗り換えが最少の経路: {}（{}区間）",
+            path.join(" → "),
+            path.len() - 1
+        ),
+        None => println!("経路がありません"),
+    }
+    let reachable: HashSet<&str> = graph.bfs("東京").into_iter().collect();
+    let unreachable: Vec<&str> = graph
+        .nodes()
+        .into_iter()
+        .filter(|node| !reachable.contains(node))
+        .collect();
+    println!("東京から行けない駅: {:?}", unreachable);
+}
+
+/// ダイクストラ法による最短経路
+pub fn dijkstra_demo() {
+    println!("\n=== ダイクストラ法（BinaryHeap） ===");
+
+    let graph = sample_graph();
+    for (from, to) in [("東京", "甲府"), ("大宮", "小田原"), ("東京", "名古屋")] {
+        match graph.dijkstra(from, to) {
+            Some((distance, path)) => {
+                println!(
+                    "{} → {}: 距離 {}  経路 {}",
+                    from,
+                    to,
+                    distance,
+                    path.join(" → ")
+                )
+            }
+            None => println!("{} → {}: 到達できません", from, to),
+        }
+    }
+
+    println!("\nBinaryHeap は最大ヒープ。Reverse で包むと最小値から取り出せる:");
+    let mut heap = BinaryHeap::new();
+    for n in [5, 1, 8, 3] {
+        heap.push(Reverse(n));
+    }
+    let order: Vec<u32> = std::iter::from_fn(|| heap.pop().map(|Reverse(n)| n)).collect();
+    println!("  取り出し順: {:?}", order);
+}
+
+/// チートシート用のデータ（`cheatsheet graphs` で表示）
+pub const CHEATSHEET: CheatSheet = CheatSheet {
+    syntax: &[
+        (
+            "HashMap<&str, Vec<(&str, u32)>>",
+            "隣接リスト（頂点 → 辺の一覧）",
+        ),
+        ("let mut queue = VecDeque::from([start]);", "BFS のキュー"),
+        ("let mut stack = vec![start];", "DFS のスタック"),
+        (
+            "BinaryHeap::from([Reverse((0, start))])",
+            "最小ヒープとして使う",
+        ),
+        (
+            "while let Some(Reverse((d, node))) = heap.pop()",
+            "パターンで Reverse を外す",
+        ),
+    ],
+    methods: &[
+        (
+            "visited.insert(x)",
+            "新しく追加できたら true（訪問済みの判定に使える）",
+        ),
+        (
+            "map.entry(k).or_default().push(v)",
+            "なければ空の Vec を作って追加",
+        ),
+        ("queue.push_back(x) / queue.pop_front()", "先入れ先出し"),
+        ("heap.push(x) / heap.pop()", "最大の要素を取り出す"),
+    ],
+    gotchas: &[
+        "BinaryHeap は最大ヒープ。最短経路では Reverse を使う",
+        "ダイクストラ法は負の重みがあると正しく動かない",
+        "HashMap の反復順は毎回変わるので、表示するときはソートする",
+    ],
+};
+
+/// すべてのデモを実行
+pub fn run_all() {
+    println!("╔════════════════════════════════════════════════════════════════╗");
+    println!("║          Rustグラフと経路探索サンプル                            ║");
+    println!("╚════════════════════════════════════════════════════════════════╝");
+
+    lesson_api::run_topic("graphs");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn traversals_visit_reachable_nodes_once() {
+        let graph = sample_graph();
+        for order in [
+            graph.bfs("東京"),
+            graph.dfs("東京"),
+            graph.dfs_iterative("東京"),
+        ] {
+            assert_eq!(order.len(), 6);
+            assert_eq!(order[0], "東京");
+            assert!(!order.contains(&"名古屋"));
+        }
+        assert_eq!(graph.dfs("東京"), graph.dfs_iterative("東京"));
+    }
+
+    #[test]
+    fn shortest_paths() {
+        let graph = sample_graph();
+        assert_eq!(graph.fewest_hops("東京", "甲府").unwrap().len(), 4);
+        assert_eq!(
+            graph.dijkstra("東京", "甲府"),
+            Some((13, vec!["東京", "横浜", "小田原", "甲府"]))
+        );
+        assert_eq!(graph.dijkstra("東京", "東京"), Some((0, vec!["東京"])));
+        assert_eq!(graph.dijkstra("東京", "名古屋"), None);
+    }
+}
@@ -0,0 +1,388 @@
+// ============================================================================
+// Rust 内部可変性サンプル - Cell、RefCell、実行時の借用チェック
+// 公式ドキュメント: https://doc.rust-lang.org/book/ch15-05-interior-mutability.html
+// ============================================================================
+//
+// 内部可変性（interior mutability）: &T しか持っていなくても中身を書き換えられる仕組み。
+// 借用規則（可変参照は1つだけ、または不変参照をいくつでも）の検査を、
+// コンパイル時から実行時に移す。
+//
+// - Cell<T>         値を出し入れする（get / set / replace / take）。参照を貸さないので検査が要らない
+// - RefCell<T>      borrow / borrow_mut で参照を貸す。借用の数を実行時に数え、違反すると panic
+// - try_borrow_mut  panic せずに Err(BorrowMutError) を受け取る
+//
+// Rc<RefCell<T>> で共有する例や Weak の木は smart_pointers の章にある。
+
+use lesson_api::panics::{self, panics_are_fatal};
+use lesson_api::{println, CheatSheet};
+use std::cell::{Cell, RefCell};
+use std::mem::size_of;
+
+// ----------------------------------------------------------------------------
+// Cell<T> と RefCell<T>
+// ----------------------------------------------------------------------------
+
+/// 閲覧数と訪問者を、&self のメソッドで記録するページ
+pub struct Page {
+    pub title: String,
+    // Copy な数は Cell で十分
+    views: Cell<u32>,
+    // Vec は参照を借りて push するので RefCell
+    visitors: RefCell<Vec<String>>,
+}
+
+impl Page {
+    pub fn new(title: &str) -> Page {
+        Page {
+            title: title.to_string(),
+            views: Cell::new(0),
+            visitors: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// 読むだけの操作に見えるので &self にしておきたい。中の記録は内部可変性で書き換える
+    pub fn view(&self, visitor: &str) {
+        self.views.set(self.views.get() + 1);
+        self.visitors.borrow_mut().push(visitor.to_string());
+    }
+
+    pub fn views(&self) -> u32 {
+        self.views.get()
+    }
+
+    pub fn last_visitor(&self) -> Option<String> {
+        self.visitors.borrow().last().cloned()
+    }
+}
+
+/// Cell<T> と RefCell<T>
+pub fn cell_vs_refcell() {
+    println!("\n=== Cell<T> と RefCell<T> ===");
+
+    // let で不変に束縛していても、&self のメソッドで記録が増える
+    let page = Page::new("はじめての Rust");
+    for visitor in ["Ferris", "Corro", "Ferris"] {
+        page.view(visitor);
+    }
+    println!(
+        "{}: 閲覧数 = {}、最後の訪問者 = {:?}",
+        page.title,
+        page.views(),
+        page.last_visitor()
+    );
+
+    // Cell: 中の値への参照は貸さず、値ごと出し入れする
+    let cell = Cell::new(5);
+    cell.set(cell.get() * 2);
+    let old = cell.replace(100);
+    println!(
+        "Cell::new(5) → set(10) → replace(100) が返した古い値: {}、今の値: {}",
+        old,
+        cell.get()
+    );
+    // get は T: Copy のときだけ。String などは take（Default と入れ替え）や replace で取り出す
+    let name = Cell::new(String::from("Ferris"));
+    let taken = name.take();
+    println!(
+        "Cell<String>::take() = {:?}、残りは {:?}",
+        taken,
+        name.into_inner()
+    );
+
+    // RefCell: borrow() はいくつでも同時に、borrow_mut() は1つだけ
+    let list = RefCell::new(vec![1, 2, 3]);
+    {
+        let a = list.borrow();
+        let b = list.borrow();
+        println!("borrow() を2つ同時に: {:?} / {:?}", *a, *b);
+    } // Ref が drop されると借用が返る
+    list.borrow_mut().push(4);
+    println!("borrow_mut().push(4) の後: {:?}", list.borrow());
+
+    // RefCell は借用の数を数えるフラグを持つぶん大きい
+    println!(
+        "size_of: u32 = {}、Cell<u32> = {}、RefCell<u32> = {}（借用のカウンタが付く）",
+        size_of::<u32>(),
+        size_of::<Cell<u32>>(),
+        size_of::<RefCell<u32>>()
+    );
+    println!(
+        "どちらも Sync ではないのでスレッド間では共有できない（Mutex / RwLock / Atomic* を使う）"
+    );
+}
+
+// ----------------------------------------------------------------------------
+// モックオブジェクト（The Book 15.5）
+// ----------------------------------------------------------------------------
+
+/// メッセージを送る相手（テストではモックに差し替える）
+pub trait Messenger {
+    fn send(&self, msg: &str);
+}
+
+/// 使用量が上限に近づいたら知らせる
+pub struct LimitTracker<'a, T: Messenger> {
+    messenger: &'a T,
+    value: usize,
+    max: usize,
+}
+
+impl<'a, T> LimitTracker<'a, T>
+where
+    T: Messenger,
+{
+    pub fn new(messenger: &'a T, max: usize) -> LimitTracker<'a, T> {
+        LimitTracker {
+            messenger,
+            value: 0,
+            max,
+        }
+    }
+
+    pub fn set_value(&mut self, value: usize) {
+        self.value = value;
+
+        let percentage_of_max = self.value as f64 / self.max as f64;
+
+        if percentage_of_max >= 1.0 {
+            self.messenger.send("エラー: 割り当てを超えました");
+        } else if percentage_of_max >= 0.9 {
+            self.messenger
+                .send("至急の警告: 割り当ての 90% 以上を使っています");
+        } else if percentage_of_max >= 0.75 {
+            self.messenger
+                .send("警告: 割り当ての 75% 以上を使っています");
+        }
+    }
+}
+
+/// 送られたメッセージを覚えておくモック
+///
+/// Messenger::send は &self なので、Vec<String> のままでは push できない（error[E0596]）。
+/// トレイトの定義をテストのために &mut self に変えたくないので、RefCell に入れる。
+pub struct MockMessenger {
+    pub sent_messages: RefCell<Vec<String>>,
+}
+
+impl MockMessenger {
+    pub fn new() -> MockMessenger {
+        MockMessenger {
+            sent_messages: RefCell::new(vec![]),
+        }
+    }
+}
+
+impl Default for MockMessenger {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Messenger for MockMessenger {
+    fn send(&self, message: &str) {
+        self.sent_messages.borrow_mut().push(String::from(message));
+    }
+}
+
+/// The Book がわざと間違えてみせるモック: 同じスコープで borrow_mut を2回呼ぶ
+pub struct DoubleBorrowMessenger {
+    pub sent_messages: RefCell<Vec<String>>,
+}
+
+impl Messenger for DoubleBorrowMessenger {
+    fn send(&self, message: &str) {
+        let mut one_borrow = self.sent_messages.borrow_mut();
+        // コンパイルは通るが、ここで panic する（already borrowed）
+        let mut two_borrow = self.sent_messages.borrow_mut();
+
+        one_borrow.push(String::from(message));
+        two_borrow.push(String::from(message));
+    }
+}
+
+/// モックオブジェクト（MockMessenger）
+pub fn mock_messenger() {
+    println!("\n=== モックオブジェクト（MockMessenger） ===");
+
+    println!("trait Messenger {{ fn send(&self, msg: &str); }}");
+    println!("// sent_messages: Vec<String> のままだと、send の中の push が error[E0596]");
+    println!("//   cannot borrow `self.sent_messages` as mutable, as it is behind a `&` reference");
+
+    let mock_messenger = MockMessenger::new();
+    let mut limit_tracker = LimitTracker::new(&mock_messenger, 100);
+    for value in [50, 80, 95, 120] {
+        limit_tracker.set_value(value);
+    }
+    println!("RefCell<Vec<String>> にしたモックが受け取ったメッセージ:");
+    for message in mock_messenger.sent_messages.borrow().iter() {
+        println!("  {}", message);
+    }
+
+    // 借用規則の違反は、コンパイル時ではなく実行時に見つかる
+    println!("\n同じスコープで borrow_mut() を2回呼ぶモック:");
+    if panics_are_fatal() {
+        return;
+    }
+    let buggy = DoubleBorrowMessenger {
+        sent_messages: RefCell::new(vec![]),
+    };
+    let mut tracker = LimitTracker::new(&buggy, 100);
+    match panics::catch_silently(|| tracker.set_value(80)) {
+        Err(message) => println!("  panic: {}", message),
+        Ok(()) => println!("  panic しませんでした"),
+    }
+}
+
+// ----------------------------------------------------------------------------
+// 実行時の借用エラー
+// ----------------------------------------------------------------------------
+
+/// 実行時の借用エラー（BorrowMutError）
+pub fn runtime_borrow_errors() {
+    println!("\n=== 実行時の借用エラー（BorrowMutError） ===");
+
+    let cell = RefCell::new(vec![1, 2, 3]);
+
+    // try_ 版は panic せずに Result を返す
+    {
+        let reader = cell.borrow();
+        match cell.try_borrow_mut() {
+            Ok(_) => println!("try_borrow_mut: Ok"),
+            Err(e) => println!("borrow() 中の try_borrow_mut: Err({:?}) → {}", e, e),
+        }
+        println!("  （reader はまだ {:?} を読んでいる）", *reader);
+    }
+    {
+        let _writer = cell.borrow_mut();
+        match cell.try_borrow() {
+            Ok(_) => println!("try_borrow: Ok"),
+            Err(e) => println!("borrow_mut() 中の try_borrow: Err({:?}) → {}", e, e),
+        }
+    }
+    println!(
+        "借用を返した後の try_borrow_mut: is_ok = {}",
+        cell.try_borrow_mut().is_ok()
+    );
+
+    // わざと panic させて捕まえる
+    if !panics_are_fatal() {
+        let result = panics::catch_silently(|| {
+            let _first = cell.borrow_mut();
+            let _second = cell.borrow_mut();
+        });
+        println!(
+            "borrow_mut() を2回 → panic: {}",
+            result.err().unwrap_or_default()
+        );
+        // panic で巻き戻るときにガードが drop されるので、借用は残らない
+        println!("panic の後も使える: {:?}", cell.borrow());
+    }
+
+    // ハマりどころ: ガードが思ったより長く生きている
+    println!("\nガードの寿命に注意:");
+    // match の対象の一時的な Ref は、match 全体の終わりまで生きる
+    match cell.borrow().first() {
+        Some(first) => println!(
+            "  match cell.borrow().first() の腕の中で try_borrow_mut: is_err = {}（先頭 = {}）",
+            cell.try_borrow_mut().is_err(),
+            first
+        ),
+        None => println!("  空です"),
+    }
+    // let の文の一時的な値は文の終わりで drop されるので、先に取り出しておけばよい
+    let first = cell.borrow().first().copied();
+    if let Some(first) = first {
+        cell.borrow_mut().push(first * 10);
+    }
+    println!(
+        "  先に let で取り出してから borrow_mut: {:?}",
+        cell.borrow()
+    );
+
+    // ループで読みながら書き足すのも同じ（for の間ずっと Ref が生きている）
+    //   for x in cell.borrow().iter() { cell.borrow_mut().push(*x); }   // panic
+    let copies: Vec<i32> = cell.borrow().iter().map(|x| x + 1).collect();
+    cell.borrow_mut().extend(copies);
+    println!("  読んだ結果を集めてから extend: {:?}", cell.borrow());
+}
+
+/// チートシート用のデータ（`cheatsheet interior_mutability` で表示）
+pub const CHEATSHEET: CheatSheet = CheatSheet {
+    syntax: &[
+        ("views: Cell<u32>", "&self で書き換える Copy な値"),
+        (
+            "log: RefCell<Vec<String>>",
+            "&self で参照を借りて書き換える値",
+        ),
+        (
+            "Rc<RefCell<T>>",
+            "複数の所有者が書き換える（シングルスレッド）",
+        ),
+    ],
+    methods: &[
+        (
+            "cell.get() / cell.set(v)",
+            "値をコピーで取り出す / 置き換える（T: Copy）",
+        ),
+        ("cell.replace(v) / cell.take()", "古い値を返して入れ替える"),
+        ("refcell.borrow()", "Ref<T>（不変の借用、いくつでも）"),
+        (
+            "refcell.borrow_mut()",
+            "RefMut<T>（可変の借用、1つだけ。違反すると panic）",
+        ),
+        (
+            "refcell.try_borrow_mut()",
+            "Result<RefMut<T>, BorrowMutError>（panic しない）",
+        ),
+        ("refcell.into_inner()", "RefCell をほどいて中身を取り出す"),
+    ],
+    gotchas: &[
+        "RefCell の借用違反はコンパイルエラーではなく実行時の panic",
+        "match refcell.borrow().. の Ref は match の終わりまで生きる",
+        "Cell / RefCell は Sync ではない。スレッド間では Mutex / RwLock / Atomic*",
+        "Cell<T>::get は T: Copy のときだけ。参照は借りられない",
+    ],
+};
+
+/// すべてのデモを実行
+pub fn run_all() {
+    println!("╔════════════════════════════════════════════════════════════════╗");
+    println!("║          Rust 内部可変性サンプル                                 ║");
+    println!("╚════════════════════════════════════════════════════════════════╝");
+
+    lesson_api::run_topic("interior_mutability");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_sends_an_over_75_percent_warning_message() {
+        let mock_messenger = MockMessenger::new();
+        let mut limit_tracker = LimitTracker::new(&mock_messenger, 100);
+
+        limit_tracker.set_value(80);
+
+        assert_eq!(mock_messenger.sent_messages.borrow().len(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "already")]
+    fn double_borrow_panics_at_runtime() {
+        let buggy = DoubleBorrowMessenger {
+            sent_messages: RefCell::new(vec![]),
+        };
+        buggy.send("hello");
+    }
+
+    #[test]
+    fn page_records_views_through_shared_reference() {
+        let page = Page::new("test");
+        let shared = &page;
+        shared.view("a");
+        shared.view("b");
+        assert_eq!(page.views(), 2);
+        assert_eq!(page.last_visitor().as_deref(), Some("b"));
+    }
+}
@@ -0,0 +1,777 @@
+// ============================================================================
+// Rustイテレータとクロージャサンプル
+// 公式ドキュメント: https://doc.rust-lang.org/book/ch13-00-functional-features.html
+// ============================================================================
+
+use lesson_api::timing::stopwatch;
+use lesson_api::{print, println, CheatSheet};
+
+/// クロージャの基本
+pub fn closure_basics() {
+    println!("\n=== クロージャの基本 ===");
+
+    // クロージャ = 匿名関数（環境をキャプチャできる）
+    let add_one = |x: i32| -> i32 { x + 1 };
+    println!("add_one(5) = {}", add_one(5));
+
+    // 型推論により注釈を省略可能
+    let add_one = |x| x + 1;
+    println!("型推論版 add_one(5) = {}", add_one(5));
+
+    // 複数の引数
+    let add = |a, b| a + b;
+    println!("add(3, 4) = {}", add(3, 4));
+
+    // 引数なし
+    let hello = || println!("Hello from closure!");
+    hello();
+
+    // 複数行のクロージャ
+    let complex = |x: i32| {
+        let a = x + 1;
+        let b = a * 2;
+        b + 10
+    };
+    println!("complex(5) = {}", complex(5));
+}
+
+/// 環境のキャプチャ
+pub fn closure_capture() {
+    println!("\n=== 環境のキャプチャ ===");
+
+    // 不変借用でキャプチャ（Fn）
+    let x = 4;
+    let equal_to_x = |z| z == x;
+    println!("equal_to_x(4) = {}", equal_to_x(4));
+    println!("xはまだ使える: {}", x);
+
+    // 可変借用でキャプチャ（FnMut）
+    let mut count = 0;
+    let mut increment = || {
+        count += 1;
+        println!("  カウント: {}", count);
+    };
+    println!("FnMut（可変借用）:");
+    increment();
+    increment();
+    increment();
+    println!("最終カウント: {}", count);
+
+    // 所有権を取得（FnOnce）
+    let s = String::from("hello");
+    let consume_string = move || {
+        println!("  文字列を消費: {}", s);
+        // sはこのクロージャに移動された
+    };
+    consume_string();
+    // println!("{}", s); // エラー！sはムーブ済み
+
+    // moveキーワード
+    println!("\nmoveキーワード:");
+    let x = vec![1, 2, 3];
+    let contains = move |n| x.contains(n);
+    println!("contains(&2) = {}", contains(&2));
+    // println!("{:?}", x); // エラー！xはムーブ済み
+}
+
+/// クロージャを引数に取る関数
+pub fn closures_as_parameters() {
+    println!("\n=== クロージャを引数に取る関数 ===");
+
+    // Fn - 不変参照でキャプチャ
+    fn apply_fn<F>(f: F)
+    where
+        F: Fn(),
+    {
+        f();
+    }
+
+    // FnMut - 可変参照でキャプチャ
+    fn apply_fn_mut<F>(mut f: F)
+    where
+        F: FnMut(),
+    {
+        f();
+        f();
+    }
+
+    // FnOnce - 所有権を取る（1回だけ呼び出し可能）
+    fn apply_fn_once<F>(f: F)
+    where
+        F: FnOnce(),
+    {
+        f();
+    }
+
+    println!("Fn:");
+    let x = 5;
+    apply_fn(|| println!("  x = {}", x));
+
+    println!("FnMut:");
+    let mut count = 0;
+    apply_fn_mut(|| {
+        count += 1;
+        println!("  count = {}", count);
+    });
+
+    println!("FnOnce:");
+    let s = String::from("hello");
+    apply_fn_once(|| println!("  s = {}", s));
+
+    // 戻り値を持つクロージャ
+    fn apply_with_result<F>(f: F) -> i32
+    where
+        F: Fn(i32) -> i32,
+    {
+        f(10)
+    }
+
+    let result = apply_with_result(|x| x * 2);
+    println!("apply_with_result: {}", result);
+}
+
+/// イテレータの基本
+pub fn iterator_basics() {
+    println!("\n=== イテレータの基本 ===");
+
+    let v = vec![1, 2, 3];
+
+    // イテレータの作成
+    let v_iter = v.iter();
+
+    // forループでの使用
+    println!("forループ:");
+    for val in v_iter {
+        print!("{} ", val);
+    }
+    println!();
+
+    // イテレータを手動で進める
+    let mut v_iter = v.iter();
+    println!("next()を手動で呼ぶ:");
+    println!("  {:?}", v_iter.next()); // Some(&1)
+    println!("  {:?}", v_iter.next()); // Some(&2)
+    println!("  {:?}", v_iter.next()); // Some(&3)
+    println!("  {:?}", v_iter.next()); // None
+
+    // 異なるイテレータメソッド
+    let v = vec![1, 2, 3];
+
+    // iter() - 不変参照のイテレータ
+    println!("iter() - &T:");
+    for val in v.iter() {
+        print!("{} ", val);
+    }
+    println!();
+
+    // iter_mut() - 可変参照のイテレータ
+    let mut v = vec![1, 2, 3];
+    println!("iter_mut() - &mut T:");
+    for val in v.iter_mut() {
+        *val *= 2;
+    }
+    println!("  結果: {:?}", v);
+
+    // into_iter() - 所有権を取るイテレータ
+    let v = vec![1, 2, 3];
+    println!("into_iter() - T:");
+    for val in v.into_iter() {
+        print!("{} ", val);
+    }
+    println!();
+    // println!("{:?}", v); // エラー！vはムーブ済み
+}
+
+/// イテレータのアダプタ（遅延評価）
+pub fn iterator_adapters() {
+    println!("\n=== イテレータアダプタ ===");
+
+    let v = vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+
+    // map - 各要素を変換
+    let squared: Vec<i32> = v.iter().map(|x| x * x).collect();
+    println!("map (二乗): {:?}", squared);
+
+    // filter - 条件を満たす要素のみ
+    let even: Vec<&i32> = v.iter().filter(|x| *x % 2 == 0).collect();
+    println!("filter (偶数): {:?}", even);
+
+    // take - 最初のn個
+    let first_three: Vec<&i32> = v.iter().take(3).collect();
+    println!("take(3): {:?}", first_three);
+
+    // skip - 最初のn個をスキップ
+    let after_five: Vec<&i32> = v.iter().skip(5).collect();
+    println!("skip(5): {:?}", after_five);
+
+    // チェーンさせる
+    let result: Vec<i32> = v
+        .iter()
+        .filter(|x| *x % 2 == 0) // 偶数のみ
+        .map(|x| x * x) // 二乗
+        .take(3) // 最初の3つ
+        .collect();
+    println!("filter->map->take: {:?}", result);
+
+    // enumerate - インデックス付き
+    println!("enumerate:");
+    for (index, value) in v.iter().enumerate().take(3) {
+        println!("  v[{}] = {}", index, value);
+    }
+
+    // zip - 2つのイテレータを結合
+    let a = vec![1, 2, 3];
+    let b = vec!["one", "two", "three"];
+    let zipped: Vec<_> = a.iter().zip(b.iter()).collect();
+    println!("zip: {:?}", zipped);
+
+    // flatten - ネストを平坦化
+    let nested = vec![vec![1, 2], vec![3, 4], vec![5, 6]];
+    let flat: Vec<i32> = nested.into_iter().flatten().collect();
+    println!("flatten: {:?}", flat);
+
+    // rev - 逆順
+    let reversed: Vec<&i32> = v.iter().rev().take(3).collect();
+    println!("rev (最後の3つを逆順で): {:?}", reversed);
+}
+
+/// イテレータの消費アダプタ
+pub fn iterator_consumers() {
+    println!("\n=== イテレータ消費アダプタ ===");
+
+    let v = vec![1, 2, 3, 4, 5];
+
+    // collect - コレクションに収集
+    let collected: Vec<i32> = v.iter().map(|x| x * 2).collect();
+    println!("collect: {:?}", collected);
+
+    // sum - 合計
+    let total: i32 = v.iter().sum();
+    println!("sum: {}", total);
+
+    // product - 積
+    let product: i32 = v.iter().product();
+    println!("product: {}", product);
+
+    // count - 要素数
+    let count = v.iter().count();
+    println!("count: {}", count);
+
+    // min, max
+    println!("min: {:?}", v.iter().min());
+    println!("max: {:?}", v.iter().max());
+
+    // fold - 畳み込み
+    let sum = v.iter().fold(0, |acc, x| acc + x);
+    println!("fold (sum): {}", sum);
+
+    let product = v.iter().fold(1, |acc, x| acc * x);
+    println!("fold (product): {}", product);
+
+    // reduce - 最初の要素を初期値として使用
+    let sum = v.iter().copied().reduce(|acc, x| acc + x);
+    println!("reduce (sum): {:?}", sum);
+
+    // any, all - 条件チェック
+    let has_even = v.iter().any(|x| x % 2 == 0);
+    let all_positive = v.iter().all(|x| *x > 0);
+    println!("any (偶数あり): {}", has_even);
+    println!("all (全て正): {}", all_positive);
+
+    // find - 最初にマッチした要素
+    let first_even = v.iter().find(|x| *x % 2 == 0);
+    println!("find (最初の偶数): {:?}", first_even);
+
+    // position - 最初にマッチした位置
+    let position = v.iter().position(|x| *x == 3);
+    println!("position (3の位置): {:?}", position);
+
+    // for_each - 各要素に対して処理（戻り値なし）
+    print!("for_each: ");
+    v.iter().for_each(|x| print!("{} ", x));
+    println!();
+}
+
+/// カスタムイテレータの作成
+pub fn custom_iterator() {
+    println!("\n=== カスタムイテレータ ===");
+
+    // カウンターを作成
+    struct Counter {
+        count: u32,
+        max: u32,
+    }
+
+    impl Counter {
+        fn new(max: u32) -> Counter {
+            Counter { count: 0, max }
+        }
+    }
+
+    // Iteratorトレイトを実装
+    impl Iterator for Counter {
+        type Item = u32;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            if self.count < self.max {
+                self.count += 1;
+                Some(self.count)
+            } else {
+                None
+            }
+        }
+    }
+
+    // 使用例
+    let counter = Counter::new(5);
+    println!("カスタムイテレータ:");
+    for num in counter {
+        print!("{} ", num);
+    }
+    println!();
+
+    // イテレータアダプタも使える
+    let sum: u32 = Counter::new(5).filter(|x| x % 2 == 0).sum();
+    println!("偶数の合計: {}", sum);
+
+    // 複雑な例: フィボナッチ数列
+    struct Fibonacci {
+        current: u64,
+        next: u64,
+    }
+
+    impl Fibonacci {
+        fn new() -> Self {
+            Fibonacci {
+                current: 0,
+                next: 1,
+            }
+        }
+    }
+
+    impl Iterator for Fibonacci {
+        type Item = u64;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            let new_next = self.current.checked_add(self.next)?;
+            self.current = self.next;
+            self.next = new_next;
+            Some(self.current)
+        }
+    }
+
+    let fibs: Vec<u64> = Fibonacci::new().take(10).collect();
+    println!("フィボナッチ数列 (最初の10個): {:?}", fibs);
+}
+
+// ----------------------------------------------------------------------------
+// windows と chunks を自作する
+// ----------------------------------------------------------------------------
+
+/// 長さ size の重なり合う区間を順に返す（slice::windows と同じ動き）
+///
+/// 返すのは元のスライスの一部なので、要素は 'a の間だけ借りる
+pub struct Windows<'a, T> {
+    rest: &'a [T],
+    size: usize,
+}
+
+/// 長さ size ずつに区切って返す。最後の1つは短いこともある（slice::chunks と同じ動き）
+pub struct Chunks<'a, T> {
+    rest: &'a [T],
+    size: usize,
+}
+
+/// size が 0 なら panic する（標準と同じ）
+pub fn windows<T>(slice: &[T], size: usize) -> Windows<'_, T> {
+    assert!(size != 0, "window size must be non-zero");
+    Windows { rest: slice, size }
+}
+
+/// size が 0 なら panic する（標準と同じ）
+pub fn chunks<T>(slice: &[T], size: usize) -> Chunks<'_, T> {
+    assert!(size != 0, "chunk size must be non-zero");
+    Chunks { rest: slice, size }
+}
+
+impl<'a, T> Iterator for Windows<'a, T> {
+    type Item = &'a [T];
+
+    fn next(&mut self) -> Option<&'a [T]> {
+        if self.rest.len() < self.size {
+            return None;
+        }
+        let window = &self.rest[..self.size];
+        // 1つずらす（次の区間と size - 1 個が重なる）
+        self.rest = &self.rest[1..];
+        Some(window)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = (self.rest.len() + 1).saturating_sub(self.size);
+        (n, Some(n))
+    }
+}
+
+impl<'a, T> Iterator for Chunks<'a, T> {
+    type Item = &'a [T];
+
+    fn next(&mut self) -> Option<&'a [T]> {
+        if self.rest.is_empty() {
+            return None;
+        }
+        let (chunk, rest) = self.rest.split_at(self.size.min(self.rest.len()));
+        self.rest = rest;
+        Some(chunk)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.rest.len().div_ceil(self.size);
+        (n, Some(n))
+    }
+}
+
+// size_hint が正確なので len() も使える
+impl<T> ExactSizeIterator for Windows<'_, T> {}
+impl<T> ExactSizeIterator for Chunks<'_, T> {}
+
+/// windows と chunks を自作する
+pub fn windows_and_chunks() {
+    println!("\n=== windows と chunks を自作する ===");
+
+    let temperatures = [21, 23, 22, 26, 25, 27, 24];
+    println!("データ: {:?}", temperatures);
+
+    // 重なり合う区間: 移動平均や、隣どうしの比較に使う
+    let averages: Vec<f64> = windows(&temperatures, 3)
+        .map(|w| w.iter().sum::<i32>() as f64 / w.len() as f64)
+        .collect();
+    println!("windows(3) の移動平均: {:.1?}", averages);
+    let rises = windows(&temperatures, 2).filter(|w| w[1] > w[0]).count();
+    println!("前日より上がった日: {} 日", rises);
+
+    // 重ならない区切り: まとめて処理する（1行に n 個ずつ表示など）
+    for (i, group) in chunks(&temperatures, 3).enumerate() {
+        println!("chunks(3) の {} 番目: {:?}", i, group);
+    }
+
+    // 標準の slice::windows / chunks と同じ結果になる
+    let same = windows(&temperatures, 3).eq(temperatures.windows(3))
+        && chunks(&temperatures, 3).eq(temperatures.chunks(3));
+    println!("標準と同じ結果: {}", same);
+    println!(
+        "len(): windows(3) = {}、chunks(3) = {}（ExactSizeIterator）",
+        windows(&temperatures, 3).len(),
+        chunks(&temperatures, 3).len()
+    );
+
+    // 区間は元の配列を借りているので、配列より長くは使えない
+    //   let first = { let v = vec![1, 2, 3]; windows(&v, 2).next() };  // エラー: v が先に消える
+    println!("Item = &'a [T]: 区間はコピーではなく元のスライスの一部（確保なし）");
+}
+
+/// イテレータとクロージャの実践例
+pub fn practical_examples() {
+    println!("\n=== 実践例 ===");
+
+    // 単語カウント
+    let text = "hello world hello rust world world";
+    let mut word_count = std::collections::HashMap::new();
+    text.split_whitespace()
+        .for_each(|word| *word_count.entry(word).or_insert(0) += 1);
+    println!("単語カウント: {:?}", word_count);
+
+    // 最大値を持つ要素を見つける
+    #[derive(Debug)]
+    struct Person {
+        name: String,
+        age: u32,
+    }
+
+    let people = vec![
+        Person {
+            name: "Alice".to_string(),
+            age: 30,
+        },
+        Person {
+            name: "Bob".to_string(),
+            age: 25,
+        },
+        Person {
+            name: "Charlie".to_string(),
+            age: 35,
+        },
+    ];
+
+    let oldest = people.iter().max_by_key(|p| p.age);
+    println!("最年長: {:?}", oldest);
+
+    // グループ化（年代別）
+    let ages: Vec<_> = people.iter().map(|p| p.age / 10 * 10).collect();
+    println!("年代: {:?}", ages);
+
+    // パイプライン処理
+    let numbers = vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+    let result: i32 = numbers
+        .iter()
+        .filter(|&x| x % 2 == 0) // 偶数
+        .map(|x| x * x) // 二乗
+        .filter(|&x| x > 10) // 10より大きい
+        .sum();
+    println!("パイプライン処理結果: {}", result);
+
+    // Option/Resultのイテレータ変換
+    let options = vec![Some(1), None, Some(2), None, Some(3)];
+    let values: Vec<i32> = options.into_iter().flatten().collect();
+    println!("Option::flatten: {:?}", values);
+}
+
+// 時間と確保の回数を測って1行表示する
+fn report<R>(name: &str, f: impl FnOnce() -> R) -> R {
+    let ((result, stats), time) = stopwatch(|| lesson_api::alloc_count::measure(f));
+    println!("  {:<36} {:>12} {:>8}", name, time, stats.allocations);
+    result
+}
+
+/// イテレータのパイプラインと確保のコスト
+pub fn pipeline_performance() {
+    println!("\n=== イテレータのパイプラインと確保のコスト ===");
+
+    // 1. 数値: filter().map().collect() と手書きのループ
+    let numbers: Vec<u64> = (0..200_000).collect();
+    println!("{}個の数から偶数の2乗を集める:", numbers.len());
+    println!("  方法{}時間 確保回数", " ".repeat(41));
+    let collected: Vec<u64> = report("filter().map().collect()", || {
+        numbers
+            .iter()
+            .filter(|&&n| n % 2 == 0)
+            .map(|&n| n * n)
+            .collect()
+    });
+    let looped = report("Vec::new() + for + push", || {
+        let mut out = Vec::new();
+        for &n in &numbers {
+            if n % 2 == 0 {
+                out.push(n * n);
+            }
+        }
+        out
+    });
+    let presized = report("Vec::with_capacity(n) + for + push", || {
+        let mut out = Vec::with_capacity(numbers.len() / 2);
+        for &n in &numbers {
+            if n % 2 == 0 {
+                out.push(n * n);
+            }
+        }
+        out
+    });
+    assert!(collected == looped && looped == presized);
+    // filter を通すと件数が分からないので、collect は Vec::new() + push と同じように伸ばしていく
+    // map だけなら size_hint で長さが分かり、collect は最初に1回だけ確保する
+    // black_box: 使わない Vec の確保を最適化で消されないようにする
+    let (mapped, stats) = lesson_api::alloc_count::measure(|| {
+        std::hint::black_box(numbers.iter().map(|&n| n * n).collect::<Vec<_>>())
+    });
+    println!(
+        "  （filter の無い map().collect() なら {} 件でも確保は {} 回）",
+        mapped.len(),
+        stats.allocations
+    );
+
+    // 2. 文字列: 要素ごとに String を作る collect と、1つの String に足していく fold
+    let words: Vec<&str> = "the quick brown fox jumps over the lazy dog"
+        .split(' ')
+        .cycle()
+        .take(20_000)
+        .collect();
+    println!("\n{}個の単語を空白区切りでつなぐ:", words.len());
+    println!("  方法{}時間 確保回数", " ".repeat(41));
+    let collected: String = report("map(format!).collect::<String>()", || {
+        words.iter().map(|w| format!("{} ", w)).collect()
+    });
+    let folded = report("fold(String::new(), push_str)", || {
+        words.iter().fold(String::new(), |mut s, w| {
+            s.push_str(w);
+            s.push(' ');
+            s
+        })
+    });
+    let total: usize = words.iter().map(|w| w.len() + 1).sum();
+    let presized = report("fold(String::with_capacity(n), ..)", || {
+        words.iter().fold(String::with_capacity(total), |mut s, w| {
+            s.push_str(w);
+            s.push(' ');
+            s
+        })
+    });
+    assert!(collected == folded && folded == presized);
+
+    // イテレータそのものはループと同じ機械語になる（ゼロコスト抽象化）。
+    // 差が出るのは「どこで何回確保するか」
+    println!("\nイテレータとループの差は小さい。時間の多くは確保（とコピー）にかかる");
+    println!("要素ごとに format! / to_string すると、その数だけ確保が起きる");
+    println!("件数の上限が分かるなら with_capacity、文字列は1つのバッファに push_str");
+    println!("※ デバッグビルドではイテレータがインライン化されず遅く見えます。cargo run --release で比べてください");
+}
+
+/// クロージャが実装するトレイトを調べる
+pub fn closure_trait_probe() {
+    println!("\n=== クロージャが実装するトレイトを調べる ===");
+
+    // トレイト境界付きの「検査用」関数。
+    // 境界を満たさないクロージャを渡すとコンパイルエラーになるので、
+    // 呼び出しを書き換えながらどのトレイトを実装しているか確かめられる。
+    fn probe_fn<F: Fn() -> R, R>(label: &str, f: &F) {
+        f();
+        f(); // &F のまま何度でも呼べる
+        println!("  {:<32} は Fn を実装", label);
+    }
+
+    fn probe_fn_mut<F: FnMut() -> R, R>(label: &str, f: &mut F) {
+        f();
+        f(); // 呼び出すには &mut F が必要
+        println!("  {:<32} は FnMut を実装", label);
+    }
+
+    fn probe_fn_once<F: FnOnce() -> R, R>(label: &str, f: F) {
+        f(); // 所有権ごと受け取り、1回だけ呼べる
+        println!("  {:<32} は FnOnce を実装", label);
+    }
+
+    // 1. キャプチャなし → Fn + FnMut + FnOnce
+    let mut no_capture = || 42;
+    probe_fn("|| 42", &no_capture);
+    probe_fn_mut("|| 42", &mut no_capture);
+    probe_fn_once("|| 42", no_capture);
+
+    // 2. 不変借用 → Fn + FnMut + FnOnce
+    let text = String::from("hello");
+    let mut borrow = || text.len();
+    probe_fn("|| text.len()", &borrow);
+    probe_fn_mut("|| text.len()", &mut borrow);
+    probe_fn_once("|| text.len()", borrow);
+
+    // 3. 可変借用 → FnMut + FnOnce
+    let mut count = 0;
+    let mut increment = || count += 1;
+    // probe_fn("|| count += 1", &increment); // エラー！ count を変更するので Fn ではない
+    probe_fn_mut("|| count += 1", &mut increment);
+    probe_fn_once("|| count += 1", increment);
+    println!("    （count は {} になった）", count);
+
+    // 4. キャプチャした値を消費 → FnOnce のみ
+    let owned = String::from("owned");
+    let consume = || drop(owned);
+    // probe_fn_mut("|| drop(owned)", &mut consume); // エラー！ 2回目の drop ができない
+    probe_fn_once("|| drop(owned)", consume);
+
+    // 5. move でキャプチャしても、消費しなければ Fn のまま
+    let name = String::from("Ferris");
+    let mut moved = move || name.len();
+    probe_fn("move || name.len()", &moved);
+    probe_fn_mut("move || name.len()", &mut moved);
+    probe_fn_once("move || name.len()", moved);
+
+    println!("\nまとめ:");
+    println!("  - トレイトはキャプチャの「使い方」から推論される（Fn ⊂ FnMut ⊂ FnOnce）");
+    println!("  - move は「どうキャプチャするか」を決めるだけで、実装するトレイトは変えない");
+    println!("  - コメントアウトした呼び出しを有効にすると、コンパイラが理由を教えてくれます");
+}
+
+/// チートシート用のデータ（`cheatsheet iterators_closures` で表示）
+pub const CHEATSHEET: CheatSheet = CheatSheet {
+    syntax: &[
+        ("let add_one = |x| x + 1;", "クロージャ"),
+        (
+            "let f = move || println!(\"{}\", s);",
+            "所有権をクロージャにムーブ",
+        ),
+        (
+            "fn apply<F: Fn(i32) -> i32>(f: F)",
+            "クロージャを引数に取る",
+        ),
+        (
+            "impl Iterator for Counter { type Item = u32; }",
+            "カスタムイテレータ",
+        ),
+        (
+            "impl<'a, T> Iterator for Windows<'a, T> { type Item = &'a [T]; }",
+            "スライスを借りる要素を返す",
+        ),
+    ],
+    methods: &[
+        (
+            "v.iter() / iter_mut() / into_iter()",
+            "&T / &mut T / T のイテレータ",
+        ),
+        ("map / filter / take / skip", "遅延評価のアダプタ"),
+        ("enumerate / zip / flatten / rev", "組み合わせ系のアダプタ"),
+        ("collect::<Vec<_>>()", "コレクションに収集"),
+        ("sum / product / count / min / max", "消費アダプタ"),
+        ("fold(0, |acc, x| acc + x)", "畳み込み"),
+        (
+            "fold(String::new(), |mut s, x| { s.push_str(x); s })",
+            "1つの String に足していく",
+        ),
+        ("any / all / find / position", "条件チェック・検索"),
+        (
+            "slice.windows(n) / chunks(n)",
+            "重なる区間 / 重ならない区切り",
+        ),
+    ],
+    gotchas: &[
+        "アダプタは遅延評価。collectなどで消費しないと何も起きない",
+        "Fn / FnMut / FnOnce はキャプチャの仕方で自動的に決まる",
+        "into_iter()は元のコレクションをムーブする",
+        "filter の後の collect は件数が分からないので、Vec を伸ばしながら集める",
+    ],
+};
+
+/// すべてのデモを実行
+pub fn run_all() {
+    println!("╔════════════════════════════════════════════════════════════════╗");
+    println!("║          Rustイテレータとクロージャサンプル                      ║");
+    println!("╚════════════════════════════════════════════════════════════════╝");
+
+    lesson_api::run_topic("iterators_closures");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn windows_matches_std() {
+        let data: Vec<i32> = (1..=7).collect();
+        for len in 0..=data.len() {
+            let slice = &data[..len];
+            for size in 1..=8 {
+                let ours: Vec<&[i32]> = windows(slice, size).collect();
+                let expected: Vec<&[i32]> = slice.windows(size).collect();
+                assert_eq!(ours, expected, "len {} size {}", len, size);
+                assert_eq!(windows(slice, size).len(), expected.len());
+            }
+        }
+    }
+
+    #[test]
+    fn chunks_matches_std() {
+        let data: Vec<i32> = (1..=7).collect();
+        for len in 0..=data.len() {
+            let slice = &data[..len];
+            for size in 1..=8 {
+                let ours: Vec<&[i32]> = chunks(slice, size).collect();
+                let expected: Vec<&[i32]> = slice.chunks(size).collect();
+                assert_eq!(ours, expected, "len {} size {}", len, size);
+                assert_eq!(chunks(slice, size).len(), expected.len());
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "non-zero")]
+    fn zero_size_panics_like_std() {
+        windows(&[1, 2, 3], 0);
+    }
+}
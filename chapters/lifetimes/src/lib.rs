@@ -6,6 +6,8 @@
 // ライフタイムはRustの借用チェッカーが参照の有効期間を追跡するための仕組み
 // 主な目的: ダングリング参照（無効なメモリを指す参照）を防ぐ
 
+use lesson_api::{println, CheatSheet};
+
 /// ライフタイムが必要な理由
 pub fn why_lifetimes() {
     println!("\n=== ライフタイムが必要な理由 ===");
@@ -313,6 +315,212 @@ pub fn practical_examples() {
     }
 }
 
+/// 区切り文字で分割するイテレータ（標準の str::split と同じ動き）
+///
+/// 切り出した &'a str は元の文字列を指すので、イテレータより長く使える
+pub struct SplitOn<'a> {
+    /// まだ分割していない残り。最後の要素を返したら None
+    remainder: Option<&'a str>,
+    delimiter: char,
+}
+
+impl<'a> SplitOn<'a> {
+    pub fn new(text: &'a str, delimiter: char) -> Self {
+        SplitOn {
+            remainder: Some(text),
+            delimiter,
+        }
+    }
+}
+
+impl<'a> Iterator for SplitOn<'a> {
+    // Item のライフタイムは &mut self ではなく元の文字列の 'a
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        let rest = self.remainder?;
+        match rest.find(self.delimiter) {
+            Some(i) => {
+                // 区切り文字は全角でもよいので、バイト数は len_utf8 で数える
+                self.remainder = Some(&rest[i + self.delimiter.len_utf8()..]);
+                Some(&rest[..i])
+            }
+            None => {
+                self.remainder = None;
+                Some(rest)
+            }
+        }
+    }
+}
+
+/// 分割イテレータを自作する
+pub fn split_iterator() {
+    println!("\n=== 分割イテレータを自作する ===");
+
+    let csv = String::from("apple,banana,,cherry");
+    let fields: Vec<&str>;
+    {
+        // イテレータはこのブロックで消えるが、切り出した &str は csv を借りているので残る
+        let split = SplitOn::new(&csv, ',');
+        fields = split.collect();
+    }
+    println!("SplitOn::new({:?}, ',') = {:?}", csv, fields);
+    println!(
+        "str::split の結果       = {:?}",
+        csv.split(',').collect::<Vec<_>>()
+    );
+
+    // 端の区切り文字や空文字列でも、標準と同じく空の要素が出る
+    for text in [",a,", "", "区切り、全角、文字"] {
+        let delimiter = if text.contains('、') { '、' } else { ',' };
+        println!(
+            "  {:?} → {:?}",
+            text,
+            SplitOn::new(text, delimiter).collect::<Vec<_>>()
+        );
+    }
+
+    // Iterator を実装すれば、アダプタもそのまま使える
+    let lengths: Vec<usize> = SplitOn::new("one two three", ' ').map(str::len).collect();
+    println!("map(str::len) = {:?}", lengths);
+
+    // よくある間違い: fn next(&mut self) -> Option<&str>
+    // 省略規則で戻り値が &mut self の借用になり、self.remainder（&'a str）を返すと
+    // 「戻り値のライフタイムが self より長い」と型が合わずにエラーになる。
+    // Item を &'a str にして、元の文字列に結び付けるのが正解
+    println!("Item = &'a str: 要素はイテレータではなく元の文字列を借りる");
+}
+
+// ----------------------------------------------------------------------------
+// ゼロコピーのパーサ
+// ----------------------------------------------------------------------------
+
+/// ログの1行を分解したもの。どのフィールドも入力の文字列を借りている（コピーしない）
+///
+/// 形式: `時刻 レベル 発生元: メッセージ`
+#[derive(Debug, PartialEq, Eq)]
+pub struct LogEntry<'a> {
+    pub timestamp: &'a str,
+    pub level: &'a str,
+    pub target: &'a str,
+    pub message: &'a str,
+}
+
+/// 入力が終わった後も残しておきたいとき用の、所有する版
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OwnedLogEntry {
+    pub timestamp: String,
+    pub level: String,
+    pub target: String,
+    pub message: String,
+}
+
+/// 足りなかったフィールドの名前
+#[derive(Debug, PartialEq, Eq)]
+pub struct MissingField(pub &'static str);
+
+/// 1行を解析する。戻り値の 'a は入力の line と同じ（line より長くは使えない）
+pub fn parse_log_line(line: &str) -> Result<LogEntry<'_>, MissingField> {
+    let mut parts = line.trim().splitn(3, ' ');
+    let timestamp = parts
+        .next()
+        .filter(|s| !s.is_empty())
+        .ok_or(MissingField("timestamp"))?;
+    let level = parts.next().ok_or(MissingField("level"))?;
+    let rest = parts.next().ok_or(MissingField("target"))?;
+    let (target, message) = rest.split_once(": ").ok_or(MissingField("message"))?;
+    Ok(LogEntry {
+        timestamp,
+        level,
+        target,
+        message,
+    })
+}
+
+impl LogEntry<'_> {
+    /// フィールドごとに str::to_owned（ToOwned）で String にする
+    ///
+    /// LogEntry に ToOwned 自体は実装できない。ToOwned::Owned は Borrow<LogEntry> を
+    /// 実装する必要があるが、OwnedLogEntry の中には LogEntry が無いので &LogEntry を返せない
+    pub fn to_owned_entry(&self) -> OwnedLogEntry {
+        OwnedLogEntry {
+            timestamp: self.timestamp.to_owned(),
+            level: self.level.to_owned(),
+            target: self.target.to_owned(),
+            message: self.message.to_owned(),
+        }
+    }
+}
+
+impl OwnedLogEntry {
+    /// 逆向き: 所有する版を借りて、ゼロコピーの版として見る
+    pub fn as_entry(&self) -> LogEntry<'_> {
+        LogEntry {
+            timestamp: &self.timestamp,
+            level: &self.level,
+            target: &self.target,
+            message: &self.message,
+        }
+    }
+}
+
+/// ゼロコピーのパーサ
+pub fn zero_copy_parser() {
+    println!("\n=== ゼロコピーのパーサ ===");
+
+    let log = String::from(
+        "2024-05-01T09:00:00 INFO server: listening on :8080\n\
+         2024-05-01T09:00:03 WARN db: slow query (1200ms)\n\
+         2024-05-01T09:00:04 ERROR db: connection lost\n\
+         broken-line",
+    );
+
+    // 解析しても String は作らないので、ヒープの確保は Vec の分だけ
+    let (entries, stats) =
+        lesson_api::alloc_count::measure(|| log.lines().map(parse_log_line).collect::<Vec<_>>());
+    for entry in &entries {
+        match entry {
+            Ok(e) => println!("  [{:<5}] {:<6} {}", e.level, e.target, e.message),
+            Err(MissingField(field)) => println!("  解析できない行（{} がありません）", field),
+        }
+    }
+    if lesson_api::alloc_count::is_installed() {
+        println!(
+            "{} 行の解析で確保 {} 回（結果を入れる Vec だけ）",
+            entries.len(),
+            stats.allocations
+        );
+    }
+
+    // フィールドは log の中を指している（アドレスが log の範囲に入る）
+    if let Some(Ok(first)) = entries.first() {
+        let range = log.as_ptr() as usize..log.as_ptr() as usize + log.len();
+        println!(
+            "message は log の中を指している: {}",
+            range.contains(&(first.message.as_ptr() as usize))
+        );
+    }
+
+    // log より長く残したいものだけ、所有する版にする
+    let errors: Vec<OwnedLogEntry> = entries
+        .iter()
+        .flatten()
+        .filter(|e| e.level == "ERROR")
+        .map(LogEntry::to_owned_entry)
+        .collect();
+    drop(entries);
+    drop(log); // ここで入力を捨てても errors は使える
+    for error in &errors {
+        println!("残したエラー: {:?}", error.as_entry());
+    }
+
+    // 借りる版を返す関数は、入力との関係をライフタイムで表す:
+    //   fn parse_log_line(line: &str) -> Result<LogEntry<'_>, MissingField>
+    //   （省略しないと fn parse_log_line<'a>(line: &'a str) -> Result<LogEntry<'a>, _>）
+    println!("LogEntry<'a>: 入力を借りるので速いが、入力より長くは持てない");
+    println!("OwnedLogEntry: コピーするぶん遅いが、どこへでも持って行ける");
+}
+
 /// ライフタイムのベストプラクティス
 pub fn best_practices() {
     println!("\n=== ライフタイムのベストプラクティス ===");
@@ -346,20 +554,96 @@ pub fn best_practices() {
     );
 }
 
+/// チートシート用のデータ（`cheatsheet lifetimes` で表示）
+pub const CHEATSHEET: CheatSheet = CheatSheet {
+    syntax: &[
+        (
+            "fn longest<'a>(x: &'a str, y: &'a str) -> &'a str",
+            "戻り値と引数のライフタイムを関連付ける",
+        ),
+        ("struct Excerpt<'a> { part: &'a str }", "参照を持つ構造体"),
+        ("impl<'a> Excerpt<'a> {}", "ライフタイム付きのimpl"),
+        ("let s: &'static str = \"...\";", "'static ライフタイム"),
+        (
+            "struct Parser<'c, 's: 'c>",
+            "ライフタイムの制約（'s は 'c より長い）",
+        ),
+    ],
+    methods: &[
+        ("Box::leak(b)", "意図的にリークさせて &'static を得る"),
+        ("s.split_whitespace()", "元の文字列を借用するイテレータ"),
+        (
+            "impl<'a> Iterator for Split<'a> { type Item = &'a str; }",
+            "元の文字列を借りる要素を返す",
+        ),
+        (
+            "fn parse(line: &str) -> Entry<'_>",
+            "入力を借りる構造体を返す（ゼロコピー）",
+        ),
+    ],
+    gotchas: &[
+        "ライフタイム注釈は参照の寿命を変えない。関係をコンパイラに伝えるだけ",
+        "省略規則: 入力が1つ、または &self があれば出力のライフタイムは推論される",
+        "エラーで'staticを提案されても、多くは別の設計上の問題",
+    ],
+};
+
 /// すべてのデモを実行
 pub fn run_all() {
     println!("╔════════════════════════════════════════════════════════════════╗");
     println!("║          Rustライフタイムサンプル                               ║");
     println!("╚════════════════════════════════════════════════════════════════╝");
 
-    why_lifetimes();
-    function_lifetimes();
-    lifetime_syntax();
-    struct_lifetimes();
-    lifetime_elision();
-    static_lifetime();
-    complex_lifetimes();
-    lifetime_bounds();
-    practical_examples();
-    best_practices();
+    lesson_api::run_topic("lifetimes");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_on_matches_std_split() {
+        let cases = [
+            ("apple,banana,,cherry", ','),
+            (",a,", ','),
+            ("", ','),
+            (",", ','),
+            ("no delimiter", ','),
+            ("区切り、全角、文字", '、'),
+            ("a\u{1F980}b", '\u{1F980}'),
+        ];
+        for (text, delimiter) in cases {
+            let ours: Vec<&str> = SplitOn::new(text, delimiter).collect();
+            let expected: Vec<&str> = text.split(delimiter).collect();
+            assert_eq!(ours, expected, "{:?} を {:?} で分割", text, delimiter);
+        }
+    }
+
+    #[test]
+    fn parse_log_line_borrows_fields() {
+        let line = "2024-05-01T09:00:03 WARN db: slow query: 1200ms";
+        let entry = parse_log_line(line).unwrap();
+        assert_eq!(
+            entry,
+            LogEntry {
+                timestamp: "2024-05-01T09:00:03",
+                level: "WARN",
+                target: "db",
+                message: "slow query: 1200ms",
+            }
+        );
+        assert_eq!(entry.to_owned_entry().as_entry(), entry);
+        assert_eq!(parse_log_line(""), Err(MissingField("timestamp")));
+        assert_eq!(parse_log_line("t INFO"), Err(MissingField("target")));
+        assert_eq!(parse_log_line("t INFO x"), Err(MissingField("message")));
+    }
+
+    #[test]
+    fn split_on_is_fused_after_last_piece() {
+        let mut split = SplitOn::new("a,b", ',');
+        assert_eq!(split.next(), Some("a"));
+        assert_eq!(split.next(), Some("b"));
+        assert_eq!(split.next(), None);
+        assert_eq!(split.next(), None);
+    }
 }
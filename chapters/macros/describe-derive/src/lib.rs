@@ -0,0 +1,228 @@
+// ============================================================================
+// #[derive(Describe)] - 手続き的マクロ（derive マクロ）
+// 公式ドキュメント: https://doc.rust-lang.org/book/ch20-05-macros.html#how-to-write-a-custom-derive-macro
+// ============================================================================
+//
+// 手続き的マクロの3つの種類をこのクレートにまとめている。
+//
+// - #[derive(Describe)]    derive マクロ（構造体に impl を足す）
+// - #[trace]               属性マクロ（付けた関数を書き換える）
+// - hex_color!("#ff8800")  関数風マクロ（引数のトークンから式を作る）
+//
+// 構造体の定義（TokenStream）を受け取り、フィールド名と型を返す
+// macros::Describe の実装を生成する。
+//
+//   #[derive(Describe)]
+//   struct Point { x: i32, y: i32 }
+//
+// は、おおよそ次のコードに展開される（cargo expand で確かめられる）:
+//
+//   impl ::macros::Describe for Point {
+//       fn type_name() -> &'static str { "Point" }
+//       fn fields() -> &'static [(&'static str, &'static str)] {
+//           &[("x", "i32"), ("y", "i32")]
+//       }
+//   }
+//
+// 構文木への変換は syn、コードの組み立ては quote に任せるのが定番。
+
+use proc_macro::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{parse_macro_input, Data, DeriveInput, Fields, FnArg, ItemFn, LitStr, Pat, ReturnType};
+
+/// フィールド名と型を返す macros::Describe を実装する
+#[proc_macro_derive(Describe)]
+pub fn derive_describe(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand(&input) {
+        Ok(tokens) => tokens.into(),
+        // エラーは compile_error!(..) に変換すると、該当する場所を指してコンパイルエラーになる
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "Describe can only be derived for structs",
+        ));
+    };
+
+    // 名前付きフィールドは名前を、タプル構造体は 0, 1, .. を使う（ユニット構造体は空）
+    let fields = match &data.fields {
+        Fields::Named(named) => named
+            .named
+            .iter()
+            .map(|field| {
+                (
+                    field.ident.as_ref().unwrap().to_string(),
+                    type_text(&field.ty),
+                )
+            })
+            .collect(),
+        Fields::Unnamed(unnamed) => unnamed
+            .unnamed
+            .iter()
+            .enumerate()
+            .map(|(i, field)| (i.to_string(), type_text(&field.ty)))
+            .collect(),
+        Fields::Unit => Vec::new(),
+    };
+    let names = fields.iter().map(|(name, _)| name);
+    let types = fields.iter().map(|(_, ty)| ty);
+
+    let ident = &input.ident;
+    let type_name = ident.to_string();
+    // ジェネリクスがあれば impl<T> .. for Name<T> where .. をそのまま引き継ぐ
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::macros::Describe for #ident #ty_generics #where_clause {
+            fn type_name() -> &'static str {
+                #type_name
+            }
+
+            fn fields() -> &'static [(&'static str, &'static str)] {
+                &[#((#names, #types)),*]
+            }
+        }
+    })
+}
+
+/// 型をソースコードに近い文字列にする
+/// TokenStream の to_string は `Vec < String >` のようにトークンの間に空白を入れるので詰める
+fn type_text(ty: &syn::Type) -> String {
+    let spaced = ty.to_token_stream().to_string();
+    let mut text = String::with_capacity(spaced.len());
+    let mut chars = spaced.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == ' ' {
+            let before = text.chars().last();
+            let after = chars.peek().copied();
+            let glued_before = matches!(before, Some('<' | '(' | '[' | '&' | ':'));
+            let glued_after = matches!(after, Some('<' | '>' | ',' | ';' | ')' | ']' | ':'));
+            if glued_before || glued_after {
+                continue;
+            }
+        }
+        text.push(c);
+    }
+    text
+}
+
+// ----------------------------------------------------------------------------
+// #[trace] - 属性マクロ
+// ----------------------------------------------------------------------------
+//
+//   #[trace]
+//   fn add(a: i32, b: i32) -> i32 { a + b }
+//
+// は、おおよそ次のコードに展開される:
+//
+//   fn add(a: i32, b: i32) -> i32 {
+//       ::macros::trace::enter("add", &[("a", format!("{:?}", a)), ("b", format!("{:?}", b))]);
+//       let result = (move || -> i32 { a + b })();
+//       ::macros::trace::exit("add", &format!("{:?}", result));
+//       result
+//   }
+//
+// 本体をクロージャに包むので、本体の中の return や ? もそのまま使える。
+
+/// 呼び出しのたびに引数と戻り値を表示するように関数を書き換える
+///
+/// 引数と戻り値の型は Debug を実装している必要がある。
+#[proc_macro_attribute]
+pub fn trace(attr: TokenStream, item: TokenStream) -> TokenStream {
+    // attr は #[trace(..)] の括弧の中、item は属性を付けた関数そのもの
+    let attr = proc_macro2::TokenStream::from(attr);
+    let function = parse_macro_input!(item as ItemFn);
+    match expand_trace(attr, &function) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+fn expand_trace(
+    attr: proc_macro2::TokenStream,
+    function: &ItemFn,
+) -> syn::Result<proc_macro2::TokenStream> {
+    if !attr.is_empty() {
+        return Err(syn::Error::new_spanned(attr, "trace takes no arguments"));
+    }
+    let signature = &function.sig;
+    if let Some(asyncness) = &signature.asyncness {
+        return Err(syn::Error::new_spanned(
+            asyncness,
+            "trace does not support async fn",
+        ));
+    }
+
+    // 引数は `名前: 型` の形だけを受け付ける（self や (a, b): (i32, i32) は扱わない）
+    let mut names = Vec::new();
+    for input in &signature.inputs {
+        let FnArg::Typed(typed) = input else {
+            return Err(syn::Error::new_spanned(
+                input,
+                "trace does not support methods taking self",
+            ));
+        };
+        let Pat::Ident(pat) = typed.pat.as_ref() else {
+            return Err(syn::Error::new_spanned(
+                &typed.pat,
+                "trace supports only simple parameter names",
+            ));
+        };
+        names.push(&pat.ident);
+    }
+    let labels = names.iter().map(|name| name.to_string());
+
+    let attrs = &function.attrs;
+    let vis = &function.vis;
+    let block = &function.block;
+    let name = signature.ident.to_string();
+    // 戻り値の型を書いておかないと、本体の return の型をクロージャが推論できないことがある
+    let output = match &signature.output {
+        ReturnType::Default => quote! {},
+        ReturnType::Type(arrow, ty) => quote! { #arrow #ty },
+    };
+    Ok(quote! {
+        #(#attrs)*
+        #vis #signature {
+            ::macros::trace::enter(#name, &[#((#labels, format!("{:?}", #names))),*]);
+            let result = (move || #output #block)();
+            ::macros::trace::exit(#name, &format!("{:?}", result));
+            result
+        }
+    })
+}
+
+// ----------------------------------------------------------------------------
+// hex_color! - 関数風マクロ
+// ----------------------------------------------------------------------------
+//
+// 文字列をコンパイル時に読んで (r, g, b) のタプルにする。書き間違いは実行時ではなく
+// コンパイルエラーになる。文字列の中身を調べるのは macro_rules! ではできない。
+//
+//   hex_color!("#ff8800")  →  (255u8, 136u8, 0u8)
+//   hex_color!("#ff88")    →  error: expected a color like "#ff8800"
+
+/// "#rrggbb" を (u8, u8, u8) の定数にする
+#[proc_macro]
+pub fn hex_color(input: TokenStream) -> TokenStream {
+    let literal = parse_macro_input!(input as LitStr);
+    match parse_hex_color(&literal.value()) {
+        Some((r, g, b)) => quote! { (#r, #g, #b) }.into(),
+        None => syn::Error::new_spanned(&literal, "expected a color like \"#ff8800\"")
+            .to_compile_error()
+            .into(),
+    }
+}
+
+fn parse_hex_color(text: &str) -> Option<(u8, u8, u8)> {
+    let hex = text.strip_prefix('#')?;
+    if hex.len() != 6 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    Some((channel(0)?, channel(2)?, channel(4)?))
+}
@@ -0,0 +1,924 @@
+// ============================================================================
+// Rust マクロサンプル
+// 公式ドキュメント: https://doc.rust-lang.org/book/ch20-05-macros.html
+// ============================================================================
+//
+// マクロはコンパイル時にコードを生成する。
+//
+// - 宣言的マクロ（macro_rules!）   トークンのパターンにマッチして置き換える（my_vec!、max!、json! など）
+// - 手続き的マクロ（derive など）   TokenStream を受け取って TokenStream を返す Rust の関数
+//
+// 手続き的マクロは専用のクレートに置く必要がある。この章の #[derive(Describe)]、
+// 属性マクロ #[trace]、関数風マクロ hex_color! は describe-derive/（proc-macro = true のクレート）にある。
+
+use lesson_api::{println, text, CheatSheet};
+use std::collections::HashMap;
+use std::fmt;
+
+// derive が生成するコードは ::macros::Describe を名指しする。
+// このクレートの中でもその名前で自分自身を参照できるようにしておく（serde なども同じ手を使う）
+extern crate self as macros;
+
+/// #[derive(Describe)] を再公開する（トレイトと derive マクロは同じ名前で共存できる）
+pub use describe_derive::Describe;
+/// 属性マクロ #[trace] と関数風マクロ hex_color! も再公開する
+pub use describe_derive::{hex_color, trace};
+
+// ----------------------------------------------------------------------------
+// macro_rules! の基本
+// ----------------------------------------------------------------------------
+//
+// 腕（パターン => 展開）を上から順に試し、最初にマッチした腕のトークンで置き換える。
+// パターンの $x:expr の expr はフラグメント指定子で、ほかに ident（識別子）、ty（型）、
+// literal（リテラル）、tt（トークン木1つ）などがある。
+//
+// macro_rules! はテキストの順にスコープに入るので、使う場所より前に定義する。
+// ほかのクレートから使わせるなら #[macro_export] を付ける。
+
+/// vec! を作り直したもの（The Book 20.5 の例に、空と [値; 個数] の腕を足した）
+///
+/// clippy は push の並びを vec![..] にするよう勧めるが、vec! を使わずに書くのが目的なので
+/// 呼び出す関数の側で clippy::vec_init_then_push を許可している
+macro_rules! my_vec {
+    () => {
+        Vec::new()
+    };
+    ($elem:expr ; $n:expr) => {{
+        let mut temp_vec = Vec::new();
+        temp_vec.resize($n, $elem);
+        temp_vec
+    }};
+    // $(..),+ は「カンマ区切りで1回以上」、$(,)? は末尾のカンマを許す
+    ($($x:expr),+ $(,)?) => {{
+        let mut temp_vec = Vec::new();
+        $(temp_vec.push($x);)+
+        temp_vec
+    }};
+}
+
+/// いくつでも引数を取る max!。先頭と「残りの max!」を比べる再帰で書く
+macro_rules! max {
+    ($x:expr $(,)?) => {
+        $x
+    };
+    ($x:expr, $($rest:expr),+ $(,)?) => {{
+        // 引数を1回だけ評価するよう、いったん変数に入れる
+        let first = $x;
+        let rest = max!($($rest),+);
+        if first > rest {
+            first
+        } else {
+            rest
+        }
+    }};
+}
+
+/// `key => value` の組を並べて HashMap を作る
+macro_rules! hashmap {
+    ($($key:expr => $value:expr),* $(,)?) => {{
+        let mut map = HashMap::new();
+        $(map.insert($key, $value);)*
+        map
+    }};
+}
+
+/// 入れ子の繰り返し: [..] の中の $x は内側の $(..),* で、行は外側の $(..),* で繰り返す
+macro_rules! matrix {
+    ($([$($x:expr),* $(,)?]),* $(,)?) => {
+        vec![$(vec![$($x),*]),*]
+    };
+}
+
+/// $(..)? は0回か1回。区切り文字を省略できる join!
+macro_rules! join {
+    ($($item:expr),+ $(; sep = $sep:expr)?) => {{
+        let sep: Option<&str> = None $(.or(Some($sep)))?;
+        [$($item.to_string()),+].join(sep.unwrap_or(", "))
+    }};
+}
+
+/// 引数をそのまま2回書く（悪い例）
+macro_rules! square_twice {
+    ($x:expr) => {
+        $x * $x
+    };
+}
+
+/// 引数を1回だけ評価する（良い例）
+macro_rules! square_once {
+    ($x:expr) => {{
+        let value = $x;
+        value * value
+    }};
+}
+
+/// 呼び出し側から受け取った識別子で変数を作る（名前を渡せば呼び出し側から見える）
+macro_rules! make_var {
+    ($name:ident = $value:expr) => {
+        let $name = $value;
+    };
+}
+
+/// マクロの中で書いた名前で変数を作る（呼び出し側からは見えない）
+macro_rules! make_hidden_var {
+    ($value:expr) => {
+        #[allow(unused_variables)]
+        let hidden = $value;
+    };
+}
+
+/// 関数などのアイテムには衛生性が無いので、マクロの中で書いた名前のまま定義される
+macro_rules! define_helper {
+    () => {
+        fn helper() -> &'static str {
+            "define_helper! が定義した helper()"
+        }
+    };
+}
+
+/// my_vec! で vec! を作り直す
+#[allow(clippy::vec_init_then_push)]
+pub fn my_vec_macro() {
+    println!("\n=== my_vec! で vec! を作り直す ===");
+
+    let empty: Vec<i32> = my_vec![];
+    let zeros = my_vec![0; 3];
+    let numbers = my_vec![1, 2, 3,];
+    println!("my_vec![]        → {:?}", empty);
+    println!("my_vec![0; 3]    → {:?}", zeros);
+    println!("my_vec![1, 2, 3,] → {:?}", numbers);
+
+    // 要素は式なら何でもよい。腕は上から順に試される
+    let words = my_vec![String::from("macro"), "rules".to_uppercase()];
+    println!("my_vec![String::from(..), ..] → {:?}", words);
+
+    // my_vec![1, 2, 3] は次のコードに展開される（$(..)+ が push を3回並べる）
+    println!("\nmy_vec![1, 2, 3] の展開結果:");
+    println!("  {{");
+    println!("      let mut temp_vec = Vec::new();");
+    for x in ["1", "2", "3"] {
+        println!("      temp_vec.push({});", x);
+    }
+    println!("      temp_vec");
+    println!("  }}");
+    println!("腕の中の {{{{ .. }}}} は、展開結果を1つのブロック式にするため（let を書ける）");
+}
+
+/// 繰り返しのパターン（max!、hashmap!、入れ子）
+pub fn repetition_patterns() {
+    println!("\n=== 繰り返しのパターン（max!、hashmap!、入れ子） ===");
+
+    // 再帰で何個でも引数を取れる。PartialOrd なら型は何でもよい
+    println!("max!(3) = {}", max!(3));
+    println!("max!(3, 9, 4) = {}", max!(3, 9, 4));
+    println!("max!(1.5, -2.0, 0.25) = {}", max!(1.5, -2.0, 0.25));
+    println!(
+        "max!(\"pear\", \"apple\", \"plum\") = {}",
+        max!("pear", "apple", "plum")
+    );
+
+    // $(..),* は0回以上。=> のような区切りもパターンに書ける
+    let scores = hashmap! { "Alice" => 90, "Bob" => 72 };
+    let mut sorted: Vec<_> = scores.iter().collect();
+    sorted.sort();
+    println!(
+        "hashmap!{{ \"Alice\" => 90, \"Bob\" => 72 }} → {:?}",
+        sorted
+    );
+
+    // 入れ子の繰り返し
+    let identity = matrix![[1, 0, 0], [0, 1, 0], [0, 0, 1]];
+    println!("matrix![[1, 0, 0], ..]:");
+    for row in &identity {
+        println!("  {:?}", row);
+    }
+
+    // $(..)? で省略できる部分
+    println!("join!(1, 2, 3) = {}", join!(1, 2, 3));
+    println!(
+        "join!(1, 2, 3; sep = \" < \") = {}",
+        join!(1, 2, 3; sep = " < ")
+    );
+
+    println!("\n繰り返しの書き方:");
+    for (pattern, meaning) in [
+        ("$(..),*", "カンマ区切りで0回以上"),
+        ("$(..),+", "カンマ区切りで1回以上"),
+        ("$(..)?", "0回か1回（区切りは書けない）"),
+        ("$(..)*", "区切り無しで0回以上（impl_from_number! など）"),
+    ] {
+        println!("  {:<10} {}", pattern, meaning);
+    }
+    println!("展開側の $(..) は、中で使った変数と同じ回数だけ繰り返される");
+}
+
+/// マクロの衛生性（hygiene）
+#[allow(clippy::vec_init_then_push)]
+pub fn macro_hygiene() {
+    println!("\n=== マクロの衛生性（hygiene） ===");
+
+    // my_vec! の中の temp_vec と、呼び出し側の temp_vec は別の変数として扱われる
+    let temp_vec = "呼び出し側の temp_vec";
+    let made = my_vec![temp_vec.len(), 2];
+    println!(
+        "my_vec![temp_vec.len(), 2] → {:?}（{:?} は上書きされない）",
+        made, temp_vec
+    );
+
+    // 識別子を引数で渡せば、その名前は呼び出し側のものになる
+    make_var!(answer = 42);
+    println!("make_var!(answer = 42) の後で answer = {}", answer);
+
+    // マクロの中で書いた hidden は呼び出し側からは見えない
+    let hidden = "呼び出し側の hidden";
+    make_hidden_var!("マクロの中の hidden");
+    println!("make_hidden_var!(..) の後でも hidden = {:?}", hidden);
+    // hidden を定義していなければ、make_hidden_var! の後に hidden と書くと E0425 になる
+
+    // 衛生性が効くのはローカル変数とラベルだけ。関数や構造体の名前はそのまま見える
+    define_helper!();
+    println!("{}", helper());
+
+    // 引数の式は展開先に書いた回数だけ評価される
+    let mut calls = 0;
+    let mut next = || {
+        calls += 1;
+        calls
+    };
+    let twice = square_twice!(next());
+    println!(
+        "\nsquare_twice!(next()) = {}（$x * $x なので next() が2回呼ばれ 1 * 2）",
+        twice
+    );
+    let once = square_once!(next());
+    println!(
+        "square_once!(next()) = {}（let で受けてから使うので1回だけ）",
+        once
+    );
+    println!("next() を呼んだ回数: {}", calls);
+    println!("マクロの中からクレートの要素を指すときは $crate:: を使う（describe_struct! を参照）");
+}
+
+// ----------------------------------------------------------------------------
+// Describe トレイトと derive
+// ----------------------------------------------------------------------------
+
+/// 構造体のフィールド名と型を返す（#[derive(Describe)] で実装する）
+pub trait Describe {
+    /// 型の名前
+    fn type_name() -> &'static str;
+
+    /// (フィールド名, 型) の一覧。タプル構造体は 0, 1, .. が名前になる
+    fn fields() -> &'static [(&'static str, &'static str)];
+
+    /// struct Name { a: A, b: B } の形の説明
+    fn describe() -> String {
+        let fields: Vec<String> = Self::fields()
+            .iter()
+            .map(|(name, ty)| format!("{}: {}", name, ty))
+            .collect();
+        if fields.is_empty() {
+            format!("struct {}", Self::type_name())
+        } else {
+            format!("struct {} {{ {} }}", Self::type_name(), fields.join(", "))
+        }
+    }
+}
+
+#[derive(Debug, Describe)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+#[derive(Debug, Describe)]
+pub struct Config {
+    pub name: String,
+    pub tags: Vec<String>,
+    pub limits: HashMap<String, u32>,
+    pub motto: Option<&'static str>,
+}
+
+/// タプル構造体とジェネリクス
+#[derive(Debug, Describe)]
+pub struct Labeled<T: Clone>(pub &'static str, pub T);
+
+#[derive(Debug, Describe)]
+pub struct Marker;
+
+// 列挙型に付けると、describe-derive が compile_error! を返してコンパイルエラーになる:
+//
+//   #[derive(Describe)]
+//   enum Shape { Circle, Square }
+//   // error: Describe can only be derived for structs
+
+/// 型の説明とフィールドの一覧を表示する
+fn print_description<T: Describe>() {
+    println!("{}", T::describe());
+    for (name, ty) in T::fields() {
+        println!("  {:<8} {}", name, ty);
+    }
+}
+
+/// #[derive(Describe)]（手続き的マクロ）
+pub fn derive_describe() {
+    println!("\n=== #[derive(Describe)]（手続き的マクロ） ===");
+
+    // #[derive(Describe)] と書くだけで、コンパイル時に impl Describe for .. が生成される
+    print_description::<Point>();
+    print_description::<Config>();
+    print_description::<Labeled<f64>>();
+    print_description::<Marker>();
+
+    // derive は impl を足すだけで、型そのものはふつうに使える
+    let point = Point { x: 3, y: -4 };
+    let labeled = Labeled("ratio", 0.75);
+    println!("値: {:?} / {:?}", point, labeled);
+    println!(
+        "{} のフィールド数: {}",
+        Point::type_name(),
+        Point::fields().len()
+    );
+}
+
+// ----------------------------------------------------------------------------
+// 宣言的マクロとの比較
+// ----------------------------------------------------------------------------
+
+// 同じことを macro_rules! でやると、構造体の定義ごとマクロに渡すことになる。
+// 宣言的マクロは既にある型の定義を読めないので、定義と impl をまとめて生成する。
+macro_rules! describe_struct {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident {
+            $($field_vis:vis $field:ident : $ty:ty),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis struct $name {
+            $($field_vis $field: $ty),*
+        }
+
+        impl $crate::Describe for $name {
+            fn type_name() -> &'static str {
+                stringify!($name)
+            }
+
+            fn fields() -> &'static [(&'static str, &'static str)] {
+                &[$((stringify!($field), stringify!($ty))),*]
+            }
+        }
+    };
+}
+
+describe_struct! {
+    #[derive(Debug)]
+    pub struct Order {
+        pub id: u32,
+        pub items: Vec<String>,
+    }
+}
+
+/// 宣言的マクロと手続き的マクロ
+pub fn declarative_vs_procedural() {
+    println!("\n=== 宣言的マクロと手続き的マクロ ===");
+
+    // macro_rules! で定義と impl を一度に作った型
+    print_description::<Order>();
+    let order = Order {
+        id: 7,
+        items: vec!["coffee".to_string(), "bagel".to_string()],
+    };
+    println!("値: {:?}", order);
+
+    // 同じ Describe を2通りで実装したので、違いを並べてみる
+    println!("\n比べてみると:");
+    let rows = [
+        (
+            "書く場所",
+            "同じクレートの中",
+            "proc-macro = true の別クレート",
+        ),
+        (
+            "入力",
+            "パターンにマッチしたトークン",
+            "TokenStream（syn で構文木にする）",
+        ),
+        (
+            "既存の型",
+            "読めない（定義ごと渡す）",
+            "#[derive] で付けた型を読める",
+        ),
+        (
+            "ジェネリクス",
+            "パターンを書き足す必要がある",
+            "split_for_impl で引き継げる",
+        ),
+        (
+            "エラー",
+            "マッチしないと no rules expected ..",
+            "compile_error! で好きな文言",
+        ),
+        ("ビルド", "速い", "syn などのコンパイルが必要"),
+    ];
+    for (aspect, declarative, procedural) in rows {
+        println!("  [{}]", aspect);
+        println!("    macro_rules!: {}", declarative);
+        println!("    derive      : {}", procedural);
+    }
+    println!("展開結果は cargo expand（cargo install cargo-expand）で確かめられる");
+}
+
+// ----------------------------------------------------------------------------
+// 属性マクロと関数風マクロ
+// ----------------------------------------------------------------------------
+
+/// #[trace] が生成するコードから呼ばれる関数（直接は使わない）
+///
+/// 生成されたコードは ::macros::trace::enter のように絶対パスで呼ぶので pub にしておく。
+#[doc(hidden)]
+pub mod trace {
+    use lesson_api::println;
+    use std::cell::Cell;
+
+    thread_local! {
+        // 呼び出しの深さ。再帰すると字下げが深くなる
+        static DEPTH: Cell<usize> = const { Cell::new(0) };
+    }
+
+    pub fn enter(name: &str, args: &[(&str, String)]) {
+        let args: Vec<String> = args
+            .iter()
+            .map(|(arg, value)| format!("{} = {}", arg, value))
+            .collect();
+        let depth = DEPTH.with(|depth| depth.replace(depth.get() + 1));
+        println!("{}→ {}({})", "  ".repeat(depth), name, args.join(", "));
+    }
+
+    pub fn exit(name: &str, result: &str) {
+        let depth = DEPTH.with(|depth| {
+            let outer = depth.get().saturating_sub(1);
+            depth.set(outer);
+            outer
+        });
+        println!("{}← {} = {}", "  ".repeat(depth), name, result);
+    }
+}
+
+// 属性を付けるだけで、呼び出しと戻り値が表示されるようになる
+#[trace]
+fn fib(n: u32) -> u64 {
+    if n < 2 {
+        return n as u64;
+    }
+    fib(n - 1) + fib(n - 2)
+}
+
+// 本体の ? や return もそのまま動く
+#[trace]
+fn parse_and_double(text: &str) -> Result<i32, std::num::ParseIntError> {
+    let n: i32 = text.trim().parse()?;
+    Ok(n * 2)
+}
+
+/// 属性マクロ #[trace]
+pub fn attribute_macro() {
+    println!("\n=== 属性マクロ #[trace] ===");
+
+    // #[proc_macro_attribute] は (属性の引数, 付けたアイテム) を受け取り、置き換えるコードを返す
+    println!("fib(3):");
+    let value = fib(3);
+    println!("結果: {}", value);
+
+    println!("\nparse_and_double:");
+    let _ = parse_and_double("21");
+    let _ = parse_and_double("twenty");
+
+    println!("\n#[trace] は関数を書き換えて、本体を enter / exit の呼び出しで挟む");
+    println!("#[trace(..)] のように引数を書いたり、self を取るメソッドに付けるとコンパイルエラー");
+}
+
+/// 関数風マクロ hex_color!
+pub fn function_like_macro() {
+    println!("\n=== 関数風マクロ hex_color! ===");
+
+    // 文字列はコンパイル時に読まれ、(u8, u8, u8) の定数に置き換わる
+    const ORANGE: (u8, u8, u8) = hex_color!("#ff8800");
+    let colors = [
+        ("#ff8800", ORANGE),
+        ("#1e90ff", hex_color!("#1e90ff")),
+        ("#FFFFFF", hex_color!("#FFFFFF")),
+    ];
+    for (text, (r, g, b)) in colors {
+        println!("hex_color!(\"{}\") → ({:>3}, {:>3}, {:>3})", text, r, g, b);
+    }
+
+    // hex_color!("#ff88") や hex_color!("orange") はコンパイルエラーになる:
+    //   error: expected a color like "#ff8800"
+    println!("\n書き間違い（hex_color!(\"#ff88\") など）は実行する前にコンパイルエラーになる");
+    println!("文字列リテラルの中身は macro_rules! では調べられない（手続き的マクロの出番）");
+
+    println!("\n手続き的マクロの3つの種類:");
+    for (kind, attribute, example) in [
+        (
+            "derive",
+            "#[proc_macro_derive(Describe)]",
+            "#[derive(Describe)] struct ..",
+        ),
+        ("属性", "#[proc_macro_attribute]", "#[trace] fn .."),
+        ("関数風", "#[proc_macro]", "hex_color!(\"#ff8800\")"),
+    ] {
+        println!(
+            "  {} {} {}",
+            text::pad_right(kind, 8),
+            text::pad_right(attribute, 32),
+            example
+        );
+    }
+    println!("derive は元の定義に impl を足すだけ、属性マクロは付けたアイテムを丸ごと置き換える");
+}
+
+// ----------------------------------------------------------------------------
+// macro_rules! で作る小さな DSL
+// ----------------------------------------------------------------------------
+//
+// 宣言的マクロは自分自身を呼び出せるので、トークンを1つずつ食べながら
+// 組み立てる（tt muncher）と、Rust の文法にない書き方も受け付けられる。
+
+/// トークン木（tt）の数を数える。再帰するたびに先頭の1つを取り除く
+///
+///   count_tts!(a b c) → 1 + count_tts!(b c) → 1 + 1 + count_tts!(c) → 1 + 1 + 1 + 0
+macro_rules! count_tts {
+    () => { 0usize };
+    ($head:tt $($tail:tt)*) => { 1usize + count_tts!($($tail)*) };
+}
+
+/// table! が作る表（列の名前と、キーから値への HashMap）
+#[derive(Debug)]
+pub struct Table<K, V> {
+    pub columns: (&'static str, &'static str),
+    pub rows: HashMap<K, V>,
+}
+
+/// `table! { name: age => "Alice": 30, "Bob": 25 }` で Table を作る
+///
+/// キーは1つのトークン木（$key:tt）。expr にすると後ろに `:` を置けない
+/// （expr の後ろに書けるのは `=>` `,` `;` だけ）ので、tt で受け取る。
+macro_rules! table {
+    ($key_column:ident : $value_column:ident => $($key:tt : $value:expr),* $(,)?) => {{
+        let mut rows = HashMap::with_capacity(count_tts!($($key)*));
+        $(rows.insert($key, $value);)*
+        Table {
+            columns: (stringify!($key_column), stringify!($value_column)),
+            rows,
+        }
+    }};
+}
+
+/// json! が作る値
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Value>),
+    /// 書いた順を保つため、HashMap ではなく Vec に入れる
+    Object(Vec<(String, Value)>),
+}
+
+// From の実装もマクロでまとめて書ける
+macro_rules! impl_from_number {
+    ($($ty:ty)*) => {
+        $(impl From<$ty> for Value {
+            fn from(n: $ty) -> Value {
+                Value::Number(n as f64)
+            }
+        })*
+    };
+}
+
+impl_from_number!(i32 i64 u32 u64 usize f32 f64);
+
+impl From<bool> for Value {
+    fn from(b: bool) -> Value {
+        Value::Bool(b)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(s: &str) -> Value {
+        Value::String(s.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(s: String) -> Value {
+        Value::String(s)
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Null => write!(f, "null"),
+            Value::Bool(b) => write!(f, "{}", b),
+            Value::Number(n) => write!(f, "{}", n),
+            // エスケープは Rust の {:?} に任せる（JSON とは \u{..} の書き方などが少し違う）
+            Value::String(s) => write!(f, "{:?}", s),
+            Value::Array(items) => {
+                write!(f, "[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                write!(f, "]")
+            }
+            Value::Object(entries) => {
+                write!(f, "{{")?;
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{:?}:{}", key, value)?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+/// serde_json::json! を小さくまねたマクロ
+///
+/// 値は `-1` や `a + b` のように複数のトークンになりうるので、`,` が来るまで
+/// トークンを1つずつ [..] にためていく。@ で始まる腕は内部用（呼び出す側は使わない）。
+macro_rules! json {
+    // 配列: @array [できた要素,] [作りかけの要素のトークン] 残りのトークン
+    (@array [$($done:expr,)*] []) => {
+        vec![$($done,)*]
+    };
+    (@array [$($done:expr,)*] [$($current:tt)+]) => {
+        vec![$($done,)* json!($($current)+)]
+    };
+    (@array [$($done:expr,)*] [$($current:tt)+] , $($rest:tt)*) => {
+        json!(@array [$($done,)* json!($($current)+),] [] $($rest)*)
+    };
+    (@array [$($done:expr,)*] [$($current:tt)*] $next:tt $($rest:tt)*) => {
+        json!(@array [$($done,)*] [$($current)* $next] $($rest)*)
+    };
+
+    // オブジェクト: キーと `:` を読んだら、値を @value でためる
+    (@object [$($done:expr,)*]) => {
+        vec![$($done,)*]
+    };
+    (@object [$($done:expr,)*] $key:literal : $($rest:tt)*) => {
+        json!(@value [$($done,)*] $key [] $($rest)*)
+    };
+    (@value [$($done:expr,)*] $key:literal [$($current:tt)+]) => {
+        json!(@object [$($done,)* ($key.to_string(), json!($($current)+)),])
+    };
+    (@value [$($done:expr,)*] $key:literal [$($current:tt)+] , $($rest:tt)*) => {
+        json!(@object [$($done,)* ($key.to_string(), json!($($current)+)),] $($rest)*)
+    };
+    (@value [$($done:expr,)*] $key:literal [$($current:tt)*] $next:tt $($rest:tt)*) => {
+        json!(@value [$($done,)*] $key [$($current)* $next] $($rest)*)
+    };
+
+    // 入口。[..] や {..} も式として読めてしまうので、$other:expr より前に置く
+    (null) => {
+        Value::Null
+    };
+    ([ $($tokens:tt)* ]) => {
+        Value::Array(json!(@array [] [] $($tokens)*))
+    };
+    ({ $($tokens:tt)* }) => {
+        Value::Object(json!(@object [] $($tokens)*))
+    };
+    ($other:expr) => {
+        Value::from($other)
+    };
+}
+
+/// macro_rules! で作る DSL（tt muncher）
+pub fn macro_dsl() {
+    println!("\n=== macro_rules! で作る DSL（tt muncher） ===");
+
+    // table! は HashMap::with_capacity と insert の並びに展開される
+    let ages = table! { name: age => "Alice": 30, "Bob": 25, "Carol": 41 };
+    println!(
+        "count_tts!(\"Alice\" \"Bob\" \"Carol\") = {}",
+        count_tts!("Alice" "Bob" "Carol")
+    );
+    println!("{} | {}", ages.columns.0, ages.columns.1);
+    let mut rows: Vec<_> = ages.rows.iter().collect();
+    rows.sort();
+    for (name, age) in rows {
+        println!("{} | {}", name, age);
+    }
+
+    // json! は入れ子の [..] と {..} を再帰で展開する
+    let retries = 3;
+    let value = json!({
+        "name": "gkRustPractice",
+        "version": [0, 1, 0],
+        "offset": -1,
+        "retries": retries * 2,
+        "tags": ["rust", "macro", null],
+        "owner": { "name": "Ferris", "admin": true }
+    });
+    println!("json! → {}", value);
+    if let Value::Object(entries) = &value {
+        let keys: Vec<&str> = entries.iter().map(|(key, _)| key.as_str()).collect();
+        println!("キーは書いた順: {:?}", keys);
+    }
+
+    // 値の -1 や retries * 2 は複数のトークン。`,` が来るまでためるので式として扱える
+    println!("展開の流れ（配列 [1, -2] の場合）:");
+    println!("  json!(@array [] [] 1 , - 2)");
+    println!("  json!(@array [] [1] , - 2)");
+    println!("  json!(@array [json!(1),] [] - 2)");
+    println!("  json!(@array [json!(1),] [-] 2)");
+    println!("  json!(@array [json!(1),] [- 2])  → vec![json!(1), json!(- 2)]");
+    println!("1回の呼び出しでトークンを1つ食べるので、長い入力は #![recursion_limit] に注意");
+}
+
+/// チートシート用のデータ（`cheatsheet macros` で表示）
+pub const CHEATSHEET: CheatSheet = CheatSheet {
+    syntax: &[
+        (
+            "macro_rules! name { ($x:expr) => { .. }; }",
+            "宣言的マクロを定義する",
+        ),
+        (
+            "$($x:expr),* / $(,)?",
+            "0回以上の繰り返し / 末尾のカンマを許す",
+        ),
+        ("$($x:expr),+ / $(..)?", "1回以上の繰り返し / 0回か1回"),
+        ("$([$($x:expr),*]),*", "入れ子の繰り返し"),
+        (
+            "($name:ident = $v:expr) => { let $name = $v; }",
+            "識別子を受け取って変数を作る",
+        ),
+        ("$crate::Item", "マクロを定義したクレートの要素を指す"),
+        (
+            "#[proc_macro_derive(Name)]",
+            "derive マクロを定義する（proc-macro クレート）",
+        ),
+        (
+            "#[proc_macro_attribute] fn f(attr, item)",
+            "属性マクロを定義する（#[f] fn ..）",
+        ),
+        (
+            "#[proc_macro] fn f(input)",
+            "関数風マクロを定義する（f!(..)）",
+        ),
+        ("[lib] proc-macro = true", "手続き的マクロのクレートにする"),
+        (
+            "extern crate self as name;",
+            "自分のクレートを ::name で参照できるようにする",
+        ),
+        (
+            "(@inner [$($done:expr,)*] $next:tt $($rest:tt)*)",
+            "トークンを1つずつ食べる再帰（tt muncher）",
+        ),
+    ],
+    methods: &[
+        (
+            "parse_macro_input!(input as DeriveInput)",
+            "TokenStream を syn の構文木にする",
+        ),
+        (
+            "quote! { impl #name { .. } }",
+            "変数を埋め込んでコードを組み立てる",
+        ),
+        ("#(#items),*", "quote! の中の繰り返し"),
+        (
+            "generics.split_for_impl()",
+            "impl<..> / 型の <..> / where を取り出す",
+        ),
+        (
+            "syn::Error::new_spanned(x, msg)",
+            "x の位置を指すコンパイルエラーを作る",
+        ),
+        ("stringify!($ty)", "トークンをそのまま文字列にする"),
+    ],
+    gotchas: &[
+        "macro_rules! は定義より後ろでしか使えない（ほかのクレートには #[macro_export]）",
+        "$x を2回書くと引数の式が2回評価される。let で受けてから使う",
+        "マクロの中で作ったローカル変数は呼び出し側から見えない（関数や型の名前は見える）",
+        "手続き的マクロは専用のクレートにしか書けない",
+        "属性マクロは付けたアイテムを置き換える。元の関数も出力に含めないと消える",
+        "derive が生成するコードはトレイトを絶対パス（::crate::Trait）で書く",
+        "TokenStream の to_string はトークンの間に空白を入れる",
+        "宣言的マクロは既にある型の定義を読めない",
+        "$x:expr の後ろに書けるのは => , ; だけ。ほかの区切りを使うなら tt で受ける",
+        "腕は上から順に試される。[..] や {..} は expr にもマッチするので先に書く",
+    ],
+};
+
+/// すべてのデモを実行
+pub fn run_all() {
+    println!("╔════════════════════════════════════════════════════════════════╗");
+    println!("║          Rust マクロサンプル                                     ║");
+    println!("╚════════════════════════════════════════════════════════════════╝");
+
+    lesson_api::run_topic("macros");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[allow(clippy::vec_init_then_push)]
+    fn declarative_macros_expand_repetitions() {
+        assert_eq!(my_vec![7; 2], vec![7, 7]);
+        assert_eq!(my_vec![1, 2, 3,], vec![1, 2, 3]);
+        assert_eq!(max!(3, 9, 4), 9);
+        assert_eq!(max!("pear", "apple"), "pear");
+        assert_eq!(hashmap! { 'a' => 1, 'b' => 2 }[&'b'], 2);
+        assert_eq!(matrix![[1, 2], [3, 4]], vec![vec![1, 2], vec![3, 4]]);
+        assert_eq!(join!(1, 2; sep = "-"), "1-2");
+
+        let mut calls = 0;
+        let mut next = || {
+            calls += 1;
+            calls
+        };
+        assert_eq!(square_twice!(next()), 2);
+        assert_eq!(square_once!(next()), 9);
+    }
+
+    #[test]
+    fn attribute_and_function_like_macros() {
+        let text = lesson_api::output::capture(|| {
+            assert_eq!(fib(2), 1);
+            assert!(parse_and_double("x").is_err());
+        });
+        assert_eq!(
+            text.lines().collect::<Vec<_>>(),
+            [
+                "→ fib(n = 2)",
+                "  → fib(n = 1)",
+                "  ← fib = 1",
+                "  → fib(n = 0)",
+                "  ← fib = 0",
+                "← fib = 1",
+                "→ parse_and_double(text = \"x\")",
+                "← parse_and_double = Err(ParseIntError { kind: InvalidDigit })",
+            ]
+        );
+        assert_eq!(hex_color!("#1E90ff"), (0x1e, 0x90, 0xff));
+    }
+
+    #[test]
+    fn derive_lists_fields_and_types() {
+        assert_eq!(Point::describe(), "struct Point { x: i32, y: i32 }");
+        assert_eq!(
+            Config::fields(),
+            [
+                ("name", "String"),
+                ("tags", "Vec<String>"),
+                ("limits", "HashMap<String, u32>"),
+                ("motto", "Option<&'static str>"),
+            ]
+        );
+        assert_eq!(Labeled::<u8>::fields(), [("0", "&'static str"), ("1", "T")]);
+        assert_eq!(Marker::describe(), "struct Marker");
+        // macro_rules! 版も同じトレイトを実装している
+        assert_eq!(
+            Order::describe(),
+            "struct Order { id: u32, items: Vec<String> }"
+        );
+    }
+
+    #[test]
+    fn json_macro_munches_expressions_and_nesting() {
+        let n = 2;
+        let value = json!({ "a": [1, -n, null,], "b": { "ok": n > 1 }, "c": [] });
+        assert_eq!(
+            value,
+            Value::Object(vec![
+                (
+                    "a".to_string(),
+                    Value::Array(vec![Value::Number(1.0), Value::Number(-2.0), Value::Null])
+                ),
+                (
+                    "b".to_string(),
+                    Value::Object(vec![("ok".to_string(), Value::Bool(true))])
+                ),
+                ("c".to_string(), Value::Array(vec![])),
+            ])
+        );
+        assert_eq!(
+            value.to_string(),
+            r#"{"a":[1,-2,null],"b":{"ok":true},"c":[]}"#
+        );
+
+        let table = table! { id: score => 1: 10, 2: 20 };
+        assert_eq!(table.columns, ("id", "score"));
+        assert_eq!(table.rows[&2], 20);
+    }
+}
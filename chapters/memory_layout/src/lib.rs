@@ -0,0 +1,361 @@
+// ============================================================================
+// Rust メモリレイアウトサンプル - size_of、align_of、repr、ニッチ最適化
+// 公式ドキュメント: https://doc.rust-lang.org/reference/type-layout.html
+// ============================================================================
+//
+// 値がメモリの上で何バイトを占め、どこに置かれるか。
+//   - size_of::<T>()   T の値1つのバイト数（配列の要素の間隔でもある）
+//   - align_of::<T>()  T を置けるアドレスの倍数。サイズは必ずアラインメントの倍数になる
+//   - 既定の repr(Rust) はフィールドを並べ替えてよい。#[repr(C)] は書いた順に C と同じ規則で並べる
+//   - ニッチ: 型が使わないビットパターン（null、bool の 2..=255 など）に、列挙型の判別値を詰める
+//
+// ポインタの大きさは環境で変わる（64ビットなら 8、wasm32 なら 4）ので、数値は実行して確かめる。
+
+use lesson_api::text::pad_right;
+use lesson_api::{println, CheatSheet};
+use std::alloc::Layout;
+use std::fmt::Debug;
+use std::mem::{align_of, offset_of, size_of};
+use std::num::NonZeroU32;
+
+// 型の名前（書いたとおり）とレイアウトの組を並べる
+macro_rules! layouts {
+    ($($t:ty),* $(,)?) => {
+        [$((stringify!($t), Layout::new::<$t>())),*]
+    };
+}
+
+fn print_layouts(rows: &[(&str, Layout)]) {
+    println!("  {} size  align", pad_right("型", 24));
+    for (name, layout) in rows {
+        println!(
+            "  {} {:>4}  {:>5}",
+            pad_right(name, 24),
+            layout.size(),
+            layout.align()
+        );
+    }
+}
+
+// ----------------------------------------------------------------------------
+// size_of と align_of
+// ----------------------------------------------------------------------------
+
+/// size_of と align_of
+pub fn sizes_and_alignment() {
+    println!("\n=== size_of と align_of ===");
+
+    println!("数値と基本の型:");
+    print_layouts(&layouts![
+        u8,
+        u16,
+        u32,
+        u64,
+        u128,
+        usize,
+        f64,
+        bool,
+        char,
+        ()
+    ]);
+
+    // 参照の大きさは、指す先が Sized かどうかで変わる
+    println!("\nポインタと、ヒープを持つ型:");
+    print_layouts(&layouts![
+        &u8,
+        &[u8],
+        &str,
+        &dyn Debug,
+        Box<u8>,
+        Box<[u8]>,
+        String,
+        Vec<u8>,
+    ]);
+    println!(
+        "  &[u8] / &str は（ポインタ, 長さ）、&dyn Debug は（ポインタ, vtable）の太いポインタ"
+    );
+    println!("  String と Vec は（ポインタ, 容量, 長さ）。中身の大きさに関係なく同じ");
+
+    println!("\n配列とタプル:");
+    print_layouts(&layouts![
+        [u8; 3],
+        [u32; 3],
+        (u8, u32),
+        (u8, u8, u32),
+        [(); 100]
+    ]);
+    println!("  [(); 100] のように大きさ 0 の型（ZST）はメモリを使わない");
+    println!(
+        "  サイズはいつもアラインメントの倍数（配列で並べても、次の要素の位置がそろうように）"
+    );
+}
+
+// ----------------------------------------------------------------------------
+// 構造体のレイアウトと #[repr(C)]
+// ----------------------------------------------------------------------------
+
+// 以下の型はレイアウトを見るためだけのもので、フィールドは読まない
+
+/// 既定のレイアウト（repr(Rust)）: コンパイラがフィールドを並べ替えてよい
+#[allow(dead_code)]
+pub struct Mixed {
+    a: u8,
+    b: u32,
+    c: u16,
+}
+
+/// #[repr(C)]: 書いた順に並べ、それぞれのアラインメントまですき間（パディング）を入れる
+#[allow(dead_code)]
+#[repr(C)]
+pub struct MixedC {
+    a: u8,
+    b: u32,
+    c: u16,
+}
+
+/// #[repr(C)] でも、大きい順に書けばすき間は減る
+#[allow(dead_code)]
+#[repr(C)]
+pub struct SortedC {
+    b: u32,
+    c: u16,
+    a: u8,
+}
+
+/// #[repr(C, packed)]: すき間を入れない。アラインメントは 1 になる
+#[allow(dead_code)]
+#[repr(C, packed)]
+pub struct PackedC {
+    a: u8,
+    b: u32,
+    c: u16,
+}
+
+/// #[repr(align(64))]: アラインメントを大きくする（キャッシュラインにそろえる）
+#[allow(dead_code)]
+#[repr(align(64))]
+pub struct CacheAligned(u8);
+
+// フィールドの位置（offset_of!）を並べて、すき間のバイト数を出す
+macro_rules! print_struct {
+    ($t:ty { $($field:ident: $ft:ty),* }) => {{
+        let layout = Layout::new::<$t>();
+        let mut fields = [$((stringify!($field), offset_of!($t, $field), size_of::<$ft>())),*];
+        fields.sort_by_key(|&(_, offset, _)| offset);
+        let used: usize = fields.iter().map(|&(_, _, size)| size).sum();
+        let placed: Vec<String> = fields
+            .iter()
+            .map(|(name, offset, size)| format!("{}@{}..{}", name, offset, offset + size))
+            .collect();
+        println!(
+            "  {} size {:>2}, align {}, すき間 {:>2}: {}",
+            pad_right(stringify!($t), 10),
+            layout.size(),
+            layout.align(),
+            layout.size() - used,
+            placed.join(" ")
+        );
+    }};
+}
+
+/// 構造体のレイアウトと #[repr(C)]
+pub fn struct_layout_and_repr() {
+    println!("\n=== 構造体のレイアウトと #[repr(C)] ===");
+
+    println!("フィールドは a: u8, b: u32, c: u16（合計 7 バイト）");
+    print_struct!(Mixed {
+        a: u8,
+        b: u32,
+        c: u16
+    });
+    print_struct!(MixedC {
+        a: u8,
+        b: u32,
+        c: u16
+    });
+    print_struct!(SortedC {
+        b: u32,
+        c: u16,
+        a: u8
+    });
+    print_struct!(PackedC {
+        a: u8,
+        b: u32,
+        c: u16
+    });
+
+    println!(
+        "\n- repr(Rust) は並べ替えてすき間を減らすが、順序は保証されない（バージョンで変わりうる）"
+    );
+    println!(
+        "- repr(C) は書いた順。b を 4 の倍数に置くため a の後に 3 バイト、末尾にも 2 バイト入る"
+    );
+    println!("- C に渡す構造体や、バイト列として読み書きする構造体には repr(C) が要る");
+    println!(
+        "- packed のフィールドへの参照は作れない（&p.b は error[E0793]、アラインされていないため）"
+    );
+
+    let layout = Layout::new::<CacheAligned>();
+    println!(
+        "\n#[repr(align(64))] struct CacheAligned(u8): size {}, align {}",
+        layout.size(),
+        layout.align()
+    );
+    println!("  別々のスレッドが書く値を別のキャッシュラインに置くと、偽共有（false sharing）を避けられる");
+}
+
+// ----------------------------------------------------------------------------
+// 列挙型とニッチ最適化
+// ----------------------------------------------------------------------------
+
+/// データを持つ列挙型: 一番大きいバリアント + 判別値（ここでは f64 のアラインメントまで広がる）
+#[allow(dead_code)]
+pub enum Shape {
+    Circle(f64),
+    Rect(f64, f64),
+    Empty,
+}
+
+/// どのビットパターンも有効なので、Option にするとそのぶん大きくなる
+#[allow(dead_code)]
+pub struct Meters(pub u32);
+
+// T と Option<T> の大きさを並べる
+macro_rules! option_sizes {
+    ($($t:ty),* $(,)?) => {
+        [$((stringify!($t), size_of::<$t>(), size_of::<Option<$t>>())),*]
+    };
+}
+
+/// 列挙型とニッチ最適化
+pub fn enum_niches() {
+    println!("\n=== 列挙型とニッチ最適化 ===");
+
+    println!("  {} T  Option<T>", pad_right("T", 16));
+    let rows = option_sizes![
+        u8,
+        u32,
+        Meters,
+        bool,
+        char,
+        NonZeroU32,
+        &u8,
+        Box<i32>,
+        Vec<u8>,
+        String,
+        Option<bool>,
+    ];
+    for (name, size, option_size) in rows {
+        let note = if size == option_size {
+            "← 同じ（ニッチに None を入れる）"
+        } else {
+            "← 判別値のぶん大きい"
+        };
+        println!(
+            "  {} {:>2}  {:>9}  {}",
+            pad_right(name, 16),
+            size,
+            option_size,
+            note
+        );
+    }
+
+    println!("\nニッチ（その型が決して使わない値）:");
+    println!("  &T / Box<T> / NonZero*: 0（null）にならない → None を 0 で表す");
+    println!("  bool: 0 と 1 だけ → 2 以降を None や外側の None に使う（Option<Option<bool>> も 1 バイト）");
+    println!("  char: 0x10FFFF より大きい値やサロゲートは使わない");
+    println!("  Vec / String: 中のポインタが null にならない");
+    println!("  u8 / u32 / Meters: すべての値が有効なので、判別値のためにアラインメントぶん広がる");
+
+    // Option<Box<T>> と Option<&T> が null 許容ポインタと同じ表現になることは保証されている（FFI で使える）
+    println!(
+        "\nOption<Box<Shape>> = {} バイト（ポインタ1つぶん）",
+        size_of::<Option<Box<Shape>>>()
+    );
+
+    println!(
+        "Shape（Circle(f64) / Rect(f64, f64) / Empty）: size {}, align {}",
+        size_of::<Shape>(),
+        align_of::<Shape>()
+    );
+    println!("  一番大きい Rect の 16 バイト + 判別値。大きいバリアントは Box に入れると全体が小さくなる");
+    println!(
+        "std::cmp::Ordering: {} バイト、Result<u32, ()>: {} バイト、Result<Box<u8>, ()>: {} バイト",
+        size_of::<std::cmp::Ordering>(),
+        size_of::<Result<u32, ()>>(),
+        size_of::<Result<Box<u8>, ()>>()
+    );
+}
+
+/// チートシート用のデータ（`cheatsheet memory_layout` で表示）
+pub const CHEATSHEET: CheatSheet = CheatSheet {
+    syntax: &[
+        ("#[repr(C)]", "C と同じ規則で、書いた順に並べる"),
+        (
+            "#[repr(C, packed)]",
+            "すき間を入れない（フィールドへの参照は作れない）",
+        ),
+        ("#[repr(align(64))]", "アラインメントを大きくする"),
+        ("#[repr(u8)] enum E { .. }", "判別値の型を決める"),
+    ],
+    methods: &[
+        ("size_of::<T>()", "値1つのバイト数"),
+        ("align_of::<T>()", "置けるアドレスの倍数"),
+        (
+            "size_of_val(&x)",
+            "値から大きさを得る（[T] や dyn Trait にも使える）",
+        ),
+        ("offset_of!(T, field)", "フィールドの先頭からの位置"),
+        (
+            "Layout::new::<T>()",
+            "大きさとアラインメントの組（アロケータで使う）",
+        ),
+    ],
+    gotchas: &[
+        "repr(Rust) のフィールドの順序は保証されない",
+        "サイズはアラインメントの倍数。u8 と u32 の組は 5 ではなく 8 バイト",
+        "Option<T> が T と同じ大きさになるのは、T にニッチがあるときだけ",
+        "ポインタの大きさは環境で変わる（64ビットで 8、wasm32 で 4）",
+    ],
+};
+
+/// すべてのデモを実行
+pub fn run_all() {
+    println!("╔════════════════════════════════════════════════════════════════╗");
+    println!("║          Rust メモリレイアウトサンプル                           ║");
+    println!("╚════════════════════════════════════════════════════════════════╝");
+
+    lesson_api::run_topic("memory_layout");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repr_c_keeps_field_order_and_padding() {
+        assert_eq!(
+            (
+                offset_of!(MixedC, a),
+                offset_of!(MixedC, b),
+                offset_of!(MixedC, c)
+            ),
+            (0, 4, 8)
+        );
+        assert_eq!(size_of::<MixedC>(), 12);
+        assert_eq!(size_of::<SortedC>(), 8);
+        assert_eq!((size_of::<PackedC>(), align_of::<PackedC>()), (7, 1));
+        // 並べ替えられる既定のレイアウトは repr(C) より大きくならない
+        assert!(size_of::<Mixed>() <= size_of::<MixedC>());
+        assert_eq!(align_of::<CacheAligned>(), 64);
+    }
+
+    #[test]
+    fn niches_make_options_free() {
+        assert_eq!(size_of::<Option<Box<u8>>>(), size_of::<usize>());
+        assert_eq!(size_of::<Option<&u8>>(), size_of::<&u8>());
+        assert_eq!(size_of::<Option<NonZeroU32>>(), 4);
+        assert_eq!(size_of::<Option<Option<bool>>>(), 1);
+        assert!(size_of::<Option<Meters>>() > size_of::<Meters>());
+    }
+}
@@ -0,0 +1,320 @@
+// ============================================================================
+// Rust モジュールとパッケージサンプル
+// 公式ドキュメント: https://doc.rust-lang.org/book/ch07-00-managing-growing-projects-with-packages-crates-and-modules.html
+// ============================================================================
+//
+// パッケージ（Cargo.toml）はクレートを持ち、クレートの中はモジュールの木になっている。
+// このファイル（src/lib.rs）がクレートのルートで、木の根は crate という名前になる。
+//
+// - mod              モジュールを作る（中身はこのファイルか、別のファイルに書く）
+// - pub              親の外からも見えるようにする（書かなければ非公開）
+// - pub(crate)       クレートの中だけに公開する
+// - pub(super)       親のモジュールだけに公開する
+// - crate:: / super:: / self::   絶対パスと相対パス
+// - use / pub use    パスを短くする / 別の場所からも使えるように再公開する
+//
+// このリポジトリ自体も例になっている。ワークスペース（ルートの Cargo.toml）の中に、
+// この章を含む章ごとのクレート（chapters/*）がパッケージとして並んでいる。
+
+use lesson_api::{println, CheatSheet};
+
+// ----------------------------------------------------------------------------
+// レストランのモジュールの木（The Book 7.2 の例）
+// ----------------------------------------------------------------------------
+//
+// crate
+// └── restaurant
+//     ├── front_of_house        （非公開）
+//     │   ├── hosting           add_to_waitlist, seat_at_table
+//     │   └── serving           take_order, serve_order, take_payment
+//     ├── back_of_house         Breakfast, Appetizer, fix_incorrect_order
+//     └── hosting               ← pub use で front_of_house::hosting を再公開
+//
+// 本物のプロジェクトでは mod front_of_house; と書いて src/front_of_house.rs
+// （または src/front_of_house/mod.rs）に中身を置くことが多い。ここでは1つのファイルに書く。
+
+pub mod restaurant {
+    // pub が無いので、restaurant の外からは見えない（中の pub な関数も呼べない）
+    mod front_of_house {
+        pub mod hosting {
+            pub fn add_to_waitlist() -> &'static str {
+                "front_of_house::hosting::add_to_waitlist"
+            }
+
+            // 兄弟のモジュールの関数は super:: からたどる
+            pub fn seat_at_table() -> String {
+                format!(
+                    "front_of_house::hosting::seat_at_table → {}",
+                    super::serving::take_order()
+                )
+            }
+        }
+
+        // serving は pub ではないが、兄弟の hosting からは使える（同じ親の中は見える）
+        mod serving {
+            pub fn take_order() -> &'static str {
+                "serving::take_order"
+            }
+
+            #[allow(dead_code)]
+            fn serve_order() {}
+
+            #[allow(dead_code)]
+            fn take_payment() {}
+        }
+    }
+
+    pub mod back_of_house {
+        /// 構造体は pub でも、フィールドは1つずつ pub を付けないと公開されない
+        #[derive(Debug)]
+        pub struct Breakfast {
+            pub toast: String,
+            seasonal_fruit: String,
+        }
+
+        impl Breakfast {
+            // seasonal_fruit が非公開なので、外では Breakfast { .. } と書いて作れない
+            // 作るための関連関数を用意する
+            pub fn summer(toast: &str) -> Breakfast {
+                Breakfast {
+                    toast: String::from(toast),
+                    seasonal_fruit: String::from("peaches"),
+                }
+            }
+
+            pub fn fruit(&self) -> &str {
+                &self.seasonal_fruit
+            }
+        }
+
+        /// 列挙型は pub にするとバリアントがすべて公開される
+        #[derive(Debug)]
+        pub enum Appetizer {
+            Soup,
+            Salad,
+        }
+
+        pub fn fix_incorrect_order() -> String {
+            cook_order();
+            // super:: は親（restaurant）。そこにある非公開の deliver_order も子からは呼べる
+            format!(
+                "back_of_house::fix_incorrect_order → super::{}",
+                super::deliver_order()
+            )
+        }
+
+        // 非公開の関数は、同じモジュールとその子からだけ呼べる
+        fn cook_order() {}
+
+        /// クレートの中からは呼べるが、クレートの外（ランナーなど）からは見えない
+        pub(crate) fn secret_recipe() -> &'static str {
+            "pub(crate) back_of_house::secret_recipe"
+        }
+
+        /// 親（restaurant）の中だけに公開する
+        pub(super) fn staff_meal() -> &'static str {
+            "pub(super) back_of_house::staff_meal"
+        }
+    }
+
+    fn deliver_order() -> &'static str {
+        "deliver_order"
+    }
+
+    // 外からは restaurant::hosting で使えるようにする（front_of_house は隠したまま）
+    pub use self::front_of_house::hosting;
+
+    /// 店の中の動き（restaurant の中なので非公開のモジュールも使える）
+    pub fn eat_at_restaurant() -> Vec<String> {
+        vec![
+            // 絶対パス: クレートのルート（crate）から
+            crate::restaurant::front_of_house::hosting::add_to_waitlist().to_string(),
+            // 相対パス: 今いるモジュール（restaurant）から
+            front_of_house::hosting::seat_at_table(),
+            // pub(super) は親である restaurant から呼べる
+            back_of_house::staff_meal().to_string(),
+        ]
+    }
+}
+
+// ----------------------------------------------------------------------------
+// デモ
+// ----------------------------------------------------------------------------
+
+/// モジュールの木と、絶対パス・相対パス
+pub fn module_tree_and_paths() {
+    println!("\n=== モジュールの木と、絶対パス・相対パス ===");
+
+    println!("crate");
+    println!("└── restaurant");
+    println!("    ├── front_of_house（非公開）");
+    println!("    │   ├── hosting");
+    println!("    │   └── serving（非公開）");
+    println!("    ├── back_of_house");
+    println!("    └── hosting（pub use で再公開）");
+
+    println!("\nrestaurant::eat_at_restaurant() の中で呼んだ関数:");
+    for call in restaurant::eat_at_restaurant() {
+        println!("  {}", call);
+    }
+    println!("  {}", restaurant::back_of_house::fix_incorrect_order());
+
+    // パスの書き始め:
+    //   crate::   クレートのルートから（ファイルを移しても呼び出し側を直さなくてよいことが多い）
+    //   super::   親のモジュールから（ファイルシステムの ..）
+    //   self::    今のモジュールから
+    //   名前       今のモジュールの中の名前か、外部クレート（std、lesson_api など）
+    println!("\n絶対パス: crate::restaurant::... / 相対パス: front_of_house::... や super::...");
+    println!(
+        "外部クレートも名前から書ける: std::cmp::max(3, 7) = {}",
+        std::cmp::max(3, 7)
+    );
+}
+
+/// pub と可視性
+pub fn visibility_rules() {
+    println!("\n=== pub と可視性 ===");
+
+    // 構造体: pub なフィールドだけ触れる
+    let mut meal = restaurant::back_of_house::Breakfast::summer("Rye");
+    meal.toast = String::from("Wheat");
+    println!("toast を変更: {}", meal.toast);
+    // meal.seasonal_fruit = ... は E0616（非公開のフィールド）。メソッドを通して読む
+    println!("季節の果物（メソッド経由）: {}", meal.fruit());
+
+    // 列挙型: バリアントはすべて公開される
+    let order = [
+        restaurant::back_of_house::Appetizer::Soup,
+        restaurant::back_of_house::Appetizer::Salad,
+    ];
+    println!("前菜（バリアントは全部 pub）: {:?}", order);
+
+    // pub(crate) はクレートの中ならどこからでも呼べる
+    println!("{}", restaurant::back_of_house::secret_recipe());
+
+    // 以下はどれもコンパイルエラー（E0603: ... is private）
+    //   restaurant::front_of_house::hosting::add_to_waitlist()   front_of_house が非公開
+    //   restaurant::back_of_house::staff_meal()                  pub(super) は restaurant の中だけ
+    println!("非公開の要素は、親から子の中は見えないが、子から親（祖先）の中は見える");
+    println!("pub(crate) / pub(super) / pub(in crate::restaurant) で公開する範囲を絞れる");
+}
+
+/// use と pub use（再公開）
+pub fn use_and_reexports() {
+    println!("\n=== use と pub use（再公開） ===");
+
+    // use はスコープにショートカットを作る（この関数の中だけ有効）
+    // 関数は親のモジュールまでを use して、呼ぶときに親の名前を付けるのが慣習
+    use restaurant::hosting;
+    println!("use restaurant::hosting; → {}", hosting::add_to_waitlist());
+
+    // 構造体や列挙型はフルパスで use する
+    use restaurant::back_of_house::Breakfast;
+    println!("use ...::Breakfast; → {:?}", Breakfast::summer("Sourdough"));
+
+    // 同じ名前がぶつかるときは親の名前で区別するか、as で別名を付ける
+    use std::fmt::Result;
+    use std::io::Result as IoResult;
+    let formatted: Result = Ok(());
+    let io: IoResult<()> = Ok(());
+    println!(
+        "std::fmt::Result と std::io::Result as IoResult: {:?} / {:?}",
+        formatted, io
+    );
+
+    // 同じ親を持つパスはまとめて書ける
+    use std::collections::{BTreeMap, HashSet};
+    let keys: HashSet<&str> = ["a", "b"].into_iter().collect();
+    let sorted: BTreeMap<&str, usize> = keys.iter().map(|k| (*k, k.len())).collect();
+    println!(
+        "use std::collections::{{BTreeMap, HashSet}}; → {:?}",
+        sorted
+    );
+
+    // pub use は、中の構造を隠したまま使いやすいパスで公開する（API の入り口を整える）
+    //   restaurant::front_of_house::hosting は非公開だが、restaurant::hosting なら使える
+    println!("pub use self::front_of_house::hosting; で、外から restaurant::hosting として使える");
+    println!(
+        "lessons クレートも pub use {{ basics, ownership, .. }} で章のクレートを再公開している"
+    );
+}
+
+/// チートシート用のデータ（`cheatsheet modules_packages` で表示）
+pub const CHEATSHEET: CheatSheet = CheatSheet {
+    syntax: &[
+        (
+            "mod front_of_house { .. }",
+            "モジュールを作る（既定で非公開）",
+        ),
+        (
+            "mod garden;",
+            "中身を src/garden.rs（または garden/mod.rs）から読む",
+        ),
+        ("pub(crate) fn f()", "クレートの中だけに公開する"),
+        ("pub(super) fn f()", "親のモジュールだけに公開する"),
+        (
+            "crate::a::b / super::c / self::d",
+            "絶対パス / 親から / 今のモジュールから",
+        ),
+        ("use std::io::Result as IoResult;", "別名を付けて持ち込む"),
+        (
+            "use std::collections::{HashMap, HashSet};",
+            "同じ親のパスをまとめる",
+        ),
+        (
+            "pub use self::inner::api;",
+            "再公開（外からは短いパスで使える）",
+        ),
+    ],
+    methods: &[
+        (
+            "cargo new --lib name",
+            "ライブラリクレートのパッケージを作る",
+        ),
+        (
+            "[workspace] members = [..]",
+            "複数のパッケージを1つのワークスペースにまとめる",
+        ),
+        (
+            "cargo doc --open",
+            "pub な API のドキュメントを見る（再公開も反映される）",
+        ),
+    ],
+    gotchas: &[
+        "pub struct でもフィールドは非公開のまま。フィールドごとに pub を付ける",
+        "pub enum のバリアントはすべて公開される",
+        "親は子の非公開の要素を見られないが、子は祖先の非公開の要素を見られる",
+        "use はその use があるスコープ（モジュールや関数）の中だけで有効",
+    ],
+};
+
+/// すべてのデモを実行
+pub fn run_all() {
+    println!("╔════════════════════════════════════════════════════════════════╗");
+    println!("║          Rust モジュールとパッケージサンプル                     ║");
+    println!("╚════════════════════════════════════════════════════════════════╝");
+
+    lesson_api::run_topic("modules_packages");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::restaurant;
+
+    #[test]
+    fn absolute_relative_and_reexported_paths_reach_the_same_items() {
+        assert_eq!(
+            restaurant::hosting::add_to_waitlist(),
+            "front_of_house::hosting::add_to_waitlist"
+        );
+        let calls = restaurant::eat_at_restaurant();
+        assert_eq!(calls[0], restaurant::hosting::add_to_waitlist());
+        assert!(calls[1].ends_with("serving::take_order"));
+        assert!(restaurant::back_of_house::fix_incorrect_order().ends_with("deliver_order"));
+        // tests モジュールもクレートの中なので pub(crate) が見える
+        assert!(restaurant::back_of_house::secret_recipe().starts_with("pub(crate)"));
+
+        let meal = restaurant::back_of_house::Breakfast::summer("Rye");
+        assert_eq!((meal.toast.as_str(), meal.fruit()), ("Rye", "peaches"));
+    }
+}
@@ -0,0 +1,338 @@
+// ============================================================================
+// Rust ネットワークサンプル - TcpListener と TcpStream のエコーサーバー
+// 公式ドキュメント: https://doc.rust-lang.org/std/net/index.html
+// ============================================================================
+//
+// std::net の TCP は2つの型でできている。
+//   - TcpListener  ポートで待ち受けて、接続ごとに TcpStream を返す（サーバー側）
+//   - TcpStream    Read と Write を実装した双方向のバイト列（クライアント側もサーバー側も同じ型）
+//
+// この章ではエコーサーバーをバックグラウンドのスレッドで動かし、同じプロセスのクライアントから
+// 接続して1行ずつ送り、返ってきた行を受け取る。ファイルの I/O（BufReader、read_line）と
+// 並行処理（thread::spawn、JoinHandle）の組み合わせそのものになっている。
+//
+// ポートに 0 を指定すると OS が空いているポートを選ぶので、実行するたびに番号が変わる。
+// スレッドの中で println! しても表示されないので、サーバーのログは join の戻り値で受け取る。
+//
+// wasm32-unknown-unknown（ブラウザ版）にはソケットもスレッドも無いので、説明だけを表示する。
+
+use lesson_api::{println, CheatSheet};
+use std::io::{self, BufRead, BufReader, ErrorKind, Read, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+// wasm32-unknown-unknown（ブラウザ版）ではソケットを開けず、スレッドも起動できない
+fn network_unavailable() -> bool {
+    let unavailable = cfg!(target_arch = "wasm32");
+    if unavailable {
+        println!("ブラウザ版ではソケットを開けません。cargo run で試してください");
+    }
+    unavailable
+}
+
+// ----------------------------------------------------------------------------
+// エコーサーバーとクライアント
+// ----------------------------------------------------------------------------
+
+/// 1つの接続を処理する。受け取った行に "echo: " を付けて送り返し、受け取った行を返す
+pub fn handle_client(stream: TcpStream) -> io::Result<Vec<String>> {
+    let peer = stream.peer_addr()?;
+    // 読む側は BufReader で包み、書く側は try_clone した同じソケットを使う
+    let mut writer = stream.try_clone()?;
+    let reader = BufReader::new(stream);
+
+    let mut received = Vec::new();
+    // lines() はクライアントが書き込みを閉じる（EOF）と終わる
+    for line in reader.lines() {
+        let line = line?;
+        writeln!(writer, "echo: {}", line)?;
+        received.push(format!("{} から {:?}", peer, line));
+    }
+    Ok(received)
+}
+
+/// エコーサーバーをバックグラウンドのスレッドで起動する
+///
+/// `clients` 個の接続を1つずつ順番に処理したら終わり、受け取った行をすべて返す。
+pub fn spawn_echo_server(
+    clients: usize,
+) -> io::Result<(SocketAddr, JoinHandle<io::Result<Vec<String>>>)> {
+    // ポート 0 は「空いているポートを OS が選ぶ」。実際の番号は local_addr で分かる
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let addr = listener.local_addr()?;
+
+    let handle = thread::spawn(move || {
+        let mut log = Vec::new();
+        // incoming() は accept() を繰り返すイテレータ。take で接続の数を区切る
+        for stream in listener.incoming().take(clients) {
+            log.extend(handle_client(stream?)?);
+        }
+        Ok(log)
+    });
+    Ok((addr, handle))
+}
+
+/// サーバーに接続して1行ずつ送り、返ってきた行を集める
+pub fn echo_round_trip(addr: SocketAddr, messages: &[&str]) -> io::Result<Vec<String>> {
+    let mut stream = TcpStream::connect(addr)?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+
+    let mut replies = Vec::new();
+    for message in messages {
+        // 行の終わりの '\n' がメッセージの区切り（TCP 自体には区切りが無い）
+        writeln!(stream, "{}", message)?;
+        let mut reply = String::new();
+        if reader.read_line(&mut reply)? == 0 {
+            return Err(io::Error::new(
+                ErrorKind::UnexpectedEof,
+                "サーバーが接続を閉じました",
+            ));
+        }
+        replies.push(reply.trim_end().to_string());
+    }
+    // 書き込みを閉じると、サーバーの lines() が EOF を受け取って終わる
+    stream.shutdown(Shutdown::Write)?;
+    Ok(replies)
+}
+
+/// エコーサーバーとクライアントで1往復する
+pub fn echo_server_and_client() {
+    println!("\n=== TCP のエコーサーバーとクライアント ===");
+    if network_unavailable() {
+        return;
+    }
+
+    let run = || -> io::Result<()> {
+        let (addr, server) = spawn_echo_server(1)?;
+        println!("サーバーが {} で待ち受けています", addr);
+
+        let replies = echo_round_trip(addr, &["hello", "こんにちは", "Rust"])?;
+        for reply in &replies {
+            println!("  クライアントが受信: {}", reply);
+        }
+
+        // サーバーのスレッドの戻り値（受け取った行のログ）を受け取る
+        let log = server
+            .join()
+            .expect("サーバーのスレッドが panic しました")?;
+        println!("サーバーのログ:");
+        for entry in &log {
+            println!("  {}", entry);
+        }
+        Ok(())
+    };
+    if let Err(e) = run() {
+        println!("通信に失敗しました: {}", e);
+    }
+}
+
+// ----------------------------------------------------------------------------
+// 複数のクライアントを同時に処理する
+// ----------------------------------------------------------------------------
+
+/// 接続ごとにスレッドを起動するエコーサーバー。`clients` 個の接続を処理したら終わる
+pub fn spawn_threaded_echo_server(
+    clients: usize,
+) -> io::Result<(SocketAddr, JoinHandle<io::Result<Vec<String>>>)> {
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let addr = listener.local_addr()?;
+
+    let handle = thread::spawn(move || {
+        let mut workers = Vec::new();
+        for stream in listener.incoming().take(clients) {
+            let stream = stream?;
+            // 1つの接続の処理が長引いても、次の accept は待たされない
+            workers.push(thread::spawn(move || handle_client(stream)));
+        }
+        let mut log = Vec::new();
+        for worker in workers {
+            log.extend(worker.join().expect("接続のスレッドが panic しました")?);
+        }
+        Ok(log)
+    });
+    Ok((addr, handle))
+}
+
+/// 複数のクライアントを同時に処理する
+pub fn concurrent_clients() {
+    println!("\n=== 複数のクライアントを同時に処理する ===");
+    if network_unavailable() {
+        return;
+    }
+
+    let run = || -> io::Result<()> {
+        let (addr, server) = spawn_threaded_echo_server(3)?;
+        println!("サーバー: {}（接続ごとにスレッドを起動）", addr);
+
+        // クライアントもそれぞれ別のスレッドから接続する
+        let clients: Vec<_> = (1..=3)
+            .map(|id| {
+                thread::spawn(move || {
+                    let first = format!("client{} の1行目", id);
+                    let second = format!("client{} の2行目", id);
+                    echo_round_trip(addr, &[&first, &second]).map(|replies| (id, replies))
+                })
+            })
+            .collect();
+
+        // 受信の順番はスレッドの都合で変わるが、join する順番は起動した順
+        for client in clients {
+            let (id, replies) = client
+                .join()
+                .expect("クライアントのスレッドが panic しました")?;
+            println!("  client{}: {:?}", id, replies);
+        }
+        let log = server
+            .join()
+            .expect("サーバーのスレッドが panic しました")?;
+        println!("サーバーが受け取った行の数: {}", log.len());
+        Ok(())
+    };
+    if let Err(e) = run() {
+        println!("通信に失敗しました: {}", e);
+    }
+}
+
+// ----------------------------------------------------------------------------
+// タイムアウトとエラー
+// ----------------------------------------------------------------------------
+
+/// 接続の失敗、読み込みのタイムアウト、shutdown による EOF
+pub fn timeouts_and_errors() {
+    println!("\n=== タイムアウトとエラー ===");
+    if network_unavailable() {
+        return;
+    }
+
+    // 1. 誰も待ち受けていないポートへの接続は ConnectionRefused
+    //    （いったん bind して番号だけもらい、すぐに閉じる）
+    let closed = TcpListener::bind("127.0.0.1:0").and_then(|listener| listener.local_addr());
+    if let Ok(addr) = closed {
+        match TcpStream::connect_timeout(&addr, Duration::from_secs(1)) {
+            Ok(_) => println!("1. {} に接続できてしまいました", addr),
+            Err(e) => println!("1. 閉じたポートへの接続: {:?}", e.kind()),
+        }
+    }
+
+    // 2. 何も送ってこない相手から読むと、既定では永遠に待つ。set_read_timeout で上限を付ける
+    let silent = || -> io::Result<()> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let mut client = TcpStream::connect(listener.local_addr()?)?;
+        let (_server_side, _) = listener.accept()?; // 接続は受け付けるが何も送らない
+        client.set_read_timeout(Some(Duration::from_millis(50)))?;
+        let mut buf = [0u8; 16];
+        match client.read(&mut buf) {
+            Ok(n) => println!("2. {} バイト読めてしまいました", n),
+            // Unix では WouldBlock、Windows では TimedOut になる
+            Err(e) => println!("2. 50ms のタイムアウト: {:?}", e.kind()),
+        }
+        Ok(())
+    };
+    if let Err(e) = silent() {
+        println!("2. 準備に失敗しました: {}", e);
+    }
+
+    // 3. 相手が書き込みを閉じると read は 0 を返す（EOF）。read_to_string はそこで終わる
+    let eof = || -> io::Result<()> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let mut client = TcpStream::connect(listener.local_addr()?)?;
+        let (mut server_side, _) = listener.accept()?;
+        server_side.write_all(b"bye")?;
+        server_side.shutdown(Shutdown::Write)?;
+        let mut text = String::new();
+        client.read_to_string(&mut text)?;
+        println!("3. shutdown の後に read_to_string: {:?}", text);
+        Ok(())
+    };
+    if let Err(e) = eof() {
+        println!("3. 通信に失敗しました: {}", e);
+    }
+}
+
+pub const CHEATSHEET: CheatSheet = CheatSheet {
+    syntax: &[
+        (
+            "let listener = TcpListener::bind(\"127.0.0.1:0\")?;",
+            "待ち受ける（ポート 0 は OS が選ぶ）",
+        ),
+        (
+            "for stream in listener.incoming() { ... }",
+            "接続を1つずつ受け付ける",
+        ),
+        (
+            "let mut stream = TcpStream::connect(addr)?;",
+            "サーバーに接続する",
+        ),
+        (
+            "let reader = BufReader::new(stream.try_clone()?);",
+            "同じソケットを読む側と書く側に分ける",
+        ),
+    ],
+    methods: &[
+        ("listener.local_addr()?", "実際に待ち受けているアドレス"),
+        (
+            "writeln!(stream, \"...\")?",
+            "1行送る（TcpStream は Write）",
+        ),
+        (
+            "reader.read_line(&mut line)?",
+            "1行受け取る（0 なら相手が閉じた）",
+        ),
+        (
+            "stream.shutdown(Shutdown::Write)?",
+            "書き込みを閉じて相手に EOF を送る",
+        ),
+        (
+            "stream.set_read_timeout(Some(d))?",
+            "read の待ち時間に上限を付ける",
+        ),
+        (
+            "TcpStream::connect_timeout(&addr, d)",
+            "接続の待ち時間に上限を付ける",
+        ),
+    ],
+    gotchas: &[
+        "TCP はバイトの流れで、メッセージの区切りは無い。改行や長さで自分で区切る",
+        "accept や read は既定でブロックする。1接続ずつ処理すると他の接続が待たされる",
+        "相手が書き込みを閉じないと lines() や read_to_string は終わらない",
+        "タイムアウトのエラーは OS によって WouldBlock か TimedOut",
+    ],
+};
+
+/// すべてのデモを実行
+pub fn run_all() {
+    println!("╔════════════════════════════════════════════════════════════════╗");
+    println!("║          Rust ネットワークサンプル                               ║");
+    println!("╚════════════════════════════════════════════════════════════════╝");
+
+    lesson_api::run_topic("networking");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn echoes_lines_back_to_the_client() {
+        let (addr, server) = spawn_echo_server(1).unwrap();
+        let replies = echo_round_trip(addr, &["ping", "ポン"]).unwrap();
+        assert_eq!(replies, ["echo: ping", "echo: ポン"]);
+
+        let log = server.join().unwrap().unwrap();
+        assert_eq!(log.len(), 2);
+        assert!(log[1].ends_with("\"ポン\""));
+    }
+
+    #[test]
+    fn threaded_server_handles_every_client() {
+        let (addr, server) = spawn_threaded_echo_server(4).unwrap();
+        let clients: Vec<_> = (0..4)
+            .map(|i| thread::spawn(move || echo_round_trip(addr, &[&i.to_string()]).unwrap()))
+            .collect();
+        for (i, client) in clients.into_iter().enumerate() {
+            assert_eq!(client.join().unwrap(), [format!("echo: {}", i)]);
+        }
+        assert_eq!(server.join().unwrap().unwrap().len(), 4);
+    }
+}
@@ -0,0 +1,208 @@
+// ============================================================================
+// #![no_std] のライブラリ（マイコンに載せる想定の部分）
+// 公式ドキュメント: https://docs.rust-embedded.org/book/intro/no-std.html
+// ============================================================================
+//
+// std を使わず、core（と feature で alloc）だけで書いたクレート。
+// PC では chapters/no_std_demo から普通のライブラリとして呼び出し、
+// OS の無いマイコン向けには次のようにビルドできる:
+//
+//   rustup target add thumbv7em-none-eabihf
+//   cargo build -p firmware --target thumbv7em-none-eabihf --no-default-features
+//
+// features:
+//   alloc（既定で有効）: Vec / String / BTreeMap を使う heap モジュール
+
+#![no_std]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+use core::fmt;
+
+/// 固定長の配列に書き込む fmt::Write
+/// ヒープを使わずに write! で文字列を組み立てられる（println! の代わりに UART へ送る前など）
+pub struct FixedBuf<const N: usize> {
+    bytes: [u8; N],
+    len: usize,
+}
+
+impl<const N: usize> FixedBuf<N> {
+    pub const fn new() -> Self {
+        FixedBuf {
+            bytes: [0; N],
+            len: 0,
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        // write_str は &str を丸ごと書くので、途中で UTF-8 が切れることはない
+        core::str::from_utf8(&self.bytes[..self.len]).unwrap_or("")
+    }
+
+    pub fn remaining(&self) -> usize {
+        N - self.len
+    }
+
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+}
+
+impl<const N: usize> Default for FixedBuf<N> {
+    fn default() -> Self {
+        FixedBuf::new()
+    }
+}
+
+impl<const N: usize> fmt::Write for FixedBuf<N> {
+    /// 入りきらないときは何も書かずに fmt::Error を返す
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        if end > N {
+            return Err(fmt::Error);
+        }
+        self.bytes[self.len..end].copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+/// センサーの1行（"temp=23" のような "名前=値"）
+/// 名前は元の文字列を借用するので、コピーもヒープも要らない
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Reading<'a> {
+    pub name: &'a str,
+    pub value: i32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseError {
+    MissingEquals,
+    EmptyName,
+    InvalidValue,
+}
+
+// std::error::Error は std にしか無い（Rust 1.81 からは core::error::Error もある）
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::MissingEquals => write!(f, "'=' がありません"),
+            ParseError::EmptyName => write!(f, "名前が空です"),
+            ParseError::InvalidValue => write!(f, "値が整数ではありません"),
+        }
+    }
+}
+
+/// "名前=値" を解析する（str のメソッドも parse も core にある）
+pub fn parse_reading(line: &str) -> Result<Reading<'_>, ParseError> {
+    let (name, value) = line
+        .trim()
+        .split_once('=')
+        .ok_or(ParseError::MissingEquals)?;
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(ParseError::EmptyName);
+    }
+    let value = value.trim().parse().map_err(|_| ParseError::InvalidValue)?;
+    Ok(Reading { name, value })
+}
+
+/// CRC-8（多項式 0x07、初期値 0）。通信データの検査に使う
+pub fn crc8(data: &[u8]) -> u8 {
+    data.iter().fold(0u8, |crc, &byte| {
+        (0..8).fold(crc ^ byte, |crc, _| {
+            if crc & 0x80 != 0 {
+                (crc << 1) ^ 0x07
+            } else {
+                crc << 1
+            }
+        })
+    })
+}
+
+/// アロケータがある環境でだけ使える部分（alloc クレート）
+#[cfg(feature = "alloc")]
+pub mod heap {
+    use super::{parse_reading, ParseError};
+    use alloc::collections::BTreeMap;
+    use alloc::string::String;
+    use alloc::vec::Vec;
+    use core::fmt::Write;
+
+    /// "temp=23; hum=40; temp=25" を名前ごとに集める
+    /// HashMap は乱数の種に OS を使うので std にしか無い。alloc には BTreeMap がある
+    pub fn collect_readings(text: &str) -> Result<BTreeMap<&str, Vec<i32>>, ParseError> {
+        let mut readings: BTreeMap<&str, Vec<i32>> = BTreeMap::new();
+        for line in text.split(';').filter(|line| !line.trim().is_empty()) {
+            let reading = parse_reading(line)?;
+            readings
+                .entry(reading.name)
+                .or_default()
+                .push(reading.value);
+        }
+        Ok(readings)
+    }
+
+    /// 名前ごとの平均を1行にまとめる（format! は alloc::format!）
+    pub fn summary(readings: &BTreeMap<&str, Vec<i32>>) -> String {
+        let mut text = String::new();
+        for (name, values) in readings {
+            let sum: i32 = values.iter().sum();
+            let _ = write!(text, "{}={} ", name, sum / values.len() as i32);
+        }
+        text.truncate(text.trim_end().len());
+        text
+    }
+}
+
+// OS の無いターゲット向けのパニックハンドラ。std があるときは std のものと
+// 重複してしまうので、target_os = "none"（thumbv7em-none-eabihf など）でだけ定義する
+#[cfg(target_os = "none")]
+#[panic_handler]
+fn panic(_info: &core::panic::PanicInfo) -> ! {
+    // 実機ならここで LED を点けたり、ウォッチドッグによるリセットを待ったりする
+    loop {
+        core::hint::spin_loop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::fmt::Write;
+
+    #[test]
+    fn fixed_buf_rejects_overflow() {
+        let mut buf = FixedBuf::<8>::new();
+        write!(buf, "{}+{}", 12, 34).unwrap();
+        assert_eq!(buf.as_str(), "12+34");
+        assert!(write!(buf, "=46!").is_err());
+        assert_eq!(buf.as_str(), "12+34");
+        assert_eq!(buf.remaining(), 3);
+    }
+
+    #[test]
+    fn parses_readings_and_checksums() {
+        assert_eq!(
+            parse_reading(" temp = -3 "),
+            Ok(Reading {
+                name: "temp",
+                value: -3
+            })
+        );
+        assert_eq!(parse_reading("temp"), Err(ParseError::MissingEquals));
+        assert_eq!(parse_reading("=1"), Err(ParseError::EmptyName));
+        assert_eq!(parse_reading("temp=hot"), Err(ParseError::InvalidValue));
+        // CRC-8/SMBUS の検査値
+        assert_eq!(crc8(b"123456789"), 0xF4);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn collects_readings_by_name() {
+        let readings = heap::collect_readings("temp=20; hum=40; temp=24;").unwrap();
+        assert_eq!(readings["temp"], [20, 24]);
+        assert_eq!(heap::summary(&readings), "hum=40 temp=22");
+    }
+}
@@ -0,0 +1,252 @@
+// ============================================================================
+// Rust no_std サンプル - core / alloc / std、パニックハンドラ
+// 公式ドキュメント: https://docs.rust-embedded.org/book/intro/no-std.html
+// ============================================================================
+//
+// 組み込み機器（OS の無いマイコン）では std が使えない。
+// 標準ライブラリは3層になっていて、どこまで使えるかは環境で決まる:
+//
+//   core  … どこでも使える（Option、Result、イテレータ、スライス、fmt）
+//   alloc … ヒープがあれば使える（Box、Vec、String、Rc、BTreeMap）
+//   std   … OS があれば使える（ファイル、スレッド、時刻、HashMap、println!）
+//
+// #![no_std] で書いた部分は同じディレクトリの firmware クレートにある。
+// この章はそれを PC から呼び出して、動きを確かめる。
+
+use lesson_api::{println, CheatSheet};
+use std::fmt::Write;
+
+use firmware::{crc8, heap, parse_reading, FixedBuf};
+
+/// core / alloc / std の3層
+pub fn core_alloc_std() {
+    println!("\n=== core / alloc / std の3層 ===");
+
+    // #![no_std] を書くと、プレリュードが std ではなく core のものになる
+    //   #![no_std]
+    //   extern crate alloc;          // ヒープを使うなら明示的に読み込む
+    //   use alloc::vec::Vec;
+    let layers = [
+        (
+            "core",
+            "Option, Result, Iterator, &str, スライス, fmt::Write, Cell",
+        ),
+        ("alloc", "Box, Vec, String, Rc, Arc, BTreeMap, format!"),
+        ("std", "File, thread, Instant, HashMap, Mutex, println!"),
+    ];
+    for (layer, items) in layers {
+        println!("  {:<6} {}", layer, items);
+    }
+
+    // std の型の多くは core / alloc の再エクスポート
+    println!("std::vec::Vec と alloc::vec::Vec は同じ型です");
+    println!(
+        "std::option::Option のサイズ: {} バイト（core::option::Option と同じ）",
+        std::mem::size_of::<core::option::Option<u32>>()
+    );
+}
+
+/// ヒープを使わない書式化
+pub fn formatting_without_heap() {
+    println!("\n=== ヒープを使わない書式化（fmt::Write） ===");
+
+    // println! も format! も使えないときは、固定長のバッファに write! する
+    let mut buf = FixedBuf::<32>::new();
+    let temperature = 23;
+    write!(buf, "temp={}C crc={:#04x}", temperature, crc8(b"temp=23")).unwrap();
+    println!(
+        "書式化した結果: {:?}（残り {} バイト）",
+        buf.as_str(),
+        buf.remaining()
+    );
+
+    // 入りきらないときは fmt::Error が返る（パニックしない）
+    buf.clear();
+    let message = "とても長いメッセージはバッファに入りきりません";
+    let result = write!(buf, "{}", message);
+    println!(
+        "入りきらない書き込み: {:?}、中身: {:?}",
+        result,
+        buf.as_str()
+    );
+}
+
+/// core だけで解析と検査
+pub fn parsing_and_checksum() {
+    println!("\n=== core だけで解析と検査 ===");
+
+    // 名前は元の文字列を借用するので、String を作らずに済む
+    for line in ["temp=23", "hum = 41", "pressure", "=5", "temp=hot"] {
+        match parse_reading(line) {
+            Ok(reading) => println!("  {:<10} → {} = {}", line, reading.name, reading.value),
+            Err(e) => println!("  {:<10} → エラー: {}", line, e),
+        }
+    }
+
+    // 受信したデータの末尾に付いた CRC で、壊れていないか確かめる
+    let packet = b"temp=23";
+    let checksum = crc8(packet);
+    println!("\"temp=23\" の CRC-8: {:#04x}", checksum);
+    let broken = b"temp=28";
+    println!(
+        "1文字壊れたデータの CRC-8: {:#04x}（一致しない → 再送を頼む）",
+        crc8(broken)
+    );
+}
+
+/// alloc のコレクション
+pub fn alloc_collections() {
+    println!("\n=== alloc のコレクション ===");
+
+    // firmware の heap モジュールは feature = "alloc" のときだけある
+    // マイコンでは #[global_allocator] でヒープの領域を用意する必要がある
+    let text = "temp=20; hum=40; temp=24; hum=44; temp=22";
+    match heap::collect_readings(text) {
+        Ok(readings) => {
+            for (name, values) in &readings {
+                println!("  {:<5} {:?}", name, values);
+            }
+            println!("平均: {}", heap::summary(&readings));
+        }
+        Err(e) => println!("解析できませんでした: {}", e),
+    }
+    println!("HashMap は OS の乱数を使うので std にしか無い。alloc では BTreeMap を使う");
+}
+
+/// パニックハンドラ
+pub fn panic_handler_demo() {
+    println!("\n=== パニックハンドラ ===");
+
+    // std はパニックしたらメッセージを表示してスレッドを巻き戻す。
+    // no_std のバイナリでは、パニックしたときの動きを自分で1つだけ決める:
+    //
+    //   #[panic_handler]
+    //   fn panic(info: &core::panic::PanicInfo) -> ! {
+    //       loop { core::hint::spin_loop(); }   // 止まって待つ（戻り値は !）
+    //   }
+    //
+    // firmware クレートでは、std のハンドラと重複しないように
+    // #[cfg(target_os = "none")] を付けて OS の無いターゲットでだけ定義している
+    println!("PC（std あり）では std のパニックハンドラが使われます");
+    println!("OS の無いターゲットでは firmware の panic() が呼ばれ、その場で止まります");
+    println!("よく使う方針: 止まる（panic-halt）/ デバッガに知らせる / リセットする");
+
+    // 組み込みでは巻き戻し（unwind）をしないのが普通なので、Cargo.toml に
+    //   [profile.release]
+    //   panic = "abort"
+    // と書いてバイナリを小さくする
+    println!("panic = \"abort\" にすると巻き戻しのためのコードが入らず、小さくなります");
+}
+
+/// これまでの章は no_std で動くか
+pub fn surviving_samples() {
+    println!("\n=== これまでの章は no_std で動くか ===");
+
+    // 出力（println!）は std なので、どの章も「計算の部分」についての判定
+    let chapters = [
+        ("basics", "core", "変数・関数・制御フローはそのまま"),
+        (
+            "ownership",
+            "core / alloc",
+            "所有権と借用は core。String の例は alloc",
+        ),
+        ("structs_enums", "core", "構造体・列挙型・Option・Result"),
+        ("pattern_matching", "core", "パターンは言語機能"),
+        (
+            "error_handling",
+            "std",
+            "File と io::Error は std。? 演算子と Result は core",
+        ),
+        (
+            "traits_generics",
+            "core",
+            "トレイトとジェネリクスは言語機能",
+        ),
+        (
+            "collections",
+            "alloc / std",
+            "Vec と String は alloc、HashMap は std",
+        ),
+        (
+            "iterators_closures",
+            "core",
+            "Iterator とクロージャ（collect は alloc）",
+        ),
+        ("lifetimes", "core", "ライフタイムは実行時のものではない"),
+        (
+            "algorithms",
+            "core / std",
+            "sort_unstable は core、計測の Instant は std",
+        ),
+        (
+            "data_structures",
+            "alloc",
+            "Box・Rc・RefCell（RefCell は core）",
+        ),
+        (
+            "graphs",
+            "alloc / std",
+            "BinaryHeap・VecDeque は alloc、HashMap は std",
+        ),
+        (
+            "parsing",
+            "core / alloc",
+            "字句解析は core、構文木の Box は alloc",
+        ),
+    ];
+    for (chapter, layer, note) in chapters {
+        println!("  {:<20} {:<12} {}", chapter, layer, note);
+    }
+    println!("言語機能の多くは core だけで動く。困るのはヒープ・OS・入出力の部分");
+}
+
+/// チートシート用のデータ（`cheatsheet no_std_demo` で表示）
+pub const CHEATSHEET: CheatSheet = CheatSheet {
+    syntax: &[
+        ("#![no_std]", "std を使わない（プレリュードが core になる）"),
+        (
+            "extern crate alloc;",
+            "ヒープを使う型（Vec、String）を読み込む",
+        ),
+        (
+            "#[panic_handler] fn panic(info: &PanicInfo) -> !",
+            "no_std のバイナリのパニック時の動き",
+        ),
+        (
+            "#[cfg(target_os = \"none\")]",
+            "OS の無いターゲットでだけ有効にする",
+        ),
+        (
+            "[features] alloc = []",
+            "アロケータが必要な部分を feature で分ける",
+        ),
+    ],
+    methods: &[
+        (
+            "impl fmt::Write for Buf",
+            "write! で固定長のバッファに書き込む",
+        ),
+        ("core::str::from_utf8(&bytes)", "バイト列を &str として見る"),
+        ("alloc::collections::BTreeMap", "no_std で使える連想配列"),
+        ("core::hint::spin_loop()", "何もせずに待つループ"),
+        (
+            "cargo build --target thumbv7em-none-eabihf",
+            "マイコン向けにビルドする",
+        ),
+    ],
+    gotchas: &[
+        "HashMap と println! は std にしか無い",
+        "alloc を使うにはバイナリ側で #[global_allocator] を用意する",
+        "std があるときに #[panic_handler] を定義すると重複エラーになる",
+        "ライブラリは no_std で書いておくと、std のある環境からも使える",
+    ],
+};
+
+/// すべてのデモを実行
+pub fn run_all() {
+    println!("╔════════════════════════════════════════════════════════════════╗");
+    println!("║          Rust no_std（組み込み向け）サンプル                     ║");
+    println!("╚════════════════════════════════════════════════════════════════╝");
+
+    lesson_api::run_topic("no_std_demo");
+}
@@ -0,0 +1,534 @@
+// ============================================================================
+// Rust オブジェクト指向のパターンサンプル
+// 公式ドキュメント: https://doc.rust-lang.org/book/ch18-00-oop.html
+// ============================================================================
+//
+// Rust にクラスの継承は無いが、カプセル化（pub を付けないフィールド）と、
+// トレイトオブジェクトによる多態で、オブジェクト指向のパターンを書ける。
+//
+// - カプセル化         フィールドを隠して、メソッドだけを公開する
+// - Box<dyn Trait>     型の違う値を1つの Vec に入れて、同じメソッドを呼ぶ
+// - State パターン     状態をトレイトオブジェクトにして、遷移を状態の側に書く
+// - 型状態（typestate）状態ごとに別の型にして、不正な遷移をコンパイルエラーにする
+//
+// ブログの投稿（下書き → レビュー待ち → 公開）を、State パターンと型状態の2通りで書いて比べる。
+
+use lesson_api::{println, text, CheatSheet};
+use std::mem;
+
+// ----------------------------------------------------------------------------
+// カプセル化
+// ----------------------------------------------------------------------------
+
+/// 平均値をいつも最新に保つコレクション（The Book 18.1 の例）
+/// フィールドが非公開なので、list を直接いじって average とずれることが無い
+#[derive(Debug, Default)]
+pub struct AveragedCollection {
+    list: Vec<i32>,
+    average: f64,
+}
+
+impl AveragedCollection {
+    pub fn new() -> AveragedCollection {
+        AveragedCollection::default()
+    }
+
+    pub fn add(&mut self, value: i32) {
+        self.list.push(value);
+        self.update_average();
+    }
+
+    pub fn remove(&mut self) -> Option<i32> {
+        let value = self.list.pop()?;
+        self.update_average();
+        Some(value)
+    }
+
+    pub fn average(&self) -> f64 {
+        self.average
+    }
+
+    fn update_average(&mut self) {
+        let total: i32 = self.list.iter().sum();
+        self.average = if self.list.is_empty() {
+            0.0
+        } else {
+            total as f64 / self.list.len() as f64
+        };
+    }
+}
+
+/// カプセル化（非公開のフィールド）
+pub fn encapsulation() {
+    println!("\n=== カプセル化（非公開のフィールド） ===");
+
+    let mut collection = AveragedCollection::new();
+    for value in [3, 5, 10] {
+        collection.add(value);
+        println!("add({:>2}) → average = {:.2}", value, collection.average());
+    }
+    let removed = collection.remove();
+    println!(
+        "remove() = {:?} → average = {:.2}",
+        removed,
+        collection.average()
+    );
+
+    // collection.list.push(100) はクレートの外からはコンパイルエラー（フィールドが非公開）
+    // 中身を Vec から HashSet に変えても、公開しているメソッドが同じなら使う側は困らない
+    println!("list と average は非公開。add / remove を通すので、平均がずれることは無い");
+}
+
+// ----------------------------------------------------------------------------
+// トレイトオブジェクト（GUI の部品）
+// ----------------------------------------------------------------------------
+
+/// 画面に描ける部品（描いた結果を行の Vec で返す）
+pub trait Draw {
+    fn draw(&self) -> Vec<String>;
+}
+
+pub struct Button {
+    pub width: usize,
+    pub label: String,
+}
+
+impl Draw for Button {
+    fn draw(&self) -> Vec<String> {
+        let inner = self.width.max(self.label.chars().count() + 2);
+        vec![
+            format!("+{}+", "-".repeat(inner)),
+            format!("|{:^inner$}|", self.label),
+            format!("+{}+", "-".repeat(inner)),
+        ]
+    }
+}
+
+pub struct SelectBox {
+    pub options: Vec<String>,
+    pub selected: usize,
+}
+
+impl Draw for SelectBox {
+    fn draw(&self) -> Vec<String> {
+        self.options
+            .iter()
+            .enumerate()
+            .map(|(i, option)| {
+                let mark = if i == self.selected { "(*)" } else { "( )" };
+                format!("{} {}", mark, option)
+            })
+            .collect()
+    }
+}
+
+/// 型の違う部品をまとめて持つ画面
+/// Vec<T: Draw> だと1種類の型しか入らないが、Box<dyn Draw> なら Draw を実装した何でも入る
+pub struct Screen {
+    pub components: Vec<Box<dyn Draw>>,
+}
+
+impl Screen {
+    pub fn run(&self) -> Vec<String> {
+        self.components
+            .iter()
+            .flat_map(|component| component.draw())
+            .collect()
+    }
+}
+
+// ライブラリの利用者があとから足した部品（Screen のコードは変えなくてよい）
+struct Label(&'static str);
+
+impl Draw for Label {
+    fn draw(&self) -> Vec<String> {
+        vec![self.0.to_string()]
+    }
+}
+
+/// Vec<Box<dyn Draw>> で GUI の部品を並べる
+pub fn gui_components() {
+    println!("\n=== Vec<Box<dyn Draw>> で GUI の部品を並べる ===");
+
+    let screen = Screen {
+        components: vec![
+            Box::new(Label("好きな言語は？")),
+            Box::new(SelectBox {
+                options: vec!["Rust".to_string(), "Go".to_string(), "C++".to_string()],
+                selected: 0,
+            }),
+            Box::new(Button {
+                width: 10,
+                label: "OK".to_string(),
+            }),
+        ],
+    };
+    for line in screen.run() {
+        println!("  {}", line);
+    }
+
+    // dyn Draw の呼び出しは vtable を引いて、実行時に実装を選ぶ（動的ディスパッチ）
+    println!(
+        "&Button のサイズ: {} バイト / &dyn Draw のサイズ: {} バイト（データ + vtable のポインタ）",
+        mem::size_of::<&Button>(),
+        mem::size_of::<&dyn Draw>()
+    );
+    // Clone のように Self を返すメソッドを持つトレイトは dyn にできない（E0038）
+    println!("Screen は部品の具体的な型を知らない。「Draw を実装している」ことだけを頼りにする");
+}
+
+// ----------------------------------------------------------------------------
+// State パターン（トレイトオブジェクト版）
+// ----------------------------------------------------------------------------
+
+/// 状態を Box<dyn State> で持つブログの投稿（The Book 18.3 の例）
+pub mod blog {
+    pub struct Post {
+        // 遷移のときに古い状態を取り出して置き換えるので Option にする
+        state: Option<Box<dyn State>>,
+        content: String,
+    }
+
+    impl Post {
+        pub fn new() -> Post {
+            Post {
+                state: Some(Box::new(Draft {})),
+                content: String::new(),
+            }
+        }
+
+        /// 本文を足す（下書きのときだけ）
+        pub fn add_text(&mut self, text: &str) {
+            if self.state().can_edit() {
+                self.content.push_str(text);
+            }
+        }
+
+        /// 公開されていれば本文、そうでなければ空
+        pub fn content(&self) -> &str {
+            self.state().content(self)
+        }
+
+        pub fn status(&self) -> &'static str {
+            self.state().name()
+        }
+
+        pub fn request_review(&mut self) {
+            self.transition(|state| state.request_review());
+        }
+
+        pub fn approve(&mut self) {
+            self.transition(|state| state.approve());
+        }
+
+        pub fn reject(&mut self) {
+            self.transition(|state| state.reject());
+        }
+
+        fn state(&self) -> &dyn State {
+            self.state.as_deref().expect("状態はいつもある")
+        }
+
+        // 状態を取り出して、遷移した先の状態に入れ替える
+        fn transition(&mut self, f: impl FnOnce(Box<dyn State>) -> Box<dyn State>) {
+            if let Some(state) = self.state.take() {
+                self.state = Some(f(state));
+            }
+        }
+    }
+
+    impl Default for Post {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    // self: Box<Self> は「Box に入った自分の所有権を受け取る」メソッド
+    // 古い状態は消費され、新しい状態が返る
+    trait State {
+        fn name(&self) -> &'static str;
+        fn request_review(self: Box<Self>) -> Box<dyn State>;
+        fn approve(self: Box<Self>) -> Box<dyn State>;
+        fn reject(self: Box<Self>) -> Box<dyn State>;
+        fn can_edit(&self) -> bool {
+            false
+        }
+        // 既定の実装は空。公開済みの状態だけが上書きする
+        fn content<'a>(&self, _post: &'a Post) -> &'a str {
+            ""
+        }
+    }
+
+    struct Draft {}
+
+    impl State for Draft {
+        fn name(&self) -> &'static str {
+            "下書き"
+        }
+        fn request_review(self: Box<Self>) -> Box<dyn State> {
+            Box::new(PendingReview { approvals: 0 })
+        }
+        fn approve(self: Box<Self>) -> Box<dyn State> {
+            self
+        }
+        fn reject(self: Box<Self>) -> Box<dyn State> {
+            self
+        }
+        fn can_edit(&self) -> bool {
+            true
+        }
+    }
+
+    /// 公開には2回の承認が要る
+    struct PendingReview {
+        approvals: u32,
+    }
+
+    impl State for PendingReview {
+        fn name(&self) -> &'static str {
+            "レビュー待ち"
+        }
+        fn request_review(self: Box<Self>) -> Box<dyn State> {
+            self
+        }
+        fn approve(self: Box<Self>) -> Box<dyn State> {
+            if self.approvals + 1 >= 2 {
+                Box::new(Published {})
+            } else {
+                Box::new(PendingReview {
+                    approvals: self.approvals + 1,
+                })
+            }
+        }
+        fn reject(self: Box<Self>) -> Box<dyn State> {
+            Box::new(Draft {})
+        }
+    }
+
+    struct Published {}
+
+    impl State for Published {
+        fn name(&self) -> &'static str {
+            "公開"
+        }
+        fn request_review(self: Box<Self>) -> Box<dyn State> {
+            self
+        }
+        fn approve(self: Box<Self>) -> Box<dyn State> {
+            self
+        }
+        fn reject(self: Box<Self>) -> Box<dyn State> {
+            self
+        }
+        fn content<'a>(&self, post: &'a Post) -> &'a str {
+            &post.content
+        }
+    }
+}
+
+/// State パターン（トレイトオブジェクト版）
+pub fn state_pattern() {
+    println!("\n=== State パターン（トレイトオブジェクト版） ===");
+
+    let mut post = blog::Post::new();
+    // 全角を含む文字列は表示幅でそろえる
+    let show = |step: &str, post: &blog::Post| {
+        println!(
+            "  {} 状態 = {} content() = {:?}",
+            text::pad_right(step, 18),
+            text::pad_right(post.status(), 12),
+            post.content()
+        );
+    };
+
+    post.add_text("今日はサラダを食べた");
+    show("add_text", &post);
+    post.request_review();
+    show("request_review", &post);
+    post.reject();
+    show("reject", &post);
+    post.request_review();
+    post.approve();
+    show("approve（1回目）", &post);
+    post.add_text("（追記）");
+    show("add_text（無視）", &post);
+    post.approve();
+    show("approve（2回目）", &post);
+
+    // Post のメソッドは、どの状態でも同じように呼べる。何が起きるかは状態の側が決める
+    println!("遷移のルールは各状態の impl にあるので、状態を足しても Post はほとんど変わらない");
+    println!("ただし、下書きで content() を呼ぶような間違いは実行時まで分からない");
+}
+
+// ----------------------------------------------------------------------------
+// 型状態（typestate）版
+// ----------------------------------------------------------------------------
+
+/// 状態ごとに別の型にしたブログの投稿
+/// 遷移のメソッドは self を消費して次の型を返すので、古い状態は使えなくなる
+pub mod typed_blog {
+    /// 公開された投稿（content を読めるのはこの型だけ）
+    pub struct Post {
+        content: String,
+    }
+
+    pub struct DraftPost {
+        content: String,
+    }
+
+    pub struct PendingReviewPost {
+        content: String,
+    }
+
+    impl Post {
+        // 作られるのは下書き
+        #[allow(clippy::new_ret_no_self)]
+        pub fn new() -> DraftPost {
+            DraftPost {
+                content: String::new(),
+            }
+        }
+
+        pub fn content(&self) -> &str {
+            &self.content
+        }
+    }
+
+    impl DraftPost {
+        pub fn add_text(&mut self, text: &str) {
+            self.content.push_str(text);
+        }
+
+        pub fn request_review(self) -> PendingReviewPost {
+            PendingReviewPost {
+                content: self.content,
+            }
+        }
+    }
+
+    impl PendingReviewPost {
+        pub fn approve(self) -> Post {
+            Post {
+                content: self.content,
+            }
+        }
+
+        pub fn reject(self) -> DraftPost {
+            DraftPost {
+                content: self.content,
+            }
+        }
+    }
+}
+
+/// 型状態（typestate）版
+pub fn typestate_pattern() {
+    println!("\n=== 型状態（typestate）版 ===");
+
+    let mut draft = typed_blog::Post::new();
+    draft.add_text("今日はサラダを食べた");
+    // draft.content() は DraftPost にメソッドが無いのでコンパイルエラー（E0599）
+    println!("DraftPost を作成（content() はまだ呼べない）");
+
+    let pending = draft.request_review();
+    // request_review は self を消費するので、ここで draft を使うと E0382
+    println!("request_review → PendingReviewPost（draft はムーブ済み）");
+
+    let mut draft = pending.reject();
+    draft.add_text("。ドレッシングはごま");
+    println!("reject → DraftPost に戻って追記");
+
+    let post = draft.request_review().approve();
+    println!(
+        "request_review → approve → Post: content() = {:?}",
+        post.content()
+    );
+
+    // 型に状態を持たせると、不正な遷移は実行する前にコンパイラが止める
+    // 代わりに、遷移のたびに let で受け直す必要があり、状態を足すと型も増える
+    println!("不正な操作はコンパイルエラー。実行時に状態を調べる分岐も要らない");
+    println!("State パターンは柔軟、型状態は安全。Rust では後者もよく使われる");
+}
+
+/// チートシート用のデータ（`cheatsheet oop_patterns` で表示）
+pub const CHEATSHEET: CheatSheet = CheatSheet {
+    syntax: &[
+        ("pub struct S { field: T }", "フィールドを非公開にしてメソッドだけ公開する"),
+        ("Vec<Box<dyn Draw>>", "Draw を実装した型の違う値をまとめる"),
+        ("fn approve(self: Box<Self>) -> Box<dyn State>", "Box に入った自分を消費して次の状態を返す"),
+        ("fn request_review(self) -> PendingReviewPost", "型状態: self を消費して次の型を返す"),
+        ("state: Option<Box<dyn State>>", "take() で取り出して入れ替えるための Option"),
+    ],
+    methods: &[
+        ("self.state.take()", "状態を取り出す（一時的に None にする）"),
+        ("self.state.as_deref()", "Option<Box<dyn State>> → Option<&dyn State>"),
+        ("component.draw()", "vtable を引いて実装を選ぶ（動的ディスパッチ）"),
+    ],
+    gotchas: &[
+        "Self を返すメソッドやジェネリックなメソッドを持つトレイトは dyn にできない（E0038）",
+        "トレイトオブジェクトの呼び出しはインライン化されにくい（静的ディスパッチより遅いことがある）",
+        "State パターンの不正な遷移は実行時まで分からない。型状態ならコンパイル時に分かる",
+        "継承は無い。共通の振る舞いはトレイトの既定の実装で共有する",
+    ],
+};
+
+/// すべてのデモを実行
+pub fn run_all() {
+    println!("╔════════════════════════════════════════════════════════════════╗");
+    println!("║          Rust オブジェクト指向のパターンサンプル                 ║");
+    println!("╚════════════════════════════════════════════════════════════════╝");
+
+    lesson_api::run_topic("oop_patterns");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn state_pattern_needs_two_approvals() {
+        let mut post = blog::Post::new();
+        post.add_text("hello");
+        post.request_review();
+        post.approve();
+        assert_eq!((post.status(), post.content()), ("レビュー待ち", ""));
+        post.reject();
+        post.add_text(" world");
+        post.request_review();
+        post.approve();
+        post.approve();
+        assert_eq!((post.status(), post.content()), ("公開", "hello world"));
+
+        let mut draft = typed_blog::Post::new();
+        draft.add_text("typed");
+        let post = draft.request_review().reject().request_review().approve();
+        assert_eq!(post.content(), "typed");
+    }
+
+    #[test]
+    fn screen_draws_every_component() {
+        let screen = Screen {
+            components: vec![
+                Box::new(Button {
+                    width: 4,
+                    label: "OK".to_string(),
+                }),
+                Box::new(SelectBox {
+                    options: vec!["a".to_string(), "b".to_string()],
+                    selected: 1,
+                }),
+            ],
+        };
+        assert_eq!(
+            screen.run(),
+            ["+----+", "| OK |", "+----+", "( ) a", "(*) b"]
+        );
+
+        let mut collection = AveragedCollection::new();
+        collection.add(1);
+        collection.add(2);
+        assert_eq!(collection.average(), 1.5);
+        assert_eq!(collection.remove(), Some(2));
+        assert_eq!(collection.average(), 1.0);
+    }
+}
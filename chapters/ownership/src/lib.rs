@@ -0,0 +1,534 @@
+// ============================================================================
+// Rust所有権システムサンプル
+// 公式ドキュメント: https://doc.rust-lang.org/book/ch04-00-understanding-ownership.html
+// ============================================================================
+//
+// 所有権のルール:
+// 1. Rustの各値には「所有者」と呼ばれる変数がある
+// 2. 値の所有者は同時に1つだけ
+// 3. 所有者がスコープを抜けると、値は破棄される（drop）
+
+use lesson_api::{println, CheatSheet};
+
+/// 所有権の基本デモ
+pub fn ownership_basics() {
+    println!("\n=== 所有権の基本 ===");
+
+    // スコープと所有権
+    {
+        let s = String::from("hello"); // sがスコープに入り、有効になる
+        println!("スコープ内: s = {}", s);
+        // sを使って何かする
+    } // スコープを抜けると、sはdropされてメモリが解放される
+      // ここではsは無効
+
+    // ムーブ（Move）
+    println!("\n-- ムーブ --");
+    let s1 = String::from("hello");
+    let s2 = s1; // s1の値はs2にムーブされる
+                 // println!("{}", s1); // エラー！s1はもう有効ではない
+    println!("s2 = {} (s1からムーブされた)", s2);
+
+    // 整数などのスカラー型はCopyトレイトを持つのでムーブされない
+    let x = 5;
+    let y = x; // xはコピーされる（ムーブではない）
+    println!("x = {}, y = {} (整数はコピーされる)", x, y);
+
+    // クローン（Clone）
+    println!("\n-- クローン --");
+    let s1 = String::from("hello");
+    let s2 = s1.clone(); // ヒープデータを含めて深いコピー
+    println!("s1 = {}, s2 = {} (クローンされた)", s1, s2);
+}
+
+/// 関数と所有権
+pub fn ownership_and_functions() {
+    println!("\n=== 関数と所有権 ===");
+
+    let s = String::from("hello"); // sがスコープに入る
+    takes_ownership(s); // sの値が関数にムーブする
+                        // println!("{}", s); // エラー！sはもう有効ではない
+
+    let x = 5; // xがスコープに入る
+    makes_copy(x); // xは関数にコピーされる
+    println!("makes_copy後も x = {} (コピーされたので使える)", x);
+
+    // 所有権を返す
+    println!("\n-- 所有権を返す --");
+    let s1 = gives_ownership(); // 関数が所有権を返す
+    println!("gives_ownership から: {}", s1);
+
+    let s2 = String::from("hello");
+    let s3 = takes_and_gives_back(s2); // s2はムーブし、戻り値はs3にムーブ
+                                       // println!("{}", s2); // エラー！s2は無効
+    println!("takes_and_gives_back から: {}", s3);
+}
+
+fn takes_ownership(some_string: String) {
+    println!("takes_ownership: {}", some_string);
+} // some_stringがスコープを抜けdropされる
+
+fn makes_copy(some_integer: i32) {
+    println!("makes_copy: {}", some_integer);
+} // some_integerがスコープを抜けるが、特に何も起きない
+
+fn gives_ownership() -> String {
+    let some_string = String::from("yours");
+    some_string // 戻り値として所有権を移動
+}
+
+fn takes_and_gives_back(a_string: String) -> String {
+    a_string // そのまま返す
+}
+
+/// 参照と借用
+pub fn references_and_borrowing() {
+    println!("\n=== 参照と借用 ===");
+
+    // 不変参照
+    println!("\n-- 不変参照 --");
+    let s1 = String::from("hello");
+    let len = calculate_length(&s1); // &s1でs1への参照を作成
+    println!("'{}' の長さは {} です", s1, len); // s1はまだ使える！
+
+    // 複数の不変参照は許可される
+    let r1 = &s1;
+    let r2 = &s1;
+    println!("r1 = {}, r2 = {}", r1, r2);
+
+    // 可変参照
+    println!("\n-- 可変参照 --");
+    let mut s = String::from("hello");
+    change(&mut s);
+    println!("変更後: {}", s);
+
+    // 可変参照の制限: 同時に1つの可変参照しか持てない
+    let mut s = String::from("hello");
+    {
+        let r1 = &mut s;
+        println!("スコープ内の可変参照: {}", r1);
+    } // r1はここでスコープを抜ける
+    let r2 = &mut s; // 新しい可変参照を作れる
+    println!("新しい可変参照: {}", r2);
+
+    // 不変参照と可変参照は同時に存在できない（データ競合を防ぐ）
+    let mut s = String::from("hello");
+    let r1 = &s; // OK
+    let r2 = &s; // OK
+    println!("r1 = {}, r2 = {}", r1, r2);
+    // r1とr2はここ以降使われないので、このスコープは終了
+    let r3 = &mut s; // OK - r1とr2はもう使われない
+    println!("r3 = {}", r3);
+}
+
+fn calculate_length(s: &String) -> usize {
+    s.len()
+} // sはスコープを抜けるが、参照なので何もdropされない
+
+fn change(some_string: &mut String) {
+    some_string.push_str(", world");
+}
+
+/// スライス型
+pub fn slices() {
+    println!("\n=== スライス ===");
+
+    // 文字列スライス
+    println!("\n-- 文字列スライス --");
+    let s = String::from("hello world");
+
+    let hello = &s[0..5]; // または &s[..5]
+    let world = &s[6..11]; // または &s[6..]
+    let whole = &s[..]; // 全体
+
+    println!("s = '{}'", s);
+    println!("hello = '{}', world = '{}'", hello, world);
+    println!("whole = '{}'", whole);
+
+    // 文字列リテラルはスライス
+    let s: &str = "Hello, world!"; // &str型
+    println!("文字列リテラル: {}", s);
+
+    // first_wordの例
+    let my_string = String::from("hello world");
+    let word = first_word(&my_string[..]); // Stringのスライスを渡す
+    println!("最初の単語: {}", word);
+
+    let my_string_literal = "hello world";
+    let word = first_word(my_string_literal); // 文字列リテラルはそのまま渡せる
+    println!("最初の単語: {}", word);
+
+    // 配列スライス
+    println!("\n-- 配列スライス --");
+    let a = [1, 2, 3, 4, 5];
+    let slice = &a[1..3]; // [2, 3]
+    println!("配列: {:?}", a);
+    println!("スライス [1..3]: {:?}", slice);
+    assert_eq!(slice, &[2, 3]);
+}
+
+// &str を受け取ることで String と &str の両方を受け入れられる
+fn first_word(s: &str) -> &str {
+    let bytes = s.as_bytes();
+
+    for (i, &item) in bytes.iter().enumerate() {
+        if item == b' ' {
+            return &s[0..i];
+        }
+    }
+
+    &s[..]
+}
+
+/// ダングリング参照の防止
+pub fn no_dangling() {
+    println!("\n=== ダングリング参照の防止 ===");
+
+    // Rustコンパイラはダングリング参照を防ぐ
+    // 以下のコードはコンパイルエラーになる:
+    //
+    // fn dangle() -> &String {
+    //     let s = String::from("hello");
+    //     &s // sへの参照を返そうとする
+    // } // sはここでdropされるので、参照は無効になる
+    //
+    // 解決策: 所有権を返す
+    fn no_dangle() -> String {
+        let s = String::from("hello");
+        s // 所有権をムーブする
+    }
+
+    let result = no_dangle();
+    println!("ダングリングしない: {}", result);
+}
+
+// クローンだらけの実装: 値で受け取るので、呼ぶ側は毎回 clone して渡す
+fn longest_owned(words: Vec<String>) -> String {
+    let mut longest = String::new();
+    for word in words {
+        if word.len() > longest.len() {
+            longest = word.clone();
+        }
+    }
+    longest
+}
+
+// 借用する実装: スライスを見るだけで、結果も元の文字列への参照
+fn longest_borrowed(words: &[String]) -> &str {
+    let mut longest = "";
+    for word in words {
+        if word.len() > longest.len() {
+            longest = word;
+        }
+    }
+    longest
+}
+
+/// クローンと借用のコスト
+pub fn clone_vs_borrow_cost() {
+    println!("\n=== クローンと借用のコスト（アロケーション回数） ===");
+
+    // 「clone はコストがかかる」を実際の確保の回数で確かめる
+    let words: Vec<String> = "the quick brown fox jumps over the lazy dog"
+        .split(' ')
+        .map(String::from)
+        .collect();
+    let rounds = 100;
+
+    let (owned, cloning) = lesson_api::alloc_count::measure(|| {
+        let mut result = String::new();
+        for _ in 0..rounds {
+            result = longest_owned(words.clone());
+        }
+        result
+    });
+    let (borrowed, borrowing) = lesson_api::alloc_count::measure(|| {
+        let mut result = "";
+        for _ in 0..rounds {
+            result = longest_borrowed(&words);
+        }
+        result
+    });
+    println!(
+        "{}語の中で一番長い単語を{}回探す: {:?} / {:?}",
+        words.len(),
+        rounds,
+        owned,
+        borrowed
+    );
+
+    if !lesson_api::alloc_count::is_installed() {
+        println!("（CountingAlloc が登録されていないので、確保の回数は数えられません）");
+        return;
+    }
+    println!("  確保回数   バイト数  実装");
+    for (name, stats) in [
+        ("clone して値で渡す", cloning),
+        ("&[String] を借用する", borrowing),
+    ] {
+        println!("  {:>8} {:>10}  {}", stats.allocations, stats.bytes, name);
+    }
+
+    // Vec の clone で1回、中の String ごとに1回ずつ確保される（さらに longest の clone の分）
+    println!(
+        "1回の呼び出しで {}回: words.clone() が Vec 1回 + String {}個、残りは word.clone()",
+        cloning.allocations / rounds,
+        words.len()
+    );
+    println!("借用なら確保は0回。読むだけの関数は &T / &[T] / &str で受け取る");
+}
+
+// ----------------------------------------------------------------------------
+// 参照の代わりにインデックスでつなぐ
+// ----------------------------------------------------------------------------
+
+/// IndexList の1要素。前後の要素を参照ではなく Vec の位置で指す
+struct IndexNode<T> {
+    /// 削除した場所は None（あとで再利用する）
+    value: Option<T>,
+    prev: Option<usize>,
+    next: Option<usize>,
+}
+
+/// Vec の位置でつないだ双方向リスト
+///
+/// 要素はすべて nodes（Vec）が所有するので、前後のつながりは usize のコピーでよい。
+/// 参照を使わないので、借用チェッカーとぶつからない
+pub struct IndexList<T> {
+    nodes: Vec<IndexNode<T>>,
+    head: Option<usize>,
+    tail: Option<usize>,
+    /// 削除して空いた位置
+    free: Vec<usize>,
+}
+
+impl<T> IndexList<T> {
+    pub fn new() -> Self {
+        IndexList {
+            nodes: Vec::new(),
+            head: None,
+            tail: None,
+            free: Vec::new(),
+        }
+    }
+
+    // 空いた位置があれば使い回し、無ければ末尾に足す
+    fn alloc(&mut self, node: IndexNode<T>) -> usize {
+        match self.free.pop() {
+            Some(index) => {
+                self.nodes[index] = node;
+                index
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        }
+    }
+
+    /// 末尾に追加して、その位置を返す
+    pub fn push_back(&mut self, value: T) -> usize {
+        let index = self.alloc(IndexNode {
+            value: Some(value),
+            prev: self.tail,
+            next: None,
+        });
+        match self.tail {
+            Some(tail) => self.nodes[tail].next = Some(index),
+            None => self.head = Some(index),
+        }
+        self.tail = Some(index);
+        index
+    }
+
+    /// 先頭に追加して、その位置を返す
+    pub fn push_front(&mut self, value: T) -> usize {
+        let index = self.alloc(IndexNode {
+            value: Some(value),
+            prev: None,
+            next: self.head,
+        });
+        match self.head {
+            Some(head) => self.nodes[head].prev = Some(index),
+            None => self.tail = Some(index),
+        }
+        self.head = Some(index);
+        index
+    }
+
+    /// index の要素を取り除く（前後をつなぎ直すだけなので O(1)）
+    pub fn remove(&mut self, index: usize) -> Option<T> {
+        let value = self.nodes.get_mut(index)?.value.take()?;
+        let (prev, next) = (self.nodes[index].prev, self.nodes[index].next);
+        match prev {
+            Some(prev) => self.nodes[prev].next = next,
+            None => self.head = next,
+        }
+        match next {
+            Some(next) => self.nodes[next].prev = prev,
+            None => self.tail = prev,
+        }
+        self.free.push(index);
+        Some(value)
+    }
+
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.nodes.get(index)?.value.as_ref()
+    }
+
+    /// 先頭から順に値を集める
+    pub fn to_vec(&self) -> Vec<&T> {
+        self.walk(self.head, |node| node.next)
+    }
+
+    /// 末尾から逆順に値を集める（prev をたどる）
+    pub fn to_vec_rev(&self) -> Vec<&T> {
+        self.walk(self.tail, |node| node.prev)
+    }
+
+    fn walk(&self, start: Option<usize>, step: fn(&IndexNode<T>) -> Option<usize>) -> Vec<&T> {
+        let mut values = Vec::new();
+        let mut current = start;
+        while let Some(index) = current {
+            let node = &self.nodes[index];
+            values.extend(node.value.as_ref());
+            current = step(node);
+        }
+        values
+    }
+}
+
+impl<T> Default for IndexList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 参照の代わりにインデックスでつなぐ
+pub fn index_based_links() {
+    println!("\n=== 参照の代わりにインデックスでつなぐ ===");
+
+    // 参照で双方向リストを作ろうとすると:
+    //   struct Node<'a> { value: i32, prev: Option<&'a Node<'a>>, next: Option<&'a Node<'a>> }
+    // a.next = Some(&b) と b.prev = Some(&a) を両方書くには、a と b を同時に
+    // 可変で借りる必要があり、借用チェッカーが許さない（所有者も決められない）
+    println!("参照で前後をつなぐと、同じ要素を同時に可変で借りることになりコンパイルできない");
+
+    // Vec が全要素を所有し、つながりは位置（usize）で持つ
+    let mut list = IndexList::new();
+    let b = list.push_back("b");
+    list.push_back("c");
+    list.push_front("a");
+    list.push_back("d");
+    println!("前から: {:?}", list.to_vec());
+    println!("後ろから: {:?}", list.to_vec_rev());
+
+    // 途中の削除も、前後の番号を書き換えるだけ
+    println!("remove(位置 {}) = {:?}", b, list.remove(b));
+    println!("前から: {:?}", list.to_vec());
+
+    // 空いた位置は次の追加で使い回される
+    let e = list.push_back("e");
+    println!("push_back(\"e\") の位置: {}（消した b の位置を再利用）", e);
+    println!("前から: {:?}", list.to_vec());
+
+    // 位置はただの数なので、古い位置を持ち続けると別の要素を指してしまう
+    println!(
+        "古い位置 {} を get すると {:?}（b ではなく e）",
+        b,
+        list.get(b)
+    );
+    println!("防ぐには、位置と一緒に世代番号を持たせて、使うときに確かめる（generational arena）");
+    println!("グラフや木も同じ形: Vec<Node> と、辺を表す usize の組");
+}
+
+/// 所有権のまとめ
+pub fn ownership_summary() {
+    println!("\n=== 所有権のまとめ ===");
+    println!(
+        r#"
+所有権のルール:
+1. 各値には1つの所有者がある
+2. 所有者は同時に1つだけ
+3. 所有者がスコープを抜けると値はdropされる
+
+参照のルール:
+1. 任意の時点で、1つの可変参照 OR 任意の数の不変参照を持てる
+2. 参照は常に有効でなければならない
+
+これらのルールにより:
+- メモリ安全性がコンパイル時に保証される
+- データ競合がコンパイル時に防がれる
+- ガベージコレクタが不要になる
+"#
+    );
+}
+
+/// チートシート用のデータ（`cheatsheet ownership` で表示）
+pub const CHEATSHEET: CheatSheet = CheatSheet {
+    syntax: &[
+        ("let s2 = s1;", "ムーブ（s1は使えなくなる）"),
+        ("let s2 = s1.clone();", "ヒープデータを含めて深いコピー"),
+        ("fn f(s: &String)", "不変参照で借用"),
+        ("fn f(s: &mut String)", "可変参照で借用"),
+        ("&s[0..5] / &s[..]", "文字列スライス"),
+        (
+            "fn first_word(s: &str) -> &str",
+            "&strを受け取るとStringも&strも渡せる",
+        ),
+        (
+            "struct Node { prev: Option<usize>, next: Option<usize> }",
+            "参照の代わりに Vec の位置でつなぐ",
+        ),
+    ],
+    methods: &[
+        ("s.as_bytes()", "バイト列として参照"),
+        ("s.push_str(\"...\")", "可変参照経由で文字列を変更"),
+        ("s.len()", "バイト長"),
+        ("bytes.iter().enumerate()", "インデックス付きで走査"),
+    ],
+    gotchas: &[
+        "所有者は常に1つ。ムーブ後の変数は使えない",
+        "可変参照は同時に1つだけ、不変参照とも同時に存在できない",
+        "整数などCopy型はムーブではなくコピーされる",
+        "関数内で作った値への参照は返せない（所有権ごと返す）",
+        "借用で済むところで clone すると、そのたびにヒープの確保が起きる",
+    ],
+};
+
+/// すべてのデモを実行
+pub fn run_all() {
+    println!("╔════════════════════════════════════════════════════════════════╗");
+    println!("║          Rust所有権システムサンプル                             ║");
+    println!("╚════════════════════════════════════════════════════════════════╝");
+
+    lesson_api::run_topic("ownership");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn index_list_links_both_ways() {
+        let mut list = IndexList::new();
+        let one = list.push_back(1);
+        list.push_back(2);
+        list.push_front(0);
+        let three = list.push_back(3);
+        assert_eq!(list.to_vec(), [&0, &1, &2, &3]);
+        assert_eq!(list.to_vec_rev(), [&3, &2, &1, &0]);
+
+        assert_eq!(list.remove(one), Some(1));
+        assert_eq!(list.remove(one), None);
+        assert_eq!(list.remove(three), Some(3));
+        assert_eq!(list.to_vec(), [&0, &2]);
+        assert_eq!(list.to_vec_rev(), [&2, &0]);
+
+        // 空いた位置を使い回す
+        let four = list.push_back(4);
+        assert!(four == one || four == three);
+        assert_eq!(list.to_vec(), [&0, &2, &4]);
+        assert_eq!(list.to_vec_rev(), [&4, &2, &0]);
+    }
+}
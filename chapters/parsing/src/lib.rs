@@ -0,0 +1,495 @@
+// ============================================================================
+// Rust構文解析サンプル - 字句解析器と再帰下降パーサ
+// 公式ドキュメント: https://doc.rust-lang.org/std/iter/struct.Peekable.html
+// ============================================================================
+//
+// 小さな S 式の言語を、自前の字句解析器（トークナイザ）とパーサで列挙型の
+// 構文木（AST）に変換する。イテレータ・列挙型・エラー処理の総まとめ。
+//
+//   (server
+//     (host "localhost")
+//     (port 8080)
+//     (debug true))
+//
+//   文字列 ──tokenize──▶ Vec<Token> ──parse──▶ SExpr
+//   失敗したときは「何行目の何文字目か」を持ったエラーを返す
+
+use lesson_api::{println, CheatSheet};
+use std::fmt;
+use std::iter::Peekable;
+use std::str::Chars;
+
+/// ソース中の位置（1 始まり）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}行{}列", self.line, self.column)
+    }
+}
+
+/// トークンの種類
+#[derive(Debug, Clone, PartialEq)]
+pub enum TokenKind {
+    LParen,
+    RParen,
+    Number(f64),
+    Str(String),
+    Symbol(String),
+}
+
+/// 位置付きのトークン
+#[derive(Debug, Clone, PartialEq)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub position: Position,
+}
+
+/// 構文木
+#[derive(Debug, Clone, PartialEq)]
+pub enum SExpr {
+    Number(f64),
+    Str(String),
+    Bool(bool),
+    Symbol(String),
+    List(Vec<SExpr>),
+}
+
+impl fmt::Display for SExpr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SExpr::Number(n) => write!(f, "{}", n),
+            SExpr::Str(s) => write!(f, "{:?}", s),
+            SExpr::Bool(b) => write!(f, "{}", b),
+            SExpr::Symbol(s) => write!(f, "{}", s),
+            SExpr::List(items) => {
+                write!(f, "(")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " ")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+/// エラーの種類
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseErrorKind {
+    UnexpectedChar(char),
+    UnterminatedString,
+    InvalidNumber(String),
+    UnexpectedCloseParen,
+    UnclosedList,
+    UnexpectedEof,
+    TrailingInput,
+}
+
+/// 位置付きのエラー
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub kind: ParseErrorKind,
+    pub position: Position,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: ", self.position)?;
+        match &self.kind {
+            ParseErrorKind::UnexpectedChar(c) => write!(f, "予期しない文字 '{}'", c),
+            ParseErrorKind::UnterminatedString => write!(f, "文字列が閉じられていません"),
+            ParseErrorKind::InvalidNumber(s) => write!(f, "数値として読めません: {}", s),
+            ParseErrorKind::UnexpectedCloseParen => write!(f, "対応する '(' がない ')'"),
+            ParseErrorKind::UnclosedList => write!(f, "この '(' が閉じられていません"),
+            ParseErrorKind::UnexpectedEof => write!(f, "入力が空です"),
+            ParseErrorKind::TrailingInput => write!(f, "式の後に余分な入力があります"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+// ----------------------------------------------------------------------------
+// 字句解析
+// ----------------------------------------------------------------------------
+
+/// 位置を数えながら1文字ずつ読むカーソル
+struct Cursor<'a> {
+    chars: Peekable<Chars<'a>>,
+    line: usize,
+    column: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(source: &'a str) -> Self {
+        Cursor {
+            chars: source.chars().peekable(),
+            line: 1,
+            column: 1,
+        }
+    }
+
+    fn position(&self) -> Position {
+        Position {
+            line: self.line,
+            column: self.column,
+        }
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().copied()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(c)
+    }
+
+    /// 条件を満たす間読み進めて、読んだ文字列を返す
+    fn take_while(&mut self, pred: impl Fn(char) -> bool) -> String {
+        let mut text = String::new();
+        while let Some(c) = self.peek() {
+            if !pred(c) {
+                break;
+            }
+            text.push(c);
+            self.bump();
+        }
+        text
+    }
+}
+
+fn is_symbol_char(c: char) -> bool {
+    c.is_alphanumeric() || "+-*/<>=!?_.".contains(c)
+}
+
+/// 文字列をトークン列に分解する
+pub fn tokenize(source: &str) -> Result<Vec<Token>, ParseError> {
+    let mut cursor = Cursor::new(source);
+    let mut tokens = Vec::new();
+
+    while let Some(c) = cursor.peek() {
+        let position = cursor.position();
+        let kind = match c {
+            c if c.is_whitespace() => {
+                cursor.bump();
+                continue;
+            }
+            ';' => {
+                // 行末までコメント
+                cursor.take_while(|c| c != '\n');
+                continue;
+            }
+            '(' => {
+                cursor.bump();
+                TokenKind::LParen
+            }
+            ')' => {
+                cursor.bump();
+                TokenKind::RParen
+            }
+            '"' => {
+                cursor.bump();
+                let text = cursor.take_while(|c| c != '"');
+                if cursor.bump().is_none() {
+                    return Err(ParseError {
+                        kind: ParseErrorKind::UnterminatedString,
+                        position,
+                    });
+                }
+                TokenKind::Str(text)
+            }
+            c if c.is_ascii_digit() => {
+                let text = cursor.take_while(is_symbol_char);
+                let n = text.parse().map_err(|_| ParseError {
+                    kind: ParseErrorKind::InvalidNumber(text.clone()),
+                    position,
+                })?;
+                TokenKind::Number(n)
+            }
+            c if is_symbol_char(c) => {
+                let text = cursor.take_while(is_symbol_char);
+                // "-5" のような負の数もここで数値として扱う
+                match text.parse() {
+                    Ok(n) if text.starts_with('-') && text.len() > 1 => TokenKind::Number(n),
+                    _ => TokenKind::Symbol(text),
+                }
+            }
+            other => {
+                return Err(ParseError {
+                    kind: ParseErrorKind::UnexpectedChar(other),
+                    position,
+                })
+            }
+        };
+        tokens.push(Token { kind, position });
+    }
+    Ok(tokens)
+}
+
+// ----------------------------------------------------------------------------
+// 構文解析
+// ----------------------------------------------------------------------------
+
+/// ちょうど1つの式を解析する
+pub fn parse(source: &str) -> Result<SExpr, ParseError> {
+    let mut tokens = tokenize(source)?.into_iter().peekable();
+    let expr = parse_expr(&mut tokens, end_position(source))?;
+    match tokens.next() {
+        None => Ok(expr),
+        Some(token) => Err(ParseError {
+            kind: ParseErrorKind::TrailingInput,
+            position: token.position,
+        }),
+    }
+}
+
+// 再帰下降: トークンを1つ見て、何を読むかを決める
+fn parse_expr<I>(tokens: &mut Peekable<I>, eof: Position) -> Result<SExpr, ParseError>
+where
+    I: Iterator<Item = Token>,
+{
+    let token = tokens.next().ok_or(ParseError {
+        kind: ParseErrorKind::UnexpectedEof,
+        position: eof,
+    })?;
+    match token.kind {
+        TokenKind::Number(n) => Ok(SExpr::Number(n)),
+        TokenKind::Str(s) => Ok(SExpr::Str(s)),
+        TokenKind::Symbol(s) => Ok(match s.as_str() {
+            "true" => SExpr::Bool(true),
+            "false" => SExpr::Bool(false),
+            _ => SExpr::Symbol(s),
+        }),
+        TokenKind::RParen => Err(ParseError {
+            kind: ParseErrorKind::UnexpectedCloseParen,
+            position: token.position,
+        }),
+        TokenKind::LParen => {
+            let mut items = Vec::new();
+            loop {
+                // peek で ')' かどうかだけ確かめ、消費はしない
+                match tokens.peek() {
+                    Some(Token {
+                        kind: TokenKind::RParen,
+                        ..
+                    }) => {
+                        tokens.next();
+                        return Ok(SExpr::List(items));
+                    }
+                    Some(_) => items.push(parse_expr(tokens, eof)?),
+                    None => {
+                        return Err(ParseError {
+                            kind: ParseErrorKind::UnclosedList,
+                            position: token.position,
+                        })
+                    }
+                }
+            }
+        }
+    }
+}
+
+// 入力の末尾の位置（EOF エラー用）
+fn end_position(source: &str) -> Position {
+    let mut cursor = Cursor::new(source);
+    while cursor.bump().is_some() {}
+    cursor.position()
+}
+
+/// エラー位置の行を表示して ^ で指し示す
+pub fn render_error(source: &str, error: &ParseError) -> String {
+    let line = source.lines().nth(error.position.line - 1).unwrap_or("");
+    format!(
+        "エラー: {}\n  | {}\n  | {}^",
+        error,
+        line,
+        " ".repeat(error.position.column - 1)
+    )
+}
+
+// ----------------------------------------------------------------------------
+// デモ
+// ----------------------------------------------------------------------------
+
+const SAMPLE_CONFIG: &str = r#"; サーバーの設定
+(server
+  (host "localhost")
+  (port 8080)
+  (debug true)
+  (allow ("127.0.0.1" "::1")))"#;
+
+/// 字句解析
+pub fn tokenizer_demo() {
+    println!("\n=== 字句解析（トークナイザ） ===");
+
+    let source = "(port 8080) ; コメント\n(name \"gk\")";
+    println!("入力:\n{}\n", source);
+    match tokenize(source) {
+        Ok(tokens) => {
+            for token in tokens {
+                println!("  {:<8} {:?}", token.position.to_string(), token.kind);
+            }
+        }
+        Err(e) => println!("{}", e),
+    }
+    println!("Peekable<Chars> の peek() で「次の文字」を消費せずに覗き、");
+    println!("数値・文字列・シンボルのどれを読むかを決めています。");
+}
+
+/// 構文解析と構文木
+pub fn parser_demo() {
+    println!("\n=== 構文解析（再帰下降パーサ） ===");
+
+    println!("入力:\n{}\n", SAMPLE_CONFIG);
+    let config = match parse(SAMPLE_CONFIG) {
+        Ok(expr) => expr,
+        Err(e) => {
+            println!("{}", render_error(SAMPLE_CONFIG, &e));
+            return;
+        }
+    };
+    println!("構文木を Display で再出力: {}", config);
+
+    // 構文木は普通の列挙型なので、パターンマッチで値を取り出せる
+    if let SExpr::List(items) = &config {
+        for item in &items[1..] {
+            if let SExpr::List(pair) = item {
+                if let [SExpr::Symbol(key), value] = pair.as_slice() {
+                    let kind = match value {
+                        SExpr::Number(_) => "数値",
+                        SExpr::Str(_) => "文字列",
+                        SExpr::Bool(_) => "真偽値",
+                        SExpr::Symbol(_) => "シンボル",
+                        SExpr::List(_) => "リスト",
+                    };
+                    println!("  {:<6} = {:<24} ({})", key, value.to_string(), kind);
+                }
+            }
+        }
+    }
+}
+
+/// 位置付きのエラー
+pub fn parse_errors_demo() {
+    println!("\n=== 位置付きのエラー ===");
+
+    let inputs = [
+        "(port 8080",
+        "(host \"localhost)",
+        "(a b))",
+        "(size 12px)",
+        "(tags [x y])",
+        "(a)\n(b)",
+        "",
+    ];
+    for source in inputs {
+        println!("\n入力: {:?}", source);
+        match parse(source) {
+            Ok(expr) => println!("OK: {}", expr),
+            Err(e) => println!("{}", render_error(source, &e)),
+        }
+    }
+}
+
+/// チートシート用のデータ（`cheatsheet parsing` で表示）
+pub const CHEATSHEET: CheatSheet = CheatSheet {
+    syntax: &[
+        (
+            "let mut it = s.chars().peekable();",
+            "1文字先読みできるイテレータ",
+        ),
+        (
+            "while let Some(c) = it.peek() { ... }",
+            "消費せずに次の要素を見る",
+        ),
+        (
+            "enum SExpr { Number(f64), List(Vec<SExpr>) }",
+            "再帰的な構文木",
+        ),
+        (
+            "fn parse_expr<I: Iterator<Item = Token>>(it: &mut Peekable<I>)",
+            "トークン列を受け取る再帰下降",
+        ),
+        (
+            "Some(Token { kind: TokenKind::RParen, .. })",
+            "構造体パターンで種類だけ調べる",
+        ),
+    ],
+    methods: &[
+        ("it.peek().copied()", "先読みした値をコピーして取り出す"),
+        ("text.parse::<f64>()", "文字列を数値に変換"),
+        ("opt.ok_or(err)?", "None をエラーにして早期リターン"),
+        ("res.map_err(|_| ParseError { .. })?", "エラーの型を変換"),
+    ],
+    gotchas: &[
+        "peek() は参照を返す。値が欲しいときは .copied() する",
+        "位置は文字単位で数える（バイト位置ではない）",
+        "エラーには位置を持たせないと、利用者が直せない",
+    ],
+};
+
+/// すべてのデモを実行
+pub fn run_all() {
+    println!("╔════════════════════════════════════════════════════════════════╗");
+    println!("║          Rust構文解析サンプル                                   ║");
+    println!("╚════════════════════════════════════════════════════════════════╝");
+
+    lesson_api::run_topic("parsing");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sym(s: &str) -> SExpr {
+        SExpr::Symbol(s.to_string())
+    }
+
+    #[test]
+    fn parses_nested_lists() {
+        let expr = parse("(a (b 1 -2.5) \"s\" true)").unwrap();
+        assert_eq!(
+            expr,
+            SExpr::List(vec![
+                sym("a"),
+                SExpr::List(vec![sym("b"), SExpr::Number(1.0), SExpr::Number(-2.5)]),
+                SExpr::Str("s".to_string()),
+                SExpr::Bool(true),
+            ])
+        );
+        assert_eq!(parse("(- 1)").unwrap().to_string(), "(- 1)");
+    }
+
+    #[test]
+    fn errors_carry_positions() {
+        let at = |line, column| Position { line, column };
+        let err = |source| parse(source).unwrap_err();
+
+        assert_eq!(err("(a\n  (b c)").kind, ParseErrorKind::UnclosedList);
+        assert_eq!(err("(a\n  (b c)").position, at(1, 1));
+        assert_eq!(err("(a))").position, at(1, 4));
+        assert_eq!(err("(a))").kind, ParseErrorKind::TrailingInput);
+        assert_eq!(err(")").kind, ParseErrorKind::UnexpectedCloseParen);
+        assert_eq!(err("(x\n \"abc)").position, at(2, 2));
+        assert_eq!(
+            err("(x 1a)").kind,
+            ParseErrorKind::InvalidNumber("1a".to_string())
+        );
+        assert_eq!(err("(x #)").kind, ParseErrorKind::UnexpectedChar('#'));
+        assert_eq!(err("  ").kind, ParseErrorKind::UnexpectedEof);
+    }
+}
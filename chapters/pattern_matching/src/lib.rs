@@ -4,6 +4,8 @@
 //                 https://doc.rust-lang.org/book/ch18-00-patterns.html
 // ============================================================================
 
+use lesson_api::{println, CheatSheet};
+
 /// match式の基本
 pub fn basic_match() {
     println!("\n=== match式の基本 ===");
@@ -287,17 +289,10 @@ pub fn destructuring_enums() {
 pub fn destructuring_references() {
     println!("\n=== 参照の分解 ===");
 
-    let points = vec![
-        (1, 2),
-        (3, 4),
-        (5, 6),
-    ];
+    let points = vec![(1, 2), (3, 4), (5, 6)];
 
     // &でパターンマッチすることで参照を外せる
-    let sum: i32 = points
-        .iter()
-        .map(|&(x, y)| x + y)
-        .sum();
+    let sum: i32 = points.iter().map(|&(x, y)| x + y).sum();
 
     println!("各点の座標の合計: {}", sum);
 }
@@ -395,25 +390,45 @@ pub fn at_bindings() {
     }
 }
 
+/// チートシート用のデータ（`cheatsheet pattern_matching` で表示）
+pub const CHEATSHEET: CheatSheet = CheatSheet {
+    syntax: &[
+        (
+            "match x { 1 => .., _ => .. }",
+            "match式（網羅的であること）",
+        ),
+        ("if let Some(v) = opt {}", "1つのパターンだけ扱う"),
+        (
+            "while let Some(top) = stack.pop() {}",
+            "パターンが一致する間ループ",
+        ),
+        ("let (a, b, c) = tuple;", "letでの分解"),
+        ("1 | 2 => ..", "複数パターン"),
+        ("1..=5 => ..", "範囲パターン"),
+        ("Point { x, y: 0 } => ..", "構造体の分解"),
+        ("Some(n) if n > 5 => ..", "マッチガード"),
+        ("id @ 3..=7 => ..", "@束縛"),
+        ("(first, .., last) => ..", "残りを無視"),
+    ],
+    methods: &[
+        ("matches!(x, Some(_))", "パターンに一致するかをboolで返す"),
+        (
+            "let Some(x) = opt else { return; };",
+            "let-else で早期リターン",
+        ),
+    ],
+    gotchas: &[
+        "matchは網羅的でなければコンパイルエラー",
+        "_ はバインドしない、_x はバインドする（所有権がムーブすることがある）",
+        "マッチガードの条件は | で並べたパターン全体にかかる",
+    ],
+};
+
 /// すべてのデモを実行
 pub fn run_all() {
     println!("╔════════════════════════════════════════════════════════════════╗");
     println!("║          Rustパターンマッチングサンプル                         ║");
     println!("╚════════════════════════════════════════════════════════════════╝");
 
-    basic_match();
-    patterns_that_bind();
-    matching_with_option();
-    exhaustiveness_and_catchall();
-    if_let_demo();
-    while_let_demo();
-    let_patterns();
-    function_parameter_patterns();
-    complex_patterns();
-    destructuring_structs();
-    destructuring_enums();
-    destructuring_references();
-    ignoring_values();
-    match_guards();
-    at_bindings();
+    lesson_api::run_topic("pattern_matching");
 }
@@ -0,0 +1,374 @@
+// ============================================================================
+// Rust プロセスと環境変数サンプル - std::env、std::process::Command
+// 公式ドキュメント: https://doc.rust-lang.org/std/env/index.html
+//                   https://doc.rust-lang.org/std/process/struct.Command.html
+// ============================================================================
+//
+// プログラムの外の世界とやり取りする入口は3つある。
+//   - env::args()     コマンドライン引数（先頭はプログラム自身のパス）
+//   - env::var(key)   環境変数（Result<String, VarError>）
+//   - Command         子プロセスを起動して、標準出力や終了ステータスを受け取る
+//
+// 子プロセスにはこのプログラム自身（env::current_exe()）を CHILD_FLAG 付きで起動する。
+// main.rs がフラグを見つけると child_main を呼び、引数に応じて動いて終了する。
+// どの OS でも同じ結果になるように、sh や cmd のような外部コマンドには頼らない。
+//
+// wasm32-unknown-unknown（ブラウザ版）には引数も環境変数も子プロセスも無い。
+// env::var は常に NotPresent、env::vars と Command は panic するので、説明だけを表示する。
+
+use lesson_api::{println, CheatSheet};
+use std::env::{self, VarError};
+use std::io::{self, Read, Write};
+use std::process::{self, Command, ExitStatus, Stdio};
+
+/// このプログラムを子プロセスとして起動するときのフラグ
+pub const CHILD_FLAG: &str = "--process-env-child";
+
+/// 子プロセスに渡す環境変数の名前
+const GREETING_VAR: &str = "GK_RUST_PRACTICE_GREETING";
+
+// wasm32-unknown-unknown（ブラウザ版）では子プロセスを起動できず、環境変数も書き換えられない
+fn processes_unavailable() -> bool {
+    let unavailable = cfg!(target_arch = "wasm32");
+    if unavailable {
+        println!("ブラウザ版ではプロセスと環境変数を扱えません。cargo run で試してください");
+    }
+    unavailable
+}
+
+// ----------------------------------------------------------------------------
+// コマンドライン引数
+// ----------------------------------------------------------------------------
+
+/// `--name=value` 形式の引数から value を探す
+pub fn flag_value<'a>(args: &'a [String], name: &str) -> Option<&'a str> {
+    args.iter().find_map(|arg| {
+        arg.strip_prefix("--")?
+            .strip_prefix(name)?
+            .strip_prefix('=')
+    })
+}
+
+/// env::args でコマンドライン引数を読む
+pub fn command_line_args() {
+    println!("\n=== env::args でコマンドライン引数を読む ===");
+
+    // 先頭はプログラム自身のパス。ブラウザ版では空になる
+    let args: Vec<String> = env::args().collect();
+    println!("引数の数: {}", args.len());
+    for (i, arg) in args.iter().enumerate() {
+        println!("  args[{}] = {:?}", i, arg);
+    }
+    match args.first() {
+        Some(program) => println!("プログラム名: {}", program),
+        None => println!("引数がありません（ブラウザ版など）"),
+    }
+
+    // 自分で解析する場合は skip(1) でプログラム名を飛ばす
+    let example: Vec<String> = ["app", "--name=Ferris", "--level=3", "input.txt"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    println!("\n例: {:?}", example);
+    println!("  --name  → {:?}", flag_value(&example[1..], "name"));
+    println!("  --level → {:?}", flag_value(&example[1..], "level"));
+    println!("  --color → {:?}", flag_value(&example[1..], "color"));
+    let positional: Vec<&String> = example
+        .iter()
+        .skip(1)
+        .filter(|arg| !arg.starts_with("--"))
+        .collect();
+    println!("  位置引数 → {:?}", positional);
+
+    // UTF-8 でない引数があると env::args() は panic する。args_os() は OsString で返す
+    println!(
+        "\nenv::args_os() の個数: {}（UTF-8 でない引数も扱える）",
+        env::args_os().count()
+    );
+    println!("本格的な解析には clap クレートが定番です");
+}
+
+// ----------------------------------------------------------------------------
+// 環境変数
+// ----------------------------------------------------------------------------
+
+/// env::var の結果を説明する文字列にする
+pub fn describe_var(key: &str) -> String {
+    match env::var(key) {
+        Ok(value) => format!("{} = {:?}", key, value),
+        Err(VarError::NotPresent) => format!("{} は設定されていません", key),
+        Err(VarError::NotUnicode(raw)) => format!("{} は UTF-8 ではありません: {:?}", key, raw),
+    }
+}
+
+/// 環境変数を読み、無ければ既定値を使う
+pub fn var_or(key: &str, default: &str) -> String {
+    env::var(key).unwrap_or_else(|_| default.to_string())
+}
+
+/// 環境変数を読む・設定する
+pub fn environment_variables() {
+    println!("\n=== 環境変数を読む・設定する ===");
+
+    for key in ["HOME", "LANG", "GK_RUST_PRACTICE_UNSET"] {
+        println!("{}", describe_var(key));
+    }
+    println!("var_or(\"EDITOR\", \"vi\") = {}", var_or("EDITOR", "vi"));
+
+    // var_os は Option<OsString>。UTF-8 かどうかを気にしないときに使う
+    println!(
+        "var_os(\"PATH\").is_some() = {}",
+        env::var_os("PATH").is_some()
+    );
+    if let Some(path) = env::var_os("PATH") {
+        // PATH の区切り（: や ;）は OS ごとに違うので split_paths に任せる
+        let dirs: Vec<_> = env::split_paths(&path).collect();
+        println!("PATH のディレクトリ数: {}", dirs.len());
+        if let Some(first) = dirs.first() {
+            println!("  先頭: {}", first.display());
+        }
+    }
+
+    if processes_unavailable() {
+        return;
+    }
+    // vars() はすべての環境変数を (String, String) で返す（ブラウザ版では panic する）
+    println!("env::vars() の個数: {}", env::vars().count());
+
+    // set_var / remove_var はプロセス全体の環境変数を書き換える。
+    // 別のスレッドが同時に環境変数を読む（Command::spawn や env::temp_dir も読む）と
+    // 未定義動作になりうるので、エディション 2024 では unsafe fn になった
+    println!("\nset_var / remove_var:");
+    println!("  {}", describe_var(GREETING_VAR));
+    println!("プロセス全体を書き換えると他のスレッドとぶつかるので、ここでは呼びません");
+    println!("子プロセスにだけ渡したいなら、次のデモの Command::env を使いましょう");
+}
+
+// ----------------------------------------------------------------------------
+// 子プロセス（このプログラム自身を CHILD_FLAG 付きで起動する）
+// ----------------------------------------------------------------------------
+
+/// 子プロセスとして起動されたときの処理（main.rs から呼ばれる）
+///
+/// - `echo 引数...`  引数と環境変数 GREETING_VAR を標準出力に書く
+/// - `upper`         標準入力を大文字にして標準出力に書く
+/// - `exit N`        標準エラーに書いて、終了コード N で終わる
+pub fn child_main(mut args: impl Iterator<Item = String>) -> ! {
+    // 親が読むのは本物の標準出力なので、lesson_api の println! ではなく直接書く
+    let mut out = io::stdout().lock();
+    let code = match args.next().as_deref() {
+        Some("echo") => {
+            let rest: Vec<String> = args.collect();
+            let _ = writeln!(out, "args: {}", rest.join(" "));
+            let _ = writeln!(out, "greeting: {}", var_or(GREETING_VAR, "(なし)"));
+            0
+        }
+        Some("upper") => {
+            let mut input = String::new();
+            let _ = io::stdin().read_to_string(&mut input);
+            let _ = write!(out, "{}", input.to_uppercase());
+            0
+        }
+        Some("exit") => {
+            let code = args.next().and_then(|n| n.parse().ok()).unwrap_or(1);
+            eprintln!("終了コード {} で終わります", code);
+            code
+        }
+        other => {
+            eprintln!("不明なモード: {:?}", other);
+            2
+        }
+    };
+    let _ = out.flush();
+    process::exit(code)
+}
+
+// このプログラム自身を子プロセスとして起動する Command を作る
+fn child_command(mode: &str) -> io::Result<Command> {
+    let mut command = Command::new(env::current_exe()?);
+    command.arg(CHILD_FLAG).arg(mode);
+    Ok(command)
+}
+
+/// 終了ステータスを説明する文字列にする
+pub fn describe_status(status: ExitStatus) -> String {
+    match status.code() {
+        Some(0) => "成功（終了コード 0）".to_string(),
+        Some(code) => format!("失敗（終了コード {}）", code),
+        // Unix でシグナルによって終了した場合は終了コードが無い
+        None => "シグナルで終了しました".to_string(),
+    }
+}
+
+/// Command で子プロセスを起動して標準出力を受け取る
+pub fn spawn_command() {
+    println!("\n=== Command で子プロセスを起動する ===");
+    if processes_unavailable() {
+        return;
+    }
+
+    // output() は子プロセスの終了を待ち、stdout と stderr をすべて集める
+    let result = child_command("echo").and_then(|mut command| {
+        command
+            .args(["hello", "from", "parent"])
+            .env(GREETING_VAR, "こんにちは") // この子プロセスにだけ設定する
+            .output()
+    });
+    match result {
+        Ok(output) => {
+            println!("status: {}", describe_status(output.status));
+            // stdout は Vec<u8>。文字列にするには from_utf8 か from_utf8_lossy
+            for line in String::from_utf8_lossy(&output.stdout).lines() {
+                println!("  stdout | {}", line);
+            }
+        }
+        Err(e) => println!("子プロセスを起動できませんでした: {}", e),
+    }
+    println!("親のプロセスでは: {}", describe_var(GREETING_VAR));
+
+    // 失敗した子プロセスも Ok(Output) になる。成否は status で調べる
+    println!("\n失敗する子プロセス:");
+    match child_command("exit").and_then(|mut command| command.arg("3").output()) {
+        Ok(output) => {
+            println!("status.success() = {}", output.status.success());
+            println!("status.code() = {:?}", output.status.code());
+            println!("{}", describe_status(output.status));
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            println!("  stderr | {}", stderr.trim_end());
+        }
+        Err(e) => println!("子プロセスを起動できませんでした: {}", e),
+    }
+
+    // 存在しないプログラムは起動の時点で Err になる
+    match Command::new("gk-rust-practice-no-such-program").output() {
+        Ok(_) => println!("（なぜか起動できました）"),
+        Err(e) => println!("\n存在しないプログラム: {:?} ({})", e.kind(), e),
+    }
+}
+
+/// パイプで子プロセスの標準入力に書き込む
+pub fn piped_stdin() {
+    println!("\n=== パイプで標準入力に書き込む ===");
+    if processes_unavailable() {
+        return;
+    }
+
+    let run = || -> io::Result<process::Output> {
+        // spawn() は終了を待たずに Child を返す。Stdio::piped() でパイプをつなぐ
+        let mut child = child_command("upper")?
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()?;
+        println!("子プロセスの ID: {}", child.id());
+
+        // take() で ChildStdin の所有権を取り出し、書き終えたら drop して EOF を送る
+        // （drop しないと子は入力の続きを待ち続け、wait_with_output が終わらない）
+        let mut stdin = child.stdin.take().expect("stdin は piped にしてある");
+        stdin.write_all("hello, process!\nrust は楽しい\n".as_bytes())?;
+        drop(stdin);
+
+        child.wait_with_output()
+    };
+    match run() {
+        Ok(output) => {
+            println!("status: {}", describe_status(output.status));
+            for line in String::from_utf8_lossy(&output.stdout).lines() {
+                println!("  stdout | {}", line);
+            }
+        }
+        Err(e) => println!("子プロセスとのやり取りに失敗しました: {}", e),
+    }
+}
+
+pub const CHEATSHEET: CheatSheet = CheatSheet {
+    syntax: &[
+        (
+            "let args: Vec<String> = env::args().collect();",
+            "コマンドライン引数（先頭はプログラム名）",
+        ),
+        ("env::var(\"KEY\")", "環境変数（Result<String, VarError>）"),
+        (
+            "Command::new(\"prog\").arg(\"x\").output()?",
+            "子プロセスを実行して出力を集める",
+        ),
+        (
+            "Command::new(\"prog\").stdin(Stdio::piped()).spawn()?",
+            "終了を待たずに起動してパイプをつなぐ",
+        ),
+    ],
+    methods: &[
+        (
+            "env::var_os(key) / env::args_os()",
+            "UTF-8 でなくても読める（OsString）",
+        ),
+        ("env::split_paths(&path)", "PATH を OS の区切りで分ける"),
+        (
+            "command.env(key, value)",
+            "子プロセスにだけ環境変数を設定する",
+        ),
+        (
+            "output.status.success() / .code()",
+            "成否と終了コード（シグナルなら None）",
+        ),
+        (
+            "String::from_utf8_lossy(&output.stdout)",
+            "出力のバイト列を文字列にする",
+        ),
+        (
+            "child.wait_with_output()",
+            "終了を待って stdout / stderr を受け取る",
+        ),
+    ],
+    gotchas: &[
+        "env::args() は UTF-8 でない引数で panic する。args_os() なら安全",
+        "set_var はプロセス全体に効き、エディション 2024 では unsafe",
+        "子プロセスが失敗しても output() は Ok。status.success() を確かめる",
+        "パイプの stdin を drop しないと子は EOF を受け取れず、待ち続ける",
+    ],
+};
+
+/// すべてのデモを実行
+pub fn run_all() {
+    println!("╔════════════════════════════════════════════════════════════════╗");
+    println!("║          Rust プロセスと環境変数サンプル                         ║");
+    println!("╚════════════════════════════════════════════════════════════════╝");
+
+    lesson_api::run_topic("process_env");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_flag_values() {
+        let args: Vec<String> = ["--name=Ferris", "--level=3", "--named=x", "input.txt"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(flag_value(&args, "name"), Some("Ferris"));
+        assert_eq!(flag_value(&args, "level"), Some("3"));
+        assert_eq!(flag_value(&args, "color"), None);
+        assert_eq!(flag_value(&args, "input.txt"), None);
+    }
+
+    #[test]
+    fn falls_back_when_a_variable_is_missing() {
+        let key = "GK_RUST_PRACTICE_SURELY_UNSET";
+        assert_eq!(var_or(key, "default"), "default");
+        assert_eq!(describe_var(key), format!("{} は設定されていません", key));
+    }
+
+    #[test]
+    fn describes_exit_statuses() {
+        let exe = env::current_exe().unwrap();
+        // テストのバイナリに存在しないテスト名を渡すと、何も実行せずに成功する
+        let status = Command::new(exe)
+            .arg("gk_rust_practice_no_such_test")
+            .stdout(Stdio::null())
+            .status()
+            .unwrap();
+        assert!(status.success());
+        assert_eq!(describe_status(status), "成功（終了コード 0）");
+    }
+}
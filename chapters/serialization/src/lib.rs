@@ -0,0 +1,396 @@
+// ============================================================================
+// Rust シリアライズサンプル - serde と serde_json（feature "serde"）
+// 公式ドキュメント: https://serde.rs/
+//                   https://docs.rs/serde_json/
+// ============================================================================
+//
+// 構造体を JSON などの形式に変換するのがシリアライズ、戻すのがデシリアライズ。
+// Rust では serde が定番で、#[derive(Serialize, Deserialize)] だけで変換のコードが生成される。
+//
+// 例には他の章の型と同じ形のものを使う。
+//   - User             structs_enums の章（名前付きフィールドの構造体）
+//   - Rectangle        structs_enums の章（メソッドのデモ）
+//   - SpreadsheetCell  collections の章（データを持つ列挙型）
+// 元の章では関数の中で定義しているので、ここでモジュールレベルに定義し直している。
+//
+// serde は optional な依存で、feature "serde" を有効にしたときだけ入る。
+//   cargo run --features serde
+// 無効なときは、derive が生成する変換を手で書いた版（ToJson）だけを表示する。
+// 型に derive を付けるかどうかは #[cfg_attr(feature = "serde", derive(...))] で切り替える。
+
+use lesson_api::{println, CheatSheet};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+// ----------------------------------------------------------------------------
+// 例に使う型
+// ----------------------------------------------------------------------------
+
+/// structs_enums の章の User と同じ形
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct User {
+    pub active: bool,
+    pub username: String,
+    pub email: String,
+    pub sign_in_count: u64,
+}
+
+/// structs_enums の章の Rectangle と同じ形
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Rectangle {
+    pub width: u32,
+    pub height: u32,
+}
+
+/// collections の章の SpreadsheetCell と同じ形
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SpreadsheetCell {
+    Int(i32),
+    Float(f64),
+    Text(String),
+}
+
+fn sample_user() -> User {
+    User {
+        active: true,
+        username: String::from("someusername123"),
+        email: String::from("someone@example.com"),
+        sign_in_count: 1,
+    }
+}
+
+fn sample_row() -> Vec<SpreadsheetCell> {
+    vec![
+        SpreadsheetCell::Int(3),
+        SpreadsheetCell::Text(String::from("blue")),
+        SpreadsheetCell::Float(10.12),
+    ]
+}
+
+// ----------------------------------------------------------------------------
+// derive が生成するものを手で書く
+// ----------------------------------------------------------------------------
+
+/// JSON の文字列にする（serde の Serialize を手で書いた小さな版）
+pub trait ToJson {
+    fn to_json(&self) -> String;
+}
+
+/// JSON の文字列リテラルにする（" と \ と制御文字をエスケープ）
+pub fn quote(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+impl ToJson for User {
+    fn to_json(&self) -> String {
+        // フィールドは宣言した順に並ぶ（derive も同じ）
+        format!(
+            "{{\"active\":{},\"username\":{},\"email\":{},\"sign_in_count\":{}}}",
+            self.active,
+            quote(&self.username),
+            quote(&self.email),
+            self.sign_in_count
+        )
+    }
+}
+
+impl ToJson for Rectangle {
+    fn to_json(&self) -> String {
+        format!("{{\"width\":{},\"height\":{}}}", self.width, self.height)
+    }
+}
+
+impl ToJson for SpreadsheetCell {
+    fn to_json(&self) -> String {
+        // serde の既定では列挙型は {"バリアント名": 中身} になる（externally tagged）
+        match self {
+            SpreadsheetCell::Int(n) => format!("{{\"Int\":{}}}", n),
+            // 整数になる f64 も 10.0 のように小数点を付ける（serde_json と同じ）
+            SpreadsheetCell::Float(x) if x.fract() == 0.0 => format!("{{\"Float\":{:.1}}}", x),
+            SpreadsheetCell::Float(x) => format!("{{\"Float\":{}}}", x),
+            SpreadsheetCell::Text(s) => format!("{{\"Text\":{}}}", quote(s)),
+        }
+    }
+}
+
+impl<T: ToJson> ToJson for Vec<T> {
+    fn to_json(&self) -> String {
+        let items: Vec<String> = self.iter().map(ToJson::to_json).collect();
+        format!("[{}]", items.join(","))
+    }
+}
+
+/// derive が生成する変換を手で書く
+pub fn manual_serialization() {
+    println!("\n=== derive が生成する変換を手で書く ===");
+
+    let user = sample_user();
+    println!("User: {:?}", user);
+    println!("  → {}", user.to_json());
+
+    let rect = Rectangle {
+        width: 30,
+        height: 50,
+    };
+    println!("Rectangle: {:?}", rect);
+    println!("  → {}", rect.to_json());
+
+    let row = sample_row();
+    println!("Vec<SpreadsheetCell>: {:?}", row);
+    println!("  → {}", row.to_json());
+
+    // 文字列はエスケープが必要。ここを忘れると壊れた JSON になる
+    println!("quote(\"say \\\"hi\\\"\\n\") = {}", quote("say \"hi\"\n"));
+
+    println!("\n型が増えるたびにこれを書き、フィールドを足すたびに直すのは大変です。");
+    println!("serde は #[derive(Serialize)] でこのコードを生成し、JSON 以外の形式にも使えます。");
+    if cfg!(feature = "serde") {
+        println!("（feature \"serde\" が有効です。次のデモで serde_json と比べます）");
+    } else {
+        println!("`cargo run --features serde` で serde を使う版のデモになります");
+    }
+}
+
+// ----------------------------------------------------------------------------
+// serde_json で往復する
+// ----------------------------------------------------------------------------
+
+/// serde_json で JSON に変換して戻す
+pub fn serde_round_trip() {
+    println!("\n=== serde_json で往復する ===");
+
+    #[cfg(feature = "serde")]
+    {
+        let user = sample_user();
+        let json = serde_json::to_string(&user).expect("User は必ず JSON にできる");
+        println!("to_string:  {}", json);
+        println!("手書きと同じ: {}", json == user.to_json());
+
+        // from_str は戻り値の型から、どの型に戻すかを決める
+        let back: User = serde_json::from_str(&json).expect("今作った JSON は読める");
+        println!("from_str:   {:?}", back);
+        println!("元と等しい: {}", back == user);
+
+        let rect = Rectangle {
+            width: 30,
+            height: 50,
+        };
+        let pretty = serde_json::to_string_pretty(&rect).expect("Rectangle は必ず JSON にできる");
+        println!("\nto_string_pretty:\n{}", pretty);
+
+        let row = sample_row();
+        let json = serde_json::to_string(&row).expect("Vec も JSON の配列になる");
+        println!("\nVec<SpreadsheetCell>: {}", json);
+        let back: Vec<SpreadsheetCell> = serde_json::from_str(&json).expect("読める");
+        println!("戻した行: {:?}", back);
+
+        // 型が決まっていない JSON は serde_json::Value で受け取る
+        let value: serde_json::Value = serde_json::from_str(&json).expect("読める");
+        println!("Value として 2 番目のセル: {}", value[1]["Text"]);
+    }
+    #[cfg(not(feature = "serde"))]
+    {
+        println!("feature \"serde\" が無効なので、serde と serde_json は入っていません。");
+        println!("`cargo run --features serde` で次のようなコードが動きます:");
+        println!("  let json = serde_json::to_string(&user)?;");
+        println!("  let back: User = serde_json::from_str(&json)?;");
+        println!("  assert_eq!(back, user);");
+    }
+}
+
+// ----------------------------------------------------------------------------
+// serde の属性とエラー
+// ----------------------------------------------------------------------------
+
+/// #[serde(...)] の属性とデシリアライズのエラー
+pub fn serde_attributes_and_errors() {
+    println!("\n=== #[serde(...)] の属性とエラー ===");
+
+    #[cfg(feature = "serde")]
+    {
+        // JSON の慣習（camelCase）に合わせる、省略されたら既定値、None なら書かない
+        #[derive(Debug, Serialize, Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Profile {
+            user_name: String,
+            #[serde(default)]
+            sign_in_count: u64,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            nickname: Option<String>,
+        }
+
+        // 列挙型を {"type": "..."} の形にする（internally tagged）
+        #[derive(Debug, Serialize, Deserialize)]
+        #[serde(tag = "type", rename_all = "lowercase")]
+        enum Shape {
+            Circle { radius: f64 },
+            Rect { width: u32, height: u32 },
+        }
+
+        let profile = Profile {
+            user_name: String::from("ferris"),
+            sign_in_count: 3,
+            nickname: None,
+        };
+        println!(
+            "rename_all + skip_serializing_if: {}",
+            to_json_or_error(&profile)
+        );
+
+        let parsed: Result<Profile, _> = serde_json::from_str(r#"{"userName":"crab"}"#);
+        println!("default（signInCount を省略）: {:?}", parsed);
+
+        let shapes = vec![
+            Shape::Circle { radius: 1.5 },
+            Shape::Rect {
+                width: 2,
+                height: 3,
+            },
+        ];
+        println!("tag = \"type\": {}", to_json_or_error(&shapes));
+
+        // エラーには行と列が付く
+        println!("\nデシリアライズのエラー:");
+        let inputs = [
+            r#"{"width": 30}"#,
+            r#"{"width": 30, "height": -1}"#,
+            "{\"width\": 30,\n \"height\": }",
+        ];
+        for input in inputs {
+            match serde_json::from_str::<Rectangle>(input) {
+                Ok(rect) => println!("  {:?}", rect),
+                Err(e) => println!(
+                    "  {:?} → {}（{}行 {}列、{:?}）",
+                    input,
+                    e,
+                    e.line(),
+                    e.column(),
+                    e.classify()
+                ),
+            }
+        }
+    }
+    #[cfg(not(feature = "serde"))]
+    {
+        println!(
+            "feature \"serde\" が無効です。`cargo run --features serde` で次の属性を試せます:"
+        );
+        println!("  #[serde(rename_all = \"camelCase\")]  フィールド名を JSON の慣習に合わせる");
+        println!("  #[serde(default)]                    省略されたら Default の値");
+        println!("  #[serde(skip_serializing_if = ...)]  条件を満たすときは書かない");
+        println!("  #[serde(tag = \"type\")]               列挙型を {{\"type\": ...}} の形にする");
+    }
+}
+
+#[cfg(feature = "serde")]
+fn to_json_or_error(value: &impl Serialize) -> String {
+    serde_json::to_string(value).unwrap_or_else(|e| format!("（エラー: {}）", e))
+}
+
+pub const CHEATSHEET: CheatSheet = CheatSheet {
+    syntax: &[
+        (
+            "#[derive(Serialize, Deserialize)]",
+            "変換のコードを生成する",
+        ),
+        (
+            "#[cfg_attr(feature = \"serde\", derive(Serialize))]",
+            "feature が有効なときだけ derive する",
+        ),
+        (
+            "#[serde(rename_all = \"camelCase\")]",
+            "フィールド名の書き方を変える",
+        ),
+        (
+            "#[serde(default)] / #[serde(skip_serializing_if = \"Option::is_none\")]",
+            "省略と既定値",
+        ),
+        ("#[serde(tag = \"type\")]", "列挙型を内部タグ付きにする"),
+    ],
+    methods: &[
+        (
+            "serde_json::to_string(&v)?",
+            "JSON の文字列にする（to_string_pretty で整形）",
+        ),
+        (
+            "let v: T = serde_json::from_str(s)?;",
+            "JSON から T に戻す（型は注釈で決まる）",
+        ),
+        (
+            "serde_json::Value",
+            "型の決まっていない JSON（v[\"key\"][0] で辿る）",
+        ),
+        ("e.line() / e.column()", "デシリアライズのエラーの位置"),
+    ],
+    gotchas: &[
+        "serde は optional な依存。`cargo run --features serde` で有効になる",
+        "列挙型は既定で {\"Variant\": 中身} の形。他の形は #[serde(tag)] や untagged",
+        "Option でないフィールドが JSON に無いとエラー。#[serde(default)] で既定値にできる",
+        "JSON の数値は f64 の範囲。大きな u64 や NaN は他の言語との間で壊れることがある",
+    ],
+};
+
+/// すべてのデモを実行
+pub fn run_all() {
+    println!("╔════════════════════════════════════════════════════════════════╗");
+    println!("║          Rust シリアライズサンプル                               ║");
+    println!("╚════════════════════════════════════════════════════════════════╝");
+
+    lesson_api::run_topic("serialization");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_json_by_hand() {
+        assert_eq!(
+            sample_user().to_json(),
+            r#"{"active":true,"username":"someusername123","email":"someone@example.com","sign_in_count":1}"#
+        );
+        assert_eq!(
+            sample_row().to_json(),
+            r#"[{"Int":3},{"Text":"blue"},{"Float":10.12}]"#
+        );
+        assert_eq!(SpreadsheetCell::Float(2.0).to_json(), r#"{"Float":2.0}"#);
+        assert_eq!(quote("a\"b\\c\n\u{1}"), r#""a\"b\\c\n\u0001""#);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_matches_the_hand_written_json_and_round_trips() {
+        let user = sample_user();
+        let json = serde_json::to_string(&user).unwrap();
+        assert_eq!(json, user.to_json());
+        assert_eq!(serde_json::from_str::<User>(&json).unwrap(), user);
+
+        let row = sample_row();
+        let json = serde_json::to_string(&row).unwrap();
+        assert_eq!(json, row.to_json());
+        assert_eq!(
+            serde_json::from_str::<Vec<SpreadsheetCell>>(&json).unwrap(),
+            row
+        );
+
+        let err = serde_json::from_str::<Rectangle>(r#"{"width": 30}"#).unwrap_err();
+        assert!(err.to_string().contains("missing field `height`"));
+    }
+}
@@ -0,0 +1,718 @@
+// ============================================================================
+// Rust スマートポインタサンプル
+// 公式ドキュメント: https://doc.rust-lang.org/book/ch15-00-smart-pointers.html
+// ============================================================================
+//
+// スマートポインタは、ポインタのようにふるまいつつ追加の情報や機能を持つ構造体。
+// Deref を実装すると * で中身を参照でき、Drop を実装するとスコープを抜けたときの
+// 後片付けを決められる。
+//
+// - Box<T>             値をヒープに置く。再帰的な型のサイズを決められる
+// - Deref / DerefMut   *x で中身を参照する（参照外し）
+// - 参照外し型強制       &MyBox<String> → &String → &str と自動で変換される
+// - メソッド呼び出し     x.len() は見つかるまで自動で参照外しする
+// - Drop               スコープを抜けたときの後片付け（宣言と逆の順に呼ばれる）
+// - Rc<T>              参照カウントで1つの値を複数の所有者が共有する
+// - RefCell<T>         不変参照しか無いところから中身を書き換える（借用規則は実行時に検査）
+
+use lesson_api::{println, CheatSheet};
+use std::cell::{Cell, RefCell};
+use std::mem;
+use std::ops::{Deref, DerefMut};
+use std::rc::{Rc, Weak};
+
+// ----------------------------------------------------------------------------
+// Box<T> と再帰的な型
+// ----------------------------------------------------------------------------
+
+/// コンスリスト（The Book 15.1 の例）
+/// Cons(i32, ConsList) と直接書くと、サイズが無限になってコンパイルできない
+#[derive(Debug)]
+pub enum ConsList {
+    Cons(i32, Box<ConsList>),
+    Nil,
+}
+
+impl ConsList {
+    /// スライスの値を順につないだリストを作る
+    pub fn from_slice(values: &[i32]) -> ConsList {
+        values.iter().rev().fold(ConsList::Nil, |tail, &value| {
+            ConsList::Cons(value, Box::new(tail))
+        })
+    }
+
+    pub fn sum(&self) -> i32 {
+        match self {
+            ConsList::Cons(value, next) => value + next.sum(),
+            ConsList::Nil => 0,
+        }
+    }
+}
+
+/// Box<T> と再帰的な型
+pub fn box_recursive_type() {
+    println!("\n=== Box<T> と再帰的な型 ===");
+
+    // Box::new で値はヒープに置かれ、変数にはポインタだけが入る
+    let b = Box::new(5);
+    println!("b = {}（中身はヒープ、b 自体はポインタ1つ分）", b);
+    println!(
+        "size_of::<i32>() = {}, size_of::<Box<i32>>() = {}",
+        mem::size_of::<i32>(),
+        mem::size_of::<Box<i32>>()
+    );
+
+    // 再帰的な型は、次の要素を Box に入れるとサイズが決まる
+    //   enum ConsList { Cons(i32, ConsList), Nil }  → error[E0072]: recursive type has infinite size
+    let list = ConsList::from_slice(&[1, 2, 3]);
+    println!("list = {:?}", list);
+    println!("合計 = {}", list.sum());
+    println!(
+        "size_of::<ConsList>() = {}（要素がいくつあっても同じ）",
+        mem::size_of::<ConsList>()
+    );
+
+    // 大きな値をムーブしてもコピーされるのはポインタだけ
+    let big = Box::new([0u8; 4096]);
+    let moved = big;
+    println!(
+        "4096 バイトの配列を Box でムーブ: 変数のサイズは {} バイト",
+        mem::size_of_val(&moved)
+    );
+}
+
+// ----------------------------------------------------------------------------
+// 自作のスマートポインタ
+// ----------------------------------------------------------------------------
+
+/// Box<T> をまねたタプル構造体（ただしヒープではなく、そのまま中に持つ）
+#[derive(Debug)]
+pub struct MyBox<T>(T);
+
+impl<T> MyBox<T> {
+    pub fn new(x: T) -> MyBox<T> {
+        MyBox(x)
+    }
+}
+
+// *my_box は *(my_box.deref()) に置き換えられる
+impl<T> Deref for MyBox<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+// *my_box = .. や、&mut MyBox<T> → &mut T の変換に使われる
+impl<T> DerefMut for MyBox<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+fn hello(name: &str) {
+    println!("Hello, {}!", name);
+}
+
+fn shout(text: &mut String) {
+    text.push('!');
+}
+
+/// Deref トレイトと * 演算子
+pub fn deref_trait() {
+    println!("\n=== Deref トレイトと * 演算子 ===");
+
+    // 普通の参照
+    let x = 5;
+    let y = &x;
+    println!("x = {}, *y = {}, x == *y: {}", x, *y, x == *y);
+
+    // Box<T> も参照と同じように * で中身を取り出せる
+    let boxed = Box::new(x);
+    println!("*Box::new(5) = {}", *boxed);
+
+    // MyBox は Deref を実装したので * が使える（実装しないと「型 MyBox<i32> は参照外しできない」エラー）
+    let my_box = MyBox::new(x);
+    println!("*MyBox::new(5) = {}", *my_box);
+
+    // *my_box の中身: deref() が参照を返し、その参照を * で外す
+    println!("*(my_box.deref()) = {}", *(my_box.deref()));
+
+    // deref が値ではなく参照を返すのは、中身を MyBox からムーブしないため
+    let mut counter = MyBox::new(0);
+    *counter += 10; // DerefMut の deref_mut が使われる
+    println!("DerefMut で書き換え: {:?}", counter);
+}
+
+/// 参照外し型強制
+pub fn deref_coercion() {
+    println!("\n=== 参照外し型強制（deref coercion） ===");
+
+    let name = MyBox::new(String::from("Rust"));
+
+    // hello は &str を受け取るが、&MyBox<String> をそのまま渡せる
+    //   &MyBox<String> → &String（MyBox の Deref）→ &str（String の Deref）
+    hello(&name);
+
+    // 型強制が無かったら、こう書くことになる
+    hello(&(*name)[..]);
+    println!("型強制なしの書き方: &(*name)[..]");
+
+    // &mut から &mut への型強制（DerefMut）
+    let mut message = MyBox::new(String::from("こんにちは"));
+    shout(&mut message);
+    println!("&mut MyBox<String> → &mut String: {}", *message);
+
+    // &mut から & への型強制もできる（逆の & → &mut はできない）
+    // 型強制はコンパイル時に決まり、deref の呼び出しはインライン化されるので実行時のコストは無い
+    println!("型強制は関数の引数・let の型注釈・メソッドの self で起きる");
+}
+
+/// メソッド呼び出しの自動参照外し
+pub fn auto_deref_methods() {
+    println!("\n=== メソッド呼び出しの自動参照外し ===");
+
+    let text = MyBox::new(String::from("hello, deref"));
+
+    // MyBox にも String にも len は無い。str::len が見つかるまで参照外しを繰り返す
+    //   MyBox<String> → String → str
+    println!("text.len() = {}", text.len());
+    println!("text.to_uppercase() = {}", text.to_uppercase());
+    println!("text.contains(\"deref\") = {}", text.contains("deref"));
+
+    // MyBox 自身のメソッドが優先され、無ければ中身のメソッドを探す
+    // 何重に包んでも同じように呼べる
+    let nested = MyBox::new(MyBox::new(vec![3, 1, 2]));
+    println!("MyBox<MyBox<Vec<i32>>> の len() = {}", nested.len());
+    println!("iter().max() = {:?}", nested.iter().max());
+
+    // 比較演算子は自動では参照外ししないので、* で中身どうしを比べる
+    let a = MyBox::new(1);
+    let b = MyBox::new(1);
+    println!(
+        "*a == *b: {}（a == b は MyBox に PartialEq が無いのでエラー）",
+        *a == *b
+    );
+}
+
+// ----------------------------------------------------------------------------
+// Drop
+// ----------------------------------------------------------------------------
+
+/// スコープを抜けると名前を表示するスマートポインタ（The Book 15.3 の例）
+struct CustomSmartPointer {
+    data: String,
+}
+
+impl Drop for CustomSmartPointer {
+    fn drop(&mut self) {
+        println!("  drop: {}", self.data);
+    }
+}
+
+/// Drop の順序
+pub fn drop_order() {
+    println!("\n=== Drop の順序 ===");
+
+    {
+        let _c = CustomSmartPointer {
+            data: String::from("c（先に作成）"),
+        };
+        let _d = CustomSmartPointer {
+            data: String::from("d（後に作成）"),
+        };
+        println!("c と d を作成。スコープを抜けると、作ったのと逆の順に drop される:");
+    }
+
+    // 構造体のフィールドは宣言した順、配列や Vec の要素は先頭から drop される
+    {
+        let _items = [
+            CustomSmartPointer {
+                data: String::from("要素 0"),
+            },
+            CustomSmartPointer {
+                data: String::from("要素 1"),
+            },
+        ];
+        println!("配列をスコープから出す:");
+    }
+
+    // drop メソッドは直接呼べない（error[E0040]）。早く片付けたいときは std::mem::drop に渡す
+    let early = CustomSmartPointer {
+        data: String::from("early"),
+    };
+    println!("mem::drop(early) を呼ぶ:");
+    drop(early);
+    println!("関数の終わりより前に片付けられた");
+
+    // _ に束縛した値は、その場で drop される（_x のような名前なら残る）
+    println!("let _ = ... は、その場で drop される:");
+    let _ = CustomSmartPointer {
+        data: String::from("_ に束縛"),
+    };
+    println!("関数の終わり");
+}
+
+// ----------------------------------------------------------------------------
+// Rc<T> と RefCell<T>
+// ----------------------------------------------------------------------------
+
+/// Rc<T> で共有するリスト（The Book 15.4 の例）
+enum SharedList {
+    Cons(i32, Rc<SharedList>),
+    Nil,
+}
+
+impl SharedList {
+    fn values(&self) -> Vec<i32> {
+        let mut values = Vec::new();
+        let mut current = self;
+        while let SharedList::Cons(value, next) = current {
+            values.push(*value);
+            current = next;
+        }
+        values
+    }
+}
+
+/// Rc<T> の参照カウント
+pub fn rc_counting() {
+    println!("\n=== Rc<T> の参照カウント ===");
+
+    // b と c が a を共有する:  b → 3 ┐
+    //                              ├→ a (5 → 10 → Nil)
+    //                          c → 4 ┘
+    // Box だと a は b にムーブされて、c から使えない（error[E0382]）
+    let a = Rc::new(SharedList::Cons(
+        5,
+        Rc::new(SharedList::Cons(10, Rc::new(SharedList::Nil))),
+    ));
+    println!("a を作成: strong_count = {}", Rc::strong_count(&a));
+
+    // Rc::clone は中身を複製せず、カウントを1増やすだけ
+    let b = SharedList::Cons(3, Rc::clone(&a));
+    println!("b を作成: strong_count = {}", Rc::strong_count(&a));
+    {
+        let c = SharedList::Cons(4, Rc::clone(&a));
+        println!("c を作成: strong_count = {}", Rc::strong_count(&a));
+        println!("  c = {:?}", c.values());
+    }
+    println!(
+        "c がスコープを抜けた: strong_count = {}",
+        Rc::strong_count(&a)
+    );
+    println!("  b = {:?}", b.values());
+
+    // カウントが 0 になったときに中身が drop される
+    // Rc が貸すのは不変参照だけ。書き換えるには RefCell と組み合わせる（次のデモ）
+    println!("Rc はシングルスレッド専用。スレッド間で共有するなら Arc");
+}
+
+/// メッセージを送る相手（テストではモックに差し替える）
+pub trait Messenger {
+    fn send(&self, msg: &str);
+}
+
+/// 使用量が上限に近づいたら知らせる（The Book 15.5 の例）
+pub struct LimitTracker<'a, T: Messenger> {
+    messenger: &'a T,
+    value: usize,
+    max: usize,
+}
+
+impl<'a, T: Messenger> LimitTracker<'a, T> {
+    pub fn new(messenger: &'a T, max: usize) -> LimitTracker<'a, T> {
+        LimitTracker {
+            messenger,
+            value: 0,
+            max,
+        }
+    }
+
+    pub fn set_value(&mut self, value: usize) {
+        self.value = value;
+        let percentage = self.value * 100 / self.max;
+        if percentage >= 100 {
+            self.messenger.send("上限を超えました");
+        } else if percentage >= 90 {
+            self.messenger.send("警告: 上限の 90% を超えました");
+        } else if percentage >= 75 {
+            self.messenger.send("注意: 上限の 75% を超えました");
+        }
+    }
+}
+
+/// 送ったメッセージを覚えておくモック
+/// send は &self しか受け取らないので、Vec を RefCell に入れて書き換える
+pub struct MockMessenger {
+    pub sent_messages: RefCell<Vec<String>>,
+}
+
+impl MockMessenger {
+    pub fn new() -> MockMessenger {
+        MockMessenger {
+            sent_messages: RefCell::new(Vec::new()),
+        }
+    }
+}
+
+impl Default for MockMessenger {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Messenger for MockMessenger {
+    fn send(&self, msg: &str) {
+        self.sent_messages.borrow_mut().push(String::from(msg));
+    }
+}
+
+/// RefCell<T> と内部可変性
+pub fn refcell_interior_mutability() {
+    println!("\n=== RefCell<T> と内部可変性 ===");
+
+    // &self のメソッドの中から、RefCell の中身を書き換える
+    let messenger = MockMessenger::new();
+    let mut tracker = LimitTracker::new(&messenger, 100);
+    for value in [50, 80, 95, 120] {
+        tracker.set_value(value);
+    }
+    println!("送られたメッセージ: {:?}", messenger.sent_messages.borrow());
+
+    // 借用規則（可変参照は1つだけ）はコンパイル時ではなく実行時に検査される
+    let cell = RefCell::new(vec![1, 2, 3]);
+    {
+        let first = cell.borrow_mut();
+        // 同時に2つ目の borrow_mut() を呼ぶと panic する（already borrowed: BorrowMutError）
+        // try_borrow_mut なら panic せずに Err が返る
+        println!(
+            "borrow_mut 中の try_borrow_mut: {}",
+            if cell.try_borrow_mut().is_err() {
+                "Err（借用中）"
+            } else {
+                "Ok"
+            }
+        );
+        println!("  借用中の値: {:?}", *first);
+    }
+    cell.borrow_mut().push(4);
+    println!("借用を返した後の borrow_mut().push(4): {:?}", cell.borrow());
+
+    // Rc<RefCell<T>>: 複数の所有者が、同じ値を書き換えられる
+    let shared = Rc::new(RefCell::new(0));
+    let owners: Vec<Rc<RefCell<i32>>> = (0..3).map(|_| Rc::clone(&shared)).collect();
+    for (i, owner) in owners.iter().enumerate() {
+        *owner.borrow_mut() += 10 * (i as i32 + 1);
+    }
+    println!(
+        "Rc<RefCell<i32>> を3つの所有者が書き換えた: {}（strong_count = {}）",
+        shared.borrow(),
+        Rc::strong_count(&shared)
+    );
+    println!("Cell<T> は Copy な値を get/set で入れ替える（参照を貸さないので実行時の検査も無い）");
+}
+
+// ----------------------------------------------------------------------------
+// Rc と Weak の木、循環参照
+// ----------------------------------------------------------------------------
+
+thread_local! {
+    // Drop が呼ばれた回数（リークしたかどうかを確かめる）
+    static DROPPED: Cell<usize> = const { Cell::new(0) };
+}
+
+fn dropped() -> usize {
+    DROPPED.with(Cell::get)
+}
+
+/// 親を Weak、子を Rc で持つ木のノード（The Book 15.6 の例）
+#[derive(Debug)]
+pub struct TreeNode {
+    pub value: i32,
+    /// 親は子を所有しない（Weak）。あとから設定するので RefCell
+    pub parent: RefCell<Weak<TreeNode>>,
+    /// 子は親が所有する（Rc）
+    pub children: RefCell<Vec<Rc<TreeNode>>>,
+}
+
+impl TreeNode {
+    pub fn new(value: i32) -> Rc<TreeNode> {
+        Rc::new(TreeNode {
+            value,
+            parent: RefCell::new(Weak::new()),
+            children: RefCell::new(Vec::new()),
+        })
+    }
+}
+
+impl Drop for TreeNode {
+    fn drop(&mut self) {
+        DROPPED.with(|n| n.set(n.get() + 1));
+    }
+}
+
+fn counts<T>(name: &str, rc: &Rc<T>) {
+    println!(
+        "  {:<6} strong = {}, weak = {}",
+        name,
+        Rc::strong_count(rc),
+        Rc::weak_count(rc)
+    );
+}
+
+/// Weak で親を指す木
+pub fn weak_parent_tree() {
+    println!("\n=== Weak で親を指す木 ===");
+
+    let before = dropped();
+    let leaf = TreeNode::new(3);
+    println!("leaf を作成:");
+    counts("leaf", &leaf);
+    println!(
+        "  leaf の親: {:?}",
+        leaf.parent.borrow().upgrade().map(|p| p.value)
+    );
+
+    {
+        let branch = TreeNode::new(5);
+        // branch が leaf を所有する（strong +1）
+        branch.children.borrow_mut().push(Rc::clone(&leaf));
+        // leaf は branch を Weak で指す（weak +1、所有はしない）
+        *leaf.parent.borrow_mut() = Rc::downgrade(&branch);
+
+        println!("branch を作成して親子をつないだ:");
+        counts("branch", &branch);
+        counts("leaf", &leaf);
+        // Weak は upgrade() で Option<Rc<T>> にしてから使う（相手がもういないかもしれない）
+        println!(
+            "  leaf の親: {:?}",
+            leaf.parent.borrow().upgrade().map(|p| p.value)
+        );
+    }
+
+    // branch の strong が 0 になったので、weak が残っていても branch は解放される
+    println!("branch のスコープを抜けた:");
+    counts("leaf", &leaf);
+    println!(
+        "  leaf の親: {:?}（upgrade が None）",
+        leaf.parent.borrow().upgrade().map(|p| p.value)
+    );
+    drop(leaf);
+    println!(
+        "解放されたノード: {} 個（branch と leaf）",
+        dropped() - before
+    );
+}
+
+/// 循環参照のリスト（The Book 15.6 の例）
+#[derive(Debug)]
+enum List {
+    Cons(i32, RefCell<Rc<List>>),
+    Nil,
+}
+
+impl List {
+    fn head(&self) -> Option<i32> {
+        match self {
+            List::Cons(value, _) => Some(*value),
+            List::Nil => None,
+        }
+    }
+
+    fn tail(&self) -> Option<&RefCell<Rc<List>>> {
+        match self {
+            List::Cons(_, item) => Some(item),
+            List::Nil => None,
+        }
+    }
+}
+
+impl Drop for List {
+    fn drop(&mut self) {
+        DROPPED.with(|n| n.set(n.get() + 1));
+    }
+}
+
+/// 循環参照でメモリがリークする
+pub fn reference_cycle_leak() {
+    println!("\n=== 循環参照でメモリがリークする ===");
+
+    let before = dropped();
+    {
+        let a = Rc::new(List::Cons(5, RefCell::new(Rc::new(List::Nil))));
+        let b = Rc::new(List::Cons(10, RefCell::new(Rc::clone(&a))));
+        println!("a → Nil、b → a:");
+        counts("a", &a);
+        counts("b", &b);
+
+        // a の次を b にすると a → b → a の輪になる
+        if let Some(link) = a.tail() {
+            *link.borrow_mut() = Rc::clone(&b);
+        }
+        println!("a の次を b にした（a → b → a）:");
+        counts("a", &a);
+        counts("b", &b);
+        let mut values = Vec::new();
+        let mut current = Rc::clone(&a);
+        for _ in 0..5 {
+            values.extend(current.head());
+            let next = current.tail().map(|link| Rc::clone(&link.borrow()));
+            match next {
+                Some(next) => current = next,
+                None => break,
+            }
+        }
+        println!("  a からたどると: {:?} …（終わらない）", values);
+        // ここで println!("{:?}", a) とすると、輪をたどり続けてスタックオーバーフローする
+    }
+    // 変数 a と b が消えても、輪の中の Rc がお互いの strong を 1 に保つ
+    println!(
+        "スコープを抜けた後に解放された List: {} 個（Nil の1個だけ。a と b はリーク）",
+        dropped() - before
+    );
+
+    // 同じ形を Weak で作ると、輪が所有の輪にならないので解放される
+    let before = dropped();
+    {
+        let parent = TreeNode::new(1);
+        let child = TreeNode::new(2);
+        parent.children.borrow_mut().push(Rc::clone(&child));
+        *child.parent.borrow_mut() = Rc::downgrade(&parent);
+        println!("親 → 子を Rc、子 → 親を Weak でつないだ:");
+        counts("parent", &parent);
+        counts("child", &child);
+    }
+    println!(
+        "Weak で戻る向きを作った場合に解放された: {} 個（両方）",
+        dropped() - before
+    );
+    println!("Rc は参照カウントなので、所有の輪は自動では回収されない（GC ではない）");
+    println!("「所有する向き」は Rc、「戻る向き」は Weak にする");
+}
+
+/// チートシート用のデータ（`cheatsheet smart_pointers` で表示）
+pub const CHEATSHEET: CheatSheet = CheatSheet {
+    syntax: &[
+        (
+            "impl<T> Deref for MyBox<T> { type Target = T; .. }",
+            "* で中身を参照できるようにする",
+        ),
+        (
+            "fn deref(&self) -> &T { &self.0 }",
+            "中身への参照を返す（ムーブしない）",
+        ),
+        (
+            "impl<T> DerefMut for MyBox<T>",
+            "*x = .. や &mut の型強制を可能にする",
+        ),
+        ("*x  ==  *(x.deref())", "* 演算子の展開"),
+        (
+            "parent: RefCell<Weak<Node>>",
+            "親は所有せずに指す（循環参照を避ける）",
+        ),
+        (
+            "enum List { Cons(i32, Box<List>), Nil }",
+            "再帰的な型は Box で包んでサイズを決める",
+        ),
+        (
+            "impl Drop for T { fn drop(&mut self) { .. } }",
+            "スコープを抜けたときの後片付け",
+        ),
+        ("Rc<RefCell<T>>", "複数の所有者が同じ値を書き換える"),
+    ],
+    methods: &[
+        (
+            "hello(&my_box)",
+            "&MyBox<String> → &String → &str（参照外し型強制）",
+        ),
+        ("&(*my_box)[..]", "型強制が無い場合の書き方"),
+        ("my_box.len()", "メソッドが見つかるまで自動で参照外しする"),
+        (
+            "Rc::downgrade(&rc) / weak.upgrade()",
+            "Weak を作る / Option<Rc<T>> に戻す",
+        ),
+        ("Rc::strong_count(&rc) / weak_count", "参照カウントを調べる"),
+        (
+            "Rc::clone(&rc)",
+            "カウントを増やして共有する（中身は複製しない）",
+        ),
+        ("drop(x)", "std::mem::drop で早めに片付ける"),
+        (
+            "cell.borrow() / borrow_mut()",
+            "RefCell の中身を借りる（規則違反は実行時に panic）",
+        ),
+        ("cell.try_borrow_mut()", "panic せずに Result で借りる"),
+    ],
+    gotchas: &[
+        "Deref はスマートポインタのためのもの。継承の代わりに使わない",
+        "& → &mut の型強制はできない（&mut → & はできる）",
+        "== などの演算子は自動で参照外ししない",
+        "Rc の循環参照はリークする。戻る向きは Weak にする",
+        "x.drop() は直接呼べない（E0040）。drop(x) を使う",
+        "Rc / RefCell はシングルスレッド専用。スレッド間では Arc / Mutex",
+        "RefCell の借用違反はコンパイルを通り、実行時に panic する",
+    ],
+};
+
+/// すべてのデモを実行
+pub fn run_all() {
+    println!("╔════════════════════════════════════════════════════════════════╗");
+    println!("║          Rust スマートポインタサンプル                           ║");
+    println!("╚════════════════════════════════════════════════════════════════╝");
+
+    lesson_api::run_topic("smart_pointers");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn my_box_derefs_like_a_reference() {
+        let mut boxed = MyBox::new(String::from("ab"));
+        assert_eq!(*boxed, "ab");
+        assert_eq!(boxed.len(), 2);
+        boxed.push('c');
+        *boxed += "d";
+        let as_str: &str = &boxed;
+        assert_eq!(as_str, "abcd");
+    }
+
+    #[test]
+    fn box_list_and_limit_tracker() {
+        let list = ConsList::from_slice(&[1, 2, 3, 4]);
+        assert_eq!(list.sum(), 10);
+        assert!(matches!(ConsList::from_slice(&[]), ConsList::Nil));
+
+        let messenger = MockMessenger::new();
+        let mut tracker = LimitTracker::new(&messenger, 100);
+        tracker.set_value(10);
+        assert!(messenger.sent_messages.borrow().is_empty());
+        tracker.set_value(80);
+        tracker.set_value(100);
+        assert_eq!(
+            *messenger.sent_messages.borrow(),
+            ["注意: 上限の 75% を超えました", "上限を超えました"]
+        );
+    }
+
+    #[test]
+    fn weak_parent_does_not_keep_the_tree_alive() {
+        let before = dropped();
+        let leaf = TreeNode::new(3);
+        {
+            let branch = TreeNode::new(5);
+            branch.children.borrow_mut().push(Rc::clone(&leaf));
+            *leaf.parent.borrow_mut() = Rc::downgrade(&branch);
+            assert_eq!(leaf.parent.borrow().upgrade().map(|p| p.value), Some(5));
+            assert_eq!((Rc::strong_count(&branch), Rc::weak_count(&branch)), (1, 1));
+            assert_eq!(Rc::strong_count(&leaf), 2);
+        }
+        assert!(leaf.parent.borrow().upgrade().is_none());
+        assert_eq!(Rc::strong_count(&leaf), 1);
+        drop(leaf);
+        assert_eq!(dropped() - before, 2);
+    }
+}
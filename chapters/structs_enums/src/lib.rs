@@ -4,6 +4,8 @@
 //                 https://doc.rust-lang.org/book/ch06-00-enums.html
 // ============================================================================
 
+use lesson_api::{println, CheatSheet};
+
 /// 基本的な構造体の定義と使用
 pub fn basic_structs() {
     println!("\n=== 基本的な構造体 ===");
@@ -48,10 +50,7 @@ pub fn basic_structs() {
         }
     }
 
-    let user3 = build_user(
-        String::from("test@example.com"),
-        String::from("testuser"),
-    );
+    let user3 = build_user(String::from("test@example.com"), String::from("testuser"));
     println!("build_userで作成: {:?}", user3);
 
     // 構造体更新記法
@@ -196,8 +195,8 @@ pub fn enums_with_data() {
     // 各バリアントが異なるデータを持てる
     #[derive(Debug)]
     enum IpAddr {
-        V4(u8, u8, u8, u8),  // タプル形式
-        V6(String),          // 単一の値
+        V4(u8, u8, u8, u8), // タプル形式
+        V6(String),         // 単一の値
     }
 
     let home = IpAddr::V4(127, 0, 0, 1);
@@ -346,19 +345,42 @@ pub fn derive_macros() {
     println!("d1 = {:?}, d2 = {:?}", d1, d2);
 }
 
+/// チートシート用のデータ（`cheatsheet structs_enums` で表示）
+pub const CHEATSHEET: CheatSheet = CheatSheet {
+    syntax: &[
+        ("struct User { name: String }", "名前付きフィールドの構造体"),
+        ("struct Color(i32, i32, i32);", "タプル構造体"),
+        ("struct AlwaysEqual;", "ユニット様構造体"),
+        (
+            "User { email, ..user1 }",
+            "フィールド初期化省略と構造体更新記法",
+        ),
+        ("impl Rect { fn area(&self) -> u32 }", "メソッド"),
+        ("fn new(w: u32) -> Self", "関連関数（Rect::new で呼ぶ）"),
+        (
+            "enum Message { Quit, Move { x: i32 } }",
+            "データを持つ列挙型",
+        ),
+        ("#[derive(Debug, Clone, PartialEq)]", "トレイトの自動実装"),
+    ],
+    methods: &[
+        ("opt.unwrap_or(0)", "Noneならデフォルト値"),
+        ("opt.is_some() / opt.is_none()", "Optionの判定"),
+        ("res.is_ok() / res.is_err()", "Resultの判定"),
+        ("{:?} / {:#?}", "Debug表示（整形あり）"),
+    ],
+    gotchas: &[
+        "構造体更新記法でStringフィールドをムーブすると元のインスタンスは部分的に無効になる",
+        "Option<T>とTは別の型なので直接演算できない",
+        "一部のフィールドだけを可変にはできない（インスタンス全体がmut）",
+    ],
+};
+
 /// すべてのデモを実行
 pub fn run_all() {
     println!("╔════════════════════════════════════════════════════════════════╗");
     println!("║          Rust構造体と列挙型サンプル                             ║");
     println!("╚════════════════════════════════════════════════════════════════╝");
 
-    basic_structs();
-    tuple_structs();
-    unit_like_structs();
-    methods();
-    basic_enums();
-    enums_with_data();
-    option_enum();
-    result_enum();
-    derive_macros();
+    lesson_api::run_topic("structs_enums");
 }
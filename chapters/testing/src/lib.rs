@@ -0,0 +1,408 @@
+// ============================================================================
+// Rust テストの書き方サンプル
+// 公式ドキュメント: https://doc.rust-lang.org/book/ch11-00-testing.html
+// ============================================================================
+//
+// #[test] を付けた関数が cargo test で実行される。panic したら失敗、しなければ成功。
+//
+// - assert! / assert_eq! / assert_ne!   条件や値を確かめる（失敗すると panic）
+// - #[should_panic(expected = "..")]    panic することを確かめる
+// - Result を返すテスト                  Err を返したら失敗（? が使える）
+// - #[ignore]                           時間のかかるテストを普段は飛ばす
+// - 単体テスト                           このファイルの #[cfg(test)] mod tests（非公開の関数も試せる）
+// - 結合テスト                           tests/ の下のファイル（公開された API だけを使う）
+//
+// メニューのデモでは、テストの中で使うのと同じアサーションをその場で実行して、
+// 成功したときと失敗したときに何が表示されるかを見せる。本物のテストは
+//   cargo test -p testing
+// で実行する。
+
+use lesson_api::panics::{self, panics_are_fatal};
+use lesson_api::{println, CheatSheet};
+
+// ----------------------------------------------------------------------------
+// テストされるコード
+// ----------------------------------------------------------------------------
+
+pub fn add_two(a: i32) -> i32 {
+    internal_adder(a, 2)
+}
+
+// 非公開の関数も、同じファイルの tests モジュールからは use super::* で試せる
+fn internal_adder(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Rectangle {
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Rectangle {
+    pub fn can_hold(&self, other: &Rectangle) -> bool {
+        self.width > other.width && self.height > other.height
+    }
+}
+
+pub fn greeting(name: &str) -> String {
+    format!("Hello {}!", name)
+}
+
+/// 1 から 100 までの数だけを持てる型（範囲外なら new が panic する）
+#[derive(Debug)]
+pub struct Guess {
+    value: i32,
+}
+
+impl Guess {
+    pub fn new(value: i32) -> Guess {
+        if value < 1 {
+            panic!(
+                "Guess value must be greater than or equal to 1, got {}.",
+                value
+            );
+        } else if value > 100 {
+            panic!(
+                "Guess value must be less than or equal to 100, got {}.",
+                value
+            );
+        }
+        Guess { value }
+    }
+
+    pub fn value(&self) -> i32 {
+        self.value
+    }
+}
+
+/// "key=value" を読む（Result を返すテストの例に使う）
+pub fn parse_setting(line: &str) -> Result<(String, u32), String> {
+    let (key, value) = line
+        .split_once('=')
+        .ok_or_else(|| format!("= がありません: {}", line))?;
+    let value = value
+        .trim()
+        .parse()
+        .map_err(|e| format!("数ではありません: {} ({})", value.trim(), e))?;
+    Ok((key.trim().to_string(), value))
+}
+
+// ----------------------------------------------------------------------------
+// アサーションをその場で実行する
+// ----------------------------------------------------------------------------
+
+/// 1つのチェックの結果（テストランナーの ok / FAILED にあたる）
+#[derive(Debug, PartialEq, Eq)]
+pub enum CheckResult {
+    Ok,
+    /// panic した。テストランナーが表示するのと同じメッセージ
+    Failed(String),
+}
+
+/// f を実行して、panic したらそのメッセージを返す
+/// パニックフックの出力は lesson_api::panics が黙らせる（このスレッドの panic だけ）
+pub fn run_check(f: impl FnOnce()) -> CheckResult {
+    match panics::catch_silently(f) {
+        Ok(()) => CheckResult::Ok,
+        Err(message) => CheckResult::Failed(message),
+    }
+}
+
+// cargo test の出力に似せて表示する
+fn report(name: &str, f: impl FnOnce()) {
+    match run_check(f) {
+        CheckResult::Ok => println!("test {} ... ok", name),
+        CheckResult::Failed(message) => {
+            println!("test {} ... FAILED", name);
+            for line in message.lines() {
+                println!("    {}", line);
+            }
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+// デモ
+// ----------------------------------------------------------------------------
+
+/// assert! / assert_eq! / assert_ne!
+pub fn assertion_macros() {
+    println!("\n=== assert! / assert_eq! / assert_ne! ===");
+
+    let larger = Rectangle {
+        width: 8,
+        height: 7,
+    };
+    let smaller = Rectangle {
+        width: 5,
+        height: 1,
+    };
+
+    println!("成功するアサーション:");
+    report("larger_can_hold_smaller", || {
+        assert!(larger.can_hold(&smaller))
+    });
+    report("it_adds_two", || assert_eq!(add_two(2), 4));
+    report("it_is_not_five", || assert_ne!(add_two(2), 5));
+
+    if panics_are_fatal() {
+        return;
+    }
+
+    // 失敗すると panic し、left と right の値が表示される（だから Debug が必要）
+    println!("\n失敗するアサーション（わざと間違えた期待値）:");
+    report("smaller_cannot_hold_larger", || {
+        assert!(smaller.can_hold(&larger))
+    });
+    report("it_adds_three", || assert_eq!(add_two(2), 5));
+
+    // 独自のメッセージを付けると、何を確かめていたかが分かる
+    let result = greeting("Carol");
+    report("greeting_contains_name", || {
+        assert!(
+            result.contains("Alice"),
+            "greeting が名前を含んでいない。値は `{}`",
+            result
+        )
+    });
+    println!("\nassert_eq! の引数の順序に決まりは無い（left / right と表示される）");
+}
+
+/// #[should_panic] と Result を返すテスト
+pub fn should_panic_and_result() {
+    println!("\n=== #[should_panic] と Result を返すテスト ===");
+
+    // #[should_panic(expected = "less than or equal to 100")] のテストは、
+    // panic して、そのメッセージに expected が含まれていれば成功になる
+    println!("Guess::new(50).value() = {}", Guess::new(50).value());
+    if !panics_are_fatal() {
+        let expected = "less than or equal to 100";
+        match run_check(|| {
+            Guess::new(200);
+        }) {
+            CheckResult::Failed(message) => println!(
+                "Guess::new(200) は panic した: {}\n  expected を含むか: {}（should_panic のテストは成功）",
+                message,
+                message.contains(expected)
+            ),
+            CheckResult::Ok => println!("Guess::new(200) が panic しなかった（テストは失敗）"),
+        }
+    }
+
+    // Result<(), E> を返すテストでは ? が使える。Err を返したら失敗
+    //   #[test]
+    //   fn parses_settings() -> Result<(), String> {
+    //       assert_eq!(parse_setting("port = 8080")?, ("port".to_string(), 8080));
+    //       Ok(())
+    //   }
+    for line in ["port = 8080", "port: 8080", "port = eighty"] {
+        match parse_setting(line) {
+            Ok(setting) => println!("parse_setting({:?}) → Ok({:?})", line, setting),
+            Err(e) => println!(
+                "parse_setting({:?}) → Err（? でテストから返ると失敗）: {}",
+                line, e
+            ),
+        }
+    }
+    println!("Result を返すテストには #[should_panic] を付けられない。Err を確かめるなら assert!(r.is_err())");
+}
+
+// #[test] の次の行の関数名を集める
+fn test_names(source: &str) -> Vec<&str> {
+    let lines: Vec<&str> = source.lines().map(str::trim).collect();
+    lines
+        .windows(3)
+        .filter(|w| w[0] == "#[test]")
+        .filter_map(|w| {
+            // #[test] と fn の間に #[should_panic] や #[ignore] が入ることもある
+            let line = if w[1].starts_with("#[") { w[2] } else { w[1] };
+            let rest = line.strip_prefix("fn ")?;
+            rest.split(['(', '<']).next()
+        })
+        .collect()
+}
+
+/// テストの置き場所と cargo test
+pub fn test_organization() {
+    println!("\n=== テストの置き場所と cargo test ===");
+
+    println!("chapters/testing/");
+    println!("├── src/lib.rs                 単体テスト: #[cfg(test)] mod tests（cargo build には入らない）");
+    println!("└── tests/");
+    println!("    ├── integration_test.rs    結合テスト: ファイルごとに別のクレートとしてコンパイルされる");
+    println!("    └── common/mod.rs          テストで共有するヘルパー（tests/common.rs にするとテスト扱いされる）");
+
+    // このクレートに実際にあるテスト（ソースを埋め込んで #[test] を探している）
+    let unit = test_names(include_str!("lib.rs"));
+    let integration = test_names(include_str!("../tests/integration_test.rs"));
+    println!("\n単体テスト（{} 個）: {}", unit.len(), unit.join(", "));
+    println!(
+        "結合テスト（{} 個）: {}",
+        integration.len(),
+        integration.join(", ")
+    );
+
+    println!("\nよく使う cargo test:");
+    for (command, description) in [
+        ("cargo test -p testing", "この章のテストをすべて実行"),
+        ("cargo test -p testing add", "名前に add を含むテストだけ"),
+        (
+            "cargo test -p testing --test integration_test",
+            "結合テストのファイルを1つだけ",
+        ),
+        (
+            "cargo test -p testing -- --ignored",
+            "#[ignore] を付けたテストだけ",
+        ),
+        (
+            "cargo test -- --test-threads=1",
+            "1スレッドで順に（共有する状態があるとき）",
+        ),
+        (
+            "cargo test -- --show-output",
+            "成功したテストの println! も表示",
+        ),
+    ] {
+        println!("  {:<48} {}", command, description);
+    }
+    println!("バイナリだけのクレート（src/main.rs）は tests/ から use できない。ロジックは lib.rs に置く");
+}
+
+/// チートシート用のデータ（`cheatsheet testing` で表示）
+pub const CHEATSHEET: CheatSheet = CheatSheet {
+    syntax: &[
+        (
+            "#[cfg(test)] mod tests { use super::*; }",
+            "単体テストのモジュール（非公開の関数も使える）",
+        ),
+        (
+            "#[test] fn it_works() { .. }",
+            "テスト関数（panic したら失敗）",
+        ),
+        (
+            "#[should_panic(expected = \"..\")]",
+            "panic して、メッセージに .. を含めば成功",
+        ),
+        (
+            "fn t() -> Result<(), String> { ..?; Ok(()) }",
+            "Err を返したら失敗（? が使える）",
+        ),
+        ("#[ignore]", "普段は飛ばす（-- --ignored で実行）"),
+        (
+            "tests/foo.rs",
+            "結合テスト（公開 API だけを使う別クレート）",
+        ),
+    ],
+    methods: &[
+        (
+            "assert!(cond, \"msg {}\", x)",
+            "cond が false なら失敗（メッセージ付き）",
+        ),
+        (
+            "assert_eq!(left, right)",
+            "等しくなければ失敗。両方の値を表示する",
+        ),
+        ("assert_ne!(left, right)", "等しければ失敗"),
+        ("cargo test name", "名前に name を含むテストだけ実行"),
+        ("cargo test -- --test-threads=1", "並列に実行しない"),
+    ],
+    gotchas: &[
+        "テストは並列に実行される。ファイルや環境変数を共有するテストは互いに干渉する",
+        "assert_eq! で比べる型には PartialEq と Debug が必要",
+        "成功したテストの println! は表示されない（--show-output で表示）",
+        "tests/common.rs はテストとして扱われる。ヘルパーは tests/common/mod.rs に置く",
+    ],
+};
+
+/// すべてのデモを実行
+pub fn run_all() {
+    println!("╔════════════════════════════════════════════════════════════════╗");
+    println!("║          Rust テストの書き方サンプル                             ║");
+    println!("╚════════════════════════════════════════════════════════════════╝");
+
+    lesson_api::run_topic("testing");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_adds_two() {
+        assert_eq!(add_two(2), 4);
+    }
+
+    #[test]
+    fn internal() {
+        // 非公開の関数も試せる
+        assert_eq!(internal_adder(2, 2), 4);
+    }
+
+    #[test]
+    fn larger_can_hold_smaller() {
+        let larger = Rectangle {
+            width: 8,
+            height: 7,
+        };
+        let smaller = Rectangle {
+            width: 5,
+            height: 1,
+        };
+        assert!(larger.can_hold(&smaller));
+        assert!(!smaller.can_hold(&larger));
+    }
+
+    #[test]
+    fn greeting_contains_name() {
+        let result = greeting("Carol");
+        assert!(
+            result.contains("Carol"),
+            "greeting が名前を含んでいない。値は `{}`",
+            result
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "less than or equal to 100")]
+    fn greater_than_100() {
+        Guess::new(200);
+    }
+
+    #[test]
+    fn parses_settings() -> Result<(), String> {
+        assert_eq!(parse_setting("port = 8080")?, ("port".to_string(), 8080));
+        assert!(parse_setting("port: 8080").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn run_check_reports_both_sides() {
+        assert_eq!(run_check(|| assert_eq!(add_two(1), 3)), CheckResult::Ok);
+        match run_check(|| assert_eq!(add_two(2), 5)) {
+            CheckResult::Failed(message) => {
+                assert!(message.contains("left: 4"), "{}", message);
+                assert!(message.contains("right: 5"), "{}", message);
+            }
+            CheckResult::Ok => panic!("失敗するはず"),
+        }
+    }
+
+    #[test]
+    fn finds_test_names_in_source() {
+        let names = test_names(include_str!("lib.rs"));
+        assert!(names.contains(&"greater_than_100"));
+        assert!(names.contains(&"parses_settings"));
+        // #[ignore] が間に挟まっていても拾う。#[test] の無い関数は拾わない
+        assert!(names.contains(&"expensive_test"));
+        assert!(!names.contains(&"run_check"));
+    }
+
+    #[test]
+    #[ignore]
+    fn expensive_test() {
+        // cargo test -p testing -- --ignored のときだけ実行される
+        let total: u64 = (1..=10_000_000u64).sum();
+        assert_eq!(total, 50_000_005_000_000);
+    }
+}
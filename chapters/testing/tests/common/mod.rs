@@ -0,0 +1,22 @@
+// ============================================================================
+// 結合テストで共有するヘルパー
+// ============================================================================
+//
+// tests/common.rs に置くと、それ自体が1つのテストクレートとして扱われ、
+// cargo test の出力に「running 0 tests」が出てしまう。
+// tests/common/mod.rs に置けば、使う側で mod common; と書いたときだけ読み込まれる。
+
+use testing::Rectangle;
+
+/// テストで使う大小の長方形
+pub fn setup() -> (Rectangle, Rectangle) {
+    let larger = Rectangle {
+        width: 8,
+        height: 7,
+    };
+    let smaller = Rectangle {
+        width: 5,
+        height: 1,
+    };
+    (larger, smaller)
+}
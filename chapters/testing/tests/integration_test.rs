@@ -0,0 +1,50 @@
+// ============================================================================
+// 結合テスト
+// ============================================================================
+//
+// tests/ の下のファイルは、それぞれ別のクレートとしてコンパイルされる。
+// 外から使う人と同じように、testing クレートの公開された API だけを使う。
+//   cargo test -p testing --test integration_test
+
+mod common;
+
+use testing::{add_two, greeting, parse_setting, run_check, CheckResult, Guess};
+
+#[test]
+fn it_adds_two() {
+    assert_eq!(add_two(2), 4);
+}
+
+#[test]
+fn rectangles_from_shared_setup() {
+    let (larger, smaller) = common::setup();
+    assert!(larger.can_hold(&smaller));
+    assert!(!smaller.can_hold(&larger));
+}
+
+#[test]
+fn greeting_says_hello() {
+    assert_eq!(greeting("Ferris"), "Hello Ferris!");
+}
+
+#[test]
+#[should_panic(expected = "greater than or equal to 1")]
+fn guess_below_range_panics() {
+    Guess::new(0);
+}
+
+#[test]
+fn parse_setting_with_question_mark() -> Result<(), String> {
+    let (key, value) = parse_setting("threads=4")?;
+    assert_eq!((key.as_str(), value), ("threads", 4));
+    Ok(())
+}
+
+#[test]
+fn run_check_catches_failed_assertions() {
+    assert_eq!(run_check(|| assert!(add_two(0) == 2)), CheckResult::Ok);
+    assert!(matches!(
+        run_check(|| assert_ne!(add_two(0), 2)),
+        CheckResult::Failed(_)
+    ));
+}
@@ -0,0 +1,460 @@
+// ============================================================================
+// Rust 時間サンプル - Instant、Duration、SystemTime
+// 公式ドキュメント: https://doc.rust-lang.org/std/time/index.html
+// ============================================================================
+//
+// std::time には2種類の時計がある。
+//   - Instant     単調増加する時計。経過時間を測るためのもので、日時には変換できない
+//   - SystemTime  壁時計（OS の現在時刻）。日時に変換できるが、時刻合わせで戻ることがある
+//   - Duration    時間の長さ（秒 + ナノ秒）。足し算・引き算・掛け算ができる
+//
+// 日時の書式やタイムゾーンは std には無い（chrono / time クレートを使う）。
+// この章では UNIX 時間から UTC の日付を計算する小さな関数を書く。
+//
+// wasm32-unknown-unknown（ブラウザ版）では Instant::now() も SystemTime::now() も panic するので、
+// 時計を読む部分は説明だけを表示する。Duration の計算はそのまま動く。
+
+use lesson_api::{println, CheatSheet};
+use std::fmt;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+// wasm32-unknown-unknown（ブラウザ版）では時計を読むと panic する
+fn clock_unavailable() -> bool {
+    let unavailable = cfg!(target_arch = "wasm32");
+    if unavailable {
+        println!("ブラウザ版では時計を読めません。cargo run で試してください");
+    }
+    unavailable
+}
+
+// ----------------------------------------------------------------------------
+// Instant で経過時間を測る
+// ----------------------------------------------------------------------------
+
+/// f を実行して、結果とかかった時間を返す
+pub fn measure<R>(f: impl FnOnce() -> R) -> (R, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+/// Instant で経過時間を測る
+pub fn instant_elapsed() {
+    println!("\n=== Instant で経過時間を測る ===");
+    if clock_unavailable() {
+        return;
+    }
+
+    let start = Instant::now();
+    thread::sleep(Duration::from_millis(20));
+    // elapsed() は Instant::now() - start と同じ
+    println!("sleep(20ms) の後の start.elapsed(): {:?}", start.elapsed());
+
+    let (sum, took) = measure(|| (1..=1_000_000u64).sum::<u64>());
+    println!("1..=1_000_000 の合計 {} にかかった時間: {:?}", sum, took);
+
+    // 2つの Instant の差は Duration
+    let earlier = Instant::now();
+    let later = Instant::now();
+    println!(
+        "later.duration_since(earlier) = {:?}",
+        later.duration_since(earlier)
+    );
+    // 順序が逆なら checked_duration_since が None を返す（duration_since は 0 になる）
+    println!(
+        "earlier.checked_duration_since(later) = {:?}",
+        earlier.checked_duration_since(later)
+    );
+
+    println!("Instant は単調増加（時刻合わせの影響を受けない）。ただし日時には変換できない");
+    println!("Instant の中身（起点）は OS しだいなので、保存したりプロセス間で比べたりしない");
+}
+
+// ----------------------------------------------------------------------------
+// Duration の計算
+// ----------------------------------------------------------------------------
+
+/// 「1時間02分03.450秒」の形にする
+pub fn format_duration(d: Duration) -> String {
+    let secs = d.as_secs();
+    let (hours, minutes, seconds) = (secs / 3600, secs / 60 % 60, secs % 60);
+    let millis = d.subsec_millis();
+    if hours > 0 {
+        format!(
+            "{}時間{:02}分{:02}.{:03}秒",
+            hours, minutes, seconds, millis
+        )
+    } else if minutes > 0 {
+        format!("{}分{:02}.{:03}秒", minutes, seconds, millis)
+    } else {
+        format!("{}.{:03}秒", seconds, millis)
+    }
+}
+
+/// Duration の計算
+pub fn duration_arithmetic() {
+    println!("\n=== Duration の計算 ===");
+
+    // いろいろな単位から作れる
+    let a = Duration::from_secs(90);
+    let b = Duration::from_millis(1_500);
+    let c = Duration::from_secs_f64(0.25);
+    println!(
+        "from_secs(90) = {:?}、from_millis(1500) = {:?}、from_secs_f64(0.25) = {:?}",
+        a, b, c
+    );
+
+    // 演算子が使える（Duration * u32、Duration / u32）
+    println!("a + b = {:?}", a + b);
+    println!("a - b = {:?}", a - b);
+    println!("b * 3 = {:?}", b * 3);
+    println!("a / 4 = {:?}", a / 4);
+    println!("b.mul_f64(1.5) = {:?}", b.mul_f64(1.5));
+
+    // Duration は負にならない。b - a は panic するので checked / saturating を使う
+    println!("b.checked_sub(a) = {:?}", b.checked_sub(a));
+    println!("b.saturating_sub(a) = {:?}", b.saturating_sub(a));
+
+    // 取り出すときの単位
+    let d = Duration::new(3_723, 450_000_000); // 3723 秒 + 0.45 秒
+    println!("\nDuration::new(3723, 450_000_000):");
+    println!("  as_secs() = {}（端数は切り捨て）", d.as_secs());
+    println!("  subsec_millis() = {}", d.subsec_millis());
+    println!("  as_millis() = {}（u128）", d.as_millis());
+    println!("  as_secs_f64() = {}", d.as_secs_f64());
+    println!(
+        "  {{:?}} = {:?}、format_duration = {}",
+        d,
+        format_duration(d)
+    );
+
+    // 比較やソートもできる
+    let mut laps = [
+        Duration::from_millis(950),
+        Duration::from_millis(870),
+        Duration::from_millis(910),
+    ];
+    laps.sort();
+    let total: Duration = laps.iter().sum();
+    println!(
+        "\nラップ {:?} の合計 = {:?}、最速 = {:?}",
+        laps, total, laps[0]
+    );
+    println!("Duration::ZERO / Duration::MAX、Duration::from_secs(u64) は u64 の秒まで表せる");
+}
+
+// ----------------------------------------------------------------------------
+// SystemTime と UNIX_EPOCH
+// ----------------------------------------------------------------------------
+
+/// UTC の日時（表示用）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DateTime {
+    pub year: i64,
+    pub month: u32,
+    pub day: u32,
+    pub hour: u32,
+    pub minute: u32,
+    pub second: u32,
+}
+
+impl fmt::Display for DateTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+            self.year, self.month, self.day, self.hour, self.minute, self.second
+        )
+    }
+}
+
+// 1970-01-01 からの日数を年月日にする（うるう年を 400 年周期でまとめて数える方法）
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    // 3月始まりの年で数えると、うるう日が年の最後に来て計算が楽になる
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097); // 400 年周期の中の日
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365; // 周期の中の年
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100); // 3月1日からの日
+    let mp = (5 * doy + 2) / 153; // 3月を 0 とした月
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// UNIX 時間（1970-01-01 00:00:00 UTC からの秒数）を UTC の日時にする
+pub fn utc_from_unix(secs: u64) -> DateTime {
+    let days = (secs / 86_400) as i64;
+    let rest = (secs % 86_400) as u32;
+    let (year, month, day) = civil_from_days(days);
+    DateTime {
+        year,
+        month,
+        day,
+        hour: rest / 3600,
+        minute: rest / 60 % 60,
+        second: rest % 60,
+    }
+}
+
+/// SystemTime と UNIX_EPOCH
+pub fn system_time() {
+    println!("\n=== SystemTime と UNIX_EPOCH ===");
+
+    // UNIX_EPOCH に Duration を足すと SystemTime になる
+    for secs in [0, 951_782_400, 1_700_000_000] {
+        let time = UNIX_EPOCH + Duration::from_secs(secs);
+        let back = time.duration_since(UNIX_EPOCH).unwrap().as_secs();
+        println!(
+            "  UNIX_EPOCH + {:>10} 秒 = {} UTC（戻すと {}）",
+            secs,
+            utc_from_unix(secs),
+            back
+        );
+    }
+
+    // 過去の時刻から見た duration_since は Err（SystemTimeError）になる
+    let earlier = UNIX_EPOCH + Duration::from_secs(100);
+    match UNIX_EPOCH.duration_since(earlier) {
+        Ok(d) => println!("Ok({:?})", d),
+        Err(e) => println!(
+            "UNIX_EPOCH.duration_since(100秒後) = Err: {}（差は {:?}）",
+            e,
+            e.duration()
+        ),
+    }
+
+    if clock_unavailable() {
+        return;
+    }
+    let now = SystemTime::now();
+    // 時計が 1970 年より前に合わされていることもありうるので Result
+    match now.duration_since(UNIX_EPOCH) {
+        Ok(since_epoch) => {
+            let secs = since_epoch.as_secs();
+            println!("\nSystemTime::now(): UNIX 時間 {} 秒", secs);
+            println!("  UTC: {}", utc_from_unix(secs));
+            // 日本時間は UTC + 9 時間（夏時間が無いので足すだけでよい）
+            println!("  JST: {}", utc_from_unix(secs + 9 * 3600));
+        }
+        Err(e) => println!("時計が 1970 年より前を指しています: {}", e),
+    }
+    println!("SystemTime は時刻合わせで戻ることがある。経過時間を測るなら Instant");
+}
+
+// ----------------------------------------------------------------------------
+// ストップウォッチ
+// ----------------------------------------------------------------------------
+
+/// ラップを記録できるストップウォッチ
+///
+/// 現在時刻は Instant::now() を中で呼ばずに引数で受け取る。
+/// テストでは start + Duration の Instant を渡して、時計を好きなだけ進められる。
+#[derive(Debug, Default)]
+pub struct Stopwatch {
+    // 動いているときだけ Some（動き始めた時刻）
+    running_since: Option<Instant>,
+    // 止めるまでに積み上がった時間
+    accumulated: Duration,
+    // 直前のラップを取った時点の合計
+    last_lap: Duration,
+    laps: Vec<Duration>,
+}
+
+impl Stopwatch {
+    pub fn new() -> Stopwatch {
+        Stopwatch::default()
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running_since.is_some()
+    }
+
+    /// 動かす（すでに動いていれば何もしない）
+    pub fn start(&mut self, now: Instant) {
+        if self.running_since.is_none() {
+            self.running_since = Some(now);
+        }
+    }
+
+    /// 止める（合計は残る）
+    pub fn stop(&mut self, now: Instant) {
+        if let Some(since) = self.running_since.take() {
+            self.accumulated += now.saturating_duration_since(since);
+        }
+    }
+
+    /// 合計の時間
+    pub fn elapsed(&self, now: Instant) -> Duration {
+        let running = self
+            .running_since
+            .map_or(Duration::ZERO, |since| now.saturating_duration_since(since));
+        self.accumulated + running
+    }
+
+    /// 前のラップからの時間を記録して返す
+    pub fn lap(&mut self, now: Instant) -> Duration {
+        let total = self.elapsed(now);
+        let lap = total - self.last_lap;
+        self.last_lap = total;
+        self.laps.push(lap);
+        lap
+    }
+
+    pub fn laps(&self) -> &[Duration] {
+        &self.laps
+    }
+
+    pub fn reset(&mut self) {
+        *self = Stopwatch::default();
+    }
+}
+
+/// ストップウォッチ（ラップタイム）
+pub fn stopwatch() {
+    println!("\n=== ストップウォッチ（ラップタイム） ===");
+    if clock_unavailable() {
+        return;
+    }
+
+    let mut watch = Stopwatch::new();
+    watch.start(Instant::now());
+    for (i, ms) in [30, 10, 20].into_iter().enumerate() {
+        thread::sleep(Duration::from_millis(ms));
+        let lap = watch.lap(Instant::now());
+        println!(
+            "  ラップ {}（約 {}ms 待った）: {}",
+            i + 1,
+            ms,
+            format_duration(lap)
+        );
+    }
+
+    // 止めている間は進まない
+    watch.stop(Instant::now());
+    let stopped = watch.elapsed(Instant::now());
+    thread::sleep(Duration::from_millis(15));
+    println!(
+        "止めてから 15ms 待っても合計は同じ: {}（{}）",
+        format_duration(watch.elapsed(Instant::now())),
+        watch.elapsed(Instant::now()) == stopped
+    );
+
+    watch.start(Instant::now());
+    thread::sleep(Duration::from_millis(10));
+    println!(
+        "再開して 10ms: 合計 {}",
+        format_duration(watch.elapsed(Instant::now()))
+    );
+
+    let fastest = watch.laps().iter().min().copied().unwrap_or_default();
+    println!("最速のラップ: {}", format_duration(fastest));
+    println!("sleep は「少なくともその時間」待つので、ラップは指定より少し長くなる");
+}
+
+/// チートシート用のデータ（`cheatsheet time_demo` で表示）
+pub const CHEATSHEET: CheatSheet = CheatSheet {
+    syntax: &[
+        ("let start = Instant::now();", "経過時間を測り始める"),
+        ("start.elapsed()", "start からの Duration"),
+        (
+            "Duration::from_millis(1500)",
+            "時間の長さを作る（from_secs / from_secs_f64 も）",
+        ),
+        (
+            "SystemTime::now().duration_since(UNIX_EPOCH)?",
+            "UNIX 時間（Result）",
+        ),
+        (
+            "UNIX_EPOCH + Duration::from_secs(n)",
+            "UNIX 時間から SystemTime を作る",
+        ),
+    ],
+    methods: &[
+        (
+            "d.as_secs() / d.subsec_millis()",
+            "秒（切り捨て）と、秒未満のミリ秒",
+        ),
+        (
+            "d.as_secs_f64() / d.as_millis()",
+            "小数の秒 / ミリ秒（u128）",
+        ),
+        (
+            "a.checked_sub(b) / a.saturating_sub(b)",
+            "負になるときは None / 0",
+        ),
+        ("later.duration_since(earlier)", "2つの Instant の差"),
+        ("thread::sleep(d)", "少なくとも d だけ待つ"),
+    ],
+    gotchas: &[
+        "Instant は日時に変換できない。日時は SystemTime",
+        "SystemTime は時刻合わせで戻ることがある。経過時間は Instant で測る",
+        "Duration は負にならない。a - b で b が大きいと panic",
+        "タイムゾーンや日時の書式は std には無い（chrono / time クレート）",
+    ],
+};
+
+/// すべてのデモを実行
+pub fn run_all() {
+    println!("╔════════════════════════════════════════════════════════════════╗");
+    println!("║          Rust 時間サンプル                                       ║");
+    println!("╚════════════════════════════════════════════════════════════════╝");
+
+    lesson_api::run_topic("time_demo");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_unix_time_to_utc() {
+        assert_eq!(utc_from_unix(0).to_string(), "1970-01-01 00:00:00");
+        assert_eq!(
+            utc_from_unix(951_782_400).to_string(),
+            "2000-02-29 00:00:00"
+        );
+        assert_eq!(
+            utc_from_unix(1_700_000_000).to_string(),
+            "2023-11-14 22:13:20"
+        );
+        assert_eq!(
+            utc_from_unix(4_107_542_399).to_string(),
+            "2100-02-28 23:59:59"
+        );
+    }
+
+    #[test]
+    fn formats_durations() {
+        assert_eq!(format_duration(Duration::from_millis(1_500)), "1.500秒");
+        assert_eq!(format_duration(Duration::new(83, 7_000_000)), "1分23.007秒");
+        assert_eq!(
+            format_duration(Duration::new(3_723, 450_000_000)),
+            "1時間02分03.450秒"
+        );
+    }
+
+    #[test]
+    fn stopwatch_with_a_fake_clock() {
+        let t0 = Instant::now();
+        let at = |ms: u64| t0 + Duration::from_millis(ms);
+
+        let mut watch = Stopwatch::new();
+        watch.start(at(0));
+        assert_eq!(watch.lap(at(300)), Duration::from_millis(300));
+        assert_eq!(watch.lap(at(500)), Duration::from_millis(200));
+
+        // 止めている 1000ms は数えない
+        watch.stop(at(600));
+        assert!(!watch.is_running());
+        watch.start(at(1_600));
+        assert_eq!(watch.elapsed(at(1_700)), Duration::from_millis(700));
+        assert_eq!(watch.lap(at(1_700)), Duration::from_millis(200));
+        assert_eq!(watch.laps().len(), 3);
+
+        watch.reset();
+        assert_eq!(watch.elapsed(at(2_000)), Duration::ZERO);
+    }
+}
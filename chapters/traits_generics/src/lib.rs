@@ -0,0 +1,1144 @@
+// ============================================================================
+// Rustトレイトとジェネリクスサンプル
+// 公式ドキュメント: https://doc.rust-lang.org/book/ch10-00-generics.html
+// ============================================================================
+
+use lesson_api::{println, CheatSheet};
+use std::collections::HashMap;
+use std::fmt::{Debug, Display};
+
+/// ジェネリクスの基本
+pub fn generics_basics() {
+    println!("\n=== ジェネリクスの基本 ===");
+
+    // ジェネリクスなしの場合 - 型ごとに関数が必要
+    fn largest_i32(list: &[i32]) -> &i32 {
+        let mut largest = &list[0];
+        for item in list {
+            if item > largest {
+                largest = item;
+            }
+        }
+        largest
+    }
+
+    fn largest_char(list: &[char]) -> &char {
+        let mut largest = &list[0];
+        for item in list {
+            if item > largest {
+                largest = item;
+            }
+        }
+        largest
+    }
+
+    let number_list = vec![34, 50, 25, 100, 65];
+    println!("最大の数: {}", largest_i32(&number_list));
+
+    let char_list = vec!['y', 'm', 'a', 'q'];
+    println!("最大の文字: {}", largest_char(&char_list));
+
+    // ジェネリクスを使った関数（後述のトレイト境界が必要）
+    fn largest<T: std::cmp::PartialOrd>(list: &[T]) -> &T {
+        let mut largest = &list[0];
+        for item in list {
+            if item > largest {
+                largest = item;
+            }
+        }
+        largest
+    }
+
+    println!("ジェネリック版 - 最大の数: {}", largest(&number_list));
+    println!("ジェネリック版 - 最大の文字: {}", largest(&char_list));
+}
+
+/// ジェネリック構造体
+pub fn generic_structs() {
+    println!("\n=== ジェネリック構造体 ===");
+
+    // 1つの型パラメータ
+    #[derive(Debug)]
+    struct Point<T> {
+        x: T,
+        y: T,
+    }
+
+    let integer_point = Point { x: 5, y: 10 };
+    let float_point = Point { x: 1.0, y: 4.0 };
+
+    println!("整数Point: {:?}", integer_point);
+    println!("浮動小数点Point: {:?}", float_point);
+
+    // 複数の型パラメータ
+    #[derive(Debug)]
+    struct Point2<T, U> {
+        x: T,
+        y: U,
+    }
+
+    let mixed_point = Point2 { x: 5, y: 4.0 };
+    println!("混合Point: {:?}", mixed_point);
+
+    // メソッドの定義
+    impl<T> Point<T> {
+        fn x(&self) -> &T {
+            &self.x
+        }
+    }
+
+    // 特定の型に対するメソッド
+    impl Point<f32> {
+        fn distance_from_origin(&self) -> f32 {
+            (self.x.powi(2) + self.y.powi(2)).sqrt()
+        }
+    }
+
+    let p = Point {
+        x: 3.0_f32,
+        y: 4.0_f32,
+    };
+    println!("原点からの距離: {}", p.distance_from_origin());
+
+    // 異なる型パラメータを持つメソッド
+    impl<T, U> Point2<T, U> {
+        fn mixup<V, W>(self, other: Point2<V, W>) -> Point2<T, W> {
+            Point2 {
+                x: self.x,
+                y: other.y,
+            }
+        }
+    }
+
+    let p1 = Point2 { x: 5, y: 10.4 };
+    let p2 = Point2 { x: "Hello", y: 'c' };
+    let p3 = p1.mixup(p2);
+    println!("mixup結果: x = {}, y = {}", p3.x, p3.y);
+}
+
+/// ジェネリック列挙型
+pub fn generic_enums() {
+    println!("\n=== ジェネリック列挙型 ===");
+
+    // 標準ライブラリのOption<T>とResult<T, E>
+    // enum Option<T> {
+    //     Some(T),
+    //     None,
+    // }
+    //
+    // enum Result<T, E> {
+    //     Ok(T),
+    //     Err(E),
+    // }
+
+    let some_number: Option<i32> = Some(5);
+    let some_string: Option<String> = Some(String::from("Hello"));
+    let absent_number: Option<i32> = None;
+
+    println!("Option<i32>: {:?}", some_number);
+    println!("Option<String>: {:?}", some_string);
+    println!("None: {:?}", absent_number);
+}
+
+/// トレイトの定義と実装
+pub fn traits_basics() {
+    println!("\n=== トレイトの基本 ===");
+
+    // トレイト定義
+    trait Summary {
+        fn summarize(&self) -> String;
+
+        // デフォルト実装
+        fn summarize_author(&self) -> String {
+            String::from("(著者不明)")
+        }
+    }
+
+    // 構造体定義
+    struct NewsArticle {
+        headline: String,
+        location: String,
+        author: String,
+        content: String,
+    }
+
+    struct Tweet {
+        username: String,
+        content: String,
+        reply: bool,
+        retweet: bool,
+    }
+
+    // トレイト実装
+    impl Summary for NewsArticle {
+        fn summarize(&self) -> String {
+            format!("{}, {} ({})", self.headline, self.author, self.location)
+        }
+
+        fn summarize_author(&self) -> String {
+            format!("@{}", self.author)
+        }
+    }
+
+    impl Summary for Tweet {
+        fn summarize(&self) -> String {
+            format!("{}: {}", self.username, self.content)
+        }
+    }
+
+    let article = NewsArticle {
+        headline: String::from("Rustが最も愛されている言語に選ばれる"),
+        location: String::from("東京"),
+        author: String::from("技術太郎"),
+        content: String::from("Rustは..."),
+    };
+
+    let tweet = Tweet {
+        username: String::from("rust_lover"),
+        content: String::from("Rustを学び始めました!"),
+        reply: false,
+        retweet: false,
+    };
+
+    println!("記事の要約: {}", article.summarize());
+    println!("記事の著者: {}", article.summarize_author());
+    println!("ツイートの要約: {}", tweet.summarize());
+    println!("ツイートの著者: {}", tweet.summarize_author()); // デフォルト実装
+}
+
+/// トレイト境界
+pub fn trait_bounds() {
+    println!("\n=== トレイト境界 ===");
+
+    trait Summary {
+        fn summarize(&self) -> String;
+    }
+
+    #[derive(Debug)]
+    struct Article {
+        title: String,
+    }
+
+    impl Summary for Article {
+        fn summarize(&self) -> String {
+            self.title.clone()
+        }
+    }
+
+    // impl Trait 構文（引数として）
+    fn notify(item: &impl Summary) {
+        println!("速報! {}", item.summarize());
+    }
+
+    // トレイト境界構文（より明示的）
+    fn notify_verbose<T: Summary>(item: &T) {
+        println!("速報（verbose）! {}", item.summarize());
+    }
+
+    // 複数のトレイト境界
+    fn notify_with_display<T: Summary + Display>(item: &T) {
+        println!("表示: {}, 要約: {}", item, item.summarize());
+    }
+
+    // where句を使った読みやすい構文
+    fn some_function<T, U>(_t: &T, _u: &U) -> i32
+    where
+        T: Display + Clone,
+        U: Clone + Debug,
+    {
+        42
+    }
+
+    let article = Article {
+        title: String::from("Rustの新機能"),
+    };
+
+    notify(&article);
+    notify_verbose(&article);
+
+    // impl Displayを実装した例
+    impl Display for Article {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "Article: {}", self.title)
+        }
+    }
+
+    notify_with_display(&article);
+}
+
+/// トレイトを戻り値として
+pub fn returning_traits() {
+    println!("\n=== 戻り値としてのトレイト ===");
+
+    trait Summary {
+        fn summarize(&self) -> String;
+    }
+
+    struct Tweet {
+        username: String,
+        content: String,
+    }
+
+    impl Summary for Tweet {
+        fn summarize(&self) -> String {
+            format!("{}: {}", self.username, self.content)
+        }
+    }
+
+    // impl Traitを戻り値として使用
+    // 注意: 1つの具体型のみ返せる
+    fn returns_summarizable() -> impl Summary {
+        Tweet {
+            username: String::from("horse_ebooks"),
+            content: String::from("もちろん、ご存知かもしれませんが"),
+        }
+    }
+
+    let item = returns_summarizable();
+    println!("戻り値: {}", item.summarize());
+}
+
+/// 条件付きメソッド実装
+pub fn conditional_implementations() {
+    println!("\n=== 条件付きメソッド実装 ===");
+
+    struct Pair<T> {
+        x: T,
+        y: T,
+    }
+
+    // すべてのPair<T>に実装
+    impl<T> Pair<T> {
+        fn new(x: T, y: T) -> Self {
+            Self { x, y }
+        }
+    }
+
+    // PartialOrdとDisplayを実装している型のPairにのみ実装
+    impl<T: Display + PartialOrd> Pair<T> {
+        fn cmp_display(&self) {
+            if self.x >= self.y {
+                println!("最大値は x = {}", self.x);
+            } else {
+                println!("最大値は y = {}", self.y);
+            }
+        }
+    }
+
+    let pair = Pair::new(10, 5);
+    pair.cmp_display();
+
+    // ブランケット実装の例（標準ライブラリ）
+    // impl<T: Display> ToString for T {
+    //     fn to_string(&self) -> String { ... }
+    // }
+    // Displayを実装していれば自動的にto_string()が使える
+
+    let s = 3.to_string();
+    println!("to_string(): {}", s);
+}
+
+/// 関連型を持つトレイト
+pub fn associated_types() {
+    println!("\n=== 関連型 ===");
+
+    // 関連型を持つトレイト
+    trait Iterator {
+        type Item; // 関連型
+
+        fn next(&mut self) -> Option<Self::Item>;
+    }
+
+    struct Counter {
+        count: u32,
+        max: u32,
+    }
+
+    impl Counter {
+        fn new(max: u32) -> Counter {
+            Counter { count: 0, max }
+        }
+    }
+
+    impl Iterator for Counter {
+        type Item = u32; // 具体的な型を指定
+
+        fn next(&mut self) -> Option<Self::Item> {
+            if self.count < self.max {
+                self.count += 1;
+                Some(self.count)
+            } else {
+                None
+            }
+        }
+    }
+
+    let mut counter = Counter::new(3);
+    println!("カウンター:");
+    while let Some(n) = counter.next() {
+        println!("  {}", n);
+    }
+}
+
+/// デフォルト型パラメータ
+pub fn default_generic_type_parameters() {
+    println!("\n=== デフォルト型パラメータ ===");
+
+    use std::ops::Add;
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    // Addトレイトの定義（参考）:
+    // trait Add<Rhs = Self> {
+    //     type Output;
+    //     fn add(self, rhs: Rhs) -> Self::Output;
+    // }
+
+    impl Add for Point {
+        type Output = Point;
+
+        fn add(self, other: Point) -> Point {
+            Point {
+                x: self.x + other.x,
+                y: self.y + other.y,
+            }
+        }
+    }
+
+    let p1 = Point { x: 1, y: 0 };
+    let p2 = Point { x: 2, y: 3 };
+    let p3 = p1 + p2;
+
+    println!("{:?} + {:?} = {:?}", p1, p2, p3);
+}
+
+/// スーパートレイト
+pub fn supertraits() {
+    println!("\n=== スーパートレイト ===");
+
+    // OutlinePrintはDisplayを要求する（スーパートレイト）
+    trait OutlinePrint: Display {
+        fn outline_print(&self) {
+            let output = self.to_string();
+            let len = output.len();
+            println!("{}", "*".repeat(len + 4));
+            println!("*{}*", " ".repeat(len + 2));
+            println!("* {} *", output);
+            println!("*{}*", " ".repeat(len + 2));
+            println!("{}", "*".repeat(len + 4));
+        }
+    }
+
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    impl Display for Point {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "({}, {})", self.x, self.y)
+        }
+    }
+
+    impl OutlinePrint for Point {}
+
+    let p = Point { x: 1, y: 3 };
+    p.outline_print();
+}
+
+/// 単相化とコードサイズ
+pub fn monomorphization() {
+    println!("\n=== 単相化とコードサイズ ===");
+
+    use std::any::type_name;
+    use std::collections::BTreeSet;
+
+    // ジェネリック版: 呼び出した型ごとに別々の関数がコンパイルされる
+    fn describe<T: Debug>(value: T, seen: &mut BTreeSet<(&'static str, usize)>) -> String {
+        // 関数ポインタのアドレスは実体（インスタンス）ごとに異なる
+        let address = describe::<T> as fn(T, &mut BTreeSet<(&'static str, usize)>) -> String;
+        seen.insert((type_name::<T>(), address as usize));
+        format!("{:?}", value)
+    }
+
+    // dyn版: どの型から呼んでも実体は1つだけ（vtable経由で呼び出す）
+    fn describe_dyn(value: &dyn Debug, seen: &mut BTreeSet<usize>) -> String {
+        seen.insert(describe_dyn as fn(&dyn Debug, &mut BTreeSet<usize>) -> String as usize);
+        format!("{:?}", value)
+    }
+
+    let mut generic_seen = BTreeSet::new();
+    describe(1_i32, &mut generic_seen);
+    describe(2_i32, &mut generic_seen); // 同じ型なら同じ実体
+    describe(3_u8, &mut generic_seen);
+    describe(4.0_f64, &mut generic_seen);
+    describe('x', &mut generic_seen);
+    describe("str", &mut generic_seen);
+    describe(String::from("String"), &mut generic_seen);
+    describe(vec![1, 2, 3], &mut generic_seen);
+    describe(Some(true), &mut generic_seen);
+
+    println!(
+        "ジェネリック版: 9回呼び出し → 実体は{}個",
+        generic_seen.len()
+    );
+    for (name, _) in &generic_seen {
+        println!("  describe::<{}>", name);
+    }
+
+    let mut dyn_seen = BTreeSet::new();
+    let values: [&dyn Debug; 9] = [
+        &1_i32,
+        &2_i32,
+        &3_u8,
+        &4.0_f64,
+        &'x',
+        &"str",
+        &String::from("String"),
+        &vec![1, 2, 3],
+        &Some(true),
+    ];
+    for value in values {
+        describe_dyn(value, &mut dyn_seen);
+    }
+    println!("dyn版: 9回呼び出し → 実体は{}個", dyn_seen.len());
+
+    // コストの違い
+    println!("\nコストモデル:");
+    println!("  ジェネリック: 型ごとにコードが増える（コードサイズ↑）が、呼び出しは静的でインライン化できる");
+    println!("  dyn Trait   : コードは1つ（コードサイズ↓）だが、vtable経由の間接呼び出しになる");
+    println!(
+        "  &i32 のサイズ: {}バイト / &dyn Debug のサイズ: {}バイト（データ + vtableのポインタ）",
+        std::mem::size_of::<&i32>(),
+        std::mem::size_of::<&dyn Debug>()
+    );
+}
+
+// ----------------------------------------------------------------------------
+// ビジターパターン
+// ----------------------------------------------------------------------------
+
+/// 式の木（basics の再帰的な型で作った Expr に、符号反転を足したもの）
+#[derive(Debug)]
+pub enum Expr {
+    Num(i64),
+    Neg(Box<Expr>),
+    Add(Box<Expr>, Box<Expr>),
+    Mul(Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    /// 1回目の振り分け: ノードの種類で、ビジターのどのメソッドを呼ぶかを決める
+    pub fn accept<V: Visitor>(&self, visitor: &mut V) -> V::Output {
+        match self {
+            Expr::Num(n) => visitor.visit_num(*n),
+            Expr::Neg(e) => visitor.visit_neg(e),
+            Expr::Add(a, b) => visitor.visit_add(a, b),
+            Expr::Mul(a, b) => visitor.visit_mul(a, b),
+        }
+    }
+}
+
+/// 式の木に対する処理。2回目の振り分けは「どのビジターの impl か」で決まる
+pub trait Visitor {
+    type Output;
+
+    fn visit_num(&mut self, n: i64) -> Self::Output;
+    fn visit_neg(&mut self, e: &Expr) -> Self::Output;
+    fn visit_add(&mut self, a: &Expr, b: &Expr) -> Self::Output;
+    fn visit_mul(&mut self, a: &Expr, b: &Expr) -> Self::Output;
+}
+
+/// 式の値を計算する
+pub struct Evaluator;
+
+impl Visitor for Evaluator {
+    type Output = i64;
+
+    fn visit_num(&mut self, n: i64) -> i64 {
+        n
+    }
+
+    fn visit_neg(&mut self, e: &Expr) -> i64 {
+        -e.accept(self)
+    }
+
+    fn visit_add(&mut self, a: &Expr, b: &Expr) -> i64 {
+        a.accept(self) + b.accept(self)
+    }
+
+    fn visit_mul(&mut self, a: &Expr, b: &Expr) -> i64 {
+        a.accept(self) * b.accept(self)
+    }
+}
+
+/// 式を文字列にする（かっこは必要なところだけ）
+pub struct PrettyPrinter;
+
+impl PrettyPrinter {
+    // 掛け算の中の足し算だけ、かっこで囲む
+    fn operand(&mut self, e: &Expr) -> String {
+        let text = e.accept(self);
+        if matches!(e, Expr::Add(..)) {
+            format!("({})", text)
+        } else {
+            text
+        }
+    }
+}
+
+impl Visitor for PrettyPrinter {
+    type Output = String;
+
+    fn visit_num(&mut self, n: i64) -> String {
+        n.to_string()
+    }
+
+    fn visit_neg(&mut self, e: &Expr) -> String {
+        match e {
+            Expr::Num(_) => format!("-{}", e.accept(self)),
+            _ => format!("-({})", e.accept(self)),
+        }
+    }
+
+    fn visit_add(&mut self, a: &Expr, b: &Expr) -> String {
+        format!("{} + {}", a.accept(self), b.accept(self))
+    }
+
+    fn visit_mul(&mut self, a: &Expr, b: &Expr) -> String {
+        format!("{} * {}", self.operand(a), self.operand(b))
+    }
+}
+
+/// 木の深さを数える。ビジターは状態（ここでは訪れたノードの数）も持てる
+#[derive(Default)]
+pub struct DepthCounter {
+    pub nodes: usize,
+}
+
+impl Visitor for DepthCounter {
+    type Output = usize;
+
+    fn visit_num(&mut self, _n: i64) -> usize {
+        self.nodes += 1;
+        1
+    }
+
+    fn visit_neg(&mut self, e: &Expr) -> usize {
+        self.nodes += 1;
+        1 + e.accept(self)
+    }
+
+    fn visit_add(&mut self, a: &Expr, b: &Expr) -> usize {
+        self.nodes += 1;
+        1 + a.accept(self).max(b.accept(self))
+    }
+
+    fn visit_mul(&mut self, a: &Expr, b: &Expr) -> usize {
+        self.nodes += 1;
+        1 + a.accept(self).max(b.accept(self))
+    }
+}
+
+/// ビジターパターン（トレイトによる二重ディスパッチ）
+pub fn visitor_pattern() {
+    println!("\n=== ビジターパターン（トレイトによる二重ディスパッチ） ===");
+
+    // -(2 + 3) * 4 + 5
+    let sum = Expr::Add(Box::new(Expr::Num(2)), Box::new(Expr::Num(3)));
+    let product = Expr::Mul(Box::new(Expr::Neg(Box::new(sum))), Box::new(Expr::Num(4)));
+    let expr = Expr::Add(Box::new(product), Box::new(Expr::Num(5)));
+
+    // 同じ木を、ビジターを取り替えて別々に処理する
+    let text = expr.accept(&mut PrettyPrinter);
+    let value = expr.accept(&mut Evaluator);
+    let mut counter = DepthCounter::default();
+    let depth = expr.accept(&mut counter);
+    println!("PrettyPrinter: {}", text);
+    println!("Evaluator    : {}", value);
+    println!(
+        "DepthCounter : 深さ {}（ノード {} 個）",
+        depth, counter.nodes
+    );
+
+    // 比較: ビジターを使わず、関数の中で match するだけ
+    fn eval(expr: &Expr) -> i64 {
+        match expr {
+            Expr::Num(n) => *n,
+            Expr::Neg(e) => -eval(e),
+            Expr::Add(a, b) => eval(a) + eval(b),
+            Expr::Mul(a, b) => eval(a) * eval(b),
+        }
+    }
+    println!("match で書いた eval: {}", eval(&expr));
+
+    // accept はジェネリックなので、どちらも静的ディスパッチ（単相化される）
+    println!("\nどちらを選ぶか:");
+    println!("  match     : 短くて読みやすい。Rust ではまずこちら（処理を足すのは関数を足すだけ）");
+    println!("  ビジター  : 木のたどり方と処理を分けられる。処理が多い・外部のクレートに足してもらうときに");
+    println!("  ノードの種類を足すと、どちらもコンパイルエラーで直す場所を教えてくれる");
+}
+
+// ----------------------------------------------------------------------------
+// トレイトオブジェクトでコマンドを登録する
+// ----------------------------------------------------------------------------
+
+/// 名前で呼び出せるコマンド
+pub trait Command {
+    fn help(&self) -> &str;
+    fn run(&self, args: &[&str]) -> String;
+}
+
+/// 名前 → コマンドの表。型の違うコマンドを Box<dyn Command> で1つの HashMap に入れる
+pub struct CommandRegistry {
+    commands: HashMap<String, Box<dyn Command>>,
+    /// 名前が見つからないときに呼ぶコマンド
+    fallback: Box<dyn Command>,
+}
+
+impl CommandRegistry {
+    pub fn new(fallback: impl Command + 'static) -> Self {
+        CommandRegistry {
+            commands: HashMap::new(),
+            fallback: Box::new(fallback),
+        }
+    }
+
+    /// 同じ名前で登録し直すと、前のコマンドを置き換える
+    pub fn register(&mut self, name: &str, command: impl Command + 'static) {
+        self.commands.insert(name.to_string(), Box::new(command));
+    }
+
+    /// 1行を「コマンド名 引数…」に分けて実行する
+    pub fn dispatch(&self, line: &str) -> String {
+        let mut words = line.split_whitespace();
+        let name = words.next().unwrap_or("");
+        let args: Vec<&str> = words.collect();
+        match self.commands.get(name) {
+            Some(command) => command.run(&args),
+            // fallback にはコマンド名も引数として渡す
+            None => self.fallback.run(&[&[name], &args[..]].concat()),
+        }
+    }
+
+    /// 登録されているコマンドの一覧（名前順）
+    pub fn help(&self) -> Vec<String> {
+        let mut names: Vec<&String> = self.commands.keys().collect();
+        names.sort();
+        names
+            .into_iter()
+            .map(|name| format!("{:<6} {}", name, self.commands[name].help()))
+            .collect()
+    }
+}
+
+struct Echo;
+
+impl Command for Echo {
+    fn help(&self) -> &str {
+        "引数をそのまま返す"
+    }
+
+    fn run(&self, args: &[&str]) -> String {
+        args.join(" ")
+    }
+}
+
+struct Sum;
+
+impl Command for Sum {
+    fn help(&self) -> &str {
+        "整数の合計"
+    }
+
+    fn run(&self, args: &[&str]) -> String {
+        let numbers: Result<Vec<i64>, _> = args.iter().map(|a| a.parse::<i64>()).collect();
+        match numbers {
+            Ok(numbers) => numbers.iter().sum::<i64>().to_string(),
+            Err(e) => format!("数ではない引数があります: {}", e),
+        }
+    }
+}
+
+/// 状態を持つコマンド（設定した挨拶の言葉を覚えている）
+struct Greet {
+    greeting: String,
+}
+
+impl Command for Greet {
+    fn help(&self) -> &str {
+        "名前に挨拶する"
+    }
+
+    fn run(&self, args: &[&str]) -> String {
+        let name = args.first().copied().unwrap_or("名無しさん");
+        format!("{}、{}！", self.greeting, name)
+    }
+}
+
+struct Unknown;
+
+impl Command for Unknown {
+    fn help(&self) -> &str {
+        "見つからないときの既定のコマンド"
+    }
+
+    fn run(&self, args: &[&str]) -> String {
+        format!("不明なコマンドです: {:?}", args.first().unwrap_or(&""))
+    }
+}
+
+/// トレイトオブジェクトでコマンドを登録する
+pub fn command_registry() {
+    println!("\n=== トレイトオブジェクトでコマンドを登録する ===");
+
+    let mut registry = CommandRegistry::new(Unknown);
+    registry.register("echo", Echo);
+    registry.register("sum", Sum);
+    registry.register(
+        "greet",
+        Greet {
+            greeting: "こんにちは".to_string(),
+        },
+    );
+
+    println!("登録したコマンド:");
+    for line in registry.help() {
+        println!("  {}", line);
+    }
+
+    for line in [
+        "echo hello world",
+        "sum 1 2 3 4",
+        "sum 1 two",
+        "greet Ferris",
+        "hello",
+    ] {
+        println!("> {:<22} → {}", line, registry.dispatch(line));
+    }
+
+    // 実行中に差し替えられる（プラグインの読み込みと同じ考え方）
+    registry.register(
+        "greet",
+        Greet {
+            greeting: "やあ".to_string(),
+        },
+    );
+    println!(
+        "> {:<22} → {}",
+        "greet Ferris",
+        registry.dispatch("greet Ferris")
+    );
+
+    // ランナーのメニューも、番号や名前からコマンドを探して呼ぶ同じ形にできる
+    println!("Box<dyn Command>: 型の違う値を1つのコレクションに入れ、実行時に選んで呼ぶ");
+    println!("新しいコマンドは Command を実装して register するだけ（dispatch は変えない）");
+}
+
+// ----------------------------------------------------------------------------
+// const ジェネリクス
+// ----------------------------------------------------------------------------
+
+// 配列の長さ N を型パラメータとして受け取る。どの長さの配列でも同じ関数で扱える
+fn sum_array<const N: usize>(values: [i32; N]) -> i32 {
+    values.iter().sum()
+}
+
+// 長さは値ではなく型から分かる
+fn length_of<T, const N: usize>(_: &[T; N]) -> usize {
+    N
+}
+
+// 戻り値の型の N で区切る長さが決まる（呼び出し側の型注釈から推論される）
+fn chunks_of<const N: usize>(bytes: &[u8]) -> Vec<[u8; N]> {
+    bytes
+        .chunks_exact(N)
+        .map(|chunk| chunk.try_into().unwrap())
+        .collect()
+}
+
+// N が 0 のときは単相化の時点でコンパイルエラーにする（インライン const ブロック）
+fn average<const N: usize>(values: [f64; N]) -> f64 {
+    const { assert!(N > 0, "空の配列の平均は取れません") };
+    values.iter().sum::<f64>() / N as f64
+}
+
+/// 配列の長さのジェネリクス（const N: usize）
+pub fn const_generic_arrays() {
+    println!("\n=== 配列の長さのジェネリクス（const N: usize） ===");
+
+    // [i32; 3] と [i32; 5] は別の型だが、1つの関数で受け取れる
+    println!("sum_array([1, 2, 3]) = {}", sum_array([1, 2, 3]));
+    println!(
+        "sum_array([10, 20, 30, 40, 50]) = {}",
+        sum_array([10, 20, 30, 40, 50])
+    );
+    println!("length_of(&['a', 'b']) = {}", length_of(&['a', 'b']));
+
+    // N は型注釈から推論される
+    let pairs: Vec<[u8; 2]> = chunks_of(b"abcdefg");
+    let triples = chunks_of::<3>(b"abcdefg");
+    println!("chunks_of::<2>(b\"abcdefg\") = {:?}", pairs);
+    println!("chunks_of::<3>(b\"abcdefg\") = {:?}", triples);
+    println!("（余りの g は chunks_exact が捨てる）");
+
+    println!("average([1.0, 2.0, 4.5]) = {}", average([1.0, 2.0, 4.5]));
+    println!("// average([]) → コンパイルエラー: 空の配列の平均は取れません");
+    println!("//   const {{ assert!(N > 0) }} は N ごとにコンパイル時に評価される");
+
+    // 標準ライブラリも const ジェネリクスで、どの長さの配列にもトレイトを実装している
+    let big = [7_u8; 40];
+    let copy = big;
+    println!(
+        "[u8; 40] も Debug / PartialEq / Copy を持つ: {}",
+        copy == big
+    );
+    println!("（const ジェネリクスの前は、長さ 32 までしか実装されていなかった）");
+    println!("Default は [T; 0] だけ T: Default が要らないため、今も長さ 32 までしかない");
+}
+
+/// R 行 C 列の行列。大きさは型の一部なので、形の合わない計算はコンパイルできない
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Matrix<const R: usize, const C: usize> {
+    rows: [[i32; C]; R],
+}
+
+impl<const R: usize, const C: usize> Matrix<R, C> {
+    pub fn new(rows: [[i32; C]; R]) -> Self {
+        Matrix { rows }
+    }
+
+    /// 行数と列数（どちらも型から決まる）
+    pub fn shape(&self) -> (usize, usize) {
+        (R, C)
+    }
+
+    /// 転置すると型も Matrix<C, R> になる
+    pub fn transpose(&self) -> Matrix<C, R> {
+        let mut rows = [[0; R]; C];
+        for (i, row) in self.rows.iter().enumerate() {
+            for (j, &value) in row.iter().enumerate() {
+                rows[j][i] = value;
+            }
+        }
+        Matrix { rows }
+    }
+
+    /// (R × C) と (C × K) の積は (R × K)。C が合わなければ型が合わずエラーになる
+    pub fn mul<const K: usize>(&self, other: &Matrix<C, K>) -> Matrix<R, K> {
+        let mut rows = [[0; K]; R];
+        for (i, row) in rows.iter_mut().enumerate() {
+            for (j, cell) in row.iter_mut().enumerate() {
+                *cell = (0..C).map(|k| self.rows[i][k] * other.rows[k][j]).sum();
+            }
+        }
+        Matrix { rows }
+    }
+}
+
+// 正方行列のときだけ単位行列を作れる
+impl<const N: usize> Matrix<N, N> {
+    pub fn identity() -> Self {
+        let mut rows = [[0; N]; N];
+        for (i, row) in rows.iter_mut().enumerate() {
+            row[i] = 1;
+        }
+        Matrix { rows }
+    }
+}
+
+// 足し算は同じ形どうしだけ
+impl<const R: usize, const C: usize> std::ops::Add for Matrix<R, C> {
+    type Output = Matrix<R, C>;
+
+    fn add(self, other: Self) -> Self::Output {
+        let mut rows = self.rows;
+        for (row, other_row) in rows.iter_mut().zip(other.rows) {
+            for (cell, value) in row.iter_mut().zip(other_row) {
+                *cell += value;
+            }
+        }
+        Matrix { rows }
+    }
+}
+
+impl<const R: usize, const C: usize> Display for Matrix<R, C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, row) in self.rows.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "  [")?;
+            for value in row {
+                write!(f, "{:>4}", value)?;
+            }
+            write!(f, " ]")?;
+        }
+        Ok(())
+    }
+}
+
+/// const ジェネリクスの行列（形をコンパイル時に検査）
+pub fn const_generic_matrix() {
+    println!("\n=== const ジェネリクスの行列（形をコンパイル時に検査） ===");
+
+    let a = Matrix::new([[1, 2, 3], [4, 5, 6]]);
+    let b = Matrix::new([[7, 8], [9, 10], [11, 12]]);
+    println!("a: Matrix<2, 3> {:?}\n{}", a.shape(), a);
+    println!("b: Matrix<3, 2> {:?}\n{}", b.shape(), b);
+
+    let product = a.mul(&b);
+    println!("a.mul(&b): Matrix<2, 2>\n{}", product);
+    let product = b.mul(&a);
+    println!("b.mul(&a): Matrix<3, 3>\n{}", product);
+
+    println!("a.transpose(): Matrix<3, 2>\n{}", a.transpose());
+    println!("a + a:\n{}", a + a);
+
+    let identity = Matrix::<2, 2>::identity();
+    println!("単位行列を掛けても変わらない: {}", identity.mul(&a) == a);
+
+    // 形の間違いは実行時ではなくコンパイル時に見つかる
+    println!("\nコンパイルできない例:");
+    println!("  a.mul(&a)            // error[E0308]: Matrix<3, _> が必要なのに Matrix<2, 3>");
+    println!("  a + b                // error[E0308]: Matrix<2, 3> に Matrix<3, 2> は足せない");
+    println!("  Matrix::<2, 3>::identity()  // error[E0599]: identity は Matrix<N, N> にだけある");
+    println!("Vec<Vec<i32>> で作ると、これらは実行時の assert! や panic! でしか見つけられない");
+}
+
+/// チートシート用のデータ（`cheatsheet traits_generics` で表示）
+pub const CHEATSHEET: CheatSheet = CheatSheet {
+    syntax: &[
+        (
+            "fn largest<T: PartialOrd>(list: &[T]) -> &T",
+            "トレイト境界付きジェネリック関数",
+        ),
+        ("struct Point<T> { x: T, y: T }", "ジェネリック構造体"),
+        (
+            "trait Summary { fn summarize(&self) -> String; }",
+            "トレイト定義",
+        ),
+        ("impl Summary for Tweet {}", "トレイト実装"),
+        ("fn notify(item: &impl Summary)", "impl Trait（引数）"),
+        ("fn f<T>(t: &T) where T: Display + Clone", "where句"),
+        (
+            "fn make() -> impl Summary",
+            "impl Trait（戻り値、具体型は1つ）",
+        ),
+        ("impl<T: Display> Pair<T> {}", "条件付き実装"),
+        ("trait Iterator { type Item; }", "関連型"),
+        ("trait OutlinePrint: Display {}", "スーパートレイト"),
+        (
+            "fn f(x: &dyn Debug)",
+            "トレイトオブジェクト（実体は1つ、動的ディスパッチ）",
+        ),
+        (
+            "fn sum<const N: usize>(a: [i32; N])",
+            "const ジェネリクス（配列の長さを型パラメータに）",
+        ),
+        (
+            "struct Matrix<const R: usize, const C: usize>",
+            "大きさを型に持つ構造体",
+        ),
+    ],
+    methods: &[
+        ("x.to_string()", "Displayのブランケット実装から得られる"),
+        (
+            "expr.accept(&mut visitor)",
+            "ビジターで木をたどる（二重ディスパッチ）",
+        ),
+        (
+            "HashMap<String, Box<dyn Command>>",
+            "名前からトレイトオブジェクトを探して呼ぶ",
+        ),
+        (
+            "impl Add for Point { type Output = Point; }",
+            "演算子オーバーロード",
+        ),
+    ],
+    gotchas: &[
+        "ジェネリクスは単相化されるので実行時コストはないがバイナリは大きくなる",
+        "孤児ルール: 外部のトレイトを外部の型に実装することはできない",
+        "impl Traitを戻り値にしても、分岐で異なる型は返せない",
+        "const ジェネリクスの引数に使えるのは整数・bool・char の定数だけ（安定版）",
+    ],
+};
+
+/// すべてのデモを実行
+pub fn run_all() {
+    println!("╔════════════════════════════════════════════════════════════════╗");
+    println!("║          Rustトレイトとジェネリクスサンプル                      ║");
+    println!("╚════════════════════════════════════════════════════════════════╝");
+
+    lesson_api::run_topic("traits_generics");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn num(n: i64) -> Box<Expr> {
+        Box::new(Expr::Num(n))
+    }
+
+    #[test]
+    fn visitors_agree_with_plain_match() {
+        // -(2 + 3) * 4 + 5
+        let expr = Expr::Add(
+            Box::new(Expr::Mul(
+                Box::new(Expr::Neg(Box::new(Expr::Add(num(2), num(3))))),
+                num(4),
+            )),
+            num(5),
+        );
+        assert_eq!(expr.accept(&mut Evaluator), -15);
+        assert_eq!(expr.accept(&mut PrettyPrinter), "-(2 + 3) * 4 + 5");
+
+        let mut counter = DepthCounter::default();
+        assert_eq!(expr.accept(&mut counter), 5);
+        assert_eq!(counter.nodes, 8);
+    }
+
+    #[test]
+    fn pretty_printer_parenthesizes_only_sums_inside_products() {
+        let expr = Expr::Mul(
+            Box::new(Expr::Add(num(1), num(2))),
+            Box::new(Expr::Neg(num(3))),
+        );
+        assert_eq!(expr.accept(&mut PrettyPrinter), "(1 + 2) * -3");
+        let expr = Expr::Add(Box::new(Expr::Mul(num(1), num(2))), num(3));
+        assert_eq!(expr.accept(&mut PrettyPrinter), "1 * 2 + 3");
+    }
+
+    #[test]
+    fn registry_dispatches_by_name_and_falls_back() {
+        let mut registry = CommandRegistry::new(Unknown);
+        registry.register("echo", Echo);
+        registry.register("sum", Sum);
+        assert_eq!(registry.dispatch("echo a  b"), "a b");
+        assert_eq!(registry.dispatch("sum 1 2 3"), "6");
+        assert!(registry.dispatch("sum 1 x").starts_with("数ではない引数"));
+        // 見つからないときは、コマンド名ごと fallback に渡る
+        assert_eq!(
+            registry.dispatch("hello x"),
+            "不明なコマンドです: \"hello\""
+        );
+
+        // 同じ名前で登録し直すと置き換わる
+        registry.register(
+            "echo",
+            Greet {
+                greeting: "やあ".to_string(),
+            },
+        );
+        assert_eq!(registry.dispatch("echo Ferris"), "やあ、Ferris！");
+        assert_eq!(
+            registry.help(),
+            ["echo   名前に挨拶する", "sum    整数の合計"]
+        );
+    }
+}
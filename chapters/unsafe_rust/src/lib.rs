@@ -0,0 +1,380 @@
+// ============================================================================
+// Rust unsafe サンプル
+// 公式ドキュメント: https://doc.rust-lang.org/book/ch20-01-unsafe-rust.html
+// ============================================================================
+//
+// unsafe ブロックの中では、コンパイラが確かめられない操作ができる。
+// 借用チェックが止まるわけではなく、「ここは人間が正しさを保証する」という印。
+//
+// - 生ポインタ（*const T / *mut T）の参照外し
+// - unsafe な関数（std::ptr::swap、slice::from_raw_parts_mut など）の呼び出し
+// - mem::transmute などによる型の読み替え
+//
+// unsafe を書いたら、前提を // SAFETY: コメントに書き、テストを Miri で動かして
+// 未定義動作（UB）が起きていないか確かめる:
+//   cargo +nightly miri test -p unsafe_rust
+
+use lesson_api::{println, CheatSheet};
+use std::mem;
+use std::ptr;
+use std::slice;
+
+// ----------------------------------------------------------------------------
+// 生ポインタ
+// ----------------------------------------------------------------------------
+
+/// スライスの合計を、先頭のポインタに add(i) して求める
+pub fn sum_by_pointer(values: &[i32]) -> i32 {
+    let start = values.as_ptr();
+    let mut total = 0;
+    for i in 0..values.len() {
+        // SAFETY: i < len なので start.add(i) はスライスの中の要素を指している
+        total += unsafe { *start.add(i) };
+    }
+    total
+}
+
+/// 両端から2つのポインタを近づけながら入れ替えて、スライスを反転する
+pub fn reverse_by_pointer<T>(values: &mut [T]) {
+    let len = values.len();
+    if len < 2 {
+        return;
+    }
+    let mut front = values.as_mut_ptr();
+    // SAFETY: len >= 2 なので、front.add(len - 1) は最後の要素を指す
+    let mut back = unsafe { front.add(len - 1) };
+    while front < back {
+        // SAFETY: front < back で、どちらもスライスの中の別々の要素を指している
+        // ループを抜けるときも、front と back はスライスの範囲（の端）から出ない
+        unsafe {
+            ptr::swap(front, back);
+            front = front.add(1);
+            back = back.sub(1);
+        }
+    }
+}
+
+/// 生ポインタ
+pub fn raw_pointers() {
+    println!("\n=== 生ポインタ ===");
+
+    // 生ポインタを作るだけなら safe。参照外しだけが unsafe
+    let mut n = 5;
+    let r1 = &raw const n;
+    let r2 = &raw mut n;
+    println!(
+        "r1 = {:p}, r2 = {:p}（同じ場所を指す *const と *mut を同時に持てる）",
+        r1, r2
+    );
+    // SAFETY: r1 も r2 も生きている n を指していて、ほかに参照は使われていない
+    unsafe {
+        *r2 += 1;
+        println!("*r2 += 1 のあと *r1 = {}", *r1);
+    }
+
+    // 任意のアドレスからも作れる（参照外しすると未定義動作なので、作るだけ）
+    let address = 0x012345usize;
+    let _anywhere = address as *const i32;
+    let null: *const i32 = ptr::null();
+    // as_ref はヌルなら None を返す（ヌルでなければ有効なポインタだという前提は変わらない）
+    // SAFETY: null はヌルなので、中身を読まれることはない
+    println!(
+        "null.is_null() = {}, as_ref = {:?}",
+        null.is_null(),
+        unsafe { null.as_ref() }
+    );
+
+    // ポインタの足し算: add(i) は i 要素ぶん（i * size_of::<T>() バイト）進む
+    let values = [10, 20, 30, 40];
+    let start = values.as_ptr();
+    // SAFETY: 2 < 4 なのでスライスの中
+    let third = unsafe { start.add(2) };
+    println!(
+        "start = {:p}, start.add(2) = {:p}（{} バイト先）, *start.add(2) = {}",
+        start,
+        third,
+        third as usize - start as usize,
+        // SAFETY: third は values[2] を指している
+        unsafe { *third }
+    );
+    println!("sum_by_pointer({:?}) = {}", values, sum_by_pointer(&values));
+
+    let mut letters = ['r', 'u', 's', 't'];
+    reverse_by_pointer(&mut letters);
+    println!("reverse_by_pointer → {:?}", letters);
+
+    // 範囲の外を指すポインタは、作る（add する）だけでも未定義動作
+    // 例外は末尾の1つ先（start.add(len)）で、比べるのには使えるが参照外しはできない
+    println!("add は割り当ての中か、末尾の1つ先までしか進めてはいけない（wrapping_add は別）");
+}
+
+// ----------------------------------------------------------------------------
+// unsafe を閉じ込めた安全な API
+// ----------------------------------------------------------------------------
+
+/// スライスを mid の位置で2つの可変スライスに分ける（標準の split_at_mut と同じ）
+///
+/// 中では unsafe を使うが、mid を確かめてから呼ぶので、呼ぶ側は unsafe を書かなくてよい。
+/// 範囲外の mid は未定義動作にせず panic する。
+pub fn split_at_mut<T>(values: &mut [T], mid: usize) -> (&mut [T], &mut [T]) {
+    let len = values.len();
+    let ptr = values.as_mut_ptr();
+    assert!(mid <= len, "mid > len");
+
+    // 借用チェッカーは「同じスライスの重ならない2か所」を区別できないので、
+    //   (&mut values[..mid], &mut values[mid..])
+    // は error[E0499]: cannot borrow `*values` as mutable more than once になる
+    // SAFETY: mid <= len なので、[0, mid) と [mid, len) はどちらも values の中で、重ならない。
+    // 返す2つのスライスは values の借用を引き継ぐので、values より長くは生きられない
+    unsafe {
+        (
+            slice::from_raw_parts_mut(ptr, mid),
+            slice::from_raw_parts_mut(ptr.add(mid), len - mid),
+        )
+    }
+}
+
+/// unsafe を閉じ込めた安全な API（split_at_mut）
+pub fn safe_abstraction() {
+    println!("\n=== unsafe を閉じ込めた安全な API（split_at_mut） ===");
+
+    let mut scores = [3, 1, 4, 1, 5, 9];
+    // 2つの可変スライスを同時に使える
+    let (left, right) = split_at_mut(&mut scores, 3);
+    left[0] = 30;
+    right[0] *= 10;
+    left.swap_with_slice(&mut right[..3]);
+    println!("前半と後半を入れ替えたあと: {:?}", scores);
+
+    // 呼ぶ側は unsafe を書かない。間違った mid は panic で止まる（未定義動作にはならない）
+    let mut empty: [i32; 0] = [];
+    let (a, b) = split_at_mut(&mut empty, 0);
+    println!("空のスライスを 0 で分ける: {:?} / {:?}", a, b);
+    // 長さ 3 のスライスを 4 で分けようとすると、from_raw_parts_mut を呼ぶ前の assert! で止まる
+    println!("split_at_mut(&mut [1, 2, 3], 4) は panic（mid > len）で止まり、範囲外を指すスライスは作られない");
+
+    println!("unsafe は小さな関数の中に閉じ込め、前提は関数の中で確かめる");
+    println!("標準ライブラリの split_at_mut、Vec::push なども同じ作り");
+}
+
+// ----------------------------------------------------------------------------
+// transmute と未定義動作
+// ----------------------------------------------------------------------------
+
+/// 色（#[repr(C)] なので、フィールドは書いた順にすき間なく並ぶ）
+#[derive(Debug, Clone, Copy)]
+#[repr(C)]
+pub struct Rgba {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+/// バイトを bool にする。0 と 1 以外は bool として無効なので None
+///
+/// transmute::<u8, bool>(2) は、できあがった値が無効なので即座に未定義動作になる。
+pub fn byte_to_bool(byte: u8) -> Option<bool> {
+    match byte {
+        0 => Some(false),
+        1 => Some(true),
+        _ => None,
+    }
+}
+
+/// transmute の危険と、安全な代わり
+pub fn transmute_hazards() {
+    println!("\n=== transmute の危険と、安全な代わり ===");
+
+    // transmute はビット列をそのまま別の型として読む。大きさが違えばコンパイルエラー
+    let color = Rgba {
+        r: 0x78,
+        g: 0x56,
+        b: 0x34,
+        a: 0x12,
+    };
+    // SAFETY: Rgba は #[repr(C)] の u8 × 4 で u32 と同じ大きさ。どんなビット列も u32 として有効
+    let n: u32 = unsafe { mem::transmute(color) };
+    println!(
+        "transmute::<Rgba, u32> = {:#010x}（エンディアンで結果が変わる）",
+        n
+    );
+    let bytes = [color.r, color.g, color.b, color.a];
+    println!(
+        "u32::from_le_bytes     = {:#010x}（どの環境でも同じ）",
+        u32::from_le_bytes(bytes)
+    );
+    // 整数と浮動小数点数のような読み替えには、transmute を使わない専用のメソッドがある
+    println!(
+        "f32::from_bits(0x3fc00000) = {}",
+        f32::from_bits(0x3fc0_0000)
+    );
+
+    // どのビット列でも有効とは限らない型がある。無効な値を作った時点で未定義動作
+    println!("\n無効な値を作らないように、確かめてから変換する:");
+    for byte in [0u8, 1, 2] {
+        println!("  byte_to_bool({}) = {:?}", byte, byte_to_bool(byte));
+    }
+    for code in [0x41u32, 0xD800, 0x11_0000] {
+        // サロゲート（0xD800..=0xDFFF）と 0x10FFFF より大きい値は char ではない
+        println!("  char::from_u32({:#x}) = {:?}", code, char::from_u32(code));
+    }
+
+    // 再確保のあとに古いポインタを使う（ダングリング）
+    let mut v = Vec::with_capacity(1);
+    v.push(1);
+    let before = v.as_ptr();
+    v.extend([2, 3, 4]);
+    let after = v.as_ptr();
+    // before はもう指してよい場所ではないので、比べるだけにする（読むのは UB）
+    println!(
+        "\nVec を伸ばす前後のポインタ: 同じ = {}（容量を超えると別の場所に移ることがある）",
+        before == after
+    );
+    println!("ポインタはいつでも作り直す。使う直前に as_ptr() し、その間は Vec を変更しない");
+
+    println!("\nほかの典型的な未定義動作:");
+    println!("  - 同じ場所を指す &mut を2つ作る（生ポインタ経由でも駄目）");
+    println!("  - transmute でライフタイムを延ばす（&'a T → &'static T）");
+    println!("  - アラインメントのそろっていないポインタを参照外しする（read_unaligned を使う）");
+}
+
+/// Miri で未定義動作を見つける
+pub fn miri_notes() {
+    println!("\n=== Miri で未定義動作を見つける ===");
+
+    // Miri は MIR（コンパイラの中間表現）を1命令ずつ解釈して、未定義動作を検出する
+    println!("Miri はテストを解釈実行し、ふつうの実行では気付けない未定義動作を見つけます");
+    println!(
+        "この章の unsafe を使う関数はテストから呼んでいるので、次のコマンドで確かめられます:\n"
+    );
+    println!("  rustup toolchain install nightly --component miri");
+    println!("  cargo +nightly miri test -p unsafe_rust");
+    println!("  MIRIFLAGS=-Zmiri-strict-provenance cargo +nightly miri test -p unsafe_rust\n");
+
+    println!("検出できるものの例:");
+    println!("  - 範囲外や解放済みのメモリへのアクセス");
+    println!("  - 無効な値（bool の 2、char のサロゲートなど）");
+    println!("  - 参照の別名の規則（Stacked Borrows / Tree Borrows）違反");
+    println!("  - アラインメントの違反、初期化していないメモリの読み出し");
+    println!("検出できないもの: 実行されなかった経路、FFI の呼び出し先（C のコード）");
+
+    // たとえば sum_by_pointer のループを 0..=values.len() にすると、末尾の1つ先を読む。
+    // ふつうに実行するとたまたまそこにある値が足されるだけだが、Miri は止めて報告する
+    println!(
+        "\n試しに sum_by_pointer のループを `0..=values.len()` に書き換えて Miri を動かすと、"
+    );
+    println!("末尾の1つ先を読んだところで out-of-bounds の未定義動作として報告されます");
+}
+
+/// チートシート用のデータ（`cheatsheet unsafe_rust` で表示）
+pub const CHEATSHEET: CheatSheet = CheatSheet {
+    syntax: &[
+        ("unsafe { *ptr }", "生ポインタを参照外しする"),
+        (
+            "&raw const x / &raw mut x",
+            "参照を経由せずに生ポインタを作る",
+        ),
+        (
+            "// SAFETY: ..",
+            "unsafe が正しい理由を書く（clippy の undocumented_unsafe_blocks）",
+        ),
+        ("unsafe fn f() { .. }", "呼ぶ側が前提を守る必要がある関数"),
+        (
+            "mem::transmute::<A, B>(a)",
+            "ビット列をそのまま別の型として読む（同じ大きさのみ）",
+        ),
+    ],
+    methods: &[
+        (
+            "ptr.add(i) / ptr.sub(i)",
+            "i 要素ぶん進める / 戻す（範囲内に限る）",
+        ),
+        (
+            "ptr.is_null() / ptr.as_ref()",
+            "ヌルかどうか / Option<&T> にする",
+        ),
+        ("ptr::swap(a, b)", "2つのポインタの中身を入れ替える"),
+        (
+            "f32::from_bits / u32::from_le_bytes",
+            "transmute の代わりになる安全な変換",
+        ),
+        ("char::from_u32(n)", "無効な値なら None"),
+        (
+            "slice::from_raw_parts_mut(ptr, len)",
+            "ポインタと長さからスライスを作る（unsafe）",
+        ),
+        (
+            "cargo +nightly miri test",
+            "テストを Miri で実行して UB を探す",
+        ),
+    ],
+    gotchas: &[
+        "範囲外を指すポインタは作るだけで UB（末尾の1つ先は例外）",
+        "無効な値（bool の 2 など）は作った時点で UB。使わなくても駄目",
+        "Vec を変更すると、前に取った as_ptr() は無効になることがある",
+        "unsafe の中でも借用チェックは働く。ゆるむのは5つの操作だけ",
+        "安全な関数の中の unsafe は、どんな引数でも UB にならないよう前提を確かめる",
+    ],
+};
+
+/// すべてのデモを実行
+pub fn run_all() {
+    println!("╔════════════════════════════════════════════════════════════════╗");
+    println!("║          Rust unsafe サンプル                                    ║");
+    println!("╚════════════════════════════════════════════════════════════════╝");
+
+    lesson_api::run_topic("unsafe_rust");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pointer_helpers_stay_in_bounds() {
+        assert_eq!(sum_by_pointer(&[]), 0);
+        assert_eq!(sum_by_pointer(&[1, 2, 3, 4]), 10);
+
+        let mut empty: [u8; 0] = [];
+        reverse_by_pointer(&mut empty);
+        let mut one = [1];
+        reverse_by_pointer(&mut one);
+        assert_eq!(one, [1]);
+        let mut odd = [1, 2, 3, 4, 5];
+        reverse_by_pointer(&mut odd);
+        assert_eq!(odd, [5, 4, 3, 2, 1]);
+        let mut words = vec!["a".to_string(), "b".to_string()];
+        reverse_by_pointer(&mut words);
+        assert_eq!(words, ["b", "a"]);
+    }
+
+    #[test]
+    fn conversions_reject_invalid_values() {
+        assert_eq!(byte_to_bool(1), Some(true));
+        assert_eq!(byte_to_bool(2), None);
+    }
+
+    #[test]
+    fn split_at_mut_matches_std() {
+        for mid in 0..=4 {
+            let mut ours = [1, 2, 3, 4];
+            let mut theirs = ours;
+            let (a, b) = split_at_mut(&mut ours, mid);
+            let (c, d) = theirs.split_at_mut(mid);
+            assert_eq!((&*a, &*b), (&*c, &*d));
+        }
+
+        let mut values = [1, 2, 3, 4];
+        let (left, right) = split_at_mut(&mut values, 2);
+        left[1] = 20;
+        right[0] = 30;
+        assert_eq!(values, [1, 20, 30, 4]);
+    }
+
+    #[test]
+    #[should_panic(expected = "mid > len")]
+    fn split_at_mut_rejects_mid_past_the_end() {
+        split_at_mut(&mut [1, 2, 3], 4);
+    }
+}
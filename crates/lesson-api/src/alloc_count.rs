@@ -0,0 +1,140 @@
+// ============================================================================
+// アロケーションを数えるグローバルアロケータ
+// 公式ドキュメント: https://doc.rust-lang.org/std/alloc/trait.GlobalAlloc.html
+// ============================================================================
+//
+// System アロケータを包んで、確保の回数とバイト数を数える。
+// デモは measure で囲んだ処理が何回ヒープを確保したかを表示できる。
+//
+// #[global_allocator] はプログラム全体で1つだけなので、登録するのは
+// 実行ファイル側（本体の main.rs、ブラウザ版の playground）:
+//
+//   #[global_allocator]
+//   static ALLOCATOR: lesson_api::alloc_count::CountingAlloc = CountingAlloc;
+//
+// 登録されていない（cargo test など）ときは数えられないので、
+// デモは is_installed() を見て表示を切り替える。
+//
+// 数えるのは measure を呼んだスレッドの確保だけなので、ランナーが
+// デモを並列に実行していても、ほかのスレッドの確保は混ざらない。
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    // measure の実行中だけ Some（const で初期化するので、アロケータの中から触っても確保しない）
+    static COUNTS: Cell<Option<AllocStats>> = const { Cell::new(None) };
+}
+
+/// 確保を数える System アロケータ
+pub struct CountingAlloc;
+
+impl CountingAlloc {
+    fn record(&self, size: usize) {
+        // アロケータの中ではヒープを使えない（再帰する）ので、確保しない変数だけを触る
+        INSTALLED.store(true, Ordering::Relaxed);
+        // スレッドの終了処理中は thread_local を使えないことがあるので try_with
+        let _ = COUNTS.try_with(|counts| {
+            if let Some(mut stats) = counts.get() {
+                stats.allocations += 1;
+                stats.bytes += size as u64;
+                counts.set(Some(stats));
+            }
+        });
+    }
+}
+
+// SAFETY: 実際の確保と解放はすべて System に任せている
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        self.record(layout.size());
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        self.record(layout.size());
+        System.alloc_zeroed(layout)
+    }
+
+    // 伸ばした（縮めた）ときも、新しい大きさで1回の確保として数える
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        self.record(new_size);
+        System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+/// measure で数えた結果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AllocStats {
+    /// 確保（alloc / realloc）の回数
+    pub allocations: u64,
+    /// 確保したバイト数の合計（解放した分は引かない）
+    pub bytes: u64,
+}
+
+/// CountingAlloc がグローバルアロケータとして登録されているか
+pub fn is_installed() -> bool {
+    // 登録されていれば、ここで Box を作ったときに INSTALLED が立つ
+    drop(std::hint::black_box(Box::new(0u8)));
+    INSTALLED.load(Ordering::Relaxed)
+}
+
+/// f の実行中に、このスレッドで起きた確保を数える
+/// 入れ子にすると、内側で数えた分は外側にも足される
+pub fn measure<R>(f: impl FnOnce() -> R) -> (R, AllocStats) {
+    let outer = COUNTS.with(|counts| counts.replace(Some(AllocStats::default())));
+    let result = f();
+    let stats = COUNTS.with(|counts| {
+        let stats = counts.get().unwrap_or_default();
+        counts.set(outer.map(|outer| AllocStats {
+            allocations: outer.allocations + stats.allocations,
+            bytes: outer.bytes + stats.bytes,
+        }));
+        stats
+    });
+    (result, stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // テストのバイナリにだけ登録する
+    #[global_allocator]
+    static ALLOCATOR: CountingAlloc = CountingAlloc;
+
+    #[test]
+    fn counts_allocations_while_measuring() {
+        assert!(is_installed());
+        let ((v, inner), outer) = measure(|| {
+            let _first = Vec::<u8>::with_capacity(3);
+            measure(|| Vec::<u32>::with_capacity(10))
+        });
+        assert_eq!(v.capacity(), 10);
+        assert_eq!(
+            inner,
+            AllocStats {
+                allocations: 1,
+                bytes: 40
+            }
+        );
+        assert_eq!(
+            outer,
+            AllocStats {
+                allocations: 2,
+                bytes: 43
+            }
+        );
+
+        // ほかのスレッドの確保は数えない
+        let (_, stats) = measure(|| std::thread::spawn(|| vec![0u8; 1 << 20]).join().unwrap());
+        assert!(stats.bytes < 1 << 20);
+    }
+}
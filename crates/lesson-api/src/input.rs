@@ -0,0 +1,194 @@
+// ============================================================================
+// デモへの入力
+// ============================================================================
+//
+// 入力を求めるデモは io::stdin() を直接読まずに Input を受け取る。
+// 普段は StdinInput で端末から読み、テストや「すべて実行」では
+// ScriptedInput に用意した行を順に渡す。
+//
+//   fn session(input: &mut impl Input) {
+//       print!("数を入力: ");
+//       while let Ok(Some(line)) = input.read_line() { .. }
+//   }
+//
+//   session(&mut StdinInput);                              // 端末から
+//   session(&mut ScriptedInput::new("12\n+\n30\n"));       // 用意した入力で
+//
+// 数を読むなら read_number。範囲外や数でない入力には呼び出し側のメッセージを出して読み直す
+// （メッセージは表示言語に合わせて呼び出し側で作る）:
+//
+//   let Some(n) = input.read_number("1-5: ", 1..=5, |text| format!("{}?", text))? else {
+//       return; // q か入力の終わりなら None
+//   };
+//
+// 出力は output モジュールの print! / println! に書くので、
+// テストでは output::capture で出力もまとめて確かめられる。
+
+use crate::output;
+use std::collections::VecDeque;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+/// 1行ずつ読める入力
+pub trait Input {
+    /// 1行読む（末尾の改行は除く）。入力が終わったら None
+    fn read_line(&mut self) -> io::Result<Option<String>>;
+
+    /// プロンプトを表示してから1行読む
+    fn prompt(&mut self, prompt: &str) -> io::Result<Option<String>> {
+        crate::print!("{}", prompt);
+        self.read_line()
+    }
+
+    /// プロンプトを表示して1行読み、parse で値にする
+    /// parse が Err(メッセージ) を返したら、それを表示して読み直す。入力が終わったら None
+    fn read_parsed<T>(
+        &mut self,
+        prompt: &str,
+        mut parse: impl FnMut(&str) -> Result<T, String>,
+    ) -> io::Result<Option<T>>
+    where
+        Self: Sized,
+    {
+        while let Some(line) = self.prompt(prompt)? {
+            match parse(line.trim()) {
+                Ok(value) => return Ok(Some(value)),
+                Err(message) => crate::println!("{}", message),
+            }
+        }
+        Ok(None)
+    }
+
+    /// range の中の数を読む。q を入力したか、入力が終わったら None
+    /// 数でないか範囲外なら、入力を invalid に渡して作ったメッセージを表示して読み直す
+    fn read_number<T>(
+        &mut self,
+        prompt: &str,
+        range: RangeInclusive<T>,
+        invalid: impl Fn(&str) -> String,
+    ) -> io::Result<Option<T>>
+    where
+        Self: Sized,
+        T: FromStr + PartialOrd,
+    {
+        while let Some(line) = self.prompt(prompt)? {
+            let text = line.trim();
+            if text == "q" {
+                return Ok(None);
+            }
+            match text.parse::<T>() {
+                Ok(n) if range.contains(&n) => return Ok(Some(n)),
+                _ => crate::println!("{}", invalid(text)),
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl<T: Input + ?Sized> Input for &mut T {
+    fn read_line(&mut self) -> io::Result<Option<String>> {
+        (**self).read_line()
+    }
+}
+
+/// 端末（標準入力）から読む
+pub struct StdinInput;
+
+impl Input for StdinInput {
+    fn read_line(&mut self) -> io::Result<Option<String>> {
+        // プロンプトが画面に出てから読む（section でためている出力も書き出す）
+        output::flush();
+        io::stdout().flush()?;
+        let mut line = String::new();
+        if io::stdin().lock().read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        Ok(Some(line.trim_end_matches(['\n', '\r']).to_string()))
+    }
+}
+
+/// 用意した行を順に返す入力（テストや、質問できないときの実演用）
+pub struct ScriptedInput {
+    lines: VecDeque<String>,
+    echo: bool,
+}
+
+impl ScriptedInput {
+    pub fn new(script: &str) -> Self {
+        ScriptedInput {
+            lines: script.lines().map(str::to_string).collect(),
+            echo: false,
+        }
+    }
+
+    /// 読んだ行を、端末で打ち込んだように出力にも書く
+    pub fn echo(mut self) -> Self {
+        self.echo = true;
+        self
+    }
+}
+
+impl Input for ScriptedInput {
+    fn read_line(&mut self) -> io::Result<Option<String>> {
+        let line = self.lines.pop_front();
+        if self.echo {
+            match &line {
+                Some(line) => crate::println!("{}", line),
+                None => crate::println!(),
+            }
+        }
+        Ok(line)
+    }
+}
+
+/// その場で質問できるか（出力をためていない、かつ標準入力が端末）
+pub fn is_interactive() -> bool {
+    !output::is_capturing() && io::stdin().is_terminal()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scripted_input_returns_lines_then_none() {
+        let mut input = ScriptedInput::new("1\r\n\nthree");
+        assert_eq!(input.read_line().unwrap().as_deref(), Some("1"));
+        assert_eq!(input.read_line().unwrap().as_deref(), Some(""));
+        assert_eq!(input.read_line().unwrap().as_deref(), Some("three"));
+        assert_eq!(input.read_line().unwrap(), None);
+
+        let echoed = output::capture(|| {
+            let mut input = ScriptedInput::new("a\nb").echo();
+            while let Ok(Some(_)) = input.read_line() {}
+        });
+        assert_eq!(echoed, "a\nb\n\n");
+    }
+
+    #[test]
+    fn read_number_retries_until_valid() {
+        let invalid = |text: &str| format!("{:?} は 1〜5 ではありません", text);
+        let mut value = None;
+        let printed = output::capture(|| {
+            let mut input = ScriptedInput::new("seven\n0\n 3 \n4\nq\n5");
+            value = input.read_number("n: ", 1..=5, invalid).unwrap();
+            // 残りの "4" は次の呼び出しで読む
+            assert_eq!(input.read_number("n: ", 1..=5, invalid).unwrap(), Some(4));
+            // q でも入力の終わりでも None
+            assert_eq!(
+                input.read_number("n: ", 1..=5, invalid).unwrap(),
+                None::<u32>
+            );
+            assert_eq!(input.read_number("n: ", 1..=5, invalid).unwrap(), Some(5));
+            assert_eq!(
+                input.read_number("n: ", 1..=5, invalid).unwrap(),
+                None::<u32>
+            );
+        });
+        assert_eq!(value, Some(3u32));
+        assert!(printed.contains("\"seven\" は 1〜5 ではありません"));
+        assert!(printed.contains("\"0\" は 1〜5 ではありません"));
+        assert_eq!(printed.matches("n: ").count(), 7);
+    }
+}
@@ -0,0 +1,169 @@
+// ============================================================================
+// レッスンの共通 API
+// ============================================================================
+//
+// 章ごとのクレート（chapters/*）、プラグイン（plugins/*）、本体（ランナー）が
+// 共有する型をまとめたクレート。どのクレートもこのクレートにだけ依存するので、
+// 章のクレートは本体を知らず、本体も章の中身を知らなくてよい。
+//
+//   gkRustPractice（本体）──┬─→ lessons ─→ chapters/basics など ─→ lesson-api
+//                           ├─→ plugins/hello-plugin ─→ lesson-api
+//                           └─→ lesson-api
+//   playground（ブラウザ版）──→ lessons
+//
+// 章のクレートが本体の関数を直接呼ぶと依存が循環してしまうので、
+// run_topic は本体が set_topic_runner で登録した関数を呼ぶ（依存性の逆転）。
+//
+// プラグインは Demo / LessonModule トレイトを実装する:
+//
+//   struct Hello;
+//
+//   impl Demo for Hello {
+//       fn name(&self) -> &'static str { "hello" }
+//       fn title(&self) -> &'static str { "あいさつ" }
+//       fn run(&self) { println!("こんにちは"); }
+//   }
+//
+// API を壊す変更をしたら API_VERSION を上げる。ランナーはバージョンの
+// 合わないプラグインを読み込まずに警告する。
+
+use std::sync::OnceLock;
+
+pub mod alloc_count;
+pub mod input;
+pub mod output;
+pub mod panics;
+pub mod random;
+pub mod reading;
+pub mod text;
+pub mod timing;
+
+/// この API のバージョン
+pub const API_VERSION: u32 = 1;
+
+/// 1章分のチートシート
+pub struct CheatSheet {
+    /// 構文の形（コード, 説明）
+    pub syntax: &'static [(&'static str, &'static str)],
+    /// よく使うメソッド・関数（コード, 説明）
+    pub methods: &'static [(&'static str, &'static str)],
+    /// ハマりどころ
+    pub gotchas: &'static [&'static str],
+}
+
+// 本体が登録する「トピックのデモを順に実行する」関数
+static TOPIC_RUNNER: OnceLock<fn(&str)> = OnceLock::new();
+
+/// トピックの実行方法を登録する（本体の main で1回だけ呼ぶ）
+pub fn set_topic_runner(runner: fn(&str)) {
+    let _ = TOPIC_RUNNER.set(runner);
+}
+
+/// トピックのデモを、難易度フィルタなどを考慮して順に実行する
+/// 各章の run_all() から呼ばれる
+pub fn run_topic(key: &str) {
+    match TOPIC_RUNNER.get() {
+        Some(run) => run(key),
+        None => eprintln!("トピック {} を実行できません（ランナーが未登録です）", key),
+    }
+}
+
+/// デモの難易度
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Beginner,
+    Intermediate,
+    Advanced,
+}
+
+/// メニューから実行できる1つのデモ
+///
+/// 見出し（`=== タイトル ===`）はランナーが表示するので、run では本文だけを出力する。
+pub trait Demo: Send + Sync {
+    /// "トピック::デモ名" の ID に使う名前（英数字と _）
+    fn name(&self) -> &'static str;
+
+    /// メニューや見出しに表示するタイトル
+    fn title(&self) -> &'static str;
+
+    fn level(&self) -> Level {
+        Level::Beginner
+    }
+
+    fn run(&self);
+}
+
+/// デモをまとめた1つのレッスン（メニューの1項目になる）
+pub trait LessonModule: Send + Sync {
+    /// 組み込みのトピックと重ならないキー（英数字と _）
+    fn key(&self) -> &'static str;
+
+    fn title(&self) -> &'static str;
+
+    /// 章の表示（既定は "拡張"）
+    fn chapter(&self) -> &'static str {
+        "拡張"
+    }
+
+    /// 先に学んでおくべきトピックのキー（組み込みのトピックも指定できる）
+    fn requires(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// このプラグインが対応している API のバージョン
+    fn api_version(&self) -> u32 {
+        API_VERSION
+    }
+
+    /// メニューに並べる順のデモ
+    fn demos(&self) -> Vec<Box<dyn Demo>>;
+}
+
+/// 関数1つでデモを定義するための実装
+///
+/// ```
+/// use lesson_api::{Demo, FnDemo, Level};
+///
+/// fn hello() {
+///     println!("こんにちは");
+/// }
+///
+/// let demo = FnDemo::new("hello", "あいさつ", Level::Beginner, hello);
+/// assert_eq!(demo.name(), "hello");
+/// demo.run();
+/// ```
+pub struct FnDemo {
+    name: &'static str,
+    title: &'static str,
+    level: Level,
+    run: fn(),
+}
+
+impl FnDemo {
+    pub const fn new(name: &'static str, title: &'static str, level: Level, run: fn()) -> Self {
+        FnDemo {
+            name,
+            title,
+            level,
+            run,
+        }
+    }
+}
+
+impl Demo for FnDemo {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn title(&self) -> &'static str {
+        self.title
+    }
+
+    fn level(&self) -> Level {
+        self.level
+    }
+
+    fn run(&self) {
+        (self.run)()
+    }
+}
@@ -0,0 +1,254 @@
+// ============================================================================
+// デモの出力先
+// ============================================================================
+//
+// 章のクレートは `use lesson_api::{print, println};` で標準の println! を
+// このモジュールのマクロに置き換えている。書き方は標準とまったく同じで、
+// 普段は標準出力に書き、capture の中で実行したときだけ文字列にためる。
+//
+//   let text = lesson_api::output::capture(|| basics::variables_demo());
+//
+// wasm32-unknown-unknown には標準出力が無い（println! の出力は捨てられる）ので、
+// ブラウザ版（crates/playground）はこの仕組みでデモの出力を受け取る。
+//
+// 標準の println! は1行ごとに write を呼ぶ（Stdout は行単位でフラッシュする）。
+// 「すべて実行」では何千回にもなるので、ランナーはデモを section の中で実行し、
+// その間の出力を共有の BufWriter<Stdout> にためて、デモの終わりにまとめて書き出す。
+//
+//   lesson_api::output::section(demo.run);
+//
+// 読み仮名の表示（reading::set_mode）を有効にすると、書き出す前に用語へ読みを添える。
+// 翻訳（set_translator）を設定すると、書き出す前に1行ずつ訳に置き換える。
+
+use crate::reading::{self, Mode};
+use std::cell::{Cell, RefCell};
+use std::fmt::{self, Write};
+use std::io::{self, BufWriter, Stdout, Write as _};
+use std::sync::{Mutex, OnceLock};
+
+thread_local! {
+    // capture の実行中だけ Some（スレッドごとに別のバッファ）
+    static CAPTURE: RefCell<Option<String>> = const { RefCell::new(None) };
+
+    // このスレッドで実行中の section の深さ（0 なら標準の print! に任せる）
+    static SECTION_DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+/// section の出力をためる容量（これを超えると途中でも書き出す）
+const BUFFER_CAPACITY: usize = 64 * 1024;
+
+// すべてのスレッドで共有する標準出力のバッファ（最初の section で作る）
+static STDOUT: Mutex<Option<BufWriter<Stdout>>> = Mutex::new(None);
+
+// 出力の1行（行頭の空白を除いたもの）から訳を探す関数（ランナーが表示言語に合わせて設定する）
+static TRANSLATOR: OnceLock<fn(&str) -> Option<&'static str>> = OnceLock::new();
+
+/// デモの出力を訳す関数を設定する（最初の1回だけ有効）
+/// 訳が見つかった行だけを置き換え、行頭の空白（字下げ）はそのまま残す
+pub fn set_translator(translate: fn(&str) -> Option<&'static str>) {
+    let _ = TRANSLATOR.set(translate);
+}
+
+fn translate_lines(text: &str, translate: fn(&str) -> Option<&'static str>) -> String {
+    let lines: Vec<String> = text
+        .split('\n')
+        .map(|line| {
+            let body = line.trim_start();
+            match translate(body) {
+                Some(translated) if !body.is_empty() => {
+                    format!("{}{}", &line[..line.len() - body.len()], translated)
+                }
+                _ => line.to_string(),
+            }
+        })
+        .collect();
+    lines.join("\n")
+}
+
+/// f の実行中の出力を BufWriter にため、終わったら（panic しても）書き出す
+/// 入れ子にした場合は一番外側の終わりで書き出す
+pub fn section(f: impl FnOnce()) {
+    struct Leave;
+    impl Drop for Leave {
+        fn drop(&mut self) {
+            let depth = SECTION_DEPTH.with(|depth| {
+                depth.set(depth.get() - 1);
+                depth.get()
+            });
+            if depth == 0 {
+                flush();
+            }
+        }
+    }
+
+    SECTION_DEPTH.with(|depth| depth.set(depth.get() + 1));
+    let _leave = Leave;
+    f();
+}
+
+/// ためている出力を書き出す（section の外から書く前などに呼ぶ）
+pub fn flush() {
+    let mut stdout = STDOUT.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(writer) = stdout.as_mut() {
+        let _ = writer.flush();
+    }
+}
+
+// section の中ならバッファに書いて true を返す
+fn write_buffered(args: fmt::Arguments, newline: bool) -> bool {
+    if SECTION_DEPTH.with(Cell::get) == 0 {
+        return false;
+    }
+    let mut stdout = STDOUT.lock().unwrap_or_else(|e| e.into_inner());
+    let writer =
+        stdout.get_or_insert_with(|| BufWriter::with_capacity(BUFFER_CAPACITY, io::stdout()));
+    // 標準の print! と違い、パイプが閉じられたなどのエラーは無視する
+    let _ = writer.write_fmt(args);
+    if newline {
+        let _ = writer.write_all(b"\n");
+    }
+    true
+}
+
+/// f の実行中に print! / println! で書いた内容を文字列で返す
+/// （入れ子にした場合は内側の capture が受け取る）
+pub fn capture(f: impl FnOnce()) -> String {
+    // f が panic しても外側のバッファに戻す
+    struct Restore(Option<String>);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            let outer = self.0.take();
+            CAPTURE.with(|cell| *cell.borrow_mut() = outer);
+        }
+    }
+
+    let outer = CAPTURE.with(|cell| cell.borrow_mut().replace(String::new()));
+    let restore = Restore(outer);
+    f();
+    let text = CAPTURE.with(|cell| cell.borrow_mut().take());
+    drop(restore);
+    text.unwrap_or_default()
+}
+
+/// このスレッドで capture の実行中か
+/// 出力がすぐには画面に出ないので、デモは入力を求める前にこれを確かめる
+pub fn is_capturing() -> bool {
+    CAPTURE.with(|cell| cell.borrow().is_some())
+}
+
+// capture 中ならバッファに書いて true を返す
+fn write_captured(args: fmt::Arguments, newline: bool) -> bool {
+    CAPTURE.with(|cell| match cell.borrow_mut().as_mut() {
+        Some(buffer) => {
+            let _ = buffer.write_fmt(args);
+            if newline {
+                buffer.push('\n');
+            }
+            true
+        }
+        None => false,
+    })
+}
+
+// capture でも section でもなければ標準のマクロに任せる（cargo test の出力キャプチャもそのまま効く）
+fn write(args: fmt::Arguments, newline: bool) {
+    if write_captured(args, newline) || write_buffered(args, newline) {
+        return;
+    }
+    if newline {
+        std::println!("{}", args);
+    } else {
+        std::print!("{}", args);
+    }
+}
+
+// 訳すときと読み仮名を添えるときだけ、いったん文字列にしてから書く
+fn write_annotated(args: fmt::Arguments, newline: bool) {
+    let translator = TRANSLATOR.get();
+    let mode = reading::mode();
+    if translator.is_none() && mode == Mode::Off {
+        return write(args, newline);
+    }
+    let mut text = args.to_string();
+    if let Some(translate) = translator {
+        text = translate_lines(&text, *translate);
+    }
+    if mode != Mode::Off {
+        text = reading::annotate(&text, mode);
+    }
+    write(format_args!("{}", text), newline);
+}
+
+#[doc(hidden)]
+pub fn _print(args: fmt::Arguments) {
+    write_annotated(args, false);
+}
+
+#[doc(hidden)]
+pub fn _println(args: fmt::Arguments) {
+    write_annotated(args, true);
+}
+
+/// 標準の print! と同じ書き方で、出力先だけを差し替えられるマクロ
+#[macro_export]
+macro_rules! print {
+    ($($arg:tt)*) => {
+        $crate::output::_print(::std::format_args!($($arg)*))
+    };
+}
+
+/// 標準の println! と同じ書き方で、出力先だけを差し替えられるマクロ
+///
+/// 章のクレートは `use lesson_api::{println, CheatSheet};` のように取り込む。
+/// 取り込んだ println! が標準のものを隠すので、デモの本文は標準と同じ書き方のままで、
+/// capture（ブラウザ版や save）と section（すべて実行）の出力先の切り替えに乗る。
+#[macro_export]
+macro_rules! println {
+    () => {
+        $crate::output::_println(::std::format_args!(""))
+    };
+    ($($arg:tt)*) => {
+        $crate::output::_println(::std::format_args!($($arg)*))
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{capture, translate_lines};
+
+    #[test]
+    fn captures_nested_output_and_recovers_from_panic() {
+        let outer = capture(|| {
+            crate::println!("外側 {}", 1);
+            let inner = capture(|| crate::print!("内側"));
+            assert_eq!(inner, "内側");
+            crate::println!();
+        });
+        assert_eq!(outer, "外側 1\n\n");
+
+        // section の中でも capture が優先される
+        super::section(|| assert_eq!(capture(|| crate::println!("中")), "中\n"));
+
+        let result = std::panic::catch_unwind(|| capture(|| panic!("失敗")));
+        assert!(result.is_err());
+        assert_eq!(capture(|| crate::print!("あと")), "あと");
+    }
+
+    #[test]
+    fn translates_whole_lines_and_keeps_the_indent() {
+        fn english(line: &str) -> Option<&'static str> {
+            match line {
+                "=== 所有権の基本 ===" => Some("=== Ownership basics ==="),
+                "ムーブしました" => Some("moved"),
+                _ => None,
+            }
+        }
+        assert_eq!(
+            translate_lines(
+                "\n=== 所有権の基本 ===\n  ムーブしました\ns = hello",
+                english
+            ),
+            "\n=== Ownership basics ===\n  moved\ns = hello"
+        );
+    }
+}
@@ -0,0 +1,100 @@
+// ============================================================================
+// デモの中で panic を捕まえる
+// ============================================================================
+//
+// testing や interior_mutability の章は、わざと panic させて、そのメッセージを表示する。
+// catch_unwind だけだと、既定のパニックフックが標準エラーにメッセージを書いてしまう。
+//
+// パニックフックはプロセスに1つしかない。呼ぶたびに take_hook / set_hook で差し替えると、
+// 「すべて実行」（スレッドで並列にデモを実行する）で差し替えが交差し、
+// 空のフックが残って他のスレッドの panic まで黙ってしまうことがある。
+//
+// そこで、最初に使うときに一度だけ「元のフックを包んだフック」を設定し、
+// catch_silently の実行中のスレッドでだけ、スレッドローカルの印を見て黙らせる。
+//
+//   match lesson_api::panics::catch_silently(|| cell.borrow_mut()) {
+//       Ok(_) => println!("借用できました"),
+//       Err(message) => println!("panic: {}", message),
+//   }
+//
+// wasm32-unknown-unknown（ブラウザ版）では panic から戻れない（その場で止まる）ので、
+// デモは先に panics_are_fatal() で確かめる。
+
+use std::any::Any;
+use std::cell::Cell;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Once;
+
+thread_local! {
+    // このスレッドで実行中の catch_silently の深さ（0 でなければフックは何も書かない）
+    static SUPPRESS: Cell<usize> = const { Cell::new(0) };
+}
+
+static INSTALL_HOOK: Once = Once::new();
+
+// 元のフックを包んだフックを一度だけ設定する
+fn install_hook() {
+    INSTALL_HOOK.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if SUPPRESS.with(Cell::get) == 0 {
+                previous(info);
+            }
+        }));
+    });
+}
+
+/// f を実行して、panic したらメッセージを Err で返す
+/// 実行中のスレッドの panic だけ、パニックフックの出力を黙らせる
+pub fn catch_silently<R>(f: impl FnOnce() -> R) -> Result<R, String> {
+    install_hook();
+    SUPPRESS.with(|depth| depth.set(depth.get() + 1));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    SUPPRESS.with(|depth| depth.set(depth.get() - 1));
+    result.map_err(|payload| message(payload.as_ref()))
+}
+
+/// panic の値（payload）をメッセージにする
+/// panic!("...") の引数は &str か String で届く
+pub fn message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "panic しました".to_string())
+}
+
+/// panic から戻れない環境（ブラウザ版）なら、説明を表示して true を返す
+pub fn panics_are_fatal() -> bool {
+    let fatal = cfg!(target_arch = "wasm32");
+    if fatal {
+        crate::println!(
+            "（ブラウザ版では panic から戻れないので、panic する例は cargo run で試してください）"
+        );
+    }
+    fatal
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn returns_the_value_or_the_panic_message() {
+        assert_eq!(catch_silently(|| 1 + 1), Ok(2));
+        assert_eq!(
+            catch_silently(|| panic!("静かに")),
+            Err::<(), _>("静かに".to_string())
+        );
+        let n = 3;
+        assert_eq!(
+            catch_silently(|| panic!("{} 回目", n)),
+            Err::<(), _>("3 回目".to_string())
+        );
+
+        // 入れ子にしても、終わったら印は元に戻る
+        let inner = catch_silently(|| catch_silently(|| panic!("内側")));
+        assert_eq!(inner, Ok(Err("内側".to_string())));
+        assert_eq!(SUPPRESS.with(Cell::get), 0);
+    }
+}
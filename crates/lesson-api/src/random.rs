@@ -0,0 +1,54 @@
+// ============================================================================
+// 簡易乱数生成器
+// ============================================================================
+//
+// カードのシャッフルなどに使う xorshift64*。
+// 暗号用途には使えないが、外部クレートなしで「それなりにばらける」順序が得られる。
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    /// 指定したシードで作る（0 は xorshift の不動点なので避ける）
+    pub fn new(seed: u64) -> Self {
+        Rng {
+            state: if seed == 0 {
+                0x9E37_79B9_7F4A_7C15
+            } else {
+                seed
+            },
+        }
+    }
+
+    /// 現在時刻からシードを作る
+    pub fn from_time() -> Self {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0);
+        Rng::new(nanos)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    /// 0..n の範囲の値
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    /// Fisher-Yates シャッフル
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.below(i + 1);
+            items.swap(i, j);
+        }
+    }
+}
@@ -0,0 +1,189 @@
+// ============================================================================
+// 用語の読み仮名（ふりがな・ローマ字）
+// ============================================================================
+//
+// 日本語を母語としない学習者向けに、デモの出力に出てくる主な技術用語へ
+// 読みを添える。output の print! / println! が書き出す前にこの annotate を通す。
+//
+//   所有権と借用          （既定）
+//   所有権（しょゆうけん）と借用（しゃくよう）   set_mode(Mode::Furigana)
+//   所有権（shoyūken）と借用（shakuyō）          set_mode(Mode::Romaji)
+//
+// 端末ではルビを振れないので、用語の直後に括弧で書く。
+// 同じ用語が1行に何度出ても、読みを添えるのは最初の1回だけ。枠で囲んだ見出しの行には添えない。
+// 「可変参照」と「参照」のように重なる用語は長い方を優先する。
+
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// 読みの添え方
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    Off,
+    Furigana,
+    Romaji,
+}
+
+impl Mode {
+    /// コマンドライン引数やメニュー入力から読み取る
+    pub fn parse(s: &str) -> Option<Mode> {
+        match s.trim().to_lowercase().as_str() {
+            "off" | "なし" => Some(Mode::Off),
+            "furigana" | "kana" | "ふりがな" => Some(Mode::Furigana),
+            "romaji" | "ローマ字" => Some(Mode::Romaji),
+            _ => None,
+        }
+    }
+}
+
+static MODE: AtomicU8 = AtomicU8::new(0);
+
+/// 読みの添え方を変える（すべてのスレッドの出力に効く）
+pub fn set_mode(mode: Mode) {
+    MODE.store(mode as u8, Ordering::Relaxed);
+}
+
+pub fn mode() -> Mode {
+    match MODE.load(Ordering::Relaxed) {
+        1 => Mode::Furigana,
+        2 => Mode::Romaji,
+        _ => Mode::Off,
+    }
+}
+
+/// 用語、ふりがな、ローマ字（ヘボン式、長音は ā ū ō）
+/// 前から順に試すので、ほかの用語で始まる長い用語（"可変参照" と "可変"）は先に書く
+#[rustfmt::skip]
+pub const TERMS: &[(&str, &str, &str)] = &[
+    ("所有権", "しょゆうけん", "shoyūken"),
+    ("所有者", "しょゆうしゃ", "shoyūsha"),
+    ("借用", "しゃくよう", "shakuyō"),
+    ("可変参照", "かへんさんしょう", "kahen sanshō"),
+    ("参照外し", "さんしょうはずし", "sanshō hazushi"),
+    ("参照カウント", "さんしょうカウント", "sanshō kaunto"),
+    ("循環参照", "じゅんかんさんしょう", "junkan sanshō"),
+    ("参照", "さんしょう", "sanshō"),
+    ("可変", "かへん", "kahen"),
+    ("不変", "ふへん", "fuhen"),
+    ("変数", "へんすう", "hensū"),
+    ("定数", "ていすう", "teisū"),
+    ("関数", "かんすう", "kansū"),
+    ("引数", "ひきすう", "hikisū"),
+    ("戻り値", "もどりち", "modorichi"),
+    ("整数", "せいすう", "seisū"),
+    ("浮動小数点数", "ふどうしょうすうてんすう", "fudō shōsūtensū"),
+    ("真偽値", "しんぎち", "shingichi"),
+    ("文字列", "もじれつ", "mojiretsu"),
+    ("配列", "はいれつ", "hairetsu"),
+    ("演算子", "えんざんし", "enzanshi"),
+    ("構造体", "こうぞうたい", "kōzōtai"),
+    ("列挙型", "れっきょがた", "rekkyogata"),
+    ("関連型", "かんれんがた", "kanrengata"),
+    ("型強制", "かたきょうせい", "kata kyōsei"),
+    ("束縛", "そくばく", "sokubaku"),
+    ("網羅性", "もうらせい", "mōrasei"),
+    ("分解", "ぶんかい", "bunkai"),
+    ("境界", "きょうかい", "kyōkai"),
+    ("単相化", "たんそうか", "tansōka"),
+    ("再帰", "さいき", "saiki"),
+    ("遅延評価", "ちえんひょうか", "chien hyōka"),
+    ("静的", "せいてき", "seiteki"),
+    ("動的", "どうてき", "dōteki"),
+    ("確保", "かくほ", "kakuho"),
+    ("解放", "かいほう", "kaihō"),
+    ("並行", "へいこう", "heikō"),
+    ("並列", "へいれつ", "heiretsu"),
+    ("非同期", "ひどうき", "hidōki"),
+    ("排他制御", "はいたせいぎょ", "haita seigyo"),
+    ("競合", "きょうごう", "kyōgō"),
+    ("共有", "きょうゆう", "kyōyū"),
+    ("宣言的", "せんげんてき", "sengenteki"),
+    ("手続き的", "てつづきてき", "tetsuzukiteki"),
+    ("未定義動作", "みていぎどうさ", "miteigi dōsa"),
+    ("生ポインタ", "なまポインタ", "nama pointa"),
+];
+
+/// text の用語に、mode に合わせて読みを添える（Off ならそのまま）
+pub fn annotate(text: &str, mode: Mode) -> String {
+    if mode == Mode::Off {
+        return text.to_string();
+    }
+    let mut out = String::with_capacity(text.len() * 2);
+    for (i, line) in text.split('\n').enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+        // 枠で囲んだ見出し（║ ... ║）は幅を合わせてあるので、崩さないようそのままにする
+        if line.trim_start().starts_with('║') {
+            out.push_str(line);
+        } else {
+            annotate_line(line, mode, &mut out);
+        }
+    }
+    out
+}
+
+fn annotate_line(line: &str, mode: Mode, out: &mut String) {
+    let mut annotated: Vec<&str> = Vec::new();
+    let mut rest = line;
+    while let Some(c) = rest.chars().next() {
+        match TERMS.iter().find(|(term, _, _)| rest.starts_with(term)) {
+            Some(&(term, kana, romaji)) => {
+                out.push_str(term);
+                if !annotated.contains(&term) {
+                    annotated.push(term);
+                    let reading = if mode == Mode::Furigana { kana } else { romaji };
+                    out.push('（');
+                    out.push_str(reading);
+                    out.push('）');
+                }
+                rest = &rest[term.len()..];
+            }
+            None => {
+                out.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn annotates_each_term_once_per_line() {
+        assert_eq!(
+            annotate("所有権と借用", Mode::Furigana),
+            "所有権（しょゆうけん）と借用（しゃくよう）"
+        );
+        assert_eq!(
+            annotate("可変参照は1つ、参照は何個でも", Mode::Romaji),
+            "可変参照（kahen sanshō）は1つ、参照（sanshō）は何個でも"
+        );
+        assert_eq!(
+            annotate("参照と参照\n参照", Mode::Furigana),
+            "参照（さんしょう）と参照\n参照（さんしょう）"
+        );
+        assert_eq!(annotate("所有権", Mode::Off), "所有権");
+        assert_eq!(annotate("║  所有権  ║", Mode::Romaji), "║  所有権  ║");
+        assert_eq!(annotate("let x = 5;", Mode::Furigana), "let x = 5;");
+
+        assert_eq!(Mode::parse("Romaji"), Some(Mode::Romaji));
+        assert_eq!(Mode::parse("ふりがな"), Some(Mode::Furigana));
+        assert_eq!(Mode::parse("ruby"), None);
+    }
+
+    #[test]
+    fn longer_terms_come_before_their_prefixes() {
+        for (i, (term, _, _)) in TERMS.iter().enumerate() {
+            for (later, _, _) in &TERMS[i + 1..] {
+                assert!(
+                    !later.starts_with(term),
+                    "{} は {} より前に書く",
+                    later,
+                    term
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,129 @@
+// ============================================================================
+// 表示幅と数値の書式
+// ============================================================================
+//
+// 端末では全角文字（漢字、かな、全角英数）が半角2文字分の幅で表示される。
+// {:<10} のような幅指定は文字数で数えるので、日本語を含む表やバナーは列がずれる。
+// ここでは表示幅で数える関数と、それを使った寄せ（左・右・中央）を用意する。
+//
+//   format!("{:<6}|", "所有権")   → "所有権   |"（表示幅 9、3つ余計）
+//   pad_right("所有権", 6) + "|"   → "所有権|"  （表示幅 6）
+//
+// 幅の判定は Unicode の East Asian Width を簡略化したもの。
+// 罫線（═ ║ など）のように環境で幅が変わる文字は 1 として扱う。
+
+/// 1文字の表示幅（結合文字などは 0、全角は 2、それ以外は 1）
+pub fn char_width(c: char) -> usize {
+    match c as u32 {
+        // 結合用の記号、ゼロ幅スペースなど、異体字セレクタ
+        0x0300..=0x036F | 0x200B..=0x200F | 0x3099..=0x309A | 0xFE00..=0xFE0F => 0,
+        0x1100..=0x115F // ハングルの字母
+        | 0x2E80..=0x303E // CJK の部首、全角の句読点
+        | 0x3041..=0x3098 | 0x309B..=0x33FF // かな、CJK の記号
+        | 0x3400..=0x4DBF | 0x4E00..=0x9FFF // CJK 統合漢字
+        | 0xA000..=0xA4CF // イ文字
+        | 0xAC00..=0xD7A3 // ハングル
+        | 0xF900..=0xFAFF // CJK 互換漢字
+        | 0xFE30..=0xFE4F // CJK 互換形
+        | 0xFF00..=0xFF60 | 0xFFE0..=0xFFE6 // 全角英数と記号
+        | 0x1F300..=0x1F64F | 0x1F900..=0x1F9FF // 絵文字
+        | 0x20000..=0x3FFFD => 2, // CJK 統合漢字の拡張
+        _ => 1,
+    }
+}
+
+/// 文字列の表示幅
+pub fn display_width(s: &str) -> usize {
+    s.chars().map(char_width).sum()
+}
+
+/// 表示幅が width になるよう右に空白を足す（左寄せ。長ければそのまま）
+pub fn pad_right(s: &str, width: usize) -> String {
+    let padding = width.saturating_sub(display_width(s));
+    format!("{}{}", s, " ".repeat(padding))
+}
+
+/// 表示幅が width になるよう左に空白を足す（右寄せ。長ければそのまま）
+pub fn pad_left(s: &str, width: usize) -> String {
+    let padding = width.saturating_sub(display_width(s));
+    format!("{}{}", " ".repeat(padding), s)
+}
+
+/// 表示幅が width になるよう両側に空白を足す（余りの1つは右に足す）
+pub fn center(s: &str, width: usize) -> String {
+    let space = width.saturating_sub(display_width(s));
+    let left = space / 2;
+    format!("{}{}{}", " ".repeat(left), s, " ".repeat(space - left))
+}
+
+/// 表示幅が width を超えないよう切り詰める（切ったときは末尾を … にする）
+pub fn truncate_to_width(s: &str, width: usize) -> String {
+    if display_width(s) <= width {
+        return s.to_string();
+    }
+    let mut out = String::new();
+    let mut used = 0;
+    for c in s.chars() {
+        // … の分（幅 1）を残しておく
+        if used + char_width(c) + 1 > width {
+            break;
+        }
+        used += char_width(c);
+        out.push(c);
+    }
+    if width > 0 {
+        out.push('…');
+    }
+    out
+}
+
+/// 整数の桁を group 桁ごとに区切る
+/// group_digits(1234567, 3, ',') → "1,234,567"、group_digits(12345678, 4, ',') → "1234,5678"
+pub fn group_digits(n: i64, group: usize, separator: char) -> String {
+    assert!(group > 0, "group must be non-zero");
+    // i64::MIN も扱えるよう、絶対値は u64 で取る
+    let digits = n.unsigned_abs().to_string();
+    let mut out = String::with_capacity(digits.len() * 2);
+    if n < 0 {
+        out.push('-');
+    }
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(group) {
+            out.push(separator);
+        }
+        out.push(c);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn widths_count_full_width_as_two() {
+        assert_eq!(display_width("Rust"), 4);
+        assert_eq!(display_width("所有権"), 6);
+        assert_eq!(display_width("ＡＢ"), 4);
+        assert_eq!(display_width("ｶﾀｶﾅ"), 4);
+        // が を「か + 濁点（結合文字）」で書いても幅は同じ
+        assert_eq!(display_width("が"), display_width("か\u{3099}"));
+
+        assert_eq!(pad_right("所有権", 8), "所有権  ");
+        assert_eq!(pad_left("42", 5), "   42");
+        assert_eq!(center("型", 7), "  型   ");
+        assert_eq!(pad_right("ライフタイム", 4), "ライフタイム");
+        assert_eq!(truncate_to_width("ライフタイム", 7), "ライフ…");
+        assert_eq!(truncate_to_width("ok", 7), "ok");
+    }
+
+    #[test]
+    fn digits_are_grouped_from_the_right() {
+        assert_eq!(group_digits(0, 3, ','), "0");
+        assert_eq!(group_digits(999, 3, ','), "999");
+        assert_eq!(group_digits(1_234_567, 3, ','), "1,234,567");
+        assert_eq!(group_digits(-1_234_567, 3, '.'), "-1.234.567");
+        assert_eq!(group_digits(123_456_789, 4, ','), "1,2345,6789");
+        assert_eq!(group_digits(i64::MIN, 3, ','), "-9,223,372,036,854,775,808");
+    }
+}
@@ -0,0 +1,37 @@
+// ============================================================================
+// 処理にかかった時間を測る
+// ============================================================================
+//
+// 速度を比べるデモ（collections、iterators_closures、algorithms など）が使う。
+//
+//   let (sum, time) = lesson_api::timing::stopwatch(|| v.iter().sum::<u64>());
+//   println!("合計 {}（{}）", sum, time);
+//
+// wasm32-unknown-unknown（ブラウザ版）には時計が無く、Instant::now() が panic するので、
+// 時間は "-" にする。
+
+use std::time::Instant;
+
+/// f の結果と、かかった時間（"1.234 ms"）を返す
+pub fn stopwatch<R>(f: impl FnOnce() -> R) -> (R, String) {
+    if cfg!(target_arch = "wasm32") {
+        return (f(), "-".to_string());
+    }
+    let start = Instant::now();
+    let result = f();
+    let time = format!("{:.3} ms", start.elapsed().as_secs_f64() * 1000.0);
+    (result, time)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn returns_the_result_and_the_time_in_milliseconds() {
+        let (sum, time) = stopwatch(|| (1..=10).sum::<u32>());
+        assert_eq!(sum, 55);
+        let ms = time.strip_suffix(" ms").unwrap();
+        assert!(ms.parse::<f64>().unwrap() >= 0.0);
+    }
+}
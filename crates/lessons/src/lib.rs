@@ -0,0 +1,938 @@
+// ============================================================================
+// レッスン一覧（トピックとデモのレジストリ）
+// ============================================================================
+//
+// すべてのトピック（章）とデモのメタデータを一元管理するライブラリ。
+// 端末のランナー（src/）とブラウザ版（crates/playground）の両方がこの一覧を使う。
+// 表示言語やメニューなど端末だけのことはランナーの registry.rs が受け持つ。
+//
+// 新しいデモを追加したら、ここに1行追加するのを忘れないこと。
+// そのとき .added_in(n) に今のコンテンツのバージョン（content_version()）より1つ大きい値を書くと、
+// `whatsnew` で新しいデモとして案内される。
+
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use lesson_api::CheatSheet;
+
+// 埋め込み用の API（GUI やノートブックからレッスンを実行して結果を受け取る）
+mod report;
+pub use report::{run_lesson, LessonReport, Outcome, Section};
+
+// デモ関数は章ごとのクレート（chapters/*）にある。依存に追加したクレートは
+// use しなくても basics::variables_demo のようにクレート名で参照できる。
+// ランナーからも lessons::basics のように使えるよう再公開しておく
+pub use {
+    advanced_traits, advanced_types, algorithms, async_await, basics, build_system, collections,
+    concurrency, data_structures, error_handling, file_io, graphs, interior_mutability,
+    iterators_closures, lifetimes, macros, memory_layout, modules_packages, networking,
+    no_std_demo, oop_patterns, ownership, parsing, pattern_matching, process_env, serialization,
+    smart_pointers, structs_enums, testing, time_demo, traits_generics, unsafe_rust,
+};
+
+// C のコードをリンクする章はブラウザ版（wasm32）には入れない
+#[cfg(not(target_arch = "wasm32"))]
+pub use ffi;
+
+/// デモの難易度
+/// 初回の授業では上級（HRTB、GATなど）を隠す、といった使い方を想定
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Beginner,
+    Intermediate,
+    Advanced,
+}
+
+impl Level {
+    /// コマンドライン引数やメニュー入力から難易度を読み取る
+    pub fn parse(s: &str) -> Option<Level> {
+        match s.trim().to_lowercase().as_str() {
+            "beginner" | "b" | "初級" => Some(Level::Beginner),
+            "intermediate" | "i" | "中級" => Some(Level::Intermediate),
+            "advanced" | "a" | "上級" => Some(Level::Advanced),
+            _ => None,
+        }
+    }
+
+    /// メニューのトグル用: 初級 → 中級 → 上級 → 初級 ...
+    pub fn next(self) -> Level {
+        match self {
+            Level::Beginner => Level::Intermediate,
+            Level::Intermediate => Level::Advanced,
+            Level::Advanced => Level::Beginner,
+        }
+    }
+
+    /// デモ1つにかかる時間の目安（分）。デモごとに Demo::minutes で変えられる
+    pub const fn estimated_minutes(self) -> u32 {
+        match self {
+            Level::Beginner => 3,
+            Level::Intermediate => 5,
+            Level::Advanced => 8,
+        }
+    }
+
+    fn from_u8(value: u8) -> Level {
+        match value {
+            0 => Level::Beginner,
+            1 => Level::Intermediate,
+            _ => Level::Advanced,
+        }
+    }
+}
+
+/// 1つのデモ関数とそのメタデータ
+pub struct Demo {
+    pub name: &'static str,
+    pub title: &'static str,
+    pub level: Level,
+    /// 読んで試すのにかかる時間の目安（分）
+    pub minutes: u32,
+    /// このデモが加わったコンテンツのバージョン
+    pub added: u32,
+    /// 最後に内容を大きく変えたコンテンツのバージョン（変えていなければ added と同じ）
+    pub revised: u32,
+    /// トピックの前提のほかに、このデモだけが使う前提トピックのキー
+    pub requires: &'static [&'static str],
+    pub run: fn(),
+}
+
+impl Demo {
+    /// 時間の目安を難易度の既定値から変える（対話するデモや長いデモ用）
+    const fn minutes(self, minutes: u32) -> Demo {
+        Demo { minutes, ..self }
+    }
+
+    /// デモを追加したバージョンを書く（書かなければ 1）
+    pub const fn added_in(self, version: u32) -> Demo {
+        Demo {
+            added: version,
+            revised: version,
+            ..self
+        }
+    }
+
+    /// このデモだけが使う前提トピックを書く（learning_path と「先に学ぶ章」の案内に入る）
+    pub const fn requires(self, topics: &'static [&'static str]) -> Demo {
+        Demo {
+            requires: topics,
+            ..self
+        }
+    }
+
+    /// 内容を大きく書き直したバージョンを書く（`whatsnew` に「更新」として出る）
+    pub const fn revised_in(self, version: u32) -> Demo {
+        Demo {
+            revised: version,
+            ..self
+        }
+    }
+}
+
+/// 1つのトピック（モジュール）とそれに属するデモ
+pub struct Topic {
+    pub key: &'static str,
+    pub title: &'static str,
+    /// メニューで名前として打てる短い呼び名（日本語と英語）。キーとタイトルは書かなくても使える
+    pub aliases: &'static [&'static str],
+    pub chapter: &'static str,
+    /// 先に学んでおくべきトピックのキー
+    pub requires: &'static [&'static str],
+    pub run_all: fn(),
+    pub cheatsheet: &'static CheatSheet,
+    pub demos: &'static [Demo],
+}
+
+impl Topic {
+    /// 現在の難易度フィルタで表示されるデモ
+    pub fn visible_demos(&self) -> impl Iterator<Item = &'static Demo> {
+        let max = max_level();
+        self.demos.iter().filter(move |demo| demo.level <= max)
+    }
+
+    /// 表示されるデモが1つでもあるか
+    pub fn is_visible(&self) -> bool {
+        self.visible_demos().next().is_some()
+    }
+
+    /// 表示されるデモにかかる時間の目安の合計（分）
+    pub fn estimated_minutes(&self) -> u32 {
+        self.visible_demos().map(|demo| demo.minutes).sum()
+    }
+
+    /// トピックの前提と、デモごとの前提を合わせたもの（重複と自分自身は除く）
+    pub fn prerequisites(&self) -> Vec<&'static str> {
+        let mut keys: Vec<&'static str> = Vec::new();
+        let demo_requires = self.demos.iter().flat_map(|demo| demo.requires);
+        for &key in self.requires.iter().chain(demo_requires) {
+            if key != self.key && !keys.contains(&key) {
+                keys.push(key);
+            }
+        }
+        keys
+    }
+}
+
+// デモ定義を短く書くためのヘルパー
+const fn demo(name: &'static str, title: &'static str, level: Level, run: fn()) -> Demo {
+    Demo {
+        name,
+        title,
+        level,
+        minutes: level.estimated_minutes(),
+        added: 1,
+        revised: 1,
+        requires: &[],
+        run,
+    }
+}
+
+use Level::{Advanced, Beginner, Intermediate};
+
+/// メニューに並ぶ順のトピック一覧（番号は 1 始まりのインデックス）
+#[rustfmt::skip]
+pub static TOPICS: &[Topic] = &[
+    Topic {
+        key: "basics",
+        title: "基本構文（変数、データ型、関数、制御フロー）",
+        aliases: &["基本", "基本構文", "basic"],
+        chapter: "Ch.3",
+        requires: &[],
+        run_all: basics::run_all,
+        cheatsheet: &basics::CHEATSHEET,
+        demos: &[
+            demo("variables_demo", "変数と可変性", Beginner, basics::variables_demo),
+            demo("constants_demo", "定数", Beginner, basics::constants_demo),
+            demo("data_types_demo", "データ型", Beginner, basics::data_types_demo),
+            demo("functions_demo", "関数", Beginner, basics::functions_demo),
+            demo("control_flow_demo", "制御フロー", Beginner, basics::control_flow_demo),
+            demo("recursion_demo", "再帰とスタック", Intermediate, basics::recursion_demo),
+            demo("recursive_types_demo", "再帰的なデータ型とBox", Intermediate, basics::recursive_types_demo),
+            demo("calculator_finale", "仕上げ: 入力を受け取って計算する", Beginner, basics::calculator_finale).minutes(8),
+        ],
+    },
+    Topic {
+        key: "ownership",
+        title: "所有権システム",
+        aliases: &["所有権", "借用", "borrowing"],
+        chapter: "Ch.4",
+        requires: &["basics"],
+        run_all: ownership::run_all,
+        cheatsheet: &ownership::CHEATSHEET,
+        demos: &[
+            demo("ownership_basics", "所有権の基本", Beginner, ownership::ownership_basics),
+            demo("ownership_and_functions", "関数と所有権", Beginner, ownership::ownership_and_functions),
+            demo("references_and_borrowing", "参照と借用", Beginner, ownership::references_and_borrowing),
+            demo("slices", "スライス", Beginner, ownership::slices),
+            demo("no_dangling", "ダングリング参照の防止", Intermediate, ownership::no_dangling),
+            demo("clone_vs_borrow_cost", "クローンと借用のコスト", Intermediate, ownership::clone_vs_borrow_cost),
+            demo("index_based_links", "参照の代わりにインデックスでつなぐ", Advanced, ownership::index_based_links),
+            demo("ownership_summary", "所有権のまとめ", Beginner, ownership::ownership_summary).minutes(2),
+        ],
+    },
+    Topic {
+        key: "structs_enums",
+        title: "構造体と列挙型",
+        aliases: &["構造体", "列挙型", "structs", "enums"],
+        chapter: "Ch.5-6",
+        requires: &["ownership"],
+        run_all: structs_enums::run_all,
+        cheatsheet: &structs_enums::CHEATSHEET,
+        demos: &[
+            demo("basic_structs", "基本的な構造体", Beginner, structs_enums::basic_structs),
+            demo("tuple_structs", "タプル構造体", Beginner, structs_enums::tuple_structs),
+            demo("unit_like_structs", "ユニット様構造体", Beginner, structs_enums::unit_like_structs),
+            demo("methods", "メソッド", Beginner, structs_enums::methods),
+            demo("basic_enums", "列挙型の基本", Beginner, structs_enums::basic_enums),
+            demo("enums_with_data", "データを持つ列挙型", Beginner, structs_enums::enums_with_data),
+            demo("option_enum", "Option列挙型", Beginner, structs_enums::option_enum),
+            demo("result_enum", "Result列挙型", Beginner, structs_enums::result_enum),
+            demo("derive_macros", "Deriveマクロ", Intermediate, structs_enums::derive_macros),
+        ],
+    },
+    Topic {
+        key: "pattern_matching",
+        title: "パターンマッチング",
+        aliases: &["パターン", "パターンマッチ", "match"],
+        chapter: "Ch.6, 18",
+        requires: &["structs_enums"],
+        run_all: pattern_matching::run_all,
+        cheatsheet: &pattern_matching::CHEATSHEET,
+        demos: &[
+            demo("basic_match", "match式の基本", Beginner, pattern_matching::basic_match),
+            demo("patterns_that_bind", "パターンと値の束縛", Beginner, pattern_matching::patterns_that_bind),
+            demo("matching_with_option", "Option<T>とのマッチング", Beginner, pattern_matching::matching_with_option),
+            demo("exhaustiveness_and_catchall", "網羅性とキャッチオール", Beginner, pattern_matching::exhaustiveness_and_catchall),
+            demo("if_let_demo", "if let", Beginner, pattern_matching::if_let_demo),
+            demo("while_let_demo", "while let", Intermediate, pattern_matching::while_let_demo),
+            demo("let_patterns", "letのパターン", Intermediate, pattern_matching::let_patterns),
+            demo("function_parameter_patterns", "関数引数のパターン", Intermediate, pattern_matching::function_parameter_patterns),
+            demo("complex_patterns", "複雑なパターン", Intermediate, pattern_matching::complex_patterns),
+            demo("destructuring_structs", "構造体の分解", Intermediate, pattern_matching::destructuring_structs),
+            demo("destructuring_enums", "列挙型の分解", Intermediate, pattern_matching::destructuring_enums),
+            demo("destructuring_references", "参照の分解", Advanced, pattern_matching::destructuring_references),
+            demo("ignoring_values", "値の無視", Intermediate, pattern_matching::ignoring_values),
+            demo("match_guards", "マッチガード", Intermediate, pattern_matching::match_guards),
+            demo("at_bindings", "@束縛", Advanced, pattern_matching::at_bindings),
+        ],
+    },
+    Topic {
+        key: "error_handling",
+        title: "エラーハンドリング",
+        aliases: &["エラー", "エラー処理", "errors"],
+        chapter: "Ch.9",
+        requires: &["pattern_matching"],
+        run_all: error_handling::run_all,
+        cheatsheet: &error_handling::CHEATSHEET,
+        demos: &[
+            demo("panic_demo", "panic! マクロ", Beginner, error_handling::panic_demo),
+            demo("result_basics", "Result型の基本", Beginner, error_handling::result_basics),
+            demo("matching_on_different_errors", "エラーの種類によるマッチング", Intermediate, error_handling::matching_on_different_errors),
+            demo("unwrap_and_expect", "unwrapとexpect", Beginner, error_handling::unwrap_and_expect),
+            demo("error_propagation", "エラー伝播", Intermediate, error_handling::error_propagation),
+            demo("question_mark_with_option", "Option<T>での?演算子", Intermediate, error_handling::question_mark_with_option),
+            demo("custom_error_types", "カスタムエラー型", Intermediate, error_handling::custom_error_types).requires(&["traits_generics"]),
+            demo("result_combinators", "Resultのコンビネータ", Advanced, error_handling::result_combinators),
+            demo("result_extension_trait", "拡張トレイトでエラーに説明を添える", Advanced, error_handling::result_extension_trait).requires(&["traits_generics"]),
+            demo("best_practices", "ベストプラクティス", Beginner, error_handling::best_practices),
+            demo("validation_pattern", "検証パターン", Intermediate, error_handling::validation_pattern),
+        ],
+    },
+    Topic {
+        key: "traits_generics",
+        title: "トレイトとジェネリクス",
+        aliases: &["トレイト", "ジェネリクス", "traits", "generics"],
+        chapter: "Ch.10",
+        requires: &["structs_enums"],
+        run_all: traits_generics::run_all,
+        cheatsheet: &traits_generics::CHEATSHEET,
+        demos: &[
+            demo("generics_basics", "ジェネリクスの基本", Beginner, traits_generics::generics_basics),
+            demo("generic_structs", "ジェネリック構造体", Beginner, traits_generics::generic_structs),
+            demo("generic_enums", "ジェネリック列挙型", Beginner, traits_generics::generic_enums),
+            demo("traits_basics", "トレイトの基本", Beginner, traits_generics::traits_basics),
+            demo("trait_bounds", "トレイト境界", Intermediate, traits_generics::trait_bounds),
+            demo("returning_traits", "戻り値としてのトレイト", Intermediate, traits_generics::returning_traits),
+            demo("conditional_implementations", "条件付きメソッド実装", Intermediate, traits_generics::conditional_implementations),
+            demo("associated_types", "関連型", Advanced, traits_generics::associated_types),
+            demo("default_generic_type_parameters", "デフォルト型パラメータ", Advanced, traits_generics::default_generic_type_parameters),
+            demo("supertraits", "スーパートレイト", Advanced, traits_generics::supertraits),
+            demo("monomorphization", "単相化とコードサイズ", Advanced, traits_generics::monomorphization),
+            demo("visitor_pattern", "ビジターパターン（トレイトによる二重ディスパッチ）", Advanced, traits_generics::visitor_pattern),
+            demo("command_registry", "トレイトオブジェクトでコマンドを登録する", Advanced, traits_generics::command_registry).requires(&["collections"]),
+            demo("const_generic_arrays", "配列の長さのジェネリクス（const N: usize）", Intermediate, traits_generics::const_generic_arrays).added_in(4),
+            demo("const_generic_matrix", "const ジェネリクスの行列（形をコンパイル時に検査）", Advanced, traits_generics::const_generic_matrix).added_in(4),
+        ],
+    },
+    Topic {
+        key: "collections",
+        title: "コレクション",
+        aliases: &["vec", "hashmap"],
+        chapter: "Ch.8",
+        requires: &["structs_enums"],
+        run_all: collections::run_all,
+        cheatsheet: &collections::CHEATSHEET,
+        demos: &[
+            demo("vector_basics", "ベクターの基本", Beginner, collections::vector_basics),
+            demo("vector_operations", "ベクターの操作", Beginner, collections::vector_operations),
+            demo("vector_iteration", "ベクターでの反復処理", Beginner, collections::vector_iteration),
+            demo("vector_with_enums", "列挙型で異なる型を格納", Intermediate, collections::vector_with_enums),
+            demo("string_basics", "文字列の基本", Beginner, collections::string_basics),
+            demo("string_indexing", "文字列のインデックスアクセス", Intermediate, collections::string_indexing),
+            demo("string_operations", "文字列の操作", Beginner, collections::string_operations),
+            demo("string_building_timing", "文字列の組み立て方の速度比較", Intermediate, collections::string_building_timing),
+            demo("combining_characters", "結合文字と正規化", Intermediate, collections::combining_characters).added_in(4),
+            demo("grapheme_clusters", "バイト・char・書記素クラスタ", Intermediate, collections::grapheme_clusters).added_in(4),
+            demo("locale_formatting", "ロケールによる書式の違い（数値、金額、全角と半角、日付）", Intermediate, collections::locale_formatting).added_in(3),
+            demo("hashmap_basics", "HashMapの基本", Beginner, collections::hashmap_basics),
+            demo("hashmap_iteration", "HashMapの反復処理", Beginner, collections::hashmap_iteration),
+            demo("hashmap_updating", "HashMapの更新", Intermediate, collections::hashmap_updating),
+            demo("hashmap_ownership", "HashMapと所有権", Intermediate, collections::hashmap_ownership),
+            demo("other_collections", "その他のコレクション", Intermediate, collections::other_collections),
+            demo("hashmap_vs_btreemap", "HashMap と BTreeMap の比較", Intermediate, collections::hashmap_vs_btreemap),
+            demo("build_your_own_hashmap", "ハッシュマップを自作する（オープンアドレス法）", Advanced, collections::build_your_own_hashmap).minutes(12),
+            demo("ring_buffer_demo", "リングバッファ（固定長のキュー）", Intermediate, collections::ring_buffer_demo),
+        ],
+    },
+    Topic {
+        key: "iterators_closures",
+        title: "イテレータとクロージャ",
+        aliases: &["イテレータ", "クロージャ", "iterators", "closures"],
+        chapter: "Ch.13",
+        requires: &["collections", "traits_generics"],
+        run_all: iterators_closures::run_all,
+        cheatsheet: &iterators_closures::CHEATSHEET,
+        demos: &[
+            demo("closure_basics", "クロージャの基本", Beginner, iterators_closures::closure_basics),
+            demo("closure_capture", "環境のキャプチャ", Intermediate, iterators_closures::closure_capture),
+            demo("closures_as_parameters", "クロージャを引数に取る関数", Intermediate, iterators_closures::closures_as_parameters),
+            demo("closure_trait_probe", "クロージャが実装するトレイトを調べる", Advanced, iterators_closures::closure_trait_probe),
+            demo("iterator_basics", "イテレータの基本", Beginner, iterators_closures::iterator_basics),
+            demo("iterator_adapters", "イテレータアダプタ", Intermediate, iterators_closures::iterator_adapters),
+            demo("iterator_consumers", "イテレータ消費アダプタ", Intermediate, iterators_closures::iterator_consumers),
+            demo("custom_iterator", "カスタムイテレータ", Advanced, iterators_closures::custom_iterator),
+            demo("windows_and_chunks", "windows と chunks を自作する", Advanced, iterators_closures::windows_and_chunks),
+            demo("practical_examples", "実践例", Intermediate, iterators_closures::practical_examples),
+            demo("pipeline_performance", "イテレータのパイプラインと確保のコスト", Advanced, iterators_closures::pipeline_performance),
+        ],
+    },
+    Topic {
+        key: "lifetimes",
+        title: "ライフタイム",
+        aliases: &["lifetime"],
+        chapter: "Ch.10",
+        requires: &["ownership", "traits_generics"],
+        run_all: lifetimes::run_all,
+        cheatsheet: &lifetimes::CHEATSHEET,
+        demos: &[
+            demo("why_lifetimes", "ライフタイムが必要な理由", Intermediate, lifetimes::why_lifetimes),
+            demo("function_lifetimes", "関数シグネチャのライフタイム", Intermediate, lifetimes::function_lifetimes),
+            demo("lifetime_syntax", "ライフタイム注釈の構文", Intermediate, lifetimes::lifetime_syntax),
+            demo("struct_lifetimes", "構造体のライフタイム", Intermediate, lifetimes::struct_lifetimes),
+            demo("lifetime_elision", "ライフタイムの省略規則", Intermediate, lifetimes::lifetime_elision),
+            demo("static_lifetime", "'static ライフタイム", Intermediate, lifetimes::static_lifetime),
+            demo("complex_lifetimes", "複合的なライフタイム", Advanced, lifetimes::complex_lifetimes),
+            demo("lifetime_bounds", "ライフタイムの制約", Advanced, lifetimes::lifetime_bounds),
+            demo("practical_examples", "実践的な例", Advanced, lifetimes::practical_examples),
+            demo("split_iterator", "分割イテレータを自作する", Intermediate, lifetimes::split_iterator),
+            demo("zero_copy_parser", "ゼロコピーのパーサ", Advanced, lifetimes::zero_copy_parser),
+            demo("best_practices", "ライフタイムのベストプラクティス", Intermediate, lifetimes::best_practices),
+        ],
+    },
+    Topic {
+        key: "algorithms",
+        title: "アルゴリズム（ソートとメモ化）",
+        aliases: &["アルゴリズム", "ソート", "sorting"],
+        chapter: "応用",
+        requires: &["traits_generics"],
+        run_all: algorithms::run_all,
+        cheatsheet: &algorithms::CHEATSHEET,
+        demos: &[
+            demo("generic_sorting", "ジェネリックなソート", Intermediate, algorithms::generic_sorting),
+            demo("sorting_timing", "ソートの速度比較", Intermediate, algorithms::sorting_timing),
+            demo("memoization", "メモ化（Memo<K, V>）", Intermediate, algorithms::memoization).requires(&["collections"]),
+        ],
+    },
+    Topic {
+        key: "data_structures",
+        title: "データ構造（連結リスト、スタック/キュー、二分探索木）",
+        aliases: &["データ構造"],
+        chapter: "応用",
+        requires: &["ownership", "traits_generics"],
+        run_all: data_structures::run_all,
+        cheatsheet: &data_structures::CHEATSHEET,
+        demos: &[
+            demo("linked_list_demo", "単方向連結リスト（Box）", Intermediate, data_structures::linked_list_demo),
+            demo("stack_queue_demo", "スタックとキュー", Beginner, data_structures::stack_queue_demo),
+            demo("bst_demo", "二分探索木（Box）", Intermediate, data_structures::bst_demo),
+            demo("rc_bst_demo", "二分探索木（Rc<RefCell>）", Advanced, data_structures::rc_bst_demo).requires(&["smart_pointers"]),
+            demo("arena_demo", "アリーナ（Vec + インデックス）", Advanced, data_structures::arena_demo),
+        ],
+    },
+    Topic {
+        key: "graphs",
+        title: "グラフと経路探索（BFS/DFS、ダイクストラ法）",
+        aliases: &["グラフ", "graph"],
+        chapter: "応用",
+        requires: &["collections"],
+        run_all: graphs::run_all,
+        cheatsheet: &graphs::CHEATSHEET,
+        demos: &[
+            demo("adjacency_list_demo", "隣接リスト", Intermediate, graphs::adjacency_list_demo),
+            demo("traversal_demo", "幅優先探索と深さ優先探索", Intermediate, graphs::traversal_demo),
+            demo("dijkstra_demo", "ダイクストラ法", Advanced, graphs::dijkstra_demo),
+        ],
+    },
+    Topic {
+        key: "parsing",
+        title: "構文解析（字句解析器と再帰下降パーサ）",
+        aliases: &["構文解析", "パーサ", "parser"],
+        chapter: "応用",
+        requires: &["error_handling", "iterators_closures"],
+        run_all: parsing::run_all,
+        cheatsheet: &parsing::CHEATSHEET,
+        demos: &[
+            demo("tokenizer_demo", "字句解析（トークナイザ）", Intermediate, parsing::tokenizer_demo),
+            demo("parser_demo", "構文解析（再帰下降パーサ）", Advanced, parsing::parser_demo).minutes(12),
+            demo("parse_errors_demo", "位置付きのエラー", Intermediate, parsing::parse_errors_demo),
+        ],
+    },
+    Topic {
+        key: "no_std_demo",
+        title: "no_std（組み込み向け）",
+        aliases: &["no_std", "組み込み", "embedded"],
+        chapter: "応用",
+        requires: &["collections", "traits_generics"],
+        run_all: no_std_demo::run_all,
+        cheatsheet: &no_std_demo::CHEATSHEET,
+        demos: &[
+            demo("core_alloc_std", "core / alloc / std の3層", Intermediate, no_std_demo::core_alloc_std),
+            demo("formatting_without_heap", "ヒープを使わない書式化（fmt::Write）", Advanced, no_std_demo::formatting_without_heap),
+            demo("parsing_and_checksum", "core だけで解析と検査", Intermediate, no_std_demo::parsing_and_checksum),
+            demo("alloc_collections", "alloc のコレクション", Advanced, no_std_demo::alloc_collections),
+            demo("panic_handler_demo", "パニックハンドラ", Advanced, no_std_demo::panic_handler_demo),
+            demo("surviving_samples", "これまでの章は no_std で動くか", Intermediate, no_std_demo::surviving_samples),
+        ],
+    },
+    #[cfg(not(target_arch = "wasm32"))]
+    Topic {
+        key: "ffi",
+        title: "FFI（C との相互運用）",
+        aliases: &["interop"],
+        chapter: "応用",
+        requires: &["ownership", "traits_generics"],
+        run_all: ffi::run_all,
+        cheatsheet: &ffi::CHEATSHEET,
+        demos: &[
+            demo("calling_libc", "libc の関数を呼ぶ", Intermediate, ffi::calling_libc),
+            demo("bundled_c_library", "同梱の C ファイルを呼ぶ（build.rs）", Intermediate, ffi::bundled_c_library),
+            demo("cstring_and_cstr", "CString と CStr", Advanced, ffi::cstring_and_cstr),
+            demo("exporting_rust", "Rust の関数を C から呼ぶ（#[no_mangle]）", Advanced, ffi::exporting_rust),
+        ],
+    },
+    Topic {
+        key: "build_system",
+        title: "Cargo の feature とビルドスクリプト",
+        aliases: &["ビルド", "cargo", "features"],
+        chapter: "応用",
+        requires: &["basics"],
+        run_all: build_system::run_all,
+        cheatsheet: &build_system::CHEATSHEET,
+        demos: &[
+            demo("cfg_feature", "cfg! と #[cfg(feature)]", Beginner, build_system::cfg_feature),
+            demo("optional_dependency", "optional な依存（dep:）", Intermediate, build_system::optional_dependency),
+            demo("build_script", "build.rs と生成コード（include!）", Intermediate, build_system::build_script),
+            demo("features_of_this_build", "このビルドの feature", Beginner, build_system::features_of_this_build),
+            demo("debug_vs_release", "デバッグビルドとリリースビルド", Beginner, build_system::debug_vs_release),
+        ],
+    },
+    Topic {
+        key: "smart_pointers",
+        title: "スマートポインタ",
+        aliases: &["box", "rc"],
+        chapter: "Ch.15",
+        requires: &["ownership", "traits_generics"],
+        run_all: smart_pointers::run_all,
+        cheatsheet: &smart_pointers::CHEATSHEET,
+        demos: &[
+            demo("box_recursive_type", "Box<T> と再帰的な型", Beginner, smart_pointers::box_recursive_type).added_in(4),
+            demo("deref_trait", "Deref トレイトと * 演算子", Intermediate, smart_pointers::deref_trait).added_in(2),
+            demo("deref_coercion", "参照外し型強制（deref coercion）", Intermediate, smart_pointers::deref_coercion).added_in(2),
+            demo("auto_deref_methods", "メソッド呼び出しの自動参照外し", Intermediate, smart_pointers::auto_deref_methods).added_in(2),
+            demo("drop_order", "Drop の順序", Intermediate, smart_pointers::drop_order).added_in(4),
+            demo("rc_counting", "Rc<T> の参照カウント", Intermediate, smart_pointers::rc_counting).added_in(4),
+            demo("refcell_interior_mutability", "RefCell<T> と内部可変性", Intermediate, smart_pointers::refcell_interior_mutability).added_in(4),
+            demo("weak_parent_tree", "Weak で親を指す木", Advanced, smart_pointers::weak_parent_tree).added_in(2),
+            demo("reference_cycle_leak", "循環参照でメモリがリークする", Advanced, smart_pointers::reference_cycle_leak).added_in(2),
+        ],
+    },
+    Topic {
+        key: "concurrency",
+        title: "並行処理",
+        aliases: &["スレッド", "threads"],
+        chapter: "Ch.16",
+        requires: &["ownership", "iterators_closures"],
+        run_all: concurrency::run_all,
+        cheatsheet: &concurrency::CHEATSHEET,
+        demos: &[
+            demo("spawn_and_join", "thread::spawn と JoinHandle", Intermediate, concurrency::spawn_and_join).added_in(4),
+            demo("move_closures", "move クロージャでスレッドに値を渡す", Intermediate, concurrency::move_closures).added_in(4),
+            demo("channels_multiple_producers", "複数の送信側から1つのチャネルに送る", Intermediate, concurrency::channels_multiple_producers).added_in(4),
+            demo("shared_state_mutex", "Arc<Mutex<T>> で状態を共有する", Intermediate, concurrency::shared_state_mutex).added_in(4).requires(&["smart_pointers"]),
+            demo("scoped_threads", "スコープ付きスレッド（thread::scope）", Intermediate, concurrency::scoped_threads).added_in(4),
+            demo("pipeline_demo", "多段パイプライン（チャネルと停止の合図）", Advanced, concurrency::pipeline_demo).added_in(2),
+        ],
+    },
+    Topic {
+        key: "async_await",
+        title: "非同期プログラミング（async/await）",
+        aliases: &["非同期", "async"],
+        chapter: "Ch.17",
+        requires: &["traits_generics", "concurrency"],
+        run_all: async_await::run_all,
+        cheatsheet: &async_await::CHEATSHEET,
+        demos: &[
+            demo("async_fn_basics", "async fn は Future を返す", Intermediate, async_await::async_fn_basics).added_in(4),
+            demo("hand_written_future", "Future を手で実装する（poll と Waker）", Advanced, async_await::hand_written_future).added_in(4),
+            demo("chaining_futures", "Future をつなげる（順番に・同時に・map）", Intermediate, async_await::chaining_futures).added_in(4),
+            demo("async_channels", "非同期チャネル（容量と背圧）", Intermediate, async_await::async_channels).added_in(2),
+            demo("select_race", "select（race）で先に終わった方を使う", Advanced, async_await::select_race).added_in(2),
+            demo("timeouts", "タイムアウトとリトライ", Advanced, async_await::timeouts).added_in(2),
+        ],
+    },
+    Topic {
+        key: "macros",
+        title: "マクロ",
+        aliases: &["macro"],
+        chapter: "Ch.20",
+        requires: &["traits_generics"],
+        run_all: macros::run_all,
+        cheatsheet: &macros::CHEATSHEET,
+        demos: &[
+            demo("my_vec_macro", "my_vec! で vec! を作り直す", Beginner, macros::my_vec_macro).added_in(4),
+            demo("repetition_patterns", "繰り返しのパターン（max!、hashmap!、入れ子）", Intermediate, macros::repetition_patterns).added_in(4),
+            demo("macro_hygiene", "マクロの衛生性（hygiene）", Intermediate, macros::macro_hygiene).added_in(4),
+            demo("derive_describe", "#[derive(Describe)]（手続き的マクロ）", Intermediate, macros::derive_describe).added_in(2),
+            demo("declarative_vs_procedural", "宣言的マクロと手続き的マクロ", Advanced, macros::declarative_vs_procedural).added_in(2),
+            demo("attribute_macro", "属性マクロ #[trace]", Advanced, macros::attribute_macro).added_in(4),
+            demo("function_like_macro", "関数風マクロ hex_color!", Advanced, macros::function_like_macro).added_in(4),
+            demo("macro_dsl", "macro_rules! で作る DSL（tt muncher）", Advanced, macros::macro_dsl).added_in(2),
+        ],
+    },
+    Topic {
+        key: "unsafe_rust",
+        title: "unsafe Rust",
+        aliases: &["unsafe"],
+        chapter: "Ch.20",
+        requires: &["ownership", "smart_pointers"],
+        run_all: unsafe_rust::run_all,
+        cheatsheet: &unsafe_rust::CHEATSHEET,
+        demos: &[
+            demo("raw_pointers", "生ポインタ", Intermediate, unsafe_rust::raw_pointers).added_in(2),
+            demo("safe_abstraction", "unsafe を閉じ込めた安全な API（split_at_mut）", Intermediate, unsafe_rust::safe_abstraction).added_in(2),
+            demo("transmute_hazards", "transmute の危険と、安全な代わり", Advanced, unsafe_rust::transmute_hazards).added_in(2),
+            demo("miri_notes", "Miri で未定義動作を見つける", Intermediate, unsafe_rust::miri_notes).added_in(2),
+        ],
+    },
+    Topic {
+        key: "oop_patterns",
+        title: "オブジェクト指向のパターン",
+        aliases: &["oop", "オブジェクト指向", "state pattern"],
+        chapter: "Ch.18",
+        requires: &["traits_generics", "smart_pointers"],
+        run_all: oop_patterns::run_all,
+        cheatsheet: &oop_patterns::CHEATSHEET,
+        demos: &[
+            demo("encapsulation", "カプセル化（非公開のフィールド）", Beginner, oop_patterns::encapsulation).added_in(4),
+            demo("gui_components", "Vec<Box<dyn Draw>> で GUI の部品を並べる", Intermediate, oop_patterns::gui_components).added_in(4),
+            demo("state_pattern", "State パターン（トレイトオブジェクト版）", Intermediate, oop_patterns::state_pattern).added_in(4),
+            demo("typestate_pattern", "型状態（typestate）版", Advanced, oop_patterns::typestate_pattern).added_in(4),
+        ],
+    },
+    Topic {
+        key: "modules_packages",
+        title: "モジュールとパッケージ",
+        aliases: &["modules", "mod", "モジュール"],
+        chapter: "Ch.7",
+        requires: &["basics"],
+        run_all: modules_packages::run_all,
+        cheatsheet: &modules_packages::CHEATSHEET,
+        demos: &[
+            demo("module_tree_and_paths", "モジュールの木と、絶対パス・相対パス", Beginner, modules_packages::module_tree_and_paths).added_in(4),
+            demo("visibility_rules", "pub と可視性", Beginner, modules_packages::visibility_rules).added_in(4),
+            demo("use_and_reexports", "use と pub use（再公開）", Intermediate, modules_packages::use_and_reexports).added_in(4),
+        ],
+    },
+    Topic {
+        key: "testing",
+        title: "テストの書き方",
+        aliases: &["tests", "test", "テスト"],
+        chapter: "Ch.11",
+        requires: &["structs_enums", "error_handling"],
+        run_all: testing::run_all,
+        cheatsheet: &testing::CHEATSHEET,
+        demos: &[
+            demo("assertion_macros", "assert! / assert_eq! / assert_ne!", Beginner, testing::assertion_macros).added_in(4),
+            demo("should_panic_and_result", "#[should_panic] と Result を返すテスト", Intermediate, testing::should_panic_and_result).added_in(4),
+            demo("test_organization", "テストの置き場所と cargo test", Intermediate, testing::test_organization).added_in(4),
+        ],
+    },
+    Topic {
+        key: "advanced_traits",
+        title: "高度なトレイト",
+        aliases: &["advanced traits", "関連定数", "完全修飾構文", "newtype"],
+        chapter: "Ch.20",
+        requires: &["traits_generics"],
+        run_all: advanced_traits::run_all,
+        cheatsheet: &advanced_traits::CHEATSHEET,
+        demos: &[
+            demo("associated_consts", "関連定数", Intermediate, advanced_traits::associated_consts).added_in(4),
+            demo("fully_qualified_syntax", "完全修飾構文（同じ名前のメソッドの呼び分け）", Intermediate, advanced_traits::fully_qualified_syntax).added_in(4),
+            demo("newtype_orphan_rule", "ニュータイプと孤児ルール", Intermediate, advanced_traits::newtype_orphan_rule).added_in(4),
+            demo("supertrait_objects", "スーパートレイトとトレイトオブジェクト", Advanced, advanced_traits::supertrait_objects).added_in(4),
+        ],
+    },
+    Topic {
+        key: "advanced_types",
+        title: "高度な型",
+        aliases: &["advanced types", "never", "型エイリアス"],
+        chapter: "Ch.20",
+        requires: &["advanced_traits"],
+        run_all: advanced_types::run_all,
+        cheatsheet: &advanced_types::CHEATSHEET,
+        demos: &[
+            demo("type_aliases", "型エイリアス", Intermediate, advanced_types::type_aliases).added_in(4),
+            demo("newtype_abstraction", "ニュータイプで抽象化する", Intermediate, advanced_types::newtype_abstraction).added_in(4),
+            demo("never_type", "never 型（!）", Advanced, advanced_types::never_type).added_in(4),
+            demo("dynamically_sized_types", "動的サイズ型と ?Sized", Advanced, advanced_types::dynamically_sized_types).added_in(4),
+        ],
+    },
+    Topic {
+        key: "interior_mutability",
+        title: "内部可変性",
+        aliases: &["interior mutability", "cell", "refcell", "内部可変"],
+        chapter: "Ch.15",
+        requires: &["smart_pointers"],
+        run_all: interior_mutability::run_all,
+        cheatsheet: &interior_mutability::CHEATSHEET,
+        demos: &[
+            demo("cell_vs_refcell", "Cell<T> と RefCell<T>", Intermediate, interior_mutability::cell_vs_refcell).added_in(4),
+            demo("mock_messenger", "モックオブジェクト（MockMessenger）", Intermediate, interior_mutability::mock_messenger).added_in(4),
+            demo("runtime_borrow_errors", "実行時の借用エラー（BorrowMutError）", Advanced, interior_mutability::runtime_borrow_errors).added_in(4),
+        ],
+    },
+    Topic {
+        key: "memory_layout",
+        title: "メモリレイアウト",
+        aliases: &["layout", "size_of", "repr", "niche", "レイアウト"],
+        chapter: "応用",
+        requires: &["structs_enums", "smart_pointers"],
+        run_all: memory_layout::run_all,
+        cheatsheet: &memory_layout::CHEATSHEET,
+        demos: &[
+            demo("sizes_and_alignment", "size_of と align_of", Intermediate, memory_layout::sizes_and_alignment).added_in(4),
+            demo("struct_layout_and_repr", "構造体のレイアウトと #[repr(C)]", Advanced, memory_layout::struct_layout_and_repr).added_in(4),
+            demo("enum_niches", "列挙型とニッチ最適化", Advanced, memory_layout::enum_niches).added_in(4),
+        ],
+    },
+    Topic {
+        key: "time_demo",
+        title: "時間（Instant / Duration / SystemTime）",
+        aliases: &["time", "duration", "instant", "時間", "stopwatch"],
+        chapter: "応用",
+        requires: &["structs_enums"],
+        run_all: time_demo::run_all,
+        cheatsheet: &time_demo::CHEATSHEET,
+        demos: &[
+            demo("instant_elapsed", "Instant で経過時間を測る", Beginner, time_demo::instant_elapsed).added_in(4),
+            demo("duration_arithmetic", "Duration の計算", Beginner, time_demo::duration_arithmetic).added_in(4),
+            demo("system_time", "SystemTime と UNIX_EPOCH", Intermediate, time_demo::system_time).added_in(4),
+            demo("stopwatch", "ストップウォッチ（ラップタイム）", Intermediate, time_demo::stopwatch).added_in(4),
+        ],
+    },
+    Topic {
+        key: "process_env",
+        title: "プロセスと環境変数",
+        aliases: &["process", "env", "command", "環境変数", "子プロセス"],
+        chapter: "応用",
+        requires: &["error_handling"],
+        run_all: process_env::run_all,
+        cheatsheet: &process_env::CHEATSHEET,
+        demos: &[
+            demo("command_line_args", "env::args でコマンドライン引数を読む", Beginner, process_env::command_line_args).added_in(4),
+            demo("environment_variables", "環境変数を読む・設定する", Beginner, process_env::environment_variables).added_in(4),
+            demo("spawn_command", "Command で子プロセスを起動する", Intermediate, process_env::spawn_command).added_in(4),
+            demo("piped_stdin", "パイプで標準入力に書き込む", Intermediate, process_env::piped_stdin).added_in(4),
+        ],
+    },
+    Topic {
+        key: "networking",
+        title: "ネットワーク（TCP のエコーサーバー）",
+        aliases: &["network", "tcp", "socket", "echo", "ネットワーク"],
+        chapter: "応用",
+        requires: &["concurrency", "error_handling"],
+        run_all: networking::run_all,
+        cheatsheet: &networking::CHEATSHEET,
+        demos: &[
+            demo("echo_server_and_client", "TCP のエコーサーバーとクライアント", Intermediate, networking::echo_server_and_client).added_in(4),
+            demo("concurrent_clients", "複数のクライアントを同時に処理する", Advanced, networking::concurrent_clients).added_in(4),
+            demo("timeouts_and_errors", "タイムアウトとエラー", Advanced, networking::timeouts_and_errors).added_in(4),
+        ],
+    },
+    Topic {
+        key: "file_io",
+        title: "ファイル I/O（BufReader / BufWriter / std::fs）",
+        aliases: &["file", "fs", "io", "bufreader", "ファイル"],
+        chapter: "応用",
+        requires: &["error_handling"],
+        run_all: file_io::run_all,
+        cheatsheet: &file_io::CHEATSHEET,
+        demos: &[
+            demo("buffered_read_write", "BufWriter で書き、BufReader で1行ずつ読む", Beginner, file_io::buffered_read_write).added_in(4),
+            demo("append_copy_rename", "追記、コピー、名前の変更", Beginner, file_io::append_copy_rename).added_in(4),
+            demo("metadata_and_directories", "メタデータとディレクトリ、後片付け", Intermediate, file_io::metadata_and_directories).added_in(4),
+        ],
+    },
+    Topic {
+        key: "serialization",
+        title: "シリアライズ（serde / serde_json）",
+        aliases: &["serde", "json", "serialize", "シリアライズ"],
+        chapter: "応用",
+        requires: &["structs_enums", "traits_generics"],
+        run_all: serialization::run_all,
+        cheatsheet: &serialization::CHEATSHEET,
+        demos: &[
+            demo("manual_serialization", "derive が生成する変換を手で書く", Intermediate, serialization::manual_serialization).added_in(4),
+            demo("serde_round_trip", "serde_json で往復する", Intermediate, serialization::serde_round_trip).added_in(4),
+            demo("serde_attributes_and_errors", "#[serde(...)] の属性とエラー", Advanced, serialization::serde_attributes_and_errors).added_in(4),
+        ],
+    },
+];
+
+// 現在の難易度フィルタ（この難易度以下のデモだけを表示・実行する）
+static MAX_LEVEL: AtomicU8 = AtomicU8::new(Level::Advanced as u8);
+
+/// 現在の難易度フィルタ
+pub fn max_level() -> Level {
+    Level::from_u8(MAX_LEVEL.load(Ordering::Relaxed))
+}
+
+/// 難易度フィルタを変更する（--level とメニューのトグルから使う）
+pub fn set_max_level(level: Level) {
+    MAX_LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+/// キー（モジュール名）からトピックを探す
+pub fn find_topic(key: &str) -> Option<&'static Topic> {
+    TOPICS.iter().find(|topic| topic.key == key)
+}
+
+/// コンテンツのバージョン（デモの added と revised の最大）
+pub fn content_version() -> u32 {
+    TOPICS
+        .iter()
+        .flat_map(|topic| topic.demos)
+        .map(|demo| demo.revised)
+        .max()
+        .unwrap_or(1)
+}
+
+/// "トピック::デモ名" 形式の ID からデモを探す
+pub fn find_demo(id: &str) -> Option<&'static Demo> {
+    let (topic, name) = id.split_once("::")?;
+    find_topic(topic)?
+        .demos
+        .iter()
+        .find(|demo| demo.name == name)
+}
+
+/// 名前を比べる。大文字と小文字、区切りの `_` `-` 空白の違いは無視する
+/// （"Smart pointers" も "smart-pointers" も smart_pointers と同じ）
+pub fn same_name(a: &str, b: &str) -> bool {
+    fn normalize(name: &str) -> impl Iterator<Item = char> + '_ {
+        name.trim().chars().map(|c| {
+            if matches!(c, '-' | ' ' | '　') {
+                '_'
+            } else {
+                c.to_ascii_lowercase()
+            }
+        })
+    }
+    normalize(a).eq(normalize(b))
+}
+
+/// 名前（キー、タイトル、別名）からトピックを探す
+pub fn find_topic_by_name(name: &str) -> Option<&'static Topic> {
+    TOPICS.iter().find(|topic| {
+        same_name(topic.key, name)
+            || same_name(topic.title, name)
+            || topic.aliases.iter().any(|alias| same_name(alias, name))
+    })
+}
+
+/// 数字（メニュー番号）または名前（キー、タイトル、別名）でトピックを指定する
+pub fn resolve_topic(arg: &str) -> Option<&'static Topic> {
+    let arg = arg.trim();
+    match arg.parse::<usize>() {
+        Ok(number) => TOPICS.get(number.checked_sub(1)?),
+        Err(_) => find_topic(arg).or_else(|| find_topic_by_name(arg)),
+    }
+}
+
+/// 前提トピックをたどり、goal に到達するまでの学習順序を返す（トポロジカルソート）
+/// デモごとの前提（Demo::requires）もたどる。goal 自身が最後の要素になる
+pub fn learning_path(goal: &'static Topic) -> Vec<&'static Topic> {
+    fn visit(
+        topic: &'static Topic,
+        visiting: &mut Vec<&'static str>,
+        path: &mut Vec<&'static Topic>,
+    ) {
+        if path.iter().any(|done| done.key == topic.key) {
+            return;
+        }
+        // レジストリのデータが壊れていないかの確認（循環は定義ミス。テストですべてのトピックを確かめている）
+        assert!(
+            !visiting.contains(&topic.key),
+            "前提関係が循環しています: {:?} -> {}",
+            visiting,
+            topic.key
+        );
+        visiting.push(topic.key);
+        for key in topic.prerequisites() {
+            let required = find_topic(key).expect("未登録の前提トピックです");
+            visit(required, visiting, path);
+        }
+        visiting.pop();
+        path.push(topic);
+    }
+
+    let mut path = Vec::new();
+    visit(goal, &mut Vec::new(), &mut path);
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn topic_names_resolve_to_one_topic() {
+        assert_eq!(resolve_topic("2").map(|t| t.key), Some("ownership"));
+        assert_eq!(resolve_topic("所有権").map(|t| t.key), Some("ownership"));
+        assert_eq!(
+            resolve_topic(" Smart Pointers ").map(|t| t.key),
+            Some("smart_pointers")
+        );
+        assert_eq!(
+            resolve_topic("スマートポインタ").map(|t| t.key),
+            Some("smart_pointers")
+        );
+        assert_eq!(resolve_topic("async").map(|t| t.key), Some("async_await"));
+        assert!(resolve_topic("ownerships").is_none());
+
+        // 別名が2つのトピックにまたがると、先に登録した方しか引けなくなる
+        for topic in TOPICS {
+            let names = [topic.key, topic.title]
+                .into_iter()
+                .chain(topic.aliases.iter().copied());
+            for name in names {
+                assert_eq!(
+                    find_topic_by_name(name).map(|t| t.key),
+                    Some(topic.key),
+                    "{}",
+                    name
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn prerequisite_graph_has_no_cycles() {
+        // 前提が循環していたり未登録のキーがあったりすると learning_path が panic する
+        for topic in TOPICS {
+            let path = learning_path(topic);
+            assert_eq!(path.last().map(|t| t.key), Some(topic.key));
+            for (i, step) in path.iter().enumerate() {
+                for key in step.prerequisites() {
+                    assert!(
+                        path[..i].iter().any(|done| done.key == key),
+                        "{} の前提 {} が先に並んでいません",
+                        step.key,
+                        key
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn demo_requires_are_part_of_the_path() {
+        let error_handling = find_topic("error_handling").unwrap();
+        assert!(!error_handling.requires.contains(&"traits_generics"));
+        assert!(error_handling.prerequisites().contains(&"traits_generics"));
+        let keys: Vec<&str> = learning_path(error_handling)
+            .iter()
+            .map(|t| t.key)
+            .collect();
+        assert!(keys.contains(&"traits_generics"), "{:?}", keys);
+    }
+}
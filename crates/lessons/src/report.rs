@@ -0,0 +1,174 @@
+// ============================================================================
+// 埋め込み用の API（run_lesson）
+// ============================================================================
+//
+// GUI やノートブックからレッスンを実行して、結果を構造化して受け取るための API。
+// 標準出力を読み取って区切る代わりに、デモごとの出力と所要時間、成否が手に入る。
+//
+//   let report = lessons::run_lesson("ownership").expect("トピックがある");
+//   for section in &report.sections {
+//       println!("{}: {:?}（{:?}）", section.title, section.outcome, section.duration);
+//       show(&section.output);
+//   }
+//
+// デモの中には assert! / assert_eq! で結果を確かめているものがある。
+// それが失敗する（デモが panic する）と、そのデモは Failed になり、残りのデモは続けて実行する。
+// panic のメッセージは Failed に入るが、既定のパニックフックは標準エラーにも書く。
+//
+// 名前には、トピック（キー、タイトル、別名、メニューの番号）か "トピック::デモ名" を使う。
+// トピックを指定したときは、難易度フィルタ（set_max_level）を通るデモだけを実行する。
+
+use crate::{find_demo, find_topic, resolve_topic, Demo, Topic};
+use lesson_api::{output, panics};
+use std::panic::{self, AssertUnwindSafe};
+use std::time::Duration;
+
+/// デモの成否
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Passed,
+    /// panic した（中のアサーションが失敗したなど）。panic のメッセージを持つ
+    Failed(String),
+}
+
+/// 1つのデモの実行結果
+#[derive(Debug, Clone)]
+pub struct Section {
+    pub demo: &'static str,
+    pub title: &'static str,
+    /// デモが print! / println! で書いた内容（panic したときは、それまでの分）
+    pub output: String,
+    pub duration: Duration,
+    pub outcome: Outcome,
+}
+
+/// レッスン（トピック、または1つのデモ）の実行結果
+#[derive(Debug, Clone)]
+pub struct LessonReport {
+    pub topic: &'static str,
+    pub title: &'static str,
+    pub sections: Vec<Section>,
+    /// すべてのデモの所要時間の合計
+    pub duration: Duration,
+}
+
+impl LessonReport {
+    /// すべてのデモが最後まで実行できたか
+    pub fn passed(&self) -> bool {
+        self.sections
+            .iter()
+            .all(|section| section.outcome == Outcome::Passed)
+    }
+
+    /// 失敗したデモ
+    pub fn failures(&self) -> impl Iterator<Item = &Section> {
+        self.sections
+            .iter()
+            .filter(|section| section.outcome != Outcome::Passed)
+    }
+
+    /// すべてのデモの出力をつなげたもの（端末で実行したときと同じ）
+    pub fn output(&self) -> String {
+        self.sections
+            .iter()
+            .map(|section| section.output.as_str())
+            .collect()
+    }
+}
+
+/// レッスンを実行して結果を返す（名前が見つからなければ None）
+pub fn run_lesson(name: &str) -> Option<LessonReport> {
+    let name = name.trim();
+    let (topic, demos): (&'static Topic, Vec<&'static Demo>) = match name.split_once("::") {
+        Some((key, _)) => (find_topic(key)?, vec![find_demo(name)?]),
+        None => {
+            let topic = resolve_topic(name)?;
+            (topic, topic.visible_demos().collect())
+        }
+    };
+
+    let sections: Vec<Section> = demos.into_iter().map(run_section).collect();
+    Some(LessonReport {
+        topic: topic.key,
+        title: topic.title,
+        duration: sections.iter().map(|section| section.duration).sum(),
+        sections,
+    })
+}
+
+fn run_section(demo: &'static Demo) -> Section {
+    let mut outcome = Outcome::Passed;
+    let (output, duration) = timed(|| {
+        // capture の内側で panic を受け止めて、panic する前の出力も残す
+        output::capture(|| {
+            if let Err(payload) = panic::catch_unwind(AssertUnwindSafe(demo.run)) {
+                outcome = Outcome::Failed(panics::message(payload.as_ref()));
+            }
+        })
+    });
+    Section {
+        demo: demo.name,
+        title: demo.title,
+        output,
+        duration,
+        outcome,
+    }
+}
+
+// wasm32-unknown-unknown では Instant::now() が使えないので、時間は測らない
+#[cfg(not(target_arch = "wasm32"))]
+fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = std::time::Instant::now();
+    let value = f();
+    (value, start.elapsed())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    (f(), Duration::ZERO)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Level;
+
+    #[test]
+    fn reports_each_demo_with_its_output() {
+        let report = run_lesson("graphs").unwrap();
+        assert_eq!(report.topic, "graphs");
+        assert_eq!(report.sections.len(), 3);
+        assert!(report.passed());
+        assert!(report.sections.iter().all(|s| !s.output.is_empty()));
+        assert_eq!(
+            report.output(),
+            report
+                .sections
+                .iter()
+                .map(|s| s.output.clone())
+                .collect::<String>()
+        );
+
+        let report = run_lesson("ownership::slices").unwrap();
+        assert_eq!(report.sections.len(), 1);
+        assert_eq!(report.sections[0].demo, "slices");
+        assert!(run_lesson("ownership::nothing").is_none());
+        assert!(run_lesson("nothing").is_none());
+    }
+
+    #[test]
+    fn failed_assertions_keep_the_output_before_them() {
+        fn broken() {
+            lesson_api::println!("ここまでは表示される");
+            assert_eq!(1 + 1, 3, "計算が合わない");
+        }
+        static BROKEN: Demo = crate::demo("broken", "壊れたデモ", Level::Beginner, broken);
+
+        let section = run_section(&BROKEN);
+        assert_eq!(section.output, "ここまでは表示される\n");
+        match section.outcome {
+            Outcome::Failed(message) => assert!(message.contains("計算が合わない")),
+            Outcome::Passed => panic!("失敗になるはず"),
+        }
+    }
+}
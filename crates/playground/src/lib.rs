@@ -0,0 +1,147 @@
+// ============================================================================
+// ブラウザ版（WebAssembly）の入口
+// ============================================================================
+//
+// lessons のデモを wasm32-unknown-unknown 向けにビルドし、web/index.html から呼ぶ。
+// デモの出力は lesson_api::output::capture で文字列として受け取る。
+//
+//   rustup target add wasm32-unknown-unknown
+//   cargo build -p playground --release --target wasm32-unknown-unknown
+//   （web/build.sh がビルドして web/playground.wasm にコピーする）
+//
+// wasm-bindgen などは使わず、数値だけをやりとりする関数を公開している。
+// 文字列を返す関数は結果を RESULT に書いてバイト数を返すので、JavaScript は
+// playground_result_ptr() の位置から WebAssembly.Memory を UTF-8 として読む。
+
+use lesson_api::output;
+use lessons::{Level, Topic, TOPICS};
+use std::sync::Mutex;
+
+// 確保の回数を数えるデモがブラウザでも動くように、本体と同じアロケータを使う
+#[global_allocator]
+static ALLOCATOR: lesson_api::alloc_count::CountingAlloc = lesson_api::alloc_count::CountingAlloc;
+
+// JavaScript に渡す最後の結果（次の呼び出しまで有効）
+static RESULT: Mutex<String> = Mutex::new(String::new());
+
+/// トピックとデモの一覧（1行1件のタブ区切り）
+///
+///   topic<TAB>キー<TAB>タイトル<TAB>章
+///   demo<TAB>名前<TAB>タイトル<TAB>難易度（beginner / intermediate / advanced）
+///
+/// demo の行は直前の topic に属する
+pub fn catalog() -> String {
+    let mut text = String::new();
+    for topic in TOPICS {
+        text.push_str(&format!(
+            "topic\t{}\t{}\t{}\n",
+            topic.key, topic.title, topic.chapter
+        ));
+        for demo in topic.demos {
+            text.push_str(&format!(
+                "demo\t{}\t{}\t{}\n",
+                demo.name,
+                demo.title,
+                level_name(demo.level)
+            ));
+        }
+    }
+    text
+}
+
+fn level_name(level: Level) -> &'static str {
+    match level {
+        Level::Beginner => "beginner",
+        Level::Intermediate => "intermediate",
+        Level::Advanced => "advanced",
+    }
+}
+
+// 章の run_all() が呼ぶランナー（難易度の絞り込みはしない）
+fn run_every_demo(key: &str) {
+    if let Some(topic) = lessons::find_topic(key) {
+        for demo in topic.demos {
+            (demo.run)();
+        }
+    }
+}
+
+fn topic_at(index: u32) -> Option<&'static Topic> {
+    TOPICS.get(index as usize)
+}
+
+/// 1つのデモを実行し、出力を返す
+pub fn run_demo(topic: u32, demo: u32) -> Option<String> {
+    let demo = topic_at(topic)?.demos.get(demo as usize)?;
+    Some(output::capture(demo.run))
+}
+
+/// トピックのデモをすべて実行し、出力を返す
+pub fn run_topic(topic: u32) -> Option<String> {
+    let topic = topic_at(topic)?;
+    lesson_api::set_topic_runner(run_every_demo);
+    Some(output::capture(topic.run_all))
+}
+
+// 結果を RESULT に置いてバイト数を返す（見つからなければ 0）
+fn publish(text: Option<String>) -> usize {
+    let mut result = RESULT.lock().unwrap_or_else(|e| e.into_inner());
+    *result = text.unwrap_or_default();
+    result.len()
+}
+
+/// 最後の結果の先頭アドレス（wasm のメモリ上の位置）
+#[no_mangle]
+pub extern "C" fn playground_result_ptr() -> *const u8 {
+    RESULT.lock().unwrap_or_else(|e| e.into_inner()).as_ptr()
+}
+
+/// catalog() を結果に置く
+#[no_mangle]
+pub extern "C" fn playground_catalog() -> usize {
+    publish(Some(catalog()))
+}
+
+/// run_demo() の出力を結果に置く（番号はどちらも 0 始まり）
+#[no_mangle]
+pub extern "C" fn playground_run_demo(topic: u32, demo: u32) -> usize {
+    publish(run_demo(topic, demo))
+}
+
+/// run_topic() の出力を結果に置く
+#[no_mangle]
+pub extern "C" fn playground_run_topic(topic: u32) -> usize {
+    publish(run_topic(topic))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn catalog_lists_every_topic_and_demo() {
+        let text = catalog();
+        let topics = text.lines().filter(|line| line.starts_with("topic\t"));
+        assert_eq!(topics.count(), TOPICS.len());
+        let demos: usize = TOPICS.iter().map(|topic| topic.demos.len()).sum();
+        assert_eq!(text.lines().count(), TOPICS.len() + demos);
+        assert!(text.contains("demo\tslices\tスライス\tbeginner\n"));
+    }
+
+    #[test]
+    fn captures_demo_and_topic_output() {
+        let ownership = TOPICS.iter().position(|t| t.key == "ownership").unwrap() as u32;
+        let slices = TOPICS[ownership as usize]
+            .demos
+            .iter()
+            .position(|d| d.name == "slices")
+            .unwrap() as u32;
+        let text = run_demo(ownership, slices).unwrap();
+        assert!(text.starts_with("\n=== スライス ==="), "{}", text);
+
+        let all = run_topic(ownership).unwrap();
+        assert!(all.contains("=== スライス ===") && all.contains("=== 所有権の基本 ==="));
+        assert_eq!(run_demo(ownership, 999), None);
+        assert_eq!(publish(run_topic(999)), 0);
+    }
+}
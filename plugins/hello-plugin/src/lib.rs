@@ -0,0 +1,83 @@
+// ============================================================================
+// プラグインの例: クレートをまたぐトレイトオブジェクト
+// ============================================================================
+//
+// lesson-api のトレイトを実装するだけで、gkRustPractice のメニューに
+// レッスンが追加される。本体では feature で有効にする:
+//
+//   cargo run --features plugin-hello
+//
+// このクレート自体も「別のクレートで定義されたトレイトを実装する」例になっている。
+
+use lesson_api::{println, Demo, FnDemo, LessonModule, Level};
+
+/// ランナーが呼び出す入り口
+pub fn lesson() -> Box<dyn LessonModule> {
+    Box::new(HelloLesson)
+}
+
+struct HelloLesson;
+
+impl LessonModule for HelloLesson {
+    fn key(&self) -> &'static str {
+        "hello_plugin"
+    }
+
+    fn title(&self) -> &'static str {
+        "プラグインの例（クレートをまたぐトレイト）"
+    }
+
+    fn requires(&self) -> &'static [&'static str] {
+        &["traits_generics"]
+    }
+
+    fn demos(&self) -> Vec<Box<dyn Demo>> {
+        vec![
+            Box::new(FnDemo::new(
+                "hello",
+                "プラグインからのあいさつ",
+                Level::Beginner,
+                hello,
+            )),
+            Box::new(Counter { start: 3 }),
+        ]
+    }
+}
+
+fn hello() {
+    println!("このデモは hello-plugin クレートで定義されています");
+    println!(
+        "crate: {} {}",
+        env!("CARGO_PKG_NAME"),
+        env!("CARGO_PKG_VERSION")
+    );
+    println!("API バージョン: {}", lesson_api::API_VERSION);
+}
+
+// 関数ではなく、状態を持つ構造体で Demo を実装する例
+struct Counter {
+    start: u32,
+}
+
+impl Demo for Counter {
+    fn name(&self) -> &'static str {
+        "countdown"
+    }
+
+    fn title(&self) -> &'static str {
+        "状態を持つデモ（構造体で Demo を実装）"
+    }
+
+    fn level(&self) -> Level {
+        Level::Intermediate
+    }
+
+    fn run(&self) {
+        let steps: Vec<String> = (1..=self.start).rev().map(|n| n.to_string()).collect();
+        println!("{} → 発射！", steps.join(" → "));
+        println!(
+            "Box<dyn Demo> の中身は {} バイトの構造体",
+            std::mem::size_of::<Self>()
+        );
+    }
+}
@@ -0,0 +1,378 @@
+// ============================================================================
+// 実績（achievements）
+// ============================================================================
+//
+// 学習の節目に実績を贈って、ひとりで学ぶときの励みにする。
+//
+//   デモを 1 / 10 / 50 / 100 回実行した
+//   トピック（章）のデモをすべて実行した      例: 「コレクション」(Ch.8) を修了
+//   トピックのクイズで全問正解した            例: 「ライフタイム」のクイズで満点
+//   初めてクイズに答えた、すべてのトピックを修了した
+//
+// 実行したデモとクイズの結果、獲得した実績は学習データの保存先の achievements.tsv に残す
+// （利用統計と違って送るものも集計するものもなく、実績の判定だけに使う）。
+// 獲得するとメニューの入力の前に知らせ、`achievements` で一覧を表示する。
+// `achievements reset` で記録ごと削除する。
+
+use crate::i18n::{t, tf};
+use crate::quiz_pack;
+use crate::registry::{Topic, TopicText, TOPICS};
+use crate::storage;
+use std::collections::{BTreeMap, BTreeSet};
+use std::io;
+use std::sync::Mutex;
+
+const ACHIEVEMENTS_FILE: &str = "achievements.tsv";
+
+/// 実績になるデモの実行回数
+const DEMO_MILESTONES: &[u64] = &[1, 10, 50, 100];
+
+/// 実績の種類
+#[derive(Clone, Copy)]
+pub enum Kind {
+    /// デモを n 回実行した
+    DemoRuns(u64),
+    /// トピックのデモをすべて実行した
+    Topic(&'static Topic),
+    /// トピックのクイズで全問正解した
+    PerfectQuiz(&'static Topic),
+    FirstQuiz,
+    AllTopics,
+}
+
+impl Kind {
+    /// 保存に使う ID（"demos-50"、"topic-collections" など）
+    pub fn id(self) -> String {
+        match self {
+            Kind::DemoRuns(n) => format!("demos-{}", n),
+            Kind::Topic(topic) => format!("topic-{}", topic.key),
+            Kind::PerfectQuiz(topic) => format!("quiz-{}", topic.key),
+            Kind::FirstQuiz => "first-quiz".to_string(),
+            Kind::AllTopics => "all-topics".to_string(),
+        }
+    }
+
+    pub fn title(self) -> String {
+        match self {
+            Kind::DemoRuns(1) => t("achievements.first_demo").to_string(),
+            Kind::DemoRuns(n) => tf("achievements.demo_runs", &[("count", &n)]),
+            Kind::Topic(topic) => tf(
+                "achievements.topic",
+                &[
+                    ("topic", &topic.display_title()),
+                    ("chapter", &topic.display_chapter()),
+                ],
+            ),
+            Kind::PerfectQuiz(topic) => tf(
+                "achievements.perfect_quiz",
+                &[("topic", &topic.display_title())],
+            ),
+            Kind::FirstQuiz => t("achievements.first_quiz").to_string(),
+            Kind::AllTopics => t("achievements.all_topics").to_string(),
+        }
+    }
+}
+
+/// すべての実績（表示の順）
+pub fn all() -> Vec<Kind> {
+    let mut kinds: Vec<Kind> = DEMO_MILESTONES.iter().map(|&n| Kind::DemoRuns(n)).collect();
+    kinds.push(Kind::FirstQuiz);
+    kinds.extend(TOPICS.iter().map(Kind::Topic));
+    // クイズの問題があるトピックだけ
+    let questions = quiz_pack::all_questions();
+    kinds.extend(
+        TOPICS
+            .iter()
+            .filter(|topic| questions.iter().any(|q| q.topic == topic.key))
+            .map(Kind::PerfectQuiz),
+    );
+    kinds.push(Kind::AllTopics);
+    kinds
+}
+
+// ----------------------------------------------------------------------------
+// 記録
+// ----------------------------------------------------------------------------
+
+/// 実績の判定に使う記録と、獲得した実績
+#[derive(Debug, Default, PartialEq)]
+pub struct Record {
+    /// デモを実行した回数
+    pub demo_runs: u64,
+    /// 一度でも実行したデモ（"トピック::デモ名"）
+    pub demos_seen: BTreeSet<String>,
+    /// 答えたクイズの回数
+    pub quizzes: u64,
+    /// 全問正解したクイズのトピック
+    pub perfect_quizzes: BTreeSet<String>,
+    /// 獲得した実績の ID → 獲得した日（1970-01-01 からの日数）
+    pub earned: BTreeMap<String, u64>,
+}
+
+impl Record {
+    /// achievements.tsv のレコードから復元する（読めない行は無視する）
+    pub fn from_records(records: &[Vec<String>]) -> Record {
+        let mut record = Record::default();
+        for fields in records {
+            match fields.as_slice() {
+                [kind, n] if kind == "runs" => record.demo_runs = n.parse().unwrap_or(0),
+                [kind, n] if kind == "quizzes" => record.quizzes = n.parse().unwrap_or(0),
+                [kind, id] if kind == "seen" => {
+                    record.demos_seen.insert(id.clone());
+                }
+                [kind, key] if kind == "perfect" => {
+                    record.perfect_quizzes.insert(key.clone());
+                }
+                [kind, id, day] if kind == "earned" => {
+                    if let Ok(day) = day.parse() {
+                        record.earned.insert(id.clone(), day);
+                    }
+                }
+                _ => {}
+            }
+        }
+        record
+    }
+
+    pub fn to_records(&self) -> Vec<Vec<String>> {
+        let mut records = vec![
+            vec!["runs".to_string(), self.demo_runs.to_string()],
+            vec!["quizzes".to_string(), self.quizzes.to_string()],
+        ];
+        for id in &self.demos_seen {
+            records.push(vec!["seen".to_string(), id.clone()]);
+        }
+        for key in &self.perfect_quizzes {
+            records.push(vec!["perfect".to_string(), key.clone()]);
+        }
+        for (id, day) in &self.earned {
+            records.push(vec!["earned".to_string(), id.clone(), day.to_string()]);
+        }
+        records
+    }
+
+    /// 実行したデモの数（トピックのデモのうち）
+    fn seen_in(&self, topic: &Topic) -> usize {
+        topic
+            .demos
+            .iter()
+            .filter(|demo| {
+                self.demos_seen
+                    .contains(&format!("{}::{}", topic.key, demo.name))
+            })
+            .count()
+    }
+
+    /// 実績の条件を満たしているか
+    pub fn meets(&self, kind: Kind) -> bool {
+        match kind {
+            Kind::DemoRuns(n) => self.demo_runs >= n,
+            Kind::Topic(topic) => self.seen_in(topic) == topic.demos.len(),
+            Kind::PerfectQuiz(topic) => self.perfect_quizzes.contains(topic.key),
+            Kind::FirstQuiz => self.quizzes > 0,
+            Kind::AllTopics => TOPICS.iter().all(|topic| self.meets(Kind::Topic(topic))),
+        }
+    }
+
+    /// 条件を満たしたのにまだ獲得していない実績を獲得済みにして返す
+    pub fn award(&mut self, today: u64) -> Vec<Kind> {
+        let new: Vec<Kind> = all()
+            .into_iter()
+            .filter(|&kind| !self.earned.contains_key(&kind.id()) && self.meets(kind))
+            .collect();
+        for kind in &new {
+            self.earned.insert(kind.id(), today);
+        }
+        new
+    }
+
+    /// 未獲得の実績の進み具合（"23/50" など。段階の無いものは None）
+    fn progress(&self, kind: Kind) -> Option<String> {
+        match kind {
+            Kind::DemoRuns(n) => Some(format!("{}/{}", self.demo_runs.min(n), n)),
+            Kind::Topic(topic) => Some(format!("{}/{}", self.seen_in(topic), topic.demos.len())),
+            _ => None,
+        }
+    }
+}
+
+// 読み込んだ記録と、まだ保存していない変更があるか（初めて使うときに読み込む）
+static STATE: Mutex<Option<(Record, bool)>> = Mutex::new(None);
+
+fn with_record<T>(f: impl FnOnce(&mut Record, &mut bool) -> T) -> T {
+    let mut state = STATE.lock().unwrap_or_else(|e| e.into_inner());
+    let (record, dirty) = state.get_or_insert_with(|| {
+        let records = storage::load_records(ACHIEVEMENTS_FILE).unwrap_or_default();
+        (Record::from_records(&records), false)
+    });
+    f(record, dirty)
+}
+
+/// デモを1回実行したことを記録する（registry::run_demos から呼ばれる）
+pub fn record_demo(topic: &str, demo: &str) {
+    with_record(|record, dirty| {
+        record.demo_runs += 1;
+        record.demos_seen.insert(format!("{}::{}", topic, demo));
+        *dirty = true;
+    });
+}
+
+/// クイズの結果を記録する（途中でやめた回は、全問正解として数えない）
+pub fn record_quiz(topic: Option<&str>, correct: usize, answered: usize, asked: usize) {
+    with_record(|record, dirty| {
+        record.quizzes += 1;
+        if let Some(topic) = topic {
+            if answered == asked && correct == answered {
+                record.perfect_quizzes.insert(topic.to_string());
+            }
+        }
+        *dirty = true;
+    });
+}
+
+/// 新しく獲得した実績を知らせ、記録を保存する（メニューの入力の前に呼ぶ）
+pub fn announce() {
+    let new = with_record(|record, dirty| {
+        if !*dirty {
+            return Vec::new();
+        }
+        let new = record.award(storage::today());
+        match storage::save_records(ACHIEVEMENTS_FILE, &record.to_records()) {
+            Ok(()) => *dirty = false,
+            Err(e) => eprintln!("{}", tf("achievements.save_failed", &[("error", &e)])),
+        }
+        new
+    });
+    for kind in &new {
+        println!(
+            "🏆 {}",
+            tf("achievements.earned", &[("title", &kind.title())])
+        );
+    }
+    if !new.is_empty() {
+        println!("{}\n", t("achievements.list_hint"));
+    }
+}
+
+// 1970-01-01 からの日数を "2024-05-01" の形にする
+fn format_day(day: u64) -> String {
+    // グレゴリオ暦の 400 年（146097 日）を単位に数える（3月始まりの年で計算する）
+    let z = day + 719_468;
+    let era = z / 146_097;
+    let doe = z % 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = doy - (153 * mp + 2) / 5 + 1;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 };
+    let y = yoe + era * 400 + u64::from(m <= 2);
+    format!("{}-{:02}-{:02}", y, m, d)
+}
+
+/// `achievements [reset]`
+pub fn run(arg: &str) {
+    match arg {
+        "" => print_achievements(),
+        "reset" => match reset() {
+            Ok(()) => println!("{}", t("achievements.reset")),
+            Err(e) => println!("{}", tf("achievements.reset_failed", &[("error", &e)])),
+        },
+        _ => println!("{}", t("achievements.usage")),
+    }
+}
+
+// 獲得した実績と、まだの実績を表示する
+fn print_achievements() {
+    with_record(|record, _| {
+        let kinds = all();
+        let (earned, locked): (Vec<Kind>, Vec<Kind>) = kinds
+            .iter()
+            .partition(|kind| record.earned.contains_key(&kind.id()));
+
+        println!(
+            "\n{}",
+            tf(
+                "achievements.header",
+                &[("earned", &earned.len()), ("total", &kinds.len())]
+            )
+        );
+        if earned.is_empty() {
+            println!("{}", t("achievements.none_yet"));
+        }
+        for kind in &earned {
+            println!(
+                "  🏆 {}  ({})",
+                kind.title(),
+                format_day(record.earned[&kind.id()])
+            );
+        }
+        if !locked.is_empty() {
+            println!("\n{}", t("achievements.locked"));
+        }
+        for kind in &locked {
+            match record.progress(*kind) {
+                Some(progress) => println!("  ・ {}  [{}]", kind.title(), progress),
+                None => println!("  ・ {}", kind.title()),
+            }
+        }
+        println!();
+    });
+}
+
+fn reset() -> io::Result<()> {
+    with_record(|record, dirty| {
+        *record = Record::default();
+        *dirty = false;
+    });
+    storage::save_records(ACHIEVEMENTS_FILE, &[])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+
+    #[test]
+    fn awards_achievements_once_when_conditions_are_met() {
+        let mut record = Record::default();
+        assert!(record.award(100).is_empty());
+
+        let collections = registry::find_topic("collections").unwrap();
+        for demo in collections.demos {
+            record.demo_runs += 1;
+            record
+                .demos_seen
+                .insert(format!("collections::{}", demo.name));
+        }
+        record.quizzes = 1;
+        record.perfect_quizzes.insert("lifetimes".to_string());
+
+        let ids: Vec<String> = record.award(100).into_iter().map(Kind::id).collect();
+        assert_eq!(
+            ids,
+            [
+                "demos-1",
+                "demos-10",
+                "first-quiz",
+                "topic-collections",
+                "quiz-lifetimes"
+            ]
+        );
+        assert!(record.award(101).is_empty());
+        assert_eq!(record.earned["topic-collections"], 100);
+        assert_eq!(
+            record.progress(Kind::DemoRuns(50)).as_deref(),
+            Some("19/50")
+        );
+
+        // 保存と復元で同じ記録に戻る
+        assert_eq!(Record::from_records(&record.to_records()), record);
+    }
+
+    #[test]
+    fn formats_days_as_dates() {
+        assert_eq!(format_day(0), "1970-01-01");
+        assert_eq!(format_day(19_723), "2024-01-01");
+        assert_eq!(format_day(19_782), "2024-02-29");
+    }
+}
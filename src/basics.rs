@@ -1,259 +0,0 @@
-// ============================================================================
-// Rust基本構文サンプル - 変数、データ型、関数
-// 公式ドキュメント: https://doc.rust-lang.org/book/ch03-00-common-programming-concepts.html
-// ============================================================================
-
-/// 変数と可変性のデモ
-/// Rustでは変数はデフォルトで不変（immutable）
-/// mutキーワードを使うことで可変（mutable）にできる
-pub fn variables_demo() {
-    println!("\n=== 変数と可変性 ===");
-
-    // 不変変数（デフォルト）
-    let x = 5;
-    println!("不変変数 x = {}", x);
-    // x = 6; // コンパイルエラー！不変変数は再代入できない
-
-    // 可変変数
-    let mut y = 5;
-    println!("可変変数 y = {}", y);
-    y = 6;
-    println!("y を変更後: y = {}", y);
-
-    // シャドーイング - 同じ名前の変数を再定義できる
-    let z = 5;
-    let z = z + 1; // 新しい変数zが前のzをシャドーイング
-    let z = z * 2;
-    println!("シャドーイング後の z = {}", z); // 12
-
-    // シャドーイングでは型も変更可能
-    let spaces = "   "; // &str型
-    let spaces = spaces.len(); // usize型に変更
-    println!("spacesの長さ: {}", spaces);
-}
-
-/// 定数のデモ
-/// 定数は常に不変で、型注釈が必須
-pub fn constants_demo() {
-    println!("\n=== 定数 ===");
-
-    // 定数は大文字スネークケースで命名
-    const MAX_POINTS: u32 = 100_000;
-    const PI: f64 = 3.14159;
-
-    println!("MAX_POINTS = {}", MAX_POINTS);
-    println!("PI = {}", PI);
-}
-
-/// データ型のデモ
-/// Rustは静的型付け言語で、コンパイル時にすべての変数の型が決まる
-pub fn data_types_demo() {
-    println!("\n=== データ型 ===");
-
-    // --- スカラー型 ---
-    println!("\n-- スカラー型 --");
-
-    // 整数型
-    let _i8: i8 = -128; // 8ビット符号付き
-    let _u8: u8 = 255; // 8ビット符号なし
-    let _i32: i32 = -2_147_483_648; // 32ビット符号付き（デフォルト）
-    let _u64: u64 = 18_446_744_073_709_551_615; // 64ビット符号なし
-    let _isize: isize = 100; // アーキテクチャ依存（32 or 64ビット）
-
-    println!("整数リテラル:");
-    println!("  10進数: {}", 98_222);
-    println!("  16進数: {}", 0xff);
-    println!("  8進数: {}", 0o77);
-    println!("  2進数: {}", 0b1111_0000);
-    println!("  バイト(u8のみ): {}", b'A');
-
-    // 浮動小数点型
-    let float64: f64 = 3.14159; // 64ビット（デフォルト）
-    let float32: f32 = 2.5; // 32ビット
-    println!("f64: {}, f32: {}", float64, float32);
-
-    // 数値演算
-    println!("\n数値演算:");
-    println!("  加算: 5 + 10 = {}", 5 + 10);
-    println!("  減算: 95.5 - 4.3 = {}", 95.5 - 4.3);
-    println!("  乗算: 4 * 30 = {}", 4 * 30);
-    println!("  除算: 56.7 / 32.2 = {}", 56.7 / 32.2);
-    println!("  整数除算: 5 / 3 = {}", 5 / 3); // 結果は1
-    println!("  剰余: 43 % 5 = {}", 43 % 5);
-
-    // ブーリアン型
-    let t: bool = true;
-    let f: bool = false;
-    println!("\nブーリアン: true={}, false={}", t, f);
-
-    // 文字型
-    let c: char = 'z';
-    let emoji: char = '🦀'; // Unicodeスカラー値を格納
-    let kanji: char = '漢';
-    println!("文字型: c='{}', emoji='{}', kanji='{}'", c, emoji, kanji);
-
-    // --- 複合型 ---
-    println!("\n-- 複合型 --");
-
-    // タプル型 - 異なる型の値をグループ化
-    let tup: (i32, f64, u8) = (500, 6.4, 1);
-    let (x, y, z) = tup; // 分解（デストラクチャリング）
-    println!("タプル分解: x={}, y={}, z={}", x, y, z);
-    println!(
-        "タプルインデックスアクセス: tup.0={}, tup.1={}, tup.2={}",
-        tup.0, tup.1, tup.2
-    );
-
-    // ユニット型 - 空のタプル、値を返さない式の戻り値
-    let _unit: () = ();
-
-    // 配列型 - 同じ型の固定長コレクション
-    let arr: [i32; 5] = [1, 2, 3, 4, 5];
-    println!("配列: {:?}", arr);
-    println!("配列の最初の要素: arr[0] = {}", arr[0]);
-
-    // 同じ値で初期化
-    let zeros = [0; 5]; // [0, 0, 0, 0, 0]
-    println!("ゼロで初期化した配列: {:?}", zeros);
-}
-
-/// 関数のデモ
-/// Rustの関数はfnキーワードで定義
-pub fn functions_demo() {
-    println!("\n=== 関数 ===");
-
-    // 引数なしの関数呼び出し
-    simple_function();
-
-    // 引数ありの関数呼び出し
-    print_value(42);
-    print_labeled_measurement(5, 'h');
-
-    // 戻り値のある関数
-    let result = add(5, 3);
-    println!("add(5, 3) = {}", result);
-
-    // 式と文の違い
-    let y = {
-        let x = 3;
-        x + 1 // セミコロンなし = 式（値を返す）
-    };
-    println!("ブロック式の結果: y = {}", y);
-
-    // 早期リターン
-    let five = return_five();
-    println!("return_five() = {}", five);
-}
-
-// 単純な関数
-fn simple_function() {
-    println!("  simple_function が呼ばれました");
-}
-
-// 引数を持つ関数（パラメータの型注釈は必須）
-fn print_value(x: i32) {
-    println!("  print_value: x = {}", x);
-}
-
-// 複数の引数
-fn print_labeled_measurement(value: i32, unit_label: char) {
-    println!("  measurement: {}{}", value, unit_label);
-}
-
-// 戻り値のある関数（-> で戻り値の型を指定）
-fn add(a: i32, b: i32) -> i32 {
-    a + b // 最後の式が戻り値（セミコロンなし）
-}
-
-// returnキーワードで早期リターン
-fn return_five() -> i32 {
-    return 5;
-    // この下のコードは実行されない
-}
-
-/// 制御フローのデモ
-pub fn control_flow_demo() {
-    println!("\n=== 制御フロー ===");
-
-    // --- if式 ---
-    println!("\n-- if式 --");
-    let number = 6;
-
-    if number % 4 == 0 {
-        println!("{} は4で割り切れる", number);
-    } else if number % 3 == 0 {
-        println!("{} は3で割り切れる", number);
-    } else if number % 2 == 0 {
-        println!("{} は2で割り切れる", number);
-    } else {
-        println!("{} は4, 3, 2で割り切れない", number);
-    }
-
-    // ifは式なのでletで使える
-    let condition = true;
-    let value = if condition { 5 } else { 6 };
-    println!("条件式の結果: value = {}", value);
-
-    // --- ループ ---
-    println!("\n-- ループ --");
-
-    // loop - 無限ループ（breakで抜ける）
-    let mut counter = 0;
-    let result = loop {
-        counter += 1;
-        if counter == 10 {
-            break counter * 2; // breakで値を返せる
-        }
-    };
-    println!("loopの結果: {}", result);
-
-    // ループラベル - ネストしたループで外側のループを制御
-    let mut count = 0;
-    'counting_up: loop {
-        let mut remaining = 10;
-        loop {
-            if remaining == 9 {
-                break; // 内側のループを抜ける
-            }
-            if count == 2 {
-                break 'counting_up; // 外側のループを抜ける
-            }
-            remaining -= 1;
-        }
-        count += 1;
-    }
-    println!("ループラベルの例: count = {}", count);
-
-    // while - 条件付きループ
-    let mut number = 3;
-    while number != 0 {
-        println!("while: {}!", number);
-        number -= 1;
-    }
-    println!("while終了!");
-
-    // for - コレクションの反復
-    let a = [10, 20, 30, 40, 50];
-    for element in a {
-        println!("for: 値は {}", element);
-    }
-
-    // Rangeを使ったfor
-    println!("Rangeでカウントダウン:");
-    for number in (1..4).rev() {
-        println!("  {}!", number);
-    }
-}
-
-/// すべてのデモを実行
-pub fn run_all() {
-    println!("╔════════════════════════════════════════════════════════════════╗");
-    println!("║          Rust基本構文サンプル                                   ║");
-    println!("╚════════════════════════════════════════════════════════════════╝");
-
-    variables_demo();
-    constants_demo();
-    data_types_demo();
-    functions_demo();
-    control_flow_demo();
-}
@@ -0,0 +1,313 @@
+// ============================================================================
+// 複数トピックの連続実行（`1,3,5` や `2-4`）
+// ============================================================================
+//
+// メニューで番号をカンマ区切りや範囲で入力すると、選んだトピックを順に実行し、
+// 最後にトピックごとのデモ数と所要時間のまとめを表示する（復習用）。
+//
+//   選択: 1,3,5      → 1, 3, 5 番
+//   選択: 2-4        → 2, 3, 4 番
+//   選択: 1, 4-6, 2  → 書いた順（同じ番号は1回だけ）
+
+use crate::i18n::{t, tf};
+use crate::plugins::{self, PluginTopic};
+use crate::registry::{Topic, TopicText, TOPICS};
+use std::collections::HashSet;
+use std::fmt;
+use std::io;
+use std::panic::{self, AssertUnwindSafe};
+use std::time::{Duration, Instant};
+
+// ----------------------------------------------------------------------------
+// 入力の解析
+// ----------------------------------------------------------------------------
+
+/// 番号の指定を読み取れなかった理由
+#[derive(Debug, PartialEq)]
+pub enum SelectionError {
+    /// 番号が1つもない（"," だけなど）
+    Empty,
+    /// 数字として読めない部分
+    NotANumber(String),
+    /// "4-2" のように範囲の始まりが終わりより大きい
+    Reversed(usize, usize),
+    /// メニューにない番号
+    OutOfRange(usize),
+}
+
+impl fmt::Display for SelectionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SelectionError::Empty => write!(f, "{}", t("batch.empty")),
+            SelectionError::NotANumber(part) => {
+                write!(f, "{}", tf("batch.not_a_number", &[("part", part)]))
+            }
+            SelectionError::Reversed(start, end) => write!(
+                f,
+                "{}",
+                tf("batch.reversed", &[("start", start), ("end", end)])
+            ),
+            SelectionError::OutOfRange(n) => {
+                write!(f, "{}", tf("batch.out_of_range", &[("number", n)]))
+            }
+        }
+    }
+}
+
+impl std::error::Error for SelectionError {}
+
+/// 複数の番号の指定に見えるか（数字と "," "-" 空白だけで、"," か "-" を含む）
+pub fn is_selection(input: &str) -> bool {
+    input.contains([',', '-'])
+        && input
+            .chars()
+            .all(|c| c.is_ascii_digit() || c == ',' || c == '-' || c.is_whitespace())
+}
+
+/// "1,3,5" や "2-4" を番号の列にする（書いた順、重複は除く）
+/// 番号は 1..=max の範囲でなければならない
+pub fn parse_selection(input: &str, max: usize) -> Result<Vec<usize>, SelectionError> {
+    let number = |text: &str| -> Result<usize, SelectionError> {
+        let text = text.trim();
+        let n: usize = text
+            .parse()
+            .map_err(|_| SelectionError::NotANumber(text.to_string()))?;
+        if (1..=max).contains(&n) {
+            Ok(n)
+        } else {
+            Err(SelectionError::OutOfRange(n))
+        }
+    };
+
+    let mut numbers = Vec::new();
+    // 空の部分（"1,,3" や末尾の ","）は読み飛ばす
+    for part in input.split(',').filter(|part| !part.trim().is_empty()) {
+        let (start, end) = match part.split_once('-') {
+            Some((start, end)) => (number(start)?, number(end)?),
+            None => {
+                let n = number(part)?;
+                (n, n)
+            }
+        };
+        if start > end {
+            return Err(SelectionError::Reversed(start, end));
+        }
+        for n in start..=end {
+            if !numbers.contains(&n) {
+                numbers.push(n);
+            }
+        }
+    }
+    if numbers.is_empty() {
+        return Err(SelectionError::Empty);
+    }
+    Ok(numbers)
+}
+
+// ----------------------------------------------------------------------------
+// 連続実行とまとめ
+// ----------------------------------------------------------------------------
+
+/// メニューの1項目（組み込みのトピックかプラグイン）
+#[derive(Clone, Copy)]
+pub enum Entry {
+    Topic(&'static Topic),
+    Plugin(&'static PluginTopic),
+}
+
+impl Entry {
+    /// メニュー番号（1始まり、プラグインは組み込みのトピックの続き）から引く
+    /// 難易度フィルタで表示されていない項目は None
+    pub fn from_number(number: usize) -> Option<Entry> {
+        let i = number.checked_sub(1)?;
+        match TOPICS.get(i) {
+            Some(topic) => topic.is_visible().then_some(Entry::Topic(topic)),
+            None => {
+                let plugin = plugins::installed().get(i - TOPICS.len())?;
+                plugin.is_visible().then_some(Entry::Plugin(plugin))
+            }
+        }
+    }
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            Entry::Topic(topic) => topic.key,
+            Entry::Plugin(plugin) => plugin.key(),
+        }
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            Entry::Topic(topic) => topic.display_title(),
+            Entry::Plugin(plugin) => plugin.title(),
+        }
+    }
+
+    pub fn requires(&self) -> Vec<&'static str> {
+        match self {
+            Entry::Topic(topic) => topic.prerequisites(),
+            Entry::Plugin(plugin) => plugin.requires().to_vec(),
+        }
+    }
+
+    /// 現在の難易度フィルタで実行されるデモの数
+    fn demo_count(&self) -> usize {
+        match self {
+            Entry::Topic(topic) => topic.visible_demos().count(),
+            Entry::Plugin(plugin) => plugin.visible_demos().count(),
+        }
+    }
+
+    fn run(&self) {
+        match self {
+            Entry::Topic(topic) => (topic.run_all)(),
+            Entry::Plugin(plugin) => plugin.run_all(),
+        }
+    }
+}
+
+/// 1トピック分の実行結果
+pub struct Outcome {
+    pub number: usize,
+    pub title: &'static str,
+    pub demos: usize,
+    pub elapsed: Duration,
+    pub panicked: bool,
+}
+
+/// メニューの項目の総数（組み込みのトピックとプラグイン）
+pub fn menu_len() -> usize {
+    TOPICS.len() + plugins::installed().len()
+}
+
+/// 選んだ番号のトピックを順に実行し、最後にまとめを表示する
+/// 実行したトピックは finished に加える（前提の確認は1つずつ実行するときと同じ）
+pub fn run(numbers: &[usize], finished: &mut HashSet<&'static str>) {
+    let entries: Vec<(usize, Entry)> = numbers
+        .iter()
+        .filter_map(|&n| Entry::from_number(n).map(|entry| (n, entry)))
+        .collect();
+    if entries.len() < numbers.len() {
+        println!("{}", t("batch.hidden_skipped"));
+    }
+
+    let mut outcomes = Vec::new();
+    for (i, (number, entry)) in entries.iter().enumerate() {
+        println!(
+            "\n{}",
+            tf(
+                "batch.progress",
+                &[
+                    ("current", &(i + 1)),
+                    ("total", &entries.len()),
+                    ("title", &entry.title())
+                ]
+            )
+        );
+        // 標準出力への書き込みに失敗しても、トピックの実行は続ける
+        let _ = crate::warn_if_jumping_ahead(
+            &mut io::stdout(),
+            entry.title(),
+            entry.key(),
+            &entry.requires(),
+            finished,
+        );
+
+        let start = Instant::now();
+        // 1つのトピックが panic しても、残りのトピックとまとめは最後まで表示する
+        let result = panic::catch_unwind(AssertUnwindSafe(|| entry.run()));
+        outcomes.push(Outcome {
+            number: *number,
+            title: entry.title(),
+            demos: entry.demo_count(),
+            elapsed: start.elapsed(),
+            panicked: result.is_err(),
+        });
+        if result.is_ok() {
+            finished.insert(entry.key());
+        }
+    }
+    print_summary(&outcomes);
+}
+
+/// トピックごとのデモ数と所要時間、その合計を表示する
+pub fn print_summary(outcomes: &[Outcome]) {
+    println!("\n{}", "═".repeat(64));
+    println!("  {}", tf("batch.summary", &[("count", &outcomes.len())]));
+    println!("{}", "═".repeat(64));
+    for outcome in outcomes {
+        let mark = if outcome.panicked { "✗" } else { "✓" };
+        println!(
+            "  {} {:>2}. {:>3} {} {:>9.1} ms  {}",
+            mark,
+            outcome.number,
+            outcome.demos,
+            t("batch.demos"),
+            outcome.elapsed.as_secs_f64() * 1000.0,
+            outcome.title
+        );
+    }
+
+    let demos: usize = outcomes.iter().map(|o| o.demos).sum();
+    let elapsed: Duration = outcomes.iter().map(|o| o.elapsed).sum();
+    let failed = outcomes.iter().filter(|o| o.panicked).count();
+    println!("{}", "─".repeat(64));
+    println!(
+        "  {}",
+        tf(
+            "batch.total",
+            &[
+                ("topics", &outcomes.len()),
+                ("demos", &demos),
+                ("ms", &format!("{:.1}", elapsed.as_secs_f64() * 1000.0))
+            ]
+        )
+    );
+    if failed > 0 {
+        println!("  {}", tf("batch.failed", &[("count", &failed)]));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_lists_and_ranges_in_written_order() {
+        assert_eq!(parse_selection("1,3,5", 10), Ok(vec![1, 3, 5]));
+        assert_eq!(parse_selection("2-4", 10), Ok(vec![2, 3, 4]));
+        assert_eq!(parse_selection(" 5, 1 - 2 ,2,", 10), Ok(vec![5, 1, 2]));
+        assert_eq!(parse_selection("3-3", 10), Ok(vec![3]));
+
+        assert!(is_selection("1,3"));
+        assert!(is_selection("2 - 4"));
+        assert!(!is_selection("12"));
+        assert!(!is_selection("smart-pointers"));
+    }
+
+    #[test]
+    fn rejects_bad_selections() {
+        assert_eq!(parse_selection(",", 10), Err(SelectionError::Empty));
+        assert_eq!(
+            parse_selection("1,x", 10),
+            Err(SelectionError::NotANumber("x".to_string()))
+        );
+        assert_eq!(
+            parse_selection("2-", 10),
+            Err(SelectionError::NotANumber(String::new()))
+        );
+        assert_eq!(
+            parse_selection("4-2", 10),
+            Err(SelectionError::Reversed(4, 2))
+        );
+        assert_eq!(
+            parse_selection("0,1", 10),
+            Err(SelectionError::OutOfRange(0))
+        );
+        // 大きな範囲でも、番号を並べる前に範囲外として弾く
+        assert_eq!(
+            parse_selection("1-999999999", 10),
+            Err(SelectionError::OutOfRange(999999999))
+        );
+    }
+}
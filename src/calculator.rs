@@ -0,0 +1,356 @@
+// ============================================================================
+// ミニプロジェクト: 電卓 REPL
+// ============================================================================
+//
+// これまでの章の総まとめとして、対話的に式を計算する電卓を作る。
+//   - 字句解析: 文字列 → トークン列（イテレータ、列挙型）
+//   - 構文解析: 優先順位と括弧を考慮した再帰下降パーサ
+//   - 評価:     変数を HashMap に保存（コレクション、エラー処理）
+//
+//   > x = 3
+//   > (x + 2) * 4 ^ 2
+//   80
+//
+// 演算子の優先順位（高い順）: ^（右結合） > 単項 - > * / % > + -
+
+// println! は capture で受け取れる lesson_api 版（テストで REPL の出力を確かめる）
+use crate::i18n::{t, tf};
+use lesson_api::input::{Input, StdinInput};
+use lesson_api::println;
+use std::collections::HashMap;
+use std::fmt;
+use std::iter::Peekable;
+use std::str::Chars;
+
+/// トークン
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Ident(String),
+    Op(char),
+    LParen,
+    RParen,
+    Assign,
+}
+
+/// 計算中に起こりうるエラー
+#[derive(Debug, Clone, PartialEq)]
+pub enum CalcError {
+    UnexpectedChar(char),
+    UnexpectedToken(String),
+    UnexpectedEnd,
+    UnknownVariable(String),
+    DivisionByZero,
+}
+
+impl fmt::Display for CalcError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CalcError::UnexpectedChar(c) => {
+                write!(f, "{}", tf("calc.unexpected_char", &[("char", c)]))
+            }
+            CalcError::UnexpectedToken(token) => {
+                write!(f, "{}", tf("calc.unexpected_token", &[("token", token)]))
+            }
+            CalcError::UnexpectedEnd => write!(f, "{}", t("calc.unexpected_end")),
+            CalcError::UnknownVariable(name) => {
+                write!(f, "{}", tf("calc.unknown_variable", &[("name", name)]))
+            }
+            CalcError::DivisionByZero => write!(f, "{}", t("calc.division_by_zero")),
+        }
+    }
+}
+
+impl std::error::Error for CalcError {}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, CalcError> {
+    let mut chars: Peekable<Chars> = input.chars().peekable();
+    let mut tokens = Vec::new();
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            c if c.is_ascii_digit() || c == '.' => {
+                let mut text = String::new();
+                while let Some(&c) = chars.peek() {
+                    if !(c.is_ascii_digit() || c == '.') {
+                        break;
+                    }
+                    text.push(c);
+                    chars.next();
+                }
+                let n = text
+                    .parse()
+                    .map_err(|_| CalcError::UnexpectedToken(text.clone()))?;
+                tokens.push(Token::Number(n));
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let mut name = String::new();
+                while let Some(&c) = chars.peek() {
+                    if !(c.is_alphanumeric() || c == '_') {
+                        break;
+                    }
+                    name.push(c);
+                    chars.next();
+                }
+                tokens.push(Token::Ident(name));
+            }
+            '+' | '-' | '*' | '/' | '%' | '^' => {
+                tokens.push(Token::Op(c));
+                chars.next();
+            }
+            '(' => {
+                tokens.push(Token::LParen);
+                chars.next();
+            }
+            ')' => {
+                tokens.push(Token::RParen);
+                chars.next();
+            }
+            '=' => {
+                tokens.push(Token::Assign);
+                chars.next();
+            }
+            other => return Err(CalcError::UnexpectedChar(other)),
+        }
+    }
+    Ok(tokens)
+}
+
+fn describe(token: &Token) -> String {
+    match token {
+        Token::Number(n) => n.to_string(),
+        Token::Ident(name) => name.clone(),
+        Token::Op(c) => format!("'{}'", c),
+        Token::LParen => "'('".to_string(),
+        Token::RParen => "')'".to_string(),
+        Token::Assign => "'='".to_string(),
+    }
+}
+
+/// 変数を保持して式を評価する電卓
+pub struct Calculator {
+    vars: HashMap<String, f64>,
+}
+
+impl Calculator {
+    pub fn new() -> Self {
+        let mut vars = HashMap::new();
+        vars.insert("pi".to_string(), std::f64::consts::PI);
+        vars.insert("e".to_string(), std::f64::consts::E);
+        Calculator { vars }
+    }
+
+    /// 1行を評価する。`名前 = 式` なら変数に代入する
+    /// 結果は `ans` にも保存される
+    pub fn eval_line(&mut self, line: &str) -> Result<f64, CalcError> {
+        let tokens = tokenize(line)?;
+        let (target, expr) = match tokens.as_slice() {
+            [Token::Ident(name), Token::Assign, rest @ ..] => (Some(name.clone()), rest),
+            _ => (None, tokens.as_slice()),
+        };
+
+        let mut parser = Parser {
+            tokens: expr.iter().peekable(),
+            vars: &self.vars,
+        };
+        let value = parser.expr()?;
+        if let Some(token) = parser.tokens.next() {
+            return Err(CalcError::UnexpectedToken(describe(token)));
+        }
+
+        if let Some(name) = target {
+            self.vars.insert(name, value);
+        }
+        self.vars.insert("ans".to_string(), value);
+        Ok(value)
+    }
+
+    /// 定義済みの変数を名前順に返す
+    pub fn variables(&self) -> Vec<(&str, f64)> {
+        let mut vars: Vec<(&str, f64)> = self
+            .vars
+            .iter()
+            .map(|(name, &value)| (name.as_str(), value))
+            .collect();
+        vars.sort_by(|a, b| a.0.cmp(b.0));
+        vars
+    }
+}
+
+impl Default for Calculator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 再帰下降パーサ（解析しながらその場で計算する）
+///   expr   = term (('+' | '-') term)*
+///   term   = unary (('*' | '/' | '%') unary)*
+///   unary  = '-' unary | power
+///   power  = atom ('^' unary)?
+///   atom   = 数値 | 変数 | '(' expr ')'
+struct Parser<'a, I: Iterator<Item = &'a Token>> {
+    tokens: Peekable<I>,
+    vars: &'a HashMap<String, f64>,
+}
+
+impl<'a, I: Iterator<Item = &'a Token>> Parser<'a, I> {
+    fn expr(&mut self) -> Result<f64, CalcError> {
+        let mut value = self.term()?;
+        while let Some(&&Token::Op(op @ ('+' | '-'))) = self.tokens.peek() {
+            self.tokens.next();
+            let rhs = self.term()?;
+            value = if op == '+' { value + rhs } else { value - rhs };
+        }
+        Ok(value)
+    }
+
+    fn term(&mut self) -> Result<f64, CalcError> {
+        let mut value = self.unary()?;
+        while let Some(&&Token::Op(op @ ('*' | '/' | '%'))) = self.tokens.peek() {
+            self.tokens.next();
+            let rhs = self.unary()?;
+            value = match op {
+                '*' => value * rhs,
+                _ if rhs == 0.0 => return Err(CalcError::DivisionByZero),
+                '/' => value / rhs,
+                _ => value % rhs,
+            };
+        }
+        Ok(value)
+    }
+
+    fn unary(&mut self) -> Result<f64, CalcError> {
+        if let Some(Token::Op('-')) = self.tokens.peek() {
+            self.tokens.next();
+            return Ok(-self.unary()?);
+        }
+        self.power()
+    }
+
+    fn power(&mut self) -> Result<f64, CalcError> {
+        let base = self.atom()?;
+        if let Some(Token::Op('^')) = self.tokens.peek() {
+            self.tokens.next();
+            // 右結合: 2 ^ 3 ^ 2 = 2 ^ (3 ^ 2)
+            let exponent = self.unary()?;
+            return Ok(base.powf(exponent));
+        }
+        Ok(base)
+    }
+
+    fn atom(&mut self) -> Result<f64, CalcError> {
+        match self.tokens.next() {
+            Some(Token::Number(n)) => Ok(*n),
+            Some(Token::Ident(name)) => self
+                .vars
+                .get(name)
+                .copied()
+                .ok_or_else(|| CalcError::UnknownVariable(name.clone())),
+            Some(Token::LParen) => {
+                let value = self.expr()?;
+                match self.tokens.next() {
+                    Some(Token::RParen) => Ok(value),
+                    Some(token) => Err(CalcError::UnexpectedToken(describe(token))),
+                    None => Err(CalcError::UnexpectedEnd),
+                }
+            }
+            Some(token) => Err(CalcError::UnexpectedToken(describe(token))),
+            None => Err(CalcError::UnexpectedEnd),
+        }
+    }
+}
+
+/// `calc`: 電卓 REPL を起動する
+pub fn run() {
+    repl(StdinInput);
+}
+
+/// 入力が終わるか q / 空行が入力されるまで、1行ずつ計算する
+pub fn repl(mut input: impl Input) {
+    println!("\n{}", t("calc.header"));
+    println!("{}", t("calc.intro"));
+    println!("{}", t("calc.keys"));
+
+    let mut calculator = Calculator::new();
+    loop {
+        let Ok(Some(line)) = input.prompt("calc> ") else {
+            return;
+        };
+
+        match line.trim() {
+            "" | "q" => return,
+            "help" => {
+                println!("  {}", t("calc.help_operators"));
+                println!("  {}", t("calc.help_assignment"));
+            }
+            "vars" => {
+                for (name, value) in calculator.variables() {
+                    println!("  {} = {}", name, value);
+                }
+            }
+            input => match calculator.eval_line(input) {
+                Ok(value) => println!("{}", value),
+                Err(e) => println!("{}", tf("calc.error", &[("error", &e)])),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lesson_api::input::ScriptedInput;
+
+    fn eval(line: &str) -> Result<f64, CalcError> {
+        Calculator::new().eval_line(line)
+    }
+
+    #[test]
+    fn respects_precedence_and_parentheses() {
+        assert_eq!(eval("1 + 2 * 3"), Ok(7.0));
+        assert_eq!(eval("(1 + 2) * 3"), Ok(9.0));
+        assert_eq!(eval("10 - 4 - 3"), Ok(3.0));
+        assert_eq!(eval("2 ^ 3 ^ 2"), Ok(512.0));
+        assert_eq!(eval("-2 ^ 2"), Ok(-4.0));
+        assert_eq!(eval("7 % 4 + 0.5"), Ok(3.5));
+    }
+
+    #[test]
+    fn stores_variables_and_ans() {
+        let mut calc = Calculator::new();
+        assert_eq!(calc.eval_line("x = 4"), Ok(4.0));
+        assert_eq!(calc.eval_line("y = x * 2"), Ok(8.0));
+        assert_eq!(calc.eval_line("ans + x"), Ok(12.0));
+        assert!(calc.variables().contains(&("y", 8.0)));
+    }
+
+    #[test]
+    fn reports_errors() {
+        assert_eq!(eval("1 / 0"), Err(CalcError::DivisionByZero));
+        assert_eq!(
+            eval("foo + 1"),
+            Err(CalcError::UnknownVariable("foo".to_string()))
+        );
+        assert_eq!(eval("(1 + 2"), Err(CalcError::UnexpectedEnd));
+        assert_eq!(
+            eval("1 2"),
+            Err(CalcError::UnexpectedToken("2".to_string()))
+        );
+        assert_eq!(eval("1 $ 2"), Err(CalcError::UnexpectedChar('$')));
+    }
+
+    #[test]
+    fn repl_reads_lines_until_quit() {
+        let script = "r = 2\n(r + 1) * 3\n1 / 0\nq\nans\n";
+        let output = lesson_api::output::capture(|| repl(ScriptedInput::new(script)));
+        let answers: Vec<&str> = output
+            .lines()
+            .filter_map(|line| line.strip_prefix("calc> "))
+            .collect();
+        // 最後の "" は q を読んだときのプロンプト（q のあとの ans は読まない）
+        assert_eq!(answers, ["2", "9", "エラー: 0 で割ることはできません", ""]);
+    }
+}
@@ -0,0 +1,112 @@
+// ============================================================================
+// チートシート生成
+// ============================================================================
+//
+// 各モジュールが持つ CHEATSHEET（構造化データ）から、章ごとの早見表を作る。
+// 文字列をその場で組み立てるのではなく、データと表示を分けておくことで
+// ターミナル表示と Markdown 出力の両方に同じ内容を使える。
+// 見出しとトピックの名前は表示言語に合わせ、説明とハマりどころは "text:" の訳があれば訳す。
+
+use crate::i18n::{self, t, tf};
+use crate::registry::{Topic, TopicText};
+use lesson_api::text::{display_width, pad_right};
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// ターミナル向けのテキストを作る
+pub fn render_text(topic: &Topic) -> String {
+    let sheet = topic.cheatsheet;
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "\n{}",
+        tf(
+            "cheatsheet.header",
+            &[
+                ("title", &topic.display_title()),
+                ("chapter", &topic.display_chapter())
+            ]
+        )
+    );
+
+    let code_width = sheet
+        .syntax
+        .iter()
+        .chain(sheet.methods)
+        .map(|(code, _)| display_width(code))
+        .max()
+        .unwrap_or(0);
+
+    let _ = writeln!(out, "\n-- {} --", t("cheatsheet.syntax"));
+    for (code, note) in sheet.syntax {
+        let _ = writeln!(
+            out,
+            "  {}  {}",
+            pad_right(code, code_width),
+            i18n::text(note)
+        );
+    }
+
+    let _ = writeln!(out, "\n-- {} --", t("cheatsheet.methods"));
+    for (code, note) in sheet.methods {
+        let _ = writeln!(
+            out,
+            "  {}  {}",
+            pad_right(code, code_width),
+            i18n::text(note)
+        );
+    }
+
+    let _ = writeln!(out, "\n-- {} --", t("cheatsheet.gotchas"));
+    for gotcha in sheet.gotchas {
+        let _ = writeln!(out, "  ⚠ {}", i18n::text(gotcha));
+    }
+    out
+}
+
+/// Markdown を作る（表のセル内の `|` はエスケープする）
+pub fn render_markdown(topic: &Topic) -> String {
+    fn cell(s: &str) -> String {
+        format!("`{}`", s.replace('|', "\\|"))
+    }
+
+    let sheet = topic.cheatsheet;
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "# {} ({})\n",
+        topic.display_title(),
+        topic.display_chapter()
+    );
+
+    let table_header = format!(
+        "| {} | {} |\n|--------|------|",
+        t("cheatsheet.code"),
+        t("cheatsheet.note")
+    );
+    let _ = writeln!(out, "## {}\n", t("cheatsheet.syntax"));
+    let _ = writeln!(out, "{}", table_header);
+    for (code, note) in sheet.syntax {
+        let _ = writeln!(out, "| {} | {} |", cell(code), i18n::text(note));
+    }
+
+    let _ = writeln!(out, "\n## {}\n", t("cheatsheet.methods"));
+    let _ = writeln!(out, "{}", table_header);
+    for (code, note) in sheet.methods {
+        let _ = writeln!(out, "| {} | {} |", cell(code), i18n::text(note));
+    }
+
+    let _ = writeln!(out, "\n## {}\n", t("cheatsheet.gotchas"));
+    for gotcha in sheet.gotchas {
+        let _ = writeln!(out, "- {}", i18n::text(gotcha));
+    }
+    out
+}
+
+/// 複数トピックのチートシートを1つの Markdown ファイルに書き出す
+pub fn write_markdown(topics: &[&Topic], path: &Path) -> io::Result<()> {
+    let body: Vec<String> = topics.iter().map(|topic| render_markdown(topic)).collect();
+    fs::write(path, body.join("\n"))
+}
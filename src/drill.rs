@@ -0,0 +1,237 @@
+// ============================================================================
+// 反復練習（repeat）
+// ============================================================================
+//
+// 1つのデモやクイズを N 回続けて実行し、最後に所要時間や得点をまとめて表示する。
+// 乱数を使うクイズや、実行時間を測るデモを何度か試すときに使う。
+//
+//   repeat ownership::slices 5     デモを 5 回（所要時間の最小・中央値・平均・最大）
+//   repeat quiz 3                  全トピックのクイズを 3 回（回ごとの得点と正答率）
+//   repeat quiz lifetimes 3        トピックを指定したクイズを 3 回
+
+use crate::achievements;
+use crate::i18n::{t, tf};
+use crate::quiz;
+use crate::quiz_results;
+use crate::registry::{self, Demo, Topic, TopicText};
+use crate::usage;
+use lesson_api::output;
+use std::time::{Duration, Instant};
+
+/// 1回の repeat で実行できる最大回数
+const MAX_REPEAT: usize = 100;
+
+/// 繰り返す対象
+pub enum Target {
+    /// "トピック::デモ名" のデモ
+    Demo(&'static str, &'static Demo),
+    /// クイズ（トピックの指定は任意）
+    Quiz(Option<&'static Topic>),
+}
+
+/// `repeat <デモ|quiz [トピック]> <回数>` の引数を読む
+pub fn parse(arg: &str) -> Result<(Target, usize), String> {
+    let Some((target, count)) = arg.trim().rsplit_once(char::is_whitespace) else {
+        return Err(t("drill.usage").to_string());
+    };
+    let count = match count.parse::<usize>() {
+        Ok(n) if (1..=MAX_REPEAT).contains(&n) => n,
+        _ => return Err(tf("drill.bad_count", &[("max", &MAX_REPEAT)])),
+    };
+
+    let target = target.trim();
+    let target = match target.split_once(char::is_whitespace) {
+        _ if target == "quiz" => Target::Quiz(None),
+        Some(("quiz", topic)) => match registry::resolve_topic(topic.trim()) {
+            Some(topic) => Target::Quiz(Some(topic)),
+            None => return Err(tf("main.unknown_topic", &[("name", &topic.trim())])),
+        },
+        _ => match registry::find_demo(target) {
+            Some(demo) => {
+                let (topic, _) = target.split_once("::").unwrap();
+                Target::Demo(registry::find_topic(topic).unwrap().key, demo)
+            }
+            None => return Err(tf("drill.unknown_demo", &[("name", &target)])),
+        },
+    };
+    Ok((target, count))
+}
+
+/// `repeat ...`: 対象を指定の回数だけ実行し、まとめを表示する
+pub fn run(arg: &str) {
+    match parse(arg) {
+        Ok((Target::Demo(topic, demo), count)) => repeat_demo(topic, demo, count),
+        Ok((Target::Quiz(topic), count)) => repeat_quiz(topic, count),
+        Err(message) => println!("{}", message),
+    }
+}
+
+// ----------------------------------------------------------------------------
+// デモ: 所要時間の統計
+// ----------------------------------------------------------------------------
+
+/// 所要時間の要約
+#[derive(Debug, PartialEq)]
+pub struct Timing {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub max: Duration,
+}
+
+/// 所要時間の列を要約する（空なら None）
+pub fn summarize(times: &[Duration]) -> Option<Timing> {
+    let mut sorted = times.to_vec();
+    sorted.sort();
+    let n = sorted.len();
+    let median = match n {
+        0 => return None,
+        // 偶数個なら真ん中の2つの平均
+        _ if n.is_multiple_of(2) => (sorted[n / 2 - 1] + sorted[n / 2]) / 2,
+        _ => sorted[n / 2],
+    };
+    Some(Timing {
+        min: sorted[0],
+        median,
+        mean: sorted.iter().sum::<Duration>() / n as u32,
+        max: sorted[n - 1],
+    })
+}
+
+fn repeat_demo(topic: &str, demo: &Demo, count: usize) {
+    let mut times = Vec::with_capacity(count);
+    for i in 0..count {
+        println!("\n─── {} / {} ───", i + 1, count);
+        let start = Instant::now();
+        output::section(demo.run);
+        times.push(start.elapsed());
+        usage::record_demo(topic, demo.name);
+        achievements::record_demo(topic, demo.name);
+    }
+
+    let ms = |d: Duration| d.as_secs_f64() * 1000.0;
+    let timing = summarize(&times).expect("count は 1 以上");
+    let title = registry::find_topic(topic).map_or(demo.title, |t| t.demo_title(demo));
+    println!(
+        "\n{}",
+        tf("drill.header", &[("title", &title), ("count", &count)])
+    );
+    println!(
+        "  {}",
+        tf(
+            "drill.timing",
+            &[
+                ("min", &format!("{:.2}", ms(timing.min))),
+                ("median", &format!("{:.2}", ms(timing.median))),
+                ("mean", &format!("{:.2}", ms(timing.mean))),
+                ("max", &format!("{:.2}", ms(timing.max)))
+            ]
+        )
+    );
+    println!("  {}", t("drill.first_run_note"));
+}
+
+// ----------------------------------------------------------------------------
+// クイズ: 得点の統計
+// ----------------------------------------------------------------------------
+
+fn repeat_quiz(topic: Option<&Topic>, count: usize) {
+    let mut scores = Vec::with_capacity(count);
+    for i in 0..count {
+        println!("\n─── {} / {} ───", i + 1, count);
+        match quiz::run(topic) {
+            Some(score) => scores.push(score),
+            // 問題がないか中断した場合は、残りの回も行わない
+            None => break,
+        }
+    }
+    if scores.is_empty() {
+        return;
+    }
+
+    println!(
+        "\n{}",
+        tf(
+            "drill.quiz_header",
+            &[
+                ("title", &quiz::scope_title(topic)),
+                ("count", &scores.len())
+            ]
+        )
+    );
+    for (i, (correct, total)) in scores.iter().enumerate() {
+        let ratio = *correct as f64 / *total as f64;
+        println!(
+            "  {}",
+            tf(
+                "drill.quiz_row",
+                &[
+                    ("round", &format!("{:>2}", i + 1)),
+                    ("bar", &quiz_results::bar(ratio, 10)),
+                    ("correct", correct),
+                    ("total", total)
+                ]
+            )
+        );
+    }
+    let correct: usize = scores.iter().map(|(c, _)| c).sum();
+    let answered: usize = scores.iter().map(|(_, t)| t).sum();
+    let best = scores
+        .iter()
+        .map(|&(c, t)| c as f64 / t as f64)
+        .fold(0.0, f64::max);
+    println!(
+        "  {}",
+        tf(
+            "drill.quiz_total",
+            &[
+                ("correct", &correct),
+                ("total", &answered),
+                (
+                    "accuracy",
+                    &format!("{:.0}", correct as f64 / answered as f64 * 100.0)
+                ),
+                ("best", &format!("{:.0}", best * 100.0))
+            ]
+        )
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summarizes_durations() {
+        let ms = Duration::from_millis;
+        let timing = summarize(&[ms(30), ms(10), ms(20), ms(60)]).unwrap();
+        assert_eq!(
+            timing,
+            Timing {
+                min: ms(10),
+                median: ms(25),
+                mean: ms(30),
+                max: ms(60),
+            }
+        );
+        assert_eq!(summarize(&[ms(7)]).unwrap().median, ms(7));
+        assert_eq!(summarize(&[]), None);
+    }
+
+    #[test]
+    fn parses_demo_and_quiz_targets() {
+        assert!(matches!(
+            parse("ownership::slices 3"),
+            Ok((Target::Demo("ownership", demo), 3)) if demo.name == "slices"
+        ));
+        assert!(matches!(parse("quiz 2"), Ok((Target::Quiz(None), 2))));
+        assert!(matches!(
+            parse("quiz 所有権 4"),
+            Ok((Target::Quiz(Some(topic)), 4)) if topic.key == "ownership"
+        ));
+        assert!(parse("ownership::slices").is_err());
+        assert!(parse("ownership::slices 0").is_err());
+        assert!(parse("ownership::nope 2").is_err());
+        assert!(parse("quiz nope 2").is_err());
+    }
+}
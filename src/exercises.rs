@@ -0,0 +1,693 @@
+// ============================================================================
+// 写経・穴埋め演習（rustlings 風）
+// ============================================================================
+//
+// `scaffold <演習>` で TODO 付きのスターターファイルを書き出し、
+// 学習者が実装したら `check <演習>` で採点する。
+//
+// 採点用のテストはスターターファイルには含めず（隠しテスト）、
+// check のときに学習者のコードの末尾へ連結して rustc --test でビルド・実行する。
+// そのため cargo プロジェクトを作らなくても、rustc さえあれば動く。
+
+use crate::i18n::{self, t, tf};
+use std::env;
+use std::fs;
+use std::io::{self, IsTerminal};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// 1つの演習
+pub struct Exercise {
+    pub id: &'static str,
+    pub topic: &'static str,
+    pub title: &'static str,
+    /// スターターファイルの中身（TODO 付き）
+    pub starter: &'static str,
+    /// 隠しテスト（check のときだけ連結する）
+    pub tests: &'static str,
+    pub hint: &'static str,
+}
+
+/// scaffold / check でディレクトリを省略したときの書き出し先
+pub const DEFAULT_DIR: &str = "exercises";
+
+pub static EXERCISES: &[Exercise] = &[
+    Exercise {
+        id: "celsius",
+        topic: "basics",
+        title: "華氏を摂氏に変換する",
+        starter: r#"// 演習: celsius（基本構文）
+// 華氏温度を摂氏温度に変換する関数を実装してください。
+// 公式: C = (F - 32) * 5 / 9
+
+fn to_celsius(fahrenheit: f64) -> f64 {
+    // TODO: 公式どおりに計算して返す
+    todo!()
+}
+
+fn main() {
+    println!("212°F = {}°C", to_celsius(212.0));
+}
+"#,
+        tests: r#"
+#[cfg(test)]
+mod hidden_tests {
+    use super::*;
+
+    #[test]
+    fn boiling_point() {
+        assert_eq!(to_celsius(212.0), 100.0);
+    }
+
+    #[test]
+    fn freezing_point() {
+        assert_eq!(to_celsius(32.0), 0.0);
+    }
+
+    #[test]
+    fn minus_forty() {
+        assert_eq!(to_celsius(-40.0), -40.0);
+    }
+}
+"#,
+        hint: "f64 同士の演算なので 5.0 / 9.0 のように小数で書くとよい",
+    },
+    Exercise {
+        id: "first_word",
+        topic: "ownership",
+        title: "最初の単語をスライスで返す",
+        starter: r#"// 演習: first_word（所有権とスライス）
+// 文字列の最初の単語（最初の空白まで）を返してください。
+// 空白が無ければ文字列全体を返します。新しい String は作らないこと。
+
+fn first_word(s: &str) -> &str {
+    // TODO: s.as_bytes() を走査して最初の b' ' を探す
+    todo!()
+}
+
+fn main() {
+    println!("{}", first_word("hello world"));
+}
+"#,
+        tests: r#"
+#[cfg(test)]
+mod hidden_tests {
+    use super::*;
+
+    #[test]
+    fn two_words() {
+        assert_eq!(first_word("hello world"), "hello");
+    }
+
+    #[test]
+    fn single_word() {
+        assert_eq!(first_word("rust"), "rust");
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(first_word(""), "");
+    }
+}
+"#,
+        hint: "for (i, &b) in s.as_bytes().iter().enumerate() で位置を取り、&s[..i] を返す",
+    },
+    Exercise {
+        id: "rectangle",
+        topic: "structs_enums",
+        title: "Rectangle のメソッド",
+        starter: r#"// 演習: rectangle（構造体とメソッド）
+// Rectangle に area と can_hold を実装してください。
+
+struct Rectangle {
+    width: u32,
+    height: u32,
+}
+
+impl Rectangle {
+    fn area(&self) -> u32 {
+        // TODO: 面積を返す
+        todo!()
+    }
+
+    fn can_hold(&self, other: &Rectangle) -> bool {
+        // TODO: other が self の内側に収まるなら true
+        todo!()
+    }
+}
+
+fn main() {
+    let r = Rectangle { width: 30, height: 50 };
+    println!("面積: {}", r.area());
+}
+"#,
+        tests: r#"
+#[cfg(test)]
+mod hidden_tests {
+    use super::*;
+
+    #[test]
+    fn area() {
+        assert_eq!(Rectangle { width: 30, height: 50 }.area(), 1500);
+    }
+
+    #[test]
+    fn larger_can_hold_smaller() {
+        let larger = Rectangle { width: 8, height: 7 };
+        let smaller = Rectangle { width: 5, height: 1 };
+        assert!(larger.can_hold(&smaller));
+        assert!(!smaller.can_hold(&larger));
+    }
+}
+"#,
+        hint: "can_hold は幅と高さの両方が大きいかどうかを && で組み合わせる",
+    },
+    Exercise {
+        id: "parse_age",
+        topic: "error_handling",
+        title: "年齢のパースと検証",
+        starter: r#"// 演習: parse_age（エラーハンドリング）
+// 文字列を年齢（0〜150）として読み取ってください。
+// 数値でなければ Err("数値ではありません")、範囲外なら Err("範囲外です") を返します。
+
+fn parse_age(input: &str) -> Result<u8, String> {
+    // TODO: trim して parse し、範囲をチェックする
+    todo!()
+}
+
+fn main() {
+    println!("{:?}", parse_age("42"));
+}
+"#,
+        tests: r#"
+#[cfg(test)]
+mod hidden_tests {
+    use super::*;
+
+    #[test]
+    fn valid() {
+        assert_eq!(parse_age(" 42 "), Ok(42));
+    }
+
+    #[test]
+    fn not_a_number() {
+        assert_eq!(parse_age("abc"), Err(String::from("数値ではありません")));
+    }
+
+    #[test]
+    fn out_of_range() {
+        assert_eq!(parse_age("200"), Err(String::from("範囲外です")));
+    }
+}
+"#,
+        hint: "u8 に直接 parse すると 256 以上もエラーになるので、u32 などで読んでから範囲を見る",
+    },
+    Exercise {
+        id: "word_count",
+        topic: "collections",
+        title: "単語の出現回数を数える",
+        starter: r#"// 演習: word_count（コレクション）
+// 空白で区切られた単語の出現回数を数えてください（大文字小文字は区別しない）。
+
+use std::collections::HashMap;
+
+fn word_count(text: &str) -> HashMap<String, usize> {
+    // TODO: split_whitespace と entry API を使う
+    todo!()
+}
+
+fn main() {
+    println!("{:?}", word_count("the cat and the hat"));
+}
+"#,
+        tests: r#"
+#[cfg(test)]
+mod hidden_tests {
+    use super::*;
+
+    #[test]
+    fn counts_words() {
+        let counts = word_count("the cat and the hat");
+        assert_eq!(counts["the"], 2);
+        assert_eq!(counts["cat"], 1);
+        assert_eq!(counts.len(), 4);
+    }
+
+    #[test]
+    fn ignores_case() {
+        let counts = word_count("Rust rust RUST");
+        assert_eq!(counts["rust"], 3);
+    }
+}
+"#,
+        hint: "*map.entry(word.to_lowercase()).or_insert(0) += 1",
+    },
+    Exercise {
+        id: "even_squares",
+        topic: "iterators_closures",
+        title: "偶数の二乗の合計",
+        starter: r#"// 演習: even_squares（イテレータ）
+// スライス中の偶数だけを二乗して合計してください。for ループは使わずに。
+
+fn sum_of_even_squares(numbers: &[i32]) -> i32 {
+    // TODO: iter / filter / map / sum をつなげる
+    todo!()
+}
+
+fn main() {
+    println!("{}", sum_of_even_squares(&[1, 2, 3, 4]));
+}
+"#,
+        tests: r#"
+#[cfg(test)]
+mod hidden_tests {
+    use super::*;
+
+    #[test]
+    fn mixed() {
+        assert_eq!(sum_of_even_squares(&[1, 2, 3, 4]), 20);
+    }
+
+    #[test]
+    fn no_evens() {
+        assert_eq!(sum_of_even_squares(&[1, 3, 5]), 0);
+    }
+}
+"#,
+        hint: "numbers.iter().filter(|&&n| n % 2 == 0).map(|n| n * n).sum()",
+    },
+    Exercise {
+        id: "longest",
+        topic: "lifetimes",
+        title: "長い方の文字列を返す",
+        starter: r#"// 演習: longest（ライフタイム）
+// 2つの文字列スライスのうち長い方を返してください（同じ長さなら x）。
+// このままではコンパイルが通りません。ライフタイム注釈を追加してください。
+
+// TODO: ライフタイム注釈を付ける
+fn longest(x: &str, y: &str) -> &str {
+    if y.len() > x.len() {
+        y
+    } else {
+        x
+    }
+}
+
+fn main() {
+    println!("{}", longest("abcd", "xyz"));
+}
+"#,
+        tests: r#"
+#[cfg(test)]
+mod hidden_tests {
+    use super::*;
+
+    #[test]
+    fn picks_longer() {
+        assert_eq!(longest("abcd", "xyz"), "abcd");
+        assert_eq!(longest("a", "xyz"), "xyz");
+    }
+
+    #[test]
+    fn tie_returns_first() {
+        assert_eq!(longest("ab", "cd"), "ab");
+    }
+}
+"#,
+        hint: "fn longest<'a>(x: &'a str, y: &'a str) -> &'a str",
+    },
+];
+
+/// 採点結果
+pub enum CheckOutcome {
+    Passed,
+    /// 学習者のファイルが見つからない
+    Missing(PathBuf),
+    CompileError(String),
+    TestFailed(String),
+}
+
+/// ID で演習を探す
+pub fn find(id: &str) -> Option<&'static Exercise> {
+    EXERCISES.iter().find(|exercise| exercise.id == id)
+}
+
+/// 学習者のファイルの置き場所
+pub fn file_path(exercise: &Exercise, dir: &Path) -> PathBuf {
+    dir.join(format!("{}.rs", exercise.id))
+}
+
+/// 演習の一覧を表示する
+pub fn print_list() {
+    println!("\n{}", t("exercises.list_header"));
+    for exercise in EXERCISES {
+        println!(
+            "  {:<14} [{}] {}",
+            exercise.id,
+            exercise.topic,
+            i18n::text(exercise.title)
+        );
+    }
+    println!("\n{}", t("exercises.scaffold_hint"));
+}
+
+/// スターターファイルを書き出す（既存のファイルは上書きしない）
+pub fn scaffold(exercise: &Exercise, dir: &Path) -> io::Result<PathBuf> {
+    fs::create_dir_all(dir)?;
+    let path = file_path(exercise, dir);
+    if path.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            tf("exercises.exists", &[("path", &path.display())]),
+        ));
+    }
+    fs::write(&path, exercise.starter)?;
+    Ok(path)
+}
+
+/// 学習者のファイルに隠しテストを連結し、rustc --test でビルドして実行する
+pub fn check(exercise: &Exercise, dir: &Path) -> io::Result<CheckOutcome> {
+    let path = file_path(exercise, dir);
+    let source = match fs::read_to_string(&path) {
+        Ok(source) => source,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(CheckOutcome::Missing(path)),
+        Err(e) => return Err(e),
+    };
+
+    let work = env::temp_dir().join(format!(
+        "gkrust-check-{}-{}",
+        std::process::id(),
+        exercise.id
+    ));
+    fs::create_dir_all(&work)?;
+    let combined = work.join("main.rs");
+    let binary = work.join("exercise_test");
+    fs::write(&combined, format!("{}\n{}", source, exercise.tests))?;
+
+    let compile = Command::new("rustc")
+        .args(["--edition", "2021", "--test", "-A", "dead_code", "-o"])
+        .arg(&binary)
+        .arg(&combined)
+        .output()?;
+    if !compile.status.success() {
+        let _ = fs::remove_dir_all(&work);
+        return Ok(CheckOutcome::CompileError(
+            String::from_utf8_lossy(&compile.stderr).into_owned(),
+        ));
+    }
+
+    // バックトレースは学習者には長すぎるので出さない（差分の表示にも邪魔になる）
+    let run = Command::new(&binary)
+        .arg("--quiet")
+        .env("RUST_BACKTRACE", "0")
+        .output()?;
+    let _ = fs::remove_dir_all(&work);
+    if run.status.success() {
+        Ok(CheckOutcome::Passed)
+    } else {
+        Ok(CheckOutcome::TestFailed(
+            String::from_utf8_lossy(&run.stdout).into_owned(),
+        ))
+    }
+}
+
+/// `check <演習> [ディレクトリ]` の結果を表示する
+pub fn print_check(exercise: &Exercise, dir: &Path) {
+    println!(
+        "\n=== check: {} ({}) ===",
+        exercise.id,
+        i18n::text(exercise.title)
+    );
+    match check(exercise, dir) {
+        Ok(CheckOutcome::Passed) => {
+            println!("{}", t("exercises.passed"));
+            let source = fs::read_to_string(file_path(exercise, dir)).unwrap_or_default();
+            if source.contains("TODO") {
+                println!("  {}", t("exercises.todo_left"));
+            }
+        }
+        Ok(CheckOutcome::Missing(path)) => {
+            println!(
+                "{}",
+                tf(
+                    "exercises.missing",
+                    &[("path", &path.display()), ("id", &exercise.id)]
+                )
+            );
+        }
+        Ok(CheckOutcome::CompileError(stderr)) => {
+            println!("{}\n{}", t("exercises.compile_error"), stderr);
+            print_hint(exercise);
+        }
+        Ok(CheckOutcome::TestFailed(stdout)) => {
+            let failures = parse_failures(&stdout);
+            if failures.is_empty() {
+                println!("{}\n{}", t("exercises.test_failed"), stdout);
+            } else {
+                print_failures(&failures);
+            }
+            print_hint(exercise);
+        }
+        Err(e) => println!("{}", tf("exercises.check_failed", &[("error", &e)])),
+    }
+}
+
+// ----------------------------------------------------------------------------
+// 期待値と実際の値の差分
+// ----------------------------------------------------------------------------
+//
+// 隠しテストは assert_eq!(学習者のコードの結果, 期待値) の形で書いてある。
+// テストの出力から left（実際）と right（期待）を取り出し、行ごとの差分で見せる。
+//
+//   ---- hidden_tests::two_words stdout ----
+//   thread 'hidden_tests::two_words' panicked at main.rs:5:26:
+//   assertion `left == right` failed
+//     left: "hello world"
+//    right: "hello"
+
+/// 失敗した1つのテスト
+#[derive(Debug, PartialEq)]
+pub struct TestFailure {
+    /// テスト名（hidden_tests:: は除く）
+    pub test: String,
+    /// panic のメッセージ（assert_eq! なら "assertion `left == right` failed"）
+    pub message: String,
+    /// assert_eq! の両辺（実際, 期待）
+    pub values: Option<(String, String)>,
+}
+
+/// テストの出力から、失敗したテストごとのメッセージと assert_eq! の両辺を取り出す
+pub fn parse_failures(stdout: &str) -> Vec<TestFailure> {
+    let mut failures = Vec::new();
+    let mut lines = stdout.lines().peekable();
+    while let Some(line) = lines.next() {
+        let Some(name) = line
+            .strip_prefix("---- ")
+            .and_then(|rest| rest.strip_suffix(" stdout ----"))
+        else {
+            continue;
+        };
+        let mut failure = TestFailure {
+            test: name.trim_start_matches("hidden_tests::").to_string(),
+            message: String::new(),
+            values: None,
+        };
+        // 値が複数行にわたることもあるので、次の見出しまでをためてから分ける
+        let mut message = Vec::new();
+        let (mut left, mut right): (Option<Vec<&str>>, Option<Vec<&str>>) = (None, None);
+        while let Some(&line) = lines.peek() {
+            if line.starts_with("---- ")
+                || line.starts_with("stack backtrace:")
+                || line.starts_with("note: ")
+                || line == "failures:"
+            {
+                break;
+            }
+            lines.next();
+            if let Some(value) = line.strip_prefix("  left: ") {
+                left = Some(vec![value]);
+            } else if let Some(value) = line.strip_prefix(" right: ") {
+                right = Some(vec![value]);
+            } else if let Some(values) = right.as_mut().or(left.as_mut()) {
+                values.push(line);
+            } else if !line.is_empty() && !line.starts_with("thread '") {
+                message.push(line);
+            }
+        }
+        failure.message = message.join("\n");
+        if let (Some(left), Some(right)) = (left, right) {
+            let trim = |values: Vec<&str>| values.join("\n").trim_end().to_string();
+            failure.values = Some((trim(left), trim(right)));
+        }
+        failures.push(failure);
+    }
+    failures
+}
+
+/// 差分の1行
+#[derive(Debug, PartialEq)]
+pub enum DiffLine<'a> {
+    /// 両方にある行
+    Same(&'a str),
+    /// 期待値にだけある行
+    Expected(&'a str),
+    /// 実際の値にだけある行
+    Actual(&'a str),
+}
+
+/// 行ごとの差分（最長共通部分列で、共通の行をできるだけ残す）
+pub fn diff_lines<'a>(expected: &[&'a str], actual: &[&'a str]) -> Vec<DiffLine<'a>> {
+    // lcs[i][j] = expected[i..] と actual[j..] の最長共通部分列の長さ
+    let (n, m) = (expected.len(), actual.len());
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if expected[i] == actual[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut diff = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if expected[i] == actual[j] {
+            diff.push(DiffLine::Same(expected[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            diff.push(DiffLine::Expected(expected[i]));
+            i += 1;
+        } else {
+            diff.push(DiffLine::Actual(actual[j]));
+            j += 1;
+        }
+    }
+    diff.extend(expected[i..].iter().map(|line| DiffLine::Expected(line)));
+    diff.extend(actual[j..].iter().map(|line| DiffLine::Actual(line)));
+    diff
+}
+
+/// {:?} で表示された値を行に分ける
+/// 改行を含む文字列（"a\nb"）は、引用符を外して \n で区切った行として比べる
+fn value_lines(value: &str) -> Vec<&str> {
+    match value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) {
+        Some(text) if text.contains("\\n") => text.split("\\n").collect(),
+        _ => value.lines().collect(),
+    }
+}
+
+/// 差分を "- 期待" "+ 実際" の形の文字列にする（color なら赤と緑で色を付ける）
+pub fn render_diff(diff: &[DiffLine], color: bool) -> String {
+    let paint = |code: &str, text: String| {
+        if color {
+            format!("\x1b[{}m{}\x1b[0m", code, text)
+        } else {
+            text
+        }
+    };
+    diff.iter()
+        .map(|line| match line {
+            DiffLine::Same(text) => format!("    {}\n", text),
+            DiffLine::Expected(text) => paint("31", format!("  - {}", text)) + "\n",
+            DiffLine::Actual(text) => paint("32", format!("  + {}", text)) + "\n",
+        })
+        .collect()
+}
+
+fn print_hint(exercise: &Exercise) {
+    println!(
+        "{}",
+        tf("exercises.hint", &[("hint", &i18n::text(exercise.hint))])
+    );
+}
+
+/// 色を付けるか（端末に出力していて、NO_COLOR が設定されていない）
+fn use_color() -> bool {
+    io::stdout().is_terminal() && env::var_os("NO_COLOR").is_none()
+}
+
+/// 失敗したテストを、assert_eq! なら差分で、それ以外は panic のメッセージで表示する
+fn print_failures(failures: &[TestFailure]) {
+    let color = use_color();
+    println!(
+        "{}",
+        tf("exercises.failures", &[("count", &failures.len())])
+    );
+    for failure in failures {
+        println!(
+            "\n  {}",
+            tf("exercises.failed_test", &[("test", &failure.test)])
+        );
+        match &failure.values {
+            Some((actual, expected)) => {
+                println!("  {}", t("exercises.diff_legend"));
+                let diff = diff_lines(&value_lines(expected), &value_lines(actual));
+                print!("{}", render_diff(&diff, color));
+            }
+            None => println!("    {}", failure.message),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OUTPUT: &str = "
+running 3 tests
+failures:
+
+---- hidden_tests::two_words stdout ----
+
+thread 'hidden_tests::two_words' (123) panicked at main.rs:5:26:
+assertion `left == right` failed
+  left: \"hello world\"
+ right: \"hello\"
+note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace
+
+---- hidden_tests::empty stdout ----
+
+thread 'hidden_tests::empty' panicked at main.rs:7:21:
+not yet implemented
+
+failures:
+    hidden_tests::empty
+    hidden_tests::two_words
+";
+
+    #[test]
+    fn parses_assert_eq_values_and_other_panics() {
+        let failures = parse_failures(OUTPUT);
+        assert_eq!(failures.len(), 2);
+        assert_eq!(failures[0].test, "two_words");
+        assert_eq!(
+            failures[0].values,
+            Some(("\"hello world\"".to_string(), "\"hello\"".to_string()))
+        );
+        assert_eq!(failures[1].test, "empty");
+        assert_eq!(failures[1].message, "not yet implemented");
+        assert_eq!(failures[1].values, None);
+    }
+
+    #[test]
+    fn diff_keeps_common_lines() {
+        let diff = diff_lines(
+            &value_lines(r#""a\nb\nc""#),
+            &value_lines(r#""a\nx\nc\nd""#),
+        );
+        assert_eq!(
+            diff,
+            vec![
+                DiffLine::Same("a"),
+                DiffLine::Expected("b"),
+                DiffLine::Actual("x"),
+                DiffLine::Same("c"),
+                DiffLine::Actual("d"),
+            ]
+        );
+        assert_eq!(render_diff(&diff[1..3], false), "  - b\n  + x\n");
+    }
+}
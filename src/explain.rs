@@ -0,0 +1,275 @@
+// ============================================================================
+// コンパイルエラーの解説（explain）
+// ============================================================================
+//
+// 自分のコードで出たエラーのコード（E0382 など）から、原因と直し方、
+// その直し方を実際に見られるデモへ案内する。
+//
+//   explain E0382
+//   explain 382
+//   explain error[E0499]: cannot borrow `v` as mutable more than once at a time
+//
+// ここにあるのは学び始めによく出るものだけ。公式の詳しい説明は `rustc --explain E0382` で読める。
+// 原因と直し方はレッスンの本文と同じく "text:" の訳があれば訳して表示する（i18n.rs）。
+
+use crate::i18n::{self, t, tf};
+use crate::registry::{self, TopicText};
+
+/// 1つのエラーコードの解説
+pub struct ErrorCode {
+    pub code: &'static str,
+    /// rustc が表示するメッセージ（の形）
+    pub message: &'static str,
+    pub cause: &'static str,
+    pub fixes: &'static [&'static str],
+    /// 直し方を見られるデモ（"トピック::デモ名"）
+    pub demos: &'static [&'static str],
+}
+
+#[rustfmt::skip]
+pub const CODES: &[ErrorCode] = &[
+    ErrorCode {
+        code: "E0382",
+        message: "borrow of moved value / use of moved value",
+        cause: "String や Vec のような値は、代入や関数への受け渡しで所有権がムーブする。ムーブした後の変数はもう使えない。",
+        fixes: &[
+            "所有権を渡さず、参照（&s）を渡す",
+            "両方で使い続けたいなら .clone() で複製する（コピーのコストに注意）",
+            "関数から所有権を返してもらう",
+        ],
+        demos: &["ownership::ownership_basics", "ownership::ownership_and_functions", "ownership::clone_vs_borrow_cost"],
+    },
+    ErrorCode {
+        code: "E0499",
+        message: "cannot borrow `x` as mutable more than once at a time",
+        cause: "同じ値への可変参照（&mut）は同時に1つまで。2つ目を作った時点で、1つ目がまだ使われている。",
+        fixes: &[
+            "1つ目の可変参照を使い終えてから2つ目を作る（スコープを分ける）",
+            "スライスの別々の部分なら split_at_mut で分ける",
+        ],
+        demos: &["ownership::references_and_borrowing", "unsafe_rust::safe_abstraction"],
+    },
+    ErrorCode {
+        code: "E0502",
+        message: "cannot borrow `x` as mutable because it is also borrowed as immutable",
+        cause: "不変参照（&）が使われている間は、可変参照（&mut）を作れない。Vec の要素への参照を持ったまま push するのが典型。",
+        fixes: &[
+            "不変参照を最後に使う場所より後で変更する",
+            "参照の代わりに値（インデックスやコピー）を持っておく",
+        ],
+        demos: &["ownership::references_and_borrowing", "ownership::slices", "ownership::index_based_links"],
+    },
+    ErrorCode {
+        code: "E0505",
+        message: "cannot move out of `x` because it is borrowed",
+        cause: "参照が使われている間に、元の値をムーブしようとしている。",
+        fixes: &[
+            "参照を使い終えてからムーブする",
+            "ムーブせずに参照で渡す、または clone する",
+        ],
+        demos: &["ownership::references_and_borrowing", "collections::hashmap_ownership"],
+    },
+    ErrorCode {
+        code: "E0597",
+        message: "`x` does not live long enough",
+        cause: "参照が、参照先の値より長く生きようとしている（値がスコープを抜けて解放された後も参照が使われる）。",
+        fixes: &[
+            "値を外側のスコープで宣言して、参照より長く生きるようにする",
+            "参照ではなく所有する値（String など）を持たせる",
+        ],
+        demos: &["lifetimes::why_lifetimes", "ownership::no_dangling"],
+    },
+    ErrorCode {
+        code: "E0106",
+        message: "missing lifetime specifier",
+        cause: "参照を返す関数や、参照を持つ構造体で、どの引数の参照なのかをコンパイラが決められない。",
+        fixes: &[
+            "ライフタイム注釈を付ける（fn longest<'a>(x: &'a str, y: &'a str) -> &'a str）",
+            "省略規則が当てはまる形にする（引数の参照が1つ、または &self）",
+            "参照ではなく所有する値を返す",
+        ],
+        demos: &["lifetimes::function_lifetimes", "lifetimes::lifetime_elision", "lifetimes::struct_lifetimes"],
+    },
+    ErrorCode {
+        code: "E0515",
+        message: "cannot return reference to local variable `x`",
+        cause: "関数の中で作った値は関数の終わりで解放されるので、その参照は返せない（ダングリング参照になる）。",
+        fixes: &["参照ではなく値そのもの（String など）を返す"],
+        demos: &["ownership::no_dangling", "lifetimes::why_lifetimes"],
+    },
+    ErrorCode {
+        code: "E0384",
+        message: "cannot assign twice to immutable variable `x`",
+        cause: "let で宣言した変数は既定で不変。再代入するには mut が必要。",
+        fixes: &[
+            "let mut x で宣言する",
+            "値を変えるのではなく、同じ名前で let し直す（シャドーイング）",
+        ],
+        demos: &["basics::variables_demo"],
+    },
+    ErrorCode {
+        code: "E0596",
+        message: "cannot borrow `x` as mutable, as it is not declared as mutable",
+        cause: "mut の付いていない変数から可変参照（&mut）は作れない。",
+        fixes: &["変数を let mut で宣言する", "関数の引数なら mut x: T や &mut T にする"],
+        demos: &["basics::variables_demo", "ownership::references_and_borrowing"],
+    },
+    ErrorCode {
+        code: "E0308",
+        message: "mismatched types",
+        cause: "期待される型と実際の型が違う。if の腕ごとに型が違う、Result を返すべきところで値を返した、などもこれになる。",
+        fixes: &[
+            "メッセージの expected と found を見比べる",
+            "変換する（as、into、parse など）か、Ok(...) / Some(...) で包む",
+        ],
+        demos: &["basics::data_types_demo", "error_handling::result_basics"],
+    },
+    ErrorCode {
+        code: "E0277",
+        message: "the trait bound `T: Trait` is not satisfied",
+        cause: "ジェネリクスの型やメソッドが求めるトレイトを、その型が実装していない（{} で表示しようとした型が Display を実装していない、など）。",
+        fixes: &[
+            "型にトレイトを実装する、または #[derive(Debug)] などを付ける",
+            "ジェネリクスの関数にトレイト境界（T: Display）を書く",
+        ],
+        demos: &["traits_generics::trait_bounds", "traits_generics::generics_basics", "structs_enums::derive_macros"],
+    },
+    ErrorCode {
+        code: "E0599",
+        message: "no method named `m` found for type `T` in the current scope",
+        cause: "その型にメソッドが無いか、トレイトのメソッドなのにトレイトがスコープに無い。トレイト境界を満たさないときも出る。",
+        fixes: &[
+            "トレイトを use する",
+            "型に impl を書く、またはトレイト境界を満たす型にする",
+        ],
+        demos: &["traits_generics::traits_basics", "traits_generics::conditional_implementations"],
+    },
+    ErrorCode {
+        code: "E0004",
+        message: "non-exhaustive patterns",
+        cause: "match がすべての場合を扱っていない。列挙型にバリアントを足したときにもよく出る。",
+        fixes: &["足りない場合の腕を書く", "残りをまとめて扱うなら _ => ... を最後に書く"],
+        demos: &["pattern_matching::exhaustiveness_and_catchall", "pattern_matching::basic_match"],
+    },
+    ErrorCode {
+        code: "E0373",
+        message: "closure may outlive the current function, but it borrows `x`",
+        cause: "クロージャ（thread::spawn に渡すものなど）が、関数より長く生きるかもしれないのに、ローカル変数を借用している。",
+        fixes: &[
+            "move クロージャにして、値の所有権をクロージャに移す",
+            "スレッドなら thread::scope を使うと借用のまま渡せる",
+        ],
+        demos: &["iterators_closures::closure_capture", "concurrency::move_closures"],
+    },
+    ErrorCode {
+        code: "E0038",
+        message: "the trait `Trait` cannot be made into an object",
+        cause: "dyn Trait にできないトレイト（ジェネリックなメソッドや Self を返すメソッドを持つ）をトレイトオブジェクトとして使おうとしている。",
+        fixes: &[
+            "問題のメソッドに where Self: Sized を付けて、トレイトオブジェクトからは呼べないようにする",
+            "ジェネリクス（impl Trait）で書く",
+        ],
+        demos: &["traits_generics::command_registry", "traits_generics::returning_traits"],
+    },
+];
+
+/// 入力からエラーコードを取り出す（"E0382"、"e0382"、"382"、"error[E0382]: ..." のどれでも）
+pub fn parse_code(input: &str) -> Option<String> {
+    let input = input.trim();
+    // E の直後に数字が続くところを探す（無ければ数字だけが書かれたとみなす）
+    let digits = input
+        .match_indices(['E', 'e'])
+        .map(|(i, _)| &input[i + 1..])
+        .find(|rest| rest.starts_with(|c: char| c.is_ascii_digit()))
+        .unwrap_or(input);
+    let digits: String = digits.chars().take_while(char::is_ascii_digit).collect();
+    if digits.is_empty() || digits.len() > 4 {
+        return None;
+    }
+    Some(format!("E{:0>4}", digits))
+}
+
+pub fn find(code: &str) -> Option<&'static ErrorCode> {
+    CODES.iter().find(|entry| entry.code == code)
+}
+
+fn print_list() {
+    println!("\n{}", t("explain.header"));
+    for entry in CODES {
+        println!("  {}  {}", entry.code, entry.message);
+    }
+    println!("\n{}\n", t("explain.list_hint"));
+}
+
+fn print_entry(entry: &ErrorCode) {
+    println!("\n=== {}: {} ===", entry.code, entry.message);
+    println!(
+        "\n{}",
+        tf("explain.cause", &[("cause", &i18n::text(entry.cause))])
+    );
+    println!("\n{}", t("explain.fixes"));
+    for fix in entry.fixes {
+        println!("  - {}", i18n::text(fix));
+    }
+    println!("\n{}", t("explain.demos"));
+    for id in entry.demos {
+        let title = id
+            .split_once("::")
+            .and_then(|(key, _)| registry::find_topic(key))
+            .zip(registry::find_demo(id))
+            .map_or("", |(topic, demo)| topic.demo_title(demo));
+        println!("  - {}  → `repeat {} 1`", title, id);
+    }
+    println!(
+        "\n{}\n",
+        tf("explain.official_details", &[("code", &entry.code)])
+    );
+}
+
+/// `explain [エラーコード]`
+pub fn run(arg: &str) {
+    if arg.is_empty() {
+        print_list();
+        return;
+    }
+    let Some(code) = parse_code(arg) else {
+        println!("{}", tf("explain.bad_code", &[("input", &arg)]));
+        return;
+    };
+    match find(&code) {
+        Some(entry) => print_entry(entry),
+        None => {
+            println!("{}", tf("explain.no_entry", &[("code", &code)]));
+            println!("{}", tf("explain.official", &[("code", &code)]));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_codes_in_several_forms() {
+        assert_eq!(parse_code("E0382").as_deref(), Some("E0382"));
+        assert_eq!(parse_code("e0499").as_deref(), Some("E0499"));
+        assert_eq!(parse_code("382").as_deref(), Some("E0382"));
+        assert_eq!(
+            parse_code("error[E0597]: `x` does not live long enough").as_deref(),
+            Some("E0597")
+        );
+        assert_eq!(parse_code("explain"), None);
+        assert_eq!(parse_code("123456"), None);
+    }
+
+    #[test]
+    fn every_entry_points_at_existing_demos() {
+        for entry in CODES {
+            assert_eq!(parse_code(entry.code).as_deref(), Some(entry.code));
+            assert!(!entry.demos.is_empty(), "{}", entry.code);
+            for id in entry.demos {
+                assert!(registry::find_demo(id).is_some(), "{}: {}", entry.code, id);
+            }
+        }
+    }
+}
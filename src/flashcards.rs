@@ -0,0 +1,229 @@
+// ============================================================================
+// フラッシュカード
+// ============================================================================
+//
+// 表面に質問、裏面に答えと関連デモを持つカードで知識を確認する。
+// 「覚えた / まだ」の結果はライトナー方式の復習スケジューラに渡され、
+// 覚えたカードほど次に出題されるまでの間隔が長くなる。
+//
+//   箱1: 1日後 → 箱2: 2日後 → 箱3: 4日後 → 箱4: 8日後 → 箱5: 16日後
+//   「まだ」と答えたカードは箱1に戻り、このセッション中にもう一度出題される
+//
+// カードの表と裏はレッスンの本文と同じく "text:" の訳があれば訳して表示する（i18n.rs）。
+
+use crate::i18n::{self, t, tf};
+use crate::registry;
+use crate::storage;
+use lesson_api::input::{Input, StdinInput};
+use lesson_api::output;
+use lesson_api::random::Rng;
+use std::collections::{HashMap, VecDeque};
+use std::io;
+
+/// 1枚のカード
+pub struct Card {
+    pub id: &'static str,
+    pub front: &'static str,
+    pub back: &'static str,
+    /// 答えを確かめられるデモ（"トピック::デモ名"）
+    pub demo: &'static str,
+}
+
+const fn card(
+    id: &'static str,
+    front: &'static str,
+    back: &'static str,
+    demo: &'static str,
+) -> Card {
+    Card {
+        id,
+        front,
+        back,
+        demo,
+    }
+}
+
+#[rustfmt::skip]
+pub static CARDS: &[Card] = &[
+    card("shadowing", "`let x = x + 1;` のように同じ名前で let し直すことを何と呼ぶ？",
+         "シャドーイング。新しい変数を作るので型を変えることもできる", "basics::variables_demo"),
+    card("expression-semicolon", "関数の最後の式にセミコロンを付けるとどうなる？",
+         "文になり () を返す。戻り値の型が合わずコンパイルエラーになる", "basics::functions_demo"),
+    card("break-value", "loop から値を返すには？",
+         "`break 値;` と書く。`let r = loop { break 10; };`", "basics::control_flow_demo"),
+    card("move", "`let s2 = s1;`（s1 は String）の後、s1 は使える？",
+         "使えない。所有権が s2 にムーブされる。使い続けたいなら s1.clone()", "ownership::ownership_basics"),
+    card("copy-types", "`let y = x;`（x は i32）の後、x は使える？",
+         "使える。整数などの Copy 型はムーブではなくコピーされる", "ownership::ownership_basics"),
+    card("borrow-rules", "参照のルールを2つ挙げると？",
+         "1. 可変参照1つ か 不変参照いくつでも  2. 参照は常に有効でなければならない", "ownership::references_and_borrowing"),
+    card("str-param", "文字列を受け取る関数の引数は &String と &str のどちらが良い？",
+         "&str。String も &str もそのまま渡せる（参照外しの型強制）", "ownership::slices"),
+    card("struct-update", "`User { email, ..user1 }` は何をする？",
+         "残りのフィールドを user1 から持ってくる構造体更新記法。String はムーブされる", "structs_enums::basic_structs"),
+    card("associated-fn", "`Rectangle::square(10)` のように self を取らない関数を何と呼ぶ？",
+         "関連関数。コンストラクタによく使われる", "structs_enums::methods"),
+    card("option-plus", "`let sum = 5 + Some(5);` はコンパイルできる？",
+         "できない。Option<i32> と i32 は別の型。unwrap_or などで取り出す", "structs_enums::option_enum"),
+    card("match-exhaustive", "match で一部のバリアントを書き忘れるとどうなる？",
+         "コンパイルエラー。match は網羅的でなければならない（_ でまとめられる）", "pattern_matching::exhaustiveness_and_catchall"),
+    card("if-let", "1つのパターンだけを扱いたいときの簡潔な書き方は？",
+         "`if let Some(v) = opt { ... }`", "pattern_matching::if_let_demo"),
+    card("at-binding", "`id @ 3..=7` の @ は何をする？",
+         "範囲に一致するか調べつつ、その値を id に束縛する", "pattern_matching::at_bindings"),
+    card("question-mark", "? 演算子は Err のとき何をする？",
+         "その Err を（From で変換して）関数から早期リターンする", "error_handling::error_propagation"),
+    card("unwrap-or-else", "`unwrap_or` と `unwrap_or_else` の違いは？",
+         "unwrap_or は値を先に評価、unwrap_or_else はエラー時だけクロージャを呼ぶ", "error_handling::result_combinators"),
+    card("impl-trait-return", "`fn f() -> impl Summary` で分岐ごとに違う型を返せる？",
+         "返せない。impl Trait の戻り値は具体型が1つに決まっている必要がある", "traits_generics::returning_traits"),
+    card("blanket-impl", "独自の型で to_string() が使えるようになる条件は？",
+         "Display を実装すること。impl<T: Display> ToString for T があるため", "traits_generics::conditional_implementations"),
+    card("entry-or-insert", "`map.entry(k).or_insert(0)` は何をする？",
+         "k が無ければ 0 を挿入し、どちらの場合も値への可変参照を返す", "collections::hashmap_updating"),
+    card("string-index", "`s[0]` で String の1文字目を取れる？",
+         "取れない。UTF-8 のため整数インデックスは不可。chars() を使う", "collections::string_indexing"),
+    card("string-plus", "`let s3 = s1 + &s2;` の後、s1 と s2 は使える？",
+         "s1 はムーブされて使えない。s2 は借用なので使える", "collections::string_basics"),
+    card("lazy-adapters", "`v.iter().map(|x| x * 2);` だけを書くと何が起きる？",
+         "何も起きない。アダプタは遅延評価で、collect などで消費する必要がある", "iterators_closures::iterator_adapters"),
+    card("fn-traits", "可変借用でキャプチャするクロージャが実装するトレイトは？",
+         "FnMut（所有権を消費すると FnOnce、不変借用だけなら Fn）", "iterators_closures::closure_capture"),
+    card("lifetime-meaning", "`fn longest<'a>(x: &'a str, y: &'a str) -> &'a str` の 'a は何を表す？",
+         "戻り値は x と y の短い方のライフタイムだけ有効、という関係", "lifetimes::function_lifetimes"),
+    card("elision", "ライフタイムを省略できる代表的な2つのケースは？",
+         "参照の引数が1つだけのとき、&self / &mut self を取るメソッドのとき", "lifetimes::lifetime_elision"),
+];
+
+const SCHEDULE_FILE: &str = "flashcards.tsv";
+const MAX_BOX: u8 = 5;
+
+/// カードごとの復習予定（箱の番号と次の出題日）
+pub struct Schedule {
+    entries: HashMap<String, (u8, u64)>,
+}
+
+impl Schedule {
+    /// 保存済みの予定を読み込む（壊れた行は無視する）
+    pub fn load() -> Schedule {
+        let mut entries = HashMap::new();
+        for record in storage::load_records(SCHEDULE_FILE).unwrap_or_default() {
+            if let [id, level, due] = record.as_slice() {
+                if let (Ok(level), Ok(due)) = (level.parse(), due.parse()) {
+                    entries.insert(id.clone(), (level, due));
+                }
+            }
+        }
+        Schedule { entries }
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let mut records: Vec<Vec<String>> = self
+            .entries
+            .iter()
+            .map(|(id, (level, due))| vec![id.clone(), level.to_string(), due.to_string()])
+            .collect();
+        records.sort();
+        storage::save_records(SCHEDULE_FILE, &records)
+    }
+
+    /// 今日出題すべきか（まだ一度も出題していないカードも対象）
+    pub fn is_due(&self, id: &str, today: u64) -> bool {
+        self.entries.get(id).is_none_or(|&(_, due)| due <= today)
+    }
+
+    /// 回答結果を記録して次の出題日を決める
+    pub fn record(&mut self, id: &str, knew: bool, today: u64) {
+        let current = self.entries.get(id).map_or(0, |&(level, _)| level);
+        let (level, due) = if knew {
+            let level = (current + 1).min(MAX_BOX);
+            (level, today + (1 << (level - 1)))
+        } else {
+            (1, today)
+        };
+        self.entries.insert(id.to_string(), (level, due));
+    }
+
+    /// 箱の番号（未出題は 0）
+    pub fn level_of(&self, id: &str) -> u8 {
+        self.entries.get(id).map_or(0, |&(level, _)| level)
+    }
+}
+
+/// `flash [トピック]`: 今日出題予定のカードを練習する
+pub fn drill(topic: Option<&str>) {
+    let today = storage::today();
+    let mut schedule = Schedule::load();
+
+    let mut due: Vec<&Card> = CARDS
+        .iter()
+        .filter(|card| topic.is_none_or(|t| card.demo.starts_with(&format!("{}::", t))))
+        .filter(|card| schedule.is_due(card.id, today))
+        .collect();
+
+    if due.is_empty() {
+        println!("{}", t("flash.none_due"));
+        return;
+    }
+    Rng::from_time().shuffle(&mut due);
+
+    println!("\n{}", tf("flash.header", &[("count", &due.len())]));
+    println!("{}", t("flash.keys"));
+
+    let mut input = StdinInput;
+    let mut queue: VecDeque<&Card> = due.into_iter().collect();
+    let (mut known, mut again) = (0, 0);
+
+    'cards: while let Some(card) = queue.pop_front() {
+        match schedule.level_of(card.id) {
+            0 => println!("\n[{}] Q. {}", t("flash.new"), i18n::text(card.front)),
+            level => println!(
+                "\n[{}] Q. {}",
+                tf("flash.box", &[("level", &level)]),
+                i18n::text(card.front)
+            ),
+        }
+        if read_answer(&mut input, &format!("  {}", t("flash.reveal"))).is_none() {
+            break;
+        }
+        println!("  A. {}", i18n::text(card.back));
+        println!("  {}", tf("flash.demo", &[("id", &card.demo)]));
+
+        let prompt = format!("  {}", t("flash.prompt"));
+        loop {
+            match read_answer(&mut input, &prompt).as_deref() {
+                Some("y") => {
+                    schedule.record(card.id, true, today);
+                    known += 1;
+                    break;
+                }
+                Some("n") => {
+                    schedule.record(card.id, false, today);
+                    again += 1;
+                    queue.push_back(card); // このセッション中にもう一度
+                    break;
+                }
+                Some("d") => match registry::find_demo(card.demo) {
+                    Some(demo) => output::section(demo.run),
+                    None => println!("  {}", tf("flash.demo_missing", &[("id", &card.demo)])),
+                },
+                Some("q") | None => break 'cards,
+                Some(_) => println!("  {}", t("flash.invalid")),
+            }
+        }
+    }
+
+    println!(
+        "\n{}",
+        tf("flash.result", &[("known", &known), ("again", &again)])
+    );
+    if let Err(e) = schedule.save() {
+        println!("{}", tf("flash.save_failed", &[("error", &e)]));
+    }
+}
+
+// 1行読み込む（EOF なら None）
+fn read_answer(input: &mut impl Input, prompt: &str) -> Option<String> {
+    let line = input.prompt(prompt).ok()??;
+    Some(line.trim().to_lowercase())
+}
@@ -0,0 +1,310 @@
+// ============================================================================
+// 演習の一括採点（講師向け）
+// ============================================================================
+//
+// `grade <ディレクトリ> [レポート.json]` で、学生の解答ファイルに対して
+// すべての演習の隠しテスト（exercises.rs の check）を実行し、
+// 演習ごとの合否をテキストと JSON で出力する。
+//
+// ディレクトリの構成は次のどちらでもよい:
+//   submissions/celsius.rs, submissions/longest.rs, ...        （1人分）
+//   submissions/alice/celsius.rs, submissions/bob/celsius.rs   （学生ごとのサブディレクトリ）
+
+use crate::exercises::{self, CheckOutcome, EXERCISES};
+use crate::i18n::{t, tf};
+use crate::json;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// 既定のレポートファイル名（採点したディレクトリに書き出す）
+const REPORT_FILE: &str = "grade_report.json";
+
+/// エラーの詳細として残す最大行数
+const DETAIL_LINES: usize = 5;
+
+/// 1つの演習の採点結果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Passed,
+    Missing,
+    CompileError,
+    TestFailed,
+    /// rustc が見つからないなど、採点そのものができなかった
+    Error,
+}
+
+impl Status {
+    pub fn label(self) -> &'static str {
+        match self {
+            Status::Passed => t("grade.passed"),
+            Status::Missing => t("grade.missing"),
+            Status::CompileError => t("grade.compile_error"),
+            Status::TestFailed => t("grade.test_failed"),
+            Status::Error => t("grade.error"),
+        }
+    }
+
+    /// JSON に書く名前
+    pub fn key(self) -> &'static str {
+        match self {
+            Status::Passed => "passed",
+            Status::Missing => "missing",
+            Status::CompileError => "compile_error",
+            Status::TestFailed => "test_failed",
+            Status::Error => "error",
+        }
+    }
+}
+
+pub struct GradeEntry {
+    pub exercise: &'static str,
+    pub status: Status,
+    /// 失敗したときのコンパイラやテストの出力（先頭の数行）
+    pub detail: String,
+}
+
+/// 1人分の採点結果
+pub struct StudentReport {
+    pub student: String,
+    pub entries: Vec<GradeEntry>,
+}
+
+impl StudentReport {
+    pub fn passed(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|entry| entry.status == Status::Passed)
+            .count()
+    }
+}
+
+/// 1つのディレクトリ（1人分）を採点する
+pub fn grade_student(student: &str, dir: &Path) -> StudentReport {
+    let entries = EXERCISES
+        .iter()
+        .map(|exercise| {
+            let (status, detail) = match exercises::check(exercise, dir) {
+                Ok(CheckOutcome::Passed) => (Status::Passed, String::new()),
+                Ok(CheckOutcome::Missing(path)) => (Status::Missing, path.display().to_string()),
+                Ok(CheckOutcome::CompileError(stderr)) => {
+                    (Status::CompileError, summarize(&stderr))
+                }
+                Ok(CheckOutcome::TestFailed(stdout)) => (Status::TestFailed, summarize(&stdout)),
+                Err(e) => (Status::Error, e.to_string()),
+            };
+            GradeEntry {
+                exercise: exercise.id,
+                status,
+                detail,
+            }
+        })
+        .collect();
+    StudentReport {
+        student: student.to_string(),
+        entries,
+    }
+}
+
+/// ディレクトリを採点する。演習ファイルが直下に無ければサブディレクトリを学生とみなす
+pub fn grade(root: &Path) -> io::Result<Vec<StudentReport>> {
+    let has_solutions = EXERCISES
+        .iter()
+        .any(|exercise| exercises::file_path(exercise, root).is_file());
+    if has_solutions {
+        let name = root.file_name().map_or_else(
+            || root.display().to_string(),
+            |n| n.to_string_lossy().into_owned(),
+        );
+        return Ok(vec![grade_student(&name, root)]);
+    }
+
+    let mut students: Vec<PathBuf> = fs::read_dir(root)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_dir())
+        .collect();
+    students.sort();
+    Ok(students
+        .iter()
+        .map(|dir| {
+            let name = dir.file_name().unwrap_or_default().to_string_lossy();
+            grade_student(&name, dir)
+        })
+        .collect())
+}
+
+// エラー出力から空行を除いた先頭の数行を取り出す
+fn summarize(output: &str) -> String {
+    output
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .take(DETAIL_LINES)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// テキストのレポート
+pub fn render_text(reports: &[StudentReport]) -> String {
+    let mut text = String::new();
+    for report in reports {
+        text.push_str(&format!(
+            "\n■ {}  {}\n",
+            report.student,
+            tf(
+                "grade.student_summary",
+                &[
+                    ("passed", &report.passed()),
+                    ("total", &report.entries.len())
+                ]
+            )
+        ));
+        for entry in &report.entries {
+            let mark = if entry.status == Status::Passed {
+                "✓"
+            } else {
+                "✗"
+            };
+            text.push_str(&format!(
+                "  {} {:<14} {}\n",
+                mark,
+                entry.exercise,
+                entry.status.label()
+            ));
+            if matches!(
+                entry.status,
+                Status::CompileError | Status::TestFailed | Status::Error
+            ) {
+                for line in entry.detail.lines() {
+                    text.push_str(&format!("      | {}\n", line));
+                }
+            }
+        }
+    }
+    text
+}
+
+/// JSON のレポート
+pub fn render_json(reports: &[StudentReport]) -> String {
+    let students: Vec<String> = reports
+        .iter()
+        .map(|report| {
+            let results: Vec<String> = report
+                .entries
+                .iter()
+                .map(|entry| {
+                    format!(
+                        "        {{ \"exercise\": {}, \"status\": {}, \"detail\": {} }}",
+                        json::quote(entry.exercise),
+                        json::quote(entry.status.key()),
+                        json::quote(&entry.detail)
+                    )
+                })
+                .collect();
+            format!(
+                "    {{\n      \"student\": {},\n      \"passed\": {},\n      \"total\": {},\n      \"results\": [\n{}\n      ]\n    }}",
+                json::quote(&report.student),
+                report.passed(),
+                report.entries.len(),
+                results.join(",\n")
+            )
+        })
+        .collect();
+    format!("{{\n  \"students\": [\n{}\n  ]\n}}\n", students.join(",\n"))
+}
+
+/// `grade <ディレクトリ> [レポート.json]`
+pub fn run(arg: &str) {
+    let mut args = arg.split_whitespace();
+    let Some(root) = args.next().map(Path::new) else {
+        println!("{}", t("grade.usage"));
+        return;
+    };
+    let report_path = args
+        .next()
+        .map_or_else(|| root.join(REPORT_FILE), PathBuf::from);
+
+    println!("\n{}", tf("grade.header", &[("dir", &root.display())]));
+    println!("{}", t("grade.slow"));
+    let reports = match grade(root) {
+        Ok(reports) if !reports.is_empty() => reports,
+        Ok(_) => {
+            println!("{}", tf("grade.nothing", &[("dir", &root.display())]));
+            return;
+        }
+        Err(e) => {
+            println!(
+                "{}",
+                tf(
+                    "grade.read_failed",
+                    &[("dir", &root.display()), ("error", &e)]
+                )
+            );
+            return;
+        }
+    };
+
+    print!("{}", render_text(&reports));
+    match fs::write(&report_path, render_json(&reports)) {
+        Ok(()) => println!(
+            "\n{}",
+            tf("grade.written", &[("path", &report_path.display())])
+        ),
+        Err(e) => println!("\n{}", tf("grade.write_failed", &[("error", &e)])),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::json::Json;
+
+    #[test]
+    fn json_report_round_trips() {
+        let reports = vec![StudentReport {
+            student: "alice \"A\"".to_string(),
+            entries: vec![
+                GradeEntry {
+                    exercise: "celsius",
+                    status: Status::Passed,
+                    detail: String::new(),
+                },
+                GradeEntry {
+                    exercise: "longest",
+                    status: Status::CompileError,
+                    detail: summarize("\nerror[E0106]: missing lifetime\n\n --> longest.rs:1:33\n"),
+                },
+            ],
+        }];
+
+        let mut root = Json::parse(&render_json(&reports))
+            .unwrap()
+            .into_object()
+            .unwrap();
+        let mut students = root.remove("students").and_then(Json::into_array).unwrap();
+        let mut alice = students.remove(0).into_object().unwrap();
+        assert_eq!(
+            alice
+                .remove("student")
+                .and_then(Json::into_string)
+                .as_deref(),
+            Some("alice \"A\"")
+        );
+        assert_eq!(alice.remove("passed").and_then(Json::into_u32), Some(1));
+        let mut results = alice.remove("results").and_then(Json::into_array).unwrap();
+        let mut longest = results.remove(1).into_object().unwrap();
+        assert_eq!(
+            longest
+                .remove("status")
+                .and_then(Json::into_string)
+                .as_deref(),
+            Some("compile_error")
+        );
+        assert_eq!(
+            longest
+                .remove("detail")
+                .and_then(Json::into_string)
+                .as_deref(),
+            Some("error[E0106]: missing lifetime\n --> longest.rs:1:33")
+        );
+    }
+}
@@ -0,0 +1,137 @@
+// ============================================================================
+// ミニプロジェクト: 数当てゲーム
+// 公式ドキュメント: https://doc.rust-lang.org/book/ch02-00-guessing-game-tutorial.html
+// ============================================================================
+//
+// The Book の最初に登場する数当てゲーム。
+//   - rand クレートで秘密の数を決める
+//   - 入力を parse して数値にする（数でないか範囲外なら読み直す。Input::read_number）
+//   - cmp で Ordering を得て match で分岐する
+//   - loop と break でゲームを続ける / 終える
+//
+// 標準入出力を直接使う代わりに Input と lesson_api の println! を使っているので、
+// テストでは用意した入力（ScriptedInput）を流し込み、出力を output::capture で確かめられる。
+
+use crate::i18n::{t, tf};
+use lesson_api::input::{Input, StdinInput};
+use lesson_api::println;
+use rand::Rng;
+use std::cmp::Ordering;
+use std::io;
+
+/// 秘密の数の範囲
+pub const RANGE: std::ops::RangeInclusive<u32> = 1..=100;
+
+/// ゲームの結果
+#[derive(Debug, PartialEq, Eq)]
+pub enum Outcome {
+    /// 当たった（数値として受け付けた予想の回数）
+    Won { attempts: u32 },
+    /// q を入力したか、入力が終わった
+    GaveUp { attempts: u32 },
+}
+
+/// 1ゲームを遊ぶ
+pub fn play(secret: u32, mut input: impl Input) -> io::Result<Outcome> {
+    println!(
+        "{}",
+        tf(
+            "guess.intro",
+            &[("min", RANGE.start()), ("max", RANGE.end())]
+        )
+    );
+    let invalid = |text: &str| {
+        tf(
+            "guess.not_a_number",
+            &[
+                ("min", RANGE.start()),
+                ("max", RANGE.end()),
+                ("input", &format!("{:?}", text)),
+            ],
+        )
+    };
+    let mut attempts = 0;
+
+    // q を入力したか、入力が終わったら None
+    while let Some(guess) = input.read_number(t("guess.prompt"), RANGE, invalid)? {
+        attempts += 1;
+
+        match guess.cmp(&secret) {
+            Ordering::Less => println!("{}", t("guess.too_small")),
+            Ordering::Greater => println!("{}", t("guess.too_big")),
+            Ordering::Equal => {
+                println!("{}", tf("guess.won", &[("attempts", &attempts)]));
+                return Ok(Outcome::Won { attempts });
+            }
+        }
+    }
+    println!("{}", tf("guess.reveal", &[("secret", &secret)]));
+    Ok(Outcome::GaveUp { attempts })
+}
+
+/// `guess`: 標準入出力で数当てゲームを遊ぶ
+pub fn run() {
+    println!("\n{}", t("guess.header"));
+
+    // thread_rng はスレッドごとの乱数生成器。gen_range で範囲内の数を得る
+    let secret = rand::thread_rng().gen_range(RANGE);
+
+    match play(secret, StdinInput) {
+        Ok(Outcome::Won { attempts }) if attempts <= 7 => {
+            println!("{}", t("guess.well_done"))
+        }
+        Ok(_) => {}
+        Err(e) => println!("{}", tf("guess.io_error", &[("error", &e)])),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lesson_api::input::ScriptedInput;
+    use lesson_api::output;
+
+    // 用意した入力で遊び、結果と出力を返す
+    fn play_scripted(secret: u32, script: &str) -> (Outcome, String) {
+        let mut outcome = None;
+        let printed = output::capture(|| {
+            outcome = Some(play(secret, ScriptedInput::new(script)).unwrap());
+        });
+        (outcome.unwrap(), printed)
+    }
+
+    #[test]
+    fn wins_after_hints() {
+        let (outcome, output) = play_scripted(42, "50\n25\n42\n");
+        assert_eq!(outcome, Outcome::Won { attempts: 3 });
+        assert!(output.contains("大きすぎます"));
+        assert!(output.contains("小さすぎます"));
+        assert!(output.contains("正解です！（3回目）"));
+    }
+
+    #[test]
+    fn skips_invalid_input_without_counting() {
+        let (outcome, output) = play_scripted(7, "seven\n-1\n101\n  7  \n");
+        assert_eq!(outcome, Outcome::Won { attempts: 1 });
+        assert_eq!(output.matches("1〜100 の数値を入力してください").count(), 3);
+    }
+
+    #[test]
+    fn gives_up_on_quit_or_end_of_input() {
+        let (outcome, output) = play_scripted(10, "3\nq\n");
+        assert_eq!(outcome, Outcome::GaveUp { attempts: 1 });
+        assert!(output.contains("正解は 10 でした"));
+
+        let (outcome, output) = play_scripted(10, "1\n2\n");
+        assert_eq!(outcome, Outcome::GaveUp { attempts: 2 });
+        assert!(output.contains("正解は 10 でした"));
+    }
+
+    #[test]
+    fn secret_is_in_range() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..1000 {
+            assert!(RANGE.contains(&rng.gen_range(RANGE)));
+        }
+    }
+}
@@ -0,0 +1,325 @@
+// ============================================================================
+// 表示言語の切り替え（メッセージファイル）
+// ============================================================================
+//
+// メニューなどの文言を locales/<言語>.json から読み込む。
+// Rust のソースを触らずに、韓国語（ko.json）や中国語（zh.json）などの
+// 翻訳ファイルを追加できる。
+//
+//   {
+//     "_language": "한국어",
+//     "_fallback": "en",
+//     "menu.prompt": "선택 (번호, l, q): ",
+//     "topic.ownership": "소유권"
+//   }
+//
+// 言語の指定: --lang <言語> > 環境変数 GKRUST_LANG > 日本語
+//
+// 翻訳ファイルの場所（先に見つかったもの）:
+//   1. 環境変数 GKRUST_LOCALE_DIR のディレクトリ
+//   2. 学習データの保存先（storage.rs）の locales/
+//   3. カレントディレクトリの locales/
+//   4. 組み込みの ja / en（バイナリに埋め込み済み）
+//
+// キーが見つからないときは次の順に探す（フォールバック）:
+//   zh-TW → zh → "_fallback" に書いた言語 → ja
+// ja はすべてのキーを持つので、訳が途中までのファイルでも表示は崩れない。
+//
+// レッスンの本文（デモの出力、チートシート、クイズやカードの問題など）は
+// 元の日本語をそのままキーにして "text:" を付ける（gettext と同じ考え方）。
+// 訳が無い文は日本語のまま表示する。デモの出力は1行ずつ、行全体が一致したときだけ訳す。
+//
+//   "text:=== 所有権の基本 ===": "=== 소유권 기초 ===",
+//   "text:ムーブ（s1は使えなくなる）": "이동 (s1은 더 이상 사용할 수 없음)"
+
+use crate::json::Json;
+use crate::storage;
+use std::collections::HashMap;
+use std::env;
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+/// 元の言語（すべてのキーがそろっている）
+pub const DEFAULT_LANG: &str = "ja";
+
+// バイナリに埋め込む翻訳
+const BUILTIN: &[(&str, &str)] = &[
+    ("ja", include_str!("../locales/ja.json")),
+    ("en", include_str!("../locales/en.json")),
+];
+
+/// フォールバックの順に並べた翻訳表
+pub struct Catalog {
+    tables: Vec<HashMap<String, String>>,
+}
+
+impl Catalog {
+    /// 言語コードからフォールバックをたどって翻訳表を集める
+    /// read は言語コードから翻訳ファイルの中身と場所を返す
+    /// 見つからない言語やエラーは警告として返す
+    pub fn load(
+        lang: &str,
+        read: impl Fn(&str) -> Option<(String, String)>,
+    ) -> (Catalog, Vec<Warning>) {
+        let lang = normalize(lang);
+        let mut chain = candidates(&lang);
+        let mut tables = Vec::new();
+        let mut warnings = Vec::new();
+
+        // 指定した言語（地域を外したものを含む）のファイルが1つでも見つかったか
+        let requested = if lang.contains('-') { 2 } else { 1 };
+        let mut found = false;
+
+        let mut i = 0;
+        while i < chain.len() {
+            let code = chain[i].clone();
+            i += 1;
+            let Some((origin, text)) = read(&code) else {
+                continue;
+            };
+            found |= i <= requested;
+            match parse_table(&text) {
+                Ok(table) => {
+                    if let Some(fallback) = table.get("_fallback") {
+                        let fallback = normalize(fallback);
+                        if !chain.contains(&fallback) {
+                            chain.insert(i, fallback);
+                        }
+                    }
+                    tables.push(table);
+                }
+                Err(error) => warnings.push(Warning::Invalid { origin, error }),
+            }
+        }
+        if !found && lang != DEFAULT_LANG {
+            warnings.push(Warning::NotFound(lang));
+        }
+        (Catalog { tables }, warnings)
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.tables
+            .iter()
+            .find_map(|table| table.get(key))
+            .map(String::as_str)
+    }
+}
+
+/// 翻訳の読み込みで起きた問題（起動は止めない）
+#[derive(Debug, PartialEq)]
+pub enum Warning {
+    NotFound(String),
+    Invalid { origin: String, error: String },
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Warning::NotFound(lang) => write!(f, "{}", tf("i18n.not_found", &[("lang", lang)])),
+            Warning::Invalid { origin, error } => write!(
+                f,
+                "{}",
+                tf("i18n.invalid_file", &[("path", origin), ("error", error)])
+            ),
+        }
+    }
+}
+
+// "ko_KR.UTF-8" → "ko-KR"
+fn normalize(lang: &str) -> String {
+    let lang = lang.trim();
+    let lang = lang.split('.').next().unwrap_or(lang);
+    lang.replace('_', "-")
+}
+
+// 最初に探す言語の並び: 指定どおり → 地域を外したもの → ja
+// （"_fallback" は読み込みながら差し込む）
+fn candidates(lang: &str) -> Vec<String> {
+    let mut chain = vec![lang.to_string()];
+    if let Some((base, _)) = lang.split_once('-') {
+        chain.push(base.to_string());
+    }
+    if !chain.iter().any(|code| code == DEFAULT_LANG) {
+        chain.push(DEFAULT_LANG.to_string());
+    }
+    chain
+}
+
+// 文字列の値だけを取り出す（"_" で始まるキーは設定用）
+fn parse_table(text: &str) -> Result<HashMap<String, String>, String> {
+    let object = Json::parse(text)?
+        .into_object()
+        .ok_or("オブジェクトではありません")?;
+    object
+        .into_iter()
+        .map(|(key, value)| match value.into_string() {
+            Some(text) => Ok((key, text)),
+            None => Err(format!("{} の値が文字列ではありません", key)),
+        })
+        .collect()
+}
+
+// 翻訳ファイルを探すディレクトリ
+fn locale_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    if let Some(dir) = env::var_os("GKRUST_LOCALE_DIR") {
+        dirs.push(PathBuf::from(dir));
+    }
+    dirs.push(storage::data_dir().join("locales"));
+    dirs.push(PathBuf::from("locales"));
+    dirs
+}
+
+// 翻訳ファイルを読む。ディレクトリに無ければ組み込みの翻訳を使う
+fn read_locale(lang: &str) -> Option<(String, String)> {
+    let file = format!("{}.json", lang);
+    for dir in locale_dirs() {
+        let path = dir.join(&file);
+        if let Ok(text) = fs::read_to_string(&path) {
+            return Some((path.display().to_string(), text));
+        }
+    }
+    BUILTIN
+        .iter()
+        .find(|(code, _)| *code == lang)
+        .map(|(code, text)| (format!("（組み込み）{}.json", code), text.to_string()))
+}
+
+static CATALOG: OnceLock<Catalog> = OnceLock::new();
+
+/// 表示言語を決める（main の最初に1回だけ呼ぶ）
+/// lang が None なら GKRUST_LANG、それも無ければ日本語
+pub fn init(lang: Option<&str>) {
+    let lang = lang
+        .map(str::to_string)
+        .or_else(|| env::var("GKRUST_LANG").ok())
+        .unwrap_or_else(|| DEFAULT_LANG.to_string());
+    let (catalog, warnings) = Catalog::load(&lang, read_locale);
+    if CATALOG.set(catalog).is_ok() {
+        for warning in warnings {
+            eprintln!("{}", warning);
+        }
+        // 日本語のときはデモの出力を調べない（訳す必要が無い）
+        if normalize(&lang) != DEFAULT_LANG {
+            lesson_api::output::set_translator(lookup_text);
+        }
+    }
+}
+
+fn catalog() -> &'static Catalog {
+    CATALOG.get_or_init(|| Catalog::load(DEFAULT_LANG, read_locale).0)
+}
+
+/// 翻訳を探す（どの言語にも無ければ None）
+pub fn lookup(key: &str) -> Option<&'static str> {
+    catalog().get(key)
+}
+
+/// 翻訳を返す。見つからなければキーをそのまま返す
+pub fn t(key: &str) -> &str {
+    lookup(key).unwrap_or(key)
+}
+
+/// レッスンの本文を訳す。訳が無ければ元の日本語を返す
+pub fn text(original: &'static str) -> &'static str {
+    lookup_text(original).unwrap_or(original)
+}
+
+fn lookup_text(original: &str) -> Option<&'static str> {
+    lookup(&format!("text:{}", original))
+}
+
+/// 翻訳の {名前} を値で置き換える
+pub fn tf(key: &str, args: &[(&str, &dyn fmt::Display)]) -> String {
+    fill(t(key), args)
+}
+
+fn fill(template: &str, args: &[(&str, &dyn fmt::Display)]) -> String {
+    let mut text = template.to_string();
+    for (name, value) in args {
+        text = text.replace(&format!("{{{}}}", name), &value.to_string());
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load(lang: &str, files: &[(&str, &str)]) -> (Catalog, Vec<Warning>) {
+        Catalog::load(lang, |code| {
+            files
+                .iter()
+                .find(|(name, _)| *name == code)
+                .map(|(name, text)| (name.to_string(), text.to_string()))
+        })
+    }
+
+    #[test]
+    fn falls_back_through_region_declared_and_default_language() {
+        let files = [
+            (
+                "ja",
+                r#"{"a": "ja-a", "b": "ja-b", "c": "ja-c", "d": "ja-d"}"#,
+            ),
+            ("en", r#"{"a": "en-a", "b": "en-b", "c": "en-c"}"#),
+            ("zh", r#"{"_fallback": "en", "a": "zh-a", "b": "zh-b"}"#),
+            ("zh-TW", r#"{"a": "tw-a"}"#),
+        ];
+        let (catalog, warnings) = load("zh_TW.UTF-8", &files);
+        assert!(warnings.is_empty());
+        assert_eq!(catalog.get("a"), Some("tw-a"));
+        assert_eq!(catalog.get("b"), Some("zh-b"));
+        assert_eq!(catalog.get("c"), Some("en-c"));
+        assert_eq!(catalog.get("d"), Some("ja-d"));
+        assert_eq!(catalog.get("e"), None);
+
+        // 地域付きのファイルが無くても、基本の言語があれば警告しない
+        let (catalog, warnings) = load("zh-CN", &files);
+        assert!(warnings.is_empty());
+        assert_eq!(catalog.get("a"), Some("zh-a"));
+    }
+
+    #[test]
+    fn warns_about_missing_and_broken_files() {
+        let files = [("ja", r#"{"a": "ja-a"}"#), ("ko", r#"{"a": 1}"#)];
+        let (catalog, warnings) = load("ko", &files);
+        assert_eq!(catalog.get("a"), Some("ja-a"));
+        assert!(matches!(&warnings[..], [Warning::Invalid { origin, .. }] if origin == "ko"));
+
+        let (_, warnings) = load("fr-CA", &files);
+        assert_eq!(warnings, vec![Warning::NotFound("fr-CA".to_string())]);
+    }
+
+    #[test]
+    fn builtin_catalogs_cover_the_same_keys() {
+        let tables: Vec<HashMap<String, String>> = BUILTIN
+            .iter()
+            .map(|(_, text)| parse_table(text).unwrap())
+            .collect();
+        let ja = &tables[0];
+        for table in &tables[1..] {
+            for key in ja.keys() {
+                assert!(table.contains_key(key), "{} が翻訳されていません", key);
+            }
+        }
+        assert_eq!(
+            fill("{level}まで {level}", &[("level", &"中級")]),
+            "中級まで 中級"
+        );
+    }
+
+    #[test]
+    fn lesson_text_uses_the_original_as_the_key() {
+        let files = [
+            ("ja", r#"{"a": "ja-a"}"#),
+            ("en", r#"{"text:所有権の基本": "Ownership basics"}"#),
+        ];
+        let (catalog, _) = load("en", &files);
+        assert_eq!(catalog.get("text:所有権の基本"), Some("Ownership basics"));
+        // 訳の無い文は元の日本語のまま
+        assert_eq!(text("まだ訳の無い文"), "まだ訳の無い文");
+    }
+}
@@ -0,0 +1,472 @@
+// ============================================================================
+// 最小限の JSON 読み書き
+// ============================================================================
+//
+// Todo リストの保存や採点レポートの出力に使う、外部クレートなしの JSON。
+// 読み込みは小さな再帰下降パーサ、書き出しは quote で文字列をエスケープして
+// format! で組み立てる。
+//
+// Json の値は Display でも書き出せる。{} なら1行、{:#} なら改行と字下げ付き:
+//
+//   println!("{}", value);    // {"done":false,"title":"牛乳"}
+//   println!("{:#}", value);  // {
+//                             //   "done": false,
+//                             //   "title": "牛乳"
+//                             // }
+//
+// 読み込みのエラーには行と列が付く。parse_with_lines を使うと、値ごとの行番号
+// （"items[2].title" のようなパス → 行）も受け取れるので、形式の検査で
+// 「何行目のどの値がおかしいか」を示せる。
+
+use std::collections::HashMap;
+use std::fmt;
+use std::iter::Peekable;
+use std::str::Chars;
+
+/// JSON の値
+pub enum Json {
+    Null,
+    Bool(bool),
+    Number(f64),
+    Str(String),
+    Array(Vec<Json>),
+    Object(HashMap<String, Json>),
+}
+
+impl Json {
+    /// 文字列全体を1つの値として解析する
+    pub fn parse(text: &str) -> Result<Json, ParseError> {
+        Cursor::new(text, false).parse_all()
+    }
+
+    /// parse と同じだが、値ごとの開始行（パス → 行番号）も返す
+    /// パスはルートが ""、その下は "questions"、"questions[0]"、"questions[0].answer" のように書く
+    pub fn parse_with_lines(text: &str) -> Result<(Json, HashMap<String, usize>), ParseError> {
+        let mut cursor = Cursor::new(text, true);
+        let value = cursor.parse_all()?;
+        Ok((value, cursor.lines.unwrap_or_default()))
+    }
+
+    pub fn into_object(self) -> Option<HashMap<String, Json>> {
+        match self {
+            Json::Object(map) => Some(map),
+            _ => None,
+        }
+    }
+
+    pub fn into_array(self) -> Option<Vec<Json>> {
+        match self {
+            Json::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    pub fn into_string(self) -> Option<String> {
+        match self {
+            Json::Str(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn into_bool(self) -> Option<bool> {
+        match self {
+            Json::Bool(b) => Some(b),
+            _ => None,
+        }
+    }
+
+    pub fn into_u32(self) -> Option<u32> {
+        match self {
+            Json::Number(n) if n >= 0.0 && n <= u32::MAX as f64 && n.fract() == 0.0 => {
+                Some(n as u32)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// 読み込みのエラー（行と列は1始まり）
+#[derive(Debug, PartialEq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}行{}列: {}", self.line, self.column, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl From<ParseError> for String {
+    fn from(e: ParseError) -> String {
+        e.to_string()
+    }
+}
+
+/// {:#} で1段ごとに入れる字下げ
+const INDENT: &str = "  ";
+
+impl fmt::Display for Json {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pretty = f.alternate();
+        write_value(f, self, pretty, 0)
+    }
+}
+
+// depth は今の値が何段目か（字下げの深さ）
+fn write_value(
+    f: &mut fmt::Formatter<'_>,
+    value: &Json,
+    pretty: bool,
+    depth: usize,
+) -> fmt::Result {
+    match value {
+        Json::Null => f.write_str("null"),
+        Json::Bool(b) => write!(f, "{}", b),
+        Json::Number(n) if n.is_finite() => write!(f, "{}", n),
+        // JSON には NaN と無限大が無い
+        Json::Number(_) => f.write_str("null"),
+        Json::Str(s) => f.write_str(&quote(s)),
+        Json::Array(items) => {
+            let entries = items.iter().map(|item| (None, item));
+            write_container(f, ('[', ']'), entries, pretty, depth)
+        }
+        Json::Object(map) => {
+            // HashMap の順番は毎回変わるので、キーの順に並べて書く
+            let mut entries: Vec<(&String, &Json)> = map.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            let entries = entries.into_iter().map(|(key, value)| (Some(key), value));
+            write_container(f, ('{', '}'), entries, pretty, depth)
+        }
+    }
+}
+
+// 配列とオブジェクトの共通部分（オブジェクトなら各要素にキーがある）
+fn write_container<'a>(
+    f: &mut fmt::Formatter<'_>,
+    (open, close): (char, char),
+    entries: impl ExactSizeIterator<Item = (Option<&'a String>, &'a Json)>,
+    pretty: bool,
+    depth: usize,
+) -> fmt::Result {
+    if entries.len() == 0 {
+        return write!(f, "{}{}", open, close);
+    }
+    write!(f, "{}", open)?;
+    for (i, (key, value)) in entries.enumerate() {
+        if i > 0 {
+            f.write_str(",")?;
+        }
+        if pretty {
+            write!(f, "\n{}", INDENT.repeat(depth + 1))?;
+        }
+        if let Some(key) = key {
+            f.write_str(&quote(key))?;
+            f.write_str(if pretty { ": " } else { ":" })?;
+        }
+        write_value(f, value, pretty, depth + 1)?;
+    }
+    if pretty {
+        write!(f, "\n{}", INDENT.repeat(depth))?;
+    }
+    write!(f, "{}", close)
+}
+
+// 読み込み位置（行と列）を数えながら1文字ずつ進める
+struct Cursor<'a> {
+    chars: Peekable<Chars<'a>>,
+    line: usize,
+    column: usize,
+    /// 今読んでいる値のパス（lines に記録するときのキー）
+    path: String,
+    /// parse_with_lines のときだけ記録する
+    lines: Option<HashMap<String, usize>>,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(text: &'a str, record_lines: bool) -> Cursor<'a> {
+        Cursor {
+            chars: text.chars().peekable(),
+            line: 1,
+            column: 1,
+            path: String::new(),
+            lines: record_lines.then(HashMap::new),
+        }
+    }
+
+    fn parse_all(&mut self) -> Result<Json, ParseError> {
+        let result = parse_value(self).and_then(|value| {
+            skip_whitespace(self);
+            match self.peek() {
+                None => Ok(value),
+                Some(c) => Err(format!("余分な文字 '{}'", c)),
+            }
+        });
+        result.map_err(|message| ParseError {
+            line: self.line,
+            column: self.column,
+            message,
+        })
+    }
+
+    fn peek(&mut self) -> Option<&char> {
+        self.chars.peek()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(c)
+    }
+
+    fn next_if(&mut self, f: impl FnOnce(&char) -> bool) -> Option<char> {
+        match self.peek() {
+            Some(c) if f(c) => self.next(),
+            _ => None,
+        }
+    }
+
+    fn next_if_eq(&mut self, expected: &char) -> Option<char> {
+        self.next_if(|c| c == expected)
+    }
+
+    // これから読む値の開始行を記録する
+    fn mark(&mut self) {
+        if let Some(lines) = &mut self.lines {
+            lines.insert(self.path.clone(), self.line);
+        }
+    }
+
+    // path に "[i]" や ".key" を付けて f を実行し、元に戻す
+    fn nested<T>(&mut self, segment: &str, f: impl FnOnce(&mut Self) -> T) -> T {
+        let len = self.path.len();
+        if !self.path.is_empty() && !segment.starts_with('[') {
+            self.path.push('.');
+        }
+        self.path.push_str(segment);
+        let result = f(self);
+        self.path.truncate(len);
+        result
+    }
+}
+
+fn skip_whitespace(chars: &mut Cursor) {
+    while chars.next_if(|c| c.is_whitespace()).is_some() {}
+}
+
+fn expect(chars: &mut Cursor, expected: char) -> Result<(), String> {
+    match chars.peek() {
+        Some(&c) if c == expected => {
+            chars.next();
+            Ok(())
+        }
+        Some(c) => Err(format!("'{}' が必要な場所に '{}' があります", expected, c)),
+        None => Err(format!(
+            "'{}' が必要な場所でファイルが終わっています",
+            expected
+        )),
+    }
+}
+
+fn parse_value(chars: &mut Cursor) -> Result<Json, String> {
+    skip_whitespace(chars);
+    chars.mark();
+    match chars.peek() {
+        Some('{') => {
+            chars.next();
+            let mut map = HashMap::new();
+            skip_whitespace(chars);
+            if chars.next_if_eq(&'}').is_some() {
+                return Ok(Json::Object(map));
+            }
+            loop {
+                skip_whitespace(chars);
+                let key = parse_string(chars)?;
+                skip_whitespace(chars);
+                expect(chars, ':')?;
+                let value = chars.nested(&key, parse_value)?;
+                map.insert(key, value);
+                skip_whitespace(chars);
+                if chars.next_if_eq(&',').is_none() {
+                    expect(chars, '}')?;
+                    return Ok(Json::Object(map));
+                }
+            }
+        }
+        Some('[') => {
+            chars.next();
+            let mut items = Vec::new();
+            skip_whitespace(chars);
+            if chars.next_if_eq(&']').is_some() {
+                return Ok(Json::Array(items));
+            }
+            loop {
+                let index = format!("[{}]", items.len());
+                items.push(chars.nested(&index, parse_value)?);
+                skip_whitespace(chars);
+                if chars.next_if_eq(&',').is_none() {
+                    expect(chars, ']')?;
+                    return Ok(Json::Array(items));
+                }
+            }
+        }
+        Some('"') => parse_string(chars).map(Json::Str),
+        Some(c) if c.is_ascii_digit() || *c == '-' => {
+            let mut text = String::new();
+            while let Some(c) = chars.next_if(|c| c.is_ascii_digit() || "+-.eE".contains(*c)) {
+                text.push(c);
+            }
+            text.parse()
+                .map(Json::Number)
+                .map_err(|_| format!("数値として読めません: {}", text))
+        }
+        Some(_) => {
+            let mut word = String::new();
+            while let Some(c) = chars.next_if(|c| c.is_ascii_alphabetic()) {
+                word.push(c);
+            }
+            match word.as_str() {
+                "true" => Ok(Json::Bool(true)),
+                "false" => Ok(Json::Bool(false)),
+                "null" => Ok(Json::Null),
+                _ => Err(format!("不明な値です: {:?}", word)),
+            }
+        }
+        None => Err("値が必要な場所でファイルが終わっています".to_string()),
+    }
+}
+
+fn parse_string(chars: &mut Cursor) -> Result<String, String> {
+    expect(chars, '"')?;
+    let mut s = String::new();
+    loop {
+        match chars.next() {
+            Some('"') => return Ok(s),
+            Some('\\') => match chars.next() {
+                Some('n') => s.push('\n'),
+                Some('t') => s.push('\t'),
+                Some('u') => {
+                    let hex: String = (0..4).filter_map(|_| chars.next()).collect();
+                    let c = u32::from_str_radix(&hex, 16)
+                        .ok()
+                        .and_then(char::from_u32)
+                        .ok_or_else(|| format!("不正なエスケープ \\u{}", hex))?;
+                    s.push(c);
+                }
+                Some(c @ ('"' | '\\' | '/')) => s.push(c),
+                Some(c) => return Err(format!("不正なエスケープ \\{}", c)),
+                None => return Err("文字列が閉じられていません".to_string()),
+            },
+            Some(c) => s.push(c),
+            None => return Err("文字列が閉じられていません".to_string()),
+        }
+    }
+}
+
+/// 文字列を JSON の文字列リテラルにする（エスケープ付き）
+pub fn quote(s: &str) -> String {
+    let mut out = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Json {
+        Json::parse(
+            r#"{"title": "牛乳を買う", "done": false, "tags": ["買い物", "急ぎ"],
+                "note": "\"2本\"\n冷蔵庫へ", "meta": {"id": 3, "empty": [], "none": {}, "x": null}}"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn display_is_compact_with_sorted_keys() {
+        assert_eq!(
+            sample().to_string(),
+            r#"{"done":false,"meta":{"empty":[],"id":3,"none":{},"x":null},"note":"\"2本\"\n冷蔵庫へ","tags":["買い物","急ぎ"],"title":"牛乳を買う"}"#
+        );
+        assert_eq!(Json::Number(f64::NAN).to_string(), "null");
+        assert_eq!(Json::Number(-1.5).to_string(), "-1.5");
+    }
+
+    #[test]
+    fn alternate_display_indents_each_level() {
+        let expected = r#"{
+  "done": false,
+  "meta": {
+    "empty": [],
+    "id": 3,
+    "none": {},
+    "x": null
+  },
+  "note": "\"2本\"\n冷蔵庫へ",
+  "tags": [
+    "買い物",
+    "急ぎ"
+  ],
+  "title": "牛乳を買う"
+}"#;
+        assert_eq!(format!("{:#}", sample()), expected);
+        assert_eq!(format!("{:#}", Json::Array(Vec::new())), "[]");
+    }
+
+    #[test]
+    fn printed_json_parses_back_to_the_same_value() {
+        let value = sample();
+        for text in [format!("{}", value), format!("{:#}", value)] {
+            let parsed = Json::parse(&text).unwrap();
+            assert_eq!(parsed.to_string(), value.to_string());
+        }
+        let tricky = Json::Str("tab\t back\\slash \u{1}".to_string());
+        assert_eq!(tricky.to_string(), r#""tab\t back\\slash \u0001""#);
+        assert_eq!(
+            Json::parse(&tricky.to_string())
+                .unwrap()
+                .into_string()
+                .unwrap(),
+            "tab\t back\\slash \u{1}"
+        );
+    }
+
+    #[test]
+    fn errors_and_lines_point_into_the_text() {
+        let error = Json::parse("{\n  \"a\": 1,\n  \"b\" 2\n}").err().unwrap();
+        assert_eq!((error.line, error.column), (3, 7));
+        assert_eq!(
+            error.to_string(),
+            "3行7列: ':' が必要な場所に '2' があります"
+        );
+
+        let (_, lines) = Json::parse_with_lines(
+            "{\n  \"items\": [\n    {\"id\": 1},\n    {\n      \"id\": 2\n    }\n  ]\n}",
+        )
+        .unwrap();
+        assert_eq!(lines[""], 1);
+        assert_eq!(lines["items"], 2);
+        assert_eq!(lines["items[0].id"], 3);
+        assert_eq!(lines["items[1]"], 4);
+        assert_eq!(lines["items[1].id"], 5);
+    }
+}
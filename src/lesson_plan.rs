@@ -0,0 +1,436 @@
+// ============================================================================
+// 授業用のバッチモード（レッスンプラン）
+// ============================================================================
+//
+// `cargo run -- --plan lessons/ownership.toml` のように、講師が用意した
+// レッスンプランに沿ってデモやクイズを順番に（対話なしで）実行する。
+//
+//   title = "第2回: 所有権"
+//   pause = 2                      # 各項目の後の既定の間（秒）
+//
+//   [[item]]
+//   section = "ムーブと借用"        # 見出しを表示
+//
+//   [[item]]
+//   demo = "ownership::ownership_basics"
+//   pause = "enter"                # Enter が押されるまで待つ
+//
+//   [[item]]
+//   quiz = "ownership"             # 問題を表示し、間をおいて答えを表示
+//   count = 2
+//
+// 項目の種類: section / note / topic / demo / quiz（1項目に1つ）
+// 形式は TOML のうち、上で使っている部分だけを読む。
+
+use crate::i18n::{t, tf};
+use crate::quiz;
+use crate::registry::{self, Demo, Topic, TOPICS};
+use crate::usage;
+use lesson_api::input::{Input, StdinInput};
+use lesson_api::output;
+use std::fmt;
+use std::path::Path;
+use std::ptr;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// 項目の後の間
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Pause {
+    Seconds(f64),
+    /// Enter キーが押されるまで待つ
+    Enter,
+}
+
+/// 1つの項目で行うこと
+pub enum Step {
+    Section(String),
+    Note(String),
+    Topic(&'static Topic),
+    Demo(&'static Demo),
+    Quiz(Option<&'static Topic>, usize),
+}
+
+pub struct PlanItem {
+    pub step: Step,
+    pub pause: Pause,
+}
+
+pub struct LessonPlan {
+    pub title: Option<String>,
+    pub items: Vec<PlanItem>,
+}
+
+/// プランの読み込みエラー（行番号付き）
+#[derive(Debug)]
+pub struct PlanError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for PlanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.line == 0 {
+            write!(f, "{}", self.message)
+        } else {
+            write!(
+                f,
+                "{}",
+                tf(
+                    "plan.line_error",
+                    &[("line", &self.line), ("message", &self.message)]
+                )
+            )
+        }
+    }
+}
+
+impl std::error::Error for PlanError {}
+
+// TOML の値（使う型だけ）
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    Str(String),
+    Number(f64),
+    Bool(bool),
+}
+
+// [[item]] 1つ分の「キー = 値」（行番号付き）
+type Table = Vec<(usize, String, Value)>;
+
+/// ファイルからプランを読み込む
+pub fn load(path: &Path) -> Result<LessonPlan, PlanError> {
+    let text = std::fs::read_to_string(path).map_err(|e| PlanError {
+        line: 0,
+        message: tf(
+            "plan.read_failed",
+            &[("path", &path.display()), ("error", &e)],
+        ),
+    })?;
+    parse(&text)
+}
+
+/// プランの文字列を解析し、デモやトピックの名前もこの時点で確かめる
+/// （授業の途中で失敗しないように、実行前にすべてのエラーを見つける）
+pub fn parse(text: &str) -> Result<LessonPlan, PlanError> {
+    let mut header: Table = Vec::new();
+    let mut tables: Vec<(usize, Table)> = Vec::new();
+
+    for (i, raw) in text.lines().enumerate() {
+        let line_no = i + 1;
+        let line = strip_comment(raw).trim();
+        if line.is_empty() {
+            continue;
+        }
+        if line == "[[item]]" {
+            tables.push((line_no, Vec::new()));
+            continue;
+        }
+        let err = |message: String| PlanError {
+            line: line_no,
+            message,
+        };
+        if line.starts_with('[') {
+            return Err(err(tf("plan.bad_header", &[("line", &line)])));
+        }
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| err(tf("plan.not_key_value", &[("line", &line)])))?;
+        let value = parse_value(value.trim()).map_err(err)?;
+        let entry = (line_no, key.trim().to_string(), value);
+        match tables.last_mut() {
+            Some((_, table)) => table.push(entry),
+            None => header.push(entry),
+        }
+    }
+
+    let mut title = None;
+    let mut default_pause = Pause::Seconds(0.0);
+    for (line, key, value) in header {
+        match key.as_str() {
+            "title" => title = Some(expect_str(line, &key, value)?),
+            "pause" => default_pause = parse_pause(line, value)?,
+            _ => return Err(unknown_key(line, &key)),
+        }
+    }
+
+    let items = tables
+        .into_iter()
+        .map(|(line, table)| parse_item(line, table, default_pause))
+        .collect::<Result<Vec<_>, _>>()?;
+    if items.is_empty() {
+        return Err(PlanError {
+            line: 0,
+            message: t("plan.no_items").to_string(),
+        });
+    }
+    Ok(LessonPlan { title, items })
+}
+
+fn parse_item(line: usize, table: Table, default_pause: Pause) -> Result<PlanItem, PlanError> {
+    let mut step = None;
+    let mut pause = default_pause;
+    let mut count = None;
+
+    for (key_line, key, value) in table {
+        let err = |message: String| PlanError {
+            line: key_line,
+            message,
+        };
+        let new_step = match key.as_str() {
+            "pause" => {
+                pause = parse_pause(key_line, value)?;
+                continue;
+            }
+            "count" => {
+                count = match value {
+                    Value::Number(n) if n >= 1.0 && n.fract() == 0.0 => Some(n as usize),
+                    _ => return Err(err(t("plan.bad_count").to_string())),
+                };
+                continue;
+            }
+            "section" => Step::Section(expect_str(key_line, &key, value)?),
+            "note" => Step::Note(expect_str(key_line, &key, value)?),
+            "topic" => {
+                let name = expect_str(key_line, &key, value)?;
+                Step::Topic(
+                    registry::resolve_topic(&name)
+                        .ok_or_else(|| err(tf("main.unknown_topic", &[("name", &name)])))?,
+                )
+            }
+            "demo" => {
+                let name = expect_str(key_line, &key, value)?;
+                Step::Demo(
+                    registry::find_demo(&name)
+                        .ok_or_else(|| err(tf("plan.unknown_demo", &[("name", &name)])))?,
+                )
+            }
+            "quiz" => {
+                let name = expect_str(key_line, &key, value)?;
+                let topic = if name == "all" {
+                    None
+                } else {
+                    Some(
+                        registry::resolve_topic(&name)
+                            .ok_or_else(|| err(tf("main.unknown_topic", &[("name", &name)])))?,
+                    )
+                };
+                Step::Quiz(topic, 0)
+            }
+            _ => return Err(unknown_key(key_line, &key)),
+        };
+        if step.is_some() {
+            return Err(err(tf("plan.duplicate_step", &[("key", &key)])));
+        }
+        step = Some(new_step);
+    }
+
+    let step = match step {
+        Some(Step::Quiz(topic, _)) => Step::Quiz(topic, count.unwrap_or(3)),
+        Some(step) => step,
+        None => {
+            return Err(PlanError {
+                line,
+                message: t("plan.missing_step").to_string(),
+            })
+        }
+    };
+    Ok(PlanItem { step, pause })
+}
+
+fn unknown_key(line: usize, key: &str) -> PlanError {
+    PlanError {
+        line,
+        message: tf("plan.unknown_key", &[("key", &key)]),
+    }
+}
+
+fn expect_str(line: usize, key: &str, value: Value) -> Result<String, PlanError> {
+    match value {
+        Value::Str(s) => Ok(s),
+        _ => Err(PlanError {
+            line,
+            message: tf("plan.expected_string", &[("key", &key)]),
+        }),
+    }
+}
+
+fn parse_pause(line: usize, value: Value) -> Result<Pause, PlanError> {
+    match value {
+        Value::Number(n) if n >= 0.0 => Ok(Pause::Seconds(n)),
+        Value::Str(s) if s == "enter" => Ok(Pause::Enter),
+        _ => Err(PlanError {
+            line,
+            message: t("plan.bad_pause").to_string(),
+        }),
+    }
+}
+
+// 文字列の外にある # 以降を取り除く
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    let mut escaped = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            '#' if !in_string => return &line[..i],
+            _ => {}
+        }
+    }
+    line
+}
+
+fn parse_value(text: &str) -> Result<Value, String> {
+    if let Some(rest) = text.strip_prefix('"') {
+        let mut s = String::new();
+        let mut chars = rest.chars();
+        loop {
+            match chars.next() {
+                Some('"') => break,
+                Some('\\') => match chars.next() {
+                    Some('n') => s.push('\n'),
+                    Some('t') => s.push('\t'),
+                    Some(c @ ('"' | '\\')) => s.push(c),
+                    _ => return Err(t("plan.bad_escape").to_string()),
+                },
+                Some(c) => s.push(c),
+                None => return Err(t("plan.unclosed_string").to_string()),
+            }
+        }
+        if !chars.as_str().trim().is_empty() {
+            return Err(tf("plan.trailing_chars", &[("rest", &chars.as_str())]));
+        }
+        return Ok(Value::Str(s));
+    }
+    match text {
+        "true" => Ok(Value::Bool(true)),
+        "false" => Ok(Value::Bool(false)),
+        _ => text
+            .replace('_', "")
+            .parse()
+            .map(Value::Number)
+            .map_err(|_| tf("plan.bad_value", &[("text", &text)])),
+    }
+}
+
+// ----------------------------------------------------------------------------
+// 実行
+// ----------------------------------------------------------------------------
+
+/// プランを先頭から順に実行する
+pub fn run(plan: &LessonPlan) {
+    if let Some(title) = &plan.title {
+        let line = "═".repeat(64);
+        println!("{}", line);
+        println!("  {}", title);
+        println!("{}", line);
+    }
+
+    let total = plan.items.len();
+    for (i, item) in plan.items.iter().enumerate() {
+        let started = Instant::now();
+        match &item.step {
+            Step::Section(title) => {
+                println!("\n■ {}", title);
+                println!("{}", "─".repeat(64));
+            }
+            Step::Note(text) => println!("\n📝 {}", text),
+            Step::Topic(topic) => (topic.run_all)(),
+            Step::Demo(demo) => output::section(demo.run),
+            Step::Quiz(topic, count) => quiz::present(*topic, *count, &|| wait(item.pause)),
+        }
+        if i + 1 < total {
+            wait(item.pause);
+        }
+        // 後の間も含めて、その項目のトピックの滞在時間として記録する
+        if let Some(key) = topic_key(&item.step) {
+            usage::record_time(key, started.elapsed());
+        }
+    }
+    usage::flush();
+    println!("\n{}", t("plan.finished"));
+}
+
+// 項目が属するトピックのキー（見出しやメモ、全トピックのクイズは None）
+fn topic_key(step: &Step) -> Option<&'static str> {
+    match step {
+        Step::Topic(topic) | Step::Quiz(Some(topic), _) => Some(topic.key),
+        Step::Demo(demo) => TOPICS
+            .iter()
+            .find(|topic| topic.demos.iter().any(|d| ptr::eq(d, *demo)))
+            .map(|topic| topic.key),
+        Step::Section(_) | Step::Note(_) | Step::Quiz(None, _) => None,
+    }
+}
+
+fn wait(pause: Pause) {
+    match pause {
+        Pause::Seconds(secs) if secs > 0.0 => thread::sleep(Duration::from_secs_f64(secs)),
+        Pause::Seconds(_) => {}
+        Pause::Enter => {
+            let _ = StdinInput.prompt(&format!("\n  {}", t("plan.next")));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_items_and_pauses() {
+        let plan = parse(
+            r#"
+            title = "第2回 # 所有権"   # コメント
+            pause = 1.5
+
+            [[item]]
+            section = "はじめに"
+
+            [[item]]
+            demo = "ownership::slices"
+            pause = "enter"
+
+            [[item]]
+            quiz = "2"
+            count = 2
+            pause = 0
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(plan.title.as_deref(), Some("第2回 # 所有権"));
+        assert_eq!(plan.items.len(), 3);
+        assert!(matches!(&plan.items[0].step, Step::Section(s) if s == "はじめに"));
+        assert_eq!(plan.items[0].pause, Pause::Seconds(1.5));
+        assert!(matches!(plan.items[1].step, Step::Demo(demo) if demo.name == "slices"));
+        assert_eq!(plan.items[1].pause, Pause::Enter);
+        assert!(
+            matches!(plan.items[2].step, Step::Quiz(Some(topic), 2) if topic.key == "ownership")
+        );
+        assert_eq!(plan.items[2].pause, Pause::Seconds(0.0));
+
+        // 滞在時間はデモの属するトピックに記録する
+        assert_eq!(topic_key(&plan.items[0].step), None);
+        assert_eq!(topic_key(&plan.items[1].step), Some("ownership"));
+        assert_eq!(topic_key(&plan.items[2].step), Some("ownership"));
+    }
+
+    #[test]
+    fn reports_errors_with_line_numbers() {
+        let line_of = |text: &str| parse(text).err().map(|e| e.line);
+
+        assert_eq!(line_of("[[item]]\ndemo = \"basics::nope\""), Some(2));
+        assert_eq!(
+            line_of("[[item]]\ntopic = \"basics\"\nquiz = \"basics\""),
+            Some(3)
+        );
+        assert_eq!(line_of("[[item]]\npause = 3"), Some(1));
+        assert_eq!(line_of("[[item]]\nsection = 見出し"), Some(2));
+        assert_eq!(line_of("[lesson]"), Some(1));
+        assert_eq!(line_of("colour = \"red\"\n[[item]]\nnote = \"x\""), Some(1));
+        assert_eq!(line_of("title = \"empty\""), Some(0));
+    }
+}
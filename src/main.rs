@@ -9,86 +9,776 @@
 // 実行方法:
 //   cargo run
 //
-// 特定のモジュールのみ実行したい場合は、main関数内で該当する
-// run_all() 以外をコメントアウトしてください。
-
-// モジュール宣言
-mod basics;            // 基本構文（変数、データ型、関数、制御フロー）
-mod collections;       // コレクション（Vec、String、HashMap）
-mod error_handling;    // エラーハンドリング（Result、panic!）
-mod iterators_closures; // イテレータとクロージャ
-mod lifetimes;         // ライフタイム
-mod ownership;         // 所有権システム
-mod pattern_matching;  // パターンマッチング
-mod structs_enums;     // 構造体と列挙型
-mod traits_generics;   // トレイトとジェネリクス
+// 難易度で絞り込みたい場合:
+//   cargo run -- --level beginner
+//
+// レッスンプランに沿って対話なしで実行する場合（授業用）:
+//   cargo run -- --plan lessons/ownership.toml
+//
+// 表示言語を切り替える場合（翻訳は locales/ にある）:
+//   cargo run -- --lang en
+//
+// 各デモの難易度は crates/lessons（トピックとデモの一覧）で管理しています。
 
+// モジュール宣言（ランナー本体）
+// 各章のサンプルは chapters/ 以下の別クレートで、crates/lessons 経由で使う
+mod achievements; // 実績（achievements）
+mod batch; // 複数トピックの連続実行（1,3,5 / 2-4）
+mod calculator; // ミニプロジェクト: 電卓 REPL
+mod cheatsheet; // チートシート生成
+mod drill; // 反復練習（repeat）
+mod exercises; // 穴埋め演習（scaffold / check）
+mod explain; // コンパイルエラーの解説（explain E0382）
+mod flashcards; // フラッシュカード（復習スケジューラ付き）
+mod grading; // 演習の一括採点（grade）
+mod guessing_game; // ミニプロジェクト: 数当てゲーム（Ch.2）
+mod i18n; // 表示言語の切り替え（--lang）
+mod json; // 最小限の JSON 読み書き
+mod lesson_plan; // 授業用のバッチモード（--plan）
+mod minigrep; // ミニプロジェクト: minigrep（Ch.12）
+mod plugins; // プラグイン（外部クレートのレッスン）
+mod quiz; // クイズ
+mod quiz_pack; // クイズの問題集の書き出しと取り込み
+mod quiz_results; // クイズ結果の保存と分析
+mod registry; // デモレジストリ（メタデータ、難易度）
+mod sandbox; // コードを試す（try）
+mod storage; // 学習データの保存先
+mod study_plan; // 目標までの学習計画（plan --goal）
+mod todo; // ミニプロジェクト: Todo リスト
+mod transcript; // デモの出力をファイルに保存（save）
+#[cfg(feature = "update-check")]
+mod update_check; // 更新の確認（check-update）
+mod usage; // 利用統計（ローカルのみ・オプトイン）
+mod web_server; // ミニプロジェクト: マルチスレッドの Web サーバー（Ch.20）
+mod whatsnew; // 新しいデモの案内（whatsnew）
+
+use i18n::{t, tf};
+use lesson_api::input::{Input, StdinInput};
+use lesson_api::reading;
+use registry::{Level, LevelLabel, Topic, TopicText, TOPICS};
+use std::collections::HashSet;
+use std::env;
 use std::io::{self, Write};
+use std::path::Path;
+use std::time::Instant;
+
+// 確保の回数を数えられるアロケータ（所有権の章の「クローンと借用のコスト」で使う）
+#[global_allocator]
+static ALLOCATOR: lesson_api::alloc_count::CountingAlloc = lesson_api::alloc_count::CountingAlloc;
 
 fn main() {
-    println!("╔════════════════════════════════════════════════════════════════╗");
-    println!("║                                                                ║");
-    println!("║               Rust学習サンプル集                               ║");
-    println!("║         The Rust Programming Language 準拠                     ║");
-    println!("║                                                                ║");
-    println!("╚════════════════════════════════════════════════════════════════╝");
-    println!();
-    println!("学習したいトピックを選択してください:");
-    println!();
-    println!("  1. 基本構文（変数、データ型、関数、制御フロー）");
-    println!("  2. 所有権システム");
-    println!("  3. 構造体と列挙型");
-    println!("  4. パターンマッチング");
-    println!("  5. エラーハンドリング");
-    println!("  6. トレイトとジェネリクス");
-    println!("  7. コレクション");
-    println!("  8. イテレータとクロージャ");
-    println!("  9. ライフタイム");
-    println!("  0. すべて実行");
-    println!("  q. 終了");
-    println!();
+    // 章のクレートの run_all() から、このレジストリでデモを実行できるようにする
+    lesson_api::set_topic_runner(registry::run_demos);
+
+    // 再帰のデモから子プロセスとして起動された場合
+    if env::args().nth(1).as_deref() == Some(lessons::basics::OVERFLOW_CHILD_FLAG) {
+        lessons::basics::overflow_stack();
+    }
+
+    // デバッグとリリースの比較のデモから、リリースビルドの子プロセスとして起動された場合
+    if env::args().nth(1).as_deref() == Some(lessons::build_system::HOT_LOOP_CHILD_FLAG) {
+        lessons::build_system::hot_loop_child();
+    }
+
+    // プロセスと環境変数のデモから、子プロセスとして起動された場合
+    if env::args().nth(1).as_deref() == Some(lessons::process_env::CHILD_FLAG) {
+        lessons::process_env::child_main(env::args().skip(2));
+    }
+
+    // cargo run -- grep <検索語> <ファイル>: minigrep として動いて終了する（The Book 第12章）
+    if env::args().nth(1).as_deref() == Some("grep") {
+        // 引数はすべて grep のものなので、表示言語は GKRUST_LANG だけで決める
+        i18n::init(None);
+        minigrep::main(env::args().skip(1));
+    }
+
+    // コマンドライン引数: --lang <言語>（ほかのメッセージより先に決める）
+    i18n::init(lang_from_args(env::args().skip(1)).as_deref());
+
+    // 利用統計（stats on で有効にした場合だけ記録する）
+    usage::init();
+
+    // コマンドライン引数: --level <beginner|intermediate|advanced>
+    if let Some(level) = level_from_args(env::args().skip(1)) {
+        registry::set_max_level(level);
+    }
+
+    // コマンドライン引数: --reading <furigana|romaji>（デモの用語に読みを添える）
+    if let Some(mode) = reading_from_args(env::args().skip(1)) {
+        reading::set_mode(mode);
+    }
+
+    // --plan <ファイル>: レッスンプランを対話なしで実行して終了する
+    if let Some(path) = plan_from_args(env::args().skip(1)) {
+        match lesson_plan::load(Path::new(&path)) {
+            Ok(plan) => lesson_plan::run(&plan),
+            Err(e) => {
+                eprintln!("{}", tf("main.plan_load_failed", &[("error", &e)]));
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    if let Err(e) = run_menu(StdinInput, io::stdout()) {
+        eprintln!("{}", e);
+    }
+}
+
+/// 対話メニュー: バナーとメニューを表示して、q が選ばれるか入力が終わるまで繰り返す
+/// メニュー自体の表示（プロンプト、エラーメッセージなど）は out に書く。
+/// 選ばれたデモやコマンドの出力はこれまでどおり標準出力に出る。
+/// 戻り値はこのセッションで実行したトピック
+fn run_menu(mut input: impl Input, mut out: impl Write) -> io::Result<HashSet<&'static str>> {
+    print_banner(&mut out)?;
+    print_menu(&mut out)?;
+
+    // このセッションで実行済みのトピック（前提を飛ばしていないかの確認用）
+    let mut finished: HashSet<&'static str> = HashSet::new();
+    // 最後に実行したトピックと開始時刻（次の入力までを滞在時間として記録する）
+    let mut reading: Option<(Vec<&'static str>, Instant)> = None;
 
     loop {
-        print!("選択 (0-9, q): ");
-        io::stdout().flush().unwrap();
-
-        let mut input = String::new();
-        io::stdin().read_line(&mut input).unwrap();
-
-        match input.trim() {
-            "1" => basics::run_all(),
-            "2" => ownership::run_all(),
-            "3" => structs_enums::run_all(),
-            "4" => pattern_matching::run_all(),
-            "5" => error_handling::run_all(),
-            "6" => traits_generics::run_all(),
-            "7" => collections::run_all(),
-            "8" => iterators_closures::run_all(),
-            "9" => lifetimes::run_all(),
+        achievements::announce();
+        write!(out, "{}", t("menu.prompt"))?;
+        out.flush()?;
+        // 入力が終わったら（Ctrl-D やパイプの終わり）q と同じように終了する
+        let line = input.read_line().ok().flatten();
+
+        if let Some((keys, started)) = reading.take() {
+            usage::record_shared_time(&keys, started.elapsed());
+        }
+        usage::flush();
+
+        let input = line.as_deref().map_or("q", str::trim);
+        let (command, arg) = match input.split_once(char::is_whitespace) {
+            Some((command, arg)) => (command, arg.trim()),
+            None => (input, ""),
+        };
+
+        match command {
             "0" => {
-                basics::run_all();
-                ownership::run_all();
-                structs_enums::run_all();
-                pattern_matching::run_all();
-                error_handling::run_all();
-                traits_generics::run_all();
-                collections::run_all();
-                iterators_closures::run_all();
-                lifetimes::run_all();
+                let topics: Vec<&Topic> =
+                    TOPICS.iter().filter(|topic| topic.is_visible()).collect();
+                registry::run_all_parallel(&topics);
+                let mut keys: Vec<&'static str> = topics.iter().map(|topic| topic.key).collect();
+                for plugin in plugins::installed().iter().filter(|p| p.is_visible()) {
+                    plugin.run_all();
+                    keys.push(plugin.key());
+                }
+                finished.extend(keys.iter().copied());
+                reading = Some((keys, Instant::now()));
             }
-            "q" | "Q" => {
-                println!("終了します。Happy Rusting!");
-                break;
+            "path" => {
+                print_path(&mut out, arg, &finished)?;
+                continue;
+            }
+            "plan" => {
+                study_plan::run(arg, &finished);
+                continue;
+            }
+            "cheatsheet" => {
+                print_cheatsheet(arg);
+                continue;
+            }
+            "quiz" => {
+                let (sub, rest) = arg.split_once(char::is_whitespace).unwrap_or((arg, ""));
+                match sub {
+                    "export" => quiz_pack::export(rest.trim()),
+                    "import" => quiz_pack::run_import(rest.trim()),
+                    "" => {
+                        quiz::run(None);
+                    }
+                    _ => match registry::resolve_topic(arg) {
+                        Some(topic) => {
+                            quiz::run(Some(topic));
+                        }
+                        None => writeln!(out, "{}", tf("main.unknown_topic", &[("name", &arg)]))?,
+                    },
+                }
+                continue;
+            }
+            "save" => {
+                transcript::run(arg);
+                continue;
             }
-            _ => {
-                println!("無効な選択です。0-9 または q を入力してください。");
+            "repeat" => {
+                drill::run(arg);
                 continue;
             }
+            "stats" => {
+                usage::run(arg);
+                continue;
+            }
+            "whatsnew" => {
+                whatsnew::run(arg);
+                continue;
+            }
+            "check-update" => {
+                #[cfg(feature = "update-check")]
+                update_check::run();
+                #[cfg(not(feature = "update-check"))]
+                writeln!(out, "{}", t("main.update_check_disabled"))?;
+                continue;
+            }
+            "achievements" => {
+                achievements::run(arg);
+                continue;
+            }
+            "report" => {
+                quiz_results::print_report();
+                continue;
+            }
+            "calc" => {
+                calculator::run();
+                continue;
+            }
+            "guess" => {
+                guessing_game::run();
+                continue;
+            }
+            "todo" => {
+                todo::run(arg);
+                continue;
+            }
+            "grep" => {
+                minigrep::run_from_menu(arg);
+                continue;
+            }
+            "serve" => {
+                web_server::run(arg);
+                continue;
+            }
+            "try" => {
+                sandbox::run(arg);
+                continue;
+            }
+            "explain" => {
+                explain::run(arg);
+                continue;
+            }
+            "scaffold" => {
+                run_scaffold(arg);
+                continue;
+            }
+            "check" => {
+                run_check(arg);
+                continue;
+            }
+            "grade" => {
+                grading::run(arg);
+                continue;
+            }
+            "flash" => {
+                if arg.is_empty() {
+                    flashcards::drill(None);
+                } else {
+                    match registry::resolve_topic(arg) {
+                        Some(topic) => flashcards::drill(Some(topic.key)),
+                        None => writeln!(out, "{}", tf("main.unknown_topic", &[("name", &arg)]))?,
+                    }
+                }
+                continue;
+            }
+            "reading" => {
+                match reading::Mode::parse(arg) {
+                    Some(mode) => {
+                        reading::set_mode(mode);
+                        writeln!(out, "{}", t(reading_message(mode)))?;
+                    }
+                    None => writeln!(out, "{}", t("main.reading_usage"))?,
+                }
+                continue;
+            }
+            "l" | "L" => {
+                registry::set_max_level(registry::max_level().next());
+                print_menu(&mut out)?;
+                continue;
+            }
+            "q" | "Q" => {
+                writeln!(out, "{}", t("main.goodbye"))?;
+                return Ok(finished);
+            }
+            other => {
+                // "1,3,5" や "2-4" は選んだトピックを続けて実行し、最後にまとめを表示する
+                if batch::is_selection(input) {
+                    match batch::parse_selection(input, batch::menu_len()) {
+                        Ok(numbers) => batch::run(&numbers, &mut finished),
+                        Err(e) => {
+                            writeln!(out, "{}", tf("batch.invalid", &[("error", &e)]))?;
+                            continue;
+                        }
+                    }
+                // 番号のほか、"ownership" や "所有権" のような名前でも選べる（空白を含む名前もある）
+                } else if let Some(topic) = topic_by_number(other).or_else(|| topic_by_name(input))
+                {
+                    warn_if_jumping_ahead(
+                        &mut out,
+                        topic.display_title(),
+                        topic.key,
+                        &topic.prerequisites(),
+                        &finished,
+                    )?;
+                    (topic.run_all)();
+                    finished.insert(topic.key);
+                    reading = Some((vec![topic.key], Instant::now()));
+                } else if let Some(plugin) = plugin_by_number(other) {
+                    warn_if_jumping_ahead(
+                        &mut out,
+                        plugin.title(),
+                        plugin.key(),
+                        plugin.requires(),
+                        &finished,
+                    )?;
+                    plugin.run_all();
+                    finished.insert(plugin.key());
+                    reading = Some((vec![plugin.key()], Instant::now()));
+                } else {
+                    writeln!(out, "{}", t("main.invalid_choice"))?;
+                    continue;
+                }
+            }
+        }
+
+        writeln!(out)?;
+        writeln!(out, "---")?;
+        writeln!(out)?;
+    }
+}
+
+/// タイトルの枠を表示（文言は翻訳ファイルから）
+fn print_banner(out: &mut impl Write) -> io::Result<()> {
+    const WIDTH: usize = 64;
+    // 全角文字は半角2文字分の幅として中央に寄せる
+    let centered = |text: &str| format!("║{}║", lesson_api::text::center(text, WIDTH));
+    writeln!(out, "╔{}╗", "═".repeat(WIDTH))?;
+    writeln!(out, "║{}║", " ".repeat(WIDTH))?;
+    writeln!(out, "{}", centered(t("banner.title")))?;
+    writeln!(out, "{}", centered(t("banner.subtitle")))?;
+    writeln!(out, "║{}║", " ".repeat(WIDTH))?;
+    writeln!(out, "╚{}╝", "═".repeat(WIDTH))?;
+    writeln!(out)
+}
+
+/// 現在の難易度フィルタで見えるトピックだけを並べたメニューを表示
+fn print_menu(out: &mut impl Write) -> io::Result<()> {
+    let max = registry::max_level();
+    writeln!(out, "{}", tf("menu.header", &[("level", &max.label())]))?;
+    writeln!(out)?;
+    for (i, topic) in TOPICS.iter().enumerate() {
+        let visible = topic.visible_demos().count();
+        if visible == 0 {
+            continue;
+        }
+        print_menu_entry(
+            out,
+            i + 1,
+            topic.display_title(),
+            topic.display_chapter(),
+            (visible, topic.demos.len()),
+            topic.estimated_minutes(),
+        )?;
+    }
+    // プラグインのトピックは組み込みのトピックの続きの番号にする
+    for (i, plugin) in plugins::installed().iter().enumerate() {
+        let visible = plugin.visible_demos().count();
+        if visible == 0 {
+            continue;
+        }
+        print_menu_entry(
+            out,
+            TOPICS.len() + i + 1,
+            plugin.title(),
+            plugin.chapter(),
+            (visible, plugin.demo_count()),
+            plugin.estimated_minutes(),
+        )?;
+    }
+    for key in [
+        "menu.run_all",
+        "menu.batch",
+        "menu.level",
+        "menu.reading",
+        "menu.path",
+        "menu.plan",
+        "menu.cheatsheet",
+        "menu.save",
+        "menu.flash",
+        "menu.quiz",
+        "menu.quiz_pack",
+        "menu.repeat",
+        "menu.report",
+        "menu.achievements",
+        "menu.stats",
+        "menu.whatsnew",
+        "menu.check_update",
+        "menu.try",
+        "menu.explain",
+        "menu.scaffold",
+        "menu.check",
+        "menu.grade",
+        "menu.calc",
+        "menu.guess",
+        "menu.todo",
+        "menu.grep",
+        "menu.serve",
+        "menu.quit",
+    ] {
+        writeln!(out, "  {}", t(key))?;
+    }
+    writeln!(out)?;
+
+    let weak = quiz_results::weakest_topics(3);
+    if !weak.is_empty() {
+        let names: Vec<String> = weak
+            .iter()
+            .map(|stats| format!("{} ({:.0}%)", stats.topic, stats.recent_accuracy * 100.0))
+            .collect();
+        writeln!(
+            out,
+            "{}",
+            tf("menu.weak_topics", &[("topics", &names.join(", "))])
+        )?;
+        writeln!(out)?;
+    }
+    if let Some(notice) = whatsnew::menu_notice() {
+        writeln!(out, "{}", notice)?;
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+// メニューの1行: 番号、タイトル、章、かかる時間の目安（難易度で隠れたデモがあればその数も）
+fn print_menu_entry(
+    out: &mut impl Write,
+    number: usize,
+    title: &str,
+    chapter: &str,
+    (visible, total): (usize, usize),
+    minutes: u32,
+) -> io::Result<()> {
+    let estimate = tf("menu.estimate", &[("minutes", &minutes)]);
+    if visible < total {
+        writeln!(
+            out,
+            "  {}. {} ({}) {} {}",
+            number,
+            title,
+            chapter,
+            estimate,
+            tf(
+                "menu.demo_count",
+                &[("visible", &visible), ("total", &total)]
+            )
+        )
+    } else {
+        writeln!(out, "  {}. {} ({}) {}", number, title, chapter, estimate)
+    }
+}
+
+/// 前提トピックを順に並べた学習パスを表示
+/// 引数なしの場合はすべてのトピックを前提関係に沿って並べる
+fn print_path(out: &mut impl Write, arg: &str, finished: &HashSet<&'static str>) -> io::Result<()> {
+    let path: Vec<&Topic> = if arg.is_empty() {
+        let mut all: Vec<&Topic> = Vec::new();
+        for topic in TOPICS {
+            for step in registry::learning_path(topic) {
+                if !all.iter().any(|t| t.key == step.key) {
+                    all.push(step);
+                }
+            }
+        }
+        all
+    } else {
+        match registry::resolve_topic(arg) {
+            Some(goal) => registry::learning_path(goal),
+            None => {
+                return writeln!(out, "{}", tf("main.unknown_topic", &[("name", &arg)]));
+            }
         }
+    };
 
-        println!();
-        println!("---");
-        println!();
+    writeln!(out, "\n{}", t("path.header"))?;
+    for (step, topic) in path.iter().enumerate() {
+        let mark = if finished.contains(topic.key) {
+            "✓"
+        } else {
+            " "
+        };
+        writeln!(
+            out,
+            "  [{}] {}. {} ({}) - {}",
+            mark,
+            step + 1,
+            topic.display_title(),
+            topic.display_chapter(),
+            topic.key
+        )?;
+    }
+    writeln!(out)
+}
+
+/// `cheatsheet <トピック|all> [ファイル.md]`
+/// ファイル名を付けると Markdown で書き出す
+fn print_cheatsheet(arg: &str) {
+    let mut args = arg.split_whitespace();
+    let topics: Vec<&Topic> = match args.next() {
+        Some("all") => TOPICS.iter().collect(),
+        Some(name) => match registry::resolve_topic(name) {
+            Some(topic) => vec![topic],
+            None => {
+                println!("{}", tf("main.unknown_topic", &[("name", &name)]));
+                return;
+            }
+        },
+        None => {
+            println!("{}", t("cheatsheet.usage"));
+            return;
+        }
+    };
+
+    match args.next() {
+        Some(file) => match cheatsheet::write_markdown(&topics, Path::new(file)) {
+            Ok(()) => println!("{}", tf("main.written", &[("path", &file)])),
+            Err(e) => println!("{}", tf("main.write_failed", &[("error", &e)])),
+        },
+        None => {
+            for topic in topics {
+                print!("{}", cheatsheet::render_text(topic));
+            }
+            println!();
+        }
+    }
+}
+
+/// `scaffold [演習] [ディレクトリ]`（演習を省略すると一覧を表示）
+fn run_scaffold(arg: &str) {
+    let mut args = arg.split_whitespace();
+    let Some(id) = args.next() else {
+        exercises::print_list();
+        return;
+    };
+    let Some(exercise) = exercises::find(id) else {
+        println!("{}", tf("exercises.unknown", &[("id", &id)]));
+        return;
+    };
+    let dir = Path::new(args.next().unwrap_or(exercises::DEFAULT_DIR));
+    match exercises::scaffold(exercise, dir) {
+        Ok(path) => {
+            println!("{}", tf("exercises.created", &[("path", &path.display())]));
+            println!(
+                "{}",
+                tf(
+                    "exercises.check_hint",
+                    &[("id", &exercise.id), ("dir", &dir.display())]
+                )
+            );
+        }
+        Err(e) => println!("{}", tf("exercises.create_failed", &[("error", &e)])),
+    }
+}
+
+/// `check <演習> [ディレクトリ]`
+fn run_check(arg: &str) {
+    let mut args = arg.split_whitespace();
+    let Some(id) = args.next() else {
+        println!("{}", t("exercises.check_usage"));
+        return;
+    };
+    match exercises::find(id) {
+        Some(exercise) => {
+            let dir = Path::new(args.next().unwrap_or(exercises::DEFAULT_DIR));
+            exercises::print_check(exercise, dir);
+        }
+        None => println!("{}", tf("exercises.unknown", &[("id", &id)])),
+    }
+}
+
+/// 前提トピックをまだ実行していない場合に警告する（実行自体は止めない）
+fn warn_if_jumping_ahead(
+    out: &mut impl Write,
+    title: &str,
+    key: &str,
+    requires: &[&str],
+    finished: &HashSet<&'static str>,
+) -> io::Result<()> {
+    let missing: Vec<&str> = requires
+        .iter()
+        .filter(|key| !finished.contains(*key))
+        .copied()
+        .collect();
+    if !missing.is_empty() {
+        writeln!(
+            out,
+            "{}",
+            tf(
+                "path.jumping_ahead",
+                &[("title", &title), ("missing", &missing.join(", "))]
+            )
+        )?;
+        // プラグインのキーは path で引けないので、そのときは前提のほうを案内する
+        let goal = if registry::find_topic(key).is_some() {
+            key
+        } else {
+            missing[missing.len() - 1]
+        };
+        writeln!(out, "{}", tf("path.hint", &[("key", &goal)]))?;
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+/// メニュー番号（1始まり）から表示中のトピックを引く
+fn topic_by_number(input: &str) -> Option<&'static Topic> {
+    let number: usize = input.parse().ok()?;
+    let topic = TOPICS.get(number.checked_sub(1)?)?;
+    topic.is_visible().then_some(topic)
+}
+
+/// 名前（キー、タイトル、別名）から表示中のトピックを引く
+fn topic_by_name(input: &str) -> Option<&'static Topic> {
+    let topic = registry::resolve_topic(input)?;
+    topic.is_visible().then_some(topic)
+}
+
+/// 組み込みのトピックに続く番号からプラグインのトピックを引く
+fn plugin_by_number(input: &str) -> Option<&'static plugins::PluginTopic> {
+    let number: usize = input.parse().ok()?;
+    let plugin = plugins::installed().get(number.checked_sub(TOPICS.len() + 1)?)?;
+    plugin.is_visible().then_some(plugin)
+}
+
+/// `--plan <ファイル>` または `--plan=<ファイル>` を探す
+fn plan_from_args(mut args: impl Iterator<Item = String>) -> Option<String> {
+    while let Some(arg) = args.next() {
+        if arg == "--plan" {
+            return args.next();
+        }
+        if let Some(path) = arg.strip_prefix("--plan=") {
+            return Some(path.to_string());
+        }
+    }
+    None
+}
+
+/// `--lang <言語>` または `--lang=<言語>` を探す
+fn lang_from_args(mut args: impl Iterator<Item = String>) -> Option<String> {
+    while let Some(arg) = args.next() {
+        if arg == "--lang" {
+            return args.next();
+        }
+        if let Some(lang) = arg.strip_prefix("--lang=") {
+            return Some(lang.to_string());
+        }
+    }
+    None
+}
+
+/// `--level <名前>` または `--level=<名前>` を探す
+fn level_from_args(mut args: impl Iterator<Item = String>) -> Option<Level> {
+    while let Some(arg) = args.next() {
+        let value = if arg == "--level" {
+            args.next()
+        } else {
+            arg.strip_prefix("--level=").map(str::to_string)
+        };
+        if let Some(value) = value {
+            match Level::parse(&value) {
+                Some(level) => return Some(level),
+                None => eprintln!("{}", tf("main.unknown_level", &[("value", &value)])),
+            }
+        }
+    }
+    None
+}
+
+/// `--reading <添え方>` または `--reading=<添え方>` を探す
+fn reading_from_args(mut args: impl Iterator<Item = String>) -> Option<reading::Mode> {
+    while let Some(arg) = args.next() {
+        let value = if arg == "--reading" {
+            args.next()
+        } else {
+            arg.strip_prefix("--reading=").map(str::to_string)
+        };
+        if let Some(value) = value {
+            match reading::Mode::parse(&value) {
+                Some(mode) => return Some(mode),
+                None => eprintln!("{}", t("main.reading_usage")),
+            }
+        }
+    }
+    None
+}
+
+// 読みの添え方を切り替えたときのメッセージのキー
+fn reading_message(mode: reading::Mode) -> &'static str {
+    match mode {
+        reading::Mode::Off => "main.reading_off",
+        reading::Mode::Furigana => "main.reading_furigana",
+        reading::Mode::Romaji => "main.reading_romaji",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lesson_api::input::ScriptedInput;
+    use std::fs;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Once;
+
+    // main と同じようにデモを実行できるようにする
+    fn init() {
+        static INIT: Once = Once::new();
+        INIT.call_once(|| lesson_api::set_topic_runner(registry::run_demos));
+    }
+
+    // 入力を順に与えてメニューを動かし、実行したトピックとメニューの表示を返す
+    // 実績やお知らせは一時ディレクトリに保存して、終わったら消す（本物の学習データを書き換えない）
+    fn session(script: &str) -> (HashSet<&'static str>, String) {
+        static NEXT: AtomicUsize = AtomicUsize::new(0);
+        init();
+        let dir = env::temp_dir().join(format!(
+            "gkrust-menu-test-{}-{}",
+            std::process::id(),
+            NEXT.fetch_add(1, Ordering::Relaxed)
+        ));
+        let mut out = Vec::new();
+        let finished =
+            storage::with_data_dir(&dir, || run_menu(ScriptedInput::new(script), &mut out));
+        let _ = fs::remove_dir_all(&dir);
+        (finished.unwrap(), String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn selects_topics_by_name_and_number_then_quits() {
+        let graphs = TOPICS.iter().position(|t| t.key == "graphs").unwrap() + 1;
+        let (finished, out) = session(&format!("graphs\n{}\nq\nbasics\n", graphs));
+
+        assert!(out.starts_with('╔'));
+        assert!(out.contains(t("menu.quit")));
+        assert_eq!(out.matches(t("menu.prompt")).count(), 3);
+        // q で終わるので、その後の入力は読まない
+        assert_eq!(finished, HashSet::from(["graphs"]));
+        assert!(out.trim_end().ends_with(t("main.goodbye")));
+        // 前提（collections）を飛ばしているので、実行するたびに警告が出る
+        let hint = tf("path.hint", &[("key", &"graphs")]);
+        assert_eq!(out.matches(&hint).count(), 2);
+    }
+
+    #[test]
+    fn reports_invalid_input_and_keeps_going() {
+        let (finished, out) = session("no such topic\n9999\n1-\nflash nosuch\npath nosuch\n");
+
+        assert!(finished.is_empty());
+        assert_eq!(out.matches(t("main.invalid_choice")).count(), 2);
+        let unknown = tf("main.unknown_topic", &[("name", &"nosuch")]);
+        assert_eq!(out.matches(&unknown).count(), 2);
+        let error = batch::parse_selection("1-", batch::menu_len()).unwrap_err();
+        assert!(out.contains(&tf("batch.invalid", &[("error", &error)])));
+        // 入力が終わったら q と同じように終了する
+        assert_eq!(out.matches(t("menu.prompt")).count(), 6);
+        assert!(out.trim_end().ends_with(t("main.goodbye")));
     }
 }
 
@@ -96,17 +786,77 @@ fn main() {
 // モジュール構成
 // ============================================================================
 //
-// src/
+// src/                      - ランナー本体（メニュー、クイズ、演習など）
 // ├── main.rs              - エントリーポイント
-// ├── basics.rs            - Ch.3: 基本的なプログラミング概念
-// ├── ownership.rs         - Ch.4: 所有権
-// ├── structs_enums.rs     - Ch.5-6: 構造体と列挙型
-// ├── pattern_matching.rs  - Ch.6, 18: パターンマッチング
-// ├── error_handling.rs    - Ch.9: エラー処理
-// ├── traits_generics.rs   - Ch.10: ジェネリクスとトレイト
-// ├── collections.rs       - Ch.8: コレクション
-// ├── iterators_closures.rs - Ch.13: イテレータとクロージャ
-// └── lifetimes.rs         - Ch.10: ライフタイム
+// ├── registry.rs          - デモレジストリ（表示言語での表示、実行）
+// ├── batch.rs             - 複数トピックの連続実行（1,3,5 / 2-4）
+// ├── plugins.rs           - プラグインの読み込み（lesson-api）
+// ├── cheatsheet.rs        - チートシート生成（ターミナル / Markdown）
+// ├── transcript.rs        - デモの出力をファイルに保存（save）
+// ├── flashcards.rs        - フラッシュカードと復習スケジューラ
+// ├── quiz.rs              - クイズ
+// ├── quiz_results.rs      - クイズ結果の保存と分析
+// ├── quiz_pack.rs         - クイズの問題集の書き出しと取り込み（quiz export / import）
+// ├── drill.rs             - 反復練習（repeat）
+// ├── exercises.rs         - 穴埋め演習（scaffold / check）
+// ├── lesson_plan.rs       - 授業用のバッチモード（--plan）
+// ├── study_plan.rs        - 目標までの学習計画（plan --goal）
+// ├── explain.rs           - コンパイルエラーの解説（explain E0382）
+// ├── sandbox.rs           - コードを試す（try）
+// ├── achievements.rs      - 実績（achievements）
+// ├── usage.rs             - 利用統計（stats）
+// ├── whatsnew.rs          - 新しいデモの案内（whatsnew）
+// ├── update_check.rs      - 更新の確認（check-update、feature "update-check"）
+// ├── json.rs              - 最小限の JSON 読み書き
+// ├── grading.rs           - 演習の一括採点（grade）
+// ├── i18n.rs              - 表示言語の切り替え（locales/*.json）
+// ├── storage.rs           - 学習データの保存先
+// ├── calculator.rs        - ミニプロジェクト: 電卓 REPL
+// ├── guessing_game.rs     - Ch.2: 数当てゲーム
+// ├── todo.rs              - ミニプロジェクト: Todo リスト
+// ├── minigrep.rs          - Ch.12: minigrep（cargo run -- grep <検索語> <ファイル>）
+// └── web_server.rs        - Ch.20: マルチスレッドの Web サーバー（ThreadPool）
+//
+// chapters/                 - 章ごとのクレート（src/lib.rs に章のデモ）
+// ├── basics               - Ch.3: 基本的なプログラミング概念
+// ├── ownership            - Ch.4: 所有権
+// ├── structs_enums        - Ch.5-6: 構造体と列挙型
+// ├── pattern_matching     - Ch.6, 18: パターンマッチング
+// ├── error_handling       - Ch.9: エラー処理
+// ├── traits_generics      - Ch.10: ジェネリクスとトレイト
+// ├── collections          - Ch.8: コレクション
+// ├── iterators_closures   - Ch.13: イテレータとクロージャ
+// ├── lifetimes            - Ch.10: ライフタイム
+// ├── algorithms           - 応用: ジェネリックなソート
+// ├── data_structures      - 応用: 連結リスト、スタック/キュー、二分探索木
+// ├── graphs               - 応用: グラフと経路探索
+// ├── parsing              - 応用: 字句解析器と再帰下降パーサ
+// ├── no_std_demo          - 応用: #![no_std]（firmware/ がマイコン向けのクレート）
+// ├── ffi                  - 応用: C との相互運用（build.rs で c/gkmath.c をコンパイル）
+// ├── build_system         - 応用: Cargo の feature とビルドスクリプト
+// ├── smart_pointers       - Ch.15: スマートポインタ（Box、Deref、Drop、Rc、RefCell、Weak）
+// ├── concurrency          - Ch.16: 並行処理（スレッドとチャネル）
+// ├── async_await          - Ch.17: 非同期プログラミング（自作のミニランタイム）
+// ├── macros               - Ch.20: マクロ（describe-derive/ が手続き的マクロのクレート）
+// ├── unsafe_rust          - Ch.20: unsafe Rust（生ポインタ、transmute、Miri）
+// ├── oop_patterns         - Ch.18: オブジェクト指向のパターン（トレイトオブジェクト、State、型状態）
+// ├── modules_packages     - Ch.7: モジュールとパッケージ（可視性、パス、再公開）
+// ├── testing              - Ch.11: テストの書き方（単体テストと tests/ の結合テスト）
+// ├── advanced_traits      - Ch.20: 高度なトレイト（関連定数、完全修飾構文、ニュータイプ）
+// ├── advanced_types       - Ch.20: 高度な型（型エイリアス、never 型、動的サイズ型）
+// ├── interior_mutability  - Ch.15: 内部可変性（Cell、RefCell、実行時の借用エラー）
+// ├── memory_layout        - 応用: メモリレイアウト（size_of、repr、ニッチ最適化）
+// ├── time_demo            - 応用: 時間（Instant、Duration、SystemTime、ストップウォッチ）
+// ├── process_env          - 応用: プロセスと環境変数（env::args、env::var、Command）
+// ├── networking           - 応用: ネットワーク（TcpListener と TcpStream のエコーサーバー）
+// ├── file_io              - 応用: ファイル I/O（BufReader、BufWriter、std::fs）
+// └── serialization        - 応用: シリアライズ（serde、feature "serde"）
+//
+// crates/lesson-api/        - 共通の型（CheatSheet、Demo トレイト、入力と出力先、panic の捕捉、計測、乱数生成器）
+// crates/lessons/           - トピックとデモの一覧（難易度などのメタデータ）
+// crates/playground/        - ブラウザ版の入口（wasm32 向け）
+// web/                      - ブラウザ版のページ（index.html）
+// plugins/hello-plugin/     - プラグインの例（--features plugin-hello）
 //
 // ============================================================================
 // 参考リンク
@@ -0,0 +1,238 @@
+// ============================================================================
+// ミニプロジェクト: minigrep
+// 公式ドキュメント: https://doc.rust-lang.org/book/ch12-00-an-io-project.html
+// ============================================================================
+//
+// The Book 第12章の入出力プロジェクト。ファイルから検索語を含む行を探して表示する。
+//   - コマンドライン引数を Config::build で読む（失敗は Result で返す）
+//   - ファイルを fs::read_to_string で読み、エラーは Box<dyn Error> で run から返す
+//   - search はテストを先に書いて作る（TDD）。第13章のイテレータ版で書いている
+//   - 環境変数 IGNORE_CASE があれば大文字と小文字を区別しない
+//   - エラーは標準エラー（eprintln!）に出すので、> で結果だけをファイルに保存できる
+//
+//   cargo run -- grep to poem.txt
+//   IGNORE_CASE=1 cargo run -- grep to poem.txt
+//
+// メニューからは grep <検索語> <ファイル> で、引数なしなら同梱の poem.txt で例を見せる。
+
+use crate::i18n::{t, tf};
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::io::{self, Write};
+use std::process;
+
+/// 同梱のサンプル（The Book と同じ Emily Dickinson の詩）
+const POEM: &str = include_str!("../poem.txt");
+
+/// 大文字と小文字を区別しないことを指定する環境変数
+pub const IGNORE_CASE_VAR: &str = "IGNORE_CASE";
+
+/// 検索の設定
+#[derive(Debug, PartialEq, Eq)]
+pub struct Config {
+    pub query: String,
+    pub file_path: String,
+    pub ignore_case: bool,
+}
+
+impl Config {
+    /// 引数から設定を作る。最初の要素はプログラム名（ここでは grep）なので読み飛ばす
+    ///
+    /// 第13章の書き方で、Vec<String> の参照ではなくイテレータを受け取る（clone が要らない）。
+    pub fn build(mut args: impl Iterator<Item = String>) -> Result<Config, &'static str> {
+        args.next();
+
+        let query = match args.next() {
+            Some(arg) => arg,
+            None => return Err(t("grep.missing_query")),
+        };
+        let file_path = match args.next() {
+            Some(arg) => arg,
+            None => return Err(t("grep.missing_file")),
+        };
+
+        // 値は見ずに、設定されているかどうかだけを見る
+        let ignore_case = env::var(IGNORE_CASE_VAR).is_ok();
+
+        Ok(Config {
+            query,
+            file_path,
+            ignore_case,
+        })
+    }
+}
+
+/// ファイルを読んで、見つかった行を out に書く。戻り値は見つかった行の数
+pub fn run(config: &Config, out: &mut impl Write) -> Result<usize, Box<dyn Error>> {
+    let contents = fs::read_to_string(&config.file_path)?;
+
+    let results = if config.ignore_case {
+        search_case_insensitive(&config.query, &contents)
+    } else {
+        search(&config.query, &contents)
+    };
+
+    for line in &results {
+        writeln!(out, "{}", line)?;
+    }
+    Ok(results.len())
+}
+
+/// query を含む行を返す（戻り値は contents を借りているので 'a でつなぐ）
+pub fn search<'a>(query: &str, contents: &'a str) -> Vec<&'a str> {
+    contents
+        .lines()
+        .filter(|line| line.contains(query))
+        .collect()
+}
+
+/// 大文字と小文字を区別せずに query を含む行を返す
+pub fn search_case_insensitive<'a>(query: &str, contents: &'a str) -> Vec<&'a str> {
+    let query = query.to_lowercase();
+    contents
+        .lines()
+        .filter(|line| line.to_lowercase().contains(&query))
+        .collect()
+}
+
+/// `cargo run -- grep <検索語> <ファイル>`: 結果を標準出力に出して終了する
+///
+/// args は grep から始まる引数。失敗したら標準エラーにメッセージを出し、終了コード 1 で終わる。
+pub fn main(args: impl Iterator<Item = String>) -> ! {
+    let config = Config::build(args).unwrap_or_else(|err| {
+        eprintln!("{}", tf("grep.bad_args", &[("error", &err)]));
+        eprintln!("{}", t("grep.usage"));
+        process::exit(1);
+    });
+
+    if let Err(e) = run(&config, &mut io::stdout()) {
+        eprintln!("{}", tf("grep.app_error", &[("error", &e)]));
+        process::exit(1);
+    }
+    process::exit(0);
+}
+
+/// `grep [検索語 ファイル]`: メニューから検索する。引数がなければ poem.txt で例を見せる
+pub fn run_from_menu(arg: &str) {
+    if arg.is_empty() {
+        sample();
+        return;
+    }
+
+    let args = std::iter::once("grep").chain(arg.split_whitespace());
+    match Config::build(args.map(str::to_string)) {
+        Ok(config) => match run(&config, &mut io::stdout()) {
+            Ok(0) => println!(
+                "{}",
+                tf(
+                    "grep.no_match",
+                    &[("query", &format!("{:?}", config.query))]
+                )
+            ),
+            Ok(_) => {}
+            Err(e) => println!("{}", tf("grep.app_error", &[("error", &e)])),
+        },
+        Err(e) => println!("{}", tf("grep.bad_args_menu", &[("error", &e)])),
+    }
+}
+
+// 同梱の詩で検索の例を見せる
+fn sample() {
+    println!("\n{}", t("grep.header"));
+    println!("poem.txt:");
+    for line in POEM.lines() {
+        println!("  | {}", line);
+    }
+
+    for (query, ignore_case) in [("body", false), ("to", false), ("to", true)] {
+        let (label, results) = if ignore_case {
+            ("IGNORE_CASE=1 ", search_case_insensitive(query, POEM))
+        } else {
+            ("", search(query, POEM))
+        };
+        println!("\n$ {}cargo run -- grep {} poem.txt", label, query);
+        for line in results {
+            println!("{}", line);
+        }
+    }
+
+    println!("\n$ cargo run -- grep to");
+    let missing = Config::build(["grep", "to"].into_iter().map(String::from));
+    if let Err(e) = missing {
+        println!("{}", tf("grep.bad_args_sample", &[("error", &e)]));
+    }
+    println!("\n{}", t("grep.menu_hint"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args<'a>(list: &'a [&str]) -> impl Iterator<Item = String> + 'a {
+        list.iter().map(|s| s.to_string())
+    }
+
+    #[test]
+    fn case_sensitive() {
+        let query = "duct";
+        let contents = "\
+Rust:
+safe, fast, productive.
+Pick three.
+Duct tape.";
+
+        assert_eq!(vec!["safe, fast, productive."], search(query, contents));
+    }
+
+    #[test]
+    fn case_insensitive() {
+        let query = "rUsT";
+        let contents = "\
+Rust:
+safe, fast, productive.
+Pick three.
+Trust me.";
+
+        assert_eq!(
+            vec!["Rust:", "Trust me."],
+            search_case_insensitive(query, contents)
+        );
+    }
+
+    #[test]
+    fn build_reports_missing_arguments() {
+        assert_eq!(Config::build(args(&["grep"])), Err("検索語がありません"));
+        assert_eq!(
+            Config::build(args(&["grep", "to"])),
+            Err("ファイル名がありません")
+        );
+        let config = Config::build(args(&["grep", "to", "poem.txt"])).unwrap();
+        assert_eq!(
+            (config.query.as_str(), config.file_path.as_str()),
+            ("to", "poem.txt")
+        );
+    }
+
+    #[test]
+    fn run_searches_the_bundled_poem_and_reports_missing_files() {
+        // テストはパッケージのルートで実行されるので poem.txt をそのまま開ける
+        let mut config = Config {
+            query: "to".to_string(),
+            file_path: "poem.txt".to_string(),
+            ignore_case: false,
+        };
+        let mut out = Vec::new();
+        assert_eq!(run(&config, &mut out).unwrap(), 2);
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "Are you nobody, too?\nHow dreary to be somebody!\n"
+        );
+
+        config.ignore_case = true;
+        assert_eq!(run(&config, &mut Vec::new()).unwrap(), 4);
+
+        config.file_path = "no-such-file.txt".to_string();
+        assert!(run(&config, &mut Vec::new()).is_err());
+    }
+}
@@ -0,0 +1,148 @@
+// ============================================================================
+// プラグイン（外部クレートのレッスンモジュール）
+// ============================================================================
+//
+// lesson-api の LessonModule を実装したクレートを、Cargo の feature で
+// 有効にするとメニューに自動で並ぶ。
+//
+// プラグインを追加する手順:
+//   1. Cargo.toml に optional な依存と feature を追加する
+//        my-plugin = { path = "plugins/my-plugin", optional = true }
+//        plugin-my = ["dep:my-plugin"]
+//   2. 下の collect() に1行追加する
+//   3. cargo run --features plugin-my
+//
+// 組み込みのトピックとキーが重なるもの、API のバージョンが合わないものは読み込まない。
+
+use crate::achievements;
+use crate::i18n::tf;
+use crate::registry::{self, Level};
+use crate::usage;
+use lesson_api::{output, Demo, LessonModule, API_VERSION};
+use std::sync::OnceLock;
+
+/// 読み込んだプラグインの1トピック
+pub struct PluginTopic {
+    module: Box<dyn LessonModule>,
+    demos: Vec<Box<dyn Demo>>,
+}
+
+impl PluginTopic {
+    pub fn key(&self) -> &'static str {
+        self.module.key()
+    }
+
+    pub fn title(&self) -> &'static str {
+        self.module.title()
+    }
+
+    pub fn chapter(&self) -> &'static str {
+        self.module.chapter()
+    }
+
+    pub fn requires(&self) -> &'static [&'static str] {
+        self.module.requires()
+    }
+
+    pub fn demo_count(&self) -> usize {
+        self.demos.len()
+    }
+
+    /// 現在の難易度フィルタで表示されるデモ
+    pub fn visible_demos(&self) -> impl Iterator<Item = &dyn Demo> {
+        let max = registry::max_level();
+        self.demos
+            .iter()
+            .map(|demo| demo.as_ref())
+            .filter(move |demo| level_of(*demo) <= max)
+    }
+
+    /// 表示されるデモにかかる時間の目安の合計（分、難易度ごとの既定値）
+    pub fn estimated_minutes(&self) -> u32 {
+        self.visible_demos()
+            .map(|demo| level_of(demo).estimated_minutes())
+            .sum()
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visible_demos().next().is_some()
+    }
+
+    /// 組み込みのトピックの run_all と同じように、見出しを付けてデモを順に実行する
+    pub fn run_all(&self) {
+        let line = "═".repeat(64);
+        println!("{}", line);
+        println!("  {}（{}）", self.title(), self.chapter());
+        println!("{}", line);
+
+        let max = registry::max_level();
+        let mut skipped = Vec::new();
+        for demo in &self.demos {
+            if level_of(demo.as_ref()) <= max {
+                println!("\n=== {} ===", demo.title());
+                output::section(|| demo.run());
+                usage::record_demo(self.key(), demo.name());
+                achievements::record_demo(self.key(), demo.name());
+            } else {
+                skipped.push(demo);
+            }
+        }
+        if !skipped.is_empty() {
+            registry::print_skipped(
+                skipped
+                    .iter()
+                    .map(|demo| (demo.title(), demo.name(), level_of(demo.as_ref()))),
+            );
+        }
+    }
+}
+
+// lesson-api の難易度を本体の難易度に変換する
+fn level_of(demo: &dyn Demo) -> Level {
+    match demo.level() {
+        lesson_api::Level::Beginner => Level::Beginner,
+        lesson_api::Level::Intermediate => Level::Intermediate,
+        lesson_api::Level::Advanced => Level::Advanced,
+    }
+}
+
+// feature で有効になっているプラグインを集める
+fn collect() -> Vec<Box<dyn LessonModule>> {
+    vec![
+        #[cfg(feature = "plugin-hello")]
+        hello_plugin::lesson(),
+    ]
+}
+
+static PLUGINS: OnceLock<Vec<PluginTopic>> = OnceLock::new();
+
+/// 読み込み済みのプラグイン（初回の呼び出しで読み込む）
+pub fn installed() -> &'static [PluginTopic] {
+    PLUGINS.get_or_init(|| {
+        let mut topics: Vec<PluginTopic> = Vec::new();
+        for module in collect() {
+            let key = module.key();
+            if module.api_version() != API_VERSION {
+                eprintln!(
+                    "{}",
+                    tf(
+                        "plugins.api_mismatch",
+                        &[
+                            ("key", &key),
+                            ("version", &module.api_version()),
+                            ("supported", &API_VERSION),
+                        ]
+                    )
+                );
+            } else if registry::find_topic(key).is_some()
+                || topics.iter().any(|topic| topic.key() == key)
+            {
+                eprintln!("{}", tf("plugins.key_taken", &[("key", &key)]));
+            } else {
+                let demos = module.demos();
+                topics.push(PluginTopic { module, demos });
+            }
+        }
+        topics
+    })
+}
@@ -0,0 +1,398 @@
+// ============================================================================
+// クイズ
+// ============================================================================
+//
+// 各章の理解度を選択式の問題で確認する。
+// 回答結果は quiz_results に保存され、章ごとの正答率や苦手なトピックの
+// 分析に使われる。
+// 下の QUESTIONS に加えて、`quiz import` で取り込んだ問題も出題する（quiz_pack.rs）。
+// 問題文・選択肢・解説はレッスンの本文と同じく "text:" の訳があれば訳して表示する（i18n.rs）。
+
+use crate::achievements;
+use crate::i18n::{self, t, tf};
+use crate::quiz_pack;
+use crate::quiz_results::{self, Answer};
+use crate::registry::{self, Topic, TopicText};
+use lesson_api::input::{Input, StdinInput};
+use lesson_api::random::Rng;
+
+/// 選択式の問題
+pub struct Question {
+    pub id: &'static str,
+    pub topic: &'static str,
+    pub question: &'static str,
+    pub choices: &'static [&'static str],
+    /// 正解の選択肢（0始まり）
+    pub answer: usize,
+    pub explanation: &'static str,
+    /// 復習に役立つデモ（"トピック::デモ名"）
+    pub demo: &'static str,
+}
+
+/// 1回のクイズで出題する最大数
+pub const QUESTIONS_PER_QUIZ: usize = 5;
+
+#[rustfmt::skip]
+pub static QUESTIONS: &[Question] = &[
+    Question {
+        id: "basics-mut", topic: "basics",
+        question: "`let x = 5; x = 6;` をコンパイルすると？",
+        choices: &["x が 6 になる", "コンパイルエラーになる", "実行時に panic する", "警告だけ出る"],
+        answer: 1,
+        explanation: "変数はデフォルトで不変。再代入するには `let mut x` と宣言する",
+        demo: "basics::variables_demo",
+    },
+    Question {
+        id: "basics-int-div", topic: "basics",
+        question: "`5 / 3` の結果は？",
+        choices: &["1", "1.666...", "2", "コンパイルエラー"],
+        answer: 0,
+        explanation: "整数同士の除算は 0 方向に切り捨てられる",
+        demo: "basics::data_types_demo",
+    },
+    Question {
+        id: "basics-block-expr", topic: "basics",
+        question: "`let y = { let x = 3; x + 1 };` の y は？",
+        choices: &["3", "4", "()", "コンパイルエラー"],
+        answer: 1,
+        explanation: "セミコロンのない最後の式がブロックの値になる",
+        demo: "basics::functions_demo",
+    },
+    Question {
+        id: "ownership-move", topic: "ownership",
+        question: "`let s1 = String::from(\"hi\"); let s2 = s1;` の後で s1 を使うと？",
+        choices: &["\"hi\" が表示される", "空文字列になる", "コンパイルエラーになる", "実行時エラーになる"],
+        answer: 2,
+        explanation: "String はムーブされ、s1 は無効になる",
+        demo: "ownership::ownership_basics",
+    },
+    Question {
+        id: "ownership-mut-refs", topic: "ownership",
+        question: "同じスコープで同時に作れる可変参照の数は？",
+        choices: &["0", "1", "2", "制限なし"],
+        answer: 1,
+        explanation: "可変参照は同時に1つだけ。データ競合をコンパイル時に防ぐ",
+        demo: "ownership::references_and_borrowing",
+    },
+    Question {
+        id: "ownership-copy", topic: "ownership",
+        question: "代入してもムーブされない（コピーされる）型は？",
+        choices: &["String", "Vec<i32>", "i32", "Box<i32>"],
+        answer: 2,
+        explanation: "i32 のようなスタックだけで完結する型は Copy トレイトを実装している",
+        demo: "ownership::ownership_basics",
+    },
+    Question {
+        id: "structs-assoc", topic: "structs_enums",
+        question: "`Rectangle::square(3)` のように呼ぶ、self を取らない関数は？",
+        choices: &["メソッド", "関連関数", "クロージャ", "マクロ"],
+        answer: 1,
+        explanation: "impl ブロック内で self を取らない関数は関連関数と呼ばれる",
+        demo: "structs_enums::methods",
+    },
+    Question {
+        id: "structs-option", topic: "structs_enums",
+        question: "Rust で「値が無いかもしれない」ことを表す型は？",
+        choices: &["null", "Option<T>", "Result<T, E>", "()"],
+        answer: 1,
+        explanation: "Rust に null は無く、Option<T> の None で表す",
+        demo: "structs_enums::option_enum",
+    },
+    Question {
+        id: "pattern-exhaustive", topic: "pattern_matching",
+        question: "match で列挙型のバリアントを1つ書き忘れると？",
+        choices: &["そのバリアントは無視される", "コンパイルエラー", "実行時に panic", "警告のみ"],
+        answer: 1,
+        explanation: "match は網羅的でなければならない",
+        demo: "pattern_matching::exhaustiveness_and_catchall",
+    },
+    Question {
+        id: "pattern-guard", topic: "pattern_matching",
+        question: "`Some(x) if x > 5 =>` の `if x > 5` を何と呼ぶ？",
+        choices: &["マッチガード", "@束縛", "範囲パターン", "let-else"],
+        answer: 0,
+        explanation: "パターンに追加の条件を付けるマッチガード",
+        demo: "pattern_matching::match_guards",
+    },
+    Question {
+        id: "pattern-at", topic: "pattern_matching",
+        question: "範囲に一致するか調べながら値を変数に束縛する構文は？",
+        choices: &["id if 3..=7", "id @ 3..=7", "id: 3..=7", "id => 3..=7"],
+        answer: 1,
+        explanation: "@ 束縛を使う",
+        demo: "pattern_matching::at_bindings",
+    },
+    Question {
+        id: "error-question", topic: "error_handling",
+        question: "`?` 演算子を使える関数の戻り値の型は？",
+        choices: &["何でもよい", "Result や Option など", "() のみ", "String のみ"],
+        answer: 1,
+        explanation: "? は Result や Option を返す関数の中でのみ使える",
+        demo: "error_handling::error_propagation",
+    },
+    Question {
+        id: "error-unwrap", topic: "error_handling",
+        question: "Err に対して unwrap() を呼ぶと？",
+        choices: &["None を返す", "デフォルト値を返す", "panic する", "無視される"],
+        answer: 2,
+        explanation: "unwrap / expect はエラー時に panic する",
+        demo: "error_handling::unwrap_and_expect",
+    },
+    Question {
+        id: "error-library", topic: "error_handling",
+        question: "ライブラリで回復可能な失敗を表すのに適切なのは？",
+        choices: &["panic!", "Result<T, E> を返す", "process::exit", "println! でエラー表示"],
+        answer: 1,
+        explanation: "呼び出し側が対処を選べるよう Result を返す",
+        demo: "error_handling::best_practices",
+    },
+    Question {
+        id: "traits-impl-return", topic: "traits_generics",
+        question: "`fn f() -> impl Summary` で返せる具体型の数は？",
+        choices: &["1つ", "2つまで", "いくつでも", "0（返せない）"],
+        answer: 0,
+        explanation: "impl Trait の戻り値は単一の具体型に決まる必要がある",
+        demo: "traits_generics::returning_traits",
+    },
+    Question {
+        id: "traits-bound", topic: "traits_generics",
+        question: "`fn largest<T: PartialOrd>` の `T: PartialOrd` は何？",
+        choices: &["ライフタイム", "トレイト境界", "関連型", "型エイリアス"],
+        answer: 1,
+        explanation: "T が比較可能であることを要求するトレイト境界",
+        demo: "traits_generics::generics_basics",
+    },
+    Question {
+        id: "traits-supertrait", topic: "traits_generics",
+        question: "`trait OutlinePrint: Display` の意味は？",
+        choices: &["Display を継承したクラス", "OutlinePrint の実装には Display の実装が必要", "Display を自動実装する", "Display と同じトレイト"],
+        answer: 1,
+        explanation: "Display はスーパートレイトで、実装する型に Display を要求する",
+        demo: "traits_generics::supertraits",
+    },
+    Question {
+        id: "collections-get", topic: "collections",
+        question: "`v.get(100)`（要素は3つ）の結果は？",
+        choices: &["panic", "None", "0", "コンパイルエラー"],
+        answer: 1,
+        explanation: "get は範囲外のとき None を返す。v[100] なら panic",
+        demo: "collections::vector_basics",
+    },
+    Question {
+        id: "collections-entry", topic: "collections",
+        question: "キーが無いときだけ挿入する HashMap のメソッドは？",
+        choices: &["insert", "entry(k).or_insert(v)", "get_or(k, v)", "push"],
+        answer: 1,
+        explanation: "entry API はキーの有無に応じた処理を1回の探索で行える",
+        demo: "collections::hashmap_updating",
+    },
+    Question {
+        id: "collections-len", topic: "collections",
+        question: "`\"こんにちは\".len()` の値は？",
+        choices: &["5", "10", "15", "20"],
+        answer: 2,
+        explanation: "len() はバイト数。ひらがなは UTF-8 で3バイトずつ",
+        demo: "collections::string_indexing",
+    },
+    Question {
+        id: "iter-lazy", topic: "iterators_closures",
+        question: "`v.iter().map(|x| x * 2);` だけを実行すると？",
+        choices: &["v が2倍になる", "新しい Vec が作られる", "何も起きない（遅延評価）", "コンパイルエラー"],
+        answer: 2,
+        explanation: "アダプタは消費されるまで何もしない（未使用の警告が出る）",
+        demo: "iterators_closures::iterator_adapters",
+    },
+    Question {
+        id: "iter-fnmut", topic: "iterators_closures",
+        question: "キャプチャした変数を変更するクロージャが実装するトレイトは？",
+        choices: &["Fn", "FnMut", "FnOnce のみ", "Copy"],
+        answer: 1,
+        explanation: "可変借用でキャプチャするので FnMut",
+        demo: "iterators_closures::closure_capture",
+    },
+    Question {
+        id: "iter-into-iter", topic: "iterators_closures",
+        question: "`v.into_iter()` の要素の型は（v: Vec<T>）？",
+        choices: &["&T", "&mut T", "T", "Option<T>"],
+        answer: 2,
+        explanation: "into_iter は所有権を取り、T を順に返す",
+        demo: "iterators_closures::iterator_basics",
+    },
+    Question {
+        id: "lifetimes-purpose", topic: "lifetimes",
+        question: "ライフタイム注釈の役割は？",
+        choices: &["参照の寿命を延ばす", "参照同士の寿命の関係をコンパイラに伝える", "メモリを解放する", "実行時に寿命を確認する"],
+        answer: 1,
+        explanation: "注釈は寿命を変えない。関係を記述するだけ",
+        demo: "lifetimes::function_lifetimes",
+    },
+    Question {
+        id: "lifetimes-elision", topic: "lifetimes",
+        question: "`fn first_word(s: &str) -> &str` に注釈が不要な理由は？",
+        choices: &["'static だから", "入力の参照が1つなので省略規則が適用される", "戻り値が参照ではないから", "コンパイラが実行時に判断するから"],
+        answer: 1,
+        explanation: "入力ライフタイムが1つなら出力も同じと推論される",
+        demo: "lifetimes::lifetime_elision",
+    },
+    Question {
+        id: "lifetimes-static", topic: "lifetimes",
+        question: "文字列リテラルのライフタイムは？",
+        choices: &["'a", "'static", "'_", "ライフタイムを持たない"],
+        answer: 1,
+        explanation: "リテラルはバイナリに埋め込まれるのでプログラム全体で有効",
+        demo: "lifetimes::static_lifetime",
+    },
+];
+
+/// `quiz [トピック]`: 問題をシャッフルして出題し、結果を保存する
+/// 戻り値は (正解数, 回答数)。問題がないか、1問も答えずに中断した場合は None
+pub fn run(topic: Option<&Topic>) -> Option<(usize, usize)> {
+    let mut rng = Rng::from_time();
+    let mut questions: Vec<&Question> = quiz_pack::all_questions()
+        .into_iter()
+        .filter(|q| topic.is_none_or(|t| q.topic == t.key))
+        .collect();
+    rng.shuffle(&mut questions);
+    questions.truncate(QUESTIONS_PER_QUIZ);
+
+    if questions.is_empty() {
+        println!("{}", t("quiz.no_questions"));
+        return None;
+    }
+
+    println!(
+        "\n{}",
+        tf(
+            "quiz.header",
+            &[("title", &scope_title(topic)), ("count", &questions.len())]
+        )
+    );
+
+    let mut input = StdinInput;
+    let mut answers = Vec::new();
+    for (i, question) in questions.iter().enumerate() {
+        print_question(i, question);
+
+        let Some(choice) = read_choice(&mut input, question.choices.len()) else {
+            println!("\n{}", t("quiz.aborted"));
+            break;
+        };
+        let correct = choice == question.answer;
+        if correct {
+            println!(
+                "  {}",
+                tf(
+                    "quiz.correct",
+                    &[("explanation", &i18n::text(question.explanation))]
+                )
+            );
+        } else {
+            println!(
+                "  {}",
+                tf(
+                    "quiz.wrong",
+                    &[
+                        ("number", &(question.answer + 1)),
+                        ("answer", &i18n::text(question.choices[question.answer]))
+                    ]
+                )
+            );
+            println!("    {}", i18n::text(question.explanation));
+            if let Some((topic, demo)) = question
+                .demo
+                .split_once("::")
+                .and_then(|(key, _)| registry::find_topic(key))
+                .zip(registry::find_demo(question.demo))
+            {
+                println!(
+                    "    {}",
+                    tf(
+                        "quiz.review",
+                        &[("title", &topic.demo_title(demo)), ("id", &question.demo)]
+                    )
+                );
+            }
+        }
+        answers.push(Answer {
+            topic: question.topic.to_string(),
+            question: question.id.to_string(),
+            correct,
+        });
+    }
+
+    if answers.is_empty() {
+        return None;
+    }
+    let score = answers.iter().filter(|a| a.correct).count();
+    println!(
+        "\n{}",
+        tf(
+            "quiz.result",
+            &[("score", &score), ("total", &answers.len())]
+        )
+    );
+    achievements::record_quiz(topic.map(|t| t.key), score, answers.len(), questions.len());
+
+    if let Err(e) = quiz_results::append(&answers) {
+        println!("{}", tf("quiz.save_failed", &[("error", &e)]));
+    }
+    println!("{}", t("quiz.report_hint"));
+    Some((score, answers.len()))
+}
+
+/// 授業用の提示モード: 問題を表示し、間をおいてから答えを表示する（回答は記録しない）
+pub fn present(topic: Option<&Topic>, count: usize, pause: &dyn Fn()) {
+    let questions: Vec<&Question> = quiz_pack::all_questions()
+        .into_iter()
+        .filter(|q| topic.is_none_or(|t| q.topic == t.key))
+        .take(count)
+        .collect();
+    println!(
+        "\n{}",
+        tf(
+            "quiz.present_header",
+            &[("title", &scope_title(topic)), ("count", &questions.len())]
+        )
+    );
+
+    for (i, question) in questions.iter().enumerate() {
+        print_question(i, question);
+        pause();
+        println!(
+            "\n  {}",
+            tf(
+                "quiz.answer",
+                &[
+                    ("number", &(question.answer + 1)),
+                    ("answer", &i18n::text(question.choices[question.answer]))
+                ]
+            )
+        );
+        println!("  {}", i18n::text(question.explanation));
+    }
+}
+
+/// 見出しに出す範囲（トピックを指定しなければ全トピック）
+pub fn scope_title(topic: Option<&Topic>) -> &'static str {
+    topic.map_or_else(|| t("quiz.all_topics"), |topic| topic.display_title())
+}
+
+fn print_question(i: usize, question: &Question) {
+    println!("\nQ{}. {}", i + 1, i18n::text(question.question));
+    for (n, choice) in question.choices.iter().enumerate() {
+        println!("  {}) {}", n + 1, i18n::text(choice));
+    }
+}
+
+// 1..=n の番号を読む（EOF や q で None）
+fn read_choice(input: &mut impl Input, n: usize) -> Option<usize> {
+    let prompt = format!("  {}", tf("quiz.prompt", &[("max", &n)]));
+    let choice = input.read_parsed(&prompt, |line| match line {
+        "q" => Ok(None),
+        s => match s.parse::<usize>() {
+            Ok(k) if (1..=n).contains(&k) => Ok(Some(k - 1)),
+            _ => Err(format!("  {}", tf("quiz.invalid_number", &[("max", &n)]))),
+        },
+    });
+    choice.ok()??
+}
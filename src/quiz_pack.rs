@@ -0,0 +1,688 @@
+// ============================================================================
+// クイズの問題集の書き出しと取り込み（quiz export / quiz import）
+// ============================================================================
+//
+// 問題を JSON ファイルに書き出したり、ほかの人が作った問題集を取り込んだりする。
+// 取り込んだ問題は学習データの保存先の quiz_imported.json に保存され、
+// 組み込みの問題と同じように `quiz` で出題される。
+//
+//   quiz export quiz.json              すべての問題を書き出す
+//   quiz export ownership own.json     トピックを指定して書き出す
+//   quiz import community.json         問題集を検査して取り込む
+//
+// 形式（format と version は必須）:
+//
+//   {
+//     "format": "gkrust-quiz",
+//     "version": 1,
+//     "title": "所有権の追加問題",
+//     "questions": [
+//       {
+//         "id": "ownership-vec-move",
+//         "topic": "ownership",
+//         "question": "`let v2 = v1;` の後で v1 を使うと？",
+//         "choices": ["使える", "コンパイルエラー"],
+//         "answer": 2,
+//         "explanation": "Vec はムーブされる",
+//         "demo": "ownership::ownership_basics"
+//       }
+//     ]
+//   }
+//
+// answer は正解の選択肢の番号（1始まり、画面の表示と同じ）。title と demo は省略できる。
+// 間違いはまとめて「何行目のどの値か」と、その行を添えて報告する。
+
+use crate::i18n::{t, tf};
+use crate::json::Json;
+use crate::quiz::{Question, QUESTIONS};
+use crate::registry::{self, TOPICS};
+use crate::storage;
+use lesson_api::text;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::Mutex;
+
+/// format に書く値
+pub const FORMAT: &str = "gkrust-quiz";
+/// 対応している形式のバージョン
+pub const VERSION: u32 = 1;
+
+/// 選択肢の数の範囲
+const CHOICES: std::ops::RangeInclusive<usize> = 2..=6;
+
+/// 取り込んだ問題の保存先（学習データの保存先の中）
+const IMPORTED_FILE: &str = "quiz_imported.json";
+
+const ROOT_KEYS: [&str; 4] = ["format", "version", "title", "questions"];
+const QUESTION_KEYS: [&str; 7] = [
+    "id",
+    "topic",
+    "question",
+    "choices",
+    "answer",
+    "explanation",
+    "demo",
+];
+
+// ----------------------------------------------------------------------------
+// 読み込みと検査
+// ----------------------------------------------------------------------------
+
+/// 問題集の1問（検査済み）
+#[derive(Debug, Clone, PartialEq)]
+pub struct PackQuestion {
+    pub id: String,
+    /// 登録済みのトピックのキー
+    pub topic: &'static str,
+    pub question: String,
+    pub choices: Vec<String>,
+    /// 正解の選択肢（0始まり）
+    pub answer: usize,
+    pub explanation: String,
+    /// 復習に役立つデモ（無ければ空）
+    pub demo: String,
+}
+
+/// 検査済みの問題集
+#[derive(Debug, PartialEq)]
+pub struct Pack {
+    pub title: Option<String>,
+    pub questions: Vec<PackQuestion>,
+}
+
+/// 問題集の間違い1つ
+#[derive(Debug, PartialEq)]
+pub struct PackError {
+    /// 1始まりの行番号
+    pub line: usize,
+    /// JSON として読めないときだけ、その列
+    pub column: Option<usize>,
+    /// "questions[1].answer" のような値の場所（ルートなら空）
+    pub path: String,
+    pub message: String,
+}
+
+impl fmt::Display for PackError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let line = tf("quiz_pack.line", &[("line", &self.line)]);
+        if self.path.is_empty() {
+            write!(f, "{}: {}", line, self.message)
+        } else {
+            write!(f, "{} {}: {}", line, self.path, self.message)
+        }
+    }
+}
+
+// 間違いを集めながら値を取り出す
+struct Checker {
+    lines: HashMap<String, usize>,
+    errors: Vec<PackError>,
+}
+
+impl Checker {
+    fn error(&mut self, path: &str, message: String) {
+        self.errors.push(PackError {
+            line: line_of(&self.lines, path),
+            column: None,
+            path: path.to_string(),
+            message,
+        });
+    }
+
+    // 知らないキーは綴りの間違いのことが多いので、無視せずに知らせる
+    fn unknown_keys(&mut self, object: &HashMap<String, Json>, path: &str, known: &[&str]) {
+        let mut unknown: Vec<&String> = object
+            .keys()
+            .filter(|key| !known.contains(&key.as_str()))
+            .collect();
+        unknown.sort();
+        for key in unknown {
+            self.error(
+                &child(path, key),
+                tf("quiz_pack.unknown_key", &[("known", &known.join(", "))]),
+            );
+        }
+    }
+
+    fn string(
+        &mut self,
+        object: &mut HashMap<String, Json>,
+        path: &str,
+        key: &str,
+        required: bool,
+    ) -> Option<String> {
+        match object.remove(key) {
+            None if required => {
+                self.error(path, tf("quiz_pack.missing", &[("key", &key)]));
+                None
+            }
+            None => None,
+            Some(Json::Str(s)) if required && s.trim().is_empty() => {
+                self.error(&child(path, key), t("quiz_pack.empty").to_string());
+                None
+            }
+            Some(Json::Str(s)) => Some(s),
+            Some(_) => {
+                self.error(&child(path, key), t("quiz_pack.not_string").to_string());
+                None
+            }
+        }
+    }
+
+    fn question(&mut self, item: Json, path: &str) -> Option<PackQuestion> {
+        let Some(mut object) = item.into_object() else {
+            self.error(path, t("quiz_pack.question_not_object").to_string());
+            return None;
+        };
+        self.unknown_keys(&object, path, &QUESTION_KEYS);
+        let errors_before = self.errors.len();
+
+        let id = self.string(&mut object, path, "id", true);
+        if let Some(id) = &id {
+            let valid = id
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_');
+            if !valid {
+                self.error(&child(path, "id"), t("quiz_pack.bad_id").to_string());
+            }
+        }
+
+        let topic = self
+            .string(&mut object, path, "topic", true)
+            .and_then(|key| match registry::find_topic(&key) {
+                Some(topic) => Some(topic.key),
+                None => {
+                    let keys: Vec<&str> = TOPICS.iter().map(|t| t.key).collect();
+                    self.error(
+                        &child(path, "topic"),
+                        tf(
+                            "quiz_pack.unknown_topic",
+                            &[("name", &key), ("keys", &keys.join(", "))],
+                        ),
+                    );
+                    None
+                }
+            });
+        let question = self.string(&mut object, path, "question", true);
+        let explanation = self.string(&mut object, path, "explanation", true);
+
+        let demo = self
+            .string(&mut object, path, "demo", false)
+            .unwrap_or_default();
+        if !demo.is_empty() && registry::find_demo(&demo).is_none() {
+            self.error(
+                &child(path, "demo"),
+                tf("quiz_pack.unknown_demo", &[("name", &demo)]),
+            );
+        }
+
+        let choices_path = child(path, "choices");
+        let choices = match object.remove("choices") {
+            None => {
+                self.error(path, tf("quiz_pack.missing", &[("key", &"choices")]));
+                None
+            }
+            Some(Json::Array(items)) => {
+                let mut choices = Vec::new();
+                for (i, item) in items.into_iter().enumerate() {
+                    match item.into_string() {
+                        Some(choice) => choices.push(choice),
+                        None => self.error(
+                            &format!("{}[{}]", choices_path, i),
+                            t("quiz_pack.choice_not_string").to_string(),
+                        ),
+                    }
+                }
+                if !CHOICES.contains(&choices.len()) {
+                    self.error(
+                        &choices_path,
+                        tf(
+                            "quiz_pack.choice_count",
+                            &[
+                                ("min", CHOICES.start()),
+                                ("max", CHOICES.end()),
+                                ("count", &choices.len()),
+                            ],
+                        ),
+                    );
+                }
+                Some(choices)
+            }
+            Some(_) => {
+                self.error(&choices_path, t("quiz_pack.choices_not_array").to_string());
+                None
+            }
+        };
+
+        let answer_path = child(path, "answer");
+        let answer = match object.remove("answer").map(Json::into_u32) {
+            None => {
+                self.error(path, tf("quiz_pack.missing", &[("key", &"answer")]));
+                None
+            }
+            Some(None) => {
+                self.error(&answer_path, t("quiz_pack.bad_answer").to_string());
+                None
+            }
+            Some(Some(n)) => {
+                let count = choices.as_ref().map_or(0, Vec::len);
+                if choices.is_some() && !(1..=count).contains(&(n as usize)) {
+                    self.error(
+                        &answer_path,
+                        tf(
+                            "quiz_pack.answer_out_of_range",
+                            &[("count", &count), ("answer", &n)],
+                        ),
+                    );
+                }
+                Some((n as usize).saturating_sub(1))
+            }
+        };
+
+        if self.errors.len() > errors_before {
+            return None;
+        }
+        Some(PackQuestion {
+            id: id?,
+            topic: topic?,
+            question: question?,
+            choices: choices?,
+            answer: answer?,
+            explanation: explanation?,
+            demo,
+        })
+    }
+}
+
+// "questions[0]" と "answer" → "questions[0].answer"
+fn child(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+// 値の行。無い値（書き忘れたキーなど）は、それを含む値の行にする
+fn line_of(lines: &HashMap<String, usize>, path: &str) -> usize {
+    let mut path = path;
+    loop {
+        if let Some(&line) = lines.get(path) {
+            return line;
+        }
+        match path.rfind(['.', '[']) {
+            Some(i) => path = &path[..i],
+            None => return lines.get("").copied().unwrap_or(1),
+        }
+    }
+}
+
+/// 問題集の JSON を読み、形式を検査する（間違いはすべて返す）
+pub fn parse_pack(source: &str) -> Result<Pack, Vec<PackError>> {
+    let (root, lines) = Json::parse_with_lines(source).map_err(|e| {
+        vec![PackError {
+            line: e.line,
+            column: Some(e.column),
+            path: String::new(),
+            message: tf("quiz_pack.invalid_json", &[("error", &e.message)]),
+        }]
+    })?;
+    let mut checker = Checker {
+        lines,
+        errors: Vec::new(),
+    };
+
+    let Some(mut root) = root.into_object() else {
+        checker.error("", t("quiz_pack.root_not_object").to_string());
+        return Err(checker.errors);
+    };
+    checker.unknown_keys(&root, "", &ROOT_KEYS);
+
+    match root.remove("format").and_then(Json::into_string) {
+        Some(format) if format == FORMAT => {}
+        _ => checker.error(
+            "format",
+            tf("quiz_pack.format_required", &[("format", &FORMAT)]),
+        ),
+    }
+    match root.remove("version").map(Json::into_u32) {
+        Some(Some(VERSION)) => {}
+        Some(Some(version)) => checker.error(
+            "version",
+            tf(
+                "quiz_pack.unsupported_version",
+                &[("version", &version), ("supported", &VERSION)],
+            ),
+        ),
+        _ => checker.error(
+            "version",
+            tf("quiz_pack.version_required", &[("version", &VERSION)]),
+        ),
+    }
+    let title = checker.string(&mut root, "", "title", false);
+
+    let mut questions = Vec::new();
+    match root.remove("questions").and_then(Json::into_array) {
+        Some(items) if !items.is_empty() => {
+            let mut seen = HashSet::new();
+            for (i, item) in items.into_iter().enumerate() {
+                let path = format!("questions[{}]", i);
+                let Some(question) = checker.question(item, &path) else {
+                    continue;
+                };
+                if !seen.insert(question.id.clone()) {
+                    checker.error(
+                        &child(&path, "id"),
+                        tf("quiz_pack.duplicate_id", &[("id", &question.id)]),
+                    );
+                }
+                questions.push(question);
+            }
+        }
+        Some(_) => checker.error("questions", t("quiz_pack.no_questions").to_string()),
+        None => checker.error("", t("quiz_pack.missing_questions").to_string()),
+    }
+
+    if checker.errors.is_empty() {
+        Ok(Pack { title, questions })
+    } else {
+        checker.errors.sort_by_key(|e| e.line);
+        Err(checker.errors)
+    }
+}
+
+/// 間違いを、ファイル名と行番号、その行の内容を添えて表示する
+pub fn print_errors(file: &str, source: &str, errors: &[PackError]) {
+    let lines: Vec<&str> = source.lines().collect();
+    for error in errors {
+        println!("{}:{}", file, error);
+        if let Some(line) = lines.get(error.line - 1) {
+            let number = error.line.to_string();
+            println!("  {} | {}", number, line);
+            if let Some(column) = error.column {
+                // 全角文字があっても ^ が揃うように表示幅で数える
+                let before: String = line.chars().take(column - 1).collect();
+                println!(
+                    "  {} | {}^",
+                    " ".repeat(number.len()),
+                    " ".repeat(text::display_width(&before))
+                );
+            }
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+// 書き出し
+// ----------------------------------------------------------------------------
+
+/// 問題を問題集の JSON にする
+pub fn to_json(title: Option<&str>, questions: &[&Question]) -> Json {
+    let string = |s: &str| Json::Str(s.to_string());
+    let items = questions
+        .iter()
+        .map(|q| {
+            let mut object = HashMap::from([
+                ("id".to_string(), string(q.id)),
+                ("topic".to_string(), string(q.topic)),
+                ("question".to_string(), string(q.question)),
+                (
+                    "choices".to_string(),
+                    Json::Array(q.choices.iter().map(|c| string(c)).collect()),
+                ),
+                ("answer".to_string(), Json::Number((q.answer + 1) as f64)),
+                ("explanation".to_string(), string(q.explanation)),
+            ]);
+            if !q.demo.is_empty() {
+                object.insert("demo".to_string(), string(q.demo));
+            }
+            Json::Object(object)
+        })
+        .collect();
+
+    let mut root = HashMap::from([
+        ("format".to_string(), string(FORMAT)),
+        ("version".to_string(), Json::Number(VERSION as f64)),
+        ("questions".to_string(), Json::Array(items)),
+    ]);
+    if let Some(title) = title {
+        root.insert("title".to_string(), string(title));
+    }
+    Json::Object(root)
+}
+
+/// `quiz export [トピック] <ファイル>`
+pub fn export(arg: &str) {
+    let (topic, file) = match arg.rsplit_once(char::is_whitespace) {
+        Some((name, file)) => match registry::resolve_topic(name.trim()) {
+            Some(topic) => (Some(topic), file),
+            None => {
+                println!("{}", tf("main.unknown_topic", &[("name", &name.trim())]));
+                return;
+            }
+        },
+        None if !arg.is_empty() => (None, arg),
+        None => {
+            println!("{}", t("quiz_pack.export_usage"));
+            return;
+        }
+    };
+
+    let questions: Vec<&Question> = all_questions()
+        .into_iter()
+        .filter(|q| topic.is_none_or(|t| q.topic == t.key))
+        .collect();
+    if questions.is_empty() {
+        println!("{}", t("quiz_pack.nothing_to_export"));
+        return;
+    }
+    let title = topic.map(|t| t.title);
+    match fs::write(file, format!("{:#}\n", to_json(title, &questions))) {
+        Ok(()) => println!(
+            "{}",
+            tf(
+                "quiz_pack.exported",
+                &[("count", &questions.len()), ("path", &file)]
+            )
+        ),
+        Err(e) => println!("{}", tf("main.write_failed", &[("error", &e)])),
+    }
+}
+
+// ----------------------------------------------------------------------------
+// 取り込み
+// ----------------------------------------------------------------------------
+
+// 取り込んだ問題（最初に使うときに保存先から読み込む）
+static IMPORTED: Mutex<Option<Vec<&'static Question>>> = Mutex::new(None);
+
+// 取り込んだ問題は終了まで使うので、文字列は 'static にして Question に揃える
+fn leak(question: PackQuestion) -> &'static Question {
+    let text = |s: String| -> &'static str { Box::leak(s.into_boxed_str()) };
+    let choices: Vec<&'static str> = question.choices.into_iter().map(text).collect();
+    Box::leak(Box::new(Question {
+        id: text(question.id),
+        topic: question.topic,
+        question: text(question.question),
+        choices: Box::leak(choices.into_boxed_slice()),
+        answer: question.answer,
+        explanation: text(question.explanation),
+        demo: text(question.demo),
+    }))
+}
+
+fn load_imported() -> Vec<&'static Question> {
+    let source = match storage::load_text(IMPORTED_FILE) {
+        Ok(Some(source)) => source,
+        Ok(None) => return Vec::new(),
+        Err(e) => {
+            eprintln!("{}", tf("quiz_pack.load_failed", &[("error", &e)]));
+            return Vec::new();
+        }
+    };
+    match parse_pack(&source) {
+        Ok(pack) => pack.questions.into_iter().map(leak).collect(),
+        Err(errors) => {
+            // トピックやデモの名前が変わった場合など
+            eprintln!(
+                "{}",
+                tf("quiz_pack.load_invalid", &[("file", &IMPORTED_FILE)])
+            );
+            for error in errors {
+                eprintln!("  {}", error);
+            }
+            Vec::new()
+        }
+    }
+}
+
+/// 組み込みの問題と取り込んだ問題
+pub fn all_questions() -> Vec<&'static Question> {
+    let mut imported = IMPORTED.lock().unwrap_or_else(|e| e.into_inner());
+    QUESTIONS
+        .iter()
+        .chain(imported.get_or_insert_with(load_imported).iter().copied())
+        .collect()
+}
+
+/// 問題集を検査し、取り込んだ問題に加えて保存する
+/// 同じ ID の問題は新しいもので置き換える。組み込みの問題と同じ ID は取り込まない
+/// 戻り値は取り込んだ問題集と、組み込みと重なって取り込まなかった数
+pub fn import(source: &str) -> io::Result<Result<(Pack, usize), Vec<PackError>>> {
+    let mut pack = match parse_pack(source) {
+        Ok(pack) => pack,
+        Err(errors) => return Ok(Err(errors)),
+    };
+    let builtin = pack.questions.len();
+    pack.questions
+        .retain(|q| !QUESTIONS.iter().any(|builtin| builtin.id == q.id));
+    let skipped = builtin - pack.questions.len();
+
+    let mut imported = IMPORTED.lock().unwrap_or_else(|e| e.into_inner());
+    let imported = imported.get_or_insert_with(load_imported);
+    for question in &pack.questions {
+        let question = leak(question.clone());
+        match imported.iter_mut().find(|q| q.id == question.id) {
+            Some(slot) => *slot = question,
+            None => imported.push(question),
+        }
+    }
+    storage::save_text(IMPORTED_FILE, &format!("{:#}\n", to_json(None, imported)))?;
+    Ok(Ok((pack, skipped)))
+}
+
+/// `quiz import <ファイル>`
+pub fn run_import(arg: &str) {
+    if arg.is_empty() {
+        println!("{}", t("quiz_pack.import_usage"));
+        return;
+    }
+    let source = match fs::read_to_string(Path::new(arg)) {
+        Ok(source) => source,
+        Err(e) => {
+            println!(
+                "{}",
+                tf("quiz_pack.read_failed", &[("path", &arg), ("error", &e)])
+            );
+            return;
+        }
+    };
+    match import(&source) {
+        Ok(Ok((pack, skipped))) => {
+            let title = pack.title.as_deref().unwrap_or(arg);
+            println!(
+                "{}",
+                tf(
+                    "quiz_pack.imported",
+                    &[("title", &title), ("count", &pack.questions.len())]
+                )
+            );
+            if skipped > 0 {
+                println!("{}", tf("quiz_pack.skipped", &[("count", &skipped)]));
+            }
+        }
+        Ok(Err(errors)) => {
+            println!(
+                "{}",
+                tf(
+                    "quiz_pack.errors",
+                    &[("path", &arg), ("count", &errors.len())]
+                )
+            );
+            print_errors(arg, &source, &errors);
+            println!("{}", t("quiz_pack.nothing_imported"));
+        }
+        Err(e) => println!("{}", tf("quiz_pack.save_failed", &[("error", &e)])),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exported_questions_parse_back() {
+        let questions: Vec<&Question> = QUESTIONS
+            .iter()
+            .filter(|q| q.topic == "ownership")
+            .collect();
+        let text = format!("{:#}", to_json(Some("所有権"), &questions));
+        let pack = parse_pack(&text).unwrap();
+        assert_eq!(pack.title.as_deref(), Some("所有権"));
+        assert_eq!(pack.questions.len(), questions.len());
+        for (parsed, original) in pack.questions.iter().zip(&questions) {
+            assert_eq!(parsed.id, original.id);
+            assert_eq!(parsed.choices, original.choices);
+            assert_eq!(parsed.answer, original.answer);
+            assert_eq!(parsed.demo, original.demo);
+        }
+    }
+
+    #[test]
+    fn reports_every_mistake_with_its_line() {
+        let text = r#"{
+  "format": "gkrust-quiz",
+  "version": 1,
+  "questions": [
+    {
+      "id": "a",
+      "topic": "ownership",
+      "question": "Q",
+      "choices": ["x", "y"],
+      "answer": 3,
+      "explanation": "E"
+    },
+    {
+      "id": "b",
+      "topic": "nope",
+      "question": "Q",
+      "choices": ["x", "y"],
+      "anwser": 1,
+      "explanation": "E"
+    }
+  ]
+}"#;
+        let errors = parse_pack(text).unwrap_err();
+        let summary: Vec<(usize, &str)> =
+            errors.iter().map(|e| (e.line, e.path.as_str())).collect();
+        assert_eq!(
+            summary,
+            [
+                (10, "questions[0].answer"),
+                (13, "questions[1]"),
+                (15, "questions[1].topic"),
+                (18, "questions[1].anwser"),
+            ]
+        );
+        assert!(errors[0]
+            .to_string()
+            .contains("1 から 2 で指定してください"));
+
+        let syntax =
+            parse_pack("{\n  \"format\": \"gkrust-quiz\",\n  \"version\" 1\n}").unwrap_err();
+        assert_eq!((syntax[0].line, syntax[0].column), (3, Some(13)));
+    }
+}
@@ -0,0 +1,171 @@
+// ============================================================================
+// クイズ結果の保存と分析
+// ============================================================================
+//
+// すべての回答を quiz_results.tsv に追記していき、そこから
+//   - 章ごとの正答率（全期間 / 直近）
+//   - 苦手なトピックの提案
+// を集計する。
+
+use crate::i18n::{t, tf};
+use crate::registry::{self, TopicText, TOPICS};
+use crate::storage;
+use std::collections::HashMap;
+use std::io;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const RESULTS_FILE: &str = "quiz_results.tsv";
+
+/// 直近の正答率を計算するときに見る回答数
+const RECENT_WINDOW: usize = 10;
+
+/// 苦手と判断する正答率の上限
+pub const WEAK_THRESHOLD: f64 = 0.7;
+
+/// 1問分の回答
+pub struct Answer {
+    pub topic: String,
+    pub question: String,
+    pub correct: bool,
+}
+
+/// 章ごとの集計結果
+pub struct TopicStats {
+    pub topic: &'static str,
+    pub attempts: usize,
+    pub correct: usize,
+    /// 直近 RECENT_WINDOW 問の正答率
+    pub recent_accuracy: f64,
+}
+
+impl TopicStats {
+    pub fn accuracy(&self) -> f64 {
+        self.correct as f64 / self.attempts as f64
+    }
+}
+
+/// 回答を履歴に追記する
+pub fn append(answers: &[Answer]) -> io::Result<()> {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let mut records = storage::load_records(RESULTS_FILE)?;
+    for answer in answers {
+        records.push(vec![
+            timestamp.to_string(),
+            answer.topic.clone(),
+            answer.question.clone(),
+            if answer.correct { "1" } else { "0" }.to_string(),
+        ]);
+    }
+    storage::save_records(RESULTS_FILE, &records)
+}
+
+/// 保存済みの回答を古い順に読み込む
+pub fn load() -> Vec<Answer> {
+    storage::load_records(RESULTS_FILE)
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|record| match record.as_slice() {
+            [_, topic, question, correct] => Some(Answer {
+                topic: topic.clone(),
+                question: question.clone(),
+                correct: correct == "1",
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+/// 章ごとに集計する（回答の無い章は含めない、レジストリの順に並ぶ）
+pub fn stats_by_topic(answers: &[Answer]) -> Vec<TopicStats> {
+    let mut by_topic: HashMap<&str, Vec<bool>> = HashMap::new();
+    for answer in answers {
+        by_topic
+            .entry(answer.topic.as_str())
+            .or_default()
+            .push(answer.correct);
+    }
+
+    TOPICS
+        .iter()
+        .filter_map(|topic| {
+            let results = by_topic.get(topic.key)?;
+            let recent = &results[results.len().saturating_sub(RECENT_WINDOW)..];
+            Some(TopicStats {
+                topic: topic.key,
+                attempts: results.len(),
+                correct: results.iter().filter(|&&c| c).count(),
+                recent_accuracy: recent.iter().filter(|&&c| c).count() as f64 / recent.len() as f64,
+            })
+        })
+        .collect()
+}
+
+/// 直近の正答率が低い順に苦手なトピックを返す
+pub fn weakest_topics(limit: usize) -> Vec<TopicStats> {
+    let mut weak: Vec<TopicStats> = stats_by_topic(&load())
+        .into_iter()
+        .filter(|stats| stats.recent_accuracy < WEAK_THRESHOLD)
+        .collect();
+    weak.sort_by(|a, b| a.recent_accuracy.total_cmp(&b.recent_accuracy));
+    weak.truncate(limit);
+    weak
+}
+
+/// `report`: 章ごとの正答率と苦手なトピックを表示する
+pub fn print_report() {
+    let answers = load();
+    if answers.is_empty() {
+        println!("{}", t("report.empty"));
+        return;
+    }
+
+    println!("\n{}", tf("report.header", &[("count", &answers.len())]));
+    println!();
+    for stats in stats_by_topic(&answers) {
+        let title = registry::find_topic(stats.topic).map_or(stats.topic, |t| t.display_title());
+        println!(
+            "  {}",
+            tf(
+                "report.row",
+                &[
+                    ("topic", &format!("{:<20}", stats.topic)),
+                    ("bar", &bar(stats.accuracy(), 20)),
+                    ("percent", &format!("{:>3.0}", stats.accuracy() * 100.0)),
+                    ("correct", &stats.correct),
+                    ("attempts", &stats.attempts),
+                    ("recent", &format!("{:.0}", stats.recent_accuracy * 100.0))
+                ]
+            )
+        );
+        println!("    {}", title);
+    }
+
+    let weak = weakest_topics(3);
+    if weak.is_empty() {
+        println!("\n{}", t("report.no_weak"));
+    } else {
+        println!("\n{}", t("report.weak_header"));
+        for stats in weak {
+            println!(
+                "  - {}",
+                tf(
+                    "report.weak_row",
+                    &[
+                        ("topic", &stats.topic),
+                        ("recent", &format!("{:.0}", stats.recent_accuracy * 100.0))
+                    ]
+                )
+            );
+        }
+    }
+    println!();
+}
+
+/// 割合を ████░░░░ 形式の棒で表す
+pub fn bar(ratio: f64, width: usize) -> String {
+    let filled = (ratio * width as f64).round() as usize;
+    format!("{}{}", "█".repeat(filled), "░".repeat(width - filled))
+}
@@ -0,0 +1,298 @@
+// ============================================================================
+// デモレジストリ（ランナー側）
+// ============================================================================
+//
+// トピックとデモの一覧（TOPICS）と難易度フィルタは crates/lessons にあり、
+// ブラウザ版（crates/playground）と共有している。
+// ここではそれを再公開し、表示言語での表示とデモの実行（利用統計の記録つき）を足す。
+
+use crate::achievements;
+use crate::i18n;
+use crate::usage;
+use lesson_api::output;
+// println! は capture で受け取れる lesson_api 版（並列実行のときに出力がトピックの中に収まる）
+use lesson_api::{print, println};
+use std::io::{self, IsTerminal};
+use std::panic;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+pub use lessons::{
+    content_version, find_demo, find_topic, learning_path, max_level, set_max_level, Demo, Level,
+    Topic, TOPICS,
+};
+
+/// 難易度の表示用ラベル（表示言語に合わせる）
+pub trait LevelLabel {
+    fn label(self) -> &'static str;
+}
+
+impl LevelLabel for Level {
+    fn label(self) -> &'static str {
+        match self {
+            Level::Beginner => i18n::t("level.beginner"),
+            Level::Intermediate => i18n::t("level.intermediate"),
+            Level::Advanced => i18n::t("level.advanced"),
+        }
+    }
+}
+
+/// トピックの表示言語でのタイトルなど（翻訳が無ければ lessons に書いた日本語）
+pub trait TopicText {
+    fn display_title(&self) -> &'static str;
+
+    /// 章（"応用" など）
+    fn display_chapter(&self) -> &'static str;
+
+    /// デモのタイトル（キーは "demo.トピック.デモ名"）
+    fn demo_title(&self, demo: &Demo) -> &'static str;
+}
+
+impl TopicText for Topic {
+    fn display_title(&self) -> &'static str {
+        i18n::lookup(&format!("topic.{}", self.key)).unwrap_or(self.title)
+    }
+
+    fn display_chapter(&self) -> &'static str {
+        i18n::lookup(&format!("chapter.{}", self.chapter)).unwrap_or(self.chapter)
+    }
+
+    fn demo_title(&self, demo: &Demo) -> &'static str {
+        i18n::lookup(&format!("demo.{}.{}", self.key, demo.name)).unwrap_or(demo.title)
+    }
+}
+
+/// 番号か名前でトピックを指定する
+/// lessons の resolve_topic（番号、キー、タイトル、別名）に加えて、表示言語のタイトルでも引ける
+pub fn resolve_topic(arg: &str) -> Option<&'static Topic> {
+    lessons::resolve_topic(arg).or_else(|| {
+        TOPICS
+            .iter()
+            .find(|topic| lessons::same_name(topic.display_title(), arg))
+    })
+}
+
+/// トピックのデモのうち、現在の難易度フィルタを通るものを順に実行する
+/// 各モジュールの run_all() から呼ばれる
+pub fn run_demos(key: &str) {
+    let topic = find_topic(key).expect("未登録のトピックです");
+    let max = max_level();
+    let mut skipped = Vec::new();
+    for demo in topic.demos {
+        if demo.level <= max {
+            // デモの出力はまとめて書き出す（1行ずつ write しない）
+            output::section(demo.run);
+            usage::record_demo(topic.key, demo.name);
+            achievements::record_demo(topic.key, demo.name);
+        } else {
+            skipped.push(demo);
+        }
+    }
+    if !skipped.is_empty() {
+        print_skipped(
+            skipped
+                .into_iter()
+                .map(|demo| (topic.demo_title(demo), demo.name, demo.level)),
+        );
+    }
+}
+
+/// トピックの run_all() をスレッドで並列に実行し、出力はトピックの順に表示する（メニューの「0」）
+///
+/// 各スレッドは output::capture でトピックの出力を文字列にためる。
+/// 終わった順に届くので、次に表示すべきトピックが届くまで後のものは待たせておく。
+/// 待っている間も分かるように、終わったデモの数を進捗バーで示す（端末のときは標準エラーに随時、最後に1行）。
+pub fn run_all_parallel(topics: &[&'static Topic]) {
+    let total_demos: usize = topics
+        .iter()
+        .map(|topic| topic.visible_demos().count())
+        .sum();
+    let mut done_demos = 0;
+    // 進捗バーは標準エラーの1行を \r で書き換える（パイプやファイルへの出力には混ぜない）
+    let live = io::stderr().is_terminal();
+    let show_progress = |done: usize| {
+        if live {
+            eprint!("\r{}", progress_line(done, total_demos));
+        }
+    };
+    let clear_progress = || {
+        if live {
+            eprint!("\r\x1b[2K");
+        }
+    };
+    show_progress(0);
+
+    let workers = thread::available_parallelism()
+        .map_or(1, |n| n.get())
+        .min(topics.len());
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            let sender = sender.clone();
+            let next = &next;
+            // 空いたスレッドが次のトピックを取りに行く（重いトピックがあっても偏らない）
+            scope.spawn(move || loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(topic) = topics.get(i) else {
+                    break;
+                };
+                // panic したトピックがあっても、ほかのトピックは最後まで表示する
+                let result = panic::catch_unwind(|| output::capture(topic.run_all));
+                if sender.send((i, result)).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(sender);
+
+        let mut finished: Vec<Option<thread::Result<String>>> =
+            topics.iter().map(|_| None).collect();
+        let mut shown = 0;
+        for (i, result) in receiver {
+            finished[i] = Some(result);
+            done_demos += topics[i].visible_demos().count();
+            while let Some(result) = finished.get_mut(shown).and_then(Option::take) {
+                // トピックの出力の前に進捗バーを消し、出力のあとに描き直す
+                clear_progress();
+                match result {
+                    Ok(text) => print!("{}", text),
+                    Err(_) => println!(
+                        "\n{}",
+                        i18n::tf(
+                            "registry.panicked",
+                            &[("title", &topics[shown].display_title())]
+                        )
+                    ),
+                }
+                output::flush();
+                shown += 1;
+            }
+            show_progress(done_demos);
+        }
+    });
+    clear_progress();
+    println!("\n{}", progress_line(done_demos, total_demos));
+}
+
+/// 進捗バーの1行（`[####----] 12/28 デモ`）
+fn progress_line(done: usize, total: usize) -> String {
+    i18n::tf(
+        "registry.progress",
+        &[
+            ("bar", &progress_bar(done, total, 20)),
+            ("done", &done),
+            ("total", &total),
+        ],
+    )
+}
+
+/// done / total を `[####----]` 形式の棒で表す（total が 0 なら空の棒）
+pub fn progress_bar(done: usize, total: usize, width: usize) -> String {
+    let filled = (done * width).checked_div(total).unwrap_or(0).min(width);
+    format!("[{}{}]", "#".repeat(filled), "-".repeat(width - filled))
+}
+
+/// 難易度フィルタで省略したデモ（タイトル, 名前, 難易度）の一覧を表示する
+pub fn print_skipped<'a>(demos: impl Iterator<Item = (&'a str, &'a str, Level)>) {
+    let max = max_level();
+    println!(
+        "\n{}",
+        i18n::tf("registry.skipped", &[("level", &max.label())])
+    );
+    for (title, name, level) in demos {
+        println!("  - {} ({}) [{}]", title, name, level.label());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn progress_bar_fills_in_proportion() {
+        assert_eq!(progress_bar(0, 28, 8), "[--------]");
+        assert_eq!(progress_bar(12, 28, 8), "[###-----]");
+        assert_eq!(progress_bar(28, 28, 8), "[########]");
+        assert_eq!(progress_bar(0, 0, 4), "[----]");
+    }
+
+    // run_all_parallel のテスト用のトピック（デモは持たない）
+    const EMPTY: lesson_api::CheatSheet = lesson_api::CheatSheet {
+        syntax: &[],
+        methods: &[],
+        gotchas: &[],
+    };
+
+    const fn test_topic(key: &'static str, title: &'static str, run_all: fn()) -> Topic {
+        Topic {
+            key,
+            title,
+            aliases: &[],
+            chapter: "テスト",
+            requires: &[],
+            run_all,
+            cheatsheet: &EMPTY,
+            demos: &[],
+        }
+    }
+
+    fn slow() {
+        // 後のトピックより遅く終わる
+        thread::sleep(std::time::Duration::from_millis(50));
+        lesson_api::println!("<slow>");
+    }
+
+    fn fast() {
+        lesson_api::println!("<fast>");
+    }
+
+    fn explode() {
+        lesson_api::println!("<explode>");
+        panic!("わざと panic");
+    }
+
+    fn last() {
+        lesson_api::println!("<last>");
+    }
+
+    static SLOW: Topic = test_topic("test_slow", "遅いトピック", slow);
+    static FAST: Topic = test_topic("test_fast", "速いトピック", fast);
+    static EXPLODE: Topic = test_topic("test_explode", "爆発するトピック", explode);
+    static LAST: Topic = test_topic("test_last", "最後のトピック", last);
+
+    #[test]
+    fn parallel_output_keeps_topic_order() {
+        let text = output::capture(|| run_all_parallel(&[&SLOW, &FAST, &LAST]));
+        let slow_at = text.find("<slow>").unwrap();
+        let fast_at = text.find("<fast>").unwrap();
+        let last_at = text.find("<last>").unwrap();
+        assert!(slow_at < fast_at && fast_at < last_at, "{}", text);
+    }
+
+    #[test]
+    fn parallel_run_reports_a_panicking_topic_and_continues() {
+        let text = output::capture(|| run_all_parallel(&[&FAST, &EXPLODE, &LAST]));
+        // panic したトピックの出力の代わりに、タイトル入りの知らせが順番どおりの位置に出る
+        let fast_at = text.find("<fast>").unwrap();
+        let notice_at = text.find("爆発するトピック").unwrap();
+        let last_at = text.find("<last>").unwrap();
+        assert!(fast_at < notice_at && notice_at < last_at, "{}", text);
+        assert!(!text.contains("<explode>"));
+    }
+
+    #[test]
+    fn demos_carry_time_estimates() {
+        let slices = find_demo("ownership::slices").unwrap();
+        assert_eq!(slices.minutes, Level::Beginner.estimated_minutes());
+        // 長いデモは難易度の既定値より長くしてある
+        let parser = find_demo("parsing::parser_demo").unwrap();
+        assert!(parser.minutes > Level::Advanced.estimated_minutes());
+        assert!(TOPICS
+            .iter()
+            .flat_map(|topic| topic.demos)
+            .all(|demo| demo.minutes > 0));
+    }
+}
@@ -0,0 +1,325 @@
+// ============================================================================
+// コードを試す（try）
+// ============================================================================
+//
+// デモを見たすぐ後に、自分で書いたコードをその場でコンパイルして実行する。
+//
+//   try              $EDITOR（または $VISUAL）でコードを書く。設定が無ければ下の入力方法になる
+//   try <<EOF        続けて入力した行を、EOF だけの行までコードとして読む（ヒアドキュメント風）
+//   try ファイル.rs   ファイルのコードを試す
+//
+// コードは一時ディレクトリの cargo プロジェクト（src/main.rs）に書いて cargo build し、
+// できた実行ファイルを子プロセスとして動かす。fn main が無ければ全体を fn main() { ... } で包む。
+// コンパイルエラーはそのまま表示し、エラーコード（E0382 など）があれば `explain` を案内する。
+// 終わらないコード（無限ループなど）は RUN_TIMEOUT で止める。
+
+use crate::explain;
+use crate::i18n::{t, tf};
+use lesson_api::input::{Input, StdinInput};
+use std::collections::BTreeSet;
+use std::env;
+use std::fs;
+use std::io::{self, IsTerminal, Read};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// 実行を打ち切るまでの時間
+const RUN_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// ヒアドキュメントの終わりの行（try <<END のように変えられる）
+const DEFAULT_TERMINATOR: &str = "EOF";
+
+/// エディタで開くときのひな形（説明のコメントは表示言語に合わせる）
+fn template() -> String {
+    format!(
+        "// {}\n// {}\n\nlet v = vec![1, 2, 3];\nprintln!(\"{{:?}}\", v);\n",
+        t("sandbox.template_save"),
+        t("sandbox.template_wrap")
+    )
+}
+
+/// 試した結果
+pub enum Outcome {
+    CompileError(String),
+    Ran {
+        stdout: String,
+        stderr: String,
+        /// 終了コード（シグナルで止まったなら None）
+        code: Option<i32>,
+    },
+    TimedOut,
+}
+
+/// コードを fn main の中に入れる（fn main があればそのまま）
+pub fn wrap(snippet: &str) -> String {
+    if snippet.contains("fn main") {
+        return snippet.to_string();
+    }
+    let body: String = snippet
+        .lines()
+        .map(|line| format!("    {}\n", line))
+        .collect();
+    format!("#[allow(unused)]\nfn main() {{\n{}}}\n", body)
+}
+
+/// 終わりの行まで読む（入力が終わったらそこまで）
+pub fn read_heredoc(input: &mut impl Input, terminator: &str) -> io::Result<String> {
+    println!(
+        "{}",
+        tf("sandbox.heredoc_prompt", &[("terminator", &terminator)])
+    );
+    let mut snippet = String::new();
+    while let Some(line) = input.read_line()? {
+        if line.trim() == terminator {
+            break;
+        }
+        snippet.push_str(&line);
+        snippet.push('\n');
+    }
+    Ok(snippet)
+}
+
+// $EDITOR でひな形を開き、保存された内容を読む
+fn read_with_editor(editor: &str, dir: &Path) -> io::Result<String> {
+    let path = dir.join("snippet.rs");
+    fs::write(&path, template())?;
+    // "code --wait" のように引数付きで設定されていることもある
+    let mut words = editor.split_whitespace();
+    let program = words.next().unwrap_or(editor);
+    let status = Command::new(program).args(words).arg(&path).status()?;
+    if !status.success() {
+        return Err(io::Error::other(tf(
+            "sandbox.editor_failed",
+            &[("editor", &editor)],
+        )));
+    }
+    fs::read_to_string(&path)
+}
+
+/// エラー出力に出てくるエラーコード（重複なし、番号順）
+pub fn error_codes(stderr: &str) -> Vec<String> {
+    let codes: BTreeSet<String> = stderr
+        .match_indices("error[E")
+        .filter_map(|(i, _)| {
+            let code = stderr.get(i + 6..i + 11)?;
+            code[1..]
+                .chars()
+                .all(|c| c.is_ascii_digit())
+                .then(|| code.to_string())
+        })
+        .collect();
+    codes.into_iter().collect()
+}
+
+// 一時ディレクトリに cargo プロジェクトを作る
+fn create_project(dir: &Path, source: &str) -> io::Result<()> {
+    fs::create_dir_all(dir.join("src"))?;
+    // 空の [workspace] で、このリポジトリのワークスペースに入らないようにする
+    fs::write(
+        dir.join("Cargo.toml"),
+        "[package]\nname = \"snippet\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[workspace]\n",
+    )?;
+    fs::write(dir.join("src/main.rs"), source)
+}
+
+// 子プロセスの出力を別スレッドで読みながら、timeout まで終わるのを待つ
+fn run_with_timeout(command: &mut Command, timeout: Duration) -> io::Result<Option<Output>> {
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    // パイプが一杯になると子プロセスが止まるので、待つ間も読み続ける
+    let read_all = |mut pipe: Box<dyn Read + Send>| {
+        thread::spawn(move || {
+            let mut buffer = Vec::new();
+            let _ = pipe.read_to_end(&mut buffer);
+            buffer
+        })
+    };
+    let stdout = read_all(Box::new(child.stdout.take().expect("piped")));
+    let stderr = read_all(Box::new(child.stderr.take().expect("piped")));
+
+    let start = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if start.elapsed() > timeout {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Ok(None);
+        }
+        thread::sleep(Duration::from_millis(20));
+    };
+    Ok(Some(Output {
+        status,
+        stdout: stdout.join().unwrap_or_default(),
+        stderr: stderr.join().unwrap_or_default(),
+    }))
+}
+
+/// コードを dir の cargo プロジェクトでビルドして実行する
+pub fn try_snippet(snippet: &str, dir: &Path) -> io::Result<Outcome> {
+    create_project(dir, &wrap(snippet))?;
+    let target = dir.join("target");
+    // cargo test から呼ばれたときは、同じ cargo を使う
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let build = Command::new(cargo)
+        .args(["build", "--quiet", "--color", "never", "--target-dir"])
+        .arg(&target)
+        .current_dir(dir)
+        .output()?;
+    if !build.status.success() {
+        let stderr = String::from_utf8_lossy(&build.stderr);
+        return Ok(Outcome::CompileError(stderr.into_owned()));
+    }
+
+    let binary = target
+        .join("debug")
+        .join(format!("snippet{}", env::consts::EXE_SUFFIX));
+    let mut run = Command::new(binary);
+    run.env("RUST_BACKTRACE", "0");
+    Ok(match run_with_timeout(&mut run, RUN_TIMEOUT)? {
+        Some(output) => Outcome::Ran {
+            stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+            stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+            code: output.status.code(),
+        },
+        None => Outcome::TimedOut,
+    })
+}
+
+fn print_outcome(outcome: &Outcome) {
+    match outcome {
+        Outcome::CompileError(stderr) => {
+            println!(
+                "\n--- {} ---\n{}",
+                t("sandbox.compile_error"),
+                stderr.trim_end()
+            );
+            for code in error_codes(stderr) {
+                if explain::find(&code).is_some() {
+                    println!("{}", tf("sandbox.explain_hint", &[("code", &code)]));
+                }
+            }
+        }
+        Outcome::Ran {
+            stdout,
+            stderr,
+            code,
+        } => {
+            println!("\n--- {} ---", t("sandbox.output"));
+            print!("{}", stdout);
+            if !stderr.is_empty() {
+                println!("--- {} ---\n{}", t("sandbox.stderr"), stderr.trim_end());
+            }
+            match code {
+                Some(0) => {}
+                Some(code) => println!("{}", tf("sandbox.exit_code", &[("code", code)])),
+                None => println!("{}", t("sandbox.signaled")),
+            }
+        }
+        Outcome::TimedOut => println!(
+            "\n{}",
+            tf("sandbox.timed_out", &[("seconds", &RUN_TIMEOUT.as_secs())])
+        ),
+    }
+    println!();
+}
+
+// 入力の方法を選んでコードを読む
+fn read_snippet(arg: &str, dir: &Path) -> io::Result<String> {
+    if let Some(terminator) = arg.strip_prefix("<<") {
+        let terminator = match terminator.trim() {
+            "" => DEFAULT_TERMINATOR,
+            terminator => terminator,
+        };
+        return read_heredoc(&mut StdinInput, terminator);
+    }
+    if !arg.is_empty() {
+        return fs::read_to_string(arg);
+    }
+    let editor = env::var("VISUAL").or_else(|_| env::var("EDITOR"));
+    match editor {
+        Ok(editor) if io::stdin().is_terminal() => read_with_editor(&editor, dir),
+        _ => read_heredoc(&mut StdinInput, DEFAULT_TERMINATOR),
+    }
+}
+
+/// `try [<<終わりの行 | ファイル.rs]`
+pub fn run(arg: &str) {
+    let dir: PathBuf = env::temp_dir().join(format!("gkrust-try-{}", std::process::id()));
+    let result = fs::create_dir_all(&dir).and_then(|()| {
+        let snippet = read_snippet(arg, &dir)?;
+        if snippet.trim().is_empty() {
+            println!("{}", t("sandbox.empty"));
+            return Ok(());
+        }
+        println!("{}", t("sandbox.compiling"));
+        try_snippet(&snippet, &dir).map(|outcome| print_outcome(&outcome))
+    });
+    if let Err(e) = result {
+        println!("{}", tf("sandbox.failed", &[("error", &e)]));
+    }
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lesson_api::input::ScriptedInput;
+
+    #[test]
+    fn wraps_snippets_and_reads_heredocs() {
+        assert_eq!(
+            wrap("let x = 1;\nprintln!(\"{}\", x);"),
+            "#[allow(unused)]\nfn main() {\n    let x = 1;\n    println!(\"{}\", x);\n}\n"
+        );
+        let full = "fn main() {\n    println!(\"hi\");\n}\n";
+        assert_eq!(wrap(full), full);
+
+        let mut input = ScriptedInput::new("let a = 1;\nlet b = a;\nEND\nignored\n");
+        assert_eq!(
+            read_heredoc(&mut input, "END").unwrap(),
+            "let a = 1;\nlet b = a;\n"
+        );
+        // 終わりの行が無ければ入力の終わりまで
+        let mut input = ScriptedInput::new("let c = 3;");
+        assert_eq!(read_heredoc(&mut input, "EOF").unwrap(), "let c = 3;\n");
+
+        let stderr = "error[E0382]: borrow of moved value\nerror[E0502]: x\nerror[E0382]: y\nerror: aborting";
+        assert_eq!(error_codes(stderr), ["E0382", "E0502"]);
+    }
+
+    #[test]
+    fn compiles_and_runs_snippets_with_cargo() {
+        let dir = env::temp_dir().join(format!("gkrust-try-test-{}", std::process::id()));
+
+        let outcome = try_snippet(
+            "let v = vec![1, 2, 3];\nprintln!(\"{}\", v.iter().sum::<i32>());",
+            &dir,
+        )
+        .unwrap();
+        match outcome {
+            Outcome::Ran { stdout, code, .. } => {
+                assert_eq!(stdout, "6\n");
+                assert_eq!(code, Some(0));
+            }
+            _ => panic!("実行できるはず"),
+        }
+
+        let outcome = try_snippet(
+            "let s = String::new();\nlet t = s;\nprintln!(\"{}\", s);",
+            &dir,
+        )
+        .unwrap();
+        match outcome {
+            Outcome::CompileError(stderr) => assert_eq!(error_codes(&stderr), ["E0382"]),
+            _ => panic!("コンパイルエラーになるはず"),
+        }
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
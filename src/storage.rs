@@ -0,0 +1,101 @@
+// ============================================================================
+// 学習データの保存先
+// ============================================================================
+//
+// フラッシュカードの復習予定など、実行をまたいで残したいデータを
+// ユーザーのホームディレクトリ配下にテキストファイルで保存する。
+//
+// 保存先:
+//   1. 環境変数 GKRUST_DATA_DIR があればそのディレクトリ
+//   2. なければ $HOME/.gkrustpractice（Windows は %USERPROFILE%）
+//   3. どちらもなければカレントディレクトリの .gkrustpractice
+//
+// テストは with_data_dir で、そのスレッドの保存先だけを一時ディレクトリに向ける
+// （環境変数を書き換えると、並列に動くほかのテストと競合する）。
+//
+// 形式は基本的に1行1レコード、フィールドはタブ区切り（外部クレートに頼らない）。
+// 構造を持つデータは load_text / save_text でファイルの中身をそのまま扱う。
+
+#[cfg(test)]
+use std::cell::RefCell;
+use std::env;
+use std::fs;
+use std::io::{self, ErrorKind};
+#[cfg(test)]
+use std::path::Path;
+use std::path::PathBuf;
+
+#[cfg(test)]
+thread_local! {
+    // with_data_dir の実行中だけ Some
+    static TEST_DATA_DIR: RefCell<Option<PathBuf>> = const { RefCell::new(None) };
+}
+
+/// f の実行中だけ、このスレッドの保存先を dir にする（テスト用）
+#[cfg(test)]
+pub fn with_data_dir<R>(dir: &Path, f: impl FnOnce() -> R) -> R {
+    let previous = TEST_DATA_DIR.with(|cell| cell.replace(Some(dir.to_path_buf())));
+    let result = f();
+    TEST_DATA_DIR.with(|cell| *cell.borrow_mut() = previous);
+    result
+}
+
+/// データを保存するディレクトリ
+pub fn data_dir() -> PathBuf {
+    #[cfg(test)]
+    if let Some(dir) = TEST_DATA_DIR.with(|cell| cell.borrow().clone()) {
+        return dir;
+    }
+    if let Some(dir) = env::var_os("GKRUST_DATA_DIR") {
+        return PathBuf::from(dir);
+    }
+    let home = env::var_os("HOME").or_else(|| env::var_os("USERPROFILE"));
+    match home {
+        Some(home) => PathBuf::from(home).join(".gkrustpractice"),
+        None => PathBuf::from(".gkrustpractice"),
+    }
+}
+
+/// レコードを読み込む（ファイルがまだ無ければ空）
+pub fn load_records(name: &str) -> io::Result<Vec<Vec<String>>> {
+    let text = load_text(name)?.unwrap_or_default();
+    Ok(text
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.split('\t').map(str::to_string).collect())
+        .collect())
+}
+
+/// レコードを書き込む（ファイル全体を置き換える）
+pub fn save_records(name: &str, records: &[Vec<String>]) -> io::Result<()> {
+    let mut text = String::new();
+    for record in records {
+        text.push_str(&record.join("\t"));
+        text.push('\n');
+    }
+    save_text(name, &text)
+}
+
+/// ファイルの中身を読み込む（ファイルがまだ無ければ None）
+pub fn load_text(name: &str) -> io::Result<Option<String>> {
+    match fs::read_to_string(data_dir().join(name)) {
+        Ok(text) => Ok(Some(text)),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// ファイルの中身を書き込む（ファイル全体を置き換える）
+pub fn save_text(name: &str, text: &str) -> io::Result<()> {
+    let dir = data_dir();
+    fs::create_dir_all(&dir)?;
+    fs::write(dir.join(name), text)
+}
+
+/// 1970-01-01 からの経過日数（復習間隔の計算に使う）
+pub fn today() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() / 86_400)
+        .unwrap_or(0)
+}
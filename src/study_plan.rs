@@ -0,0 +1,456 @@
+// ============================================================================
+// 学習計画（plan --goal）
+// ============================================================================
+//
+// 目標（トピック、デモ、または「trait objects」のような概念）を決めると、
+// 前提トピックのグラフ（learning_path）と、これまでの学習の記録から、
+// そこへたどり着くまでにやることを順に並べる。
+//
+//   plan --goal trait objects
+//   plan --goal lifetimes
+//   plan --goal traits_generics::command_registry
+//
+// 学習の記録として見るもの:
+//   - このセッションで実行したトピック
+//   - 利用統計（`stats on` のとき）に残っている、実行したことのあるデモ
+//   - クイズの結果（直近の正答率）
+//
+// 実行済みのデモは飛ばし、トピックを学び終えたところでそのトピックのクイズを挟む。
+// 受けたことのないクイズと、直近の正答率が低いクイズを計画に入れる。
+
+use crate::i18n::{t, tf};
+use crate::quiz::QUESTIONS_PER_QUIZ;
+use crate::quiz_pack;
+use crate::quiz_results::{self, WEAK_THRESHOLD};
+use crate::registry::{self, Demo, Level, Topic, TopicText, TOPICS};
+use crate::usage;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::ptr;
+
+/// 概念の名前から、それを扱うデモへの対応（トピックやデモの名前で引けないもの）
+#[rustfmt::skip]
+const GOALS: &[(&[&str], &str)] = &[
+    (&["trait objects", "trait object", "dyn", "トレイトオブジェクト"], "traits_generics::command_registry"),
+    (&["generics", "ジェネリクス"], "traits_generics::generics_basics"),
+    (&["closures", "closure", "クロージャ"], "iterators_closures::closure_basics"),
+    (&["iterators", "iterator", "イテレータ"], "iterators_closures::iterator_basics"),
+    (&["? operator", "question mark", "?演算子"], "error_handling::error_propagation"),
+    (&["custom errors", "独自のエラー型"], "error_handling::custom_error_types"),
+    (&["borrowing", "references", "借用"], "ownership::references_and_borrowing"),
+    (&["box", "recursive types", "再帰的なデータ型"], "smart_pointers::box_recursive_type"),
+    (&["drop", "デストラクタ"], "smart_pointers::drop_order"),
+    (&["rc", "reference counting", "参照カウント"], "smart_pointers::rc_counting"),
+    (&["refcell", "interior mutability", "内部可変性"], "smart_pointers::refcell_interior_mutability"),
+    (&["weak", "reference cycles", "循環参照"], "smart_pointers::weak_parent_tree"),
+    (&["threads", "spawn", "スレッド"], "concurrency::spawn_and_join"),
+    (&["channels", "mpsc", "チャネル"], "concurrency::channels_multiple_producers"),
+    (&["mutex", "arc", "shared state", "共有状態"], "concurrency::shared_state_mutex"),
+    (&["pipeline", "backpressure", "パイプライン", "背圧"], "concurrency::pipeline_demo"),
+    (&["future", "poll", "waker"], "async_await::hand_written_future"),
+    (&["should_panic"], "testing::should_panic_and_result"),
+    (&["integration tests", "結合テスト"], "testing::test_organization"),
+    (&["hashmap", "hash map"], "collections::hashmap_basics"),
+];
+
+/// 目指すトピック（と、その中のデモ）
+#[derive(Clone, Copy)]
+pub struct Goal {
+    pub topic: &'static Topic,
+    /// None ならトピック全体
+    pub demo: Option<&'static Demo>,
+}
+
+impl Goal {
+    fn new(topic: &'static Topic, demo: Option<&'static Demo>) -> Goal {
+        Goal { topic, demo }
+    }
+
+    fn title(&self) -> String {
+        match self.demo {
+            Some(demo) => tf(
+                "study_plan.goal",
+                &[
+                    ("title", &self.topic.demo_title(demo)),
+                    ("key", &format!("{}::{}", self.topic.key, demo.name)),
+                ],
+            ),
+            None => tf(
+                "study_plan.goal",
+                &[
+                    ("title", &self.topic.display_title()),
+                    ("key", &self.topic.key),
+                ],
+            ),
+        }
+    }
+}
+
+/// 目標を引く（トピック、"トピック::デモ名"、概念の名前、デモの名前、デモのタイトルの一部の順に試す）
+pub fn resolve_goal(arg: &str) -> Option<Goal> {
+    let arg = arg.trim();
+    if arg.is_empty() {
+        return None;
+    }
+    if let Some(topic) = registry::resolve_topic(arg) {
+        return Some(Goal::new(topic, None));
+    }
+    let in_topic = |id: &str| {
+        let (key, _) = id.split_once("::")?;
+        Some(Goal::new(
+            registry::find_topic(key)?,
+            registry::find_demo(id),
+        ))
+    };
+    if let Some(goal) = in_topic(arg).filter(|goal| goal.demo.is_some()) {
+        return Some(goal);
+    }
+    if let Some((_, id)) = GOALS
+        .iter()
+        .find(|(names, _)| names.iter().any(|name| lessons::same_name(name, arg)))
+    {
+        return in_topic(id);
+    }
+
+    let demos = || {
+        TOPICS
+            .iter()
+            .flat_map(|topic| topic.demos.iter().map(move |demo| (topic, demo)))
+    };
+    let lower = arg.to_lowercase();
+    demos()
+        .find(|(_, demo)| lessons::same_name(demo.name, arg))
+        .or_else(|| {
+            demos().find(|(topic, demo)| {
+                demo.title.to_lowercase().contains(&lower)
+                    || topic.demo_title(demo).to_lowercase().contains(&lower)
+            })
+        })
+        .map(|(topic, demo)| Goal::new(topic, Some(demo)))
+}
+
+// ----------------------------------------------------------------------------
+// 学習の記録
+// ----------------------------------------------------------------------------
+
+/// 計画を立てるときに見る学習の記録
+#[derive(Default)]
+pub struct Progress {
+    /// このセッションで実行したトピック
+    pub finished: HashSet<&'static str>,
+    /// "トピック::デモ名" → 実行回数（利用統計を記録しているときだけ）
+    pub demo_runs: BTreeMap<String, u64>,
+    /// トピックのキー → クイズの直近の正答率（受けたことのあるトピックだけ）
+    pub quiz_accuracy: HashMap<&'static str, f64>,
+}
+
+impl Progress {
+    /// 保存してある記録と、このセッションで実行したトピックを集める
+    pub fn load(finished: &HashSet<&'static str>) -> Progress {
+        Progress {
+            finished: finished.clone(),
+            demo_runs: usage::recorded().demo_runs,
+            quiz_accuracy: quiz_results::stats_by_topic(&quiz_results::load())
+                .into_iter()
+                .map(|stats| (stats.topic, stats.recent_accuracy))
+                .collect(),
+        }
+    }
+
+    fn has_run(&self, topic: &Topic, demo: &Demo) -> bool {
+        self.finished.contains(topic.key)
+            || self
+                .demo_runs
+                .contains_key(&format!("{}::{}", topic.key, demo.name))
+    }
+}
+
+// ----------------------------------------------------------------------------
+// 計画
+// ----------------------------------------------------------------------------
+
+/// 計画の1歩
+pub enum Step {
+    /// トピックのデモを実行する（まだ実行していないものだけ）
+    Study {
+        topic: &'static Topic,
+        demos: Vec<&'static Demo>,
+    },
+    /// トピックのクイズを受ける
+    Quiz {
+        topic: &'static Topic,
+        questions: usize,
+        /// 直近の正答率（None なら受けたことがない）
+        accuracy: Option<f64>,
+    },
+}
+
+impl Step {
+    /// 時間の目安（分）。クイズは1問1分で数える
+    pub fn minutes(&self) -> u32 {
+        match self {
+            Step::Study { demos, .. } => demos.iter().map(|demo| demo.minutes).sum(),
+            Step::Quiz { questions, .. } => *questions as u32,
+        }
+    }
+}
+
+pub struct Plan {
+    pub goal: Goal,
+    /// 学び終えているので飛ばす前提トピック
+    pub done: Vec<&'static Topic>,
+    pub steps: Vec<Step>,
+}
+
+/// 目標までの計画を立てる
+/// question_count はトピックのキーからクイズの問題数を返す（問題がなければ 0）
+pub fn build_plan(
+    goal: Goal,
+    progress: &Progress,
+    max_level: Level,
+    question_count: &dyn Fn(&str) -> usize,
+) -> Plan {
+    let mut plan = Plan {
+        goal,
+        done: Vec::new(),
+        steps: Vec::new(),
+    };
+    let is_goal_demo = |demo: &Demo| goal.demo.is_some_and(|target| ptr::eq(demo, target));
+    for topic in registry::learning_path(goal.topic) {
+        let is_goal = ptr::eq(topic, goal.topic);
+        // 目標のデモがあるなら、そのトピックはそこまで（目標のデモは難易度フィルタに関係なく入れる）
+        let end = match goal.demo {
+            Some(target) if is_goal => topic
+                .demos
+                .iter()
+                .position(|demo| ptr::eq(demo, target))
+                .map_or(topic.demos.len(), |i| i + 1),
+            _ => topic.demos.len(),
+        };
+        let demos: Vec<&'static Demo> = topic.demos[..end]
+            .iter()
+            .filter(|demo| demo.level <= max_level || is_goal_demo(demo))
+            .filter(|demo| !progress.has_run(topic, demo))
+            .collect();
+
+        let accuracy = progress.quiz_accuracy.get(topic.key).copied();
+        let questions = question_count(topic.key).min(QUESTIONS_PER_QUIZ);
+        let quiz_needed = questions > 0 && accuracy.is_none_or(|a| a < WEAK_THRESHOLD);
+
+        if demos.is_empty() && !quiz_needed && !is_goal {
+            plan.done.push(topic);
+            continue;
+        }
+        if !demos.is_empty() {
+            plan.steps.push(Step::Study { topic, demos });
+        }
+        if quiz_needed {
+            plan.steps.push(Step::Quiz {
+                topic,
+                questions,
+                accuracy,
+            });
+        }
+    }
+    plan
+}
+
+fn print_plan(plan: &Plan) {
+    println!(
+        "\n{}",
+        tf("study_plan.header", &[("goal", &plan.goal.title())])
+    );
+    if !plan.done.is_empty() {
+        let done: Vec<&str> = plan.done.iter().map(|t| t.display_title()).collect();
+        println!(
+            "{}",
+            tf(
+                "study_plan.done",
+                &[("topics", &done.join(t("study_plan.separator")))]
+            )
+        );
+    }
+    if plan.steps.is_empty() {
+        println!("\n{}", t("study_plan.all_done"));
+        return;
+    }
+
+    println!();
+    for (i, step) in plan.steps.iter().enumerate() {
+        match step {
+            Step::Study { topic, demos } => {
+                println!(
+                    "  {:>2}. {}",
+                    i + 1,
+                    tf(
+                        "study_plan.study_step",
+                        &[
+                            ("topic", &topic.display_title()),
+                            ("chapter", &topic.display_chapter()),
+                            ("demos", &demos.len()),
+                            ("minutes", &step.minutes()),
+                        ]
+                    )
+                );
+                // 表示中のデモを全部やるならトピックごと、一部ならデモを1つずつ
+                if demos.len() == topic.visible_demos().count() {
+                    println!("      → `{}`", topic.key);
+                } else {
+                    for demo in demos {
+                        println!(
+                            "      → `repeat {}::{} 1`  {}",
+                            topic.key,
+                            demo.name,
+                            topic.demo_title(demo)
+                        );
+                    }
+                }
+            }
+            Step::Quiz {
+                topic,
+                questions,
+                accuracy,
+            } => {
+                let reason = match accuracy {
+                    None => t("study_plan.not_taken").to_string(),
+                    Some(a) => tf(
+                        "study_plan.last_accuracy",
+                        &[("percent", &format!("{:.0}", a * 100.0))],
+                    ),
+                };
+                println!(
+                    "  {:>2}. {}",
+                    i + 1,
+                    tf(
+                        "study_plan.quiz_step",
+                        &[
+                            ("topic", &topic.display_title()),
+                            ("questions", questions),
+                            ("reason", &reason),
+                        ]
+                    )
+                );
+                println!("      → `quiz {}`", topic.key);
+            }
+        }
+    }
+
+    let minutes: u32 = plan.steps.iter().map(Step::minutes).sum();
+    let quizzes = plan
+        .steps
+        .iter()
+        .filter(|step| matches!(step, Step::Quiz { .. }))
+        .count();
+    println!(
+        "\n{}",
+        tf(
+            "study_plan.total",
+            &[("minutes", &minutes), ("quizzes", &quizzes)]
+        )
+    );
+    if !usage::is_enabled() {
+        println!("{}", t("study_plan.stats_hint"));
+    }
+}
+
+/// `plan --goal <目標>`
+pub fn run(arg: &str, finished: &HashSet<&'static str>) {
+    let goal = arg.strip_prefix("--goal").map(str::trim).unwrap_or(arg);
+    if goal.is_empty() {
+        println!("{}", t("study_plan.usage"));
+        return;
+    }
+    let Some(goal) = resolve_goal(goal) else {
+        println!("{}", tf("study_plan.unknown_goal", &[("goal", &goal)]));
+        return;
+    };
+
+    let questions = quiz_pack::all_questions();
+    let question_count = |key: &str| questions.iter().filter(|q| q.topic == key).count();
+    let plan = build_plan(
+        goal,
+        &Progress::load(finished),
+        registry::max_level(),
+        &question_count,
+    );
+    print_plan(&plan);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(plan: &Plan) -> Vec<String> {
+        plan.steps
+            .iter()
+            .map(|step| match step {
+                Step::Study { topic, .. } => topic.key.to_string(),
+                Step::Quiz { topic, .. } => format!("quiz {}", topic.key),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn resolves_topics_demos_and_concepts() {
+        let goal = resolve_goal("trait objects").unwrap();
+        assert_eq!(goal.topic.key, "traits_generics");
+        assert_eq!(goal.demo.unwrap().name, "command_registry");
+
+        assert!(resolve_goal("lifetimes").unwrap().demo.is_none());
+        let goal = resolve_goal("ownership::slices").unwrap();
+        assert_eq!(goal.demo.unwrap().name, "slices");
+        assert_eq!(resolve_goal("Dijkstra demo").unwrap().topic.key, "graphs");
+        assert_eq!(resolve_goal("ダイクストラ").unwrap().topic.key, "graphs");
+        assert!(resolve_goal("quantum computing").is_none());
+
+        for (_, id) in GOALS {
+            assert!(registry::find_demo(id).is_some(), "{} が見つかりません", id);
+        }
+    }
+
+    #[test]
+    fn plans_follow_prerequisites_and_skip_what_is_done() {
+        let goal = resolve_goal("trait objects").unwrap();
+        let count = |key: &str| if key == "pattern_matching" { 0 } else { 3 };
+
+        let plan = build_plan(goal, &Progress::default(), Level::Advanced, &count);
+        assert!(plan.done.is_empty());
+        assert_eq!(
+            keys(&plan),
+            [
+                "basics",
+                "quiz basics",
+                "ownership",
+                "quiz ownership",
+                "structs_enums",
+                "quiz structs_enums",
+                // command_registry（目標のデモ）だけが HashMap を使うので collections が入る
+                "collections",
+                "quiz collections",
+                "traits_generics",
+                "quiz traits_generics",
+            ]
+        );
+        // 目標のトピックは目標のデモまで
+        let Step::Study { demos, .. } = &plan.steps[8] else {
+            panic!("デモの実行ではありません");
+        };
+        assert_eq!(demos.last().unwrap().name, "command_registry");
+
+        let mut progress = Progress::default();
+        progress.finished.insert("basics");
+        progress.finished.insert("ownership");
+        progress.quiz_accuracy.insert("basics", 1.0);
+        progress.quiz_accuracy.insert("ownership", 0.4);
+        progress
+            .demo_runs
+            .insert("structs_enums::basic_structs".to_string(), 2);
+        let plan = build_plan(goal, &progress, Level::Advanced, &count);
+        assert_eq!(plan.done.len(), 1);
+        assert_eq!(plan.done[0].key, "basics");
+        assert_eq!(keys(&plan)[..2], ["quiz ownership", "structs_enums"]);
+        let Step::Study { demos, .. } = &plan.steps[1] else {
+            panic!("デモの実行ではありません");
+        };
+        assert!(demos.iter().all(|demo| demo.name != "basic_structs"));
+    }
+}
@@ -0,0 +1,341 @@
+// ============================================================================
+// ミニプロジェクト: Todo リスト
+// ============================================================================
+//
+// 構造体・コレクション・エラー処理・ファイル入出力を1つのアプリにまとめる。
+//
+//   todo add 牛乳を買う     → 追加
+//   todo list               → 一覧
+//   todo done 1             → 完了にする
+//   todo remove 1           → 削除
+//
+// データは学習データの保存先（storage.rs）に todo.json として保存する。
+// JSON の読み書きは外部クレートを使わずに json.rs で手書きしている。
+
+use crate::i18n::{t, tf};
+use crate::json::{self, Json};
+use crate::storage;
+use std::fmt;
+use std::io;
+
+const TODO_FILE: &str = "todo.json";
+
+/// Todo の1項目
+#[derive(Debug, Clone, PartialEq)]
+pub struct TodoItem {
+    pub id: u32,
+    pub title: String,
+    pub done: bool,
+}
+
+/// Todo リスト全体
+#[derive(Debug, PartialEq)]
+pub struct TodoList {
+    items: Vec<TodoItem>,
+    next_id: u32,
+}
+
+/// Todo 操作のエラー
+#[derive(Debug)]
+pub enum TodoError {
+    EmptyTitle,
+    NotFound(u32),
+    AlreadyDone(u32),
+    InvalidId(String),
+    /// 保存ファイルが壊れている
+    Corrupted(String),
+    Io(io::Error),
+}
+
+impl fmt::Display for TodoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TodoError::EmptyTitle => write!(f, "{}", t("todo.empty_title")),
+            TodoError::NotFound(id) => write!(f, "{}", tf("todo.not_found", &[("id", id)])),
+            TodoError::AlreadyDone(id) => {
+                write!(f, "{}", tf("todo.already_done", &[("id", id)]))
+            }
+            TodoError::InvalidId(text) => write!(
+                f,
+                "{}",
+                tf("todo.invalid_id", &[("text", &format!("{:?}", text))])
+            ),
+            TodoError::Corrupted(reason) => write!(
+                f,
+                "{}",
+                tf(
+                    "todo.corrupted",
+                    &[("file", &TODO_FILE), ("reason", reason)]
+                )
+            ),
+            TodoError::Io(e) => write!(f, "{}", tf("todo.io", &[("error", e)])),
+        }
+    }
+}
+
+impl std::error::Error for TodoError {}
+
+// ? 演算子で io::Error を TodoError に自動変換できるようにする
+impl From<io::Error> for TodoError {
+    fn from(e: io::Error) -> Self {
+        TodoError::Io(e)
+    }
+}
+
+impl TodoList {
+    pub fn new() -> Self {
+        TodoList {
+            items: Vec::new(),
+            next_id: 1,
+        }
+    }
+
+    pub fn items(&self) -> &[TodoItem] {
+        &self.items
+    }
+
+    /// 追加して、振った番号を返す
+    pub fn add(&mut self, title: &str) -> Result<u32, TodoError> {
+        let title = title.trim();
+        if title.is_empty() {
+            return Err(TodoError::EmptyTitle);
+        }
+        let id = self.next_id;
+        self.next_id += 1;
+        self.items.push(TodoItem {
+            id,
+            title: title.to_string(),
+            done: false,
+        });
+        Ok(id)
+    }
+
+    pub fn complete(&mut self, id: u32) -> Result<&TodoItem, TodoError> {
+        let item = self
+            .items
+            .iter_mut()
+            .find(|item| item.id == id)
+            .ok_or(TodoError::NotFound(id))?;
+        if item.done {
+            return Err(TodoError::AlreadyDone(id));
+        }
+        item.done = true;
+        Ok(item)
+    }
+
+    pub fn remove(&mut self, id: u32) -> Result<TodoItem, TodoError> {
+        let index = self
+            .items
+            .iter()
+            .position(|item| item.id == id)
+            .ok_or(TodoError::NotFound(id))?;
+        Ok(self.items.remove(index))
+    }
+
+    /// 保存済みのリストを読み込む（まだ無ければ空のリスト）
+    pub fn load() -> Result<TodoList, TodoError> {
+        match storage::load_text(TODO_FILE)? {
+            Some(text) => TodoList::from_json(&text),
+            None => Ok(TodoList::new()),
+        }
+    }
+
+    pub fn save(&self) -> Result<(), TodoError> {
+        storage::save_text(TODO_FILE, &self.to_json())?;
+        Ok(())
+    }
+
+    /// JSON に変換する
+    pub fn to_json(&self) -> String {
+        let mut json = format!("{{\n  \"next_id\": {},\n  \"items\": [", self.next_id);
+        for (i, item) in self.items.iter().enumerate() {
+            json.push_str(if i == 0 { "\n" } else { ",\n" });
+            json.push_str(&format!(
+                "    {{ \"id\": {}, \"title\": {}, \"done\": {} }}",
+                item.id,
+                json::quote(&item.title),
+                item.done
+            ));
+        }
+        json.push_str(if self.items.is_empty() {
+            "]\n}\n"
+        } else {
+            "\n  ]\n}\n"
+        });
+        json
+    }
+
+    /// JSON から復元する
+    pub fn from_json(text: &str) -> Result<TodoList, TodoError> {
+        let corrupted = |reason: &str| TodoError::Corrupted(reason.to_string());
+
+        let root = Json::parse(text).map_err(|e| corrupted(&e.to_string()))?;
+        let mut root = root
+            .into_object()
+            .ok_or_else(|| corrupted(t("todo.corrupted_root")))?;
+        let items = root
+            .remove("items")
+            .and_then(Json::into_array)
+            .ok_or_else(|| corrupted(t("todo.corrupted_items")))?;
+
+        let mut list = TodoList::new();
+        for item in items {
+            let mut fields = item
+                .into_object()
+                .ok_or_else(|| corrupted(t("todo.corrupted_item")))?;
+            let id = fields.remove("id").and_then(Json::into_u32);
+            let title = fields.remove("title").and_then(Json::into_string);
+            let done = fields.remove("done").and_then(Json::into_bool);
+            match (id, title, done) {
+                (Some(id), Some(title), Some(done)) => {
+                    list.items.push(TodoItem { id, title, done })
+                }
+                _ => return Err(corrupted(t("todo.corrupted_fields"))),
+            }
+        }
+
+        // next_id が無い・小さすぎる場合も、既存の番号と重ならないようにする
+        let max_id = list.items.iter().map(|item| item.id).max().unwrap_or(0);
+        let next_id = root.remove("next_id").and_then(Json::into_u32).unwrap_or(0);
+        list.next_id = next_id.max(max_id + 1);
+        Ok(list)
+    }
+}
+
+impl Default for TodoList {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ----------------------------------------------------------------------------
+// コマンド
+// ----------------------------------------------------------------------------
+
+/// `todo <サブコマンド>`: Todo リストを操作する
+pub fn run(arg: &str) {
+    if let Err(e) = execute(arg) {
+        println!("{}", tf("todo.error", &[("error", &e)]));
+    }
+}
+
+fn execute(arg: &str) -> Result<(), TodoError> {
+    let (command, rest) = match arg.split_once(char::is_whitespace) {
+        Some((command, rest)) => (command, rest.trim()),
+        None => (arg, ""),
+    };
+    let parse_id = |text: &str| {
+        text.trim_start_matches('#')
+            .parse::<u32>()
+            .map_err(|_| TodoError::InvalidId(text.to_string()))
+    };
+
+    let mut list = TodoList::load()?;
+    match command {
+        "" | "list" => {
+            print_list(&list);
+            return Ok(());
+        }
+        "add" => {
+            let id = list.add(rest)?;
+            println!("{}", tf("todo.added", &[("id", &id), ("title", &rest)]));
+        }
+        "done" => {
+            let item = list.complete(parse_id(rest)?)?;
+            println!(
+                "{}",
+                tf("todo.done", &[("id", &item.id), ("title", &item.title)])
+            );
+        }
+        "remove" | "rm" => {
+            let item = list.remove(parse_id(rest)?)?;
+            println!(
+                "{}",
+                tf("todo.removed", &[("id", &item.id), ("title", &item.title)])
+            );
+        }
+        _ => {
+            println!("{}", t("todo.usage"));
+            return Ok(());
+        }
+    }
+    list.save()
+}
+
+fn print_list(list: &TodoList) {
+    if list.items().is_empty() {
+        println!("{}", t("todo.empty"));
+        return;
+    }
+    let remaining = list.items().iter().filter(|item| !item.done).count();
+    println!(
+        "\n{}",
+        tf(
+            "todo.header",
+            &[("remaining", &remaining), ("total", &list.items().len())]
+        )
+    );
+    for item in list.items() {
+        let mark = if item.done { "[x]" } else { "[ ]" };
+        println!("  {} #{:<3} {}", mark, item.id, item.title);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_complete_remove() {
+        let mut list = TodoList::new();
+        assert_eq!(list.add("  牛乳を買う ").unwrap(), 1);
+        assert_eq!(list.add("本を返す").unwrap(), 2);
+        assert!(matches!(list.add("   "), Err(TodoError::EmptyTitle)));
+
+        assert_eq!(list.complete(1).unwrap().title, "牛乳を買う");
+        assert!(matches!(list.complete(1), Err(TodoError::AlreadyDone(1))));
+        assert!(matches!(list.complete(9), Err(TodoError::NotFound(9))));
+
+        assert_eq!(list.remove(1).unwrap().id, 1);
+        assert_eq!(list.items().len(), 1);
+        // 削除しても番号は使い回さない
+        assert_eq!(list.add("次").unwrap(), 3);
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let mut list = TodoList::new();
+        list.add("\"引用\" と \\ バックスラッシュ").unwrap();
+        list.add("タブ\tと改行\nを含む").unwrap();
+        list.complete(2).unwrap();
+        list.remove(1).unwrap();
+
+        let restored = TodoList::from_json(&list.to_json()).unwrap();
+        assert_eq!(restored, list);
+        assert_eq!(
+            TodoList::from_json(&TodoList::new().to_json()).unwrap(),
+            TodoList::new()
+        );
+    }
+
+    #[test]
+    fn rejects_corrupted_json() {
+        for text in [
+            "",
+            "[]",
+            "{\"items\": [1]}",
+            "{\"items\": [{\"id\": 1}]}",
+            "{\"items\": [",
+        ] {
+            assert!(
+                matches!(TodoList::from_json(text), Err(TodoError::Corrupted(_))),
+                "{}",
+                text
+            );
+        }
+        // next_id が無くても既存の番号から続ける
+        let list =
+            TodoList::from_json(r#"{"items": [{"id": 4, "title": "a", "done": false}]}"#).unwrap();
+        assert_eq!(list.next_id, 5);
+    }
+}
@@ -0,0 +1,140 @@
+// ============================================================================
+// デモの出力をファイルに保存（save）
+// ============================================================================
+//
+// `save <トピック> <ファイル>` で、トピックのデモを実行した出力をファイルに書き出す。
+// シェルのリダイレクト（cargo run > out.txt）だとメニューの表示まで書き込まれて
+// 対話できなくなるので、lesson_api::output::capture で出力だけを受け取る。
+//
+// 拡張子が .md なら Markdown（デモごとの見出しとコードブロック）、それ以外はそのままのテキスト。
+
+use crate::i18n::{t, tf};
+use crate::registry::{self, Topic, TopicText};
+use lesson_api::output;
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// 書き出す形式
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    Text,
+    Markdown,
+}
+
+impl Format {
+    /// 拡張子から形式を決める（.md / .markdown なら Markdown）
+    pub fn from_path(path: &Path) -> Format {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("md") || ext.eq_ignore_ascii_case("markdown") => {
+                Format::Markdown
+            }
+            _ => Format::Text,
+        }
+    }
+}
+
+/// デモの出力を Markdown にする
+/// `=== タイトル ===` の行をデモの見出しにして、その間の出力をコードブロックに入れる。
+/// 枠で囲んだバナーはトピックの見出しと重なるので省く。
+pub fn render_markdown(topic: &Topic, text: &str) -> String {
+    // 出力に ``` が含まれていてもブロックが途中で閉じないようにする
+    let fence = if text.contains("```") { "````" } else { "```" };
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "# {} ({})",
+        topic.display_title(),
+        topic.display_chapter()
+    );
+
+    let mut block: Vec<&str> = Vec::new();
+    let flush = |out: &mut String, block: &mut Vec<&str>| {
+        while block.last().is_some_and(|line| line.trim().is_empty()) {
+            block.pop();
+        }
+        let start = block
+            .iter()
+            .take_while(|line| line.trim().is_empty())
+            .count();
+        if start < block.len() {
+            let _ = writeln!(out, "\n{}text", fence);
+            for line in &block[start..] {
+                let _ = writeln!(out, "{}", line);
+            }
+            let _ = writeln!(out, "{}", fence);
+        }
+        block.clear();
+    };
+
+    for line in text.lines() {
+        if line.starts_with(['╔', '║', '╚']) {
+            continue;
+        }
+        match line
+            .strip_prefix("=== ")
+            .and_then(|rest| rest.strip_suffix(" ==="))
+        {
+            Some(title) => {
+                flush(&mut out, &mut block);
+                let _ = writeln!(out, "\n## {}", title);
+            }
+            None => block.push(line),
+        }
+    }
+    flush(&mut out, &mut block);
+    out
+}
+
+/// トピックを実行し、その出力をファイルに書き出す
+pub fn save(topic: &Topic, path: &Path) -> io::Result<Format> {
+    let text = output::capture(topic.run_all);
+    let format = Format::from_path(path);
+    let body = match format {
+        Format::Text => text,
+        Format::Markdown => render_markdown(topic, &text),
+    };
+    fs::write(path, body)?;
+    Ok(format)
+}
+
+/// `save <トピック> <ファイル>`（トピックは番号か名前。名前に空白があってもよい）
+pub fn run(arg: &str) {
+    let Some((name, file)) = arg.trim().rsplit_once(char::is_whitespace) else {
+        println!("{}", t("save.usage"));
+        return;
+    };
+    let Some(topic) = registry::resolve_topic(name.trim()) else {
+        println!("{}", tf("main.unknown_topic", &[("name", &name.trim())]));
+        return;
+    };
+
+    let path = Path::new(file);
+    let saved = |key| tf(key, &[("title", &topic.display_title()), ("path", &file)]);
+    match save(topic, path) {
+        Ok(Format::Text) => println!("{}", saved("save.written")),
+        Ok(Format::Markdown) => println!("{}", saved("save.written_markdown")),
+        Err(e) => println!("{}", tf("main.write_failed", &[("error", &e)])),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn markdown_turns_demo_titles_into_headings() {
+        let topic = registry::find_topic("ownership").unwrap();
+        let text = "╔══╗\n║ 所有権 ║\n╚══╝\n\n=== ムーブ ===\ns1 = hello\n\n=== 空 ===\n\n=== 借用 ===\nlen = 5\n";
+        let markdown = render_markdown(topic, text);
+        assert!(markdown.starts_with("# "));
+        assert!(markdown.contains("\n## ムーブ\n\n```text\ns1 = hello\n```\n"));
+        assert!(markdown.contains("\n## 空\n\n## 借用\n"));
+        assert!(!markdown.contains('║'));
+
+        assert_eq!(Format::from_path(Path::new("out.MD")), Format::Markdown);
+        assert_eq!(Format::from_path(Path::new("out.txt")), Format::Text);
+        assert_eq!(Format::from_path(Path::new("out")), Format::Text);
+    }
+}
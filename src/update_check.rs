@@ -0,0 +1,370 @@
+// ============================================================================
+// 更新の確認（check-update、feature "update-check"）
+// ============================================================================
+//
+// リリースの API に問い合わせて、新しいバージョンのサンプルが出ているかを知らせる。
+// ネットワークに接続するコードは、cargo run --features update-check で
+// ビルドしたときだけ入る（既定のビルドは通信しない）。
+//
+// 小さな実例として、HTTP/1.1 のやり取りを標準ライブラリだけで書いている:
+//
+//   GET /repos/godslew/gkRustPractice/releases/latest HTTP/1.1   ← リクエスト行
+//   Host: api.github.com                                         ← ヘッダ
+//   User-Agent: gkRustPractice/0.1.0
+//   Connection: close
+//                                                                ← 空行
+//   HTTP/1.1 200 OK                                              ← ステータス行
+//   Content-Type: application/json
+//   Transfer-Encoding: chunked                                   ← 本文は長さ付きの塊の列
+//
+//   {"tag_name": "v0.2.0", "html_url": "https://...", ...}      ← 本文（json.rs で読む）
+//
+// TLS は標準ライブラリに無いので、https:// の URL は curl コマンドに取得を任せる。
+// 問い合わせ先は環境変数 GKRUST_UPDATE_URL で変えられる（http:// なら自前の HTTP で取得する）。
+
+use crate::i18n::{t, tf};
+use crate::json::Json;
+use std::cmp::Ordering;
+use std::env;
+use std::fmt;
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::process::Command;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// 既定の問い合わせ先（最新のリリース）
+pub const DEFAULT_URL: &str = "https://api.github.com/repos/godslew/gkRustPractice/releases/latest";
+
+/// 接続と読み書きの待ち時間
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+// ----------------------------------------------------------------------------
+// バージョン
+// ----------------------------------------------------------------------------
+
+/// "1.2.3" や "v1.2.3" 形式のバージョン（比べるのは数字の3つだけ）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Version {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl FromStr for Version {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Version, String> {
+        let text = s.trim().trim_start_matches('v');
+        // "1.2.3-beta.1" の後ろは比べない
+        let core = text.split(['-', '+']).next().unwrap_or(text);
+        let numbers: Vec<u32> = core
+            .split('.')
+            .map(|part| part.parse())
+            .collect::<Result<_, _>>()
+            .map_err(|_| tf("update.bad_version", &[("text", &s)]))?;
+        match numbers[..] {
+            [major, minor, patch] => Ok(Version {
+                major,
+                minor,
+                patch,
+            }),
+            [major, minor] => Ok(Version {
+                major,
+                minor,
+                patch: 0,
+            }),
+            _ => Err(tf("update.bad_version", &[("text", &s)])),
+        }
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// 今動いているサンプルのバージョン（Cargo.toml の version）
+pub fn current_version() -> Version {
+    env!("CARGO_PKG_VERSION")
+        .parse()
+        .expect("Cargo.toml の version")
+}
+
+// ----------------------------------------------------------------------------
+// リリース情報（JSON）
+// ----------------------------------------------------------------------------
+
+/// リリースの API の応答から使う部分
+#[derive(Debug, PartialEq)]
+pub struct Release {
+    pub version: Version,
+    pub name: Option<String>,
+    pub url: Option<String>,
+}
+
+/// {"tag_name": "v0.2.0", "name": "...", "html_url": "..."} を読む
+pub fn parse_release(body: &str) -> Result<Release, String> {
+    let mut object = Json::parse(body)?
+        .into_object()
+        .ok_or(t("update.response_not_object"))?;
+    let tag = object
+        .remove("tag_name")
+        .and_then(Json::into_string)
+        .ok_or(t("update.missing_tag"))?;
+    Ok(Release {
+        version: tag.parse()?,
+        name: object.remove("name").and_then(Json::into_string),
+        url: object.remove("html_url").and_then(Json::into_string),
+    })
+}
+
+// ----------------------------------------------------------------------------
+// HTTP
+// ----------------------------------------------------------------------------
+
+/// http://host[:port]/path を分ける
+fn split_url(url: &str) -> Result<(&str, u16, &str), String> {
+    let rest = url
+        .strip_prefix("http://")
+        .ok_or_else(|| tf("update.not_http", &[("url", &url)]))?;
+    let (authority, path) = match rest.find('/') {
+        Some(i) => (&rest[..i], &rest[i..]),
+        None => (rest, "/"),
+    };
+    let (host, port) = match authority.rsplit_once(':') {
+        Some((host, port)) => (
+            host,
+            port.parse()
+                .map_err(|_| tf("update.bad_port", &[("port", &port)]))?,
+        ),
+        None => (authority, 80),
+    };
+    Ok((host, port, path))
+}
+
+/// 応答の全体（ステータス行、ヘッダ、本文）から、ステータスコードと本文を取り出す
+pub fn parse_response(raw: &[u8]) -> Result<(u16, String), String> {
+    let split = raw
+        .windows(4)
+        .position(|w| w == b"\r\n\r\n")
+        .ok_or(t("update.unterminated_headers"))?;
+    let head = String::from_utf8_lossy(&raw[..split]);
+    let body = &raw[split + 4..];
+
+    let mut lines = head.split("\r\n");
+    let status_line = lines.next().unwrap_or_default();
+    // "HTTP/1.1 200 OK"
+    let status = status_line
+        .split_whitespace()
+        .nth(1)
+        .and_then(|code| code.parse().ok())
+        .ok_or_else(|| tf("update.bad_status_line", &[("line", &status_line)]))?;
+
+    let chunked = lines.any(|line| {
+        line.split_once(':').is_some_and(|(name, value)| {
+            name.trim().eq_ignore_ascii_case("transfer-encoding")
+                && value.trim().eq_ignore_ascii_case("chunked")
+        })
+    });
+    let body = if chunked {
+        decode_chunked(body)?
+    } else {
+        body.to_vec()
+    };
+    String::from_utf8(body)
+        .map(|body| (status, body))
+        .map_err(|_| t("update.body_not_utf8").to_string())
+}
+
+// "1a\r\n<26バイト>\r\n0\r\n\r\n" のような塊の列をつなげる
+fn decode_chunked(mut data: &[u8]) -> Result<Vec<u8>, String> {
+    let mut body = Vec::new();
+    loop {
+        let line_end = data
+            .windows(2)
+            .position(|w| w == b"\r\n")
+            .ok_or(t("update.unterminated_chunk_size"))?;
+        let size_text = String::from_utf8_lossy(&data[..line_end]);
+        // "1a;name=value" のような拡張は無視する
+        let size_text = size_text.split(';').next().unwrap_or_default().trim();
+        let size = usize::from_str_radix(size_text, 16)
+            .map_err(|_| tf("update.bad_chunk_size", &[("size", &size_text)]))?;
+        data = &data[line_end + 2..];
+        if size == 0 {
+            return Ok(body);
+        }
+        let chunk = data.get(..size).ok_or(t("update.truncated_chunk"))?;
+        body.extend_from_slice(chunk);
+        data = data.get(size + 2..).unwrap_or_default();
+    }
+}
+
+// http:// の URL を自前の HTTP/1.1 で取得する
+fn http_get(url: &str) -> Result<String, String> {
+    let (host, port, path) = split_url(url)?;
+    let error = |e: std::io::Error| {
+        tf(
+            "update.connect_failed",
+            &[("host", &host), ("port", &port), ("error", &e)],
+        )
+    };
+
+    let mut stream = TcpStream::connect((host, port)).map_err(error)?;
+    stream.set_read_timeout(Some(TIMEOUT)).map_err(error)?;
+    stream.set_write_timeout(Some(TIMEOUT)).map_err(error)?;
+    // Connection: close なので、相手が閉じるまで読めば応答の全体になる
+    write!(
+        stream,
+        "GET {} HTTP/1.1\r\nHost: {}\r\nUser-Agent: {}\r\nAccept: application/json\r\nConnection: close\r\n\r\n",
+        path,
+        host,
+        user_agent()
+    )
+    .map_err(error)?;
+    let mut raw = Vec::new();
+    stream.read_to_end(&mut raw).map_err(error)?;
+
+    match parse_response(&raw)? {
+        (200, body) => Ok(body),
+        (status, _) => Err(tf("update.bad_status", &[("status", &status)])),
+    }
+}
+
+// https:// の URL は curl に任せる（-f: 4xx/5xx を失敗にする）
+fn curl_get(url: &str) -> Result<String, String> {
+    let output = Command::new("curl")
+        .args(["-fsSL", "--max-time", &TIMEOUT.as_secs().to_string()])
+        .args(["-H", &format!("User-Agent: {}", user_agent())])
+        .args(["-H", "Accept: application/json"])
+        .arg(url)
+        .output()
+        .map_err(|e| tf("update.curl_failed", &[("error", &e)]))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(tf("update.fetch_failed", &[("error", &stderr.trim())]));
+    }
+    String::from_utf8(output.stdout).map_err(|_| t("update.response_not_utf8").to_string())
+}
+
+// GitHub の API は User-Agent の無いリクエストを断る
+fn user_agent() -> String {
+    format!("gkRustPractice/{}", env!("CARGO_PKG_VERSION"))
+}
+
+fn fetch(url: &str) -> Result<String, String> {
+    if url.starts_with("https://") {
+        curl_get(url)
+    } else {
+        http_get(url)
+    }
+}
+
+// ----------------------------------------------------------------------------
+// コマンド
+// ----------------------------------------------------------------------------
+
+/// `check-update`: 最新のリリースを調べて、今のバージョンと比べる
+pub fn run() {
+    let url = env::var("GKRUST_UPDATE_URL").unwrap_or_else(|_| DEFAULT_URL.to_string());
+    let current = current_version();
+    println!("{}", tf("update.checking", &[("url", &url)]));
+
+    let release = match fetch(&url).and_then(|body| parse_release(&body)) {
+        Ok(release) => release,
+        Err(e) => {
+            println!("{}", tf("update.check_failed", &[("error", &e)]));
+            return;
+        }
+    };
+    match release.version.cmp(&current) {
+        Ordering::Greater => {
+            println!(
+                "{}",
+                tf(
+                    "update.available",
+                    &[("latest", &release.version), ("current", &current)]
+                )
+            );
+            if let Some(name) = &release.name {
+                println!("  {}", name);
+            }
+            if let Some(url) = &release.url {
+                println!("  {}", url);
+            }
+            println!("  {}", t("update.how_to_update"));
+        }
+        Ordering::Equal => println!("{}", tf("update.up_to_date", &[("current", &current)])),
+        Ordering::Less => println!(
+            "{}",
+            tf(
+                "update.ahead",
+                &[("latest", &release.version), ("current", &current)]
+            )
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+    use std::thread;
+
+    #[test]
+    fn parses_versions_and_releases() {
+        let v = |s: &str| s.parse::<Version>().unwrap();
+        assert!(v("v0.2.0") > v("0.1.9"));
+        assert!(v("1.10.0") > v("1.9.3"));
+        assert_eq!(v("v1.2"), v("1.2.0"));
+        assert_eq!(v("1.2.3-beta.1"), v("1.2.3"));
+        assert!("latest".parse::<Version>().is_err());
+
+        let release =
+            parse_release(r#"{"tag_name": "v0.3.1", "name": "秋の更新", "draft": false}"#).unwrap();
+        assert_eq!(release.version, v("0.3.1"));
+        assert_eq!(release.name.as_deref(), Some("秋の更新"));
+        assert_eq!(release.url, None);
+        assert!(parse_release(r#"{"message": "Not Found"}"#).is_err());
+    }
+
+    #[test]
+    fn decodes_chunked_responses() {
+        let raw = b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n5\r\nhello\r\n7;x=1\r\n, world\r\n0\r\n\r\n";
+        assert_eq!(parse_response(raw), Ok((200, "hello, world".to_string())));
+        let raw = b"HTTP/1.1 404 Not Found\r\nContent-Length: 2\r\n\r\n{}";
+        assert_eq!(parse_response(raw), Ok((404, "{}".to_string())));
+        assert!(parse_response(b"HTTP/1.1 200 OK\r\n").is_err());
+    }
+
+    #[test]
+    fn fetches_over_http_from_a_local_server() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0; 512];
+            while !request.ends_with(b"\r\n\r\n") {
+                let n = stream.read(&mut buf).unwrap();
+                request.extend_from_slice(&buf[..n]);
+            }
+            let body = r#"{"tag_name": "v9.0.0"}"#;
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n{}",
+                body.len(),
+                body
+            )
+            .unwrap();
+            String::from_utf8(request).unwrap()
+        });
+
+        let body = fetch(&format!("http://127.0.0.1:{}/releases/latest", port)).unwrap();
+        assert_eq!(parse_release(&body).unwrap().version.major, 9);
+        let request = server.join().unwrap();
+        assert!(request.starts_with("GET /releases/latest HTTP/1.1\r\n"));
+        assert!(request.contains("User-Agent: gkRustPractice/"));
+    }
+}
@@ -0,0 +1,340 @@
+// ============================================================================
+// 利用統計（ローカルのみ・オプトイン）
+// ============================================================================
+//
+// `stats on` で記録を始めると、次のことを学習データの保存先の usage.tsv に残す。
+//   - デモごとの実行回数
+//   - トピックごとの滞在時間（トピックを実行してから次のコマンドを入力するまで。
+//     レッスンプランでは項目の実行から、後の間（pause）が終わるまで。
+//     「0」ですべて実行したときは、実行したトピックで等分する）
+//
+// 記録はこのコンピュータの中だけに保存し、どこにも送信しない。
+// `stats` で棒グラフを表示、`stats off` で記録を止め、`stats reset` で削除する。
+
+use crate::i18n::{t, tf};
+use crate::quiz_results;
+use crate::registry::{self, TopicText};
+use crate::storage;
+use lesson_api::text;
+use std::collections::BTreeMap;
+use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+const USAGE_FILE: &str = "usage.tsv";
+
+/// 1回の滞在として数える上限（席を離れていた時間を数えすぎないように）
+pub const MAX_VISIT: Duration = Duration::from_secs(30 * 60);
+
+/// 棒グラフに並べるデモの数
+const TOP_DEMOS: usize = 10;
+
+/// 集計した利用状況
+#[derive(Debug, Default, PartialEq)]
+pub struct Usage {
+    pub enabled: bool,
+    /// "トピック::デモ名" → 実行回数
+    pub demo_runs: BTreeMap<String, u64>,
+    /// トピックのキー → 滞在時間（秒）
+    pub topic_secs: BTreeMap<String, u64>,
+}
+
+impl Usage {
+    const fn new() -> Usage {
+        Usage {
+            enabled: false,
+            demo_runs: BTreeMap::new(),
+            topic_secs: BTreeMap::new(),
+        }
+    }
+
+    /// usage.tsv のレコードから復元する（読めない行は無視する）
+    pub fn from_records(records: &[Vec<String>]) -> Usage {
+        let mut usage = Usage::new();
+        for record in records {
+            match record.as_slice() {
+                [kind, value] if kind == "enabled" => usage.enabled = value == "1",
+                [kind, key, n] => {
+                    let Ok(n) = n.parse::<u64>() else { continue };
+                    match kind.as_str() {
+                        "demo" => *usage.demo_runs.entry(key.clone()).or_default() += n,
+                        "topic" => *usage.topic_secs.entry(key.clone()).or_default() += n,
+                        _ => {}
+                    }
+                }
+                _ => {}
+            }
+        }
+        usage
+    }
+
+    pub fn to_records(&self) -> Vec<Vec<String>> {
+        let flag = if self.enabled { "1" } else { "0" };
+        let mut records = vec![vec!["enabled".to_string(), flag.to_string()]];
+        for (kind, map) in [("demo", &self.demo_runs), ("topic", &self.topic_secs)] {
+            for (key, n) in map {
+                records.push(vec![kind.to_string(), key.clone(), n.to_string()]);
+            }
+        }
+        records
+    }
+
+    /// 別の集計（このセッションの分）を足し込む
+    pub fn merge(&mut self, other: &Usage) {
+        for (key, n) in &other.demo_runs {
+            *self.demo_runs.entry(key.clone()).or_default() += n;
+        }
+        for (key, n) in &other.topic_secs {
+            *self.topic_secs.entry(key.clone()).or_default() += n;
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.demo_runs.is_empty() && self.topic_secs.is_empty()
+    }
+}
+
+// 記録するかどうか（起動時に usage.tsv から読み込む）
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+// まだ保存していないこのセッションの記録
+static PENDING: Mutex<Usage> = Mutex::new(Usage::new());
+
+fn load() -> io::Result<Usage> {
+    Ok(Usage::from_records(&storage::load_records(USAGE_FILE)?))
+}
+
+/// 記録の設定を読み込む（main の最初に呼ぶ）
+pub fn init() {
+    let enabled = load().map(|usage| usage.enabled).unwrap_or(false);
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// デモを1回実行したことを記録する（registry::run_demos から呼ばれる）
+pub fn record_demo(topic: &str, demo: &str) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    let mut pending = PENDING.lock().unwrap_or_else(|e| e.into_inner());
+    *pending
+        .demo_runs
+        .entry(format!("{}::{}", topic, demo))
+        .or_default() += 1;
+}
+
+/// トピックに滞在した時間を記録する（MAX_VISIT で打ち切る）
+pub fn record_time(topic: &str, elapsed: Duration) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    let secs = elapsed.min(MAX_VISIT).as_secs();
+    if secs == 0 {
+        return;
+    }
+    let mut pending = PENDING.lock().unwrap_or_else(|e| e.into_inner());
+    *pending.topic_secs.entry(topic.to_string()).or_default() += secs;
+}
+
+/// 複数のトピックをまとめて実行したとき（メニューの「0」）の滞在時間を等分して記録する
+pub fn record_shared_time(topics: &[&str], elapsed: Duration) {
+    for topic in topics {
+        record_time(topic, share_of_visit(elapsed, topics.len()));
+    }
+}
+
+/// 1回の滞在（MAX_VISIT で打ち切る）を n 個のトピックで等分した時間
+fn share_of_visit(elapsed: Duration, n: usize) -> Duration {
+    let n = u32::try_from(n.max(1)).unwrap_or(u32::MAX);
+    elapsed.min(MAX_VISIT) / n
+}
+
+/// このセッションの記録をファイルに足し込む
+pub fn flush() {
+    let mut pending = PENDING.lock().unwrap_or_else(|e| e.into_inner());
+    if pending.is_empty() || !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    let result = load().and_then(|mut usage| {
+        usage.merge(&pending);
+        storage::save_records(USAGE_FILE, &usage.to_records())
+    });
+    match result {
+        Ok(()) => *pending = Usage::new(),
+        Err(e) => eprintln!("{}", tf("stats.save_failed", &[("error", &e)])),
+    }
+}
+
+/// 保存してある記録に、まだ保存していないこのセッションの分を足したもの
+pub fn recorded() -> Usage {
+    let mut usage = load().unwrap_or_default();
+    usage.merge(&PENDING.lock().unwrap_or_else(|e| e.into_inner()));
+    usage
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+// 記録の有効・無効を切り替える（集計済みのデータは残す）
+fn set_enabled(enabled: bool) -> io::Result<()> {
+    let mut usage = load()?;
+    usage.enabled = enabled;
+    storage::save_records(USAGE_FILE, &usage.to_records())?;
+    ENABLED.store(enabled, Ordering::Relaxed);
+    Ok(())
+}
+
+/// `stats [on|off|reset]`
+pub fn run(arg: &str) {
+    let result = match arg {
+        "" => {
+            print_stats();
+            Ok(())
+        }
+        "on" => set_enabled(true).map(|()| {
+            println!("{}", t("stats.enabled"));
+        }),
+        "off" => set_enabled(false).map(|()| {
+            println!("{}", t("stats.disabled"));
+        }),
+        "reset" => {
+            let enabled = ENABLED.load(Ordering::Relaxed);
+            *PENDING.lock().unwrap_or_else(|e| e.into_inner()) = Usage::new();
+            let usage = Usage {
+                enabled,
+                ..Usage::new()
+            };
+            storage::save_records(USAGE_FILE, &usage.to_records())
+                .map(|()| println!("{}", t("stats.reset")))
+        }
+        _ => {
+            println!("{}", t("stats.usage"));
+            Ok(())
+        }
+    };
+    if let Err(e) = result {
+        println!("{}", tf("stats.save_failed", &[("error", &e)]));
+    }
+}
+
+fn print_stats() {
+    flush();
+    let usage = load().unwrap_or_default();
+    let state = if usage.enabled {
+        t("stats.recording")
+    } else {
+        t("stats.stopped")
+    };
+    println!("\n{}", tf("stats.header", &[("state", &state)]));
+    if usage.is_empty() {
+        if usage.enabled {
+            println!("{}", t("stats.empty"));
+        } else {
+            println!("{}", t("stats.off_hint"));
+        }
+        return;
+    }
+
+    if !usage.topic_secs.is_empty() {
+        println!("\n{}", t("stats.time_per_topic"));
+        let max = usage.topic_secs.values().copied().max().unwrap_or(0).max(1);
+        let mut topics: Vec<(&String, &u64)> = usage.topic_secs.iter().collect();
+        topics.sort_by(|a, b| b.1.cmp(a.1));
+        for (key, &secs) in topics {
+            let topic = registry::find_topic(key);
+            // 目安はメニューと同じもの（いまの難易度で表示されるデモの合計）
+            let estimate = topic.map_or(String::new(), |topic| {
+                tf("menu.estimate", &[("minutes", &topic.estimated_minutes())])
+            });
+            println!(
+                "  {:<20} {} {:>6} {} {}",
+                key,
+                quiz_results::bar(secs as f64 / max as f64, 20),
+                format_duration(secs),
+                text::pad_right(&estimate, 12),
+                topic.map_or(key.as_str(), |topic| topic.display_title())
+            );
+        }
+    }
+
+    if !usage.demo_runs.is_empty() {
+        let total: u64 = usage.demo_runs.values().sum();
+        println!("\n{}", tf("stats.top_demos", &[("total", &total)]));
+        let max = usage.demo_runs.values().copied().max().unwrap_or(0).max(1);
+        let mut demos: Vec<(&String, &u64)> = usage.demo_runs.iter().collect();
+        demos.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+        for (id, &runs) in demos.iter().take(TOP_DEMOS) {
+            println!(
+                "  {:<40} {} {}",
+                id,
+                quiz_results::bar(runs as f64 / max as f64, 20),
+                tf("stats.runs", &[("runs", &format!("{:>4}", runs))])
+            );
+        }
+        if demos.len() > TOP_DEMOS {
+            println!(
+                "  {}",
+                tf("stats.more_demos", &[("count", &(demos.len() - TOP_DEMOS))])
+            );
+        }
+    }
+    println!();
+}
+
+// 秒を "1h05m" / "12m30s" / "45s" の形にする
+fn format_duration(secs: u64) -> String {
+    match secs {
+        0..=59 => format!("{}s", secs),
+        60..=3599 => format!("{}m{:02}s", secs / 60, secs % 60),
+        _ => format!("{}h{:02}m", secs / 3600, secs % 3600 / 60),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_round_trip_and_merge() {
+        let records: Vec<Vec<String>> = [
+            "enabled\t1",
+            "demo\townership::slices\t3",
+            "topic\townership\t120",
+            "demo\tbroken",
+            "topic\tlifetimes\tabc",
+        ]
+        .iter()
+        .map(|line| line.split('\t').map(str::to_string).collect())
+        .collect();
+        let mut usage = Usage::from_records(&records);
+        assert!(usage.enabled);
+        assert_eq!(usage.demo_runs.len(), 1);
+        assert_eq!(usage.topic_secs.len(), 1);
+        assert_eq!(Usage::from_records(&usage.to_records()), usage);
+
+        let mut session = Usage::new();
+        session.demo_runs.insert("ownership::slices".to_string(), 2);
+        session.topic_secs.insert("basics".to_string(), 30);
+        usage.merge(&session);
+        assert_eq!(usage.demo_runs["ownership::slices"], 5);
+        assert_eq!(usage.topic_secs["basics"], 30);
+        assert_eq!(usage.topic_secs["ownership"], 120);
+    }
+
+    #[test]
+    fn shares_one_visit_between_topics() {
+        let minute = Duration::from_secs(60);
+        assert_eq!(share_of_visit(minute, 3), Duration::from_secs(20));
+        assert_eq!(share_of_visit(minute, 0), minute);
+        // 席を離れていた分まで等分しない
+        assert_eq!(share_of_visit(MAX_VISIT * 4, 2), MAX_VISIT / 2);
+    }
+
+    #[test]
+    fn formats_durations() {
+        assert_eq!(format_duration(45), "45s");
+        assert_eq!(format_duration(750), "12m30s");
+        assert_eq!(format_duration(3900), "1h05m");
+    }
+}
@@ -0,0 +1,369 @@
+// ============================================================================
+// ミニプロジェクト: マルチスレッドの Web サーバー
+// 公式ドキュメント: https://doc.rust-lang.org/book/ch21-00-final-project-a-web-server.html
+// ============================================================================
+//
+// The Book の最後のプロジェクト（旧版では第20章）。
+//   - TcpListener で待ち受け、HTTP のリクエスト行を読んで HTML を返す
+//   - シングルスレッド版では、/sleep の遅いリクエストが後ろのリクエストを待たせる
+//   - スレッドプール（ThreadPool）に仕事を渡すと、遅いリクエストがあってもほかを返せる
+//   - ThreadPool の Drop で送信側を閉じ、各ワーカーの終了を join で待つ（グレースフルシャットダウン）
+//
+//   serve [回数]   http://127.0.0.1:7878 で待ち受け、指定した回数（既定 5）のリクエストの後に止まる
+//   serve compare  シングルスレッド版とスレッドプール版で、遅いリクエストの影響を比べる
+
+use crate::i18n::{t, tf};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// 待ち受けるアドレス（The Book と同じポート）
+pub const ADDR: &str = "127.0.0.1:7878";
+
+/// serve で止まるまでのリクエスト数の既定値
+const DEFAULT_REQUESTS: usize = 5;
+
+/// /sleep が待つ時間
+const SLEEP: Duration = Duration::from_secs(5);
+
+const HELLO_HTML: &str = r#"<!DOCTYPE html>
+<html lang="ja">
+  <head>
+    <meta charset="utf-8">
+    <title>Hello!</title>
+  </head>
+  <body>
+    <h1>Hello!</h1>
+    <p>Hi from Rust</p>
+  </body>
+</html>
+"#;
+
+const NOT_FOUND_HTML: &str = r#"<!DOCTYPE html>
+<html lang="ja">
+  <head>
+    <meta charset="utf-8">
+    <title>Hello!</title>
+  </head>
+  <body>
+    <h1>Oops!</h1>
+    <p>Sorry, I don't know what you're asking for.</p>
+  </body>
+</html>
+"#;
+
+// ----------------------------------------------------------------------------
+// リクエストの処理
+// ----------------------------------------------------------------------------
+
+/// 1つの接続を処理する。戻り値は読んだリクエスト行
+pub fn handle_connection(mut stream: TcpStream, sleep: Duration) -> io::Result<String> {
+    // ヘッダーの終わり（空行）まで読む。読み残すと、閉じたときに接続がリセットされることがある
+    let buf_reader = BufReader::new(&stream);
+    let mut lines = buf_reader.lines();
+    let request_line = lines.next().transpose()?.unwrap_or_default();
+    for line in lines {
+        if line?.is_empty() {
+            break;
+        }
+    }
+
+    let (status_line, contents) = match &request_line[..] {
+        "GET / HTTP/1.1" => ("HTTP/1.1 200 OK", HELLO_HTML),
+        "GET /sleep HTTP/1.1" => {
+            // 遅い処理のまね
+            thread::sleep(sleep);
+            ("HTTP/1.1 200 OK", HELLO_HTML)
+        }
+        _ => ("HTTP/1.1 404 NOT FOUND", NOT_FOUND_HTML),
+    };
+
+    let length = contents.len();
+    let response = format!(
+        "{}\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\n\r\n{}",
+        status_line, length, contents
+    );
+    stream.write_all(response.as_bytes())?;
+    Ok(request_line)
+}
+
+/// path に GET を送り、レスポンス全体を返す（ブラウザや curl の代わり）
+pub fn get(addr: SocketAddr, path: &str) -> io::Result<String> {
+    let mut stream = TcpStream::connect(addr)?;
+    write!(stream, "GET {} HTTP/1.1\r\nHost: {}\r\n\r\n", path, addr)?;
+    let mut response = String::new();
+    stream.read_to_string(&mut response)?;
+    Ok(response)
+}
+
+// ----------------------------------------------------------------------------
+// スレッドプール
+// ----------------------------------------------------------------------------
+
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+/// 決まった数のスレッドを先に作っておき、仕事（クロージャ）を順に渡す
+pub struct ThreadPool {
+    workers: Vec<Worker>,
+    // Drop で先に閉じられるよう Option にしておく
+    sender: Option<mpsc::Sender<Job>>,
+}
+
+impl ThreadPool {
+    /// size 個のスレッドを持つプールを作る
+    ///
+    /// # Panics
+    ///
+    /// size が 0 のとき
+    pub fn new(size: usize) -> ThreadPool {
+        assert!(size > 0);
+
+        let (sender, receiver) = mpsc::channel();
+        // 受信側は1つしか作れないので、Arc<Mutex<..>> にしてワーカーで共有する
+        let receiver = Arc::new(Mutex::new(receiver));
+
+        let workers = (0..size)
+            .map(|id| Worker::new(id, Arc::clone(&receiver)))
+            .collect();
+
+        ThreadPool {
+            workers,
+            sender: Some(sender),
+        }
+    }
+
+    /// 空いているワーカーに f を実行させる（thread::spawn と同じ境界）
+    pub fn execute<F>(&self, f: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        let job = Box::new(f);
+        self.sender.as_ref().unwrap().send(job).unwrap();
+    }
+}
+
+impl Drop for ThreadPool {
+    fn drop(&mut self) {
+        // 送信側を閉じると、ワーカーの recv が Err を返してループを抜ける
+        drop(self.sender.take());
+
+        for worker in self.workers.drain(..) {
+            println!("{}", tf("serve.worker_stopping", &[("id", &worker.id)]));
+            worker.thread.join().unwrap();
+        }
+    }
+}
+
+struct Worker {
+    id: usize,
+    thread: thread::JoinHandle<()>,
+}
+
+impl Worker {
+    fn new(id: usize, receiver: Arc<Mutex<mpsc::Receiver<Job>>>) -> Worker {
+        let thread = thread::spawn(move || loop {
+            // lock() の一時的な MutexGuard は let の文の終わりで解放される。
+            // while let Ok(job) = receiver.lock().unwrap().recv() と書くと、
+            // job を実行している間もロックを持ち続けてしまう
+            let message = receiver.lock().unwrap().recv();
+
+            match message {
+                Ok(job) => {
+                    println!("{}", tf("serve.worker_got_job", &[("id", &id)]));
+                    job();
+                }
+                Err(_) => {
+                    println!("{}", tf("serve.worker_exiting", &[("id", &id)]));
+                    break;
+                }
+            }
+        });
+
+        Worker { id, thread }
+    }
+}
+
+// ----------------------------------------------------------------------------
+// サーバー
+// ----------------------------------------------------------------------------
+
+/// シングルスレッド版: 1つずつ順に処理する。requests 回処理したら戻る
+pub fn serve_single(listener: &TcpListener, requests: usize, sleep: Duration) {
+    for stream in listener.incoming().take(requests) {
+        match stream.and_then(|stream| handle_connection(stream, sleep)) {
+            Ok(request_line) => println!("{}", tf("serve.handled", &[("request", &request_line)])),
+            Err(e) => println!("{}", tf("serve.connection_error", &[("error", &e)])),
+        }
+    }
+}
+
+/// スレッドプール版: 接続ごとの処理をプールに渡す。requests 回受け付けたら、
+/// プールを drop して処理中のリクエストが終わるのを待ってから戻る
+pub fn serve_pool(listener: &TcpListener, requests: usize, workers: usize, sleep: Duration) {
+    let pool = ThreadPool::new(workers);
+
+    for stream in listener.incoming().take(requests) {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                println!("{}", tf("serve.connection_error", &[("error", &e)]));
+                continue;
+            }
+        };
+        pool.execute(move || match handle_connection(stream, sleep) {
+            Ok(request_line) => println!("{}", tf("serve.handled", &[("request", &request_line)])),
+            Err(e) => println!("{}", tf("serve.connection_error", &[("error", &e)])),
+        });
+    }
+
+    println!("{}", tf("serve.stopping", &[("requests", &requests)]));
+}
+
+/// `serve [回数|compare]`
+pub fn run(arg: &str) {
+    if arg == "compare" {
+        compare();
+        return;
+    }
+    let requests = if arg.is_empty() {
+        DEFAULT_REQUESTS
+    } else {
+        match arg.parse::<usize>() {
+            Ok(n) if n > 0 => n,
+            _ => {
+                println!(
+                    "{}",
+                    tf("serve.bad_count", &[("input", &format!("{:?}", arg))])
+                );
+                return;
+            }
+        }
+    };
+
+    println!("\n{}", t("serve.header"));
+    // 7878 が使われていたら、OS に空いているポートを選ばせる
+    let listener = match TcpListener::bind(ADDR).or_else(|_| TcpListener::bind("127.0.0.1:0")) {
+        Ok(listener) => listener,
+        Err(e) => {
+            println!("{}", tf("serve.bind_failed", &[("error", &e)]));
+            return;
+        }
+    };
+    let url = match listener.local_addr() {
+        Ok(addr) => format!("http://{}", addr),
+        Err(_) => format!("http://{}", ADDR),
+    };
+    let workers = 4;
+    println!(
+        "{}",
+        tf("serve.listening", &[("url", &url), ("workers", &workers)])
+    );
+    println!("  {}/       → Hello!", url);
+    println!(
+        "  {}/sleep  → {}",
+        url,
+        tf("serve.sleep_route", &[("seconds", &SLEEP.as_secs())])
+    );
+    println!("  {}        → 404", t("serve.other_routes"));
+    println!("{}", tf("serve.request_limit", &[("requests", &requests)]));
+    println!("{}\n", t("serve.favicon_note"));
+
+    serve_pool(&listener, requests, workers, SLEEP);
+}
+
+/// 遅いリクエストの後ろで、速いリクエストがどれだけ待たされるかを測る
+fn measure(pooled: bool, sleep: Duration) -> io::Result<Duration> {
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let addr = listener.local_addr()?;
+    let server = thread::spawn(move || {
+        if pooled {
+            serve_pool(&listener, 2, 2, sleep);
+        } else {
+            serve_single(&listener, 2, sleep);
+        }
+    });
+
+    let slow = thread::spawn(move || get(addr, "/sleep"));
+    // /sleep が先に受け付けられるよう少し待ってから送る
+    thread::sleep(Duration::from_millis(100));
+    let started = Instant::now();
+    get(addr, "/")?;
+    let waited = started.elapsed();
+
+    slow.join()
+        .expect("クライアントのスレッドが panic しました")?;
+    server.join().expect("サーバーのスレッドが panic しました");
+    Ok(waited)
+}
+
+// シングルスレッド版とスレッドプール版を比べる
+fn compare() {
+    println!("\n{}", t("serve.compare_header"));
+    let sleep = Duration::from_secs(1);
+    println!(
+        "{}\n",
+        tf("serve.compare_intro", &[("seconds", &sleep.as_secs())])
+    );
+
+    for (label, pooled) in [
+        (t("serve.single_thread"), false),
+        (t("serve.thread_pool"), true),
+    ] {
+        println!("--- {} ---", label);
+        match measure(pooled, sleep) {
+            Ok(waited) => println!(
+                "→ {}\n",
+                tf(
+                    "serve.waited",
+                    &[("seconds", &format!("{:.2}", waited.as_secs_f64()))]
+                )
+            ),
+            Err(e) => println!("→ {}\n", tf("serve.measure_failed", &[("error", &e)])),
+        }
+    }
+    println!("{}", t("serve.compare_summary"));
+    println!("{}", t("serve.try_hint"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn pool_runs_every_job_before_drop_returns() {
+        let count = Arc::new(AtomicUsize::new(0));
+        {
+            let pool = ThreadPool::new(3);
+            for _ in 0..10 {
+                let count = Arc::clone(&count);
+                pool.execute(move || {
+                    count.fetch_add(1, Ordering::SeqCst);
+                });
+            }
+            // ここで drop され、キューに残った仕事も終わるまで待つ
+        }
+        assert_eq!(count.load(Ordering::SeqCst), 10);
+    }
+
+    #[test]
+    fn serves_pages_and_stops_after_the_requests() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = thread::spawn(move || serve_pool(&listener, 2, 2, Duration::ZERO));
+
+        let hello = get(addr, "/").unwrap();
+        assert!(hello.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(hello.contains(&format!("Content-Length: {}", HELLO_HTML.len())));
+        assert!(hello.ends_with(HELLO_HTML));
+
+        let missing = get(addr, "/missing").unwrap();
+        assert!(missing.starts_with("HTTP/1.1 404 NOT FOUND\r\n"));
+        assert!(missing.contains("Oops!"));
+
+        // 2回で止まるので join が戻る
+        server.join().unwrap();
+    }
+}
@@ -0,0 +1,146 @@
+// ============================================================================
+// 新しいデモの案内（whatsnew）
+// ============================================================================
+//
+// デモにはコンテンツのバージョン（加わった版 added と、大きく書き直した版 revised）がある
+// （crates/lessons/src/lib.rs の .added_in / .revised_in）。
+// 最後に `whatsnew` で確認したバージョンを学習データの保存先の whatsnew.tsv に残し、
+// それより新しいデモを「新規」「更新」として一覧にする。
+//
+//   whatsnew        前回の確認から追加・更新されたデモ（確認済みにする）
+//   whatsnew all    最初の版（バージョン 1）からの追加・更新すべて
+//
+// 初めて起動したときは、すべてのデモが新しいので案内せずに今のバージョンを記録する。
+
+use crate::i18n::{t, tf};
+use crate::registry::{self, Demo, TopicText, TOPICS};
+use crate::storage;
+use std::io;
+
+const SEEN_FILE: &str = "whatsnew.tsv";
+
+/// 前回から何が変わったか
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Change {
+    Added,
+    Revised,
+}
+
+/// seen より新しいバージョンで追加・更新されたデモ（新しい版から順）
+pub fn changes_since(seen: u32) -> Vec<(&'static str, &'static Demo, Change)> {
+    let mut changes: Vec<_> = TOPICS
+        .iter()
+        .flat_map(|topic| topic.demos.iter().map(move |demo| (topic.key, demo)))
+        .filter_map(|(key, demo)| {
+            let change = if demo.added > seen {
+                Change::Added
+            } else if demo.revised > seen {
+                Change::Revised
+            } else {
+                return None;
+            };
+            Some((key, demo, change))
+        })
+        .collect();
+    // 同じ版の中ではメニューの順のまま
+    changes.sort_by_key(|(_, demo, _)| std::cmp::Reverse(demo.revised));
+    changes
+}
+
+// 最後に確認したバージョン（記録がなければ None）
+fn load_seen() -> io::Result<Option<u32>> {
+    let records = storage::load_records(SEEN_FILE)?;
+    Ok(records.iter().find_map(|record| match record.as_slice() {
+        [kind, version] if kind == "seen" => version.parse().ok(),
+        _ => None,
+    }))
+}
+
+fn save_seen(version: u32) -> io::Result<()> {
+    storage::save_records(SEEN_FILE, &[vec!["seen".to_string(), version.to_string()]])
+}
+
+/// メニューに出す案内（新しいデモがなければ None）
+/// 初めての起動なら今のバージョンを記録するだけにする
+pub fn menu_notice() -> Option<String> {
+    let seen = match load_seen() {
+        Ok(Some(seen)) => seen,
+        Ok(None) => {
+            let _ = save_seen(registry::content_version());
+            return None;
+        }
+        Err(_) => return None,
+    };
+    let count = changes_since(seen).len();
+    (count > 0).then(|| tf("whatsnew.notice", &[("count", &count)]))
+}
+
+/// `whatsnew [all]`
+pub fn run(arg: &str) {
+    let current = registry::content_version();
+    let seen = match arg {
+        "all" => 1,
+        "" => load_seen().ok().flatten().unwrap_or(0),
+        _ => {
+            println!("{}", t("whatsnew.usage"));
+            return;
+        }
+    };
+
+    let changes = changes_since(seen);
+    println!("\n{}", tf("whatsnew.header", &[("version", &current)]));
+    if changes.is_empty() {
+        println!("{}", tf("whatsnew.no_changes", &[("version", &seen)]));
+    }
+    let mut version = None;
+    for (key, demo, change) in &changes {
+        if version != Some(demo.revised) {
+            version = Some(demo.revised);
+            println!(
+                "\n{}",
+                tf("whatsnew.version", &[("version", &demo.revised)])
+            );
+        }
+        let label = match change {
+            Change::Added => t("whatsnew.added"),
+            Change::Revised => t("whatsnew.revised"),
+        };
+        let title = registry::find_topic(key).map_or(demo.title, |topic| topic.demo_title(demo));
+        println!("  [{}] {}::{}  {}", label, key, demo.name, title);
+    }
+    if !changes.is_empty() {
+        println!("\n{}", t("whatsnew.repeat_hint"));
+    }
+
+    if arg.is_empty() {
+        if let Err(e) = save_seen(current) {
+            println!("{}", tf("whatsnew.save_failed", &[("error", &e)]));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lists_demos_newer_than_the_seen_version() {
+        let current = registry::content_version();
+        assert!(changes_since(current).is_empty());
+
+        let all = changes_since(0);
+        let total: usize = TOPICS.iter().map(|topic| topic.demos.len()).sum();
+        assert_eq!(all.len(), total);
+        assert!(all.iter().all(|(_, _, change)| *change == Change::Added));
+        // 新しい版から順に並ぶ
+        assert!(all
+            .windows(2)
+            .all(|pair| pair[0].1.revised >= pair[1].1.revised));
+
+        let since_first = changes_since(1);
+        assert!(since_first
+            .iter()
+            .any(|(key, demo, _)| *key == "smart_pointers" && demo.name == "deref_trait"));
+        assert!(since_first.iter().all(|(key, _, _)| *key != "basics"));
+    }
+}
@@ -0,0 +1,9 @@
+#!/bin/sh
+# ブラウザ版をビルドして web/playground.wasm に置く
+#   sh web/build.sh && python3 -m http.server -d web 8000
+set -e
+cd "$(dirname "$0")/.."
+rustup target add wasm32-unknown-unknown >/dev/null 2>&1 || true
+cargo build -p playground --release --target wasm32-unknown-unknown
+cp target/wasm32-unknown-unknown/release/playground.wasm web/playground.wasm
+echo "web/playground.wasm を更新しました"